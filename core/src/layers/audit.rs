@@ -0,0 +1,981 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::future::Future;
+use std::io::SeekFrom;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::DateTime;
+use chrono::Utc;
+
+use crate::raw::*;
+use crate::*;
+
+/// Emit a structured [`AuditRecord`] for every operation to given sink.
+///
+/// # Notes
+///
+/// Unlike logging and tracing which are designed for debugging, records
+/// emitted by AuditLayer form an audit trail of who accessed what and when:
+///
+/// - One record will be emitted for every operation, succeeded or not.
+/// - The principal and context set via [`AuditLayer::with_principal`] and
+///   [`AuditLayer::with_context`] are attached to every record. To audit
+///   different callers, layer a clone of the shared operator for each of
+///   them, or resolve the principal of every operation via
+///   [`AuditLayer::with_principal_fn`].
+/// - For `read`, `write` and `append`, the record will be emitted after the
+///   returned reader, writer or appender is dropped, so that `bytes` and
+///   `duration` cover the whole IO. A writer that is aborted will be
+///   recorded with `aborted` set, and a writer that is dropped without
+///   `close` or `abort` will be recorded as failed.
+/// - For `batch`, one record will be emitted for every path in the batch.
+/// - For `list`, the record will be emitted after the returned lister is
+///   dropped, so that `entries` and errors while listing are covered.
+/// - The principal is resolved when the operation starts, in the caller's
+///   task, even if the record is emitted later.
+///
+/// The sink is called in the IO path, so it must be quick. Please send
+/// records to a channel and persist them elsewhere if the durable storage
+/// is slow.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::AuditLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let op = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .finish();
+///
+/// let audit = AuditLayer::new(|record| println!("{record:?}"));
+/// let _alice = op.clone().layer(audit.clone().with_principal("alice"));
+/// let _bob = op.layer(audit.with_principal("bob").with_context("tenant", "foo"));
+/// ```
+#[derive(Clone)]
+pub struct AuditLayer {
+    sink: Arc<dyn Fn(AuditRecord) + Send + Sync>,
+    principal: Option<String>,
+    principal_fn: Option<PrincipalFn>,
+    context: HashMap<String, String>,
+}
+
+type PrincipalFn = Arc<dyn Fn(Operation, &str) -> Option<String> + Send + Sync>;
+
+impl AuditLayer {
+    /// Create a new AuditLayer which emits records to given sink.
+    pub fn new(sink: impl Fn(AuditRecord) + Send + Sync + 'static) -> Self {
+        Self {
+            sink: Arc::new(sink),
+            principal: None,
+            principal_fn: None,
+            context: HashMap::new(),
+        }
+    }
+
+    /// Set the principal that will be attached to every record.
+    pub fn with_principal(mut self, principal: &str) -> Self {
+        self.principal = Some(principal.to_string());
+        self
+    }
+
+    /// Set the function that resolves the principal of every operation
+    /// from its operation and path.
+    ///
+    /// The function is called when the operation starts, so it can read the
+    /// caller from task local or thread local storage. Returning `None`
+    /// will fall back to the principal set via [`AuditLayer::with_principal`].
+    pub fn with_principal_fn(
+        mut self,
+        f: impl Fn(Operation, &str) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.principal_fn = Some(Arc::new(f));
+        self
+    }
+
+    /// Add a context entry that will be attached to every record.
+    pub fn with_context(mut self, key: &str, value: &str) -> Self {
+        self.context.insert(key.to_string(), value.to_string());
+        self
+    }
+}
+
+impl Debug for AuditLayer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuditLayer")
+            .field("principal", &self.principal)
+            .field("context", &self.context)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: Accessor> Layer<A> for AuditLayer {
+    type LayeredAccessor = AuditAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        AuditAccessor {
+            inner,
+            auditor: Arc::new(Auditor {
+                sink: self.sink.clone(),
+                principal: self.principal.clone(),
+                principal_fn: self.principal_fn.clone(),
+                context: self.context.clone(),
+            }),
+        }
+    }
+}
+
+/// AuditRecord is the structured record of an operation.
+///
+/// More fields could be added in the future, so this struct can't be
+/// constructed outside of OpenDAL.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct AuditRecord {
+    /// The time when the operation started.
+    pub time: DateTime<Utc>,
+    /// The operation that happened.
+    pub operation: Operation,
+    /// The path of the operation, it's the source path for `copy` and
+    /// `rename`, and the path of every batched operation for `batch`.
+    pub path: String,
+    /// The target path for `copy` and `rename`.
+    pub target: Option<String>,
+    /// The principal resolved via [`AuditLayer::with_principal_fn`] or set
+    /// via [`AuditLayer::with_principal`].
+    pub principal: Option<String>,
+    /// The context set via [`AuditLayer::with_context`].
+    pub context: HashMap<String, String>,
+    /// The kind of error if the operation failed.
+    pub error_kind: Option<ErrorKind>,
+    /// The description of error if the operation failed.
+    pub error: Option<String>,
+    /// The bytes read, written or appended, `None` for other operations.
+    pub bytes: Option<u64>,
+    /// The entries listed, `None` for other operations.
+    pub entries: Option<u64>,
+    /// Whether the writer has been aborted.
+    pub aborted: bool,
+    /// The duration that the operation took.
+    pub duration: Duration,
+}
+
+struct Auditor {
+    sink: Arc<dyn Fn(AuditRecord) + Send + Sync>,
+    principal: Option<String>,
+    principal_fn: Option<PrincipalFn>,
+    context: HashMap<String, String>,
+}
+
+/// The state captured when an operation starts.
+#[derive(Clone)]
+struct Started {
+    time: DateTime<Utc>,
+    start: Instant,
+    principal: Option<String>,
+}
+
+impl Auditor {
+    /// Capture the start of an operation.
+    ///
+    /// The principal is resolved here instead of while emitting, because
+    /// records of readers and writers are emitted in `Drop` which could
+    /// run in another task.
+    fn start(&self, operation: Operation, path: &str) -> Started {
+        let principal = self
+            .principal_fn
+            .as_ref()
+            .and_then(|f| f(operation, path))
+            .or_else(|| self.principal.clone());
+
+        Started {
+            time: Utc::now(),
+            start: Instant::now(),
+            principal,
+        }
+    }
+
+    fn emit(
+        &self,
+        started: &Started,
+        operation: Operation,
+        path: &str,
+        target: Option<&str>,
+        err: Option<(ErrorKind, String)>,
+    ) {
+        (self.sink)(self.record(started, operation, path, target, err))
+    }
+
+    fn record(
+        &self,
+        started: &Started,
+        operation: Operation,
+        path: &str,
+        target: Option<&str>,
+        err: Option<(ErrorKind, String)>,
+    ) -> AuditRecord {
+        let (error_kind, error) = match err {
+            Some((kind, msg)) => (Some(kind), Some(msg)),
+            None => (None, None),
+        };
+
+        AuditRecord {
+            time: started.time,
+            operation,
+            path: path.to_string(),
+            target: target.map(|v| v.to_string()),
+            principal: started.principal.clone(),
+            context: self.context.clone(),
+            error_kind,
+            error,
+            bytes: None,
+            entries: None,
+            aborted: false,
+            duration: started.start.elapsed(),
+        }
+    }
+}
+
+fn describe(err: &Error) -> (ErrorKind, String) {
+    (err.kind(), err.to_string())
+}
+
+pub struct AuditAccessor<A: Accessor> {
+    inner: A,
+    auditor: Arc<Auditor>,
+}
+
+impl<A: Accessor> Debug for AuditAccessor<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuditAccessor")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: Accessor> AuditAccessor<A> {
+    async fn audit<T, F>(&self, op: Operation, path: &str, target: Option<&str>, f: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        let started = self.auditor.start(op, path);
+        let res = f.await;
+        self.auditor
+            .emit(&started, op, path, target, res.as_ref().err().map(describe));
+        res
+    }
+
+    fn blocking_audit<T>(
+        &self,
+        op: Operation,
+        path: &str,
+        target: Option<&str>,
+        f: impl FnOnce() -> Result<T>,
+    ) -> Result<T> {
+        let started = self.auditor.start(op, path);
+        let res = f();
+        self.auditor
+            .emit(&started, op, path, target, res.as_ref().err().map(describe));
+        res
+    }
+
+    /// Wrap the reader, writer or lister created by `res` so that the
+    /// record will be emitted after it's dropped.
+    fn wrap<R, T>(
+        &self,
+        op: Operation,
+        path: &str,
+        started: Started,
+        res: Result<(T, R)>,
+    ) -> Result<(T, AuditWrapper<R>)> {
+        let is_list = matches!(op, Operation::List | Operation::BlockingList);
+        match res {
+            Ok((rp, r)) => Ok((
+                rp,
+                AuditWrapper {
+                    inner: r,
+                    auditor: self.auditor.clone(),
+                    operation: op,
+                    path: path.to_string(),
+                    started,
+                    bytes: 0,
+                    entries: 0,
+                    error: None,
+                    // Only writers need to be closed explicitly.
+                    closed: !matches!(
+                        op,
+                        Operation::Write | Operation::BlockingWrite | Operation::Append
+                    ),
+                    aborted: false,
+                },
+            )),
+            Err(err) => {
+                let mut record =
+                    self.auditor
+                        .record(&started, op, path, None, Some(describe(&err)));
+                if is_list {
+                    record.entries = Some(0);
+                } else {
+                    record.bytes = Some(0);
+                }
+                (self.auditor.sink)(record);
+                Err(err)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for AuditAccessor<A> {
+    type Inner = A;
+    type Reader = AuditWrapper<A::Reader>;
+    type BlockingReader = AuditWrapper<A::BlockingReader>;
+    type Writer = AuditWrapper<A::Writer>;
+    type BlockingWriter = AuditWrapper<A::BlockingWriter>;
+    type Appender = AuditWrapper<A::Appender>;
+    type Pager = AuditWrapper<A::Pager>;
+    type BlockingPager = AuditWrapper<A::BlockingPager>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.audit(
+            Operation::CreateDir,
+            path,
+            None,
+            self.inner.create_dir(path, args),
+        )
+        .await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let started = self.auditor.start(Operation::Read, path);
+        let res = self.inner.read(path, args).await;
+        self.wrap(Operation::Read, path, started, res)
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let started = self.auditor.start(Operation::Write, path);
+        let res = self.inner.write(path, args).await;
+        self.wrap(Operation::Write, path, started, res)
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        let started = self.auditor.start(Operation::Append, path);
+        let res = self.inner.append(path, args).await;
+        self.wrap(Operation::Append, path, started, res)
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.audit(
+            Operation::Copy,
+            from,
+            Some(to),
+            self.inner.copy(from, to, args),
+        )
+        .await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.audit(
+            Operation::Rename,
+            from,
+            Some(to),
+            self.inner.rename(from, to, args),
+        )
+        .await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.audit(Operation::Stat, path, None, self.inner.stat(path, args))
+            .await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.audit(Operation::Delete, path, None, self.inner.delete(path, args))
+            .await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        let started = self.auditor.start(Operation::List, path);
+        let res = self.inner.list(path, args).await;
+        self.wrap(Operation::List, path, started, res)
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let started: HashMap<String, Started> = args
+            .operation()
+            .iter()
+            .map(|(path, _)| (path.clone(), self.auditor.start(Operation::Batch, path)))
+            .collect();
+        let res = self.inner.batch(args).await;
+
+        match &res {
+            Ok(rp) => {
+                for (path, res) in rp.results() {
+                    let started = match started.get(path) {
+                        Some(v) => v.clone(),
+                        None => self.auditor.start(Operation::Batch, path),
+                    };
+                    self.auditor.emit(
+                        &started,
+                        Operation::Batch,
+                        path,
+                        None,
+                        res.as_ref().err().map(describe),
+                    );
+                }
+            }
+            Err(err) => {
+                let started = self.auditor.start(Operation::Batch, "");
+                self.auditor
+                    .emit(&started, Operation::Batch, "", None, Some(describe(err)))
+            }
+        }
+        res
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.audit(
+            Operation::Presign,
+            path,
+            None,
+            self.inner.presign(path, args),
+        )
+        .await
+    }
+
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        self.audit(
+            Operation::GetTags,
+            path,
+            None,
+            self.inner.get_tags(path, args),
+        )
+        .await
+    }
+
+    async fn put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        self.audit(
+            Operation::PutTags,
+            path,
+            None,
+            self.inner.put_tags(path, args),
+        )
+        .await
+    }
+
+    async fn get_object_lock(&self, path: &str, args: OpGetObjectLock) -> Result<RpGetObjectLock> {
+        self.audit(
+            Operation::GetObjectLock,
+            path,
+            None,
+            self.inner.get_object_lock(path, args),
+        )
+        .await
+    }
+
+    async fn put_object_lock(&self, path: &str, args: OpPutObjectLock) -> Result<RpPutObjectLock> {
+        self.audit(
+            Operation::PutObjectLock,
+            path,
+            None,
+            self.inner.put_object_lock(path, args),
+        )
+        .await
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.blocking_audit(Operation::BlockingCreateDir, path, None, || {
+            self.inner.blocking_create_dir(path, args)
+        })
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let started = self.auditor.start(Operation::BlockingRead, path);
+        let res = self.inner.blocking_read(path, args);
+        self.wrap(Operation::BlockingRead, path, started, res)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let started = self.auditor.start(Operation::BlockingWrite, path);
+        let res = self.inner.blocking_write(path, args);
+        self.wrap(Operation::BlockingWrite, path, started, res)
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.blocking_audit(Operation::BlockingCopy, from, Some(to), || {
+            self.inner.blocking_copy(from, to, args)
+        })
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.blocking_audit(Operation::BlockingRename, from, Some(to), || {
+            self.inner.blocking_rename(from, to, args)
+        })
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.blocking_audit(Operation::BlockingStat, path, None, || {
+            self.inner.blocking_stat(path, args)
+        })
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.blocking_audit(Operation::BlockingDelete, path, None, || {
+            self.inner.blocking_delete(path, args)
+        })
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        let started = self.auditor.start(Operation::BlockingList, path);
+        let res = self.inner.blocking_list(path, args);
+        self.wrap(Operation::BlockingList, path, started, res)
+    }
+
+    fn blocking_get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        self.blocking_audit(Operation::BlockingGetTags, path, None, || {
+            self.inner.blocking_get_tags(path, args)
+        })
+    }
+
+    fn blocking_put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        self.blocking_audit(Operation::BlockingPutTags, path, None, || {
+            self.inner.blocking_put_tags(path, args)
+        })
+    }
+}
+
+pub struct AuditWrapper<R> {
+    inner: R,
+    auditor: Arc<Auditor>,
+
+    operation: Operation,
+    path: String,
+    started: Started,
+    bytes: u64,
+    entries: u64,
+    /// The first error met while doing IO.
+    error: Option<(ErrorKind, String)>,
+    closed: bool,
+    aborted: bool,
+}
+
+impl<R> AuditWrapper<R> {
+    fn observe<T>(&mut self, res: Result<T>) -> Result<T> {
+        if let Err(err) = &res {
+            self.error.get_or_insert_with(|| describe(err));
+        }
+        res
+    }
+}
+
+impl<R> Drop for AuditWrapper<R> {
+    fn drop(&mut self) {
+        let error = match self.error.take() {
+            Some(err) => Some(err),
+            None if !self.closed && !self.aborted => Some((
+                ErrorKind::Unexpected,
+                format!("{} is dropped without close", self.operation),
+            )),
+            None => None,
+        };
+
+        let mut record =
+            self.auditor
+                .record(&self.started, self.operation, &self.path, None, error);
+        if matches!(self.operation, Operation::List | Operation::BlockingList) {
+            record.entries = Some(self.entries);
+        } else {
+            record.bytes = Some(self.bytes);
+        }
+        record.aborted = self.aborted;
+        (self.auditor.sink)(record);
+    }
+}
+
+impl<R: oio::Read> oio::Read for AuditWrapper<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        self.inner.poll_read(cx, buf).map(|res| {
+            let res = self.observe(res);
+            if let Ok(n) = &res {
+                self.bytes += *n as u64;
+            }
+            res
+        })
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        self.inner.poll_seek(cx, pos).map(|res| self.observe(res))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        self.inner.poll_next(cx).map(|res| {
+            res.map(|res| {
+                let res = self.observe(res);
+                if let Ok(bs) = &res {
+                    self.bytes += bs.len() as u64;
+                }
+                res
+            })
+        })
+    }
+}
+
+impl<R: oio::BlockingRead> oio::BlockingRead for AuditWrapper<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let res = self.inner.read(buf);
+        let res = self.observe(res);
+        if let Ok(n) = &res {
+            self.bytes += *n as u64;
+        }
+        res
+    }
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let res = self.inner.seek(pos);
+        self.observe(res)
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        self.inner.next().map(|res| {
+            let res = self.observe(res);
+            if let Ok(bs) = &res {
+                self.bytes += bs.len() as u64;
+            }
+            res
+        })
+    }
+}
+
+#[async_trait]
+impl<R: oio::Write> oio::Write for AuditWrapper<R> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        let size = bs.len() as u64;
+        let res = self.inner.write(bs).await;
+        let res = self.observe(res);
+        if res.is_ok() {
+            self.bytes += size;
+        }
+        res
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        let res = self.inner.abort().await;
+        let res = self.observe(res);
+        self.aborted = true;
+        res
+    }
+
+    async fn sink(&mut self, size: u64, s: oio::Streamer) -> Result<()> {
+        let res = self.inner.sink(size, s).await;
+        let res = self.observe(res);
+        if res.is_ok() {
+            self.bytes += size;
+        }
+        res
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        let res = self.inner.close().await;
+        let res = self.observe(res);
+        self.closed = res.is_ok();
+        res
+    }
+
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        self.inner.checkpoint()
+    }
+}
+
+impl<R: oio::BlockingWrite> oio::BlockingWrite for AuditWrapper<R> {
+    fn write(&mut self, bs: Bytes) -> Result<()> {
+        let size = bs.len() as u64;
+        let res = self.inner.write(bs);
+        let res = self.observe(res);
+        if res.is_ok() {
+            self.bytes += size;
+        }
+        res
+    }
+
+    fn close(&mut self) -> Result<()> {
+        let res = self.inner.close();
+        let res = self.observe(res);
+        self.closed = res.is_ok();
+        res
+    }
+}
+
+#[async_trait]
+impl<R: oio::Append> oio::Append for AuditWrapper<R> {
+    async fn append(&mut self, bs: Bytes) -> Result<()> {
+        let size = bs.len() as u64;
+        let res = self.inner.append(bs).await;
+        let res = self.observe(res);
+        if res.is_ok() {
+            self.bytes += size;
+        }
+        res
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        let res = self.inner.close().await;
+        let res = self.observe(res);
+        self.closed = res.is_ok();
+        res
+    }
+}
+
+#[async_trait]
+impl<P: oio::Page> oio::Page for AuditWrapper<P> {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        let res = self.inner.next().await;
+        let res = self.observe(res);
+        if let Ok(Some(entries)) = &res {
+            self.entries += entries.len() as u64;
+        }
+        res
+    }
+}
+
+impl<P: oio::BlockingPage> oio::BlockingPage for AuditWrapper<P> {
+    fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        let res = self.inner.next();
+        let res = self.observe(res);
+        if let Ok(Some(entries)) = &res {
+            self.entries += entries.len() as u64;
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_audit() -> Result<()> {
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let sink = records.clone();
+        let op = Operator::new(Memory::default())?
+            .layer(
+                AuditLayer::new(move |record| sink.lock().unwrap().push(record))
+                    .with_principal("alice")
+                    .with_context("tenant", "foo"),
+            )
+            .finish();
+
+        op.write("test", "Hello, World!").await?;
+        assert_eq!(op.read("test").await?, b"Hello, World!");
+        assert!(op.stat("not_exist").await.is_err());
+
+        let records = records.lock().unwrap();
+        let write = records
+            .iter()
+            .find(|v| v.operation == Operation::Write)
+            .expect("write must be recorded");
+        assert_eq!(write.path, "test");
+        assert_eq!(write.bytes, Some(13));
+        assert!(write.error_kind.is_none());
+        assert_eq!(write.principal.as_deref(), Some("alice"));
+        assert_eq!(write.context.get("tenant").map(|v| v.as_str()), Some("foo"));
+
+        let read = records
+            .iter()
+            .find(|v| v.operation == Operation::Read)
+            .expect("read must be recorded");
+        assert_eq!(read.bytes, Some(13));
+        assert!(read.error_kind.is_none());
+
+        let stat = records
+            .iter()
+            .find(|v| v.operation == Operation::Stat && v.path == "not_exist")
+            .expect("stat must be recorded");
+        assert_eq!(stat.error_kind, Some(ErrorKind::NotFound));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_audit_principal_fn_and_abort() -> Result<()> {
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let sink = records.clone();
+        let op = Operator::new(Memory::default())?
+            .layer(
+                AuditLayer::new(move |record| sink.lock().unwrap().push(record))
+                    .with_principal("alice")
+                    .with_principal_fn(|op, path| {
+                        (op == Operation::Write && path.starts_with("bob/"))
+                            .then(|| "bob".to_string())
+                    }),
+            )
+            .finish();
+
+        let mut w = op.writer("bob/test").await?;
+        w.write("Hello").await?;
+        w.abort().await?;
+        drop(w);
+        op.write("test", "Hello").await?;
+
+        let records = records.lock().unwrap();
+        let aborted = records
+            .iter()
+            .find(|v| v.path == "bob/test")
+            .expect("aborted write must be recorded");
+        assert!(aborted.aborted);
+        assert!(aborted.error_kind.is_none());
+        assert_eq!(aborted.principal.as_deref(), Some("bob"));
+
+        let write = records
+            .iter()
+            .find(|v| v.path == "test")
+            .expect("write must be recorded");
+        assert!(!write.aborted);
+        assert_eq!(write.principal.as_deref(), Some("alice"));
+        Ok(())
+    }
+
+    tokio::task_local! {
+        static CALLER: String;
+    }
+
+    #[tokio::test]
+    async fn test_audit_principal_resolved_at_start() -> Result<()> {
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let sink = records.clone();
+        let op = Operator::new(Memory::default())?
+            .layer(
+                AuditLayer::new(move |record| sink.lock().unwrap().push(record))
+                    .with_principal_fn(|_, _| CALLER.try_with(|v| v.clone()).ok()),
+            )
+            .finish();
+        op.write("dir/test", "Hello, World!").await?;
+
+        let r = CALLER
+            .scope("carol".to_string(), op.reader("dir/test"))
+            .await?;
+        // Drop the reader in another task which has no principal.
+        tokio::spawn(async move { drop(r) })
+            .await
+            .expect("task must succeed");
+
+        let entries = CALLER
+            .scope("carol".to_string(), op.list("dir/"))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(entries.len(), 1);
+
+        let records = records.lock().unwrap();
+        let read = records
+            .iter()
+            .find(|v| v.operation == Operation::Read)
+            .expect("read must be recorded");
+        assert_eq!(read.principal.as_deref(), Some("carol"));
+
+        let list = records
+            .iter()
+            .find(|v| v.operation == Operation::List)
+            .expect("list must be recorded");
+        assert_eq!(list.principal.as_deref(), Some("carol"));
+        assert_eq!(list.entries, Some(1));
+        assert!(list.bytes.is_none());
+        Ok(())
+    }
+
+    #[derive(Debug)]
+    struct MockService;
+
+    #[async_trait]
+    impl Accessor for MockService {
+        type Reader = ();
+        type BlockingReader = ();
+        type Writer = ();
+        type BlockingWriter = ();
+        type Appender = ();
+        type Pager = ();
+        type BlockingPager = ();
+
+        fn info(&self) -> AccessorInfo {
+            AccessorInfo::default()
+        }
+
+        async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+            let results = args
+                .into_operation()
+                .into_iter()
+                .map(|(path, _)| {
+                    let res = if path == "not_exist" {
+                        Err(Error::new(ErrorKind::NotFound, "not found"))
+                    } else {
+                        Ok(RpDelete::default().into())
+                    };
+                    (path, res)
+                })
+                .collect();
+            Ok(RpBatch::new(results))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_audit_batch() -> Result<()> {
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let sink = records.clone();
+        let acc =
+            AuditLayer::new(move |record| sink.lock().unwrap().push(record)).layer(MockService);
+
+        Accessor::batch(
+            &acc,
+            OpBatch::new(vec![
+                ("a".to_string(), BatchOperation::Delete(OpDelete::new())),
+                (
+                    "not_exist".to_string(),
+                    BatchOperation::Delete(OpDelete::new()),
+                ),
+            ]),
+        )
+        .await?;
+
+        let records = records.lock().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].operation, Operation::Batch);
+        assert_eq!(records[0].path, "a");
+        assert!(records[0].error_kind.is_none());
+        assert_eq!(records[1].path, "not_exist");
+        assert_eq!(records[1].error_kind, Some(ErrorKind::NotFound));
+        Ok(())
+    }
+}
@@ -15,10 +15,12 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::cmp::Ordering;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::io;
 use std::sync::Arc;
+use std::task::ready;
 use std::task::Context;
 use std::task::Poll;
 
@@ -105,6 +107,12 @@ use crate::*;
 ///
 /// In this way, we can reduce the extra cost of dropping reader.
 ///
+/// ### Content Length Check
+///
+/// While reading the whole file, the reader will check the returned bytes
+/// against the content length of [`RpRead`]. A temporary `Unexpected` error
+/// will be returned at the end of reading if they don't match. Users can skip the check via `skip_content_length_check`.
+///
 /// ## List Completion
 ///
 /// There are two styles of list, but not all services support both of
@@ -124,8 +132,30 @@ use crate::*;
 /// the operation against capability of the underlying service. If the
 /// operation is not supported, an error will be returned directly.
 ///
+/// ## Native Only
+///
+/// With [`CompleteLayer::with_native_only`], the emulations that could
+/// introduce extra requests will be disabled, and operations that can't
+/// be served natively will return `Unsupported` instead:
+///
+/// - Seeking on readers of services that are not `read_can_seek`.
+/// - Listing without delimiter on services that are not `list_without_delimiter`.
+/// - Listing with `/` on services that are not `list_with_delimiter_slash`.
+///
 /// [`AccessorHint`]: crate::raw::AccessorHint
-pub struct CompleteLayer;
+#[derive(Default)]
+pub struct CompleteLayer {
+    native_only: bool,
+}
+
+impl CompleteLayer {
+    /// Disable all emulations so that operations can't be served natively
+    /// will return `Unsupported` error.
+    pub fn with_native_only(mut self) -> Self {
+        self.native_only = true;
+        self
+    }
+}
 
 impl<A: Accessor> Layer<A> for CompleteLayer {
     type LayeredAccessor = CompleteReaderAccessor<A>;
@@ -135,6 +165,7 @@ impl<A: Accessor> Layer<A> for CompleteLayer {
         CompleteReaderAccessor {
             meta,
             inner: Arc::new(inner),
+            native_only: self.native_only,
         }
     }
 }
@@ -143,6 +174,7 @@ impl<A: Accessor> Layer<A> for CompleteLayer {
 pub struct CompleteReaderAccessor<A: Accessor> {
     meta: AccessorInfo,
     inner: Arc<A>,
+    native_only: bool,
 }
 
 impl<A: Accessor> Debug for CompleteReaderAccessor<A> {
@@ -152,30 +184,83 @@ impl<A: Accessor> Debug for CompleteReaderAccessor<A> {
 }
 
 impl<A: Accessor> CompleteReaderAccessor<A> {
+    fn new_native_unsupported_error(&self, op: Operation, capability: &'static str) -> Error {
+        Error::new(
+            ErrorKind::Unsupported,
+            "operation is not natively supported by service",
+        )
+        .with_operation(op)
+        .with_context("service", self.meta.scheme())
+        .with_context("capability", capability)
+    }
+
+    /// Metadata of the target can only be given while replacing, otherwise
+    /// it would be silently dropped by services.
+    fn check_copy_metadata(&self, op: Operation, args: &OpCopy) -> Result<()> {
+        let has_metadata = args.content_type().is_some()
+            || args.content_disposition().is_some()
+            || args.cache_control().is_some()
+            || args.user_metadata().is_some();
+
+        if has_metadata && args.metadata_directive() != MetadataDirective::Replace {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "metadata of target can only be set with MetadataDirective::Replace",
+            )
+            .with_operation(op)
+            .with_context("service", self.meta.scheme()));
+        }
+
+        Ok(())
+    }
+
     async fn complete_reader(
         &self,
         path: &str,
         args: OpRead,
-    ) -> Result<(RpRead, CompleteReader<A, A::Reader>)> {
+    ) -> Result<(RpRead, CompleteCheckedReader<A, A::Reader>)> {
         let capability = self.meta.capability();
         if !capability.read {
             return new_capability_unsupported_error(Operation::Read);
         }
+        check_custom_headers(args.headers()).map_err(|err| err.with_operation(Operation::Read))?;
+        if !args.headers().is_empty()
+            && !args.ignore_unsupported_headers()
+            && !capability.read_with_headers
+        {
+            return new_capability_unsupported_error(Operation::Read);
+        }
 
         let seekable = capability.read_can_seek;
         let streamable = capability.read_can_next;
 
         let range = args.range();
+        let check_content_length = range.is_full() && !args.skip_content_length_check();
         let (rp, r) = self.inner.read(path, args).await?;
-        let content_length = rp.metadata().content_length();
-
-        match (seekable, streamable) {
-            (true, true) => Ok((rp, CompleteReader::AlreadyComplete(r))),
+        let content_length = rp.metadata().content_length_raw();
+        let expect = rp
+            .metadata()
+            .content_length_raw()
+            .filter(|_| check_content_length);
+
+        let r = match (seekable, streamable) {
+            (true, true) => CompleteReader::AlreadyComplete(r),
             (true, false) => {
                 let r = oio::into_streamable_read(r, 256 * 1024);
-                Ok((rp, CompleteReader::NeedStreamable(r)))
+                CompleteReader::NeedStreamable(r)
+            }
+            // Seeking will be served by the underlying reader which
+            // returns `Unsupported` error. It's also the case while content
+            // length is unknown, for example the body is decoded on the fly.
+            (false, true) if self.native_only || content_length.is_none() => {
+                CompleteReader::AlreadyComplete(r)
+            }
+            (false, false) if self.native_only || content_length.is_none() => {
+                let r = oio::into_streamable_read(r, 256 * 1024);
+                CompleteReader::NeedStreamable(r)
             }
             _ => {
+                let content_length = content_length.unwrap_or_default();
                 let (offset, size) = match (range.offset(), range.size()) {
                     (Some(offset), _) => (offset, content_length),
                     (None, None) => (0, content_length),
@@ -196,41 +281,60 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
                 let r = oio::into_seekable_read_by_range(self.inner.clone(), path, r, offset, size);
 
                 if streamable {
-                    Ok((rp, CompleteReader::NeedSeekable(r)))
+                    CompleteReader::NeedSeekable(r)
                 } else {
                     let r = oio::into_streamable_read(r, 256 * 1024);
-                    Ok((rp, CompleteReader::NeedBoth(r)))
+                    CompleteReader::NeedBoth(r)
                 }
             }
-        }
+        };
+
+        Ok((rp, CheckedReader::new(r, expect)))
     }
 
     fn complete_blocking_reader(
         &self,
         path: &str,
         args: OpRead,
-    ) -> Result<(RpRead, CompleteReader<A, A::BlockingReader>)> {
+    ) -> Result<(RpRead, CompleteCheckedReader<A, A::BlockingReader>)> {
         let capability = self.meta.capability();
         if !capability.read || !capability.blocking {
             return new_capability_unsupported_error(Operation::BlockingRead);
         }
+        check_custom_headers(args.headers())
+            .map_err(|err| err.with_operation(Operation::BlockingRead))?;
+        if !args.headers().is_empty()
+            && !args.ignore_unsupported_headers()
+            && !capability.read_with_headers
+        {
+            return new_capability_unsupported_error(Operation::BlockingRead);
+        }
 
         let seekable = capability.read_can_seek;
         let streamable = capability.read_can_next;
 
+        let check_content_length = args.range().is_full() && !args.skip_content_length_check();
         let (rp, r) = self.inner.blocking_read(path, args)?;
+        let expect = rp
+            .metadata()
+            .content_length_raw()
+            .filter(|_| check_content_length);
 
-        match (seekable, streamable) {
-            (true, true) => Ok((rp, CompleteReader::AlreadyComplete(r))),
+        let r = match (seekable, streamable) {
+            (true, true) => CompleteReader::AlreadyComplete(r),
             (true, false) => {
                 let r = oio::into_streamable_read(r, 256 * 1024);
-                Ok((rp, CompleteReader::NeedStreamable(r)))
+                CompleteReader::NeedStreamable(r)
             }
-            (false, _) => Err(Error::new(
-                ErrorKind::Unsupported,
-                "non seekable blocking reader is not supported",
-            )),
-        }
+            (false, _) => {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "non seekable blocking reader is not supported",
+                ))
+            }
+        };
+
+        Ok((rp, CheckedReader::new(r, expect)))
     }
 
     async fn complete_list(
@@ -246,6 +350,20 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
                     .with_operation("list"),
             );
         }
+        if args.continuation_token().is_some() && !cap.list_with_continuation_token {
+            return new_capability_unsupported_error(Operation::List);
+        }
+        if args.versions() && !cap.list_with_versions {
+            return new_capability_unsupported_error(Operation::List);
+        }
+        if args.deleted() && !args.versions() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "list with deleted requires versions to be enabled",
+            )
+            .with_operation(Operation::List)
+            .with_context("service", self.meta.scheme()));
+        }
 
         let delimiter = args.delimiter();
 
@@ -253,12 +371,10 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
             return if cap.list_without_delimiter {
                 let (rp, p) = self.inner.list(path, args).await?;
                 Ok((rp, CompletePager::AlreadyComplete(p)))
+            } else if self.native_only {
+                Err(self.new_native_unsupported_error(Operation::List, "list_without_delimiter"))
             } else {
-                let p = into_flat_page(
-                    self.inner.clone(),
-                    path,
-                    args.with_delimiter("/").limit().unwrap_or(1000),
-                );
+                let p = into_flat_page(self.inner.clone(), path, args.with_delimiter("/"));
                 Ok((RpList::default(), CompletePager::NeedFlat(p)))
             };
         }
@@ -267,6 +383,8 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
             return if cap.list_with_delimiter_slash {
                 let (rp, p) = self.inner.list(path, args).await?;
                 Ok((rp, CompletePager::AlreadyComplete(p)))
+            } else if self.native_only {
+                Err(self.new_native_unsupported_error(Operation::List, "list_with_delimiter_slash"))
             } else {
                 let (_, p) = self.inner.list(path, args.with_delimiter("")).await?;
                 let p = into_hierarchy_page(p, path);
@@ -295,6 +413,20 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
                     .with_operation("list"),
             );
         }
+        if args.continuation_token().is_some() && !cap.list_with_continuation_token {
+            return new_capability_unsupported_error(Operation::BlockingList);
+        }
+        if args.versions() && !cap.list_with_versions {
+            return new_capability_unsupported_error(Operation::BlockingList);
+        }
+        if args.deleted() && !args.versions() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "list with deleted requires versions to be enabled",
+            )
+            .with_operation(Operation::BlockingList)
+            .with_context("service", self.meta.scheme()));
+        }
 
         let delimiter = args.delimiter();
 
@@ -302,12 +434,13 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
             return if cap.list_without_delimiter {
                 let (rp, p) = self.inner.blocking_list(path, args)?;
                 Ok((rp, CompletePager::AlreadyComplete(p)))
+            } else if self.native_only {
+                Err(self.new_native_unsupported_error(
+                    Operation::BlockingList,
+                    "list_without_delimiter",
+                ))
             } else {
-                let p = into_flat_page(
-                    self.inner.clone(),
-                    path,
-                    args.with_delimiter("/").limit().unwrap_or(1000),
-                );
+                let p = into_flat_page(self.inner.clone(), path, args.with_delimiter("/"));
                 Ok((RpList::default(), CompletePager::NeedFlat(p)))
             };
         }
@@ -316,6 +449,11 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
             return if cap.list_with_delimiter_slash {
                 let (rp, p) = self.inner.blocking_list(path, args)?;
                 Ok((rp, CompletePager::AlreadyComplete(p)))
+            } else if self.native_only {
+                Err(self.new_native_unsupported_error(
+                    Operation::BlockingList,
+                    "list_with_delimiter_slash",
+                ))
             } else {
                 let (_, p) = self.inner.blocking_list(path, args.with_delimiter(""))?;
                 let p: HierarchyPager<<A as Accessor>::BlockingPager> =
@@ -336,8 +474,8 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
 #[async_trait]
 impl<A: Accessor> LayeredAccessor for CompleteReaderAccessor<A> {
     type Inner = A;
-    type Reader = CompleteReader<A, A::Reader>;
-    type BlockingReader = CompleteReader<A, A::BlockingReader>;
+    type Reader = CompleteCheckedReader<A, A::Reader>;
+    type BlockingReader = CompleteCheckedReader<A, A::BlockingReader>;
     type Writer = CompleteWriter<A::Writer>;
     type BlockingWriter = CompleteWriter<A::BlockingWriter>;
     type Appender = CompleteAppender<A::Appender>;
@@ -362,10 +500,12 @@ impl<A: Accessor> LayeredAccessor for CompleteReaderAccessor<A> {
             return new_capability_unsupported_error(Operation::Stat);
         }
 
+        let metakey = args.metakey();
         self.inner.stat(path, args).await.map(|v| {
             v.map_metadata(|m| {
+                let m = complete_mode(path, m);
                 let bit = m.bit();
-                m.with_bit(bit | Metakey::Complete)
+                m.with_bit(bit | metakey)
             })
         })
     }
@@ -376,10 +516,12 @@ impl<A: Accessor> LayeredAccessor for CompleteReaderAccessor<A> {
             return new_capability_unsupported_error(Operation::BlockingStat);
         }
 
+        let metakey = args.metakey();
         self.inner.blocking_stat(path, args).map(|v| {
             v.map_metadata(|m| {
+                let m = complete_mode(path, m);
                 let bit = m.bit();
-                m.with_bit(bit | Metakey::Complete)
+                m.with_bit(bit | metakey)
             })
         })
     }
@@ -389,8 +531,59 @@ impl<A: Accessor> LayeredAccessor for CompleteReaderAccessor<A> {
         if !capability.write {
             return new_capability_unsupported_error(Operation::Write);
         }
+        if args.content_md5().is_some() && !capability.write_with_content_md5 {
+            return new_capability_unsupported_error(Operation::Write);
+        }
+        if args.ttl().is_some() && !capability.write_with_ttl {
+            return new_capability_unsupported_error(Operation::Write);
+        }
+        if args.append() && !capability.write_with_append {
+            return new_capability_unsupported_error(Operation::Write);
+        }
+        if args.tags().is_some() && !capability.write_with_tags {
+            return new_capability_unsupported_error(Operation::Write);
+        }
+        if args.user_metadata().is_some() && !capability.write_with_user_metadata {
+            return new_capability_unsupported_error(Operation::Write);
+        }
+        if args.resume().is_some() && !capability.write_with_resume {
+            return new_capability_unsupported_error(Operation::Write);
+        }
+        if args.range().is_some() && !capability.write_with_range {
+            return new_capability_unsupported_error(Operation::Write);
+        }
+        if args.if_match().is_some() && !capability.write_with_if_match {
+            return new_capability_unsupported_error(Operation::Write);
+        }
+        if args.if_none_match().is_some() && !capability.write_with_if_none_match {
+            return new_capability_unsupported_error(Operation::Write);
+        }
+        if args.idempotency_key().is_some() && !capability.write_with_idempotency_key {
+            return new_capability_unsupported_error(Operation::Write);
+        }
+        if args.content_encoding().is_some() && !capability.write_with_content_encoding {
+            return new_capability_unsupported_error(Operation::Write);
+        }
+        if args.content_language().is_some() && !capability.write_with_content_language {
+            return new_capability_unsupported_error(Operation::Write);
+        }
+        check_custom_headers(args.headers()).map_err(|err| err.with_operation(Operation::Write))?;
+        if !args.headers().is_empty()
+            && !args.ignore_unsupported_headers()
+            && !capability.write_with_headers
+        {
+            return new_capability_unsupported_error(Operation::Write);
+        }
+        if args.storage_class().is_some() && !capability.write_with_storage_class {
+            return new_capability_unsupported_error(Operation::Write);
+        }
+        if args.object_lock() != &ObjectLock::default() && !capability.write_with_object_lock {
+            return new_capability_unsupported_error(Operation::Write);
+        }
 
-        let size = args.content_length();
+        let size = args
+            .content_length()
+            .or_else(|| args.range().and_then(|v| v.size()));
         self.inner
             .write(path, args)
             .await
@@ -402,8 +595,60 @@ impl<A: Accessor> LayeredAccessor for CompleteReaderAccessor<A> {
         if !capability.write || !capability.blocking {
             return new_capability_unsupported_error(Operation::BlockingWrite);
         }
+        if args.content_md5().is_some() && !capability.write_with_content_md5 {
+            return new_capability_unsupported_error(Operation::BlockingWrite);
+        }
+        if args.ttl().is_some() && !capability.write_with_ttl {
+            return new_capability_unsupported_error(Operation::BlockingWrite);
+        }
+        if args.append() && !capability.write_with_append {
+            return new_capability_unsupported_error(Operation::BlockingWrite);
+        }
+        if args.tags().is_some() && !capability.write_with_tags {
+            return new_capability_unsupported_error(Operation::BlockingWrite);
+        }
+        if args.user_metadata().is_some() && !capability.write_with_user_metadata {
+            return new_capability_unsupported_error(Operation::BlockingWrite);
+        }
+        if args.resume().is_some() && !capability.write_with_resume {
+            return new_capability_unsupported_error(Operation::BlockingWrite);
+        }
+        if args.range().is_some() && !capability.write_with_range {
+            return new_capability_unsupported_error(Operation::BlockingWrite);
+        }
+        if args.if_match().is_some() && !capability.write_with_if_match {
+            return new_capability_unsupported_error(Operation::BlockingWrite);
+        }
+        if args.if_none_match().is_some() && !capability.write_with_if_none_match {
+            return new_capability_unsupported_error(Operation::BlockingWrite);
+        }
+        if args.idempotency_key().is_some() && !capability.write_with_idempotency_key {
+            return new_capability_unsupported_error(Operation::BlockingWrite);
+        }
+        if args.content_encoding().is_some() && !capability.write_with_content_encoding {
+            return new_capability_unsupported_error(Operation::BlockingWrite);
+        }
+        if args.content_language().is_some() && !capability.write_with_content_language {
+            return new_capability_unsupported_error(Operation::BlockingWrite);
+        }
+        check_custom_headers(args.headers())
+            .map_err(|err| err.with_operation(Operation::BlockingWrite))?;
+        if !args.headers().is_empty()
+            && !args.ignore_unsupported_headers()
+            && !capability.write_with_headers
+        {
+            return new_capability_unsupported_error(Operation::BlockingWrite);
+        }
+        if args.storage_class().is_some() && !capability.write_with_storage_class {
+            return new_capability_unsupported_error(Operation::BlockingWrite);
+        }
+        if args.object_lock() != &ObjectLock::default() && !capability.write_with_object_lock {
+            return new_capability_unsupported_error(Operation::BlockingWrite);
+        }
 
-        let size = args.content_length();
+        let size = args
+            .content_length()
+            .or_else(|| args.range().and_then(|v| v.size()));
         self.inner
             .blocking_write(path, args)
             .map(|(rp, w)| (rp, CompleteWriter::new(w, size)))
@@ -444,6 +689,12 @@ impl<A: Accessor> LayeredAccessor for CompleteReaderAccessor<A> {
         if !capability.delete {
             return new_capability_unsupported_error(Operation::Delete);
         }
+        if args.version().is_some() && !capability.delete_with_version {
+            return new_capability_unsupported_error(Operation::Delete);
+        }
+        if args.if_match().is_some() && !capability.delete_with_if_match {
+            return new_capability_unsupported_error(Operation::Delete);
+        }
 
         self.inner().delete(path, args).await
     }
@@ -453,6 +704,12 @@ impl<A: Accessor> LayeredAccessor for CompleteReaderAccessor<A> {
         if !capability.delete || !capability.blocking {
             return new_capability_unsupported_error(Operation::BlockingDelete);
         }
+        if args.version().is_some() && !capability.delete_with_version {
+            return new_capability_unsupported_error(Operation::BlockingDelete);
+        }
+        if args.if_match().is_some() && !capability.delete_with_if_match {
+            return new_capability_unsupported_error(Operation::BlockingDelete);
+        }
 
         self.inner().blocking_delete(path, args)
     }
@@ -462,6 +719,15 @@ impl<A: Accessor> LayeredAccessor for CompleteReaderAccessor<A> {
         if !capability.copy {
             return new_capability_unsupported_error(Operation::Copy);
         }
+        if args.metadata_directive() == MetadataDirective::Replace
+            && !capability.copy_with_metadata_directive
+        {
+            return new_capability_unsupported_error(Operation::Copy);
+        }
+        if args.if_source_match().is_some() && !capability.copy_with_if_source_match {
+            return new_capability_unsupported_error(Operation::Copy);
+        }
+        self.check_copy_metadata(Operation::Copy, &args)?;
 
         self.inner().copy(from, to, args).await
     }
@@ -471,6 +737,15 @@ impl<A: Accessor> LayeredAccessor for CompleteReaderAccessor<A> {
         if !capability.copy || !capability.blocking {
             return new_capability_unsupported_error(Operation::BlockingCopy);
         }
+        if args.metadata_directive() == MetadataDirective::Replace
+            && !capability.copy_with_metadata_directive
+        {
+            return new_capability_unsupported_error(Operation::BlockingCopy);
+        }
+        if args.if_source_match().is_some() && !capability.copy_with_if_source_match {
+            return new_capability_unsupported_error(Operation::BlockingCopy);
+        }
+        self.check_copy_metadata(Operation::BlockingCopy, &args)?;
 
         self.inner().blocking_copy(from, to, args)
     }
@@ -520,6 +795,60 @@ impl<A: Accessor> LayeredAccessor for CompleteReaderAccessor<A> {
         self.inner.presign(path, args).await
     }
 
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        let capability = self.meta.capability();
+        if !capability.get_tags {
+            return new_capability_unsupported_error(Operation::GetTags);
+        }
+
+        self.inner.get_tags(path, args).await
+    }
+
+    async fn put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        let capability = self.meta.capability();
+        if !capability.put_tags {
+            return new_capability_unsupported_error(Operation::PutTags);
+        }
+
+        self.inner.put_tags(path, args).await
+    }
+
+    fn blocking_get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        let capability = self.meta.capability();
+        if !capability.get_tags || !capability.blocking {
+            return new_capability_unsupported_error(Operation::BlockingGetTags);
+        }
+
+        self.inner.blocking_get_tags(path, args)
+    }
+
+    fn blocking_put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        let capability = self.meta.capability();
+        if !capability.put_tags || !capability.blocking {
+            return new_capability_unsupported_error(Operation::BlockingPutTags);
+        }
+
+        self.inner.blocking_put_tags(path, args)
+    }
+
+    async fn get_object_lock(&self, path: &str, args: OpGetObjectLock) -> Result<RpGetObjectLock> {
+        let capability = self.meta.capability();
+        if !capability.get_object_lock {
+            return new_capability_unsupported_error(Operation::GetObjectLock);
+        }
+
+        self.inner.get_object_lock(path, args).await
+    }
+
+    async fn put_object_lock(&self, path: &str, args: OpPutObjectLock) -> Result<RpPutObjectLock> {
+        let capability = self.meta.capability();
+        if !capability.put_object_lock {
+            return new_capability_unsupported_error(Operation::PutObjectLock);
+        }
+
+        self.inner.put_object_lock(path, args).await
+    }
+
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
         let capability = self.meta.capability();
         if !capability.batch {
@@ -612,6 +941,119 @@ where
     }
 }
 
+type CompleteCheckedReader<A, R> = CheckedReader<CompleteReader<A, R>>;
+
+/// CheckedReader makes sure the reader returns exactly `expect` bytes
+/// before reaching the end.
+///
+/// The position is tracked across seeks, so the check still holds while
+/// users read to the end after seeking.
+pub struct CheckedReader<R> {
+    inner: R,
+    expect: Option<u64>,
+    pos: u64,
+}
+
+impl<R> CheckedReader<R> {
+    fn new(inner: R, expect: Option<u64>) -> Self {
+        Self {
+            inner,
+            expect,
+            pos: 0,
+        }
+    }
+
+    fn check(&self) -> Result<()> {
+        let expect = match self.expect {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+        let actual = self.pos;
+
+        match actual.cmp(&expect) {
+            Ordering::Equal => Ok(()),
+            Ordering::Less => Err(
+                Error::new(ErrorKind::Unexpected, "reader got too less data")
+                    .with_context("expect", expect.to_string())
+                    .with_context("actual", actual.to_string())
+                    .set_temporary(),
+            ),
+            Ordering::Greater => Err(
+                Error::new(ErrorKind::Unexpected, "reader got too much data")
+                    .with_context("expect", expect.to_string())
+                    .with_context("actual", actual.to_string())
+                    .set_temporary(),
+            ),
+        }
+    }
+}
+
+impl<R: oio::Read> oio::Read for CheckedReader<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        let n = ready!(self.inner.poll_read(cx, buf))?;
+        if n == 0 && !buf.is_empty() {
+            self.check()?;
+        }
+        self.pos += n as u64;
+
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: io::SeekFrom) -> Poll<Result<u64>> {
+        let n = ready!(self.inner.poll_seek(cx, pos))?;
+        self.pos = n;
+
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        match ready!(self.inner.poll_next(cx)) {
+            Some(Ok(bs)) => {
+                self.pos += bs.len() as u64;
+                Poll::Ready(Some(Ok(bs)))
+            }
+            Some(Err(err)) => Poll::Ready(Some(Err(err))),
+            None => {
+                self.check()?;
+                Poll::Ready(None)
+            }
+        }
+    }
+}
+
+impl<R: oio::BlockingRead> oio::BlockingRead for CheckedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        if n == 0 && !buf.is_empty() {
+            self.check()?;
+        }
+        self.pos += n as u64;
+
+        Ok(n)
+    }
+
+    fn seek(&mut self, pos: io::SeekFrom) -> Result<u64> {
+        let n = self.inner.seek(pos)?;
+        self.pos = n;
+
+        Ok(n)
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        match self.inner.next() {
+            Some(Ok(bs)) => {
+                self.pos += bs.len() as u64;
+                Some(Ok(bs))
+            }
+            Some(Err(err)) => Some(Err(err)),
+            None => match self.check() {
+                Ok(()) => None,
+                Err(err) => Some(Err(err)),
+            },
+        }
+    }
+}
+
 pub enum CompletePager<A: Accessor, P> {
     AlreadyComplete(P),
     NeedFlat(FlatPager<Arc<A>, P>),
@@ -633,6 +1075,15 @@ where
             NeedHierarchy(p) => p.next().await,
         }
     }
+
+    /// Emulated pagers carry their own states, so we can't resume them
+    /// from the token of inner pager.
+    fn continuation_token(&self) -> Option<String> {
+        match self {
+            CompletePager::AlreadyComplete(p) => p.continuation_token(),
+            _ => None,
+        }
+    }
 }
 
 impl<A, P> oio::BlockingPage for CompletePager<A, P>
@@ -761,6 +1212,10 @@ where
 
         Ok(())
     }
+
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        self.inner.as_ref().and_then(|w| w.checkpoint())
+    }
 }
 
 impl<W> oio::BlockingWrite for CompleteWriter<W>
@@ -866,6 +1321,20 @@ fn new_capability_unsupported_error<R>(operation: Operation) -> Result<R> {
     Err(Error::new(ErrorKind::Unsupported, "operation is not supported").with_operation(operation))
 }
 
+/// Classify the mode by path if service returns `Unknown`, so that users
+/// always get a consistent mode from `stat`.
+fn complete_mode(path: &str, m: Metadata) -> Metadata {
+    if m.mode() != EntryMode::Unknown {
+        return m;
+    }
+
+    if path.ends_with('/') {
+        m.with_mode(EntryMode::DIR)
+    } else {
+        m.with_mode(EntryMode::FILE)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -1018,4 +1487,160 @@ mod tests {
     capability_test!(presign, |op| {
         op.presign_read("/path/to/mock_file", Duration::from_secs(1))
     });
+
+    #[tokio::test]
+    async fn test_custom_headers() {
+        let builder = MockBuilder::default().with_capacity(Capability {
+            read: true,
+            write: true,
+            ..Default::default()
+        });
+        let op = Operator::new(builder).expect("should build").finish();
+
+        let err = op
+            .reader_with("/path/to/mock_file")
+            .header("x-vendor-namespace", "test")
+            .await
+            .err()
+            .expect("reader must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        let err = op
+            .writer_with("/path/to/mock_file")
+            .header("x-vendor-namespace", "test")
+            .await
+            .err()
+            .expect("writer must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        assert!(op
+            .reader_with("/path/to/mock_file")
+            .header("x-vendor-namespace", "test")
+            .ignore_unsupported_headers(true)
+            .await
+            .is_ok());
+        assert!(op
+            .writer_with("/path/to/mock_file")
+            .header("x-vendor-namespace", "test")
+            .ignore_unsupported_headers(true)
+            .await
+            .is_ok());
+
+        let err = op
+            .writer_with("/path/to/mock_file")
+            .header("Authorization", "Bearer token")
+            .ignore_unsupported_headers(true)
+            .await
+            .err()
+            .expect("writer must fail");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[tokio::test]
+    async fn test_copy_metadata_requires_replace() {
+        let builder = MockBuilder::default().with_capacity(Capability {
+            copy: true,
+            copy_with_metadata_directive: true,
+            ..Default::default()
+        });
+        let op = Operator::new(builder).expect("should build").finish();
+
+        let err = op
+            .copy_with("/path/to/mock_file", "/path/to/mock_file_2")
+            .content_type("text/plain")
+            .await
+            .expect_err("copy must fail");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let err = op
+            .copy_with("/path/to/mock_file", "/path/to/mock_file_2")
+            .user_metadata([("location", "everywhere")])
+            .await
+            .expect_err("copy must fail");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+        assert!(op
+            .copy_with("/path/to/mock_file", "/path/to/mock_file_2")
+            .metadata_directive(MetadataDirective::Replace)
+            .content_type("text/plain")
+            .user_metadata([("location", "everywhere")])
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_native_only_list() {
+        let builder = || {
+            MockBuilder::default()
+                .with_capacity(Capability {
+                    list: true,
+                    ..Default::default()
+                })
+                .build()
+                .expect("should build")
+        };
+
+        let op = OperatorBuilder::new(builder()).finish();
+        assert!(op.list("/path/to/mock_dir/").await.is_ok());
+
+        let op = OperatorBuilder::new_native_only(builder()).finish();
+        let err = op
+            .list("/path/to/mock_dir/")
+            .await
+            .err()
+            .expect("list must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        let err = op
+            .scan("/path/to/mock_dir/")
+            .await
+            .err()
+            .expect("scan must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[tokio::test]
+    async fn test_checked_reader() {
+        use oio::ReadExt;
+
+        let mut r = CheckedReader::new(oio::Cursor::from(vec![0; 8]), Some(8));
+        let mut buf = vec![0; 16];
+        assert_eq!(r.read(&mut buf).await.unwrap(), 8);
+        assert_eq!(r.read(&mut buf).await.unwrap(), 0);
+
+        let mut r = CheckedReader::new(oio::Cursor::from(vec![0; 4]), Some(8));
+        assert_eq!(r.read(&mut buf).await.unwrap(), 4);
+        let err = r.read(&mut buf).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.is_temporary());
+
+        let mut r = CheckedReader::new(oio::Cursor::from(vec![0; 12]), Some(8));
+        assert!(r.next().await.unwrap().is_ok());
+        let err = r.next().await.unwrap().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.is_temporary());
+
+        // Seeking to the end should pass the check.
+        let mut r = CheckedReader::new(oio::Cursor::from(vec![0; 8]), Some(8));
+        assert_eq!(r.seek(io::SeekFrom::Start(6)).await.unwrap(), 6);
+        assert_eq!(r.read(&mut buf).await.unwrap(), 2);
+        assert_eq!(r.read(&mut buf).await.unwrap(), 0);
+
+        // Check will be skipped without expected size.
+        let mut r = CheckedReader::new(oio::Cursor::from(vec![0; 4]), None);
+        assert_eq!(r.read(&mut buf).await.unwrap(), 4);
+        assert_eq!(r.read(&mut buf).await.unwrap(), 0);
+    }
+
+    #[test]
+    fn test_blocking_checked_reader() {
+        use oio::BlockingRead;
+
+        let mut r = CheckedReader::new(oio::Cursor::from(vec![0; 4]), Some(8));
+        let mut buf = vec![0; 16];
+        assert_eq!(r.read(&mut buf).unwrap(), 4);
+        let err = r.read(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        let mut r = CheckedReader::new(oio::Cursor::from(vec![0; 8]), Some(8));
+        assert!(r.next().unwrap().is_ok());
+        assert!(r.next().is_none());
+    }
 }
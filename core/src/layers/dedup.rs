@@ -0,0 +1,500 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::future::Future;
+use std::io;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use bytes::BytesMut;
+use futures::channel::oneshot;
+
+use crate::raw::*;
+use crate::*;
+
+/// Collapse concurrent identical `read` and `stat` into one request.
+///
+/// # Notes
+///
+/// Under load, many callers could request the same hot object at the same
+/// time. DedupLayer will send only one request to the underlying storage for
+/// the same key, and share its result among all callers that arrive while
+/// it's in flight:
+///
+/// - Errors will be propagated to all callers too.
+/// - Nothing will be cached after the request finishes, combine with a
+///   cache layer if needed.
+/// - `read` with any different args will not be collapsed, and `read` with
+///   a progress callback will never be collapsed.
+/// - Content of `read` will be buffered in memory only if other callers are
+///   waiting for it and its size is known and not larger than
+///   [`DedupLayer::with_max_buffer_size`]. Otherwise, waiting callers will
+///   send requests by themselves.
+/// - Blocking operations are passed through as is.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::DedupLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(DedupLayer::default().with_max_buffer_size(1024 * 1024))
+///     .finish();
+/// ```
+#[derive(Debug, Clone)]
+pub struct DedupLayer {
+    max_buffer_size: usize,
+}
+
+impl Default for DedupLayer {
+    fn default() -> Self {
+        Self {
+            max_buffer_size: 4 * 1024 * 1024,
+        }
+    }
+}
+
+impl DedupLayer {
+    /// Set the max size of content that could be buffered to share among
+    /// callers of `read`.
+    ///
+    /// Default to 4 MiB.
+    pub fn with_max_buffer_size(mut self, size: usize) -> Self {
+        self.max_buffer_size = size;
+        self
+    }
+}
+
+impl<A: Accessor> Layer<A> for DedupLayer {
+    type LayeredAccessor = DedupAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        DedupAccessor {
+            inner,
+            max_buffer_size: self.max_buffer_size,
+            reads: Arc::new(InFlight::default()),
+            stats: Arc::new(InFlight::default()),
+        }
+    }
+}
+
+type Waiters<T> = Mutex<HashMap<String, Vec<oneshot::Sender<Result<T>>>>>;
+
+/// InFlight tracks the callers waiting for an on-going request.
+#[derive(Debug)]
+struct InFlight<T> {
+    calls: Waiters<T>,
+}
+
+impl<T> Default for InFlight<T> {
+    fn default() -> Self {
+        Self {
+            calls: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+/// Joined is the result of joining an in-flight request.
+enum Joined<'a, T> {
+    /// The same key is already in flight, wait for its result.
+    Waiter(oneshot::Receiver<Result<T>>),
+    /// We are the first one, the request should be sent by us.
+    Leader(InFlightGuard<'a, T>),
+}
+
+impl<T: Clone> InFlight<T> {
+    fn join(&self, key: String) -> Joined<'_, T> {
+        let mut calls = self.calls.lock().expect("lock must be valid");
+        match calls.get_mut(&key) {
+            Some(waiters) => {
+                let (tx, rx) = oneshot::channel();
+                waiters.push(tx);
+                Joined::Waiter(rx)
+            }
+            None => {
+                calls.insert(key.clone(), Vec::new());
+                Joined::Leader(InFlightGuard {
+                    calls: &self.calls,
+                    key,
+                })
+            }
+        }
+    }
+
+    /// Run `f` for given key, or wait for the result of the same key if
+    /// it's already in flight.
+    async fn run(&self, key: String, f: impl Future<Output = Result<T>>) -> Result<T> {
+        let guard = match self.join(key) {
+            Joined::Waiter(rx) => {
+                return match rx.await {
+                    Ok(res) => res,
+                    // The leading call has been dropped before finishing, we
+                    // should send the request by ourselves.
+                    Err(_) => f.await,
+                };
+            }
+            Joined::Leader(guard) => guard,
+        };
+
+        let res = f.await;
+        guard.finish(&res);
+        res
+    }
+}
+
+/// InFlightGuard makes sure the key will be removed even if the leading
+/// call is dropped, so that waiters won't wait forever.
+struct InFlightGuard<'a, T> {
+    calls: &'a Waiters<T>,
+    key: String,
+}
+
+impl<'a, T: Clone> InFlightGuard<'a, T> {
+    /// Check if there are callers waiting for this request.
+    fn has_waiters(&self) -> bool {
+        self.calls
+            .lock()
+            .expect("lock must be valid")
+            .get(&self.key)
+            .map(|v| !v.is_empty())
+            .unwrap_or_default()
+    }
+
+    /// Remove the key and send the result to all waiters.
+    fn finish(self, res: &Result<T>) {
+        self.send(|| match res {
+            Ok(v) => Ok(v.clone()),
+            Err(err) => Err(share_error(err)),
+        })
+    }
+
+    /// Remove the key and send the error to all waiters.
+    fn fail(self, err: &Error) {
+        self.send(|| Err(share_error(err)))
+    }
+
+    fn send(self, f: impl Fn() -> Result<T>) {
+        let waiters = self
+            .calls
+            .lock()
+            .expect("lock must be valid")
+            .remove(&self.key)
+            .unwrap_or_default();
+
+        for tx in waiters {
+            let _ = tx.send(f());
+        }
+    }
+}
+
+impl<'a, T> Drop for InFlightGuard<'a, T> {
+    fn drop(&mut self) {
+        if let Ok(mut calls) = self.calls.lock() {
+            calls.remove(&self.key);
+        }
+    }
+}
+
+/// Error can't be cloned, so we build a new one carrying the same kind,
+/// status and description for every waiter.
+fn share_error(err: &Error) -> Error {
+    let shared = Error::new(err.kind(), &err.to_string()).with_operation("DedupLayer");
+    if err.is_temporary() {
+        shared.set_temporary()
+    } else {
+        shared
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DedupAccessor<A: Accessor> {
+    inner: A,
+    max_buffer_size: usize,
+    reads: Arc<InFlight<(RpRead, Bytes)>>,
+    stats: Arc<InFlight<RpStat>>,
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for DedupAccessor<A> {
+    type Inner = A;
+    type Reader = DedupReader<A::Reader>;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Appender = A::Appender;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        // Progress callback belongs to every single caller, don't share it.
+        if args.progress().is_some() {
+            return self
+                .inner
+                .read(path, args)
+                .await
+                .map(|(rp, r)| (rp, DedupReader::Inner(r)));
+        }
+
+        let key = format!("{path}:{args:?}");
+        let guard = match self.reads.join(key) {
+            Joined::Waiter(rx) => match rx.await {
+                Ok(res) => return res.map(|(rp, bs)| (rp, DedupReader::Shared(bs.into()))),
+                // The leading call has been dropped or decided not to
+                // share its content, send the request by ourselves.
+                Err(_) => {
+                    return self
+                        .inner
+                        .read(path, args)
+                        .await
+                        .map(|(rp, r)| (rp, DedupReader::Inner(r)))
+                }
+            },
+            Joined::Leader(guard) => guard,
+        };
+
+        let (rp, mut r) = match self.inner.read(path, args).await {
+            Ok(v) => v,
+            Err(err) => {
+                guard.fail(&err);
+                return Err(err);
+            }
+        };
+
+        // Only buffer the content while other callers are waiting for it and
+        // it's small enough, otherwise they will send requests by themselves.
+        let shareable = match rp.metadata().content_length_raw() {
+            Some(size) => size <= self.max_buffer_size as u64,
+            None => false,
+        };
+        if !shareable || !guard.has_waiters() {
+            drop(guard);
+            return Ok((rp, DedupReader::Inner(r)));
+        }
+
+        let mut buf = BytesMut::new();
+        let res = loop {
+            match oio::ReadExt::next(&mut r).await {
+                Some(Ok(bs)) => buf.extend_from_slice(&bs),
+                Some(Err(err)) => break Err(err),
+                None => break Ok((rp, buf.freeze())),
+            }
+        };
+        guard.finish(&res);
+        res.map(|(rp, bs)| (rp, DedupReader::Shared(bs.into())))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(path, args).await
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        self.inner.append(path, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let key = format!("{path}:{args:?}");
+
+        self.stats.run(key, self.inner.stat(path, args)).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner.list(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+pub enum DedupReader<R> {
+    Shared(oio::Cursor),
+    Inner(R),
+}
+
+impl<R: oio::Read> oio::Read for DedupReader<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        match self {
+            Self::Shared(r) => r.poll_read(cx, buf),
+            Self::Inner(r) => r.poll_read(cx, buf),
+        }
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: io::SeekFrom) -> Poll<Result<u64>> {
+        match self {
+            Self::Shared(r) => r.poll_seek(cx, pos),
+            Self::Inner(r) => r.poll_seek(cx, pos),
+        }
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        match self {
+            Self::Shared(r) => r.poll_next(cx),
+            Self::Inner(r) => r.poll_next(cx),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    use futures::future::join_all;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct MockService {
+        reads: Arc<AtomicUsize>,
+        stats: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Accessor for MockService {
+        type Reader = oio::Cursor;
+        type BlockingReader = ();
+        type Writer = ();
+        type BlockingWriter = ();
+        type Appender = ();
+        type Pager = ();
+        type BlockingPager = ();
+
+        fn info(&self) -> AccessorInfo {
+            AccessorInfo::default()
+        }
+
+        async fn read(&self, _: &str, _: OpRead) -> Result<(RpRead, Self::Reader)> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            // Make sure other calls have the chance to join.
+            tokio::task::yield_now().await;
+
+            Ok((RpRead::new(5), oio::Cursor::from(Bytes::from("hello"))))
+        }
+
+        async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+            self.stats.fetch_add(1, Ordering::SeqCst);
+            // Make sure other calls have the chance to join.
+            tokio::task::yield_now().await;
+
+            if path == "not_exist" {
+                return Err(Error::new(ErrorKind::NotFound, "not found"));
+            }
+            Ok(RpStat::new(
+                Metadata::new(EntryMode::FILE).with_content_length(1),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dedup_stat() {
+        let srv = MockService::default();
+        let stats = srv.stats.clone();
+        let acc = DedupLayer::default().layer(srv);
+
+        let res = join_all((0..10).map(|_| Accessor::stat(&acc, "file", OpStat::new()))).await;
+        assert_eq!(stats.load(Ordering::SeqCst), 1);
+        for rp in res {
+            assert_eq!(
+                rp.expect("stat must succeed")
+                    .into_metadata()
+                    .content_length(),
+                1
+            );
+        }
+
+        // Nothing should be cached after the request finished.
+        Accessor::stat(&acc, "file", OpStat::new())
+            .await
+            .expect("stat must succeed");
+        assert_eq!(stats.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_dedup_read() {
+        let srv = MockService::default();
+        let reads = srv.reads.clone();
+        let acc = DedupLayer::default().layer(srv);
+
+        let res = join_all((0..10).map(|_| async {
+            let (_, mut r) = Accessor::read(&acc, "file", OpRead::new()).await?;
+            let mut bs = Vec::new();
+            while let Some(v) = oio::ReadExt::next(&mut r).await {
+                bs.extend_from_slice(&v?);
+            }
+            Ok::<_, Error>(bs)
+        }))
+        .await;
+        assert_eq!(reads.load(Ordering::SeqCst), 1);
+        for bs in res {
+            assert_eq!(bs.expect("read must succeed"), b"hello");
+        }
+
+        // Different args should not be collapsed.
+        Accessor::read(&acc, "file", OpRead::new().with_if_match("\"etag\""))
+            .await
+            .expect("read must succeed");
+        assert_eq!(reads.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_dedup_read_too_large() {
+        let srv = MockService::default();
+        let reads = srv.reads.clone();
+        let acc = DedupLayer::default().with_max_buffer_size(1).layer(srv);
+
+        let res = join_all((0..10).map(|_| Accessor::read(&acc, "file", OpRead::new()))).await;
+        assert_eq!(reads.load(Ordering::SeqCst), 10);
+        for rp in res {
+            rp.expect("read must succeed");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dedup_stat_error() {
+        let srv = MockService::default();
+        let stats = srv.stats.clone();
+        let acc = DedupLayer::default().layer(srv);
+
+        let res = join_all((0..10).map(|_| Accessor::stat(&acc, "not_exist", OpStat::new()))).await;
+        assert_eq!(stats.load(Ordering::SeqCst), 1);
+        for rp in res {
+            assert_eq!(rp.unwrap_err().kind(), ErrorKind::NotFound);
+        }
+    }
+}
@@ -0,0 +1,363 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use log::info;
+
+use crate::raw::*;
+use crate::*;
+
+/// Preview mutating operations without touching underlying storage.
+///
+/// # Notes
+///
+/// DryRunLayer will intercept `create_dir`, `write`, `append`, `delete`,
+/// `copy`, `rename`, `put_tags`, `put_object_lock` and batch delete: the
+/// intended actions will be logged and recorded, and success will be returned
+/// directly. Data written into writers or appenders will be discarded.
+///
+/// Other operations like `read`, `stat` and `list` will be passed through,
+/// so the effect of previous actions is NOT visible to them.
+///
+/// Recorded actions can be fetched via [`DryRunLayer::recorded_actions`],
+/// all clones of the same layer share the same records.
+///
+/// # Examples
+///
+/// ```
+/// # use anyhow::Result;
+/// use opendal::layers::DryRunAction;
+/// use opendal::layers::DryRunLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<()> {
+/// let layer = DryRunLayer::new();
+/// let op = Operator::new(services::Memory::default())?
+///     .layer(layer.clone())
+///     .finish();
+///
+/// op.delete("path/to/file").await?;
+/// assert_eq!(
+///     layer.recorded_actions(),
+///     vec![DryRunAction::Delete("path/to/file".to_string())]
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct DryRunLayer {
+    actions: Arc<Mutex<Vec<DryRunAction>>>,
+}
+
+/// Action recorded by [`DryRunLayer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DryRunAction {
+    /// Create dir at given path.
+    CreateDir(String),
+    /// Write to given path.
+    Write(String),
+    /// Append to given path.
+    Append(String),
+    /// Delete given path.
+    Delete(String),
+    /// Copy `from` to `to`.
+    Copy {
+        /// Source path.
+        from: String,
+        /// Target path.
+        to: String,
+    },
+    /// Rename `from` to `to`.
+    Rename {
+        /// Source path.
+        from: String,
+        /// Target path.
+        to: String,
+    },
+    /// Replace tags of given path.
+    PutTags(String),
+    /// Update object lock of given path.
+    PutObjectLock(String),
+}
+
+impl DryRunLayer {
+    /// Create a new DryRunLayer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get all recorded actions in the order they are issued.
+    pub fn recorded_actions(&self) -> Vec<DryRunAction> {
+        self.actions.lock().expect("lock must be valid").clone()
+    }
+
+    /// Clear all recorded actions.
+    pub fn clear(&self) {
+        self.actions.lock().expect("lock must be valid").clear();
+    }
+}
+
+impl<A: Accessor> Layer<A> for DryRunLayer {
+    type LayeredAccessor = DryRunAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        DryRunAccessor {
+            scheme: inner.info().scheme(),
+            inner,
+            actions: self.actions.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DryRunAccessor<A: Accessor> {
+    inner: A,
+    scheme: Scheme,
+    actions: Arc<Mutex<Vec<DryRunAction>>>,
+}
+
+impl<A: Accessor> DryRunAccessor<A> {
+    fn record(&self, action: DryRunAction) {
+        info!(
+            target: "opendal::layers::dry_run",
+            "service={} dry run: {:?}", self.scheme, action
+        );
+        self.actions
+            .lock()
+            .expect("lock must be valid")
+            .push(action);
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for DryRunAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = DryRunWriter;
+    type BlockingWriter = DryRunWriter;
+    type Appender = DryRunWriter;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        self.record(DryRunAction::CreateDir(path.to_string()));
+        Ok(RpCreateDir::default())
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.record(DryRunAction::Write(path.to_string()));
+        Ok((RpWrite::new(), DryRunWriter))
+    }
+
+    async fn append(&self, path: &str, _: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        self.record(DryRunAction::Append(path.to_string()));
+        Ok((RpAppend::new(), DryRunWriter))
+    }
+
+    async fn copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
+        self.record(DryRunAction::Copy {
+            from: from.to_string(),
+            to: to.to_string(),
+        });
+        Ok(RpCopy::new())
+    }
+
+    async fn rename(&self, from: &str, to: &str, _: OpRename) -> Result<RpRename> {
+        self.record(DryRunAction::Rename {
+            from: from.to_string(),
+            to: to.to_string(),
+        });
+        Ok(RpRename::new())
+    }
+
+    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        self.record(DryRunAction::Delete(path.to_string()));
+        Ok(RpDelete::default())
+    }
+
+    async fn put_tags(&self, path: &str, _: OpPutTags) -> Result<RpPutTags> {
+        self.record(DryRunAction::PutTags(path.to_string()));
+        Ok(RpPutTags::new())
+    }
+
+    async fn put_object_lock(&self, path: &str, _: OpPutObjectLock) -> Result<RpPutObjectLock> {
+        self.record(DryRunAction::PutObjectLock(path.to_string()));
+        Ok(RpPutObjectLock::new())
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let results = args
+            .into_operation()
+            .into_iter()
+            .map(|(path, op)| match op {
+                BatchOperation::Delete(_) => {
+                    self.record(DryRunAction::Delete(path.clone()));
+                    (path, Ok(BatchedReply::Delete(RpDelete::default())))
+                }
+            })
+            .collect();
+        Ok(RpBatch::new(results))
+    }
+
+    fn blocking_create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        self.record(DryRunAction::CreateDir(path.to_string()));
+        Ok(RpCreateDir::default())
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.record(DryRunAction::Write(path.to_string()));
+        Ok((RpWrite::new(), DryRunWriter))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
+        self.record(DryRunAction::Copy {
+            from: from.to_string(),
+            to: to.to_string(),
+        });
+        Ok(RpCopy::new())
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, _: OpRename) -> Result<RpRename> {
+        self.record(DryRunAction::Rename {
+            from: from.to_string(),
+            to: to.to_string(),
+        });
+        Ok(RpRename::new())
+    }
+
+    fn blocking_delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        self.record(DryRunAction::Delete(path.to_string()));
+        Ok(RpDelete::default())
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner.blocking_list(path, args)
+    }
+
+    fn blocking_put_tags(&self, path: &str, _: OpPutTags) -> Result<RpPutTags> {
+        self.record(DryRunAction::PutTags(path.to_string()));
+        Ok(RpPutTags::new())
+    }
+}
+
+/// DryRunWriter will discard all data written into it.
+pub struct DryRunWriter;
+
+#[async_trait]
+impl oio::Write for DryRunWriter {
+    async fn write(&mut self, _: Bytes) -> Result<()> {
+        Ok(())
+    }
+
+    async fn sink(&mut self, _: u64, _: oio::Streamer) -> Result<()> {
+        Ok(())
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl oio::BlockingWrite for DryRunWriter {
+    fn write(&mut self, _: Bytes) -> Result<()> {
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl oio::Append for DryRunWriter {
+    async fn append(&mut self, _: Bytes) -> Result<()> {
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+    use crate::Operator;
+
+    #[tokio::test]
+    async fn test_dry_run() -> anyhow::Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        op.write("file", "hello").await?;
+
+        let layer = DryRunLayer::new();
+        let dry = op.clone().layer(layer.clone());
+
+        dry.write("new_file", "world").await?;
+        dry.delete("file").await?;
+        dry.rename("file", "renamed").await?;
+
+        // Nothing should be changed.
+        assert!(op.is_exist("file").await?);
+        assert!(!op.is_exist("new_file").await?);
+        assert_eq!(dry.read("file").await?, b"hello");
+
+        assert_eq!(
+            layer.recorded_actions(),
+            vec![
+                DryRunAction::Write("new_file".to_string()),
+                DryRunAction::Delete("file".to_string()),
+                DryRunAction::Rename {
+                    from: "file".to_string(),
+                    to: "renamed".to_string(),
+                },
+            ]
+        );
+
+        layer.clear();
+        assert!(layer.recorded_actions().is_empty());
+        Ok(())
+    }
+}
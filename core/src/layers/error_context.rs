@@ -41,6 +41,11 @@ use crate::*;
 /// - `service`: The [`Scheme`] of underlying service.
 /// - `operation`: The [`Operation`] of this operation
 /// - `path`: The path of this operation
+///
+/// Errors returned by http based services will also carry `status`,
+/// `request_id` and `response_body` (if available), which can be fetched
+/// via [`Error::http_status`], [`Error::request_id`] and
+/// [`Error::response_body`].
 pub struct ErrorContextLayer;
 
 impl<A: Accessor> Layer<A> for ErrorContextLayer {
@@ -235,6 +240,38 @@ impl<A: Accessor> LayeredAccessor for ErrorContextAccessor<A> {
         })
     }
 
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        self.inner.get_tags(path, args).await.map_err(|err| {
+            err.with_operation(Operation::GetTags)
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+        })
+    }
+
+    async fn put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        self.inner.put_tags(path, args).await.map_err(|err| {
+            err.with_operation(Operation::PutTags)
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+        })
+    }
+
+    async fn get_object_lock(&self, path: &str, args: OpGetObjectLock) -> Result<RpGetObjectLock> {
+        self.inner.get_object_lock(path, args).await.map_err(|err| {
+            err.with_operation(Operation::GetObjectLock)
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+        })
+    }
+
+    async fn put_object_lock(&self, path: &str, args: OpPutObjectLock) -> Result<RpPutObjectLock> {
+        self.inner.put_object_lock(path, args).await.map_err(|err| {
+            err.with_operation(Operation::PutObjectLock)
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+        })
+    }
+
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
         self.inner
             .batch(args)
@@ -362,6 +399,22 @@ impl<A: Accessor> LayeredAccessor for ErrorContextAccessor<A> {
                     .with_context("path", path)
             })
     }
+
+    fn blocking_get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        self.inner.blocking_get_tags(path, args).map_err(|err| {
+            err.with_operation(Operation::BlockingGetTags)
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+        })
+    }
+
+    fn blocking_put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        self.inner.blocking_put_tags(path, args).map_err(|err| {
+            err.with_operation(Operation::BlockingPutTags)
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+        })
+    }
 }
 
 pub struct ErrorContextWrapper<T> {
@@ -457,6 +510,10 @@ impl<T: oio::Write> oio::Write for ErrorContextWrapper<T> {
                 .with_context("path", &self.path)
         })
     }
+
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        self.inner.checkpoint()
+    }
 }
 
 impl<T: oio::BlockingWrite> oio::BlockingWrite for ErrorContextWrapper<T> {
@@ -505,6 +562,10 @@ impl<T: oio::Page> oio::Page for ErrorContextWrapper<T> {
                 .with_context("path", &self.path)
         })
     }
+
+    fn continuation_token(&self) -> Option<String> {
+        self.inner.continuation_token()
+    }
 }
 
 impl<T: oio::BlockingPage> oio::BlockingPage for ErrorContextWrapper<T> {
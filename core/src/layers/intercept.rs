@@ -0,0 +1,428 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use async_trait::async_trait;
+
+use crate::raw::*;
+use crate::*;
+
+/// Add an [`Interceptor`] around every operation.
+///
+/// # Notes
+///
+/// Implementing [`Layer`] and [`LayeredAccessor`] requires forwarding
+/// every accessor method and wrapping readers, writers and pagers. Most
+/// layers like audit or metrics only care about which operation happened
+/// on which path and whether it succeeded. `InterceptLayer` handles all
+/// the forwarding so that users only need to implement [`Interceptor`].
+///
+/// Readers, writers and pagers returned by inner accessor are passed
+/// through as is. So for `read`, `write`, `append` and `list`, the
+/// interceptor observes the operation that creates them, not the IO
+/// happened on them later.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::atomic::AtomicUsize;
+/// use std::sync::atomic::Ordering;
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// use async_trait::async_trait;
+/// use opendal::layers::InterceptLayer;
+/// use opendal::layers::Interceptor;
+/// use opendal::raw::Operation;
+/// use opendal::services;
+/// use opendal::Error;
+/// use opendal::ErrorKind;
+/// use opendal::Operator;
+/// use opendal::Result;
+///
+/// /// An audit interceptor which counts operations and rejects
+/// /// any access to `secret/`.
+/// #[derive(Default)]
+/// struct AuditInterceptor {
+///     count: AtomicUsize,
+/// }
+///
+/// #[async_trait]
+/// impl Interceptor for AuditInterceptor {
+///     async fn before(&self, op: Operation, path: &str) -> Result<()> {
+///         self.blocking_before(op, path)
+///     }
+///
+///     fn blocking_before(&self, _: Operation, path: &str) -> Result<()> {
+///         if path.starts_with("secret/") {
+///             return Err(Error::new(
+///                 ErrorKind::PermissionDenied,
+///                 "access to secret is not allowed",
+///             ));
+///         }
+///         Ok(())
+///     }
+///
+///     fn after(&self, op: Operation, path: &str, err: Option<&Error>, dur: Duration) {
+///         self.count.fetch_add(1, Ordering::Relaxed);
+///         println!("{op} {path} took {dur:?}, failed: {}", err.is_some());
+///     }
+/// }
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(InterceptLayer::new(AuditInterceptor::default()))
+///     .finish();
+/// ```
+pub struct InterceptLayer<I: Interceptor> {
+    interceptor: Arc<I>,
+}
+
+impl<I: Interceptor> InterceptLayer<I> {
+    /// Create a new InterceptLayer with given interceptor.
+    pub fn new(interceptor: I) -> Self {
+        Self {
+            interceptor: Arc::new(interceptor),
+        }
+    }
+}
+
+impl<I: Interceptor> Clone for InterceptLayer<I> {
+    fn clone(&self) -> Self {
+        Self {
+            interceptor: self.interceptor.clone(),
+        }
+    }
+}
+
+impl<A: Accessor, I: Interceptor> Layer<A> for InterceptLayer<I> {
+    type LayeredAccessor = InterceptAccessor<A, I>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        InterceptAccessor {
+            inner,
+            interceptor: self.interceptor.clone(),
+        }
+    }
+}
+
+/// Interceptor is used to intercept every operation in [`InterceptLayer`].
+///
+/// All functions have default implementations which do nothing, users
+/// only need to implement the ones they care about.
+///
+/// `path` is the path of operation. For `copy` and `rename`, it's the
+/// source path. For `batch`, it's empty.
+#[async_trait]
+pub trait Interceptor: Send + Sync + 'static {
+    /// Everytime an async operation starts, this function will be called.
+    ///
+    /// Returning an error will abort the operation with this error.
+    async fn before(&self, op: Operation, path: &str) -> Result<()> {
+        let _ = (op, path);
+        Ok(())
+    }
+
+    /// Everytime a blocking operation starts, this function will be called.
+    ///
+    /// Returning an error will abort the operation with this error.
+    fn blocking_before(&self, op: Operation, path: &str) -> Result<()> {
+        let _ = (op, path);
+        Ok(())
+    }
+
+    /// Everytime an operation finishes, this function will be called.
+    ///
+    /// # Inputs
+    ///
+    /// - op: The operation that finished.
+    /// - path: The path of the operation.
+    /// - err: The error returned by operation, `None` if succeeded.
+    /// - dur: The duration that the operation took.
+    ///
+    /// # Notes
+    ///
+    /// The intercept must be quick and non-blocking. No heavy IO is
+    /// allowed. Otherwise the operation will be blocked.
+    fn after(&self, op: Operation, path: &str, err: Option<&Error>, dur: Duration) {
+        let _ = (op, path, err, dur);
+    }
+}
+
+pub struct InterceptAccessor<A: Accessor, I: Interceptor> {
+    inner: A,
+    interceptor: Arc<I>,
+}
+
+impl<A: Accessor, I: Interceptor> Debug for InterceptAccessor<A, I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InterceptAccessor")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: Accessor, I: Interceptor> InterceptAccessor<A, I> {
+    async fn intercept<T, F>(&self, op: Operation, path: &str, f: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        self.interceptor.before(op, path).await?;
+
+        let start = Instant::now();
+        let res = f.await;
+        self.interceptor
+            .after(op, path, res.as_ref().err(), start.elapsed());
+        res
+    }
+
+    fn blocking_intercept<T>(
+        &self,
+        op: Operation,
+        path: &str,
+        f: impl FnOnce() -> Result<T>,
+    ) -> Result<T> {
+        self.interceptor.blocking_before(op, path)?;
+
+        let start = Instant::now();
+        let res = f();
+        self.interceptor
+            .after(op, path, res.as_ref().err(), start.elapsed());
+        res
+    }
+}
+
+#[async_trait]
+impl<A: Accessor, I: Interceptor> LayeredAccessor for InterceptAccessor<A, I> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Appender = A::Appender;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.intercept(
+            Operation::CreateDir,
+            path,
+            self.inner.create_dir(path, args),
+        )
+        .await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.intercept(Operation::Read, path, self.inner.read(path, args))
+            .await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.intercept(Operation::Write, path, self.inner.write(path, args))
+            .await
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        self.intercept(Operation::Append, path, self.inner.append(path, args))
+            .await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.intercept(Operation::Copy, from, self.inner.copy(from, to, args))
+            .await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.intercept(Operation::Rename, from, self.inner.rename(from, to, args))
+            .await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.intercept(Operation::Stat, path, self.inner.stat(path, args))
+            .await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.intercept(Operation::Delete, path, self.inner.delete(path, args))
+            .await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.intercept(Operation::List, path, self.inner.list(path, args))
+            .await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        self.intercept(Operation::Batch, "", self.inner.batch(args))
+            .await
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.intercept(Operation::Presign, path, self.inner.presign(path, args))
+            .await
+    }
+
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        self.intercept(Operation::GetTags, path, self.inner.get_tags(path, args))
+            .await
+    }
+
+    async fn put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        self.intercept(Operation::PutTags, path, self.inner.put_tags(path, args))
+            .await
+    }
+
+    async fn get_object_lock(&self, path: &str, args: OpGetObjectLock) -> Result<RpGetObjectLock> {
+        self.intercept(
+            Operation::GetObjectLock,
+            path,
+            self.inner.get_object_lock(path, args),
+        )
+        .await
+    }
+
+    async fn put_object_lock(&self, path: &str, args: OpPutObjectLock) -> Result<RpPutObjectLock> {
+        self.intercept(
+            Operation::PutObjectLock,
+            path,
+            self.inner.put_object_lock(path, args),
+        )
+        .await
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.blocking_intercept(Operation::BlockingCreateDir, path, || {
+            self.inner.blocking_create_dir(path, args)
+        })
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.blocking_intercept(Operation::BlockingRead, path, || {
+            self.inner.blocking_read(path, args)
+        })
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.blocking_intercept(Operation::BlockingWrite, path, || {
+            self.inner.blocking_write(path, args)
+        })
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.blocking_intercept(Operation::BlockingCopy, from, || {
+            self.inner.blocking_copy(from, to, args)
+        })
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.blocking_intercept(Operation::BlockingRename, from, || {
+            self.inner.blocking_rename(from, to, args)
+        })
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.blocking_intercept(Operation::BlockingStat, path, || {
+            self.inner.blocking_stat(path, args)
+        })
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.blocking_intercept(Operation::BlockingDelete, path, || {
+            self.inner.blocking_delete(path, args)
+        })
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.blocking_intercept(Operation::BlockingList, path, || {
+            self.inner.blocking_list(path, args)
+        })
+    }
+
+    fn blocking_get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        self.blocking_intercept(Operation::BlockingGetTags, path, || {
+            self.inner.blocking_get_tags(path, args)
+        })
+    }
+
+    fn blocking_put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        self.blocking_intercept(Operation::BlockingPutTags, path, || {
+            self.inner.blocking_put_tags(path, args)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::services::Memory;
+
+    #[derive(Default)]
+    struct MockInterceptor {
+        ops: Mutex<Vec<(Operation, String, bool)>>,
+    }
+
+    #[async_trait]
+    impl Interceptor for MockInterceptor {
+        async fn before(&self, _: Operation, path: &str) -> Result<()> {
+            if path == "denied" {
+                return Err(Error::new(ErrorKind::PermissionDenied, "denied"));
+            }
+            Ok(())
+        }
+
+        fn after(&self, op: Operation, path: &str, err: Option<&Error>, _: Duration) {
+            self.ops
+                .lock()
+                .unwrap()
+                .push((op, path.to_string(), err.is_none()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_intercept() {
+        let layer = InterceptLayer::new(MockInterceptor::default());
+        let op = Operator::new(Memory::default())
+            .unwrap()
+            .layer(layer.clone())
+            .finish();
+
+        op.write("test", "Hello, World!").await.unwrap();
+        assert!(op.stat("not_exist").await.is_err());
+        assert!(op.get_tags("test").await.is_err());
+
+        let err = op.stat("denied").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        let ops = layer.interceptor.ops.lock().unwrap();
+        assert!(ops.contains(&(Operation::Write, "test".to_string(), true)));
+        assert!(ops.contains(&(Operation::Stat, "not_exist".to_string(), false)));
+        assert!(ops.contains(&(Operation::GetTags, "test".to_string(), false)));
+        assert!(!ops.iter().any(|(_, path, _)| path == "denied"));
+    }
+}
@@ -15,10 +15,16 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::borrow::Cow;
+use std::cell::Cell;
 use std::fmt::Debug;
+use std::fmt::Formatter;
 use std::io;
+use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
 use async_trait::async_trait;
 use bytes::Bytes;
@@ -47,6 +53,21 @@ use crate::*;
 /// - The default log level while expected error happened is `Warn`.
 /// - The default log level while unexpected failure happened is `Error`.
 ///
+/// # Sampling
+///
+/// Logging every operation could be too expensive under very high request
+/// rates. Use [`LoggingLayer::with_sample_ratio`] to log only a fraction of
+/// operations: the `started` and `finished` entries of an operation (and of
+/// its reader, writer or pager) are logged only if the operation is sampled.
+///
+/// Errors and failures are always logged regardless of the ratio.
+///
+/// # Redaction
+///
+/// Paths could carry sensitive data. Use [`LoggingLayer::with_path_redactor`]
+/// to rewrite every path before it's logged, for example with the rules of
+/// [`RedactLayer`](crate::layers::RedactLayer).
+///
 /// # Todo
 ///
 /// We should migrate to log's kv api after it's ready.
@@ -83,10 +104,12 @@ use crate::*;
 /// ```shell
 /// RUST_LOG="info,opendal::services=debug" ./app
 /// ```
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub struct LoggingLayer {
     error_level: Option<Level>,
     failure_level: Option<Level>,
+    sample_ratio: f64,
+    path_redactor: Option<PathRedactor>,
 }
 
 impl Default for LoggingLayer {
@@ -94,6 +117,8 @@ impl Default for LoggingLayer {
         Self {
             error_level: Some(Level::Warn),
             failure_level: Some(Level::Error),
+            sample_ratio: 1.0,
+            path_redactor: None,
         }
     }
 }
@@ -134,6 +159,67 @@ impl LoggingLayer {
         }
         Ok(self)
     }
+
+    /// Setting the fraction of operations to log, `1.0` by default.
+    ///
+    /// For example: `0.01` means only about 1% of operations will be logged.
+    /// Errors and failures are always logged.
+    ///
+    /// The ratio will be clamped into `[0.0, 1.0]`.
+    pub fn with_sample_ratio(mut self, ratio: f64) -> Self {
+        self.sample_ratio = if ratio.is_nan() {
+            1.0
+        } else {
+            ratio.clamp(0.0, 1.0)
+        };
+        self
+    }
+
+    /// Setting the function to redact paths before they are logged.
+    ///
+    /// The function will be applied to every path logged by operations,
+    /// readers, writers, appenders and pagers. With feature `layers-redact`
+    /// enabled, `RedactLayer::redact` could be used here to share the same
+    /// rules with `RedactLayer`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use opendal::layers::LoggingLayer;
+    /// use opendal::services;
+    /// use opendal::Operator;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let _ = Operator::new(services::Memory::default())?
+    ///     .layer(LoggingLayer::default().with_path_redactor(|p| {
+    ///         if p.starts_with("secret/") {
+    ///             "secret/***".to_string()
+    ///         } else {
+    ///             p.to_string()
+    ///         }
+    ///     }))
+    ///     .finish();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_path_redactor(
+        mut self,
+        f: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.path_redactor = Some(PathRedactor(Arc::new(f)));
+        self
+    }
+}
+
+/// PathRedactor rewrites paths before they are logged.
+#[derive(Clone)]
+struct PathRedactor(Arc<dyn Fn(&str) -> String + Send + Sync>);
+
+impl Debug for PathRedactor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PathRedactor").finish_non_exhaustive()
+    }
 }
 
 impl<A: Accessor> Layer<A> for LoggingLayer {
@@ -147,6 +233,8 @@ impl<A: Accessor> Layer<A> for LoggingLayer {
 
             error_level: self.error_level,
             failure_level: self.failure_level,
+            sampler: Sampler::new(self.sample_ratio),
+            path_redactor: self.path_redactor.clone(),
         }
     }
 }
@@ -158,6 +246,8 @@ pub struct LoggingAccessor<A: Accessor> {
 
     error_level: Option<Level>,
     failure_level: Option<Level>,
+    sampler: Sampler,
+    path_redactor: Option<PathRedactor>,
 }
 
 static LOGGING_TARGET: &str = "opendal::services";
@@ -180,6 +270,75 @@ impl<A: Accessor> LoggingAccessor<A> {
             self.error_level
         }
     }
+
+    /// Decide whether the non-error logs of current operation will be emitted.
+    #[inline]
+    fn sample(&self) -> bool {
+        self.sampler.sample()
+    }
+
+    /// Redact the path to be logged.
+    #[inline]
+    fn redact<'a>(&self, path: &'a str) -> Cow<'a, str> {
+        match &self.path_redactor {
+            Some(PathRedactor(f)) => Cow::Owned(f(path)),
+            None => Cow::Borrowed(path),
+        }
+    }
+}
+
+/// Sampler decides whether an operation should be logged.
+#[derive(Clone, Copy, Debug)]
+struct Sampler {
+    ratio: f64,
+}
+
+thread_local! {
+    /// State of the thread local splitmix64 RNG used by [`Sampler`].
+    ///
+    /// Every thread owns its own state so that sampling never contends
+    /// on a shared counter.
+    static SAMPLER_STATE: Cell<u64> = Cell::new(sampler_seed());
+}
+
+/// Seed the RNG of current thread with time and the address of a thread
+/// local value, so that threads started at the same time still diverge.
+fn sampler_seed() -> u64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default();
+    let local = 0u8;
+
+    nanos ^ (&local as *const u8 as u64).rotate_left(32)
+}
+
+impl Sampler {
+    fn new(ratio: f64) -> Self {
+        Self { ratio }
+    }
+
+    #[inline]
+    fn sample(&self) -> bool {
+        if self.ratio >= 1.0 {
+            return true;
+        }
+        if self.ratio <= 0.0 {
+            return false;
+        }
+
+        let mut z = SAMPLER_STATE.with(|state| {
+            let v = state.get().wrapping_add(0x9E37_79B9_7F4A_7C15);
+            state.set(v);
+            v
+        });
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        // Take the high 53 bits to build a float in [0, 1).
+        ((z >> 11) as f64 / (1u64 << 53) as f64) < self.ratio
+    }
 }
 
 #[async_trait]
@@ -198,44 +357,56 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     fn metadata(&self) -> AccessorInfo {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} -> started",
-            self.scheme,
-            Operation::Info
-        );
+        let sampled = self.sample();
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} -> started",
+                self.scheme,
+                Operation::Info
+            );
+        }
         let result = self.inner.info();
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} -> finished: {:?}",
-            self.scheme,
-            Operation::Info,
-            result
-        );
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} -> finished: {:?}",
+                self.scheme,
+                Operation::Info,
+                result
+            );
+        }
 
         result
     }
 
     async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.scheme,
-            Operation::CreateDir,
-            path
-        );
+        let sampled = self.sample();
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} path={} -> started",
+                self.scheme,
+                Operation::CreateDir,
+                &self.redact(path)
+            );
+        }
 
         self.inner
             .create_dir(path, args)
             .await
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished",
-                    self.scheme,
-                    Operation::CreateDir,
-                    path
-                );
+                if sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} -> finished",
+                        self.scheme,
+                        Operation::CreateDir,
+                        &self.redact(path)
+                    );
+                }
                 v
             })
             .map_err(|err| {
@@ -246,7 +417,7 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
                         "service={} operation={} path={} -> {}: {err:?}",
                         self.scheme,
                         Operation::CreateDir,
-                        path,
+                        &self.redact(path),
                         self.err_status(&err)
                     )
                 };
@@ -255,14 +426,18 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} range={} -> started",
-            self.scheme,
-            Operation::Read,
-            path,
-            args.range()
-        );
+        let sampled = self.sample();
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} path={} range={} -> started",
+                self.scheme,
+                Operation::Read,
+                &self.redact(path),
+                args.range()
+            );
+        }
 
         let range = args.range();
 
@@ -270,17 +445,26 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
             .read(path, args)
             .await
             .map(|(rp, r)| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} range={} -> got reader",
-                    self.scheme,
-                    Operation::Read,
-                    path,
-                    range
-                );
+                if sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} range={} -> got reader",
+                        self.scheme,
+                        Operation::Read,
+                        &self.redact(path),
+                        range
+                    );
+                }
                 (
                     rp,
-                    LoggingReader::new(self.scheme, Operation::Read, path, r, self.failure_level),
+                    LoggingReader::new(
+                        self.scheme,
+                        Operation::Read,
+                        &self.redact(path),
+                        r,
+                        self.failure_level,
+                        sampled,
+                    ),
                 )
             })
             .map_err(|err| {
@@ -291,7 +475,7 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
                         "service={} operation={} path={} range={} -> {}: {err:?}",
                         self.scheme,
                         Operation::Read,
-                        path,
+                        &self.redact(path),
                         range,
                         self.err_status(&err)
                     )
@@ -301,27 +485,39 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.scheme,
-            Operation::Write,
-            path
-        );
+        let sampled = self.sample();
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} path={} -> started",
+                self.scheme,
+                Operation::Write,
+                &self.redact(path)
+            );
+        }
 
         self.inner
             .write(path, args)
             .await
             .map(|(rp, w)| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> start writing",
+                if sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} -> start writing",
+                        self.scheme,
+                        Operation::Write,
+                        &self.redact(path),
+                    );
+                }
+                let w = LoggingWriter::new(
                     self.scheme,
                     Operation::Write,
-                    path,
+                    &self.redact(path),
+                    w,
+                    self.failure_level,
+                    sampled,
                 );
-                let w =
-                    LoggingWriter::new(self.scheme, Operation::Write, path, w, self.failure_level);
                 (rp, w)
             })
             .map_err(|err| {
@@ -332,7 +528,7 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
                         "service={} operation={} path={} -> {}: {err:?}",
                         self.scheme,
                         Operation::Write,
-                        path,
+                        &self.redact(path),
                         self.err_status(&err)
                     )
                 };
@@ -341,31 +537,38 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.scheme,
-            Operation::Append,
-            path
-        );
+        let sampled = self.sample();
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} path={} -> started",
+                self.scheme,
+                Operation::Append,
+                &self.redact(path)
+            );
+        }
 
         self.inner
             .append(path, args)
             .await
             .map(|(rp, a)| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> start appending",
-                    self.scheme,
-                    Operation::Append,
-                    path,
-                );
+                if sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} -> start appending",
+                        self.scheme,
+                        Operation::Append,
+                        &self.redact(path),
+                    );
+                }
                 let a = LoggingAppender::new(
                     self.scheme,
                     Operation::Append,
-                    path,
+                    &self.redact(path),
                     a,
                     self.failure_level,
+                    sampled,
                 );
                 (rp, a)
             })
@@ -377,7 +580,7 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
                         "service={} operation={} path={} -> {}: {err:?}",
                         self.scheme,
                         Operation::Append,
-                        path,
+                        &self.redact(path),
                         self.err_status(&err)
                     )
                 };
@@ -386,27 +589,33 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} from={} to={} -> started",
-            self.scheme,
-            Operation::Copy,
-            from,
-            to
-        );
+        let sampled = self.sample();
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} from={} to={} -> started",
+                self.scheme,
+                Operation::Copy,
+                &self.redact(from),
+                &self.redact(to)
+            );
+        }
 
         self.inner
             .copy(from, to, args)
             .await
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} from={} to={} -> finished",
-                    self.scheme,
-                    Operation::Copy,
-                    from,
-                    to
-                );
+                if sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} from={} to={} -> finished",
+                        self.scheme,
+                        Operation::Copy,
+                        &self.redact(from),
+                        &self.redact(to)
+                    );
+                }
                 v
             })
             .map_err(|err| {
@@ -417,8 +626,8 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
                         "service={} operation={} from={} to={} -> {}: {err:?}",
                         self.scheme,
                         Operation::Copy,
-                        from,
-                        to,
+                        &self.redact(from),
+                        &self.redact(to),
                         self.err_status(&err)
                     )
                 };
@@ -427,27 +636,33 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} from={} to={} -> started",
-            self.scheme,
-            Operation::Rename,
-            from,
-            to
-        );
+        let sampled = self.sample();
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} from={} to={} -> started",
+                self.scheme,
+                Operation::Rename,
+                &self.redact(from),
+                &self.redact(to)
+            );
+        }
 
         self.inner
             .rename(from, to, args)
             .await
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} from={} to={} -> finished",
-                    self.scheme,
-                    Operation::Rename,
-                    from,
-                    to
-                );
+                if sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} from={} to={} -> finished",
+                        self.scheme,
+                        Operation::Rename,
+                        &self.redact(from),
+                        &self.redact(to)
+                    );
+                }
                 v
             })
             .map_err(|err| {
@@ -458,8 +673,8 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
                         "service={} operation={} from={} to={} -> {}: {err:?}",
                         self.scheme,
                         Operation::Rename,
-                        from,
-                        to,
+                        &self.redact(from),
+                        &self.redact(to),
                         self.err_status(&err)
                     )
                 };
@@ -468,25 +683,31 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.scheme,
-            Operation::Stat,
-            path
-        );
+        let sampled = self.sample();
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} path={} -> started",
+                self.scheme,
+                Operation::Stat,
+                &self.redact(path)
+            );
+        }
 
         self.inner
             .stat(path, args)
             .await
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished: {v:?}",
-                    self.scheme,
-                    Operation::Stat,
-                    path
-                );
+                if sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} -> finished: {v:?}",
+                        self.scheme,
+                        Operation::Stat,
+                        &self.redact(path)
+                    );
+                }
                 v
             })
             .map_err(|err| {
@@ -497,7 +718,7 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
                         "service={} operation={} path={} -> {}: {err:?}",
                         self.scheme,
                         Operation::Stat,
-                        path,
+                        &self.redact(path),
                         self.err_status(&err)
                     );
                 };
@@ -506,25 +727,31 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.scheme,
-            Operation::Delete,
-            path
-        );
+        let sampled = self.sample();
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} path={} -> started",
+                self.scheme,
+                Operation::Delete,
+                &self.redact(path)
+            );
+        }
 
         self.inner
             .delete(path, args.clone())
             .inspect(|v| match v {
                 Ok(_) => {
-                    debug!(
-                        target: LOGGING_TARGET,
-                        "service={} operation={} path={} -> finished",
-                        self.scheme,
-                        Operation::Delete,
-                        path
-                    );
+                    if sampled {
+                        debug!(
+                            target: LOGGING_TARGET,
+                            "service={} operation={} path={} -> finished",
+                            self.scheme,
+                            Operation::Delete,
+                            &self.redact(path)
+                        );
+                    }
                 }
                 Err(err) => {
                     if let Some(lvl) = self.err_level(err) {
@@ -534,7 +761,7 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
                             "service={} operation={} path={} -> {}: {err:?}",
                             self.scheme,
                             Operation::Delete,
-                            path,
+                            &self.redact(path),
                             self.err_status(err)
                         );
                     }
@@ -544,32 +771,39 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.scheme,
-            Operation::List,
-            path
-        );
+        let sampled = self.sample();
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} path={} -> started",
+                self.scheme,
+                Operation::List,
+                &self.redact(path)
+            );
+        }
 
         self.inner
             .list(path, args)
             .map(|v| match v {
                 Ok((rp, v)) => {
-                    debug!(
-                        target: LOGGING_TARGET,
-                        "service={} operation={} path={} -> start listing dir",
-                        self.scheme,
-                        Operation::List,
-                        path
-                    );
+                    if sampled {
+                        debug!(
+                            target: LOGGING_TARGET,
+                            "service={} operation={} path={} -> start listing dir",
+                            self.scheme,
+                            Operation::List,
+                            &self.redact(path)
+                        );
+                    }
                     let streamer = LoggingPager::new(
                         self.scheme,
-                        path,
+                        &self.redact(path),
                         Operation::List,
                         v,
                         self.error_level,
                         self.failure_level,
+                        sampled,
                     );
                     Ok((rp, streamer))
                 }
@@ -581,7 +815,7 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
                             "service={} operation={} path={} -> {}: {err:?}",
                             self.scheme,
                             Operation::List,
-                            path,
+                            &self.redact(path),
                             self.err_status(&err)
                         );
                     }
@@ -592,25 +826,31 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.scheme,
-            Operation::Presign,
-            path
-        );
+        let sampled = self.sample();
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} path={} -> started",
+                self.scheme,
+                Operation::Presign,
+                &self.redact(path)
+            );
+        }
 
         self.inner
             .presign(path, args)
             .await
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished: {v:?}",
-                    self.scheme,
-                    Operation::Presign,
-                    path
-                );
+                if sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} -> finished: {v:?}",
+                        self.scheme,
+                        Operation::Presign,
+                        &self.redact(path)
+                    );
+                }
                 v
             })
             .map_err(|err| {
@@ -621,7 +861,7 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
                         "service={} operation={} path={} -> {}: {err:?}",
                         self.scheme,
                         Operation::Presign,
-                        path,
+                        &self.redact(path),
                         self.err_status(&err)
                     );
                 }
@@ -629,30 +869,124 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
             })
     }
 
-    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
-        let (op, count) = (args.operation()[0].1.operation(), args.operation().len());
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        let sampled = self.sample();
 
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={}-{op} count={count} -> started",
-            self.scheme,
-            Operation::Batch,
-        );
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} path={} -> started",
+                self.scheme,
+                Operation::GetTags,
+                &self.redact(path)
+            );
+        }
 
         self.inner
-            .batch(args)
-            .map_ok(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={}-{op} count={count} -> finished: {}, succeed: {}, failed: {}",
-                    self.scheme,
-                    Operation::Batch,
-                    v.results().len(),
-                    v.results().iter().filter(|(_, v)|v.is_ok()).count(),
-                    v.results().iter().filter(|(_, v)|v.is_err()).count(),
-                );
-                v
-            })
+            .get_tags(path, args)
+            .await
+            .map(|v| {
+                if sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} -> finished",
+                        self.scheme,
+                        Operation::GetTags,
+                        &self.redact(path)
+                    );
+                }
+                v
+            })
+            .map_err(|err| {
+                if let Some(lvl) = self.err_level(&err) {
+                    log!(
+                        target: LOGGING_TARGET,
+                        lvl,
+                        "service={} operation={} path={} -> {}: {err:?}",
+                        self.scheme,
+                        Operation::GetTags,
+                        &self.redact(path),
+                        self.err_status(&err)
+                    );
+                }
+                err
+            })
+    }
+
+    async fn put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        let sampled = self.sample();
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} path={} -> started",
+                self.scheme,
+                Operation::PutTags,
+                &self.redact(path)
+            );
+        }
+
+        self.inner
+            .put_tags(path, args)
+            .await
+            .map(|v| {
+                if sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} -> finished",
+                        self.scheme,
+                        Operation::PutTags,
+                        &self.redact(path)
+                    );
+                }
+                v
+            })
+            .map_err(|err| {
+                if let Some(lvl) = self.err_level(&err) {
+                    log!(
+                        target: LOGGING_TARGET,
+                        lvl,
+                        "service={} operation={} path={} -> {}: {err:?}",
+                        self.scheme,
+                        Operation::PutTags,
+                        &self.redact(path),
+                        self.err_status(&err)
+                    );
+                }
+                err
+            })
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let sampled = self.sample();
+
+        let (op, count) = (args.operation()[0].1.operation(), args.operation().len());
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={}-{op} count={count} -> started",
+                self.scheme,
+                Operation::Batch,
+            );
+        }
+
+        self.inner
+            .batch(args)
+            .map_ok(|v| {
+                if sampled {
+                debug!(
+                    target: LOGGING_TARGET,
+                    "service={} operation={}-{op} count={count} -> finished: {}, succeed: {}, failed: {}",
+                    self.scheme,
+                    Operation::Batch,
+                    v.results().len(),
+                    v.results().iter().filter(|(_, v)|v.is_ok()).count(),
+                    v.results().iter().filter(|(_, v)|v.is_err()).count(),
+                );
+                }
+                v
+            })
             .map_err(|err| {
                 if let Some(lvl) = self.err_level(&err) {
                     log!(
@@ -670,24 +1004,30 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.scheme,
-            Operation::BlockingCreateDir,
-            path
-        );
+        let sampled = self.sample();
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} path={} -> started",
+                self.scheme,
+                Operation::BlockingCreateDir,
+                &self.redact(path)
+            );
+        }
 
         self.inner
             .blocking_create_dir(path, args)
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished",
-                    self.scheme,
-                    Operation::BlockingCreateDir,
-                    path
-                );
+                if sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} -> finished",
+                        self.scheme,
+                        Operation::BlockingCreateDir,
+                        &self.redact(path)
+                    );
+                }
                 v
             })
             .map_err(|err| {
@@ -698,7 +1038,7 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
                         "service={} operation={} path={} -> {}: {err:?}",
                         self.scheme,
                         Operation::BlockingCreateDir,
-                        path,
+                        &self.redact(path),
                         self.err_status(&err)
                     );
                 }
@@ -707,32 +1047,39 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} range={} -> started",
-            self.scheme,
-            Operation::BlockingRead,
-            path,
-            args.range(),
-        );
+        let sampled = self.sample();
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} path={} range={} -> started",
+                self.scheme,
+                Operation::BlockingRead,
+                &self.redact(path),
+                args.range(),
+            );
+        }
 
         self.inner
             .blocking_read(path, args.clone())
             .map(|(rp, r)| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} range={} -> got reader",
-                    self.scheme,
-                    Operation::BlockingRead,
-                    path,
-                    args.range(),
-                );
+                if sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} range={} -> got reader",
+                        self.scheme,
+                        Operation::BlockingRead,
+                        &self.redact(path),
+                        args.range(),
+                    );
+                }
                 let r = LoggingReader::new(
                     self.scheme,
                     Operation::BlockingRead,
-                    path,
+                    &self.redact(path),
                     r,
                     self.failure_level,
+                    sampled,
                 );
                 (rp, r)
             })
@@ -744,7 +1091,7 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
                         "service={} operation={} path={} range={} -> {}: {err:?}",
                         self.scheme,
                         Operation::BlockingRead,
-                        path,
+                        &self.redact(path),
                         args.range(),
                         self.err_status(&err)
                     );
@@ -754,30 +1101,37 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.scheme,
-            Operation::BlockingWrite,
-            path,
-        );
+        let sampled = self.sample();
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} path={} -> started",
+                self.scheme,
+                Operation::BlockingWrite,
+                &self.redact(path),
+            );
+        }
 
         self.inner
             .blocking_write(path, args)
             .map(|(rp, w)| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> start writing",
-                    self.scheme,
-                    Operation::BlockingWrite,
-                    path,
-                );
+                if sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} -> start writing",
+                        self.scheme,
+                        Operation::BlockingWrite,
+                        &self.redact(path),
+                    );
+                }
                 let w = LoggingWriter::new(
                     self.scheme,
                     Operation::BlockingWrite,
-                    path,
+                    &self.redact(path),
                     w,
                     self.failure_level,
+                    sampled,
                 );
                 (rp, w)
             })
@@ -789,7 +1143,7 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
                         "service={} operation={} path={} -> {}: {err:?}",
                         self.scheme,
                         Operation::BlockingWrite,
-                        path,
+                        &self.redact(path),
                         self.err_status(&err)
                     );
                 }
@@ -798,26 +1152,32 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} from={} to={} -> started",
-            self.scheme,
-            Operation::BlockingCopy,
-            from,
-            to,
-        );
+        let sampled = self.sample();
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} from={} to={} -> started",
+                self.scheme,
+                Operation::BlockingCopy,
+                &self.redact(from),
+                &self.redact(to),
+            );
+        }
 
         self.inner
             .blocking_copy(from, to, args)
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} from={} to={} -> finished",
-                    self.scheme,
-                    Operation::BlockingCopy,
-                    from,
-                    to,
-                );
+                if sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} from={} to={} -> finished",
+                        self.scheme,
+                        Operation::BlockingCopy,
+                        &self.redact(from),
+                        &self.redact(to),
+                    );
+                }
                 v
             })
             .map_err(|err| {
@@ -828,8 +1188,8 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
                         "service={} operation={} from={} to={} -> {}: {err:?}",
                         self.scheme,
                         Operation::BlockingCopy,
-                        from,
-                        to,
+                        &self.redact(from),
+                        &self.redact(to),
                         self.err_status(&err)
                     );
                 }
@@ -838,26 +1198,32 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} from={} to={} -> started",
-            self.scheme,
-            Operation::BlockingRename,
-            from,
-            to,
-        );
+        let sampled = self.sample();
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} from={} to={} -> started",
+                self.scheme,
+                Operation::BlockingRename,
+                &self.redact(from),
+                &self.redact(to),
+            );
+        }
 
         self.inner
             .blocking_rename(from, to, args)
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} from={} to={} -> finished",
-                    self.scheme,
-                    Operation::BlockingRename,
-                    from,
-                    to,
-                );
+                if sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} from={} to={} -> finished",
+                        self.scheme,
+                        Operation::BlockingRename,
+                        &self.redact(from),
+                        &self.redact(to),
+                    );
+                }
                 v
             })
             .map_err(|err| {
@@ -868,8 +1234,8 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
                         "service={} operation={} from={} to={} -> {}: {err:?}",
                         self.scheme,
                         Operation::BlockingRename,
-                        from,
-                        to,
+                        &self.redact(from),
+                        &self.redact(to),
                         self.err_status(&err)
                     );
                 }
@@ -878,24 +1244,30 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.scheme,
-            Operation::BlockingStat,
-            path
-        );
+        let sampled = self.sample();
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} path={} -> started",
+                self.scheme,
+                Operation::BlockingStat,
+                &self.redact(path)
+            );
+        }
 
         self.inner
             .blocking_stat(path, args)
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished: {v:?}",
-                    self.scheme,
-                    Operation::BlockingStat,
-                    path
-                );
+                if sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} -> finished: {v:?}",
+                        self.scheme,
+                        Operation::BlockingStat,
+                        &self.redact(path)
+                    );
+                }
                 v
             })
             .map_err(|err| {
@@ -906,7 +1278,7 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
                         "service={} operation={} path={} -> {}: {err:?}",
                         self.scheme,
                         Operation::BlockingStat,
-                        path,
+                        &self.redact(path),
                         self.err_status(&err)
                     );
                 }
@@ -915,24 +1287,30 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.scheme,
-            Operation::BlockingDelete,
-            path
-        );
+        let sampled = self.sample();
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} path={} -> started",
+                self.scheme,
+                Operation::BlockingDelete,
+                &self.redact(path)
+            );
+        }
 
         self.inner
             .blocking_delete(path, args)
             .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished",
-                    self.scheme,
-                    Operation::BlockingDelete,
-                    path
-                );
+                if sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} -> finished",
+                        self.scheme,
+                        Operation::BlockingDelete,
+                        &self.redact(path)
+                    );
+                }
                 v
             })
             .map_err(|err| {
@@ -943,7 +1321,7 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
                         "service={} operation={} path={} -> {}: {err:?}",
                         self.scheme,
                         Operation::BlockingDelete,
-                        path,
+                        &self.redact(path),
                         self.err_status(&err)
                     );
                 }
@@ -952,31 +1330,38 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.scheme,
-            Operation::BlockingList,
-            path
-        );
+        let sampled = self.sample();
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} path={} -> started",
+                self.scheme,
+                Operation::BlockingList,
+                &self.redact(path)
+            );
+        }
 
         self.inner
             .blocking_list(path, args)
             .map(|(rp, v)| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> got dir",
-                    self.scheme,
-                    Operation::BlockingList,
-                    path
-                );
+                if sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} -> got dir",
+                        self.scheme,
+                        Operation::BlockingList,
+                        &self.redact(path)
+                    );
+                }
                 let li = LoggingPager::new(
                     self.scheme,
-                    path,
+                    &self.redact(path),
                     Operation::BlockingList,
                     v,
                     self.error_level,
                     self.failure_level,
+                    sampled,
                 );
                 (rp, li)
             })
@@ -988,7 +1373,93 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
                         "service={} operation={} path={} -> {}: {err:?}",
                         self.scheme,
                         Operation::BlockingList,
-                        path,
+                        &self.redact(path),
+                        self.err_status(&err)
+                    );
+                }
+                err
+            })
+    }
+
+    fn blocking_get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        let sampled = self.sample();
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} path={} -> started",
+                self.scheme,
+                Operation::BlockingGetTags,
+                &self.redact(path)
+            );
+        }
+
+        self.inner
+            .blocking_get_tags(path, args)
+            .map(|v| {
+                if sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} -> finished",
+                        self.scheme,
+                        Operation::BlockingGetTags,
+                        &self.redact(path)
+                    );
+                }
+                v
+            })
+            .map_err(|err| {
+                if let Some(lvl) = self.err_level(&err) {
+                    log!(
+                        target: LOGGING_TARGET,
+                        lvl,
+                        "service={} operation={} path={} -> {}: {err:?}",
+                        self.scheme,
+                        Operation::BlockingGetTags,
+                        &self.redact(path),
+                        self.err_status(&err)
+                    );
+                }
+                err
+            })
+    }
+
+    fn blocking_put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        let sampled = self.sample();
+
+        if sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} path={} -> started",
+                self.scheme,
+                Operation::BlockingPutTags,
+                &self.redact(path)
+            );
+        }
+
+        self.inner
+            .blocking_put_tags(path, args)
+            .map(|v| {
+                if sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} -> finished",
+                        self.scheme,
+                        Operation::BlockingPutTags,
+                        &self.redact(path)
+                    );
+                }
+                v
+            })
+            .map_err(|err| {
+                if let Some(lvl) = self.err_level(&err) {
+                    log!(
+                        target: LOGGING_TARGET,
+                        lvl,
+                        "service={} operation={} path={} -> {}: {err:?}",
+                        self.scheme,
+                        Operation::BlockingPutTags,
+                        &self.redact(path),
                         self.err_status(&err)
                     );
                 }
@@ -1005,6 +1476,7 @@ pub struct LoggingReader<R> {
 
     read: u64,
     failure_level: Option<Level>,
+    sampled: bool,
 
     inner: R,
 }
@@ -1016,6 +1488,7 @@ impl<R> LoggingReader<R> {
         path: &str,
         reader: R,
         failure_level: Option<Level>,
+        sampled: bool,
     ) -> Self {
         Self {
             scheme,
@@ -1026,38 +1499,43 @@ impl<R> LoggingReader<R> {
 
             inner: reader,
             failure_level,
+            sampled,
         }
     }
 }
 
 impl<R> Drop for LoggingReader<R> {
     fn drop(&mut self) {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} read={} -> data read finished",
-            self.scheme,
-            self.op,
-            self.path,
-            self.read
-        );
+        if self.sampled {
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} path={} read={} -> data read finished",
+                self.scheme,
+                self.op,
+                self.path,
+                self.read
+            );
+        }
     }
 }
 
-impl<R: oio::Read> oio::Read for LoggingReader<R> {
-    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
-        match self.inner.poll_read(cx, buf) {
-            Poll::Ready(res) => match res {
-                Ok(n) => {
-                    self.read += n as u64;
-                    trace!(
-                        target: LOGGING_TARGET,
-                        "service={} operation={} path={} read={} -> data read {}B ",
-                        self.scheme,
-                        ReadOperation::Read,
-                        self.path,
-                        self.read,
-                        n
-                    );
+impl<R: oio::Read> oio::Read for LoggingReader<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        match self.inner.poll_read(cx, buf) {
+            Poll::Ready(res) => match res {
+                Ok(n) => {
+                    self.read += n as u64;
+                    if self.sampled {
+                        trace!(
+                            target: LOGGING_TARGET,
+                            "service={} operation={} path={} read={} -> data read {}B ",
+                            self.scheme,
+                            ReadOperation::Read,
+                            self.path,
+                            self.read,
+                            n
+                        );
+                    }
                     Poll::Ready(Ok(n))
                 }
                 Err(err) => {
@@ -1076,14 +1554,16 @@ impl<R: oio::Read> oio::Read for LoggingReader<R> {
                 }
             },
             Poll::Pending => {
-                trace!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} read={} -> data read pending",
-                    self.scheme,
-                    ReadOperation::Read,
-                    self.path,
-                    self.read
-                );
+                if self.sampled {
+                    trace!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} read={} -> data read pending",
+                        self.scheme,
+                        ReadOperation::Read,
+                        self.path,
+                        self.read
+                    );
+                }
                 Poll::Pending
             }
         }
@@ -1093,14 +1573,16 @@ impl<R: oio::Read> oio::Read for LoggingReader<R> {
         match self.inner.poll_seek(cx, pos) {
             Poll::Ready(res) => match res {
                 Ok(n) => {
-                    trace!(
-                        target: LOGGING_TARGET,
-                        "service={} operation={} path={} read={} -> data seek to offset {n}",
-                        self.scheme,
-                        ReadOperation::Seek,
-                        self.path,
-                        self.read,
-                    );
+                    if self.sampled {
+                        trace!(
+                            target: LOGGING_TARGET,
+                            "service={} operation={} path={} read={} -> data seek to offset {n}",
+                            self.scheme,
+                            ReadOperation::Seek,
+                            self.path,
+                            self.read,
+                        );
+                    }
                     Poll::Ready(Ok(n))
                 }
                 Err(err) => {
@@ -1119,14 +1601,16 @@ impl<R: oio::Read> oio::Read for LoggingReader<R> {
                 }
             },
             Poll::Pending => {
-                trace!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} read={} -> data seek pending",
-                    self.scheme,
-                    ReadOperation::Seek,
-                    self.path,
-                    self.read
-                );
+                if self.sampled {
+                    trace!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} read={} -> data seek pending",
+                        self.scheme,
+                        ReadOperation::Seek,
+                        self.path,
+                        self.read
+                    );
+                }
                 Poll::Pending
             }
         }
@@ -1137,15 +1621,17 @@ impl<R: oio::Read> oio::Read for LoggingReader<R> {
             Poll::Ready(res) => match res {
                 Some(Ok(bs)) => {
                     self.read += bs.len() as u64;
-                    trace!(
-                        target: LOGGING_TARGET,
-                        "service={} operation={} path={} read={} -> data read {}B",
-                        self.scheme,
-                        ReadOperation::Next,
-                        self.path,
-                        self.read,
-                        bs.len()
-                    );
+                    if self.sampled {
+                        trace!(
+                            target: LOGGING_TARGET,
+                            "service={} operation={} path={} read={} -> data read {}B",
+                            self.scheme,
+                            ReadOperation::Next,
+                            self.path,
+                            self.read,
+                            bs.len()
+                        );
+                    }
                     Poll::Ready(Some(Ok(bs)))
                 }
                 Some(Err(err)) => {
@@ -1165,14 +1651,16 @@ impl<R: oio::Read> oio::Read for LoggingReader<R> {
                 None => Poll::Ready(None),
             },
             Poll::Pending => {
-                trace!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} read={} -> data read pending",
-                    self.scheme,
-                    ReadOperation::Next,
-                    self.path,
-                    self.read
-                );
+                if self.sampled {
+                    trace!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} read={} -> data read pending",
+                        self.scheme,
+                        ReadOperation::Next,
+                        self.path,
+                        self.read
+                    );
+                }
                 Poll::Pending
             }
         }
@@ -1184,15 +1672,17 @@ impl<R: oio::BlockingRead> oio::BlockingRead for LoggingReader<R> {
         match self.inner.read(buf) {
             Ok(n) => {
                 self.read += n as u64;
-                trace!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} read={} -> data read {}B",
-                    self.scheme,
-                    ReadOperation::BlockingRead,
-                    self.path,
-                    self.read,
-                    n
-                );
+                if self.sampled {
+                    trace!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} read={} -> data read {}B",
+                        self.scheme,
+                        ReadOperation::BlockingRead,
+                        self.path,
+                        self.read,
+                        n
+                    );
+                }
                 Ok(n)
             }
             Err(err) => {
@@ -1216,14 +1706,16 @@ impl<R: oio::BlockingRead> oio::BlockingRead for LoggingReader<R> {
     fn seek(&mut self, pos: io::SeekFrom) -> Result<u64> {
         match self.inner.seek(pos) {
             Ok(n) => {
-                trace!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} read={} -> data seek to offset {n}",
-                    self.scheme,
-                    ReadOperation::BlockingSeek,
-                    self.path,
-                    self.read,
-                );
+                if self.sampled {
+                    trace!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} read={} -> data seek to offset {n}",
+                        self.scheme,
+                        ReadOperation::BlockingSeek,
+                        self.path,
+                        self.read,
+                    );
+                }
                 Ok(n)
             }
             Err(err) => {
@@ -1247,15 +1739,17 @@ impl<R: oio::BlockingRead> oio::BlockingRead for LoggingReader<R> {
         match self.inner.next() {
             Some(Ok(bs)) => {
                 self.read += bs.len() as u64;
-                trace!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} read={} -> data read {}B",
-                    self.scheme,
-                    ReadOperation::BlockingNext,
-                    self.path,
-                    self.read,
-                    bs.len()
-                );
+                if self.sampled {
+                    trace!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} read={} -> data read {}B",
+                        self.scheme,
+                        ReadOperation::BlockingNext,
+                        self.path,
+                        self.read,
+                        bs.len()
+                    );
+                }
                 Some(Ok(bs))
             }
             Some(Err(err)) => {
@@ -1284,6 +1778,7 @@ pub struct LoggingWriter<W> {
 
     written: u64,
     failure_level: Option<Level>,
+    sampled: bool,
 
     inner: W,
 }
@@ -1295,6 +1790,7 @@ impl<W> LoggingWriter<W> {
         path: &str,
         writer: W,
         failure_level: Option<Level>,
+        sampled: bool,
     ) -> Self {
         Self {
             scheme,
@@ -1304,6 +1800,7 @@ impl<W> LoggingWriter<W> {
             written: 0,
             inner: writer,
             failure_level,
+            sampled,
         }
     }
 }
@@ -1315,15 +1812,17 @@ impl<W: oio::Write> oio::Write for LoggingWriter<W> {
         match self.inner.write(bs).await {
             Ok(_) => {
                 self.written += size as u64;
-                trace!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} written={} -> data write {}B",
-                    self.scheme,
-                    WriteOperation::Write,
-                    self.path,
-                    self.written,
-                    size
-                );
+                if self.sampled {
+                    trace!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} written={} -> data write {}B",
+                        self.scheme,
+                        WriteOperation::Write,
+                        self.path,
+                        self.written,
+                        size
+                    );
+                }
                 Ok(())
             }
             Err(err) => {
@@ -1347,15 +1846,17 @@ impl<W: oio::Write> oio::Write for LoggingWriter<W> {
         match self.inner.sink(size, s).await {
             Ok(_) => {
                 self.written += size;
-                trace!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} written={} -> data sink {}B",
-                    self.scheme,
-                    WriteOperation::Sink,
-                    self.path,
-                    self.written,
-                    size
-                );
+                if self.sampled {
+                    trace!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} written={} -> data sink {}B",
+                        self.scheme,
+                        WriteOperation::Sink,
+                        self.path,
+                        self.written,
+                        size
+                    );
+                }
                 Ok(())
             }
             Err(err) => {
@@ -1378,14 +1879,16 @@ impl<W: oio::Write> oio::Write for LoggingWriter<W> {
     async fn abort(&mut self) -> Result<()> {
         match self.inner.abort().await {
             Ok(_) => {
-                trace!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} written={} -> abort writer",
-                    self.scheme,
-                    WriteOperation::Abort,
-                    self.path,
-                    self.written,
-                );
+                if self.sampled {
+                    trace!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} written={} -> abort writer",
+                        self.scheme,
+                        WriteOperation::Abort,
+                        self.path,
+                        self.written,
+                    );
+                }
                 Ok(())
             }
             Err(err) => {
@@ -1408,14 +1911,16 @@ impl<W: oio::Write> oio::Write for LoggingWriter<W> {
     async fn close(&mut self) -> Result<()> {
         match self.inner.close().await {
             Ok(_) => {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} written={} -> data written finished",
-                    self.scheme,
-                    self.op,
-                    self.path,
-                    self.written
-                );
+                if self.sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} written={} -> data written finished",
+                        self.scheme,
+                        self.op,
+                        self.path,
+                        self.written
+                    );
+                }
                 Ok(())
             }
             Err(err) => {
@@ -1434,6 +1939,10 @@ impl<W: oio::Write> oio::Write for LoggingWriter<W> {
             }
         }
     }
+
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        self.inner.checkpoint()
+    }
 }
 
 impl<W: oio::BlockingWrite> oio::BlockingWrite for LoggingWriter<W> {
@@ -1442,15 +1951,17 @@ impl<W: oio::BlockingWrite> oio::BlockingWrite for LoggingWriter<W> {
         match self.inner.write(bs) {
             Ok(_) => {
                 self.written += size as u64;
-                trace!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} written={} -> data write {}B",
-                    self.scheme,
-                    WriteOperation::BlockingWrite,
-                    self.path,
-                    self.written,
-                    size
-                );
+                if self.sampled {
+                    trace!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} written={} -> data write {}B",
+                        self.scheme,
+                        WriteOperation::BlockingWrite,
+                        self.path,
+                        self.written,
+                        size
+                    );
+                }
                 Ok(())
             }
             Err(err) => {
@@ -1473,14 +1984,16 @@ impl<W: oio::BlockingWrite> oio::BlockingWrite for LoggingWriter<W> {
     fn close(&mut self) -> Result<()> {
         match self.inner.close() {
             Ok(_) => {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} written={} -> data written finished",
-                    self.scheme,
-                    self.op,
-                    self.path,
-                    self.written
-                );
+                if self.sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} written={} -> data written finished",
+                        self.scheme,
+                        self.op,
+                        self.path,
+                        self.written
+                    );
+                }
                 Ok(())
             }
             Err(err) => {
@@ -1507,6 +2020,7 @@ pub struct LoggingAppender<A> {
     path: String,
 
     failure_level: Option<Level>,
+    sampled: bool,
 
     inner: A,
 }
@@ -1518,6 +2032,7 @@ impl<A> LoggingAppender<A> {
         path: &str,
         appender: A,
         failure_level: Option<Level>,
+        sampled: bool,
     ) -> Self {
         Self {
             scheme,
@@ -1525,6 +2040,7 @@ impl<A> LoggingAppender<A> {
             path: path.to_string(),
 
             failure_level,
+            sampled,
 
             inner: appender,
         }
@@ -1538,14 +2054,16 @@ impl<A: oio::Append> oio::Append for LoggingAppender<A> {
 
         match self.inner.append(bs).await {
             Ok(_) => {
-                trace!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> data append {}B",
-                    self.scheme,
-                    self.op,
-                    self.path,
-                    len
-                );
+                if self.sampled {
+                    trace!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} -> data append {}B",
+                        self.scheme,
+                        self.op,
+                        self.path,
+                        len
+                    );
+                }
                 Ok(())
             }
             Err(err) => {
@@ -1567,13 +2085,15 @@ impl<A: oio::Append> oio::Append for LoggingAppender<A> {
     async fn close(&mut self) -> Result<()> {
         match self.inner.close().await {
             Ok(_) => {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> data appended finished",
-                    self.scheme,
-                    self.op,
-                    self.path,
-                );
+                if self.sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} -> data appended finished",
+                        self.scheme,
+                        self.op,
+                        self.path,
+                    );
+                }
                 Ok(())
             }
             Err(err) => {
@@ -1602,6 +2122,7 @@ pub struct LoggingPager<P> {
     inner: P,
     error_level: Option<Level>,
     failure_level: Option<Level>,
+    sampled: bool,
 }
 
 impl<P> LoggingPager<P> {
@@ -1612,6 +2133,7 @@ impl<P> LoggingPager<P> {
         inner: P,
         error_level: Option<Level>,
         failure_level: Option<Level>,
+        sampled: bool,
     ) -> Self {
         Self {
             scheme,
@@ -1621,12 +2143,17 @@ impl<P> LoggingPager<P> {
             inner,
             error_level,
             failure_level,
+            sampled,
         }
     }
 }
 
 impl<P> Drop for LoggingPager<P> {
     fn drop(&mut self) {
+        if !self.sampled {
+            return;
+        }
+
         if self.finished {
             debug!(
                 target: LOGGING_TARGET,
@@ -1674,20 +2201,24 @@ impl<P: oio::Page> oio::Page for LoggingPager<P> {
 
         match &res {
             Ok(Some(des)) => {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> listed {} entries",
-                    self.scheme,
-                    self.op,
-                    self.path,
-                    des.len(),
-                );
+                if self.sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} -> listed {} entries",
+                        self.scheme,
+                        self.op,
+                        self.path,
+                        des.len(),
+                    );
+                }
             }
             Ok(None) => {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished", self.scheme, self.op, self.path
-                );
+                if self.sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} -> finished", self.scheme, self.op, self.path
+                    );
+                }
                 self.finished = true;
             }
             Err(err) => {
@@ -1707,6 +2238,10 @@ impl<P: oio::Page> oio::Page for LoggingPager<P> {
 
         res
     }
+
+    fn continuation_token(&self) -> Option<String> {
+        self.inner.continuation_token()
+    }
 }
 
 impl<P: oio::BlockingPage> oio::BlockingPage for LoggingPager<P> {
@@ -1715,20 +2250,24 @@ impl<P: oio::BlockingPage> oio::BlockingPage for LoggingPager<P> {
 
         match &res {
             Ok(Some(des)) => {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> got {} entries",
-                    self.scheme,
-                    self.op,
-                    self.path,
-                    des.len(),
-                );
+                if self.sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} -> got {} entries",
+                        self.scheme,
+                        self.op,
+                        self.path,
+                        des.len(),
+                    );
+                }
             }
             Ok(None) => {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished", self.scheme, self.op, self.path
-                );
+                if self.sampled {
+                    debug!(
+                        target: LOGGING_TARGET,
+                        "service={} operation={} path={} -> finished", self.scheme, self.op, self.path
+                    );
+                }
                 self.finished = true;
             }
             Err(err) => {
@@ -1749,3 +2288,55 @@ impl<P: oio::BlockingPage> oio::BlockingPage for LoggingPager<P> {
         res
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[test]
+    fn test_sampler() {
+        let sampler = Sampler::new(0.0);
+        assert!((0..1000).all(|_| !sampler.sample()));
+
+        let sampler = Sampler::new(1.0);
+        assert!((0..1000).all(|_| sampler.sample()));
+
+        let sampler = Sampler::new(0.1);
+        let sampled = (0..100_000).filter(|_| sampler.sample()).count();
+        assert!(
+            (8_000..12_000).contains(&sampled),
+            "sampled {sampled} of 100000 must be around 10%"
+        );
+    }
+
+    #[test]
+    fn test_with_sample_ratio() {
+        assert_eq!(LoggingLayer::default().sample_ratio, 1.0);
+        assert_eq!(
+            LoggingLayer::default().with_sample_ratio(2.0).sample_ratio,
+            1.0
+        );
+        assert_eq!(
+            LoggingLayer::default().with_sample_ratio(-1.0).sample_ratio,
+            0.0
+        );
+        assert_eq!(
+            LoggingLayer::default()
+                .with_sample_ratio(f64::NAN)
+                .sample_ratio,
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_with_path_redactor() {
+        let acc = LoggingLayer::default().layer(Memory::default().build().unwrap());
+        assert_eq!(acc.redact("secret/a.txt"), "secret/a.txt");
+
+        let acc = LoggingLayer::default()
+            .with_path_redactor(|p| p.replace("secret", "[REDACTED]"))
+            .layer(Memory::default().build().unwrap());
+        assert_eq!(acc.redact("secret/a.txt"), "[REDACTED]/a.txt");
+    }
+}
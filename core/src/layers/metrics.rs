@@ -888,6 +888,10 @@ impl<R: oio::Write> oio::Write for MetricWrapper<R> {
             err
         })
     }
+
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        self.inner.checkpoint()
+    }
 }
 
 impl<R: oio::BlockingWrite> oio::BlockingWrite for MetricWrapper<R> {
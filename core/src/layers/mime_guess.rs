@@ -0,0 +1,199 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::raw::*;
+use crate::*;
+
+/// Add content type inferred from path's extension to writes without an
+/// explicit content type.
+///
+/// Content types are looked up from the mime database provided by
+/// [`mime_guess`](https://docs.rs/mime_guess). Users can add or override
+/// mappings via [`MimeGuessLayer::with_mapping`].
+///
+/// # Notes
+///
+/// - Content type set by `write_with(path).content_type(..)` will never
+///   be changed.
+/// - Paths without a known extension will be written as is.
+///
+/// # Examples
+///
+/// ```no_run
+/// use anyhow::Result;
+/// use opendal::layers::MimeGuessLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(MimeGuessLayer::default().with_mapping("wasm", "application/wasm"))
+///     .finish();
+/// ```
+#[derive(Default, Debug, Clone)]
+pub struct MimeGuessLayer {
+    mappings: Arc<HashMap<String, String>>,
+}
+
+impl MimeGuessLayer {
+    /// Map files with given extension (without the leading `.`) to given
+    /// content type, overriding the mime database.
+    pub fn with_mapping(mut self, extension: &str, content_type: &str) -> Self {
+        Arc::make_mut(&mut self.mappings).insert(
+            extension.trim_start_matches('.').to_ascii_lowercase(),
+            content_type.to_string(),
+        );
+        self
+    }
+
+    /// Guess content type for given path.
+    pub fn guess(&self, path: &str) -> Option<String> {
+        let name = get_basename(path);
+        let (_, ext) = name.rsplit_once('.')?;
+        if ext.is_empty() {
+            return None;
+        }
+
+        if let Some(v) = self.mappings.get(&ext.to_ascii_lowercase()) {
+            return Some(v.clone());
+        }
+        mime_guess::from_ext(ext).first_raw().map(|v| v.to_string())
+    }
+
+    fn guess_write(&self, path: &str, args: OpWrite) -> OpWrite {
+        if args.content_type().is_some() {
+            return args;
+        }
+
+        match self.guess(path) {
+            Some(v) => args.with_content_type(&v),
+            None => args,
+        }
+    }
+
+    fn guess_append(&self, path: &str, args: OpAppend) -> OpAppend {
+        if args.content_type().is_some() {
+            return args;
+        }
+
+        match self.guess(path) {
+            Some(v) => args.with_content_type(&v),
+            None => args,
+        }
+    }
+}
+
+impl<A: Accessor> Layer<A> for MimeGuessLayer {
+    type LayeredAccessor = MimeGuessAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        MimeGuessAccessor {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MimeGuessAccessor<A: Accessor> {
+    inner: A,
+    layer: MimeGuessLayer,
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for MimeGuessAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Appender = A::Appender;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let args = self.layer.guess_write(path, args);
+        self.inner.write(path, args).await
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        let args = self.layer.guess_append(path, args);
+        self.inner.append(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner.list(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let args = self.layer.guess_write(path, args);
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guess() {
+        let layer = MimeGuessLayer::default().with_mapping(".wasm", "application/x-test");
+
+        assert_eq!(layer.guess("a/b.png").as_deref(), Some("image/png"));
+        assert_eq!(layer.guess("a/b.HTML").as_deref(), Some("text/html"));
+        assert_eq!(
+            layer.guess("a/b.wasm").as_deref(),
+            Some("application/x-test")
+        );
+        assert_eq!(layer.guess("a/b"), None);
+        assert_eq!(layer.guess("a.d/b"), None);
+        assert_eq!(layer.guess("a/b."), None);
+    }
+
+    #[test]
+    fn test_explicit_content_type_is_kept() {
+        let layer = MimeGuessLayer::default();
+
+        let args = layer.guess_write("index.html", OpWrite::new());
+        assert_eq!(args.content_type(), Some("text/html"));
+
+        let args = layer.guess_write("style.css", OpWrite::new().with_content_type("text/plain"));
+        assert_eq!(args.content_type(), Some("text/plain"));
+    }
+}
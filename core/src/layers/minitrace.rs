@@ -388,6 +388,10 @@ impl<R: oio::Write> oio::Write for MinitraceWrapper<R> {
             ))
             .await
     }
+
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        self.inner.checkpoint()
+    }
 }
 
 impl<R: oio::BlockingWrite> oio::BlockingWrite for MinitraceWrapper<R> {
@@ -438,6 +442,10 @@ impl<R: oio::Page> oio::Page for MinitraceWrapper<R> {
             ))
             .await
     }
+
+    fn continuation_token(&self) -> Option<String> {
+        self.inner.continuation_token()
+    }
 }
 
 impl<R: oio::BlockingPage> oio::BlockingPage for MinitraceWrapper<R> {
@@ -17,18 +17,46 @@
 
 //! `Layer` is the mechanism to intercept operations.
 
+mod audit;
+pub use audit::AuditLayer;
+pub use audit::AuditRecord;
+
 mod concurrent_limit;
 pub use concurrent_limit::ConcurrentLimitLayer;
 
+mod dedup;
+pub use dedup::DedupLayer;
+
+mod dry_run;
+pub use dry_run::DryRunAction;
+pub use dry_run::DryRunLayer;
+
 mod immutable_index;
 pub use immutable_index::ImmutableIndexLayer;
 
+mod intercept;
+pub use intercept::InterceptLayer;
+pub use intercept::Interceptor;
+
 mod logging;
 pub use logging::LoggingLayer;
 
+mod normalize_root;
+pub use normalize_root::NormalizeRootLayer;
+
+mod replica;
+pub use replica::ReplicaLayer;
+pub use replica::ReplicaWriteMode;
+
+mod stat_cache;
+pub use stat_cache::StatCacheLayer;
+
 mod timeout;
 pub use timeout::TimeoutLayer;
 
+mod versioned_cache;
+pub use versioned_cache::VersionedCacheLayer;
+
 #[cfg(feature = "layers-chaos")]
 mod chaos;
 #[cfg(feature = "layers-chaos")]
@@ -84,3 +112,13 @@ mod throttle;
 pub use self::oteltrace::OtelTraceLayer;
 #[cfg(feature = "layers-throttle")]
 pub use self::throttle::ThrottleLayer;
+
+#[cfg(feature = "layers-redact")]
+mod redact;
+#[cfg(feature = "layers-redact")]
+pub use self::redact::RedactLayer;
+
+#[cfg(feature = "layers-mime-guess")]
+mod mime_guess;
+#[cfg(feature = "layers-mime-guess")]
+pub use self::mime_guess::MimeGuessLayer;
@@ -0,0 +1,422 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::raw::*;
+use crate::*;
+
+/// Sandbox all operations inside a fixed root.
+///
+/// # Notes
+///
+/// NormalizeRootLayer will prepend `root` to every input path, and reject
+/// any path that would resolve outside of it with `PermissionDenied`:
+///
+/// - Leading `/` will be trimmed, so absolute paths are treated as relative
+///   to `root`: `/etc/passwd` => `<root>/etc/passwd`
+/// - `.` segments will be removed and `..` segments will remove the previous
+///   segment: `abc/../def` => `<root>/def`
+/// - `..` that goes above `root` will be rejected: `abc/../../def`
+/// - Segments like `abc\..` will be rejected too, since `\` is a path
+///   separator on windows.
+///
+/// Paths returned by `list` will be relative to `root` again, so callers
+/// can't observe anything outside of it.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::NormalizeRootLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(NormalizeRootLayer::new("tenants/alice"))
+///     .finish();
+/// ```
+#[derive(Debug, Clone)]
+pub struct NormalizeRootLayer {
+    root: Arc<String>,
+}
+
+impl NormalizeRootLayer {
+    /// Create a new NormalizeRootLayer with given root.
+    ///
+    /// The root itself is trusted, `..` in it will be resolved directly.
+    pub fn new(root: &str) -> Self {
+        let root = resolve_dot_segments(&normalize_path(root));
+        let root = match root.as_str() {
+            "/" => String::new(),
+            v if v.ends_with('/') => root,
+            _ => format!("{root}/"),
+        };
+
+        Self {
+            root: Arc::new(root),
+        }
+    }
+}
+
+impl<A: Accessor> Layer<A> for NormalizeRootLayer {
+    type LayeredAccessor = NormalizeRootAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        NormalizeRootAccessor {
+            inner,
+            root: self.root.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NormalizeRootAccessor<A: Accessor> {
+    inner: A,
+    root: Arc<String>,
+}
+
+impl<A: Accessor> NormalizeRootAccessor<A> {
+    /// Resolve input path into the path with root.
+    fn resolve(&self, path: &str, op: Operation) -> Result<String> {
+        let escape_error = || {
+            Error::new(ErrorKind::PermissionDenied, "path escapes the root")
+                .with_operation(op)
+                .with_context("path", path)
+        };
+
+        let mut segs: Vec<&str> = Vec::new();
+        let mut is_dir = path.ends_with('/');
+        for seg in path.split('/').filter(|v| !v.is_empty()) {
+            if seg.contains('\\') && seg.split('\\').any(|v| v == "..") {
+                return Err(escape_error());
+            }
+
+            match seg {
+                "." => is_dir = true,
+                ".." => {
+                    if segs.pop().is_none() {
+                        return Err(escape_error());
+                    }
+                    is_dir = true;
+                }
+                v => {
+                    segs.push(v);
+                    is_dir = path.ends_with('/');
+                }
+            }
+        }
+
+        if segs.is_empty() {
+            return if self.root.is_empty() {
+                Ok("/".to_string())
+            } else {
+                Ok(self.root.to_string())
+            };
+        }
+
+        let mut p = format!("{}{}", self.root, segs.join("/"));
+        if is_dir {
+            p.push('/');
+        }
+        Ok(p)
+    }
+
+    fn resolve_list_args(&self, args: OpList) -> Result<OpList> {
+        match args.start_after() {
+            Some(v) => {
+                let start_after = self.resolve(v, Operation::List)?;
+                Ok(args.with_start_after(&start_after))
+            }
+            None => Ok(args),
+        }
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for NormalizeRootAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Appender = A::Appender;
+    type Pager = NormalizeRootPager<A::Pager>;
+    type BlockingPager = NormalizeRootPager<A::BlockingPager>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let path = self.resolve(path, Operation::CreateDir)?;
+        self.inner.create_dir(&path, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let path = self.resolve(path, Operation::Read)?;
+        self.inner.read(&path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let path = self.resolve(path, Operation::Write)?;
+        self.inner.write(&path, args).await
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        let path = self.resolve(path, Operation::Append)?;
+        self.inner.append(&path, args).await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let from = self.resolve(from, Operation::Copy)?;
+        let to = self.resolve(to, Operation::Copy)?;
+        self.inner.copy(&from, &to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let from = self.resolve(from, Operation::Rename)?;
+        let to = self.resolve(to, Operation::Rename)?;
+        self.inner.rename(&from, &to, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let path = self.resolve(path, Operation::Stat)?;
+        self.inner.stat(&path, args).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let path = self.resolve(path, Operation::Delete)?;
+        self.inner.delete(&path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        let path = self.resolve(path, Operation::List)?;
+        let args = self.resolve_list_args(args)?;
+        self.inner
+            .list(&path, args)
+            .await
+            .map(|(rp, p)| (rp, NormalizeRootPager::new(p, self.root.clone())))
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let ops = args
+            .into_operation()
+            .into_iter()
+            .map(|(path, op)| Ok((self.resolve(&path, Operation::Batch)?, op)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let rp = self.inner.batch(OpBatch::new(ops)).await?;
+        let results = rp
+            .into_results()
+            .into_iter()
+            .map(|(path, res)| (relativize(&self.root, &path), res))
+            .collect();
+        Ok(RpBatch::new(results))
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        let path = self.resolve(path, Operation::Presign)?;
+        self.inner.presign(&path, args).await
+    }
+
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        let path = self.resolve(path, Operation::GetTags)?;
+        self.inner.get_tags(&path, args).await
+    }
+
+    async fn put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        let path = self.resolve(path, Operation::PutTags)?;
+        self.inner.put_tags(&path, args).await
+    }
+
+    async fn get_object_lock(&self, path: &str, args: OpGetObjectLock) -> Result<RpGetObjectLock> {
+        let path = self.resolve(path, Operation::GetObjectLock)?;
+        self.inner.get_object_lock(&path, args).await
+    }
+
+    async fn put_object_lock(&self, path: &str, args: OpPutObjectLock) -> Result<RpPutObjectLock> {
+        let path = self.resolve(path, Operation::PutObjectLock)?;
+        self.inner.put_object_lock(&path, args).await
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let path = self.resolve(path, Operation::BlockingCreateDir)?;
+        self.inner.blocking_create_dir(&path, args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let path = self.resolve(path, Operation::BlockingRead)?;
+        self.inner.blocking_read(&path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let path = self.resolve(path, Operation::BlockingWrite)?;
+        self.inner.blocking_write(&path, args)
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let from = self.resolve(from, Operation::BlockingCopy)?;
+        let to = self.resolve(to, Operation::BlockingCopy)?;
+        self.inner.blocking_copy(&from, &to, args)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let from = self.resolve(from, Operation::BlockingRename)?;
+        let to = self.resolve(to, Operation::BlockingRename)?;
+        self.inner.blocking_rename(&from, &to, args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let path = self.resolve(path, Operation::BlockingStat)?;
+        self.inner.blocking_stat(&path, args)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let path = self.resolve(path, Operation::BlockingDelete)?;
+        self.inner.blocking_delete(&path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        let path = self.resolve(path, Operation::BlockingList)?;
+        let args = self.resolve_list_args(args)?;
+        self.inner
+            .blocking_list(&path, args)
+            .map(|(rp, p)| (rp, NormalizeRootPager::new(p, self.root.clone())))
+    }
+
+    fn blocking_get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        let path = self.resolve(path, Operation::BlockingGetTags)?;
+        self.inner.blocking_get_tags(&path, args)
+    }
+
+    fn blocking_put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        let path = self.resolve(path, Operation::BlockingPutTags)?;
+        self.inner.blocking_put_tags(&path, args)
+    }
+}
+
+/// Build the path relative to root from the path returned by service.
+fn relativize(root: &str, path: &str) -> String {
+    if root.is_empty() {
+        return path.to_string();
+    }
+    match path.strip_prefix(root) {
+        Some("") => "/".to_string(),
+        Some(v) => v.to_string(),
+        None => path.to_string(),
+    }
+}
+
+pub struct NormalizeRootPager<P> {
+    inner: P,
+    root: Arc<String>,
+}
+
+impl<P> NormalizeRootPager<P> {
+    fn new(inner: P, root: Arc<String>) -> Self {
+        Self { inner, root }
+    }
+
+    fn relativize(&self, entries: &mut [oio::Entry]) {
+        for de in entries {
+            let path = relativize(&self.root, de.path());
+            de.set_path(&path);
+        }
+    }
+}
+
+#[async_trait]
+impl<P: oio::Page> oio::Page for NormalizeRootPager<P> {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        let mut entries = self.inner.next().await?;
+        if let Some(entries) = &mut entries {
+            self.relativize(entries);
+        }
+        Ok(entries)
+    }
+
+    fn continuation_token(&self) -> Option<String> {
+        self.inner.continuation_token()
+    }
+}
+
+impl<P: oio::BlockingPage> oio::BlockingPage for NormalizeRootPager<P> {
+    fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        let mut entries = self.inner.next()?;
+        if let Some(entries) = &mut entries {
+            self.relativize(entries);
+        }
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::services::Memory;
+
+    #[test]
+    fn test_resolve() {
+        let acc = NormalizeRootLayer::new("/tenant/").layer(());
+
+        let cases = vec![
+            ("root", "/", "tenant/"),
+            ("file", "abc", "tenant/abc"),
+            ("dir", "abc/", "tenant/abc/"),
+            ("absolute path", "/etc/passwd", "tenant/etc/passwd"),
+            ("dot", "abc/./def", "tenant/abc/def"),
+            ("dot dot", "abc/../def", "tenant/def"),
+            ("ends with dot dot", "abc/def/..", "tenant/abc/"),
+            ("back to root", "abc/..", "tenant/"),
+        ];
+        for (name, input, expect) in cases {
+            assert_eq!(
+                acc.resolve(input, Operation::Stat).expect(name),
+                expect,
+                "{name}"
+            );
+        }
+
+        for input in ["..", "../abc", "abc/../../def", "/..", "abc\\..\\..\\def"] {
+            let err = acc.resolve(input, Operation::Stat).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::PermissionDenied, "{input}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list() -> Result<()> {
+        let op = Operator::new(Memory::default())?
+            .layer(NormalizeRootLayer::new("tenant"))
+            .finish();
+
+        op.write("/dir/file", "Hello, World!").await?;
+        let entries: Vec<_> = op.list("dir/").await?.try_collect().await?;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path(), "dir/file");
+
+        let err = op.read("dir/../../file").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        Ok(())
+    }
+}
@@ -333,6 +333,10 @@ impl<R: oio::Write> oio::Write for OtelTraceWrapper<R> {
     async fn close(&mut self) -> Result<()> {
         self.inner.close().await
     }
+
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        self.inner.checkpoint()
+    }
 }
 
 impl<R: oio::BlockingWrite> oio::BlockingWrite for OtelTraceWrapper<R> {
@@ -350,6 +354,10 @@ impl<R: oio::Page> oio::Page for OtelTraceWrapper<R> {
     async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
         self.inner.next().await
     }
+
+    fn continuation_token(&self) -> Option<String> {
+        self.inner.continuation_token()
+    }
 }
 
 impl<R: oio::BlockingPage> oio::BlockingPage for OtelTraceWrapper<R> {
@@ -713,6 +713,10 @@ impl<R: oio::Write> oio::Write for PrometheusMetricWrapper<R> {
             err
         })
     }
+
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        self.inner.checkpoint()
+    }
 }
 
 impl<R: oio::BlockingWrite> oio::BlockingWrite for PrometheusMetricWrapper<R> {
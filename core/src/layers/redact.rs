@@ -0,0 +1,584 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::io::SeekFrom;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::TryFutureExt;
+use regex::Regex;
+
+use crate::raw::*;
+use crate::*;
+
+/// The text used to replace sensitive data.
+const REDACTED: &str = "[REDACTED]";
+
+/// Patterns enabled by default, the first capture group will be kept.
+const DEFAULT_PATTERNS: &[&str] = &[
+    // Signatures, credentials and tokens carried in query string or
+    // authorization header, like presigned urls.
+    r#"(?i)\b((?:x-amz-signature|x-amz-credential|x-amz-security-token|x-goog-signature|x-goog-credential|x-oss-signature|ossaccesskeyid|security-token|access_token|signature|credential|token|sig)=)[^&\s,;"']+"#,
+    // Bearer tokens.
+    r"(?i)\b(bearer\s+)[A-Za-z0-9\-._~+/]+=*",
+];
+
+/// Strip sensitive data from errors before they propagate.
+///
+/// # Notes
+///
+/// Errors could carry presigned urls, tokens in query string or sensitive
+/// paths in their message, context and source. RedactLayer will replace
+/// the following data with `[REDACTED]` in all errors returned by
+/// operations, readers, writers and pagers:
+///
+/// - Signature, credential and token query params like `X-Amz-Signature`.
+/// - Bearer tokens.
+/// - Texts that match patterns added by [`RedactLayer::with_pattern`].
+/// - Paths that start with prefixes added by [`RedactLayer::with_path_prefix`].
+///
+/// Layers added after RedactLayer will only see redacted errors. So please
+/// add RedactLayer before [`LoggingLayer`](crate::layers::LoggingLayer) to
+/// make sure errors are redacted before logging. Paths logged by
+/// LoggingLayer itself should be redacted by the same rules via
+/// [`LoggingLayer::with_path_redactor`](crate::layers::LoggingLayer::with_path_redactor).
+///
+/// # Examples
+///
+/// ```
+/// # use anyhow::Result;
+/// use opendal::layers::LoggingLayer;
+/// use opendal::layers::RedactLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// # fn main() -> Result<()> {
+/// let redact = RedactLayer::new()
+///     .with_pattern(r"password=\S+")?
+///     .with_path_prefix("secret/");
+///
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(redact.clone())
+///     .layer(LoggingLayer::default().with_path_redactor(move |p| redact.redact(p)))
+///     .finish();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RedactLayer {
+    redactor: Redactor,
+}
+
+impl Default for RedactLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RedactLayer {
+    /// Create a new RedactLayer with default patterns.
+    pub fn new() -> Self {
+        let rules = DEFAULT_PATTERNS
+            .iter()
+            .map(|p| {
+                (
+                    Regex::new(p).expect("default pattern must be valid"),
+                    format!("${{1}}{REDACTED}"),
+                )
+            })
+            .collect();
+
+        Self {
+            redactor: Redactor { rules },
+        }
+    }
+
+    /// Redact texts that match given regex pattern.
+    ///
+    /// The whole match will be replaced.
+    pub fn with_pattern(mut self, pattern: &str) -> Result<Self> {
+        let re = Regex::new(pattern).map_err(|err| {
+            Error::new(ErrorKind::ConfigInvalid, "redact pattern is invalid")
+                .with_context("pattern", pattern)
+                .set_source(err)
+        })?;
+
+        self.redactor.rules.push((re, REDACTED.to_string()));
+        Ok(self)
+    }
+
+    /// Redact paths that start with given prefix.
+    pub fn with_path_prefix(mut self, prefix: &str) -> Self {
+        let re = Regex::new(&format!(r#"{}[^\s,;"']*"#, regex::escape(prefix)))
+            .expect("escaped prefix must be valid");
+
+        self.redactor.rules.push((re, REDACTED.to_string()));
+        self
+    }
+
+    /// Redact given text with patterns of this layer.
+    pub fn redact(&self, text: &str) -> String {
+        self.redactor.redact(text)
+    }
+}
+
+impl<A: Accessor> Layer<A> for RedactLayer {
+    type LayeredAccessor = RedactAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        RedactAccessor {
+            inner,
+            redactor: Arc::new(self.redactor.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Redactor {
+    rules: Vec<(Regex, String)>,
+}
+
+impl Redactor {
+    fn redact(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        for (re, rep) in self.rules.iter() {
+            if re.is_match(&text) {
+                text = re.replace_all(&text, rep.as_str()).into_owned();
+            }
+        }
+        text
+    }
+
+    fn redact_err(&self, err: Error) -> Error {
+        err.map_text(|v| self.redact(v))
+    }
+}
+
+pub struct RedactAccessor<A: Accessor> {
+    inner: A,
+    redactor: Arc<Redactor>,
+}
+
+impl<A: Accessor> Debug for RedactAccessor<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedactAccessor")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: Accessor> RedactAccessor<A> {
+    fn wrap<T>(&self, inner: T) -> RedactWrapper<T> {
+        RedactWrapper {
+            inner,
+            redactor: self.redactor.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for RedactAccessor<A> {
+    type Inner = A;
+    type Reader = RedactWrapper<A::Reader>;
+    type BlockingReader = RedactWrapper<A::BlockingReader>;
+    type Writer = RedactWrapper<A::Writer>;
+    type BlockingWriter = RedactWrapper<A::BlockingWriter>;
+    type Appender = RedactWrapper<A::Appender>;
+    type Pager = RedactWrapper<A::Pager>;
+    type BlockingPager = RedactWrapper<A::BlockingPager>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.inner
+            .create_dir(path, args)
+            .map_err(|err| self.redactor.redact_err(err))
+            .await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner
+            .read(path, args)
+            .map_ok(|(rp, r)| (rp, self.wrap(r)))
+            .map_err(|err| self.redactor.redact_err(err))
+            .await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner
+            .write(path, args)
+            .map_ok(|(rp, w)| (rp, self.wrap(w)))
+            .map_err(|err| self.redactor.redact_err(err))
+            .await
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        self.inner
+            .append(path, args)
+            .map_ok(|(rp, a)| (rp, self.wrap(a)))
+            .map_err(|err| self.redactor.redact_err(err))
+            .await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.inner
+            .copy(from, to, args)
+            .map_err(|err| self.redactor.redact_err(err))
+            .await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.inner
+            .rename(from, to, args)
+            .map_err(|err| self.redactor.redact_err(err))
+            .await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.inner
+            .stat(path, args)
+            .map_err(|err| self.redactor.redact_err(err))
+            .await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.inner
+            .delete(path, args)
+            .map_err(|err| self.redactor.redact_err(err))
+            .await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner
+            .list(path, args)
+            .map_ok(|(rp, p)| (rp, self.wrap(p)))
+            .map_err(|err| self.redactor.redact_err(err))
+            .await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        self.inner
+            .batch(args)
+            .map_ok(|v| {
+                let res = v
+                    .into_results()
+                    .into_iter()
+                    .map(|(path, res)| (path, res.map_err(|err| self.redactor.redact_err(err))))
+                    .collect();
+
+                RpBatch::new(res)
+            })
+            .map_err(|err| self.redactor.redact_err(err))
+            .await
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.inner
+            .presign(path, args)
+            .map_err(|err| self.redactor.redact_err(err))
+            .await
+    }
+
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        self.inner
+            .get_tags(path, args)
+            .map_err(|err| self.redactor.redact_err(err))
+            .await
+    }
+
+    async fn put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        self.inner
+            .put_tags(path, args)
+            .map_err(|err| self.redactor.redact_err(err))
+            .await
+    }
+
+    async fn get_object_lock(&self, path: &str, args: OpGetObjectLock) -> Result<RpGetObjectLock> {
+        self.inner
+            .get_object_lock(path, args)
+            .map_err(|err| self.redactor.redact_err(err))
+            .await
+    }
+
+    async fn put_object_lock(&self, path: &str, args: OpPutObjectLock) -> Result<RpPutObjectLock> {
+        self.inner
+            .put_object_lock(path, args)
+            .map_err(|err| self.redactor.redact_err(err))
+            .await
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.inner
+            .blocking_create_dir(path, args)
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner
+            .blocking_read(path, args)
+            .map(|(rp, r)| (rp, self.wrap(r)))
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner
+            .blocking_write(path, args)
+            .map(|(rp, w)| (rp, self.wrap(w)))
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.inner
+            .blocking_copy(from, to, args)
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.inner
+            .blocking_rename(from, to, args)
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.inner
+            .blocking_stat(path, args)
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.inner
+            .blocking_delete(path, args)
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner
+            .blocking_list(path, args)
+            .map(|(rp, p)| (rp, self.wrap(p)))
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+
+    fn blocking_get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        self.inner
+            .blocking_get_tags(path, args)
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+
+    fn blocking_put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        self.inner
+            .blocking_put_tags(path, args)
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+}
+
+pub struct RedactWrapper<T> {
+    inner: T,
+    redactor: Arc<Redactor>,
+}
+
+impl<T: oio::Read> oio::Read for RedactWrapper<T> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        self.inner
+            .poll_read(cx, buf)
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        self.inner
+            .poll_seek(cx, pos)
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        self.inner
+            .poll_next(cx)
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+}
+
+impl<T: oio::BlockingRead> oio::BlockingRead for RedactWrapper<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.inner
+            .read(buf)
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner
+            .seek(pos)
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        self.inner
+            .next()
+            .map(|v| v.map_err(|err| self.redactor.redact_err(err)))
+    }
+}
+
+#[async_trait]
+impl<T: oio::Write> oio::Write for RedactWrapper<T> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.inner
+            .write(bs)
+            .await
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+
+    async fn sink(&mut self, size: u64, s: oio::Streamer) -> Result<()> {
+        self.inner
+            .sink(size, s)
+            .await
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner
+            .abort()
+            .await
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.inner
+            .close()
+            .await
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        self.inner.checkpoint()
+    }
+}
+
+impl<T: oio::BlockingWrite> oio::BlockingWrite for RedactWrapper<T> {
+    fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.inner
+            .write(bs)
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.inner
+            .close()
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+}
+
+#[async_trait]
+impl<T: oio::Append> oio::Append for RedactWrapper<T> {
+    async fn append(&mut self, bs: Bytes) -> Result<()> {
+        self.inner
+            .append(bs)
+            .await
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.inner
+            .close()
+            .await
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+}
+
+#[async_trait]
+impl<T: oio::Page> oio::Page for RedactWrapper<T> {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        self.inner
+            .next()
+            .await
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+
+    fn continuation_token(&self) -> Option<String> {
+        self.inner.continuation_token()
+    }
+}
+
+impl<T: oio::BlockingPage> oio::BlockingPage for RedactWrapper<T> {
+    fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        self.inner
+            .next()
+            .map_err(|err| self.redactor.redact_err(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[test]
+    fn test_redact_default_patterns() {
+        let layer = RedactLayer::new();
+
+        let cases = vec![
+            (
+                "https://bucket.s3.amazonaws.com/key?X-Amz-Credential=AKID%2F20230101&X-Amz-Signature=abcdef",
+                "https://bucket.s3.amazonaws.com/key?X-Amz-Credential=[REDACTED]&X-Amz-Signature=[REDACTED]",
+            ),
+            (
+                "https://account.blob.core.windows.net/c/b?sv=2021&sig=abc%2Fdef",
+                "https://account.blob.core.windows.net/c/b?sv=2021&sig=[REDACTED]",
+            ),
+            ("authorization: Bearer abc.def-ghi", "authorization: Bearer [REDACTED]"),
+            ("object not found", "object not found"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(layer.redact(input), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_redact_custom_rules() -> Result<()> {
+        let layer = RedactLayer::new()
+            .with_pattern(r"password=\S+")?
+            .with_path_prefix("secret/");
+
+        assert_eq!(
+            layer.redact("read secret/a.txt failed, password=123"),
+            "read [REDACTED] failed, [REDACTED]"
+        );
+        assert_eq!(
+            RedactLayer::new().with_pattern("(").unwrap_err().kind(),
+            ErrorKind::ConfigInvalid
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_redact_error() {
+        let op = Operator::new(Memory::default())
+            .unwrap()
+            .layer(RedactLayer::new().with_path_prefix("secret/"))
+            .finish();
+
+        let err = op.stat("secret/not_exist").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        assert!(!err.to_string().contains("secret/not_exist"), "{err}");
+        assert!(err.to_string().contains(REDACTED), "{err}");
+    }
+}
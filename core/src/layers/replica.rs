@@ -0,0 +1,1083 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::future::Future;
+use std::io::SeekFrom;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::channel::mpsc;
+use futures::future::join;
+use futures::future::join_all;
+use futures::future::try_join;
+use futures::future::try_join3;
+use futures::future::try_join_all;
+use futures::future::BoxFuture;
+use futures::SinkExt;
+use futures::StreamExt;
+
+use crate::raw::oio::ReadExt;
+
+use crate::raw::*;
+use crate::*;
+
+/// Fail over reads to replicas of the underlying storage.
+///
+/// # Notes
+///
+/// The operator that this layer is added to is the primary, the given
+/// operators are its replicas which should hold the same data under the
+/// same paths.
+///
+/// - `read`, `stat` and `list` will try the next replica if the current one
+///   returns a temporary error (see [`Error::is_temporary`]). Other errors
+///   like `NotFound` are returned as is. Readers also fail over on temporary
+///   errors while reading, the rest of content will be read from the next
+///   replica.
+/// - With [`ReplicaLayer::with_round_robin`] enabled, reads will start from
+///   a different replica every time to spread the load.
+/// - `write`, `append`, `create_dir`, `delete`, `copy`, `rename` and `batch`
+///   go to the primary by default. With [`ReplicaWriteMode::All`], they will
+///   be sent to the primary and all replicas, and fail if any of them fails.
+/// - `presign` and tags operations always go to the primary.
+///
+/// With [`ReplicaWriteMode::All`], there is no rollback for finished
+/// mutations: if the primary succeeded but a replica failed, the replicas
+/// have diverged. The returned error carries the failed `replica` (`0` for
+/// the primary, `i` for the `i - 1` replica) in its context and a warning
+/// will be logged. Writers will be aborted on all replicas if any of them
+/// fails before `close`.
+///
+/// Please add [`RetryLayer`][crate::layers::RetryLayer] to every replica
+/// instead of the primary operator, otherwise the retry will hide temporary
+/// errors from this layer.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::ReplicaLayer;
+/// use opendal::layers::ReplicaWriteMode;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let replica = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .finish();
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(
+///         ReplicaLayer::new([replica])
+///             .with_round_robin(true)
+///             .with_write_mode(ReplicaWriteMode::All),
+///     )
+///     .finish();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReplicaLayer {
+    replicas: Vec<FusedAccessor>,
+    round_robin: bool,
+    write_mode: ReplicaWriteMode,
+}
+
+/// ReplicaWriteMode decides where the mutations go in [`ReplicaLayer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReplicaWriteMode {
+    /// Only write to the primary.
+    #[default]
+    Primary,
+    /// Write to the primary and all replicas.
+    All,
+}
+
+impl ReplicaLayer {
+    /// Create a new ReplicaLayer with given replicas.
+    pub fn new(replicas: impl IntoIterator<Item = Operator>) -> Self {
+        Self {
+            replicas: replicas.into_iter().map(|v| v.into_inner()).collect(),
+            round_robin: false,
+            write_mode: ReplicaWriteMode::Primary,
+        }
+    }
+
+    /// Start reads from a different replica every time.
+    ///
+    /// By default, reads always start from the primary.
+    pub fn with_round_robin(mut self, round_robin: bool) -> Self {
+        self.round_robin = round_robin;
+        self
+    }
+
+    /// Set where the mutations go.
+    pub fn with_write_mode(mut self, mode: ReplicaWriteMode) -> Self {
+        self.write_mode = mode;
+        self
+    }
+}
+
+impl<A: Accessor> Layer<A> for ReplicaLayer {
+    type LayeredAccessor = ReplicaAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        ReplicaAccessor {
+            inner: Arc::new(inner),
+            replicas: Arc::new(self.replicas.clone()),
+            round_robin: self.round_robin,
+            write_mode: self.write_mode,
+            next: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReplicaAccessor<A: Accessor> {
+    inner: Arc<A>,
+    replicas: Arc<Vec<FusedAccessor>>,
+    round_robin: bool,
+    write_mode: ReplicaWriteMode,
+    next: Arc<AtomicUsize>,
+}
+
+impl<A: Accessor> ReplicaAccessor<A> {
+    /// Return the order to try, `0` means the primary and `i` means the
+    /// `i - 1` replica.
+    fn order(&self) -> impl Iterator<Item = usize> {
+        let total = self.replicas.len() + 1;
+        let start = if self.round_robin {
+            self.next.fetch_add(1, Ordering::Relaxed) % total
+        } else {
+            0
+        };
+        (0..total).map(move |i| (start + i) % total)
+    }
+
+    fn fan_out(&self) -> &[FusedAccessor] {
+        match self.write_mode {
+            ReplicaWriteMode::Primary => &[],
+            ReplicaWriteMode::All => &self.replicas,
+        }
+    }
+
+    /// Open the first readable one in the given order, the order will be
+    /// consumed until the returned accessor.
+    async fn open(
+        &self,
+        path: &str,
+        args: &OpRead,
+        order: &mut Vec<usize>,
+    ) -> Result<(RpRead, oio::Reader)> {
+        let mut last_err = None;
+        while let Some(idx) = order.pop() {
+            let fut = open_read(
+                self.inner.clone(),
+                self.replicas.clone(),
+                idx,
+                path.to_string(),
+                args.clone(),
+                0,
+            );
+            match fut.await {
+                Err(err) if err.is_temporary() => last_err = Some(err),
+                res => return res,
+            }
+        }
+        Err(last_err.expect("at least one accessor must be tried"))
+    }
+
+    fn blocking_open(
+        &self,
+        path: &str,
+        args: &OpRead,
+        order: &mut Vec<usize>,
+    ) -> Result<(RpRead, oio::BlockingReader)> {
+        let mut last_err = None;
+        while let Some(idx) = order.pop() {
+            match blocking_open_read(&self.inner, &self.replicas, idx, path, args.clone(), 0) {
+                Err(err) if err.is_temporary() => last_err = Some(err),
+                res => return res,
+            }
+        }
+        Err(last_err.expect("at least one accessor must be tried"))
+    }
+
+    /// Send the mutation that succeeded on the primary to all replicas.
+    fn blocking_fan_out_all<T>(&self, f: impl Fn(&FusedAccessor) -> Result<T>) -> Result<()> {
+        for (i, acc) in self.fan_out().iter().enumerate() {
+            f(acc).map_err(|err| diverged(i + 1, err))?;
+        }
+        Ok(())
+    }
+
+    async fn failover<T, P, PF, R, RF>(&self, primary: P, replica: R) -> Result<T>
+    where
+        P: Fn() -> PF,
+        PF: Future<Output = Result<T>>,
+        R: Fn(FusedAccessor) -> RF,
+        RF: Future<Output = Result<T>>,
+    {
+        let mut last_err = None;
+        for idx in self.order() {
+            let res = match idx {
+                0 => primary().await,
+                i => replica(self.replicas[i - 1].clone()).await,
+            };
+            match res {
+                Err(err) if err.is_temporary() => last_err = Some(err),
+                res => return res,
+            }
+        }
+        Err(last_err.expect("at least one accessor must be tried"))
+    }
+
+    fn blocking_failover<T>(
+        &self,
+        primary: impl Fn() -> Result<T>,
+        replica: impl Fn(&FusedAccessor) -> Result<T>,
+    ) -> Result<T> {
+        let mut last_err = None;
+        for idx in self.order() {
+            let res = match idx {
+                0 => primary(),
+                i => replica(&self.replicas[i - 1]),
+            };
+            match res {
+                Err(err) if err.is_temporary() => last_err = Some(err),
+                res => return res,
+            }
+        }
+        Err(last_err.expect("at least one accessor must be tried"))
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for ReplicaAccessor<A> {
+    type Inner = A;
+    type Reader = ReplicaReader<A>;
+    type BlockingReader = BlockingReplicaReader<A>;
+    type Writer = ReplicaWriter<A::Writer, oio::Writer>;
+    type BlockingWriter = ReplicaWriter<A::BlockingWriter, oio::BlockingWriter>;
+    type Appender = ReplicaAppender<A::Appender>;
+    type Pager = oio::Pager;
+    type BlockingPager = oio::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let rp = self.inner.create_dir(path, args.clone()).await?;
+        fan_out_all(
+            self.fan_out()
+                .iter()
+                .map(|acc| acc.create_dir(path, args.clone())),
+        )
+        .await?;
+        Ok(rp)
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let mut order: Vec<_> = self.order().collect();
+        order.reverse();
+
+        let (rp, r) = self.open(path, &args, &mut order).await?;
+        Ok((
+            rp,
+            ReplicaReader {
+                primary: self.inner.clone(),
+                replicas: self.replicas.clone(),
+                path: path.to_string(),
+                args,
+                order,
+                pos: 0,
+                state: ReaderState::Reading(r),
+            },
+        ))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let (rp, primary) = self.inner.write(path, args.clone()).await?;
+        let replicas = try_join_all(self.fan_out().iter().map(|acc| {
+            let args = args.clone();
+            async move { acc.write(path, args).await.map(|(_, w)| w) }
+        }))
+        .await?;
+        Ok((rp, ReplicaWriter { primary, replicas }))
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        let (rp, primary) = self.inner.append(path, args.clone()).await?;
+        let replicas = try_join_all(self.fan_out().iter().map(|acc| {
+            let args = args.clone();
+            async move { acc.append(path, args).await.map(|(_, a)| a) }
+        }))
+        .await?;
+        Ok((rp, ReplicaAppender { primary, replicas }))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let rp = self.inner.copy(from, to, args.clone()).await?;
+        fan_out_all(
+            self.fan_out()
+                .iter()
+                .map(|acc| acc.copy(from, to, args.clone())),
+        )
+        .await?;
+        Ok(rp)
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let rp = self.inner.rename(from, to, args.clone()).await?;
+        fan_out_all(
+            self.fan_out()
+                .iter()
+                .map(|acc| acc.rename(from, to, args.clone())),
+        )
+        .await?;
+        Ok(rp)
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.failover(
+            || self.inner.stat(path, args.clone()),
+            |acc| {
+                let args = args.clone();
+                async move { acc.stat(path, args).await }
+            },
+        )
+        .await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let rp = self.inner.delete(path, args.clone()).await?;
+        fan_out_all(
+            self.fan_out()
+                .iter()
+                .map(|acc| acc.delete(path, args.clone())),
+        )
+        .await?;
+        Ok(rp)
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.failover(
+            || {
+                let args = args.clone();
+                async move {
+                    let (rp, p) = self.inner.list(path, args).await?;
+                    Ok((rp, Box::new(p) as oio::Pager))
+                }
+            },
+            |acc| {
+                let args = args.clone();
+                async move { acc.list(path, args).await }
+            },
+        )
+        .await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let rp = self.inner.batch(args.clone()).await?;
+        fan_out_all(self.fan_out().iter().map(|acc| acc.batch(args.clone()))).await?;
+        Ok(rp)
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let rp = self.inner.blocking_create_dir(path, args.clone())?;
+        self.blocking_fan_out_all(|acc| acc.blocking_create_dir(path, args.clone()))?;
+        Ok(rp)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let mut order: Vec<_> = self.order().collect();
+        order.reverse();
+
+        let (rp, r) = self.blocking_open(path, &args, &mut order)?;
+        Ok((
+            rp,
+            BlockingReplicaReader {
+                primary: self.inner.clone(),
+                replicas: self.replicas.clone(),
+                path: path.to_string(),
+                args,
+                order,
+                pos: 0,
+                inner: r,
+            },
+        ))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let (rp, primary) = self.inner.blocking_write(path, args.clone())?;
+        let replicas = self
+            .fan_out()
+            .iter()
+            .map(|acc| acc.blocking_write(path, args.clone()).map(|(_, w)| w))
+            .collect::<Result<Vec<_>>>()?;
+        Ok((rp, ReplicaWriter { primary, replicas }))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let rp = self.inner.blocking_copy(from, to, args.clone())?;
+        self.blocking_fan_out_all(|acc| acc.blocking_copy(from, to, args.clone()))?;
+        Ok(rp)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let rp = self.inner.blocking_rename(from, to, args.clone())?;
+        self.blocking_fan_out_all(|acc| acc.blocking_rename(from, to, args.clone()))?;
+        Ok(rp)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.blocking_failover(
+            || self.inner.blocking_stat(path, args.clone()),
+            |acc| acc.blocking_stat(path, args.clone()),
+        )
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let rp = self.inner.blocking_delete(path, args.clone())?;
+        self.blocking_fan_out_all(|acc| acc.blocking_delete(path, args.clone()))?;
+        Ok(rp)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.blocking_failover(
+            || {
+                let (rp, p) = self.inner.blocking_list(path, args.clone())?;
+                Ok((rp, Box::new(p) as oio::BlockingPager))
+            },
+            |acc| acc.blocking_list(path, args.clone()),
+        )
+    }
+}
+
+/// Wait for the mutation that succeeded on the primary on all replicas.
+async fn fan_out_all<T>(futs: impl Iterator<Item = impl Future<Output = Result<T>>>) -> Result<()> {
+    try_join_all(
+        futs.enumerate()
+            .map(|(i, fut)| async move { fut.await.map_err(|err| diverged(i + 1, err)) }),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Mark the error of given accessor while others may have succeeded.
+///
+/// `idx` is `0` for the primary and `i` for the `i - 1` replica.
+fn diverged(idx: usize, err: Error) -> Error {
+    log::warn!(
+        "replica {idx} failed while others may have succeeded, replicas may diverge: {err:?}"
+    );
+    err.with_context("replica", idx.to_string())
+}
+
+/// Collect results of all accessors, return the first error if any of
+/// them failed.
+fn report(results: Vec<Result<()>>) -> Result<()> {
+    let mut errs = results
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, res)| res.err().map(|err| diverged(i, err)));
+    match errs.next() {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+/// Read from the given accessor, `pos` is the position in the range of
+/// `args` to start from.
+fn open_read<A: Accessor>(
+    primary: Arc<A>,
+    replicas: Arc<Vec<FusedAccessor>>,
+    idx: usize,
+    path: String,
+    args: OpRead,
+    pos: u64,
+) -> BoxFuture<'static, Result<(RpRead, oio::Reader)>> {
+    Box::pin(async move {
+        let (rp, mut r) = match idx {
+            0 => {
+                let (rp, r) = primary.read(&path, args).await?;
+                (rp, Box::new(r) as oio::Reader)
+            }
+            i => replicas[i - 1].read(&path, args).await?,
+        };
+        if pos != 0 {
+            r.seek(SeekFrom::Start(pos)).await?;
+        }
+        Ok((rp, r))
+    })
+}
+
+fn blocking_open_read<A: Accessor>(
+    primary: &A,
+    replicas: &[FusedAccessor],
+    idx: usize,
+    path: &str,
+    args: OpRead,
+    pos: u64,
+) -> Result<(RpRead, oio::BlockingReader)> {
+    let (rp, mut r) = match idx {
+        0 => {
+            let (rp, r) = primary.blocking_read(path, args)?;
+            (rp, Box::new(r) as oio::BlockingReader)
+        }
+        i => replicas[i - 1].blocking_read(path, args)?,
+    };
+    if pos != 0 {
+        r.seek(SeekFrom::Start(pos))?;
+    }
+    Ok((rp, r))
+}
+
+/// ReplicaReader reads the rest of content from the next replica if the
+/// current one returns a temporary error while reading.
+pub struct ReplicaReader<A: Accessor> {
+    primary: Arc<A>,
+    replicas: Arc<Vec<FusedAccessor>>,
+    path: String,
+    args: OpRead,
+    /// Accessors left to fail over to, the last one will be tried first.
+    order: Vec<usize>,
+    /// Current position in the range of `args`.
+    pos: u64,
+    state: ReaderState,
+}
+
+enum ReaderState {
+    Reading(oio::Reader),
+    Opening(BoxFuture<'static, Result<(RpRead, oio::Reader)>>),
+}
+
+/// Safety: the future is only polled with `&mut self`.
+unsafe impl Sync for ReaderState {}
+
+impl<A: Accessor> ReplicaReader<A> {
+    /// Start reading from the next accessor, return the error back if it
+    /// can't be failed over.
+    fn failover(&mut self, err: Error) -> Result<()> {
+        if !err.is_temporary() {
+            return Err(err);
+        }
+        let Some(idx) = self.order.pop() else {
+            return Err(err);
+        };
+
+        log::warn!(
+            "read {} failed at {}, fail over to replica {idx}: {err:?}",
+            self.path,
+            self.pos
+        );
+        self.state = ReaderState::Opening(open_read(
+            self.primary.clone(),
+            self.replicas.clone(),
+            idx,
+            self.path.clone(),
+            self.args.clone(),
+            self.pos,
+        ));
+        Ok(())
+    }
+
+    /// Poll until the reader is ready.
+    fn poll_reader(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        loop {
+            match &mut self.state {
+                ReaderState::Reading(_) => return Poll::Ready(Ok(())),
+                ReaderState::Opening(fut) => match ready!(fut.as_mut().poll(cx)) {
+                    Ok((_, r)) => self.state = ReaderState::Reading(r),
+                    Err(err) => self.failover(err)?,
+                },
+            }
+        }
+    }
+}
+
+impl<A: Accessor> oio::Read for ReplicaReader<A> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        loop {
+            ready!(self.poll_reader(cx))?;
+            let ReaderState::Reading(r) = &mut self.state else {
+                unreachable!("reader must be ready")
+            };
+            match ready!(r.poll_read(cx, buf)) {
+                Ok(n) => {
+                    self.pos += n as u64;
+                    return Poll::Ready(Ok(n));
+                }
+                Err(err) => self.failover(err)?,
+            }
+        }
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        ready!(self.poll_reader(cx))?;
+        let ReaderState::Reading(r) = &mut self.state else {
+            unreachable!("reader must be ready")
+        };
+        let pos = ready!(r.poll_seek(cx, pos))?;
+        self.pos = pos;
+        Poll::Ready(Ok(pos))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        loop {
+            if let Err(err) = ready!(self.poll_reader(cx)) {
+                return Poll::Ready(Some(Err(err)));
+            }
+            let ReaderState::Reading(r) = &mut self.state else {
+                unreachable!("reader must be ready")
+            };
+            match ready!(r.poll_next(cx)) {
+                Some(Ok(bs)) => {
+                    self.pos += bs.len() as u64;
+                    return Poll::Ready(Some(Ok(bs)));
+                }
+                Some(Err(err)) => {
+                    if let Err(err) = self.failover(err) {
+                        return Poll::Ready(Some(Err(err)));
+                    }
+                }
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+/// BlockingReplicaReader is the blocking version of [`ReplicaReader`].
+pub struct BlockingReplicaReader<A: Accessor> {
+    primary: Arc<A>,
+    replicas: Arc<Vec<FusedAccessor>>,
+    path: String,
+    args: OpRead,
+    order: Vec<usize>,
+    pos: u64,
+    inner: oio::BlockingReader,
+}
+
+impl<A: Accessor> BlockingReplicaReader<A> {
+    fn failover(&mut self, mut err: Error) -> Result<()> {
+        loop {
+            if !err.is_temporary() {
+                return Err(err);
+            }
+            let Some(idx) = self.order.pop() else {
+                return Err(err);
+            };
+
+            log::warn!(
+                "read {} failed at {}, fail over to replica {idx}: {err:?}",
+                self.path,
+                self.pos
+            );
+            match blocking_open_read(
+                &self.primary,
+                &self.replicas,
+                idx,
+                &self.path,
+                self.args.clone(),
+                self.pos,
+            ) {
+                Ok((_, r)) => {
+                    self.inner = r;
+                    return Ok(());
+                }
+                Err(e) => err = e,
+            }
+        }
+    }
+}
+
+impl<A: Accessor> oio::BlockingRead for BlockingReplicaReader<A> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        loop {
+            match self.inner.read(buf) {
+                Ok(n) => {
+                    self.pos += n as u64;
+                    return Ok(n);
+                }
+                Err(err) => self.failover(err)?,
+            }
+        }
+    }
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let pos = self.inner.seek(pos)?;
+        self.pos = pos;
+        Ok(pos)
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        loop {
+            match oio::BlockingRead::next(&mut self.inner)? {
+                Ok(bs) => {
+                    self.pos += bs.len() as u64;
+                    return Some(Ok(bs));
+                }
+                Err(err) => {
+                    if let Err(err) = self.failover(err) {
+                        return Some(Err(err));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// ReplicaWriter writes the same content to the primary and all replicas.
+pub struct ReplicaWriter<W, R> {
+    primary: W,
+    replicas: Vec<R>,
+}
+
+impl<W: oio::Write> ReplicaWriter<W, oio::Writer> {
+    /// Abort all writers after any of them failed, so that nothing will be
+    /// committed. Abort is best effort, its errors will be logged only.
+    async fn abort_all(&mut self, err: Error) -> Error {
+        let replicas = join_all(self.replicas.iter_mut().map(|w| w.abort()));
+        let (primary, replicas) = join(self.primary.abort(), replicas).await;
+        for (i, res) in [primary].into_iter().chain(replicas).enumerate() {
+            if let Err(e) = res {
+                log::warn!("abort writer of replica {i} failed: {e:?}");
+            }
+        }
+        err
+    }
+}
+
+#[async_trait]
+impl<W: oio::Write> oio::Write for ReplicaWriter<W, oio::Writer> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        let replicas = try_join_all(self.replicas.iter_mut().map(|w| w.write(bs.clone())));
+        match try_join(self.primary.write(bs.clone()), replicas).await {
+            Ok(_) => Ok(()),
+            Err(err) => Err(self.abort_all(err).await),
+        }
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        let replicas = try_join_all(self.replicas.iter_mut().map(|w| w.abort()));
+        try_join(self.primary.abort(), replicas).await?;
+        Ok(())
+    }
+
+    async fn sink(&mut self, size: u64, mut s: oio::Streamer) -> Result<()> {
+        if self.replicas.is_empty() {
+            return self.primary.sink(size, s).await;
+        }
+
+        // Stream can only be consumed once, so we tee every chunk into all
+        // writers via bounded channels instead of buffering the whole stream.
+        let (mut txs, mut rxs): (Vec<_>, Vec<_>) = (0..=self.replicas.len())
+            .map(|_| {
+                let (tx, rx) = mpsc::channel::<Result<Bytes>>(1);
+                (tx, Box::new(oio::into_stream(rx)) as oio::Streamer)
+            })
+            .unzip();
+
+        let primary = self.primary.sink(size, rxs.remove(0));
+        let replicas = try_join_all(
+            self.replicas
+                .iter_mut()
+                .zip(rxs)
+                .map(|(w, rx)| w.sink(size, rx)),
+        );
+        let tee = async move {
+            while let Some(bs) = s.next().await {
+                let bs = match bs {
+                    Ok(bs) => bs,
+                    Err(err) => {
+                        // Fail all writers instead of ending their streams,
+                        // otherwise they could commit partial content.
+                        for tx in txs.iter_mut() {
+                            let _ = tx
+                                .send(Err(Error::new(
+                                    ErrorKind::Unexpected,
+                                    "source stream of replica writer failed",
+                                )))
+                                .await;
+                        }
+                        return Err(err);
+                    }
+                };
+                for tx in txs.iter_mut() {
+                    tx.send(Ok(bs.clone())).await.map_err(|err| {
+                        Error::new(ErrorKind::Unexpected, "replica writer stopped sinking")
+                            .set_source(err)
+                    })?;
+                }
+            }
+            Ok(())
+        };
+
+        let res = try_join3(primary, replicas, tee).await;
+        match res {
+            Ok(_) => Ok(()),
+            Err(err) => Err(self.abort_all(err).await),
+        }
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        // Close all of them even if some failed, so that we can report
+        // which replicas have diverged.
+        let replicas = join_all(self.replicas.iter_mut().map(|w| w.close()));
+        let (primary, replicas) = join(self.primary.close(), replicas).await;
+        report([primary].into_iter().chain(replicas).collect())
+    }
+
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        // Replicas have their own upload state, so resuming via the
+        // primary's checkpoint is only possible without them.
+        if self.replicas.is_empty() {
+            self.primary.checkpoint()
+        } else {
+            None
+        }
+    }
+}
+
+/// ReplicaAppender appends the same content to the primary and all replicas.
+pub struct ReplicaAppender<A> {
+    primary: A,
+    replicas: Vec<oio::Appender>,
+}
+
+#[async_trait]
+impl<A: oio::Append> oio::Append for ReplicaAppender<A> {
+    async fn append(&mut self, bs: Bytes) -> Result<()> {
+        // Appended content can't be rolled back, report which replicas
+        // have diverged instead.
+        let replicas = join_all(self.replicas.iter_mut().map(|a| a.append(bs.clone())));
+        let (primary, replicas) = join(self.primary.append(bs.clone()), replicas).await;
+        report([primary].into_iter().chain(replicas).collect())
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        let replicas = join_all(self.replicas.iter_mut().map(|a| a.close()));
+        let (primary, replicas) = join(self.primary.close(), replicas).await;
+        report([primary].into_iter().chain(replicas).collect())
+    }
+}
+
+impl<W: oio::BlockingWrite> oio::BlockingWrite for ReplicaWriter<W, oio::BlockingWriter> {
+    fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.primary.write(bs.clone())?;
+        for w in self.replicas.iter_mut() {
+            w.write(bs.clone())?;
+        }
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<()> {
+        let primary = self.primary.close();
+        let replicas = self.replicas.iter_mut().map(|w| w.close());
+        report([primary].into_iter().chain(replicas).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[derive(Debug)]
+    struct MockService;
+
+    #[async_trait]
+    impl Accessor for MockService {
+        type Reader = MockReader;
+        type BlockingReader = ();
+        type Writer = ();
+        type BlockingWriter = ();
+        type Appender = ();
+        type Pager = ();
+        type BlockingPager = ();
+
+        fn info(&self) -> AccessorInfo {
+            AccessorInfo::default()
+        }
+
+        async fn stat(&self, _: &str, _: OpStat) -> Result<RpStat> {
+            Err(Error::new(ErrorKind::Unexpected, "service is down").set_temporary())
+        }
+
+        async fn read(&self, _: &str, _: OpRead) -> Result<(RpRead, Self::Reader)> {
+            Ok((RpRead::new(13), MockReader(false)))
+        }
+    }
+
+    /// MockReader returns some content and then breaks.
+    struct MockReader(bool);
+
+    impl oio::Read for MockReader {
+        fn poll_read(&mut self, _: &mut Context<'_>, _: &mut [u8]) -> Poll<Result<usize>> {
+            unimplemented!()
+        }
+
+        fn poll_seek(&mut self, _: &mut Context<'_>, _: SeekFrom) -> Poll<Result<u64>> {
+            unimplemented!()
+        }
+
+        fn poll_next(&mut self, _: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+            if self.0 {
+                return Poll::Ready(Some(Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "connection reset",
+                )
+                .set_temporary())));
+            }
+            self.0 = true;
+            Poll::Ready(Some(Ok(Bytes::from("Hello, "))))
+        }
+    }
+
+    /// MockWriter records chunks it received.
+    #[derive(Clone, Default)]
+    struct MockWriter(Arc<std::sync::Mutex<Vec<Bytes>>>);
+
+    #[async_trait]
+    impl oio::Write for MockWriter {
+        async fn write(&mut self, bs: Bytes) -> Result<()> {
+            self.0.lock().unwrap().push(bs);
+            Ok(())
+        }
+
+        async fn sink(&mut self, _: u64, mut s: oio::Streamer) -> Result<()> {
+            while let Some(bs) = s.next().await {
+                self.write(bs?).await?;
+            }
+            Ok(())
+        }
+
+        async fn abort(&mut self) -> Result<()> {
+            self.0.lock().unwrap().clear();
+            Ok(())
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failover() -> Result<()> {
+        let replica = Operator::new(Memory::default())?.finish();
+        replica.write("test", "Hello, World!").await?;
+
+        let acc = ReplicaLayer::new([replica]).layer(MockService);
+        let rp = Accessor::stat(&acc, "test", OpStat::new()).await?;
+        assert_eq!(rp.into_metadata().content_length(), 13);
+
+        // Permanent errors should not fail over.
+        let err = Accessor::stat(&acc, "not_exist", OpStat::new())
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_failover_while_reading() -> Result<()> {
+        let replica = Operator::new(Memory::default())?.finish();
+        replica.write("test", "Hello, World!").await?;
+
+        let acc = ReplicaLayer::new([replica]).layer(MockService);
+        let (_, mut r) = Accessor::read(&acc, "test", OpRead::new()).await?;
+        let mut content = Vec::new();
+        while let Some(bs) = r.next().await {
+            content.extend_from_slice(&bs?);
+        }
+        assert_eq!(content, b"Hello, World!");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sink_to_all() -> Result<()> {
+        let (primary, replica) = (MockWriter::default(), MockWriter::default());
+        let mut w = ReplicaWriter {
+            primary: primary.clone(),
+            replicas: vec![Box::new(replica.clone()) as oio::Writer],
+        };
+
+        let chunks = vec![Bytes::from("Hello, "), Bytes::from("World!")];
+        let s = futures::stream::iter(chunks.clone().into_iter().map(Ok));
+        oio::Write::sink(&mut w, 13, Box::new(oio::into_stream(s))).await?;
+
+        // Chunks are teed as is instead of being buffered into one.
+        assert_eq!(*primary.0.lock().unwrap(), chunks);
+        assert_eq!(*replica.0.lock().unwrap(), chunks);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_to_all() -> Result<()> {
+        let replica = Operator::new(Memory::default())?.finish();
+        let op = Operator::new(Memory::default())?
+            .layer(ReplicaLayer::new([replica.clone()]).with_write_mode(ReplicaWriteMode::All))
+            .finish();
+
+        op.write("test", "Hello, World!").await?;
+        assert_eq!(replica.read("test").await?, b"Hello, World!");
+
+        op.delete("test").await?;
+        assert!(!replica.is_exist("test").await?);
+        Ok(())
+    }
+
+    #[cfg(feature = "services-fs")]
+    #[tokio::test]
+    async fn test_append_to_all() -> Result<()> {
+        use crate::services::Fs;
+
+        let roots: Vec<_> = ["primary", "replica"]
+            .iter()
+            .map(|name| std::env::temp_dir().join(format!("{name}-{}", uuid::Uuid::new_v4())))
+            .collect();
+        let fs = |root: &std::path::Path| {
+            let mut builder = Fs::default();
+            builder.root(&root.to_string_lossy());
+            Operator::new(builder).map(|v| v.finish())
+        };
+
+        let replica = fs(&roots[1])?;
+        let op = fs(&roots[0])?
+            .layer(ReplicaLayer::new([replica.clone()]).with_write_mode(ReplicaWriteMode::All));
+
+        let res = async {
+            op.append("test", "Hello, ").await?;
+            op.append("test", "World!").await?;
+            assert_eq!(op.read("test").await?, b"Hello, World!");
+            assert_eq!(replica.read("test").await?, b"Hello, World!");
+            Ok(())
+        }
+        .await;
+
+        for root in roots {
+            let _ = std::fs::remove_dir_all(root);
+        }
+        res
+    }
+}
@@ -34,6 +34,7 @@ use backon::Retryable;
 use bytes::Bytes;
 use futures::FutureExt;
 use log::warn;
+use parking_lot::Mutex;
 
 use crate::raw::oio::AppendOperation;
 use crate::raw::oio::PageOperation;
@@ -47,9 +48,13 @@ use crate::*;
 /// # Notes
 ///
 /// This layer will retry failed operations when [`Error::is_temporary`]
-/// returns true. If operation still failed, this layer will set error to
+/// returns true or the HTTP status of error has been registered via
+/// [`RetryLayer::retry_on_status`]. If operation still failed, this layer will set error to
 /// `Persistent` which means error has been retried.
 ///
+/// Retries will wait at least [`Error::retry_after`] if the service asks us
+/// to slow down.
+///
 /// `write` and `blocking_write` don't support retry so far, visit [this issue](https://github.com/apache/incubator-opendal/issues/1223) for more details.
 ///
 /// # Examples
@@ -99,6 +104,7 @@ use crate::*;
 pub struct RetryLayer<I = DefaultRetryInterceptor> {
     builder: ExponentialBuilder,
     notify: Arc<I>,
+    retry_on_status: Arc<Vec<u16>>,
 }
 
 impl<I> Clone for RetryLayer<I> {
@@ -106,6 +112,7 @@ impl<I> Clone for RetryLayer<I> {
         Self {
             builder: self.builder.clone(),
             notify: self.notify.clone(),
+            retry_on_status: self.retry_on_status.clone(),
         }
     }
 }
@@ -115,6 +122,7 @@ impl Default for RetryLayer {
         Self {
             builder: ExponentialBuilder::default(),
             notify: Arc::new(DefaultRetryInterceptor),
+            retry_on_status: Arc::new(Vec::new()),
         }
     }
 }
@@ -168,6 +176,7 @@ impl RetryLayer {
         RetryLayer {
             builder: self.builder,
             notify: Arc::new(notify),
+            retry_on_status: self.retry_on_status,
         }
     }
 
@@ -213,6 +222,29 @@ impl RetryLayer {
     }
 }
 
+impl<I> RetryLayer<I> {
+    /// Retry errors caused by responses with given HTTP status codes too.
+    ///
+    /// Some S3 compatible services return non-standard status codes like
+    /// `599` for temporary failures. Errors with these status codes will be
+    /// retried even if they are not marked as temporary by the service.
+    ///
+    /// ```
+    /// use opendal::layers::RetryLayer;
+    /// use opendal::services;
+    /// use opendal::Operator;
+    ///
+    /// let _ = Operator::new(services::Memory::default())
+    ///     .expect("must init")
+    ///     .layer(RetryLayer::new().retry_on_status([599, 500]))
+    ///     .finish();
+    /// ```
+    pub fn retry_on_status(mut self, statuses: impl IntoIterator<Item = u16>) -> Self {
+        Arc::make_mut(&mut self.retry_on_status).extend(statuses);
+        self
+    }
+}
+
 impl<A: Accessor, I: RetryInterceptor> Layer<A> for RetryLayer<I> {
     type LayeredAccessor = RetryAccessor<A, I>;
 
@@ -221,6 +253,7 @@ impl<A: Accessor, I: RetryInterceptor> Layer<A> for RetryLayer<I> {
             inner,
             builder: self.builder.clone(),
             notify: self.notify.clone(),
+            retry_on_status: self.retry_on_status.clone(),
         }
     }
 }
@@ -268,6 +301,7 @@ pub struct RetryAccessor<A: Accessor, I: RetryInterceptor> {
     inner: A,
     builder: ExponentialBuilder,
     notify: Arc<I>,
+    retry_on_status: Arc<Vec<u16>>,
 }
 
 impl<A: Accessor, I: RetryInterceptor> Debug for RetryAccessor<A, I> {
@@ -294,9 +328,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
         { || self.inner.create_dir(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
             .notify(|err, dur: Duration| {
                 self.notify.intercept(
                     err,
@@ -312,9 +347,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
         { || self.inner.read(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -326,7 +362,13 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
                 v.map(|(rp, r)| {
                     (
                         rp,
-                        RetryWrapper::new(r, self.notify.clone(), path, self.builder.clone()),
+                        RetryWrapper::new(
+                            r,
+                            self.notify.clone(),
+                            path,
+                            self.builder.clone(),
+                            self.retry_on_status.clone(),
+                        ),
                     )
                 })
                 .map_err(|e| e.set_persistent())
@@ -338,9 +380,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     ///
     /// Allowing users to retry the write request from upper logic.
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
         { || self.inner.write(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -355,7 +398,13 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
                 v.map(|(rp, r)| {
                     (
                         rp,
-                        RetryWrapper::new(r, self.notify.clone(), path, self.builder.clone()),
+                        RetryWrapper::new(
+                            r,
+                            self.notify.clone(),
+                            path,
+                            self.builder.clone(),
+                            self.retry_on_status.clone(),
+                        ),
                     )
                 })
                 .map_err(|e| e.set_persistent())
@@ -364,9 +413,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
         { || self.inner.append(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -381,7 +431,13 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
                 v.map(|(rp, r)| {
                     (
                         rp,
-                        RetryWrapper::new(r, self.notify.clone(), path, self.builder.clone()),
+                        RetryWrapper::new(
+                            r,
+                            self.notify.clone(),
+                            path,
+                            self.builder.clone(),
+                            self.retry_on_status.clone(),
+                        ),
                     )
                 })
                 .map_err(|e| e.set_persistent())
@@ -390,9 +446,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
         { || self.inner.stat(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -405,9 +462,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
         { || self.inner.delete(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -422,10 +480,49 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
             .await
     }
 
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
+        { || self.inner.get_tags(path, args.clone()) }
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
+            .notify(|err, dur| {
+                self.notify.intercept(
+                    err,
+                    dur,
+                    &[
+                        ("operation", Operation::GetTags.into_static()),
+                        ("path", path),
+                    ],
+                )
+            })
+            .map(|v| v.map_err(|e| e.set_persistent()))
+            .await
+    }
+
+    async fn put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
+        { || self.inner.put_tags(path, args.clone()) }
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
+            .notify(|err, dur| {
+                self.notify.intercept(
+                    err,
+                    dur,
+                    &[
+                        ("operation", Operation::PutTags.into_static()),
+                        ("path", path),
+                    ],
+                )
+            })
+            .map(|v| v.map_err(|e| e.set_persistent()))
+            .await
+    }
+
     async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
         { || self.inner.copy(from, to, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -442,9 +539,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
         { || self.inner.rename(from, to, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -461,9 +559,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
         { || self.inner.list(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -473,8 +572,13 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
             })
             .map(|v| {
                 v.map(|(l, p)| {
-                    let pager =
-                        RetryWrapper::new(p, self.notify.clone(), path, self.builder.clone());
+                    let pager = RetryWrapper::new(
+                        p,
+                        self.notify.clone(),
+                        path,
+                        self.builder.clone(),
+                        self.retry_on_status.clone(),
+                    );
                     (l, pager)
                 })
                 .map_err(|e| e.set_persistent())
@@ -483,6 +587,7 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
         {
             || async {
                 let rp = self.inner.batch(args.clone()).await?;
@@ -494,8 +599,8 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
                 Ok(RpBatch::new(nrp))
             }
         }
-        .retry(&self.builder)
-        .when(|e: &Error| e.is_temporary())
+        .retry(&backoff)
+        .when(|e: &Error| backoff.should_retry(e, &self.retry_on_status))
         .notify(|err, dur| {
             self.notify.intercept(
                 err,
@@ -511,9 +616,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
         { || self.inner.blocking_create_dir(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -529,9 +635,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
         { || self.inner.blocking_read(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -546,16 +653,23 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
             .map(|(rp, r)| {
                 (
                     rp,
-                    RetryWrapper::new(r, self.notify.clone(), path, self.builder.clone()),
+                    RetryWrapper::new(
+                        r,
+                        self.notify.clone(),
+                        path,
+                        self.builder.clone(),
+                        self.retry_on_status.clone(),
+                    ),
                 )
             })
             .map_err(|e| e.set_persistent())
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
         { || self.inner.blocking_write(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -570,16 +684,23 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
             .map(|(rp, r)| {
                 (
                     rp,
-                    RetryWrapper::new(r, self.notify.clone(), path, self.builder.clone()),
+                    RetryWrapper::new(
+                        r,
+                        self.notify.clone(),
+                        path,
+                        self.builder.clone(),
+                        self.retry_on_status.clone(),
+                    ),
                 )
             })
             .map_err(|e| e.set_persistent())
     }
 
     fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
         { || self.inner.blocking_stat(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -595,9 +716,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
         { || self.inner.blocking_delete(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -613,9 +735,10 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
         { || self.inner.blocking_list(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -628,11 +751,138 @@ impl<A: Accessor, I: RetryInterceptor> LayeredAccessor for RetryAccessor<A, I> {
             })
             .call()
             .map(|(rp, p)| {
-                let p = RetryWrapper::new(p, self.notify.clone(), path, self.builder.clone());
+                let p = RetryWrapper::new(
+                    p,
+                    self.notify.clone(),
+                    path,
+                    self.builder.clone(),
+                    self.retry_on_status.clone(),
+                );
                 (rp, p)
             })
             .map_err(|e| e.set_persistent())
     }
+
+    fn blocking_get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
+        { || self.inner.blocking_get_tags(path, args.clone()) }
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
+            .notify(|err, dur| {
+                self.notify.intercept(
+                    err,
+                    dur,
+                    &[
+                        ("operation", Operation::BlockingGetTags.into_static()),
+                        ("path", path),
+                    ],
+                )
+            })
+            .call()
+            .map_err(|e| e.set_persistent())
+    }
+
+    fn blocking_put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
+        { || self.inner.blocking_put_tags(path, args.clone()) }
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
+            .notify(|err, dur| {
+                self.notify.intercept(
+                    err,
+                    dur,
+                    &[
+                        ("operation", Operation::BlockingPutTags.into_static()),
+                        ("path", path),
+                    ],
+                )
+            })
+            .call()
+            .map_err(|e| e.set_persistent())
+    }
+}
+
+/// Check if the error should be retried.
+///
+/// Errors with status in `retry_on_status` will be retried even if they
+/// are not temporary, unless they have been retried already by inner
+/// layers and marked as persistent.
+fn is_retryable(err: &Error, retry_on_status: &[u16]) -> bool {
+    // `Option::is_some_and` requires rust 1.70 which is newer than our MSRV.
+    err.is_temporary()
+        || (!err.is_persistent()
+            && matches!(err.http_status(), Some(v) if retry_on_status.contains(&v)))
+}
+
+/// Returns the delay before next retry.
+///
+/// We will wait at least the `Retry-After` duration returned by the
+/// service, so that we won't hit the rate limit again.
+fn retry_delay(err: &Error, dur: Duration) -> Duration {
+    match err.retry_after() {
+        Some(v) => dur.max(v),
+        None => dur,
+    }
+}
+
+/// RetryAfterBuilder builds exponential backoffs that wait at least the
+/// `Retry-After` duration of the last retried error.
+///
+/// It's created for every operation so that the `Retry-After` won't be
+/// shared between concurrent operations.
+#[derive(Clone, Debug)]
+struct RetryAfterBuilder {
+    inner: ExponentialBuilder,
+    retry_after: Arc<Mutex<Option<Duration>>>,
+}
+
+impl RetryAfterBuilder {
+    fn new(builder: &ExponentialBuilder) -> Self {
+        Self {
+            inner: builder.clone(),
+            retry_after: Arc::default(),
+        }
+    }
+
+    /// Check if the error should be retried, and record its `Retry-After`
+    /// for the next backoff.
+    fn should_retry(&self, err: &Error, retry_on_status: &[u16]) -> bool {
+        if !is_retryable(err, retry_on_status) {
+            return false;
+        }
+
+        *self.retry_after.lock() = err.retry_after();
+        true
+    }
+}
+
+impl BackoffBuilder for RetryAfterBuilder {
+    type Backoff = RetryAfterBackoff;
+
+    fn build(&self) -> Self::Backoff {
+        RetryAfterBackoff {
+            inner: self.inner.build(),
+            retry_after: self.retry_after.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RetryAfterBackoff {
+    inner: ExponentialBackoff,
+    retry_after: Arc<Mutex<Option<Duration>>>,
+}
+
+impl Iterator for RetryAfterBackoff {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let dur = self.inner.next()?;
+        Some(match self.retry_after.lock().take() {
+            Some(v) => dur.max(v),
+            None => dur,
+        })
+    }
 }
 
 pub struct RetryWrapper<R, I> {
@@ -641,18 +891,26 @@ pub struct RetryWrapper<R, I> {
 
     path: String,
     builder: ExponentialBuilder,
+    retry_on_status: Arc<Vec<u16>>,
     current_backoff: Option<ExponentialBackoff>,
     sleep: Option<Pin<Box<tokio::time::Sleep>>>,
 }
 
 impl<R, I> RetryWrapper<R, I> {
-    fn new(inner: R, notify: Arc<I>, path: &str, backoff: ExponentialBuilder) -> Self {
+    fn new(
+        inner: R,
+        notify: Arc<I>,
+        path: &str,
+        backoff: ExponentialBuilder,
+        retry_on_status: Arc<Vec<u16>>,
+    ) -> Self {
         Self {
             inner,
             notify,
 
             path: path.to_string(),
             builder: backoff,
+            retry_on_status,
             current_backoff: None,
             sleep: None,
         }
@@ -671,7 +929,7 @@ impl<R: oio::Read, I: RetryInterceptor> oio::Read for RetryWrapper<R, I> {
                 self.current_backoff = None;
                 Poll::Ready(Ok(v))
             }
-            Err(err) if !err.is_temporary() => {
+            Err(err) if !is_retryable(&err, &self.retry_on_status) => {
                 self.current_backoff = None;
                 Poll::Ready(Err(err))
             }
@@ -690,6 +948,7 @@ impl<R: oio::Read, I: RetryInterceptor> oio::Read for RetryWrapper<R, I> {
                         Poll::Ready(Err(err))
                     }
                     Some(dur) => {
+                        let dur = retry_delay(&err, dur);
                         self.notify.intercept(
                             &err,
                             dur,
@@ -717,7 +976,7 @@ impl<R: oio::Read, I: RetryInterceptor> oio::Read for RetryWrapper<R, I> {
                 self.current_backoff = None;
                 Poll::Ready(Ok(v))
             }
-            Err(err) if !err.is_temporary() => {
+            Err(err) if !is_retryable(&err, &self.retry_on_status) => {
                 self.current_backoff = None;
                 Poll::Ready(Err(err))
             }
@@ -736,6 +995,7 @@ impl<R: oio::Read, I: RetryInterceptor> oio::Read for RetryWrapper<R, I> {
                         Poll::Ready(Err(err))
                     }
                     Some(dur) => {
+                        let dur = retry_delay(&err, dur);
                         self.notify.intercept(
                             &err,
                             dur,
@@ -767,7 +1027,7 @@ impl<R: oio::Read, I: RetryInterceptor> oio::Read for RetryWrapper<R, I> {
                 self.current_backoff = None;
                 Poll::Ready(Some(Ok(v)))
             }
-            Some(Err(err)) if !err.is_temporary() => {
+            Some(Err(err)) if !is_retryable(&err, &self.retry_on_status) => {
                 self.current_backoff = None;
                 Poll::Ready(Some(Err(err)))
             }
@@ -786,6 +1046,7 @@ impl<R: oio::Read, I: RetryInterceptor> oio::Read for RetryWrapper<R, I> {
                         Poll::Ready(Some(Err(err)))
                     }
                     Some(dur) => {
+                        let dur = retry_delay(&err, dur);
                         self.notify.intercept(
                             &err,
                             dur,
@@ -805,9 +1066,10 @@ impl<R: oio::Read, I: RetryInterceptor> oio::Read for RetryWrapper<R, I> {
 
 impl<R: oio::BlockingRead, I: RetryInterceptor> oio::BlockingRead for RetryWrapper<R, I> {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
         { || self.inner.read(buf) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -823,9 +1085,10 @@ impl<R: oio::BlockingRead, I: RetryInterceptor> oio::BlockingRead for RetryWrapp
     }
 
     fn seek(&mut self, pos: io::SeekFrom) -> Result<u64> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
         { || self.inner.seek(pos) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -841,9 +1104,10 @@ impl<R: oio::BlockingRead, I: RetryInterceptor> oio::BlockingRead for RetryWrapp
     }
 
     fn next(&mut self) -> Option<Result<Bytes>> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
         { || self.inner.next().transpose() }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -868,10 +1132,11 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
         loop {
             match self.inner.write(bs.clone()).await {
                 Ok(v) => return Ok(v),
-                Err(e) if !e.is_temporary() => return Err(e),
+                Err(e) if !is_retryable(&e, &self.retry_on_status) => return Err(e),
                 Err(e) => match backoff.next() {
                     None => return Err(e),
                     Some(dur) => {
+                        let dur = retry_delay(&e, dur);
                         self.notify.intercept(
                             &e,
                             dur,
@@ -899,10 +1164,11 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
         loop {
             match self.inner.abort().await {
                 Ok(v) => return Ok(v),
-                Err(e) if !e.is_temporary() => return Err(e),
+                Err(e) if !is_retryable(&e, &self.retry_on_status) => return Err(e),
                 Err(e) => match backoff.next() {
                     None => return Err(e),
                     Some(dur) => {
+                        let dur = retry_delay(&e, dur);
                         self.notify.intercept(
                             &e,
                             dur,
@@ -925,10 +1191,11 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
         loop {
             match self.inner.close().await {
                 Ok(v) => return Ok(v),
-                Err(e) if !e.is_temporary() => return Err(e),
+                Err(e) if !is_retryable(&e, &self.retry_on_status) => return Err(e),
                 Err(e) => match backoff.next() {
                     None => return Err(e),
                     Some(dur) => {
+                        let dur = retry_delay(&e, dur);
                         self.notify.intercept(
                             &e,
                             dur,
@@ -944,13 +1211,18 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
             }
         }
     }
+
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        self.inner.checkpoint()
+    }
 }
 
 impl<R: oio::BlockingWrite, I: RetryInterceptor> oio::BlockingWrite for RetryWrapper<R, I> {
     fn write(&mut self, bs: Bytes) -> Result<()> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
         { || self.inner.write(bs.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -966,9 +1238,10 @@ impl<R: oio::BlockingWrite, I: RetryInterceptor> oio::BlockingWrite for RetryWra
     }
 
     fn close(&mut self) -> Result<()> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
         { || self.inner.close() }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -992,10 +1265,11 @@ impl<A: oio::Append, I: RetryInterceptor> oio::Append for RetryWrapper<A, I> {
         loop {
             match self.inner.append(bs.clone()).await {
                 Ok(v) => return Ok(v),
-                Err(e) if !e.is_temporary() => return Err(e),
+                Err(e) if !is_retryable(&e, &self.retry_on_status) => return Err(e),
                 Err(e) => match backoff.next() {
                     None => return Err(e),
                     Some(dur) => {
+                        let dur = retry_delay(&e, dur);
                         self.notify.intercept(
                             &e,
                             dur,
@@ -1018,10 +1292,11 @@ impl<A: oio::Append, I: RetryInterceptor> oio::Append for RetryWrapper<A, I> {
         loop {
             match self.inner.close().await {
                 Ok(v) => return Ok(v),
-                Err(e) if !e.is_temporary() => return Err(e),
+                Err(e) if !is_retryable(&e, &self.retry_on_status) => return Err(e),
                 Err(e) => match backoff.next() {
                     None => return Err(e),
                     Some(dur) => {
+                        let dur = retry_delay(&e, dur);
                         self.notify.intercept(
                             &e,
                             dur,
@@ -1047,10 +1322,11 @@ impl<P: oio::Page, I: RetryInterceptor> oio::Page for RetryWrapper<P, I> {
         loop {
             match self.inner.next().await {
                 Ok(v) => return Ok(v),
-                Err(e) if !e.is_temporary() => return Err(e),
+                Err(e) if !is_retryable(&e, &self.retry_on_status) => return Err(e),
                 Err(e) => match backoff.next() {
                     None => return Err(e),
                     Some(dur) => {
+                        let dur = retry_delay(&e, dur);
                         self.notify.intercept(
                             &e,
                             dur,
@@ -1066,13 +1342,18 @@ impl<P: oio::Page, I: RetryInterceptor> oio::Page for RetryWrapper<P, I> {
             }
         }
     }
+
+    fn continuation_token(&self) -> Option<String> {
+        self.inner.continuation_token()
+    }
 }
 
 impl<P: oio::BlockingPage, I: RetryInterceptor> oio::BlockingPage for RetryWrapper<P, I> {
     fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        let backoff = RetryAfterBuilder::new(&self.builder);
         { || self.inner.next() }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e, &self.retry_on_status))
             .notify(|err, dur| {
                 self.notify.intercept(
                     err,
@@ -1147,6 +1428,7 @@ mod tests {
                 list_with_delimiter_slash: true,
                 list_without_delimiter: true,
                 batch: true,
+                get_tags: true,
                 ..Default::default()
             });
 
@@ -1168,6 +1450,22 @@ mod tests {
             Ok((RpList::default(), pager))
         }
 
+        async fn get_tags(&self, _: &str, _: OpGetTags) -> Result<RpGetTags> {
+            let mut attempt = self.attempt.lock().unwrap();
+            *attempt += 1;
+
+            match *attempt {
+                1 => Err(
+                    Error::new(ErrorKind::Unexpected, "retryable_error from get_tags")
+                        .set_temporary(),
+                ),
+                _ => Ok(RpGetTags::new(HashMap::from([(
+                    "project".to_string(),
+                    "opendal".to_string(),
+                )]))),
+            }
+        }
+
         async fn batch(&self, op: OpBatch) -> Result<RpBatch> {
             let mut attempt = self.attempt.lock().unwrap();
             *attempt += 1;
@@ -1367,6 +1665,21 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[tokio::test]
+    async fn test_retry_get_tags() {
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+        let builder = MockBuilder::default();
+        let op = Operator::new(builder.clone())
+            .unwrap()
+            .layer(RetryLayer::new().with_min_delay(Duration::from_millis(10)))
+            .finish();
+
+        let tags = op.get_tags("retryable_error").await.unwrap();
+        assert_eq!(tags.get("project").map(|v| v.as_str()), Some("opendal"));
+        assert_eq!(*builder.attempt.lock().unwrap(), 2);
+    }
+
     #[tokio::test]
     async fn test_retry_batch() {
         let _ = tracing_subscriber::fmt().with_test_writer().try_init();
@@ -1391,4 +1704,62 @@ mod tests {
         op.remove(paths).await.expect("batch must succeed");
         assert_eq!(*builder.attempt.lock().unwrap(), 5);
     }
+
+    #[test]
+    fn test_retry_delay() {
+        let err = Error::new(ErrorKind::Unexpected, "retryable_error").set_temporary();
+        assert_eq!(
+            retry_delay(&err, Duration::from_secs(1)),
+            Duration::from_secs(1)
+        );
+
+        let err = Error::new(ErrorKind::RateLimited, "slow down")
+            .with_context("retry_after", "3s")
+            .set_temporary();
+        assert_eq!(
+            retry_delay(&err, Duration::from_secs(1)),
+            Duration::from_secs(3)
+        );
+        assert_eq!(
+            retry_delay(&err, Duration::from_secs(5)),
+            Duration::from_secs(5)
+        );
+    }
+
+    #[test]
+    fn test_retry_after_backoff() {
+        let builder = RetryAfterBuilder {
+            inner: ExponentialBuilder::default().with_min_delay(Duration::from_millis(1)),
+            retry_after: Arc::default(),
+        };
+        let mut backoff = builder.build();
+
+        let err = Error::new(ErrorKind::RateLimited, "slow down")
+            .with_context("retry_after", "3s")
+            .set_temporary();
+        assert!(builder.should_retry(&err, &[]));
+        assert!(backoff.next().unwrap() >= Duration::from_secs(3));
+        // Retry-After only applies to the next retry.
+        assert!(backoff.next().unwrap() < Duration::from_secs(3));
+
+        let err =
+            Error::new(ErrorKind::Unexpected, "not retryable").with_context("retry_after", "3s");
+        assert!(!builder.should_retry(&err, &[]));
+        assert!(backoff.next().unwrap() < Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_is_retryable_with_status() {
+        let err = Error::new(ErrorKind::Unexpected, "vendor error").with_context("status", "599");
+        assert!(!is_retryable(&err, &[]));
+        assert!(is_retryable(&err, &[500, 599]));
+
+        let err = Error::new(ErrorKind::Unexpected, "temporary error").set_temporary();
+        assert!(is_retryable(&err, &[]));
+
+        let err = Error::new(ErrorKind::Unexpected, "vendor error")
+            .with_context("status", "599")
+            .set_persistent();
+        assert!(!is_retryable(&err, &[500, 599]));
+    }
 }
@@ -0,0 +1,545 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use flagset::FlagSet;
+
+use crate::raw::*;
+use crate::*;
+
+/// Serve `stat` from the metadata returned by `list`.
+///
+/// # Notes
+///
+/// Listing a dir and then stat its entries is a really common pattern,
+/// but it will send one extra request for every entry even if the list
+/// already returned their metadata.
+///
+/// StatCacheLayer will remember the metadata of listed entries for `ttl`,
+/// and serve following `stat` on the same path without touching the
+/// underlying storage:
+///
+/// - Only dirs and files with content length in list result will be cached.
+///   The cache only remembers the fields that list returned, a plain `stat`
+///   will be served with these fields, check [`Metadata::metakey`] for the
+///   populated ones. `stat_with(path).metakey(..)` that needs more fields
+///   will still be sent to the underlying storage.
+/// - `stat` with `if_match`, `if_none_match` or `version` will always
+///   bypass the cache.
+/// - `write`, `append`, `delete`, `copy`, `rename` and `create_dir` via the
+///   same operator will invalidate affected paths after they complete,
+///   deleting a dir invalidates all its children. Changes made by others
+///   will be visible only after `ttl`.
+/// - At most `capacity` entries will be cached, new entries will be dropped
+///   when the cache is full of unexpired entries.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use anyhow::Result;
+/// use opendal::layers::StatCacheLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(StatCacheLayer::new(Duration::from_secs(30)))
+///     .finish();
+/// ```
+#[derive(Debug, Clone)]
+pub struct StatCacheLayer {
+    ttl: Duration,
+    capacity: usize,
+}
+
+impl StatCacheLayer {
+    /// Create a new StatCacheLayer with given ttl.
+    ///
+    /// At most 10000 entries will be cached by default.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            capacity: 10000,
+        }
+    }
+
+    /// Set the max entries that could be cached.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+}
+
+impl<A: Accessor> Layer<A> for StatCacheLayer {
+    type LayeredAccessor = StatCacheAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        StatCacheAccessor {
+            inner,
+            cache: Arc::new(StatCache {
+                ttl: self.ttl,
+                capacity: self.capacity,
+                entries: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct StatCache {
+    ttl: Duration,
+    capacity: usize,
+    entries: Mutex<HashMap<String, (Instant, Metadata)>>,
+}
+
+impl StatCache {
+    /// Get the cached metadata.
+    ///
+    /// If `metakey` is given, the cached metadata must contain all of its
+    /// fields, otherwise the fields returned by list will be served.
+    fn get(&self, path: &str, metakey: Option<FlagSet<Metakey>>) -> Option<Metadata> {
+        let mut entries = self.entries.lock().expect("lock must be valid");
+        match entries.get(path) {
+            Some((expire_at, meta)) if *expire_at > Instant::now() => {
+                let bit = meta.bit();
+                if bit.contains(Metakey::Complete) || metakey.map_or(true, |v| bit.contains(v)) {
+                    Some(meta.clone())
+                } else {
+                    None
+                }
+            }
+            Some(_) => {
+                entries.remove(path);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, list: &[oio::Entry]) {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().expect("lock must be valid");
+        if entries.len() + list.len() > self.capacity {
+            entries.retain(|_, (expire_at, _)| *expire_at > now);
+        }
+
+        for de in list {
+            if entries.len() >= self.capacity {
+                break;
+            }
+
+            let meta = de.metadata();
+            if !de.mode().is_dir() && !meta.bit().contains(Metakey::ContentLength) {
+                continue;
+            }
+            entries.insert(de.path().to_string(), (now + self.ttl, meta.clone()));
+        }
+    }
+
+    /// Invalidate given path, all children will be invalidated too if
+    /// path is a dir.
+    fn invalidate(&self, path: &str) {
+        let mut entries = self.entries.lock().expect("lock must be valid");
+        if path.ends_with('/') {
+            entries.retain(|k, _| !k.starts_with(path));
+        } else {
+            entries.remove(path);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StatCacheAccessor<A: Accessor> {
+    inner: A,
+    cache: Arc<StatCache>,
+}
+
+impl<A: Accessor> StatCacheAccessor<A> {
+    fn cached(&self, path: &str, args: &OpStat) -> Option<RpStat> {
+        if args.if_match().is_some() || args.if_none_match().is_some() || args.version().is_some() {
+            return None;
+        }
+
+        let metakey = args.has_metakey().then(|| args.metakey());
+        self.cache.get(path, metakey).map(RpStat::new)
+    }
+
+    fn batch_paths(args: &OpBatch) -> Vec<String> {
+        args.operation().iter().map(|(p, _)| p.clone()).collect()
+    }
+
+    fn invalidate_paths(&self, paths: &[String]) {
+        for path in paths {
+            self.cache.invalidate(path);
+        }
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for StatCacheAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = StatCacheWrapper<A::Writer>;
+    type BlockingWriter = StatCacheWrapper<A::BlockingWriter>;
+    type Appender = StatCacheWrapper<A::Appender>;
+    type Pager = StatCachePager<A::Pager>;
+    type BlockingPager = StatCachePager<A::BlockingPager>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let res = self.inner.create_dir(path, args).await;
+        self.cache.invalidate(path);
+        res
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner
+            .write(path, args)
+            .await
+            .map(|(rp, w)| (rp, StatCacheWrapper::new(w, path, self.cache.clone())))
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        self.inner
+            .append(path, args)
+            .await
+            .map(|(rp, a)| (rp, StatCacheWrapper::new(a, path, self.cache.clone())))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let res = self.inner.copy(from, to, args).await;
+        self.cache.invalidate(to);
+        res
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let res = self.inner.rename(from, to, args).await;
+        self.cache.invalidate(from);
+        self.cache.invalidate(to);
+        res
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if let Some(rp) = self.cached(path, &args) {
+            return Ok(rp);
+        }
+        self.inner.stat(path, args).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let res = self.inner.delete(path, args).await;
+        self.cache.invalidate(path);
+        res
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner
+            .list(path, args)
+            .await
+            .map(|(rp, p)| (rp, StatCachePager::new(p, self.cache.clone())))
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let paths = Self::batch_paths(&args);
+        let res = self.inner.batch(args).await;
+        self.invalidate_paths(&paths);
+        res
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let res = self.inner.blocking_create_dir(path, args);
+        self.cache.invalidate(path);
+        res
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner
+            .blocking_write(path, args)
+            .map(|(rp, w)| (rp, StatCacheWrapper::new(w, path, self.cache.clone())))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let res = self.inner.blocking_copy(from, to, args);
+        self.cache.invalidate(to);
+        res
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let res = self.inner.blocking_rename(from, to, args);
+        self.cache.invalidate(from);
+        self.cache.invalidate(to);
+        res
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if let Some(rp) = self.cached(path, &args) {
+            return Ok(rp);
+        }
+        self.inner.blocking_stat(path, args)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let res = self.inner.blocking_delete(path, args);
+        self.cache.invalidate(path);
+        res
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner
+            .blocking_list(path, args)
+            .map(|(rp, p)| (rp, StatCachePager::new(p, self.cache.clone())))
+    }
+}
+
+/// StatCacheWrapper invalidates the path after the content has been
+/// changed, so that concurrent `stat` won't cache the old metadata again.
+pub struct StatCacheWrapper<R> {
+    inner: R,
+    path: String,
+    cache: Arc<StatCache>,
+}
+
+impl<R> StatCacheWrapper<R> {
+    fn new(inner: R, path: &str, cache: Arc<StatCache>) -> Self {
+        Self {
+            inner,
+            path: path.to_string(),
+            cache,
+        }
+    }
+}
+
+#[async_trait]
+impl<R: oio::Write> oio::Write for StatCacheWrapper<R> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.inner.write(bs).await
+    }
+
+    async fn sink(&mut self, size: u64, s: oio::Streamer) -> Result<()> {
+        self.inner.sink(size, s).await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        let res = self.inner.close().await;
+        self.cache.invalidate(&self.path);
+        res
+    }
+
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        self.inner.checkpoint()
+    }
+}
+
+impl<R: oio::BlockingWrite> oio::BlockingWrite for StatCacheWrapper<R> {
+    fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.inner.write(bs)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        let res = self.inner.close();
+        self.cache.invalidate(&self.path);
+        res
+    }
+}
+
+#[async_trait]
+impl<R: oio::Append> oio::Append for StatCacheWrapper<R> {
+    async fn append(&mut self, bs: Bytes) -> Result<()> {
+        let res = self.inner.append(bs).await;
+        self.cache.invalidate(&self.path);
+        res
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        let res = self.inner.close().await;
+        self.cache.invalidate(&self.path);
+        res
+    }
+}
+
+pub struct StatCachePager<P> {
+    inner: P,
+    cache: Arc<StatCache>,
+}
+
+impl<P> StatCachePager<P> {
+    fn new(inner: P, cache: Arc<StatCache>) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[async_trait]
+impl<P: oio::Page> oio::Page for StatCachePager<P> {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        let entries = self.inner.next().await?;
+        if let Some(entries) = &entries {
+            self.cache.insert(entries);
+        }
+        Ok(entries)
+    }
+
+    fn continuation_token(&self) -> Option<String> {
+        self.inner.continuation_token()
+    }
+}
+
+impl<P: oio::BlockingPage> oio::BlockingPage for StatCachePager<P> {
+    fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        let entries = self.inner.next()?;
+        if let Some(entries) = &entries {
+            self.cache.insert(entries);
+        }
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::services::Memory;
+    use crate::Operator;
+
+    fn new_cache(ttl: Duration) -> StatCache {
+        StatCache {
+            ttl,
+            capacity: 10,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stat_after_list() -> Result<()> {
+        let op = Operator::new(Memory::default())?
+            .layer(StatCacheLayer::new(Duration::from_secs(60)))
+            .finish();
+
+        op.create_dir("dir/sub/").await?;
+        op.write("dir/file", "hello").await?;
+
+        let _: Vec<_> = op.list("dir/").await?.try_collect().await?;
+
+        let meta = op.stat("dir/sub/").await?;
+        assert!(meta.is_dir());
+        let meta = op
+            .stat_with("dir/file")
+            .metakey(Metakey::ContentLength)
+            .await?;
+        assert_eq!(meta.content_length(), 5);
+
+        // Cache should be invalidated after the write has been closed.
+        op.write("dir/file", "hello, world").await?;
+        let meta = op
+            .stat_with("dir/file")
+            .metakey(Metakey::ContentLength)
+            .await?;
+        assert_eq!(meta.content_length(), 12);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cache_insert() {
+        let cache = new_cache(Duration::from_secs(60));
+        cache.insert(&[
+            oio::Entry::new("dir/", Metadata::new(EntryMode::DIR)),
+            oio::Entry::new(
+                "dir/a",
+                Metadata::new(EntryMode::FILE).with_content_length(1),
+            ),
+            oio::Entry::new("dir/b", Metadata::new(EntryMode::FILE)),
+        ]);
+
+        assert!(cache.get("dir/", Some(Metakey::Complete.into())).is_some());
+        let meta = cache
+            .get("dir/a", Some(Metakey::Mode | Metakey::ContentLength))
+            .expect("must be cached");
+        assert!(!meta.bit().contains(Metakey::Complete));
+        assert_eq!(meta.content_length(), 1);
+        // Plain stat will be served with fields returned by list.
+        let meta = cache.get("dir/a", None).expect("must be cached");
+        assert_eq!(meta.content_length(), 1);
+        // Fields not returned by list must be fetched from storage.
+        assert!(cache.get("dir/a", Some(Metakey::Complete.into())).is_none());
+        assert!(cache.get("dir/a", Some(Metakey::Etag.into())).is_none());
+        // Entry without content length should not be cached.
+        assert!(cache
+            .get("dir/b", Some(Metakey::Mode | Metakey::ContentLength))
+            .is_none());
+
+        cache.invalidate("dir/a");
+        assert!(cache.get("dir/a", Some(Metakey::Mode.into())).is_none());
+    }
+
+    #[test]
+    fn test_cache_invalidate_dir() {
+        let cache = new_cache(Duration::from_secs(60));
+        cache.insert(&[
+            oio::Entry::new("dir/", Metadata::new(EntryMode::DIR)),
+            oio::Entry::new("dir/sub/", Metadata::new(EntryMode::DIR)),
+            oio::Entry::new(
+                "dir/a",
+                Metadata::new(EntryMode::FILE).with_content_length(1),
+            ),
+            oio::Entry::new(
+                "other",
+                Metadata::new(EntryMode::FILE).with_content_length(1),
+            ),
+        ]);
+
+        cache.invalidate("dir/");
+        assert!(cache.get("dir/", Some(Metakey::Mode.into())).is_none());
+        assert!(cache.get("dir/sub/", Some(Metakey::Mode.into())).is_none());
+        assert!(cache.get("dir/a", Some(Metakey::Mode.into())).is_none());
+        assert!(cache.get("other", Some(Metakey::Mode.into())).is_some());
+    }
+
+    #[test]
+    fn test_cache_expire() {
+        let cache = new_cache(Duration::from_secs(0));
+        cache.insert(&[oio::Entry::new("dir/", Metadata::new(EntryMode::DIR))]);
+        assert!(cache.get("dir/", Some(Metakey::Mode.into())).is_none());
+    }
+}
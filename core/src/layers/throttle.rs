@@ -263,6 +263,10 @@ impl<R: oio::Write> oio::Write for ThrottleWrapper<R> {
     async fn close(&mut self) -> Result<()> {
         self.inner.close().await
     }
+
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        self.inner.checkpoint()
+    }
 }
 
 impl<R: oio::BlockingWrite> oio::BlockingWrite for ThrottleWrapper<R> {
@@ -383,6 +383,10 @@ impl<R: oio::Write> oio::Write for TimeoutWrapper<R> {
                     .set_temporary()
             })?
     }
+
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        self.inner.checkpoint()
+    }
 }
 
 #[async_trait]
@@ -424,4 +428,8 @@ impl<R: oio::Page> oio::Page for TimeoutWrapper<R> {
                     .set_temporary()
             })?
     }
+
+    fn continuation_token(&self) -> Option<String> {
+        self.inner.continuation_token()
+    }
 }
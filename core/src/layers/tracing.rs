@@ -356,6 +356,10 @@ impl<R: oio::Write> oio::Write for TracingWrapper<R> {
     async fn close(&mut self) -> Result<()> {
         self.inner.close().await
     }
+
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        self.inner.checkpoint()
+    }
 }
 
 impl<R: oio::BlockingWrite> oio::BlockingWrite for TracingWrapper<R> {
@@ -382,6 +386,10 @@ impl<R: oio::Page> oio::Page for TracingWrapper<R> {
     async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
         self.inner.next().await
     }
+
+    fn continuation_token(&self) -> Option<String> {
+        self.inner.continuation_token()
+    }
 }
 
 impl<R: oio::BlockingPage> oio::BlockingPage for TracingWrapper<R> {
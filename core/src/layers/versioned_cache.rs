@@ -0,0 +1,888 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::io;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use bytes::BytesMut;
+use tokio::runtime::Handle;
+
+use crate::raw::oio::ReadExt;
+use crate::raw::*;
+use crate::*;
+
+/// Cache the content of small objects by etag, optionally serving stale
+/// content while revalidating it in the background.
+///
+/// # Notes
+///
+/// VersionedCacheLayer is designed for read-heavy workloads like serving
+/// config files, where most reads will hit an unchanged object:
+///
+/// - Only whole object reads without `if_match`, `if_none_match`, `version`,
+///   overrides or custom headers will be cached. Objects without etag or
+///   larger than `max_object_size` will be passed through.
+/// - Within `ttl`, cached content will be served without touching the
+///   underlying storage.
+/// - Within `ttl` + `stale_while_revalidate`, cached content will be served
+///   immediately while a conditional read is sent in the background.
+/// - After that, a conditional read will be sent before serving. If the
+///   storage returns `ConditionNotMatch` (`304 Not Modified`), the cached
+///   content will be kept and served, otherwise the cache will be refreshed.
+/// - `write`, `append`, `delete`, `copy`, `rename` via the same operator
+///   will invalidate affected paths. Writers and appenders will invalidate
+///   the path again after closed, and on-going reads of a path started
+///   before its invalidation won't fill the cache.
+/// - Blocking reads will always bypass the cache.
+///
+/// Background revalidation is spawned on the current tokio runtime. Outside
+/// of a tokio runtime, stale content will be revalidated before serving
+/// instead.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use anyhow::Result;
+/// use opendal::layers::VersionedCacheLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(
+///         VersionedCacheLayer::new(Duration::from_secs(10))
+///             .with_stale_while_revalidate(Duration::from_secs(60)),
+///     )
+///     .finish();
+/// ```
+#[derive(Debug, Clone)]
+pub struct VersionedCacheLayer {
+    ttl: Duration,
+    stale_while_revalidate: Duration,
+    capacity: usize,
+    max_object_size: u64,
+}
+
+impl VersionedCacheLayer {
+    /// Create a new VersionedCacheLayer with given ttl.
+    ///
+    /// At most 1000 objects which are smaller than 4 MiB will be cached
+    /// by default.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            stale_while_revalidate: Duration::ZERO,
+            capacity: 1000,
+            max_object_size: 4 * 1024 * 1024,
+        }
+    }
+
+    /// Serve stale content for `duration` after `ttl` while revalidating
+    /// it in the background.
+    pub fn with_stale_while_revalidate(mut self, duration: Duration) -> Self {
+        self.stale_while_revalidate = duration;
+        self
+    }
+
+    /// Set the max objects that could be cached.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Set the max size of object that could be cached.
+    pub fn with_max_object_size(mut self, size: u64) -> Self {
+        self.max_object_size = size;
+        self
+    }
+}
+
+impl<A: Accessor> Layer<A> for VersionedCacheLayer {
+    type LayeredAccessor = VersionedCacheAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        VersionedCacheAccessor {
+            inner: Arc::new(inner),
+            cache: Arc::new(VersionedCache {
+                ttl: self.ttl,
+                stale_while_revalidate: self.stale_while_revalidate,
+                capacity: self.capacity,
+                max_object_size: self.max_object_size,
+                entries: Mutex::new(HashMap::new()),
+                fills: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    meta: Metadata,
+    content: Bytes,
+    etag: String,
+    fetched_at: Instant,
+    revalidating: bool,
+}
+
+enum Lookup {
+    Miss,
+    Fresh(CacheEntry),
+    /// The entry is stale but could be served. Caller should start a
+    /// revalidation if `bool` is true.
+    Stale(CacheEntry, bool),
+    /// The entry must be revalidated before serving.
+    Expired(CacheEntry),
+}
+
+/// On-going fills of the same path.
+#[derive(Debug, Default)]
+struct Fills {
+    /// Generation will be increased by every invalidation of this path,
+    /// fills started before that will be dropped.
+    generation: u64,
+    /// Number of on-going fills, the record will be removed once all of
+    /// them are finished.
+    pending: usize,
+}
+
+#[derive(Debug)]
+struct VersionedCache {
+    ttl: Duration,
+    stale_while_revalidate: Duration,
+    capacity: usize,
+    max_object_size: u64,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+    /// Only paths with on-going fills are tracked, so that invalidations
+    /// won't affect fills of other paths and the map stays small.
+    ///
+    /// Lock order: `entries` must be locked before `fills`.
+    fills: Mutex<HashMap<String, Fills>>,
+}
+
+/// FillGuard marks a fill of given path is on-going.
+struct FillGuard<'a> {
+    cache: &'a VersionedCache,
+    path: &'a str,
+    generation: u64,
+}
+
+impl Drop for FillGuard<'_> {
+    fn drop(&mut self) {
+        let mut fills = self.cache.fills.lock().expect("lock must be valid");
+        if let Some(f) = fills.get_mut(self.path) {
+            f.pending -= 1;
+            if f.pending == 0 {
+                fills.remove(self.path);
+            }
+        }
+    }
+}
+
+impl VersionedCache {
+    fn get(&self, path: &str) -> Lookup {
+        let mut entries = self.entries.lock().expect("lock must be valid");
+        let Some(entry) = entries.get_mut(path) else {
+            return Lookup::Miss;
+        };
+
+        let age = entry.fetched_at.elapsed();
+        if age < self.ttl {
+            Lookup::Fresh(entry.clone())
+        } else if age < self.ttl + self.stale_while_revalidate {
+            let revalidate = !entry.revalidating;
+            entry.revalidating = true;
+            Lookup::Stale(entry.clone(), revalidate)
+        } else {
+            Lookup::Expired(entry.clone())
+        }
+    }
+
+    fn peek(&self, path: &str) -> Option<CacheEntry> {
+        let entries = self.entries.lock().expect("lock must be valid");
+        entries.get(path).cloned()
+    }
+
+    /// Start a fill of given path, it's finished once the guard is dropped.
+    fn start_fill<'a>(&'a self, path: &'a str) -> FillGuard<'a> {
+        let mut fills = self.fills.lock().expect("lock must be valid");
+        let f = fills.entry(path.to_string()).or_default();
+        f.pending += 1;
+        FillGuard {
+            cache: self,
+            path,
+            generation: f.generation,
+        }
+    }
+
+    /// Insert the content if it's cacheable, return `false` if not.
+    ///
+    /// Content will be dropped if the path has been invalidated after the
+    /// fill started.
+    fn insert(&self, fill: &FillGuard, meta: Metadata, content: Bytes) -> bool {
+        let Some(etag) = etag_of(&meta).map(|v| v.to_string()) else {
+            return false;
+        };
+
+        let now = Instant::now();
+        let path = fill.path;
+        let mut entries = self.entries.lock().expect("lock must be valid");
+        // Invalidation bumps generation while holding the entries lock, so
+        // it can't happen between this check and the insert.
+        let generation = self
+            .fills
+            .lock()
+            .expect("lock must be valid")
+            .get(path)
+            .map(|f| f.generation);
+        if generation != Some(fill.generation) {
+            return false;
+        }
+        if !entries.contains_key(path) && entries.len() >= self.capacity {
+            let expire = self.ttl + self.stale_while_revalidate;
+            entries.retain(|_, e| now.duration_since(e.fetched_at) < expire);
+            if entries.len() >= self.capacity {
+                return false;
+            }
+        }
+
+        entries.insert(
+            path.to_string(),
+            CacheEntry {
+                meta,
+                content,
+                etag,
+                fetched_at: now,
+                revalidating: false,
+            },
+        );
+        true
+    }
+
+    /// Mark the entry as fresh again if it still has the same etag.
+    fn touch(&self, path: &str, etag: &str) {
+        let mut entries = self.entries.lock().expect("lock must be valid");
+        if let Some(entry) = entries.get_mut(path) {
+            if entry.etag == etag {
+                entry.fetched_at = Instant::now();
+                entry.revalidating = false;
+            }
+        }
+    }
+
+    /// Allow the entry to be revalidated again.
+    fn release(&self, path: &str) {
+        let mut entries = self.entries.lock().expect("lock must be valid");
+        if let Some(entry) = entries.get_mut(path) {
+            entry.revalidating = false;
+        }
+    }
+
+    /// Remove the cached entry only, on-going fills are not affected.
+    fn remove(&self, path: &str) {
+        let mut entries = self.entries.lock().expect("lock must be valid");
+        entries.remove(path);
+    }
+
+    /// Remove the cached entry and drop on-going fills of this path.
+    fn invalidate(&self, path: &str) {
+        let mut entries = self.entries.lock().expect("lock must be valid");
+        if let Some(f) = self.fills.lock().expect("lock must be valid").get_mut(path) {
+            f.generation += 1;
+        }
+        entries.remove(path);
+    }
+
+    /// Check if given read could be cached.
+    fn is_cacheable(args: &OpRead) -> bool {
+        args.range().is_full()
+            && args.if_match().is_none()
+            && args.if_none_match().is_none()
+            && args.version().is_none()
+            && args.override_cache_control().is_none()
+            && args.override_content_disposition().is_none()
+            && args.headers().is_empty()
+    }
+
+    /// Check if the object returned by read could be cached.
+    fn accepts(&self, meta: &Metadata) -> bool {
+        etag_of(meta).is_some()
+            && meta.bit().contains(Metakey::ContentLength)
+            && meta.content_length() <= self.max_object_size
+    }
+}
+
+/// Metadata returned by read could be partial, check the bit before visiting.
+fn etag_of(meta: &Metadata) -> Option<&str> {
+    if meta.bit().contains(Metakey::Etag) {
+        meta.etag()
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VersionedCacheAccessor<A: Accessor> {
+    inner: Arc<A>,
+    cache: Arc<VersionedCache>,
+}
+
+impl<A: Accessor> VersionedCacheAccessor<A> {
+    fn serve(entry: CacheEntry) -> (RpRead, VersionedCacheReader<A::Reader>) {
+        (
+            RpRead::with_metadata(entry.meta),
+            VersionedCacheReader::Cached(oio::Cursor::from(entry.content)),
+        )
+    }
+
+    /// Read the object from storage and fill the cache.
+    ///
+    /// If `etag` is given, a conditional read will be sent and the cached
+    /// entry will be served if the object is not modified.
+    async fn fetch(
+        &self,
+        path: &str,
+        etag: Option<&str>,
+    ) -> Result<(RpRead, VersionedCacheReader<A::Reader>)> {
+        let mut op = OpRead::new();
+        if let Some(etag) = etag {
+            op = op.with_if_none_match(etag);
+        }
+
+        match fill(&self.inner, &self.cache, path, op).await {
+            Ok(Filled::Cached(meta, content)) => Ok((
+                RpRead::with_metadata(meta),
+                VersionedCacheReader::Cached(oio::Cursor::from(content)),
+            )),
+            Ok(Filled::Bypass(rp, r)) => {
+                // The object has been changed and is not cacheable anymore.
+                if etag.is_some() {
+                    self.cache.remove(path);
+                }
+                Ok((rp, VersionedCacheReader::Inner(r)))
+            }
+            Err(err) if etag.is_some() && err.kind() == ErrorKind::ConditionNotMatch => {
+                let etag = etag.expect("etag must be valid");
+                self.cache.touch(path, etag);
+                match self.cache.peek(path) {
+                    Some(entry) if entry.etag == etag => Ok(Self::serve(entry)),
+                    // The entry has been invalidated while we are revalidating,
+                    // read the object again.
+                    _ => self
+                        .inner
+                        .read(path, OpRead::new())
+                        .await
+                        .map(|(rp, r)| (rp, VersionedCacheReader::Inner(r))),
+                }
+            }
+            Err(err) => {
+                if err.kind() == ErrorKind::NotFound {
+                    self.cache.invalidate(path);
+                }
+                Err(err)
+            }
+        }
+    }
+
+    fn revalidate_in_background(&self, handle: &Handle, path: &str, etag: String) {
+        let inner = self.inner.clone();
+        let cache = self.cache.clone();
+        let path = path.to_string();
+
+        handle.spawn(async move {
+            let op = OpRead::new().with_if_none_match(&etag);
+            match fill(&inner, &cache, &path, op).await {
+                Ok(Filled::Cached(..)) => {}
+                // The object is not cacheable anymore.
+                Ok(Filled::Bypass(..)) => cache.remove(&path),
+                Err(err) if err.kind() == ErrorKind::ConditionNotMatch => cache.touch(&path, &etag),
+                Err(err) if err.kind() == ErrorKind::NotFound => cache.invalidate(&path),
+                Err(err) => {
+                    log::warn!("revalidate cached content of {path} failed: {err:?}");
+                    cache.release(&path)
+                }
+            }
+        });
+    }
+}
+
+enum Filled<R> {
+    Cached(Metadata, Bytes),
+    Bypass(RpRead, R),
+}
+
+/// Read the object and insert it into cache if it's cacheable.
+///
+/// Objects that are not cacheable (too large or without etag) are passed
+/// through, other fills are not affected.
+async fn fill<A: Accessor>(
+    inner: &A,
+    cache: &VersionedCache,
+    path: &str,
+    op: OpRead,
+) -> Result<Filled<A::Reader>> {
+    let guard = cache.start_fill(path);
+    let (rp, mut r) = inner.read(path, op).await?;
+    if !cache.accepts(rp.metadata()) {
+        return Ok(Filled::Bypass(rp, r));
+    }
+
+    let mut buf = BytesMut::with_capacity(rp.metadata().content_length() as usize);
+    while let Some(bs) = r.next().await {
+        buf.extend_from_slice(&bs?);
+    }
+    let content = buf.freeze();
+    let meta = rp.into_metadata().with_content_length(content.len() as u64);
+
+    cache.insert(&guard, meta.clone(), content.clone());
+    Ok(Filled::Cached(meta, content))
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for VersionedCacheAccessor<A> {
+    type Inner = A;
+    type Reader = VersionedCacheReader<A::Reader>;
+    type BlockingReader = A::BlockingReader;
+    type Writer = VersionedCacheWrapper<A::Writer>;
+    type BlockingWriter = VersionedCacheWrapper<A::BlockingWriter>;
+    type Appender = VersionedCacheWrapper<A::Appender>;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        if !VersionedCache::is_cacheable(&args) {
+            return self
+                .inner
+                .read(path, args)
+                .await
+                .map(|(rp, r)| (rp, VersionedCacheReader::Inner(r)));
+        }
+
+        match self.cache.get(path) {
+            Lookup::Fresh(entry) => Ok(Self::serve(entry)),
+            Lookup::Stale(entry, revalidate) => {
+                if !revalidate {
+                    return Ok(Self::serve(entry));
+                }
+
+                if let Ok(handle) = Handle::try_current() {
+                    self.revalidate_in_background(&handle, path, entry.etag.clone());
+                    return Ok(Self::serve(entry));
+                }
+
+                // There is no runtime to spawn on, revalidate before serving.
+                match self.fetch(path, Some(&entry.etag)).await {
+                    Err(err) if err.kind() != ErrorKind::NotFound => {
+                        log::warn!("revalidate cached content of {path} failed: {err:?}");
+                        self.cache.release(path);
+                        Ok(Self::serve(entry))
+                    }
+                    res => res,
+                }
+            }
+            Lookup::Expired(entry) => self.fetch(path, Some(&entry.etag)).await,
+            Lookup::Miss => self.fetch(path, None).await,
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.cache.invalidate(path);
+        self.inner
+            .write(path, args)
+            .await
+            .map(|(rp, w)| (rp, VersionedCacheWrapper::new(w, path, self.cache.clone())))
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        self.cache.invalidate(path);
+        self.inner
+            .append(path, args)
+            .await
+            .map(|(rp, a)| (rp, VersionedCacheWrapper::new(a, path, self.cache.clone())))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.cache.invalidate(to);
+        self.inner.copy(from, to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.cache.invalidate(from);
+        self.cache.invalidate(to);
+        self.inner.rename(from, to, args).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.cache.invalidate(path);
+        self.inner.delete(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        for (path, _) in args.operation() {
+            self.cache.invalidate(path);
+        }
+        self.inner.batch(args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.cache.invalidate(path);
+        self.inner
+            .blocking_write(path, args)
+            .map(|(rp, w)| (rp, VersionedCacheWrapper::new(w, path, self.cache.clone())))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.cache.invalidate(to);
+        self.inner.blocking_copy(from, to, args)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.cache.invalidate(from);
+        self.cache.invalidate(to);
+        self.inner.blocking_rename(from, to, args)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.cache.invalidate(path);
+        self.inner.blocking_delete(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+pub struct VersionedCacheWrapper<R> {
+    inner: R,
+    path: String,
+    cache: Arc<VersionedCache>,
+}
+
+impl<R> VersionedCacheWrapper<R> {
+    fn new(inner: R, path: &str, cache: Arc<VersionedCache>) -> Self {
+        Self {
+            inner,
+            path: path.to_string(),
+            cache,
+        }
+    }
+}
+
+#[async_trait]
+impl<R: oio::Write> oio::Write for VersionedCacheWrapper<R> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.inner.write(bs).await
+    }
+
+    async fn sink(&mut self, size: u64, s: oio::Streamer) -> Result<()> {
+        self.inner.sink(size, s).await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        let res = self.inner.close().await;
+        self.cache.invalidate(&self.path);
+        res
+    }
+
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        self.inner.checkpoint()
+    }
+}
+
+impl<R: oio::BlockingWrite> oio::BlockingWrite for VersionedCacheWrapper<R> {
+    fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.inner.write(bs)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        let res = self.inner.close();
+        self.cache.invalidate(&self.path);
+        res
+    }
+}
+
+#[async_trait]
+impl<R: oio::Append> oio::Append for VersionedCacheWrapper<R> {
+    async fn append(&mut self, bs: Bytes) -> Result<()> {
+        let res = self.inner.append(bs).await;
+        self.cache.invalidate(&self.path);
+        res
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        let res = self.inner.close().await;
+        self.cache.invalidate(&self.path);
+        res
+    }
+}
+
+pub enum VersionedCacheReader<R> {
+    Cached(oio::Cursor),
+    Inner(R),
+}
+
+impl<R: oio::Read> oio::Read for VersionedCacheReader<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        match self {
+            Self::Cached(r) => r.poll_read(cx, buf),
+            Self::Inner(r) => r.poll_read(cx, buf),
+        }
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: io::SeekFrom) -> Poll<Result<u64>> {
+        match self {
+            Self::Cached(r) => r.poll_seek(cx, pos),
+            Self::Inner(r) => r.poll_seek(cx, pos),
+        }
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        match self {
+            Self::Cached(r) => r.poll_next(cx),
+            Self::Inner(r) => r.poll_next(cx),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::FutureExt;
+
+    use super::*;
+
+    #[derive(Debug, Default, Clone)]
+    struct MockService {
+        version: Arc<Mutex<usize>>,
+        reads: Arc<Mutex<usize>>,
+    }
+
+    impl MockService {
+        fn update(&self) {
+            *self.version.lock().unwrap() += 1;
+        }
+
+        fn reads(&self) -> usize {
+            *self.reads.lock().unwrap()
+        }
+    }
+
+    #[async_trait]
+    impl Accessor for MockService {
+        type Reader = oio::Cursor;
+        type BlockingReader = ();
+        type Writer = ();
+        type BlockingWriter = ();
+        type Appender = ();
+        type Pager = ();
+        type BlockingPager = ();
+
+        fn info(&self) -> AccessorInfo {
+            AccessorInfo::default()
+        }
+
+        async fn read(&self, _: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            *self.reads.lock().unwrap() += 1;
+
+            let version = *self.version.lock().unwrap();
+            let etag = format!("\"v{version}\"");
+            if args.if_none_match() == Some(etag.as_str()) {
+                return Err(Error::new(ErrorKind::ConditionNotMatch, "not modified"));
+            }
+
+            let content = args
+                .range()
+                .apply_on_bytes(Bytes::from(format!("version {version}")));
+            let meta = Metadata::new(EntryMode::FILE)
+                .with_content_length(content.len() as u64)
+                .with_etag(etag);
+            Ok((RpRead::with_metadata(meta), oio::Cursor::from(content)))
+        }
+
+        async fn write(&self, _: &str, _: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+            Ok((RpWrite::default(), ()))
+        }
+    }
+
+    async fn read<A: Accessor>(acc: &A, args: OpRead) -> Result<String> {
+        let (_, mut r) = acc.read("test", args).await?;
+        let mut buf = Vec::new();
+        while let Some(bs) = r.next().await {
+            buf.extend_from_slice(&bs?);
+        }
+        Ok(String::from_utf8(buf).expect("must be valid utf-8"))
+    }
+
+    #[tokio::test]
+    async fn test_serve_fresh() -> Result<()> {
+        let srv = MockService::default();
+        let acc = VersionedCacheLayer::new(Duration::from_secs(60)).layer(srv.clone());
+
+        assert_eq!(read(&acc, OpRead::new()).await?, "version 0");
+        srv.update();
+        assert_eq!(read(&acc, OpRead::new()).await?, "version 0");
+        assert_eq!(srv.reads(), 1);
+
+        // Ranged read should bypass the cache.
+        let args = OpRead::new().with_range(BytesRange::new(Some(0), Some(7)));
+        assert_eq!(read(&acc, args).await?, "version");
+        assert_eq!(srv.reads(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_revalidate() -> Result<()> {
+        let srv = MockService::default();
+        let acc = VersionedCacheLayer::new(Duration::ZERO).layer(srv.clone());
+
+        assert_eq!(read(&acc, OpRead::new()).await?, "version 0");
+        // Not modified, serve from cache.
+        assert_eq!(read(&acc, OpRead::new()).await?, "version 0");
+        assert_eq!(srv.reads(), 2);
+
+        srv.update();
+        assert_eq!(read(&acc, OpRead::new()).await?, "version 1");
+        assert_eq!(srv.reads(), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stale_while_revalidate() -> Result<()> {
+        let srv = MockService::default();
+        let acc = VersionedCacheLayer::new(Duration::ZERO)
+            .with_stale_while_revalidate(Duration::from_secs(60))
+            .layer(srv.clone());
+
+        assert_eq!(read(&acc, OpRead::new()).await?, "version 0");
+        srv.update();
+        // Stale content should be served immediately.
+        assert_eq!(read(&acc, OpRead::new()).await?, "version 0");
+
+        // Wait for the background revalidation.
+        while acc.cache.peek("test").map(|e| e.etag).as_deref() != Some("\"v1\"") {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(srv.reads(), 2);
+        assert_eq!(read(&acc, OpRead::new()).await?, "version 1");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_after_writer_closed() -> Result<()> {
+        let srv = MockService::default();
+        let acc = VersionedCacheLayer::new(Duration::from_secs(60)).layer(srv.clone());
+
+        let (_, mut w) = Accessor::write(&acc, "test", OpWrite::new()).await?;
+        // Content read while the writer is open will be cached.
+        assert_eq!(read(&acc, OpRead::new()).await?, "version 0");
+        srv.update();
+        let _ = oio::Write::close(&mut w).await;
+
+        assert_eq!(read(&acc, OpRead::new()).await?, "version 1");
+        assert_eq!(srv.reads(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_stale_while_revalidate_without_runtime() -> Result<()> {
+        let srv = MockService::default();
+        let acc = VersionedCacheLayer::new(Duration::ZERO)
+            .with_stale_while_revalidate(Duration::from_secs(60))
+            .layer(srv.clone());
+        let read_now = || {
+            read(&acc, OpRead::new())
+                .now_or_never()
+                .expect("mock read must be ready")
+        };
+
+        assert_eq!(read_now()?, "version 0");
+        srv.update();
+        // Stale content should be revalidated before serving.
+        assert_eq!(read_now()?, "version 1");
+        assert_eq!(srv.reads(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_drop_fill_started_before_invalidation() {
+        let acc = VersionedCacheLayer::new(Duration::from_secs(60)).layer(MockService::default());
+        let meta = Metadata::new(EntryMode::FILE)
+            .with_content_length(1)
+            .with_etag("\"v0\"".to_string());
+
+        let fill = acc.cache.start_fill("test");
+        // Invalidation of other paths should not affect this fill.
+        acc.cache.invalidate("other");
+        acc.cache.remove("test");
+        assert!(acc.cache.insert(&fill, meta.clone(), Bytes::from("a")));
+
+        acc.cache.invalidate("test");
+        assert!(!acc.cache.insert(&fill, meta.clone(), Bytes::from("a")));
+        drop(fill);
+        assert!(acc.cache.fills.lock().unwrap().is_empty());
+
+        let fill = acc.cache.start_fill("test");
+        assert!(acc.cache.insert(&fill, meta, Bytes::from("a")));
+    }
+
+    #[tokio::test]
+    async fn test_bypass_keeps_other_entries() -> Result<()> {
+        let srv = MockService::default();
+        let acc = VersionedCacheLayer::new(Duration::from_secs(60))
+            .with_max_object_size(4)
+            .layer(srv.clone());
+        acc.cache.insert(
+            &acc.cache.start_fill("small"),
+            Metadata::new(EntryMode::FILE)
+                .with_content_length(1)
+                .with_etag("\"v0\"".to_string()),
+            Bytes::from("a"),
+        );
+
+        // "version 0" is too large to be cached.
+        assert_eq!(read(&acc, OpRead::new()).await?, "version 0");
+        assert_eq!(read(&acc, OpRead::new()).await?, "version 0");
+        assert_eq!(srv.reads(), 2);
+        assert!(acc.cache.peek("small").is_some());
+        Ok(())
+    }
+}
@@ -88,9 +88,9 @@ mod tests {
     /// unexpected struct/enum size change.
     #[test]
     fn assert_size() {
-        assert_eq!(24, size_of::<Operator>());
-        assert_eq!(264, size_of::<Entry>());
-        assert_eq!(240, size_of::<Metadata>());
+        assert_eq!(48, size_of::<Operator>());
+        assert_eq!(448, size_of::<Entry>());
+        assert_eq!(424, size_of::<Metadata>());
         assert_eq!(1, size_of::<EntryMode>());
         assert_eq!(24, size_of::<Scheme>());
     }
@@ -253,6 +253,58 @@ pub trait Accessor: Send + Sync + Debug + Unpin + 'static {
         ))
     }
 
+    /// Invoke the `get_tags` operation on the specified path.
+    ///
+    /// Require [`Capability::get_tags`]
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `put_tags` operation on the specified path.
+    ///
+    /// Tags of the file will be replaced by the given tags.
+    ///
+    /// Require [`Capability::put_tags`]
+    async fn put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `get_object_lock` operation on the specified path.
+    ///
+    /// Require [`Capability::get_object_lock`]
+    async fn get_object_lock(&self, path: &str, args: OpGetObjectLock) -> Result<RpGetObjectLock> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `put_object_lock` operation on the specified path.
+    ///
+    /// Only the retention or legal hold that is set in args will be updated.
+    ///
+    /// Require [`Capability::put_object_lock`]
+    async fn put_object_lock(&self, path: &str, args: OpPutObjectLock) -> Result<RpPutObjectLock> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
     /// Invoke the `batch` operations.
     ///
     /// Require [`Capability::batch`]
@@ -380,6 +432,34 @@ pub trait Accessor: Send + Sync + Debug + Unpin + 'static {
             "operation is not supported",
         ))
     }
+
+    /// Invoke the `blocking_get_tags` operation on the specified path.
+    ///
+    /// This operation is the blocking version of [`Accessor::get_tags`]
+    ///
+    /// Require [`Capability::get_tags`] and [`Capability::blocking`]
+    fn blocking_get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `blocking_put_tags` operation on the specified path.
+    ///
+    /// This operation is the blocking version of [`Accessor::put_tags`]
+    ///
+    /// Require [`Capability::put_tags`] and [`Capability::blocking`]
+    fn blocking_put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
 }
 
 /// Dummy implementation of accessor.
@@ -460,6 +540,22 @@ impl<T: Accessor + ?Sized> Accessor for Arc<T> {
         self.as_ref().presign(path, args).await
     }
 
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        self.as_ref().get_tags(path, args).await
+    }
+
+    async fn put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        self.as_ref().put_tags(path, args).await
+    }
+
+    async fn get_object_lock(&self, path: &str, args: OpGetObjectLock) -> Result<RpGetObjectLock> {
+        self.as_ref().get_object_lock(path, args).await
+    }
+
+    async fn put_object_lock(&self, path: &str, args: OpPutObjectLock) -> Result<RpPutObjectLock> {
+        self.as_ref().put_object_lock(path, args).await
+    }
+
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         self.as_ref().blocking_create_dir(path, args)
     }
@@ -487,6 +583,14 @@ impl<T: Accessor + ?Sized> Accessor for Arc<T> {
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
         self.as_ref().blocking_list(path, args)
     }
+
+    fn blocking_get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        self.as_ref().blocking_get_tags(path, args)
+    }
+
+    fn blocking_put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        self.as_ref().blocking_put_tags(path, args)
+    }
 }
 
 /// FusedAccessor is the type erased accessor with `Arc<dyn Accessor>`.
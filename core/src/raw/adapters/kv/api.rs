@@ -16,6 +16,7 @@
 // under the License.
 
 use std::fmt::Debug;
+use std::time::Duration;
 
 use async_trait::async_trait;
 
@@ -50,6 +51,21 @@ pub trait Adapter: Send + Sync + Debug + Unpin + 'static {
         .with_operation("kv::Adapter::blocking_get"))
     }
 
+    /// Get the size of a key's value from service.
+    ///
+    /// - return `Ok(None)` if this key is not exist.
+    ///
+    /// The default implementation reads the whole value, services that
+    /// can get the size cheaper should override it.
+    async fn size(&self, path: &str) -> Result<Option<u64>> {
+        Ok(self.get(path).await?.map(|v| v.len() as u64))
+    }
+
+    /// The blocking version of size.
+    fn blocking_size(&self, path: &str) -> Result<Option<u64>> {
+        Ok(self.blocking_get(path)?.map(|v| v.len() as u64))
+    }
+
     /// Set a key into service.
     async fn set(&self, path: &str, value: &[u8]) -> Result<()>;
 
@@ -64,6 +80,48 @@ pub trait Adapter: Send + Sync + Debug + Unpin + 'static {
         .with_operation("kv::Adapter::blocking_set"))
     }
 
+    /// Set a key into service with ttl, the key will expire after ttl.
+    ///
+    /// Adapters that implement this should also set `write_with_ttl`
+    /// in their capability.
+    async fn set_with_ttl(&self, path: &str, value: &[u8], ttl: Duration) -> Result<()> {
+        let _ = (path, value, ttl);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "kv adapter doesn't support this operation",
+        )
+        .with_operation("kv::Adapter::set_with_ttl"))
+    }
+
+    /// The blocking version of set_with_ttl.
+    fn blocking_set_with_ttl(&self, path: &str, value: &[u8], ttl: Duration) -> Result<()> {
+        let _ = (path, value, ttl);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "kv adapter doesn't support this operation",
+        )
+        .with_operation("kv::Adapter::blocking_set_with_ttl"))
+    }
+
+    /// Get the remaining ttl of a key from service.
+    ///
+    /// - return `Ok(None)` if this key doesn't expire or the service
+    ///   can't report it.
+    async fn ttl(&self, path: &str) -> Result<Option<Duration>> {
+        let _ = path;
+
+        Ok(None)
+    }
+
+    /// The blocking version of ttl.
+    fn blocking_ttl(&self, path: &str) -> Result<Option<Duration>> {
+        let _ = path;
+
+        Ok(None)
+    }
+
     /// Delete a key from service.
     ///
     /// - return `Ok(())` even if this key is not exist.
@@ -131,6 +189,22 @@ pub trait Adapter: Send + Sync + Debug + Unpin + 'static {
     }
 }
 
+/// Convert ttl into whole seconds for services that set expiration in seconds.
+///
+/// The ttl will be rounded up so that keys never expire earlier than
+/// expected. Zero ttl is rejected since services treat it as either
+/// "expire now" or "never expire".
+pub fn parse_ttl(scheme: Scheme, ttl: Duration) -> Result<u64> {
+    if ttl.is_zero() {
+        return Err(
+            Error::new(ErrorKind::InvalidInput, "ttl must be larger than 0")
+                .with_context("service", scheme),
+        );
+    }
+
+    Ok(ttl.as_secs() + u64::from(ttl.subsec_nanos() > 0))
+}
+
 /// Metadata for this key value accessor.
 pub struct Metadata {
     scheme: Scheme,
@@ -174,3 +248,27 @@ impl From<Metadata> for AccessorInfo {
         am
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ttl() {
+        assert_eq!(
+            parse_ttl(Scheme::Redis, Duration::from_millis(100)).unwrap(),
+            1
+        );
+        assert_eq!(
+            parse_ttl(Scheme::Redis, Duration::from_millis(1500)).unwrap(),
+            2
+        );
+        assert_eq!(
+            parse_ttl(Scheme::Redis, Duration::from_secs(60)).unwrap(),
+            60
+        );
+
+        let err = parse_ttl(Scheme::Redis, Duration::ZERO).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+}
@@ -16,6 +16,7 @@
 // under the License.
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use bytes::Bytes;
@@ -147,7 +148,10 @@ impl<S: Adapter> Accessor for Backend<S> {
 
         let p = build_abs_path(&self.root, path);
 
-        Ok((RpWrite::new(), KvWriter::new(self.kv.clone(), p)))
+        Ok((
+            RpWrite::new(),
+            KvWriter::new(self.kv.clone(), p, args.ttl()),
+        ))
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
@@ -160,7 +164,10 @@ impl<S: Adapter> Accessor for Backend<S> {
 
         let p = build_abs_path(&self.root, path);
 
-        Ok((RpWrite::new(), KvWriter::new(self.kv.clone(), p)))
+        Ok((
+            RpWrite::new(),
+            KvWriter::new(self.kv.clone(), p, args.ttl()),
+        ))
     }
 
     async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
@@ -169,11 +176,15 @@ impl<S: Adapter> Accessor for Backend<S> {
         if p.is_empty() || p.ends_with('/') {
             Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
         } else {
-            let bs = self.kv.get(&p).await?;
-            match bs {
-                Some(bs) => Ok(RpStat::new(
-                    Metadata::new(EntryMode::FILE).with_content_length(bs.len() as u64),
-                )),
+            let size = self.kv.size(&p).await?;
+            match size {
+                Some(size) => {
+                    let mut meta = Metadata::new(EntryMode::FILE).with_content_length(size);
+                    if let Some(ttl) = self.kv.ttl(&p).await? {
+                        meta.set_ttl(ttl);
+                    }
+                    Ok(RpStat::new(meta))
+                }
                 None => Err(Error::new(ErrorKind::NotFound, "kv doesn't have this path")),
             }
         }
@@ -185,11 +196,15 @@ impl<S: Adapter> Accessor for Backend<S> {
         if p.is_empty() || p.ends_with('/') {
             Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
         } else {
-            let bs = self.kv.blocking_get(&p)?;
-            match bs {
-                Some(bs) => Ok(RpStat::new(
-                    Metadata::new(EntryMode::FILE).with_content_length(bs.len() as u64),
-                )),
+            let size = self.kv.blocking_size(&p)?;
+            match size {
+                Some(size) => {
+                    let mut meta = Metadata::new(EntryMode::FILE).with_content_length(size);
+                    if let Some(ttl) = self.kv.blocking_ttl(&p)? {
+                        meta.set_ttl(ttl);
+                    }
+                    Ok(RpStat::new(meta))
+                }
                 None => Err(Error::new(ErrorKind::NotFound, "kv doesn't have this path")),
             }
         }
@@ -371,16 +386,18 @@ impl oio::BlockingPage for KvPager {
 pub struct KvWriter<S> {
     kv: Arc<S>,
     path: String,
+    ttl: Option<Duration>,
 
     /// TODO: if kv supports append, we can use them directly.
     buf: Option<Vec<u8>>,
 }
 
 impl<S> KvWriter<S> {
-    fn new(kv: Arc<S>, path: String) -> Self {
+    fn new(kv: Arc<S>, path: String, ttl: Option<Duration>) -> Self {
         KvWriter {
             kv,
             path,
+            ttl,
             buf: None,
         }
     }
@@ -411,7 +428,10 @@ impl<S: Adapter> oio::Write for KvWriter<S> {
 
     async fn close(&mut self) -> Result<()> {
         if let Some(buf) = self.buf.as_deref() {
-            self.kv.set(&self.path, buf).await?;
+            match self.ttl {
+                Some(ttl) => self.kv.set_with_ttl(&self.path, buf, ttl).await?,
+                None => self.kv.set(&self.path, buf).await?,
+            }
         }
 
         Ok(())
@@ -427,7 +447,10 @@ impl<S: Adapter> oio::BlockingWrite for KvWriter<S> {
 
     fn close(&mut self) -> Result<()> {
         if let Some(buf) = self.buf.as_deref() {
-            self.kv.blocking_set(&self.path, buf)?;
+            match self.ttl {
+                Some(ttl) => self.kv.blocking_set_with_ttl(&self.path, buf, ttl)?,
+                None => self.kv.blocking_set(&self.path, buf)?,
+            }
         }
 
         Ok(())
@@ -20,6 +20,7 @@
 //! Any services that implement `Adapter` can be used an OpenDAL Service.
 
 mod api;
+pub use api::parse_ttl;
 pub use api::Adapter;
 pub use api::Metadata;
 
@@ -0,0 +1,220 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use async_trait::async_trait;
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::Utc;
+use tokio::sync::Mutex;
+
+use crate::*;
+
+/// Credential carries a service specific credential value together with
+/// its expiry time.
+#[derive(Clone)]
+pub struct Credential<T> {
+    value: T,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+impl<T> Debug for Credential<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credential")
+            .field("value", &"<redacted>")
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+impl<T> Credential<T> {
+    /// Create a new credential that never expires.
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            expires_at: None,
+        }
+    }
+
+    /// Set the expiry time of this credential.
+    pub fn with_expires_at(mut self, expires_at: DateTime<Utc>) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Get the credential value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Consume self to get the credential value.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// Get the expiry time of this credential.
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.expires_at
+    }
+
+    /// Check if this credential is still valid at `now` with given grace
+    /// period. Credentials without expiry time are always valid.
+    fn is_valid_at(&self, now: DateTime<Utc>, grace: Duration) -> bool {
+        match self.expires_at {
+            Some(expires_at) => now + grace < expires_at,
+            None => true,
+        }
+    }
+}
+
+/// CredentialProvider is used by services to fetch credentials from
+/// external sources like cloud metadata servers, STS or secret managers.
+///
+/// Implementations don't need to cache credentials by themselves, wrap
+/// them with [`CachedCredentialProvider`] instead.
+#[async_trait]
+pub trait CredentialProvider<T>: Debug + Send + Sync + 'static {
+    /// Fetch a credential.
+    ///
+    /// Returns `Ok(None)` if no credential is available from this provider.
+    async fn get_credential(&self) -> Result<Option<Credential<T>>>;
+}
+
+#[async_trait]
+impl<T, P: CredentialProvider<T> + ?Sized> CredentialProvider<T> for Box<P>
+where
+    T: Send + 'static,
+{
+    async fn get_credential(&self) -> Result<Option<Credential<T>>> {
+        self.as_ref().get_credential().await
+    }
+}
+
+/// CachedCredentialProvider caches credentials returned by the inner
+/// provider until they are about to expire.
+///
+/// Credentials will be refreshed `grace` (2 minutes by default) before
+/// their expiry time so that in-flight requests won't be signed with
+/// expired credentials.
+pub struct CachedCredentialProvider<T, P> {
+    inner: P,
+    grace: Duration,
+    cache: Mutex<Option<Credential<T>>>,
+}
+
+impl<T, P: Debug> Debug for CachedCredentialProvider<T, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedCredentialProvider")
+            .field("inner", &self.inner)
+            .field("grace", &self.grace)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, P> CachedCredentialProvider<T, P> {
+    /// Create a new cached provider with given inner provider.
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            grace: Duration::minutes(2),
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// Set the grace period before expiry to refresh the credential.
+    pub fn with_grace(mut self, grace: std::time::Duration) -> Self {
+        self.grace = Duration::from_std(grace).unwrap_or_else(|_| Duration::zero());
+        self
+    }
+}
+
+#[async_trait]
+impl<T, P> CredentialProvider<T> for CachedCredentialProvider<T, P>
+where
+    T: Clone + Send + Sync + 'static,
+    P: CredentialProvider<T>,
+{
+    async fn get_credential(&self) -> Result<Option<Credential<T>>> {
+        // Hold the lock while refreshing so that concurrent callers
+        // won't hit the provider at the same time.
+        let mut cache = self.cache.lock().await;
+        if let Some(cred) = cache.as_ref() {
+            if cred.is_valid_at(Utc::now(), self.grace) {
+                return Ok(Some(cred.clone()));
+            }
+        }
+
+        let cred = self.inner.get_credential().await?;
+        *cache = cred.clone();
+        Ok(cred)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct CountingProvider {
+        count: AtomicUsize,
+        ttl: Duration,
+    }
+
+    #[async_trait]
+    impl CredentialProvider<String> for CountingProvider {
+        async fn get_credential(&self) -> Result<Option<Credential<String>>> {
+            let n = self.count.fetch_add(1, Ordering::SeqCst);
+            Ok(Some(
+                Credential::new(format!("token-{n}")).with_expires_at(Utc::now() + self.ttl),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_credential_reused_before_expiry() {
+        let p = CachedCredentialProvider::new(CountingProvider {
+            count: AtomicUsize::new(0),
+            ttl: Duration::hours(1),
+        });
+
+        let first = p.get_credential().await.unwrap().unwrap();
+        let second = p.get_credential().await.unwrap().unwrap();
+        assert_eq!(first.value(), "token-0");
+        assert_eq!(second.value(), "token-0");
+        assert_eq!(p.inner.count.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cached_credential_refreshed_near_expiry() {
+        // The credential expires within grace period, so it must be
+        // refreshed every time.
+        let p = CachedCredentialProvider::new(CountingProvider {
+            count: AtomicUsize::new(0),
+            ttl: Duration::seconds(30),
+        });
+
+        let first = p.get_credential().await.unwrap().unwrap();
+        let second = p.get_credential().await.unwrap().unwrap();
+        assert_eq!(first.value(), "token-0");
+        assert_eq!(second.value(), "token-1");
+    }
+}
@@ -48,7 +48,8 @@ pub enum AsyncBody {
 ///
 /// # Notes
 ///
-/// Client SHOULD NEVER construct this body.
+/// Services SHOULD NEVER construct this body, it's built by [`HttpFetch`]
+/// implementations.
 pub struct IncomingAsyncBody {
     /// # TODO
     ///
@@ -19,8 +19,14 @@ use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::mem;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
+use async_trait::async_trait;
 use futures::TryStreamExt;
+use http::header::HeaderName;
+use http::HeaderMap;
+use http::HeaderValue;
 use http::Request;
 use http::Response;
 
@@ -33,9 +39,14 @@ use crate::ErrorKind;
 use crate::Result;
 
 /// HttpClient that used across opendal.
+///
+/// All requests are sent by the inner [`HttpFetch`], which is `reqwest`
+/// by default. Users can build a client upon their own transport via
+/// [`HttpClient::with_fetcher`].
 #[derive(Clone)]
 pub struct HttpClient {
     client: reqwest::Client,
+    fetcher: HttpFetcher,
 }
 
 /// We don't want users to know details about our clients.
@@ -48,7 +59,7 @@ impl Debug for HttpClient {
 impl HttpClient {
     /// Create a new http client in async context.
     pub fn new() -> Result<Self> {
-        Self::build(reqwest::ClientBuilder::new())
+        Self::build(reqwest::ClientBuilder::new().user_agent(default_user_agent()))
     }
 
     /// Build a new http client in async context.
@@ -63,20 +74,146 @@ impl HttpClient {
         #[cfg(feature = "trust-dns")]
         let builder = builder.trust_dns(true);
 
-        Ok(Self {
-            client: builder.build().map_err(|err| {
-                Error::new(ErrorKind::Unexpected, "async client build failed").set_source(err)
-            })?,
-        })
+        let client = builder.build().map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "async client build failed").set_source(err)
+        })?;
+
+        Ok(Self::with_client(client))
     }
 
-    /// Get the async client from http client.
+    /// Build a new http client with given config.
+    ///
+    /// The config only covers the options that commonly used in
+    /// production like proxy, timeouts, user agent and extra root
+    /// certificates. Please use [`HttpClient::build`] for more advanced usage.
+    pub fn with_config(config: &HttpClientConfig) -> Result<Self> {
+        let mut builder = reqwest::ClientBuilder::new();
+
+        builder = builder.user_agent(config.user_agent.clone().unwrap_or_else(default_user_agent));
+        if !config.headers.is_empty() {
+            let mut headers = HeaderMap::with_capacity(config.headers.len());
+            for (k, v) in &config.headers {
+                let name = HeaderName::from_str(k).map_err(|err| {
+                    Error::new(ErrorKind::ConfigInvalid, "header name is invalid")
+                        .with_operation("HttpClient::with_config")
+                        .with_context("header", k)
+                        .set_source(err)
+                })?;
+                let value = HeaderValue::from_str(v).map_err(|err| {
+                    Error::new(ErrorKind::ConfigInvalid, "header value is invalid")
+                        .with_operation("HttpClient::with_config")
+                        .with_context("header", k)
+                        .set_source(err)
+                })?;
+                headers.append(name, value);
+            }
+            builder = builder.default_headers(headers);
+        }
+
+        if let Some(proxy) = &config.proxy {
+            let proxy = reqwest::Proxy::all(proxy).map_err(|err| {
+                Error::new(ErrorKind::ConfigInvalid, "proxy url is invalid")
+                    .with_operation("HttpClient::with_config")
+                    .set_source(err)
+            })?;
+            builder = builder.proxy(proxy);
+        }
+        if let Some(timeout) = config.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = config.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if !config.root_certificates.is_empty() {
+            builder = add_root_certificates(builder, &config.root_certificates)?;
+        }
+
+        Self::build(builder)
+    }
+
+    /// Create a http client from a pre-built `reqwest::Client`.
+    ///
+    /// # Notes
+    ///
+    /// OpenDAL expects the client to not decompress response body
+    /// automatically, please make sure gzip, brotli and deflate are
+    /// disabled on the given client.
+    pub fn with_client(client: reqwest::Client) -> Self {
+        Self {
+            fetcher: Arc::new(client.clone()),
+            client,
+        }
+    }
+
+    /// Create a http client that sends all requests via given [`HttpFetch`].
+    ///
+    /// # Notes
+    ///
+    /// Some services load credentials via `reqwest` directly, they will still
+    /// use a default `reqwest::Client` which can be got by [`HttpClient::client`].
+    pub fn with_fetcher(fetcher: impl HttpFetch) -> Result<Self> {
+        let mut client = Self::new()?;
+        client.fetcher = Arc::new(fetcher);
+        Ok(client)
+    }
+
+    /// Get the underlying `reqwest::Client` from http client.
     pub fn client(&self) -> reqwest::Client {
         self.client.clone()
     }
 
     /// Send a request in async way.
     pub async fn send(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
+        self.fetcher.fetch(req).await
+    }
+}
+
+/// HttpFetch is the trait to send http requests, which allows users to
+/// route OpenDAL's requests through their own transport like a mock server,
+/// an instrumented client or a connection pool shared across the app.
+///
+/// # Request
+///
+/// The request carries method, uri (always an absolute url), version,
+/// headers and an [`AsyncBody`]:
+///
+/// - [`AsyncBody::Empty`]: no body should be sent.
+/// - [`AsyncBody::Bytes`]: the whole body is in memory.
+/// - [`AsyncBody::Stream`]: the body must be sent in streaming way, its size
+///   is decided by the `Content-Length` header if provided.
+///
+/// # Response
+///
+/// Implementations should return the response as is, no matter what the
+/// status code is, services will check the status by themselves. The
+/// response body should be built via [`IncomingAsyncBody::new`] with a
+/// streaming [`oio::Streamer`] and the content length parsed from headers
+/// (`None` for `HEAD` requests).
+///
+/// The uri of the request should be inserted into response extensions so that
+/// services can fetch it later.
+///
+/// # Errors
+///
+/// Errors that could be resolved by retrying (for example, connection reset)
+/// should be marked as temporary via [`Error::set_temporary`].
+///
+/// # Notes
+///
+/// This API is part of OpenDAL's Raw API. `HttpFetch` could be changed
+/// during minor updates.
+#[async_trait]
+pub trait HttpFetch: Send + Sync + 'static {
+    /// Send the request and return its response.
+    async fn fetch(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>>;
+}
+
+/// HttpFetcher is a type erased [`HttpFetch`].
+pub type HttpFetcher = Arc<dyn HttpFetch>;
+
+#[async_trait]
+impl HttpFetch for reqwest::Client {
+    async fn fetch(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
         // Uri stores all string alike data in `Bytes` which means
         // the clone here is cheap.
         let uri = req.uri().clone();
@@ -85,7 +222,6 @@ impl HttpClient {
         let (parts, body) = req.into_parts();
 
         let mut req_builder = self
-            .client
             .request(
                 parts.method,
                 reqwest::Url::from_str(&uri.to_string()).expect("input request url must be valid"),
@@ -157,3 +293,229 @@ impl HttpClient {
         Ok(resp)
     }
 }
+
+/// The default user agent of OpenDAL: `opendal/<version>`.
+fn default_user_agent() -> String {
+    format!("opendal/{VERSION}")
+}
+
+#[cfg(any(
+    feature = "rustls",
+    feature = "native-tls",
+    feature = "native-tls-vendored"
+))]
+fn add_root_certificates(
+    mut builder: reqwest::ClientBuilder,
+    certs: &[Vec<u8>],
+) -> Result<reqwest::ClientBuilder> {
+    for pem in certs {
+        let cert = reqwest::Certificate::from_pem(pem).map_err(|err| {
+            Error::new(ErrorKind::ConfigInvalid, "root certificate is invalid")
+                .with_operation("HttpClient::with_config")
+                .set_source(err)
+        })?;
+        builder = builder.add_root_certificate(cert);
+    }
+    Ok(builder)
+}
+
+#[cfg(not(any(
+    feature = "rustls",
+    feature = "native-tls",
+    feature = "native-tls-vendored"
+)))]
+fn add_root_certificates(
+    _: reqwest::ClientBuilder,
+    _: &[Vec<u8>],
+) -> Result<reqwest::ClientBuilder> {
+    Err(Error::new(
+        ErrorKind::ConfigInvalid,
+        "root certificates require tls support, please enable rustls or native-tls",
+    )
+    .with_operation("HttpClient::with_config"))
+}
+
+/// Config for building a [`HttpClient`].
+///
+/// # Notes
+///
+/// This API is part of OpenDAL's Raw API. `HttpClientConfig` could be changed
+/// during minor updates.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use opendal::raw::HttpClient;
+/// use opendal::raw::HttpClientConfig;
+/// use opendal::services::S3;
+///
+/// # fn main() -> opendal::Result<()> {
+/// let mut config = HttpClientConfig::default();
+/// config
+///     .proxy("http://127.0.0.1:3128")
+///     .connect_timeout(Duration::from_secs(5))
+///     .user_agent("my-app/1.0")
+///     .header("x-request-tag", "batch-job");
+///
+/// let mut builder = S3::default();
+/// builder.http_client(HttpClient::with_config(&config)?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default, Clone)]
+pub struct HttpClientConfig {
+    proxy: Option<String>,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    root_certificates: Vec<Vec<u8>>,
+    user_agent: Option<String>,
+    headers: Vec<(String, String)>,
+}
+
+impl Debug for HttpClientConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpClientConfig")
+            .field("proxy", &self.proxy.as_ref().map(|_| "<redacted>"))
+            .field("connect_timeout", &self.connect_timeout)
+            .field("timeout", &self.timeout)
+            .field("root_certificates", &self.root_certificates.len())
+            .field("user_agent", &self.user_agent)
+            .field("headers", &self.headers)
+            .finish()
+    }
+}
+
+impl HttpClientConfig {
+    /// Set the proxy url used by all requests, for example
+    /// `http://127.0.0.1:3128` or `socks5://127.0.0.1:1080`.
+    pub fn proxy(&mut self, proxy: &str) -> &mut Self {
+        if !proxy.is_empty() {
+            self.proxy = Some(proxy.to_string());
+        }
+        self
+    }
+
+    /// Set the timeout for the connect phase of a request.
+    pub fn connect_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the total timeout of a request, from connecting until the
+    /// response body has been read.
+    ///
+    /// # Notes
+    ///
+    /// This timeout applies to the whole response body, please make sure
+    /// it's large enough for reading or writing big files.
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Add an extra PEM encoded root certificate, which is useful for
+    /// services behind private CAs.
+    ///
+    /// This requires one of `rustls`, `native-tls` or
+    /// `native-tls-vendored` to be enabled.
+    pub fn add_root_certificate(&mut self, pem: &[u8]) -> &mut Self {
+        self.root_certificates.push(pem.to_vec());
+        self
+    }
+
+    /// Set the `User-Agent` sent with all requests.
+    ///
+    /// Default to `opendal/<version>`.
+    pub fn user_agent(&mut self, user_agent: &str) -> &mut Self {
+        if !user_agent.is_empty() {
+            self.user_agent = Some(user_agent.to_string());
+        }
+        self
+    }
+
+    /// Add a static header that will be sent with all requests, which is
+    /// useful to tag requests for server side observability.
+    ///
+    /// # Notes
+    ///
+    /// Headers set by services (like `Authorization`) will take precedence.
+    pub fn header(&mut self, name: &str, value: &str) -> &mut Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use futures::stream;
+
+    use super::*;
+
+    struct MockFetcher;
+
+    #[async_trait]
+    impl HttpFetch for MockFetcher {
+        async fn fetch(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
+            let content = Bytes::from(format!("{} {}", req.method(), req.uri()));
+            let size = content.len() as u64;
+            let stream = oio::into_stream(stream::iter(vec![Ok(content)]));
+
+            Ok(Response::builder()
+                .status(http::StatusCode::OK)
+                .extension(req.uri().clone())
+                .body(IncomingAsyncBody::new(Box::new(stream), Some(size)))
+                .expect("response must build succeed"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_with_fetcher() {
+        let client = HttpClient::with_fetcher(MockFetcher).unwrap();
+
+        let req = Request::get("http://example.com/hello")
+            .body(AsyncBody::Empty)
+            .unwrap();
+        let resp = client.send(req).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+
+        let bs = resp.into_body().bytes().await.unwrap();
+        assert_eq!(bs, Bytes::from("GET http://example.com/hello"));
+    }
+
+    #[test]
+    fn test_with_config_invalid_proxy() {
+        let mut config = HttpClientConfig::default();
+        config.proxy("http://[::1");
+
+        let err = HttpClient::with_config(&config).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
+    #[test]
+    fn test_with_config() {
+        let mut config = HttpClientConfig::default();
+        config
+            .proxy("http://127.0.0.1:3128")
+            .connect_timeout(Duration::from_secs(5))
+            .timeout(Duration::from_secs(60));
+
+        assert!(HttpClient::with_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_with_config_headers() {
+        let mut config = HttpClientConfig::default();
+        config
+            .user_agent("my-app/1.0")
+            .header("x-request-tag", "batch-job");
+        assert!(HttpClient::with_config(&config).is_ok());
+
+        let mut config = HttpClientConfig::default();
+        config.header("invalid header", "value");
+        let err = HttpClient::with_config(&config).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+}
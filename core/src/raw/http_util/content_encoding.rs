@@ -0,0 +1,267 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io;
+use std::io::Write;
+use std::mem;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::Bytes;
+use flate2::write::DeflateDecoder;
+use flate2::write::MultiGzDecoder;
+use flate2::write::ZlibDecoder;
+use http::header::CONTENT_ENCODING;
+use http::header::CONTENT_LENGTH;
+use http::Response;
+
+use crate::raw::*;
+use crate::*;
+
+/// Decode response body according to its `Content-Encoding` header.
+///
+/// # Notes
+///
+/// - Only `gzip`, `x-gzip` and `deflate` are supported, other encodings
+///   will return an `Unsupported` error.
+/// - Body is decoded chunk by chunk while reading, so the memory usage is
+///   bounded by the decoded size of one chunk instead of the whole body.
+/// - The decoded size is unknown before the whole body has been read, so
+///   both `Content-Encoding` and `Content-Length` headers will be removed.
+pub fn decode_content_encoding(
+    resp: Response<IncomingAsyncBody>,
+) -> Result<Response<IncomingAsyncBody>> {
+    let encoding = match parse_content_encoding(resp.headers())? {
+        None => return Ok(resp),
+        Some(v) if v.trim().eq_ignore_ascii_case("identity") => return Ok(resp),
+        Some(v) => v.trim().to_ascii_lowercase(),
+    };
+    let decoder = Decoder::new(&encoding)?;
+
+    let (mut parts, body) = resp.into_parts();
+    parts.headers.remove(CONTENT_ENCODING);
+    parts.headers.remove(CONTENT_LENGTH);
+
+    let stream = DecodeStream {
+        inner: body,
+        decoder,
+        encoding,
+        done: false,
+    };
+    let body = IncomingAsyncBody::new(Box::new(stream), None);
+
+    Ok(Response::from_parts(parts, body))
+}
+
+enum Decoder {
+    Gzip(MultiGzDecoder<Vec<u8>>),
+    /// `deflate` should be zlib wrapped as described in RFC 9110, but
+    /// some servers send raw deflate stream. We buffer the first two
+    /// bytes to check if it's a zlib header.
+    Deflate(Vec<u8>),
+    Zlib(ZlibDecoder<Vec<u8>>),
+    RawDeflate(DeflateDecoder<Vec<u8>>),
+}
+
+impl Decoder {
+    fn new(encoding: &str) -> Result<Self> {
+        match encoding {
+            "gzip" | "x-gzip" => Ok(Decoder::Gzip(MultiGzDecoder::new(Vec::new()))),
+            "deflate" => Ok(Decoder::Deflate(Vec::with_capacity(2))),
+            _ => Err(
+                Error::new(ErrorKind::Unsupported, "content encoding is not supported")
+                    .with_operation("http_util::decode_content_encoding")
+                    .with_context("content_encoding", encoding),
+            ),
+        }
+    }
+
+    /// Feed compressed data into decoder and take all decoded data out.
+    fn write(&mut self, bs: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            Decoder::Gzip(d) => {
+                d.write_all(bs)?;
+                d.flush()?;
+                Ok(mem::take(d.get_mut()))
+            }
+            Decoder::Zlib(d) => {
+                d.write_all(bs)?;
+                d.flush()?;
+                Ok(mem::take(d.get_mut()))
+            }
+            Decoder::RawDeflate(d) => {
+                d.write_all(bs)?;
+                d.flush()?;
+                Ok(mem::take(d.get_mut()))
+            }
+            Decoder::Deflate(head) => {
+                head.extend_from_slice(bs);
+                if head.len() < 2 {
+                    return Ok(Vec::new());
+                }
+
+                let head = mem::take(head);
+                *self = if is_zlib_header(head[0], head[1]) {
+                    Decoder::Zlib(ZlibDecoder::new(Vec::new()))
+                } else {
+                    Decoder::RawDeflate(DeflateDecoder::new(Vec::new()))
+                };
+                self.write(&head)
+            }
+        }
+    }
+
+    /// Finish the decoding and take the rest of decoded data out.
+    fn finish(&mut self) -> io::Result<Vec<u8>> {
+        match self {
+            Decoder::Gzip(d) => {
+                d.try_finish()?;
+                Ok(mem::take(d.get_mut()))
+            }
+            Decoder::Zlib(d) => {
+                d.try_finish()?;
+                Ok(mem::take(d.get_mut()))
+            }
+            Decoder::RawDeflate(d) => {
+                d.try_finish()?;
+                Ok(mem::take(d.get_mut()))
+            }
+            Decoder::Deflate(head) => {
+                // Too short to be zlib wrapped, try it as raw deflate.
+                let head = mem::take(head);
+                *self = Decoder::RawDeflate(DeflateDecoder::new(Vec::new()));
+                let mut buf = self.write(&head)?;
+                buf.extend(self.finish()?);
+                Ok(buf)
+            }
+        }
+    }
+}
+
+/// Check if given bytes are a valid zlib header as described in RFC 1950.
+fn is_zlib_header(cmf: u8, flg: u8) -> bool {
+    cmf & 0x0f == 8 && ((u16::from(cmf) << 8) | u16::from(flg)) % 31 == 0
+}
+
+struct DecodeStream {
+    inner: IncomingAsyncBody,
+    decoder: Decoder,
+    encoding: String,
+    done: bool,
+}
+
+impl oio::Stream for DecodeStream {
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        loop {
+            if self.done {
+                return Poll::Ready(None);
+            }
+
+            let res = match ready!(oio::Read::poll_next(&mut self.inner, cx)) {
+                Some(Ok(bs)) => self.decoder.write(&bs),
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => {
+                    self.done = true;
+                    self.decoder.finish()
+                }
+            };
+
+            match res {
+                Ok(buf) if buf.is_empty() => continue,
+                Ok(buf) => return Poll::Ready(Some(Ok(Bytes::from(buf)))),
+                Err(err) => {
+                    self.done = true;
+                    return Poll::Ready(Some(Err(Error::new(
+                        ErrorKind::Unexpected,
+                        "decode response body failed",
+                    )
+                    .with_operation("http_util::decode_content_encoding")
+                    .with_context("content_encoding", &self.encoding)
+                    .set_source(err))));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use flate2::write::DeflateEncoder;
+    use flate2::write::GzEncoder;
+    use flate2::write::ZlibEncoder;
+    use flate2::Compression;
+
+    use super::*;
+
+    /// Build a response whose body is split into small chunks.
+    fn new_response(encoding: &str, encoded: Vec<u8>) -> Response<IncomingAsyncBody> {
+        let size = encoded.len() as u64;
+        let chunks: Vec<Result<Bytes>> = encoded
+            .chunks(7)
+            .map(|v| Ok(Bytes::copy_from_slice(v)))
+            .collect();
+        let stream = oio::into_stream(futures::stream::iter(chunks));
+        Response::builder()
+            .header(CONTENT_ENCODING, encoding)
+            .header(CONTENT_LENGTH, size)
+            .body(IncomingAsyncBody::new(Box::new(stream), Some(size)))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_decode_content_encoding() -> Result<()> {
+        let content = b"Hello, World!".repeat(64);
+
+        let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+        gz.write_all(&content).unwrap();
+        let gz = gz.finish().unwrap();
+
+        let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+        zlib.write_all(&content).unwrap();
+        let zlib = zlib.finish().unwrap();
+
+        let mut deflate = DeflateEncoder::new(Vec::new(), Compression::default());
+        deflate.write_all(&content).unwrap();
+        let deflate = deflate.finish().unwrap();
+
+        for (encoding, encoded) in [("gzip", gz), ("deflate", zlib), ("deflate", deflate)] {
+            let resp = decode_content_encoding(new_response(encoding, encoded))?;
+            assert!(resp.headers().get(CONTENT_ENCODING).is_none());
+            assert!(resp.headers().get(CONTENT_LENGTH).is_none());
+            assert_eq!(resp.into_body().bytes().await?, content.as_slice());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_unsupported() {
+        let err = decode_content_encoding(new_response("br", vec![]))
+            .err()
+            .expect("decode must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[tokio::test]
+    async fn test_decode_invalid() {
+        let resp = decode_content_encoding(new_response("gzip", b"not gzip data".to_vec()))
+            .expect("decode must succeed");
+        let err = resp.into_body().bytes().await.expect_err("read must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+}
@@ -18,6 +18,7 @@
 use http::response::Parts;
 use http::Uri;
 
+use super::parse_retry_after;
 use crate::Error;
 use crate::ErrorKind;
 
@@ -46,17 +47,46 @@ pub fn new_request_sign_error(err: anyhow::Error) -> Error {
         .set_source(err)
 }
 
+/// Headers that storage services use to return the request id.
+const REQUEST_ID_HEADERS: &[&str] = &[
+    "x-amz-request-id",
+    "x-ms-request-id",
+    "x-oss-request-id",
+    "x-cos-request-id",
+    "x-obs-request-id",
+    "x-request-id",
+];
+
+/// The max size of response body that will be kept in error context.
+const MAX_RESPONSE_BODY_SIZE: usize = 1024;
+
 /// Add response context to error.
 ///
 /// This helper function will:
 ///
 /// - remove sensitive or useless headers from parts.
 /// - fetch uri if parts extensions contains `Uri`.
+/// - record http status, request id and retry after so that users can fetch
+///   them via [`Error::http_status`], [`Error::request_id`] and
+///   [`Error::retry_after`].
 pub fn with_error_response_context(mut err: Error, mut parts: Parts) -> Error {
     if let Some(uri) = parts.extensions.get::<Uri>() {
         err = err.with_context("uri", uri.to_string());
     }
 
+    err = err.with_context("status", parts.status.as_u16().to_string());
+
+    if let Some(request_id) = REQUEST_ID_HEADERS
+        .iter()
+        .find_map(|k| parts.headers.get(*k).and_then(|v| v.to_str().ok()))
+    {
+        err = err.with_context("request_id", request_id);
+    }
+
+    if let Ok(Some(dur)) = parse_retry_after(&parts.headers) {
+        err = err.with_context("retry_after", format!("{}s", dur.as_secs()));
+    }
+
     // The following headers may contains sensitive information.
     parts.headers.remove("Set-Cookie");
     parts.headers.remove("WWW-Authenticate");
@@ -66,3 +96,58 @@ pub fn with_error_response_context(mut err: Error, mut parts: Parts) -> Error {
 
     err
 }
+
+/// Add response body snippet to error.
+///
+/// The body will be truncated to at most 1 KiB so that users can fetch it
+/// via [`Error::response_body`] without carrying the whole response.
+pub fn with_error_response_body(err: Error, bs: &[u8]) -> Error {
+    if bs.is_empty() {
+        return err;
+    }
+
+    let body = String::from_utf8_lossy(bs);
+    let mut end = body.len().min(MAX_RESPONSE_BODY_SIZE);
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    err.with_context("response_body", &body[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use http::Response;
+    use http::StatusCode;
+
+    use super::*;
+
+    #[test]
+    fn test_with_error_response_context() {
+        let (parts, _) = Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .header("x-amz-request-id", "4442587FB7D0A2F9")
+            .header("retry-after", "3")
+            .body(())
+            .unwrap()
+            .into_parts();
+
+        let err = with_error_response_context(Error::new(ErrorKind::NotFound, "not found"), parts);
+        assert_eq!(err.http_status(), Some(404));
+        assert_eq!(err.request_id(), Some("4442587FB7D0A2F9"));
+        assert_eq!(err.retry_after(), Some(Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn test_with_error_response_body() {
+        let err = with_error_response_body(Error::new(ErrorKind::Unexpected, "test"), b"");
+        assert_eq!(err.response_body(), None);
+
+        let bs = "a".repeat(2048);
+        let err =
+            with_error_response_body(Error::new(ErrorKind::Unexpected, "test"), bs.as_bytes());
+        assert_eq!(err.response_body().map(|v| v.len()), Some(1024));
+    }
+}
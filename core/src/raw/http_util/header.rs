@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::time::Duration;
+
 use base64::engine::general_purpose;
 use base64::Engine;
 use chrono::DateTime;
@@ -22,12 +24,15 @@ use chrono::Utc;
 use http::header::HeaderName;
 use http::header::CACHE_CONTROL;
 use http::header::CONTENT_DISPOSITION;
+use http::header::CONTENT_ENCODING;
+use http::header::CONTENT_LANGUAGE;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_RANGE;
 use http::header::CONTENT_TYPE;
 use http::header::ETAG;
 use http::header::LAST_MODIFIED;
 use http::header::LOCATION;
+use http::header::RETRY_AFTER;
 use http::HeaderMap;
 use http::HeaderValue;
 use md5::Digest;
@@ -116,6 +121,36 @@ pub fn parse_content_md5(headers: &HeaderMap) -> Result<Option<&str>> {
     }
 }
 
+/// Parse content encoding from header map.
+pub fn parse_content_encoding(headers: &HeaderMap) -> Result<Option<&str>> {
+    match headers.get(CONTENT_ENCODING) {
+        None => Ok(None),
+        Some(v) => Ok(Some(v.to_str().map_err(|e| {
+            Error::new(
+                ErrorKind::Unexpected,
+                "header value is not valid utf-8 string",
+            )
+            .with_operation("http_util::parse_content_encoding")
+            .set_source(e)
+        })?)),
+    }
+}
+
+/// Parse content language from header map.
+pub fn parse_content_language(headers: &HeaderMap) -> Result<Option<&str>> {
+    match headers.get(CONTENT_LANGUAGE) {
+        None => Ok(None),
+        Some(v) => Ok(Some(v.to_str().map_err(|e| {
+            Error::new(
+                ErrorKind::Unexpected,
+                "header value is not valid utf-8 string",
+            )
+            .with_operation("http_util::parse_content_language")
+            .set_source(e)
+        })?)),
+    }
+}
+
 /// Parse content type from header map.
 pub fn parse_content_type(headers: &HeaderMap) -> Result<Option<&str>> {
     match headers.get(CONTENT_TYPE) {
@@ -184,6 +219,33 @@ pub fn parse_etag(headers: &HeaderMap) -> Result<Option<&str>> {
     }
 }
 
+/// Parse retry after from header map.
+///
+/// `Retry-After` could be either a number of seconds or a http date. The
+/// returned duration will be zero if the date is already passed.
+pub fn parse_retry_after(headers: &HeaderMap) -> Result<Option<Duration>> {
+    match headers.get(RETRY_AFTER) {
+        None => Ok(None),
+        Some(v) => {
+            let v = v.to_str().map_err(|e| {
+                Error::new(
+                    ErrorKind::Unexpected,
+                    "header value is not valid utf-8 string",
+                )
+                .with_operation("http_util::parse_retry_after")
+                .set_source(e)
+            })?;
+
+            if let Ok(secs) = v.trim().parse::<u64>() {
+                return Ok(Some(Duration::from_secs(secs)));
+            }
+
+            let dt = parse_datetime_from_rfc2822(v)?;
+            Ok(Some((dt - Utc::now()).to_std().unwrap_or_default()))
+        }
+    }
+}
+
 /// Parse Content-Disposition for header map
 pub fn parse_content_disposition(headers: &HeaderMap) -> Result<Option<&str>> {
     match headers.get(CONTENT_DISPOSITION) {
@@ -246,6 +308,14 @@ pub fn parse_into_metadata(path: &str, headers: &HeaderMap) -> Result<Metadata>
         m.set_content_disposition(v);
     }
 
+    if let Some(v) = parse_content_encoding(headers)? {
+        m.set_content_encoding(v);
+    }
+
+    if let Some(v) = parse_content_language(headers)? {
+        m.set_content_language(v);
+    }
+
     Ok(m)
 }
 
@@ -303,10 +373,71 @@ pub fn build_header_value(v: &str) -> Result<HeaderValue> {
     })
 }
 
+/// Headers that are managed by OpenDAL or the request signer, users
+/// can't override them via custom headers.
+const RESERVED_HEADERS: &[&str] = &[
+    "authorization",
+    "connection",
+    "content-length",
+    "date",
+    "expect",
+    "host",
+    "range",
+    "transfer-encoding",
+    "x-amz-content-sha256",
+    "x-amz-date",
+    "x-amz-security-token",
+    "x-ms-date",
+    "x-ms-version",
+];
+
+/// Check custom headers given by users.
+///
+/// Returns [`ErrorKind::InvalidInput`] if the header is invalid or reserved.
+pub fn check_custom_headers(headers: &[(String, String)]) -> Result<()> {
+    for (name, value) in headers {
+        let header = HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+            Error::new(ErrorKind::InvalidInput, "header name is invalid")
+                .with_operation("http_util::check_custom_headers")
+                .with_context("header", name)
+                .set_source(e)
+        })?;
+        if RESERVED_HEADERS.contains(&header.as_str()) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "header is reserved and can't be set as custom header",
+            )
+            .with_operation("http_util::check_custom_headers")
+            .with_context("header", name));
+        }
+        HeaderValue::from_str(value).map_err(|e| {
+            Error::new(ErrorKind::InvalidInput, "header value is invalid")
+                .with_operation("http_util::check_custom_headers")
+                .with_context("header", name)
+                .set_source(e)
+        })?;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_check_custom_headers() {
+        let headers = |name: &str| vec![(name.to_string(), "value".to_string())];
+
+        assert!(check_custom_headers(&headers("x-vendor-namespace")).is_ok());
+        for name in ["Authorization", "host", "Range", "X-Amz-Date"] {
+            let err = check_custom_headers(&headers(name)).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidInput, "{name}");
+        }
+        let err = check_custom_headers(&headers("invalid header")).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
     /// Test cases is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_DeleteObjects.html
     #[test]
     fn test_format_content_md5() {
@@ -367,4 +498,23 @@ mod tests {
             assert_eq!(actual, expected)
         }
     }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers).unwrap(), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+        assert_eq!(
+            parse_retry_after(&headers).unwrap(),
+            Some(Duration::from_secs(120))
+        );
+
+        // Dates in the past should be treated as retry immediately.
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(parse_retry_after(&headers).unwrap(), Some(Duration::ZERO));
+    }
 }
@@ -24,6 +24,9 @@
 
 mod client;
 pub use client::HttpClient;
+pub use client::HttpClientConfig;
+pub use client::HttpFetch;
+pub use client::HttpFetcher;
 
 mod body;
 pub use body::AsyncBody;
@@ -31,10 +34,13 @@ pub use body::IncomingAsyncBody;
 
 mod header;
 pub use header::build_header_value;
+pub use header::check_custom_headers;
 pub use header::format_authorization_by_basic;
 pub use header::format_authorization_by_bearer;
 pub use header::format_content_md5;
 pub use header::parse_content_disposition;
+pub use header::parse_content_encoding;
+pub use header::parse_content_language;
 pub use header::parse_content_length;
 pub use header::parse_content_md5;
 pub use header::parse_content_range;
@@ -43,6 +49,7 @@ pub use header::parse_etag;
 pub use header::parse_into_metadata;
 pub use header::parse_last_modified;
 pub use header::parse_location;
+pub use header::parse_retry_after;
 
 mod uri;
 pub use uri::percent_encode_path;
@@ -51,11 +58,17 @@ mod error;
 pub use error::new_request_build_error;
 pub use error::new_request_credential_error;
 pub use error::new_request_sign_error;
+pub use error::with_error_response_body;
 pub use error::with_error_response_context;
 
 mod bytes_range;
 pub use bytes_range::BytesRange;
 
+#[cfg(any(feature = "services-http", feature = "services-webdav"))]
+mod content_encoding;
+#[cfg(any(feature = "services-http", feature = "services-webdav"))]
+pub use content_encoding::decode_content_encoding;
+
 mod bytes_content_range;
 pub use bytes_content_range::BytesContentRange;
 
@@ -183,6 +183,22 @@ pub trait LayeredAccessor: Send + Sync + Debug + Unpin + 'static {
         self.inner().presign(path, args).await
     }
 
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        self.inner().get_tags(path, args).await
+    }
+
+    async fn put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        self.inner().put_tags(path, args).await
+    }
+
+    async fn get_object_lock(&self, path: &str, args: OpGetObjectLock) -> Result<RpGetObjectLock> {
+        self.inner().get_object_lock(path, args).await
+    }
+
+    async fn put_object_lock(&self, path: &str, args: OpPutObjectLock) -> Result<RpPutObjectLock> {
+        self.inner().put_object_lock(path, args).await
+    }
+
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         self.inner().blocking_create_dir(path, args)
     }
@@ -208,6 +224,14 @@ pub trait LayeredAccessor: Send + Sync + Debug + Unpin + 'static {
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)>;
+
+    fn blocking_get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        self.inner().blocking_get_tags(path, args)
+    }
+
+    fn blocking_put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        self.inner().blocking_put_tags(path, args)
+    }
 }
 
 #[async_trait]
@@ -268,6 +292,22 @@ impl<L: LayeredAccessor> Accessor for L {
         (self as &L).presign(path, args).await
     }
 
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        (self as &L).get_tags(path, args).await
+    }
+
+    async fn put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        (self as &L).put_tags(path, args).await
+    }
+
+    async fn get_object_lock(&self, path: &str, args: OpGetObjectLock) -> Result<RpGetObjectLock> {
+        (self as &L).get_object_lock(path, args).await
+    }
+
+    async fn put_object_lock(&self, path: &str, args: OpPutObjectLock) -> Result<RpPutObjectLock> {
+        (self as &L).put_object_lock(path, args).await
+    }
+
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         (self as &L).blocking_create_dir(path, args)
     }
@@ -299,6 +339,14 @@ impl<L: LayeredAccessor> Accessor for L {
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
         (self as &L).blocking_list(path, args)
     }
+
+    fn blocking_get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        (self as &L).blocking_get_tags(path, args)
+    }
+
+    fn blocking_put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        (self as &L).blocking_put_tags(path, args)
+    }
 }
 
 #[cfg(test)]
@@ -56,6 +56,12 @@ pub use serde_util::*;
 mod chrono_util;
 pub use chrono_util::*;
 
+mod tokio_util;
+pub use tokio_util::*;
+
+mod credential;
+pub use credential::*;
+
 // Expose as a pub mod to avoid confusing.
 pub mod adapters;
 pub mod oio;
@@ -66,6 +66,16 @@ pub trait Page: Send + Sync + 'static {
     /// `Ok(None)` means all pages have been returned. Any following call
     /// to `next` will always get the same result.
     async fn next(&mut self) -> Result<Option<Vec<Entry>>>;
+
+    /// Get the continuation token of the next page.
+    ///
+    /// The token can be passed back via `OpList::with_continuation_token` to
+    /// resume listing from the next page. Pagers that don't support resume
+    /// should return `None`, and layers that wrap pagers should forward this
+    /// call to inner pager.
+    fn continuation_token(&self) -> Option<String> {
+        None
+    }
 }
 
 /// The boxed version of [`Page`]
@@ -76,6 +86,10 @@ impl Page for Pager {
     async fn next(&mut self) -> Result<Option<Vec<Entry>>> {
         self.as_mut().next().await
     }
+
+    fn continuation_token(&self) -> Option<String> {
+        self.as_ref().continuation_token()
+    }
 }
 
 #[async_trait]
@@ -93,6 +107,10 @@ impl<P: Page> Page for Option<P> {
             None => Ok(None),
         }
     }
+
+    fn continuation_token(&self) -> Option<String> {
+        self.as_ref().and_then(|p| p.continuation_token())
+    }
 }
 
 /// BlockingPage is the blocking version of [`Page`].
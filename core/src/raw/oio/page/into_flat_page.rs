@@ -24,7 +24,12 @@ use crate::raw::*;
 use crate::*;
 
 /// to_flat_pager is used to make a hierarchy pager flat.
-pub fn into_flat_page<A: Accessor, P>(acc: A, path: &str, size: usize) -> FlatPager<A, P> {
+///
+/// The page size is decided by `args.limit()`, and `args.metadata()` will
+/// be passed to every nested list.
+pub fn into_flat_page<A: Accessor, P>(acc: A, path: &str, args: OpList) -> FlatPager<A, P> {
+    let size = args.limit().unwrap_or(1000);
+
     #[cfg(debug_assertions)]
     {
         let meta = acc.info();
@@ -37,6 +42,7 @@ pub fn into_flat_page<A: Accessor, P>(acc: A, path: &str, size: usize) -> FlatPa
     FlatPager {
         acc,
         size,
+        metadata: args.metadata(),
         root: path.to_string(),
         dirs: VecDeque::from([oio::Entry::new(path, Metadata::new(EntryMode::DIR))]),
         pagers: vec![],
@@ -83,6 +89,7 @@ pub fn into_flat_page<A: Accessor, P>(acc: A, path: &str, size: usize) -> FlatPa
 pub struct FlatPager<A: Accessor, P> {
     acc: A,
     size: usize,
+    metadata: bool,
     root: String,
     dirs: VecDeque<oio::Entry>,
     pagers: Vec<(P, oio::Entry, Vec<oio::Entry>)>,
@@ -98,7 +105,10 @@ where
     async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
         loop {
             if let Some(de) = self.dirs.pop_back() {
-                let (_, op) = self.acc.list(de.path(), OpList::new()).await?;
+                let (_, op) = self
+                    .acc
+                    .list(de.path(), OpList::new().with_metadata(self.metadata))
+                    .await?;
                 self.pagers.push((op, de, vec![]))
             }
 
@@ -158,7 +168,9 @@ where
     fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
         loop {
             if let Some(de) = self.dirs.pop_back() {
-                let (_, op) = self.acc.blocking_list(de.path(), OpList::new())?;
+                let (_, op) = self
+                    .acc
+                    .blocking_list(de.path(), OpList::new().with_metadata(self.metadata))?;
                 self.pagers.push((op, de, vec![]))
             }
 
@@ -299,7 +311,7 @@ mod tests {
         let _ = tracing_subscriber::fmt().with_test_writer().try_init();
 
         let acc = MockService::new();
-        let mut pager = into_flat_page(acc, "x/", 10);
+        let mut pager = into_flat_page(acc, "x/", OpList::new().with_limit(10));
 
         let mut entries = Vec::default();
 
@@ -0,0 +1,81 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use async_trait::async_trait;
+
+use crate::raw::*;
+use crate::*;
+
+/// into_glob_page is used to filter entries that match given glob pattern.
+///
+/// Refer to [`glob_match`] for the rules of glob pattern.
+pub fn into_glob_page<P>(pager: P, pattern: &str) -> GlobPager<P> {
+    GlobPager {
+        pager,
+        pattern: pattern.to_string(),
+    }
+}
+
+/// GlobPager will filter out entries that don't match the glob pattern.
+///
+/// # Notes
+///
+/// GlobPager filter entries after fetch entries. So it's possible
+/// to return an empty vec. It doesn't mean the all pages have been
+/// returned.
+///
+/// Please keep calling next_page until we returned `Ok(None)`
+pub struct GlobPager<P> {
+    pager: P,
+    pattern: String,
+}
+
+impl<P> GlobPager<P> {
+    fn filter_entries(&self, entries: Vec<oio::Entry>) -> Vec<oio::Entry> {
+        entries
+            .into_iter()
+            .filter(|e| glob_match(&self.pattern, e.path()))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl<P: oio::Page> oio::Page for GlobPager<P> {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        let entries = match self.pager.next().await? {
+            Some(entries) => entries,
+            None => return Ok(None),
+        };
+
+        Ok(Some(self.filter_entries(entries)))
+    }
+
+    fn continuation_token(&self) -> Option<String> {
+        self.pager.continuation_token()
+    }
+}
+
+impl<P: oio::BlockingPage> oio::BlockingPage for GlobPager<P> {
+    fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        let entries = match self.pager.next()? {
+            Some(entries) => entries,
+            None => return Ok(None),
+        };
+
+        Ok(Some(self.filter_entries(entries)))
+    }
+}
@@ -0,0 +1,128 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use async_trait::async_trait;
+
+use crate::raw::*;
+use crate::*;
+
+/// into_limit_page is used to stop listing after given count of entries
+/// have been returned.
+pub fn into_limit_page<P>(pager: P, limit: usize) -> LimitPager<P> {
+    LimitPager {
+        pager,
+        remaining: limit,
+    }
+}
+
+/// LimitPager will truncate entries to the limit and stop fetching new
+/// pages from underlying pager once the limit has been reached.
+pub struct LimitPager<P> {
+    pager: P,
+    remaining: usize,
+}
+
+impl<P> LimitPager<P> {
+    fn truncate_entries(&mut self, mut entries: Vec<oio::Entry>) -> Vec<oio::Entry> {
+        entries.truncate(self.remaining);
+        self.remaining -= entries.len();
+        entries
+    }
+}
+
+#[async_trait]
+impl<P: oio::Page> oio::Page for LimitPager<P> {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        let entries = match self.pager.next().await? {
+            Some(entries) => entries,
+            None => return Ok(None),
+        };
+
+        Ok(Some(self.truncate_entries(entries)))
+    }
+
+    /// Entries could be dropped while truncating, so we can't resume from
+    /// underlying pager's token after the limit has been reached.
+    fn continuation_token(&self) -> Option<String> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.pager.continuation_token()
+    }
+}
+
+impl<P: oio::BlockingPage> oio::BlockingPage for LimitPager<P> {
+    fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        let entries = match self.pager.next()? {
+            Some(entries) => entries,
+            None => return Ok(None),
+        };
+
+        Ok(Some(self.truncate_entries(entries)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockPager {
+        pages: Vec<Vec<&'static str>>,
+        fetched: usize,
+    }
+
+    impl oio::BlockingPage for MockPager {
+        fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+            if self.fetched == self.pages.len() {
+                return Ok(None);
+            }
+            let page = self.pages[self.fetched]
+                .iter()
+                .map(|p| oio::Entry::new(p, Metadata::new(EntryMode::FILE)))
+                .collect();
+            self.fetched += 1;
+            Ok(Some(page))
+        }
+    }
+
+    #[test]
+    fn test_limit_page_stops_fetching() {
+        let pager = MockPager {
+            pages: vec![vec!["a", "b"], vec!["c", "d"], vec!["e"]],
+            fetched: 0,
+        };
+        let mut pager = into_limit_page(pager, 3);
+
+        let mut paths = vec![];
+        while let Some(entries) = oio::BlockingPage::next(&mut pager).unwrap() {
+            paths.extend(entries.into_iter().map(|e| e.path().to_string()));
+        }
+
+        assert_eq!(paths, vec!["a", "b", "c"]);
+        // The last page should never be fetched.
+        assert_eq!(pager.pager.fetched, 2);
+    }
+}
@@ -0,0 +1,143 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use async_trait::async_trait;
+use chrono::DateTime;
+use chrono::Utc;
+use futures::stream;
+use futures::StreamExt;
+use futures::TryStreamExt;
+
+use crate::raw::*;
+use crate::*;
+
+/// into_modified_since_page is used to filter files that modified after
+/// given time.
+///
+/// Entries that carry last modified are filtered directly, others will be
+/// stat concurrently (at most 16 at the same time) to fetch the metadata.
+/// An `Unsupported` error will be returned if service still doesn't
+/// return last modified for them.
+pub fn into_modified_since_page<A: Accessor, P>(
+    acc: A,
+    pager: P,
+    since: DateTime<Utc>,
+) -> ModifiedSincePager<A, P> {
+    ModifiedSincePager { acc, pager, since }
+}
+
+/// ModifiedSincePager will filter out dirs and files that not modified
+/// after the given time.
+///
+/// # Notes
+///
+/// ModifiedSincePager filter entries after fetch entries. So it's possible
+/// to return an empty vec. It doesn't mean the all pages have been
+/// returned.
+///
+/// Please keep calling next_page until we returned `Ok(None)`
+pub struct ModifiedSincePager<A: Accessor, P> {
+    acc: A,
+    pager: P,
+    since: DateTime<Utc>,
+}
+
+/// Check if the last modified of entry has been fetched.
+fn has_last_modified(entry: &oio::Entry) -> bool {
+    let bit = entry.metadata().bit();
+    bit.contains(Metakey::LastModified) || bit.contains(Metakey::Complete)
+}
+
+impl<A: Accessor, P> ModifiedSincePager<A, P> {
+    fn is_modified(&self, entry: &oio::Entry) -> Result<bool> {
+        match entry.metadata().last_modified() {
+            Some(v) => Ok(v > self.since),
+            None => Err(Error::new(
+                ErrorKind::Unsupported,
+                "service doesn't return last modified of files",
+            )
+            .with_operation("ModifiedSincePager::next")
+            .with_context("path", entry.path())),
+        }
+    }
+
+    fn blocking_fill_metadata(&self, entry: oio::Entry) -> Result<oio::Entry> {
+        if has_last_modified(&entry) {
+            return Ok(entry);
+        }
+
+        let rp = self.acc.blocking_stat(entry.path(), OpStat::new())?;
+        Ok(oio::Entry::new(entry.path(), rp.into_metadata()))
+    }
+}
+
+/// Stat the entry to fetch its last modified.
+async fn fill_metadata<A: Accessor>(acc: &A, entry: oio::Entry) -> Result<oio::Entry> {
+    let rp = acc.stat(entry.path(), OpStat::new()).await?;
+    Ok(oio::Entry::new(entry.path(), rp.into_metadata()))
+}
+
+#[async_trait]
+impl<A: Accessor, P: oio::Page> oio::Page for ModifiedSincePager<A, P> {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        let entries = match self.pager.next().await? {
+            Some(entries) => entries,
+            None => return Ok(None),
+        };
+
+        let mut res = Vec::with_capacity(entries.len());
+        let mut unknown = Vec::new();
+        for entry in entries.into_iter().filter(|e| e.mode().is_file()) {
+            if !has_last_modified(&entry) {
+                unknown.push(entry);
+            } else if self.is_modified(&entry)? {
+                res.push(entry);
+            }
+        }
+
+        let acc = &self.acc;
+        let mut filled = stream::iter(unknown)
+            .map(|e| fill_metadata(acc, e))
+            .buffer_unordered(16);
+        while let Some(entry) = filled.try_next().await? {
+            if self.is_modified(&entry)? {
+                res.push(entry);
+            }
+        }
+
+        Ok(Some(res))
+    }
+}
+
+impl<A: Accessor, P: oio::BlockingPage> oio::BlockingPage for ModifiedSincePager<A, P> {
+    fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        let entries = match self.pager.next()? {
+            Some(entries) => entries,
+            None => return Ok(None),
+        };
+
+        let mut res = Vec::with_capacity(entries.len());
+        for entry in entries.into_iter().filter(|e| e.mode().is_file()) {
+            let entry = self.blocking_fill_metadata(entry)?;
+            if self.is_modified(&entry)? {
+                res.push(entry);
+            }
+        }
+
+        Ok(Some(res))
+    }
+}
@@ -0,0 +1,218 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use async_trait::async_trait;
+
+use crate::raw::*;
+use crate::*;
+
+/// into_sorted_page is used to make sure entries returned by pager are in
+/// lexicographic order.
+///
+/// - If `ordered_pages` is true, underlying service guarantees that every
+///   page is greater than the previous one, we only need to sort entries
+///   within each page.
+/// - If `ordered_pages` is false, we have to buffer all entries in memory
+///   and sort them before returning the first page.
+pub fn into_sorted_page<P>(pager: P, ordered_pages: bool) -> SortedPager<P> {
+    SortedPager {
+        pager,
+        ordered_pages,
+        done: false,
+    }
+}
+
+/// SortedPager will sort entries returned by underlying pager.
+///
+/// # Notes
+///
+/// SortedPager will buffer the whole listing in memory if underlying
+/// service doesn't return pages in order. Please take care of the memory
+/// usage while listing a huge dir.
+pub struct SortedPager<P> {
+    pager: P,
+    ordered_pages: bool,
+    done: bool,
+}
+
+fn sort_entries(entries: &mut [oio::Entry]) {
+    entries.sort_unstable_by(|a, b| a.path().cmp(b.path()));
+}
+
+#[async_trait]
+impl<P: oio::Page> oio::Page for SortedPager<P> {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        if self.ordered_pages {
+            let mut entries = match self.pager.next().await? {
+                Some(entries) => entries,
+                None => {
+                    self.done = true;
+                    return Ok(None);
+                }
+            };
+            sort_entries(&mut entries);
+            return Ok(Some(entries));
+        }
+
+        let mut entries = vec![];
+        while let Some(mut es) = self.pager.next().await? {
+            entries.append(&mut es);
+        }
+        sort_entries(&mut entries);
+        self.done = true;
+
+        Ok(Some(entries))
+    }
+
+    /// Pages are sorted one by one or the whole listing has been consumed,
+    /// so it's safe to resume from underlying pager's token.
+    fn continuation_token(&self) -> Option<String> {
+        self.pager.continuation_token()
+    }
+}
+
+impl<P: oio::BlockingPage> oio::BlockingPage for SortedPager<P> {
+    fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        if self.ordered_pages {
+            let mut entries = match self.pager.next()? {
+                Some(entries) => entries,
+                None => {
+                    self.done = true;
+                    return Ok(None);
+                }
+            };
+            sort_entries(&mut entries);
+            return Ok(Some(entries));
+        }
+
+        let mut entries = vec![];
+        while let Some(mut es) = self.pager.next()? {
+            entries.append(&mut es);
+        }
+        sort_entries(&mut entries);
+        self.done = true;
+
+        Ok(Some(entries))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use oio::BlockingPage;
+
+    use super::*;
+
+    struct MockPager {
+        pages: Vec<Vec<&'static str>>,
+    }
+
+    impl MockPager {
+        fn next_entries(&mut self) -> Option<Vec<oio::Entry>> {
+            if self.pages.is_empty() {
+                return None;
+            }
+
+            let entries = self
+                .pages
+                .remove(0)
+                .into_iter()
+                .map(|path| oio::Entry::new(path, Metadata::new(EntryMode::FILE)))
+                .collect();
+
+            Some(entries)
+        }
+    }
+
+    #[async_trait]
+    impl oio::Page for MockPager {
+        async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+            Ok(self.next_entries())
+        }
+
+        fn continuation_token(&self) -> Option<String> {
+            (!self.pages.is_empty()).then(|| format!("page-{}", self.pages.len()))
+        }
+    }
+
+    impl BlockingPage for MockPager {
+        fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+            Ok(self.next_entries())
+        }
+    }
+
+    fn collect(mut pager: impl BlockingPage) -> Result<Vec<String>> {
+        let mut paths = vec![];
+        while let Some(es) = pager.next()? {
+            paths.extend(es.into_iter().map(|e| e.path().to_string()));
+        }
+        Ok(paths)
+    }
+
+    #[test]
+    fn test_sorted_within_ordered_pages() -> Result<()> {
+        let pager = MockPager {
+            pages: vec![vec!["b", "a"], vec!["d", "c"]],
+        };
+
+        let paths = collect(into_sorted_page(pager, true))?;
+        assert_eq!(paths, vec!["a", "b", "c", "d"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sorted_across_pages() -> Result<()> {
+        let pager = MockPager {
+            pages: vec![vec!["d", "a"], vec!["c", "b"]],
+        };
+
+        let paths = collect(into_sorted_page(pager, false))?;
+        assert_eq!(paths, vec!["a", "b", "c", "d"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_forward_continuation_token() -> Result<()> {
+        let pager = MockPager {
+            pages: vec![vec!["b", "a"], vec!["d", "c"]],
+        };
+
+        let mut pager = into_sorted_page(pager, true);
+        assert_eq!(
+            oio::Page::continuation_token(&pager),
+            Some("page-2".to_string())
+        );
+        oio::Page::next(&mut pager).await?;
+        assert_eq!(
+            oio::Page::continuation_token(&pager),
+            Some("page-1".to_string())
+        );
+        oio::Page::next(&mut pager).await?;
+        assert_eq!(oio::Page::continuation_token(&pager), None);
+
+        Ok(())
+    }
+}
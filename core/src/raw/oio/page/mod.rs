@@ -29,3 +29,23 @@ pub use into_flat_page::FlatPager;
 mod into_hierarchy_pager;
 pub use into_hierarchy_pager::into_hierarchy_page;
 pub use into_hierarchy_pager::HierarchyPager;
+
+mod into_glob_page;
+pub use into_glob_page::into_glob_page;
+pub use into_glob_page::GlobPager;
+
+mod into_prefixes_page;
+pub use into_prefixes_page::into_prefixes_page;
+pub use into_prefixes_page::PrefixesPager;
+
+mod into_sorted_page;
+pub use into_sorted_page::into_sorted_page;
+pub use into_sorted_page::SortedPager;
+
+mod into_limit_page;
+pub use into_limit_page::into_limit_page;
+pub use into_limit_page::LimitPager;
+
+mod into_modified_since_page;
+pub use into_modified_since_page::into_modified_since_page;
+pub use into_modified_since_page::ModifiedSincePager;
@@ -0,0 +1,216 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cmp;
+use std::future::Future;
+use std::io::SeekFrom;
+use std::pin::Pin;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+
+use crate::raw::*;
+use crate::*;
+
+/// Convert given range of path into [`oio::Read`] which fetches data by
+/// sending one range request for every `chunk` bytes.
+///
+/// # Notes
+///
+/// Only one chunk will be fetched at a time, combine with
+/// [`oio::into_read_ahead`] to prefetch the next chunk.
+pub fn into_chunked_read<A: Accessor + Clone>(
+    acc: A,
+    path: &str,
+    op: OpRead,
+    offset: u64,
+    size: u64,
+    chunk: usize,
+) -> ChunkedReader<A> {
+    ChunkedReader {
+        acc,
+        path: path.to_string(),
+        op,
+        offset,
+        size,
+        chunk: chunk.max(1) as u64,
+        cur: 0,
+        chunk_end: 0,
+        state: State::Idle,
+    }
+}
+
+/// ChunkedReader reads data by range requests of fixed size.
+pub struct ChunkedReader<A: Accessor> {
+    acc: A,
+    path: String,
+    op: OpRead,
+
+    offset: u64,
+    size: u64,
+    chunk: u64,
+    cur: u64,
+    /// The end position of the chunk that is being read.
+    chunk_end: u64,
+    state: State<A::Reader>,
+}
+
+enum State<R: oio::Read> {
+    Idle,
+    Sending(BoxFuture<'static, Result<(RpRead, R)>>),
+    Reading(R),
+}
+
+/// Safety: State will only be accessed under &mut.
+unsafe impl<R: oio::Read> Sync for State<R> {}
+
+impl<A: Accessor + Clone> ChunkedReader<A> {
+    fn read_future(&mut self) -> BoxFuture<'static, Result<(RpRead, A::Reader)>> {
+        let acc = self.acc.clone();
+        let path = self.path.clone();
+        let size = cmp::min(self.chunk, self.size - self.cur);
+        let op = self
+            .op
+            .clone()
+            .with_range(BytesRange::new(Some(self.offset + self.cur), Some(size)));
+        self.chunk_end = self.cur + size;
+
+        Box::pin(async move { acc.read(&path, op).await })
+    }
+
+    /// Check the chunk has been fully consumed while reaching its end.
+    fn finish_chunk(&mut self) -> Result<()> {
+        self.state = State::Idle;
+
+        if self.cur < self.chunk_end {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "service returned less data than the requested chunk",
+            )
+            .with_context("path", &self.path)
+            .with_context("expect", self.chunk_end.to_string())
+            .with_context("actual", self.cur.to_string()));
+        }
+        Ok(())
+    }
+}
+
+impl<A: Accessor + Clone> oio::Read for ChunkedReader<A> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        match &mut self.state {
+            State::Idle => {
+                if self.cur >= self.size || buf.is_empty() {
+                    return Poll::Ready(Ok(0));
+                }
+
+                self.state = State::Sending(self.read_future());
+                self.poll_read(cx, buf)
+            }
+            State::Sending(fut) => {
+                let (_, r) = ready!(Pin::new(fut).poll(cx)).map_err(|err| {
+                    // If read future returns an error, we should reset
+                    // state to Idle so that we can retry it.
+                    self.state = State::Idle;
+                    err
+                })?;
+
+                self.state = State::Reading(r);
+                self.poll_read(cx, buf)
+            }
+            State::Reading(r) => match ready!(Pin::new(r).poll_read(cx, buf)) {
+                Ok(0) => {
+                    self.finish_chunk()?;
+                    self.poll_read(cx, buf)
+                }
+                Ok(n) => {
+                    self.cur += n as u64;
+                    Poll::Ready(Ok(n))
+                }
+                Err(e) => {
+                    self.state = State::Idle;
+                    Poll::Ready(Err(e))
+                }
+            },
+        }
+    }
+
+    fn poll_seek(&mut self, _: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        let (base, amt) = match pos {
+            SeekFrom::Start(n) => (0, n as i64),
+            SeekFrom::End(n) => (self.size as i64, n),
+            SeekFrom::Current(n) => (self.cur as i64, n),
+        };
+
+        let n = match base.checked_add(amt) {
+            Some(n) if n >= 0 => n as u64,
+            _ => {
+                return Poll::Ready(Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "invalid seek to a negative or overflowing position",
+                )))
+            }
+        };
+
+        // Drop the current chunk, the next read will start from new pos.
+        if n != self.cur {
+            self.state = State::Idle;
+            self.cur = n;
+        }
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        match &mut self.state {
+            State::Idle => {
+                if self.cur >= self.size {
+                    return Poll::Ready(None);
+                }
+
+                self.state = State::Sending(self.read_future());
+                self.poll_next(cx)
+            }
+            State::Sending(fut) => {
+                let (_, r) = ready!(Pin::new(fut).poll(cx)).map_err(|err| {
+                    // If read future returns an error, we should reset
+                    // state to Idle so that we can retry it.
+                    self.state = State::Idle;
+                    err
+                })?;
+
+                self.state = State::Reading(r);
+                self.poll_next(cx)
+            }
+            State::Reading(r) => match ready!(Pin::new(r).poll_next(cx)) {
+                Some(Ok(bs)) => {
+                    self.cur += bs.len() as u64;
+                    Poll::Ready(Some(Ok(bs)))
+                }
+                Some(Err(e)) => {
+                    self.state = State::Idle;
+                    Poll::Ready(Some(Err(e)))
+                }
+                None => {
+                    self.finish_chunk()?;
+                    self.poll_next(cx)
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,311 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cmp::min;
+use std::io::SeekFrom;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::Buf;
+use bytes::Bytes;
+
+use crate::raw::*;
+use crate::*;
+
+/// into_read_ahead is used to make [`oio::Read`] prefetch the next chunk
+/// while users are consuming the current one.
+///
+/// The returning reader will hold at most `size` bytes in memory: half of
+/// them for the chunk being consumed and another half for the chunk being
+/// prefetched.
+///
+/// # Notes
+///
+/// No task will be spawned, the next chunk is fetched every time the
+/// returning reader is polled. So the returning reader works with any
+/// runtime, and the prefetch will be stopped once the reader is dropped.
+pub fn into_read_ahead<R: oio::Read>(r: R, size: usize) -> ReadAheadReader<R> {
+    ReadAheadReader {
+        inner: r,
+        chunk_size: (size / 2).max(1),
+        pos: 0,
+        chunk: Bytes::new(),
+        buf: Vec::new(),
+        filled: 0,
+        next: None,
+        err: None,
+        eof: false,
+    }
+}
+
+/// ReadAheadReader will prefetch the next chunk while users are consuming
+/// the current one.
+pub struct ReadAheadReader<R> {
+    inner: R,
+    chunk_size: usize,
+    /// The position that has been consumed by users.
+    pos: u64,
+    /// The chunk that is being consumed by users.
+    chunk: Bytes,
+    /// The chunk that is being prefetched, only `buf[..filled]` is valid.
+    buf: Vec<u8>,
+    filled: usize,
+    /// The chunk that has been prefetched.
+    next: Option<Result<Bytes>>,
+    /// The error met while prefetching, it will be returned after the
+    /// bytes prefetched before it.
+    err: Option<Error>,
+    eof: bool,
+}
+
+impl<R: oio::Read> ReadAheadReader<R> {
+    /// Drive the inner reader to prefetch the next chunk.
+    ///
+    /// Returns `Ready` once the next chunk is ready or EOF is reached.
+    fn poll_fill(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if self.next.is_some() {
+            return Poll::Ready(());
+        }
+        if let Some(err) = self.err.take() {
+            self.next = Some(Err(err));
+            return Poll::Ready(());
+        }
+        if self.eof {
+            return Poll::Ready(());
+        }
+
+        if self.buf.is_empty() {
+            self.buf = vec![0; self.chunk_size];
+        }
+
+        loop {
+            let n = match self.inner.poll_read(cx, &mut self.buf[self.filled..]) {
+                Poll::Ready(Ok(n)) => n,
+                Poll::Ready(Err(err)) => {
+                    self.err = Some(err);
+                    0
+                }
+                Poll::Pending => return Poll::Pending,
+            };
+            self.filled += n;
+
+            if n == 0 {
+                self.eof = self.err.is_none();
+                if self.filled > 0 {
+                    self.next = Some(Ok(self.take_buf()));
+                } else if let Some(err) = self.err.take() {
+                    self.next = Some(Err(err));
+                }
+                return Poll::Ready(());
+            }
+            if self.filled == self.chunk_size {
+                self.next = Some(Ok(self.take_buf()));
+                return Poll::Ready(());
+            }
+        }
+    }
+
+    fn take_buf(&mut self) -> Bytes {
+        let mut buf = std::mem::take(&mut self.buf);
+        buf.truncate(self.filled);
+        self.filled = 0;
+        Bytes::from(buf)
+    }
+
+    fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        ready!(self.poll_fill(cx));
+
+        let res = self.next.take();
+        // Start to prefetch the next chunk while users are consuming this
+        // one, we will continue on following polls if it's pending.
+        if res.is_some() {
+            let _ = self.poll_fill(cx);
+        }
+        Poll::Ready(res)
+    }
+}
+
+impl<R: oio::Read> oio::Read for ReadAheadReader<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        if self.chunk.is_empty() {
+            match ready!(self.poll_recv(cx)) {
+                Some(Ok(bs)) => self.chunk = bs,
+                Some(Err(err)) => return Poll::Ready(Err(err)),
+                None => return Poll::Ready(Ok(0)),
+            }
+        } else {
+            let _ = self.poll_fill(cx);
+        }
+
+        let n = min(buf.len(), self.chunk.len());
+        buf[..n].copy_from_slice(&self.chunk[..n]);
+        self.chunk.advance(n);
+        self.pos += n as u64;
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        // The inner reader is ahead of users, so we must seek from the
+        // position that has been consumed.
+        let target = match pos {
+            SeekFrom::Current(0) => return Poll::Ready(Ok(self.pos)),
+            SeekFrom::Current(n) => {
+                let v = if n >= 0 {
+                    self.pos.checked_add(n as u64)
+                } else {
+                    self.pos.checked_sub(n.unsigned_abs())
+                };
+                match v {
+                    Some(v) => SeekFrom::Start(v),
+                    None => {
+                        return Poll::Ready(Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            "invalid seek to a negative or overflowing position",
+                        )))
+                    }
+                }
+            }
+            pos => pos,
+        };
+
+        let n = ready!(self.inner.poll_seek(cx, target))?;
+        self.pos = n;
+        self.chunk = Bytes::new();
+        self.filled = 0;
+        self.next = None;
+        self.err = None;
+        self.eof = false;
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        if !self.chunk.is_empty() {
+            let bs = std::mem::take(&mut self.chunk);
+            self.pos += bs.len() as u64;
+            return Poll::Ready(Some(Ok(bs)));
+        }
+
+        match ready!(self.poll_recv(cx)) {
+            Some(Ok(bs)) => {
+                self.pos += bs.len() as u64;
+                Poll::Ready(Some(Ok(bs)))
+            }
+            res => Poll::Ready(res),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::prelude::*;
+
+    use super::*;
+    use crate::raw::oio::ReadExt;
+
+    #[tokio::test]
+    async fn test_read_ahead() {
+        let mut rng = ThreadRng::default();
+        let size = rng.gen_range(1..1024 * 1024);
+        let mut content = vec![0; size];
+        rng.fill_bytes(&mut content);
+
+        let r = oio::Cursor::from(content.clone());
+        let mut r = into_read_ahead(r, 4096);
+
+        let mut bs = Vec::new();
+        let mut buf = vec![0; 100];
+        loop {
+            let n = r.read(&mut buf).await.expect("read must succeed");
+            if n == 0 {
+                break;
+            }
+            bs.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(bs, content);
+    }
+
+    #[tokio::test]
+    async fn test_read_ahead_seek() {
+        let content: Vec<u8> = (0..=255).collect();
+
+        let r = oio::Cursor::from(content.clone());
+        let mut r = into_read_ahead(r, 16);
+
+        let mut buf = vec![0; 10];
+        let n = r.read(&mut buf).await.expect("read must succeed");
+        assert_eq!(&buf[..n], &content[..n]);
+
+        let pos = r
+            .seek(SeekFrom::Current(100))
+            .await
+            .expect("seek must succeed");
+        assert_eq!(pos, n as u64 + 100);
+
+        let n = r.read(&mut buf).await.expect("read must succeed");
+        assert_eq!(&buf[..n], &content[pos as usize..pos as usize + n]);
+
+        let pos = r.seek(SeekFrom::End(-5)).await.expect("seek must succeed");
+        assert_eq!(pos, 251);
+        let n = r.read(&mut buf).await.expect("read must succeed");
+        assert_eq!(&buf[..n], &content[251..]);
+    }
+
+    #[tokio::test]
+    async fn test_read_ahead_error_after_data() {
+        struct MockReader {
+            data: Option<Bytes>,
+        }
+
+        impl oio::Read for MockReader {
+            fn poll_read(&mut self, _: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+                match self.data.take() {
+                    Some(bs) => {
+                        buf[..bs.len()].copy_from_slice(&bs);
+                        Poll::Ready(Ok(bs.len()))
+                    }
+                    None => Poll::Ready(Err(Error::new(ErrorKind::Unexpected, "broken"))),
+                }
+            }
+
+            fn poll_seek(&mut self, _: &mut Context<'_>, _: SeekFrom) -> Poll<Result<u64>> {
+                unimplemented!()
+            }
+
+            fn poll_next(&mut self, _: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+                unimplemented!()
+            }
+        }
+
+        let mut r = into_read_ahead(
+            MockReader {
+                data: Some(Bytes::from("hello")),
+            },
+            16,
+        );
+
+        let mut buf = vec![0; 16];
+        let n = r.read(&mut buf).await.expect("read must succeed");
+        assert_eq!(&buf[..n], b"hello");
+        let err = r.read(&mut buf).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+}
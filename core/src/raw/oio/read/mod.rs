@@ -31,6 +31,14 @@ mod into_seekable_read_by_range;
 pub use into_seekable_read_by_range::into_seekable_read_by_range;
 pub use into_seekable_read_by_range::ByRangeSeekableReader;
 
+mod into_read_ahead;
+pub use into_read_ahead::into_read_ahead;
+pub use into_read_ahead::ReadAheadReader;
+
+mod into_chunked_read;
+pub use into_chunked_read::into_chunked_read;
+pub use into_chunked_read::ChunkedReader;
+
 mod into_read_from_file;
 pub use into_read_from_file::into_read_from_file;
 pub use into_read_from_file::FromFileReader;
@@ -105,6 +105,14 @@ pub trait Write: Unpin + Send + Sync {
 
     /// Close the writer and make sure all data has been flushed.
     async fn close(&mut self) -> Result<()>;
+
+    /// Get the checkpoint of current upload which can be used to resume.
+    ///
+    /// Writers that don't support resume should return `None`, and layers
+    /// that wrap writers should forward this call to inner writer.
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        None
+    }
 }
 
 #[async_trait]
@@ -157,6 +165,10 @@ impl<T: Write + ?Sized> Write for Box<T> {
     async fn close(&mut self) -> Result<()> {
         (**self).close().await
     }
+
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        (**self).checkpoint()
+    }
 }
 
 /// BlockingWriter is a type erased [`BlockingWrite`]
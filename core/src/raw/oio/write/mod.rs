@@ -23,6 +23,7 @@ pub use api::WriteOperation;
 pub use api::Writer;
 
 mod multipart_upload_write;
+pub use multipart_upload_write::report_part;
 pub use multipart_upload_write::MultipartUploadPart;
 pub use multipart_upload_write::MultipartUploadWrite;
 pub use multipart_upload_write::MultipartUploadWriter;
@@ -16,9 +16,14 @@
 // under the License.
 
 use std::sync::Arc;
+use std::time::Instant;
 
 use async_trait::async_trait;
 use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::stream::FuturesOrdered;
+use futures::FutureExt;
+use futures::StreamExt;
 
 use crate::{raw::*, *};
 
@@ -94,29 +99,77 @@ pub struct MultipartUploadPart {
 /// - Add threshold for `write_once` to avoid unnecessary multipart uploads.
 /// - Allow users to switch to un-buffered mode if users write 16MiB every time.
 pub struct MultipartUploadWriter<W: MultipartUploadWrite> {
-    inner: W,
+    inner: Arc<W>,
     total_size: Option<u64>,
 
     upload_id: Option<String>,
     parts: Vec<MultipartUploadPart>,
+    uploaded: u64,
     buffer: oio::VectorCursor,
     buffer_size: usize,
+    concurrent: usize,
+    inflight: InflightParts,
     runtime: Option<Arc<tokio::runtime::Runtime>>,
+    part_callback: Option<WritePartCallback>,
 }
 
+/// The result of uploading a part: part number, data, start time and the
+/// uploaded part.
+type PartResult = (usize, Bytes, Instant, Result<MultipartUploadPart>);
+
+/// Parts that are being uploaded, in the order of part number.
+#[derive(Default)]
+struct InflightParts(FuturesOrdered<BoxFuture<'static, PartResult>>);
+
+/// # Safety
+///
+/// InflightParts will only be accessed by `&mut Self`
+unsafe impl Sync for InflightParts {}
+
 impl<W: MultipartUploadWrite> MultipartUploadWriter<W> {
     /// Create a new MultipartUploadWriter.
     pub fn new(inner: W, total_size: Option<u64>) -> Self {
         Self {
-            inner,
+            inner: Arc::new(inner),
             total_size,
 
             upload_id: None,
             parts: Vec::new(),
+            uploaded: 0,
             buffer: oio::VectorCursor::new(),
             buffer_size: DEFAULT_WRITE_MIN_SIZE,
+            concurrent: 1,
+            inflight: InflightParts::default(),
             runtime: None,
+            part_callback: None,
+        }
+    }
+
+    /// Configure the callback that will be called every time a part
+    /// has been uploaded.
+    pub fn with_part_callback(mut self, callback: Option<WritePartCallback>) -> Self {
+        self.part_callback = callback;
+        self
+    }
+
+    /// Resume the multipart upload from given checkpoint.
+    ///
+    /// Parts recorded in checkpoint will be reused, and new parts will be
+    /// appended after them.
+    pub fn with_checkpoint(mut self, checkpoint: Option<WriteCheckpoint>) -> Self {
+        if let Some(checkpoint) = checkpoint {
+            self.upload_id = Some(checkpoint.upload_id().to_string());
+            self.parts = checkpoint
+                .parts()
+                .iter()
+                .map(|(part_number, etag)| MultipartUploadPart {
+                    part_number: *part_number,
+                    etag: etag.clone(),
+                })
+                .collect();
+            self.uploaded = checkpoint.size();
         }
+        self
     }
 
     /// Configure the write_min_size.
@@ -132,19 +185,146 @@ impl<W: MultipartUploadWrite> MultipartUploadWriter<W> {
         self
     }
 
+    /// Configure the max number of parts to upload concurrently.
+    ///
+    /// Parts in flight will only be polled while writing or closing. Once
+    /// `concurrent` parts are in flight, write will wait for the first one
+    /// before accepting new data. Failed parts will be uploaded again by
+    /// the next write or close.
+    ///
+    /// This value is default to 1, which means parts are uploaded one by one.
+    pub fn with_concurrent(mut self, v: usize) -> Self {
+        self.concurrent = v.max(1);
+        self
+    }
+
     pub fn set_runtime(&mut self, runtime: Arc<tokio::runtime::Runtime>) {
         self.runtime = Some(runtime);
     }
+
+    fn blocking_runtime(&self) -> Result<Arc<tokio::runtime::Runtime>> {
+        self.runtime.clone().ok_or_else(|| {
+            Error::new(
+                ErrorKind::Unsupported,
+                "blocking write is not supported without runtime",
+            )
+            .with_operation("MultipartUploadWriter::blocking_write")
+        })
+    }
+
+    fn report_part(&self, part_number: usize, size: u64, start: Instant) {
+        report_part(
+            self.part_callback.as_ref(),
+            part_number,
+            size,
+            start,
+            self.inflight.0.len(),
+            self.buffer.len() as u64,
+        );
+    }
+}
+
+/// Report the stats of a flushed part to the callback if any.
+///
+/// This is shared by [`MultipartUploadWriter`] and services that upload
+/// parts by themselves.
+pub fn report_part(
+    callback: Option<&WritePartCallback>,
+    part_number: usize,
+    size: u64,
+    start: Instant,
+    parts_in_flight: usize,
+    buffered: u64,
+) {
+    if let Some(callback) = callback {
+        callback.call(WritePartStats {
+            part_number,
+            size,
+            elapsed: start.elapsed(),
+            parts_in_flight,
+            buffered,
+        });
+    }
+}
+
+impl<W: MultipartUploadWrite + 'static> MultipartUploadWriter<W> {
+    fn upload_part(
+        &self,
+        upload_id: &str,
+        part_number: usize,
+        bs: Bytes,
+    ) -> BoxFuture<'static, PartResult> {
+        let inner = self.inner.clone();
+        let upload_id = upload_id.to_string();
+
+        async move {
+            let start = Instant::now();
+            let res = inner
+                .write_part(
+                    &upload_id,
+                    part_number,
+                    bs.len() as u64,
+                    AsyncBody::Bytes(bs.clone()),
+                )
+                .await;
+            (part_number, bs, start, res)
+        }
+        .boxed()
+    }
+
+    /// Record the result of a part in flight.
+    ///
+    /// Failed part will be pushed back to the front so that it will be
+    /// uploaded again by the next call.
+    fn finish_part(
+        &mut self,
+        upload_id: &str,
+        (part_number, bs, start, res): PartResult,
+    ) -> Result<()> {
+        match res {
+            Ok(part) => {
+                self.uploaded += bs.len() as u64;
+                self.report_part(part_number, bs.len() as u64, start);
+                self.parts.push(part);
+                Ok(())
+            }
+            Err(err) => {
+                let fut = self.upload_part(upload_id, part_number, bs);
+                self.inflight.0.push_front(fut);
+                Err(err)
+            }
+        }
+    }
+
+    /// Poll parts in flight, and wait until there are less than `limit`
+    /// parts in flight.
+    async fn poll_inflight(&mut self, upload_id: &str, limit: usize) -> Result<()> {
+        loop {
+            let res = if self.inflight.0.len() >= limit {
+                self.inflight.0.next().await
+            } else {
+                match self.inflight.0.next().now_or_never() {
+                    Some(res) => res,
+                    None => return Ok(()),
+                }
+            };
+
+            match res {
+                Some(res) => self.finish_part(upload_id, res)?,
+                None => return Ok(()),
+            }
+        }
+    }
 }
 
 #[async_trait]
 impl<W> oio::Write for MultipartUploadWriter<W>
 where
-    W: MultipartUploadWrite,
+    W: MultipartUploadWrite + 'static,
 {
     async fn write(&mut self, bs: Bytes) -> Result<()> {
         let upload_id = match &self.upload_id {
-            Some(upload_id) => upload_id,
+            Some(upload_id) => upload_id.clone(),
             None => {
                 if self.total_size.unwrap_or_default() == bs.len() as u64 {
                     return self
@@ -154,8 +334,8 @@ where
                 }
 
                 let upload_id = self.inner.initiate_part().await?;
-                self.upload_id = Some(upload_id);
-                self.upload_id.as_deref().unwrap()
+                self.upload_id = Some(upload_id.clone());
+                upload_id
             }
         };
 
@@ -164,6 +344,24 @@ where
             return Ok(());
         }
 
+        if self.concurrent > 1 {
+            // Make sure there is room for a new part before accepting `bs`,
+            // so that `bs` can be written again if any part failed.
+            self.poll_inflight(&upload_id, self.concurrent).await?;
+
+            self.buffer.push(bs);
+            if self.buffer.len() <= self.buffer_size {
+                return Ok(());
+            }
+
+            let bs = self.buffer.peak_at_least(self.buffer_size);
+            self.buffer.take(bs.len());
+            let part_number = self.parts.len() + self.inflight.0.len();
+            let fut = self.upload_part(&upload_id, part_number, bs);
+            self.inflight.0.push_back(fut);
+            return Ok(());
+        }
+
         self.buffer.push(bs);
         // Return directly if the buffer is not full
         if self.buffer.len() <= self.buffer_size {
@@ -172,11 +370,12 @@ where
 
         let bs = self.buffer.peak_at_least(self.buffer_size);
         let size = bs.len();
+        let start = Instant::now();
 
         match self
             .inner
             .write_part(
-                upload_id,
+                &upload_id,
                 self.parts.len(),
                 size as u64,
                 AsyncBody::Bytes(bs),
@@ -185,6 +384,8 @@ where
         {
             Ok(part) => {
                 self.buffer.take(size);
+                self.uploaded += size as u64;
+                self.report_part(self.parts.len(), size as u64, start);
                 self.parts.push(part);
                 Ok(())
             }
@@ -206,22 +407,28 @@ where
         }
 
         let upload_id = match &self.upload_id {
-            Some(upload_id) => upload_id,
+            Some(upload_id) => upload_id.clone(),
             None => {
                 if self.total_size.unwrap_or_default() == size {
                     return self.inner.write_once(size, AsyncBody::Stream(s)).await;
                 }
 
                 let upload_id = self.inner.initiate_part().await?;
-                self.upload_id = Some(upload_id);
-                self.upload_id.as_deref().unwrap()
+                self.upload_id = Some(upload_id.clone());
+                upload_id
             }
         };
 
+        // Parts must be recorded in order, wait for all parts in flight.
+        self.poll_inflight(&upload_id, 1).await?;
+
+        let start = Instant::now();
         let part = self
             .inner
-            .write_part(upload_id, self.parts.len(), size, AsyncBody::Stream(s))
+            .write_part(&upload_id, self.parts.len(), size, AsyncBody::Stream(s))
             .await?;
+        self.uploaded += size;
+        self.report_part(self.parts.len(), size, start);
         self.parts.push(part);
 
         Ok(())
@@ -229,27 +436,29 @@ where
 
     async fn close(&mut self) -> Result<()> {
         let upload_id = if let Some(upload_id) = &self.upload_id {
-            upload_id
+            upload_id.clone()
         } else {
             return Ok(());
         };
 
+        // Make sure all parts in flight have been uploaded.
+        self.poll_inflight(&upload_id, 1).await?;
+
         // Make sure internal buffer has been flushed.
         if !self.buffer.is_empty() {
             let bs = self.buffer.peak_exact(self.buffer.len());
+            let size = bs.len() as u64;
+            let start = Instant::now();
 
             match self
                 .inner
-                .write_part(
-                    upload_id,
-                    self.parts.len(),
-                    bs.len() as u64,
-                    AsyncBody::Bytes(bs),
-                )
+                .write_part(&upload_id, self.parts.len(), size, AsyncBody::Bytes(bs))
                 .await
             {
                 Ok(part) => {
                     self.buffer.clear();
+                    self.uploaded += size;
+                    self.report_part(self.parts.len(), size, start);
                     self.parts.push(part);
                 }
                 Err(e) => {
@@ -258,17 +467,38 @@ where
             }
         }
 
-        self.inner.complete_part(upload_id, &self.parts).await
+        self.inner.complete_part(&upload_id, &self.parts).await
     }
 
     async fn abort(&mut self) -> Result<()> {
+        // Parts in flight are useless after abort.
+        self.inflight = InflightParts::default();
+
         let upload_id = if let Some(upload_id) = &self.upload_id {
             upload_id
         } else {
             return Ok(());
         };
 
-        self.inner.abort_part(upload_id).await
+        self.inner.abort_part(upload_id).await?;
+
+        // The upload has been aborted, make sure we will not reuse it.
+        self.upload_id = None;
+        self.parts.clear();
+        self.uploaded = 0;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        let upload_id = self.upload_id.as_deref()?;
+        let parts = self
+            .parts
+            .iter()
+            .map(|p| (p.part_number, p.etag.clone()))
+            .collect();
+
+        Some(WriteCheckpoint::new(upload_id, parts, self.uploaded))
     }
 }
 
@@ -277,16 +507,191 @@ where
     W: MultipartUploadWrite + 'static,
 {
     fn write(&mut self, bs: Bytes) -> Result<()> {
-        self.runtime
-            .clone()
-            .unwrap()
-            .block_on(oio::Write::write(self, bs))
+        let runtime = self.blocking_runtime()?;
+        block_on(&runtime, oio::Write::write(self, bs))
     }
 
     fn close(&mut self) -> Result<()> {
-        self.runtime
-            .clone()
-            .unwrap()
-            .block_on(oio::Write::close(self))
+        let runtime = self.blocking_runtime()?;
+        block_on(&runtime, oio::Write::close(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicBool;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::raw::oio::Write;
+
+    struct MockWrite;
+
+    #[async_trait]
+    impl MultipartUploadWrite for MockWrite {
+        async fn write_once(&self, _: u64, _: AsyncBody) -> Result<()> {
+            Ok(())
+        }
+
+        async fn initiate_part(&self) -> Result<String> {
+            Ok("upload_id".to_string())
+        }
+
+        async fn write_part(
+            &self,
+            _: &str,
+            part_number: usize,
+            _: u64,
+            _: AsyncBody,
+        ) -> Result<MultipartUploadPart> {
+            Ok(MultipartUploadPart {
+                part_number,
+                etag: format!("etag-{part_number}"),
+            })
+        }
+
+        async fn complete_part(&self, _: &str, _: &[MultipartUploadPart]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn abort_part(&self, _: &str) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_part_callback() {
+        let stats = Arc::new(Mutex::new(Vec::new()));
+        let collected = stats.clone();
+        let callback = WritePartCallback::new(move |v| collected.lock().unwrap().push(v));
+
+        let mut w = MultipartUploadWriter::new(MockWrite, None)
+            .with_write_min_size(4)
+            .with_part_callback(Some(callback));
+        w.write(Bytes::from("hello")).await.unwrap();
+        w.write(Bytes::from("world!")).await.unwrap();
+        w.close().await.unwrap();
+
+        let stats = stats.lock().unwrap();
+        let parts: Vec<_> = stats.iter().map(|v| (v.part_number, v.size)).collect();
+        assert_eq!(parts, vec![(0, 5), (1, 6)]);
+        assert!(stats.iter().all(|v| v.parts_in_flight == 0));
+    }
+
+    #[tokio::test]
+    async fn test_resume_from_checkpoint() {
+        let mut w = MultipartUploadWriter::new(MockWrite, None).with_write_min_size(4);
+        assert_eq!(w.checkpoint(), None);
+
+        w.write(Bytes::from("hello")).await.unwrap();
+        let checkpoint = w.checkpoint().expect("checkpoint must exist");
+        assert_eq!(checkpoint.upload_id(), "upload_id");
+        assert_eq!(checkpoint.parts(), &[(0, "etag-0".to_string())]);
+        assert_eq!(checkpoint.size(), 5);
+
+        // Checkpoint should be able to persist and load back.
+        let content = serde_json::to_string(&checkpoint).unwrap();
+        let checkpoint: WriteCheckpoint = serde_json::from_str(&content).unwrap();
+
+        let mut w = MultipartUploadWriter::new(MockWrite, None)
+            .with_write_min_size(4)
+            .with_checkpoint(Some(checkpoint));
+        w.write(Bytes::from("world!")).await.unwrap();
+
+        let checkpoint = w.checkpoint().expect("checkpoint must exist");
+        assert_eq!(
+            checkpoint.parts(),
+            &[(0, "etag-0".to_string()), (1, "etag-1".to_string())]
+        );
+        assert_eq!(checkpoint.size(), 11);
+        w.close().await.unwrap();
+    }
+
+    /// MockConcurrentWrite fails the first upload of part 1 and tracks the
+    /// max number of parts in flight.
+    #[derive(Default)]
+    struct MockConcurrentWrite {
+        inflight: AtomicUsize,
+        max_inflight: AtomicUsize,
+        failed: AtomicBool,
+    }
+
+    #[async_trait]
+    impl MultipartUploadWrite for MockConcurrentWrite {
+        async fn write_once(&self, _: u64, _: AsyncBody) -> Result<()> {
+            Ok(())
+        }
+
+        async fn initiate_part(&self) -> Result<String> {
+            Ok("upload_id".to_string())
+        }
+
+        async fn write_part(
+            &self,
+            _: &str,
+            part_number: usize,
+            _: u64,
+            _: AsyncBody,
+        ) -> Result<MultipartUploadPart> {
+            let n = self.inflight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_inflight.fetch_max(n, Ordering::SeqCst);
+            for _ in 0..3 {
+                tokio::task::yield_now().await;
+            }
+            self.inflight.fetch_sub(1, Ordering::SeqCst);
+
+            if part_number == 1 && !self.failed.swap(true, Ordering::SeqCst) {
+                return Err(Error::new(ErrorKind::Unexpected, "part failed").set_temporary());
+            }
+            Ok(MultipartUploadPart {
+                part_number,
+                etag: format!("etag-{part_number}"),
+            })
+        }
+
+        async fn complete_part(&self, _: &str, parts: &[MultipartUploadPart]) -> Result<()> {
+            let numbers: Vec<_> = parts.iter().map(|p| p.part_number).collect();
+            assert_eq!(numbers, (0..numbers.len()).collect::<Vec<_>>());
+            Ok(())
+        }
+
+        async fn abort_part(&self, _: &str) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_write() {
+        let stats = Arc::new(Mutex::new(Vec::new()));
+        let collected = stats.clone();
+        let callback = WritePartCallback::new(move |v| collected.lock().unwrap().push(v));
+
+        let mut w = MultipartUploadWriter::new(MockConcurrentWrite::default(), None)
+            .with_write_min_size(4)
+            .with_concurrent(2)
+            .with_part_callback(Some(callback));
+
+        let mut failed = 0;
+        for bs in ["hello", "world", "write", "parts", "again"] {
+            // Write the same bytes again after failure like RetryLayer does.
+            while let Err(err) = w.write(Bytes::from(bs)).await {
+                assert!(err.is_temporary());
+                failed += 1;
+            }
+        }
+        w.close().await.unwrap();
+
+        assert_eq!(failed, 1);
+        assert_eq!(w.parts.len(), 5);
+        assert_eq!(w.uploaded, 25);
+        assert_eq!(w.inner.max_inflight.load(Ordering::SeqCst), 2);
+
+        let stats = stats.lock().unwrap();
+        assert_eq!(stats.len(), 5);
+        // The other part is still in flight while one part finishes.
+        assert!(stats.iter().any(|v| v.parts_in_flight == 1));
+        assert!(stats.iter().all(|v| v.parts_in_flight <= 1));
     }
 }
@@ -47,6 +47,14 @@ pub enum Operation {
     Batch,
     /// Operation for [`crate::raw::Accessor::presign`]
     Presign,
+    /// Operation for [`crate::raw::Accessor::get_tags`]
+    GetTags,
+    /// Operation for [`crate::raw::Accessor::put_tags`]
+    PutTags,
+    /// Operation for [`crate::raw::Accessor::get_object_lock`]
+    GetObjectLock,
+    /// Operation for [`crate::raw::Accessor::put_object_lock`]
+    PutObjectLock,
     /// Operation for [`crate::raw::Accessor::blocking_create_dir`]
     BlockingCreateDir,
     /// Operation for [`crate::raw::Accessor::blocking_read`]
@@ -63,6 +71,10 @@ pub enum Operation {
     BlockingDelete,
     /// Operation for [`crate::raw::Accessor::blocking_list`]
     BlockingList,
+    /// Operation for [`crate::raw::Accessor::blocking_get_tags`]
+    BlockingGetTags,
+    /// Operation for [`crate::raw::Accessor::blocking_put_tags`]
+    BlockingPutTags,
 }
 
 impl Operation {
@@ -93,6 +105,10 @@ impl From<Operation> for &'static str {
             Operation::List => "list",
             Operation::Presign => "presign",
             Operation::Batch => "batch",
+            Operation::GetTags => "get_tags",
+            Operation::PutTags => "put_tags",
+            Operation::GetObjectLock => "get_object_lock",
+            Operation::PutObjectLock => "put_object_lock",
             Operation::BlockingCreateDir => "blocking_create_dir",
             Operation::BlockingRead => "blocking_read",
             Operation::BlockingWrite => "blocking_write",
@@ -101,6 +117,8 @@ impl From<Operation> for &'static str {
             Operation::BlockingStat => "blocking_stat",
             Operation::BlockingDelete => "blocking_delete",
             Operation::BlockingList => "blocking_list",
+            Operation::BlockingGetTags => "blocking_get_tags",
+            Operation::BlockingPutTags => "blocking_put_tags",
         }
     }
 }
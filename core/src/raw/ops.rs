@@ -19,9 +19,22 @@
 //!
 //! By using ops, users can add more context for operation.
 
+use std::collections::HashMap;
 use std::time::Duration;
 
+use chrono::DateTime;
+use chrono::Utc;
+use flagset::FlagSet;
+
 use crate::raw::*;
+use crate::ChecksumAlgo;
+use crate::MetadataDirective;
+use crate::Metakey;
+use crate::ObjectLock;
+use crate::ObjectLockMode;
+use crate::ProgressCallback;
+use crate::WriteCheckpoint;
+use crate::WritePartCallback;
 
 /// Args for `create` operation.
 ///
@@ -42,6 +55,9 @@ impl OpCreateDir {
 #[derive(Debug, Clone, Default)]
 pub struct OpDelete {
     version: Option<String>,
+    if_match: Option<String>,
+    recursive: bool,
+    allow_root: bool,
 }
 
 impl OpDelete {
@@ -62,6 +78,46 @@ impl OpDelete {
     pub fn version(&self) -> Option<&str> {
         self.version.as_deref()
     }
+
+    /// Set the If-Match of this delete operation.
+    ///
+    /// The path will only be deleted if its etag matches.
+    pub fn with_if_match(mut self, if_match: &str) -> Self {
+        self.if_match = Some(if_match.into());
+        self
+    }
+
+    /// Get the If-Match of this delete operation.
+    pub fn if_match(&self) -> Option<&str> {
+        self.if_match.as_deref()
+    }
+
+    /// Change the recursive flag of this delete operation.
+    ///
+    /// Recursive delete is handled by [`Operator`], services will never
+    /// see this flag set.
+    pub fn with_recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Get the recursive flag of this delete operation.
+    pub fn recursive(&self) -> bool {
+        self.recursive
+    }
+
+    /// Change the allow_root flag of this delete operation.
+    ///
+    /// Recursive delete on root will be refused unless this flag is set.
+    pub fn with_allow_root(mut self, allow_root: bool) -> Self {
+        self.allow_root = allow_root;
+        self
+    }
+
+    /// Get the allow_root flag of this delete operation.
+    pub fn allow_root(&self) -> bool {
+        self.allow_root
+    }
 }
 
 /// Args for `list` operation.
@@ -71,20 +127,55 @@ pub struct OpList {
     /// that could return.
     limit: Option<usize>,
 
+    /// The max count of entries that this list operation will return in
+    /// total.
+    max_entries: Option<usize>,
+
     /// The start_after passes to underlying service to specify the specified key
     /// to start listing from.
     start_after: Option<String>,
 
+    /// The continuation token returned by a previous list to resume
+    /// listing from.
+    continuation_token: Option<String>,
+
     /// The delimiter used to for the list operation. Default to be `/`
     delimiter: String,
+
+    /// Whether the entries should be returned in lexicographic order.
+    sorted: bool,
+
+    /// The glob pattern that entries should match.
+    glob: Option<String>,
+
+    /// Whether only common prefixes (dirs) should be returned.
+    prefixes_only: bool,
+
+    /// Whether services should return metadata like content length and
+    /// last modified in list if they can.
+    metadata: bool,
+
+    /// Whether all versions of files should be listed.
+    versions: bool,
+
+    /// Whether delete markers should be listed along with versions.
+    deleted: bool,
 }
 
 impl Default for OpList {
     fn default() -> Self {
         OpList {
             limit: None,
+            max_entries: None,
             start_after: None,
+            continuation_token: None,
             delimiter: "/".to_string(),
+            sorted: false,
+            glob: None,
+            prefixes_only: false,
+            metadata: false,
+            versions: false,
+            deleted: false,
         }
     }
 }
@@ -106,6 +197,17 @@ impl OpList {
         self.limit
     }
 
+    /// Change the max entries of this list operation.
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries);
+        self
+    }
+
+    /// Get the max entries of list operation.
+    pub fn max_entries(&self) -> Option<usize> {
+        self.max_entries
+    }
+
     /// Change the start_after of this list operation.
     pub fn with_start_after(mut self, start_after: &str) -> Self {
         self.start_after = Some(start_after.into());
@@ -117,6 +219,17 @@ impl OpList {
         self.start_after.as_deref()
     }
 
+    /// Change the continuation token of this list operation.
+    pub fn with_continuation_token(mut self, token: &str) -> Self {
+        self.continuation_token = Some(token.into());
+        self
+    }
+
+    /// Get the continuation token of list operation.
+    pub fn continuation_token(&self) -> Option<&str> {
+        self.continuation_token.as_deref()
+    }
+
     /// Change the delimiter. The default delimiter is "/"
     pub fn with_delimiter(mut self, delimiter: &str) -> Self {
         self.delimiter = delimiter.to_string();
@@ -127,6 +240,72 @@ impl OpList {
     pub fn delimiter(&self) -> &str {
         &self.delimiter
     }
+
+    /// Change whether the entries should be returned in lexicographic order.
+    pub fn with_sorted(mut self, sorted: bool) -> Self {
+        self.sorted = sorted;
+        self
+    }
+
+    /// Check if the entries should be returned in lexicographic order.
+    pub fn sorted(&self) -> bool {
+        self.sorted
+    }
+
+    /// Change the glob pattern that entries should match.
+    pub fn with_glob(mut self, pattern: &str) -> Self {
+        self.glob = Some(pattern.to_string());
+        self
+    }
+
+    /// Get the glob pattern of list operation.
+    pub fn glob(&self) -> Option<&str> {
+        self.glob.as_deref()
+    }
+
+    /// Change whether only common prefixes (dirs) should be returned.
+    pub fn with_prefixes_only(mut self, prefixes_only: bool) -> Self {
+        self.prefixes_only = prefixes_only;
+        self
+    }
+
+    /// Check if only common prefixes (dirs) should be returned.
+    pub fn prefixes_only(&self) -> bool {
+        self.prefixes_only
+    }
+
+    /// Change whether services should return metadata in list.
+    pub fn with_metadata(mut self, metadata: bool) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Check if services should return metadata in list.
+    pub fn metadata(&self) -> bool {
+        self.metadata
+    }
+
+    /// Change whether all versions of files should be listed.
+    pub fn with_versions(mut self, versions: bool) -> Self {
+        self.versions = versions;
+        self
+    }
+
+    /// Check if all versions of files should be listed.
+    pub fn versions(&self) -> bool {
+        self.versions
+    }
+
+    /// Change whether delete markers should be listed along with versions.
+    pub fn with_deleted(mut self, deleted: bool) -> Self {
+        self.deleted = deleted;
+        self
+    }
+
+    /// Check if delete markers should be listed along with versions.
+    pub fn deleted(&self) -> bool {
+        self.deleted
+    }
 }
 
 /// Args for `presign` operation.
@@ -162,6 +341,7 @@ impl OpPresign {
 /// Presign operation used for presign.
 #[derive(Debug, Clone)]
 #[non_exhaustive]
+#[allow(clippy::large_enum_variant)]
 pub enum PresignOperation {
     /// Presign a stat(head) operation.
     Stat(OpStat),
@@ -245,6 +425,13 @@ pub struct OpRead {
     override_cache_control: Option<String>,
     override_content_disposition: Option<String>,
     version: Option<String>,
+    verify_checksum: bool,
+    progress: Option<ProgressCallback>,
+    buffer: Option<usize>,
+    chunk: Option<usize>,
+    headers: Vec<(String, String)>,
+    ignore_unsupported_headers: bool,
+    skip_content_length_check: bool,
 }
 
 impl OpRead {
@@ -319,6 +506,88 @@ impl OpRead {
     pub fn version(&self) -> Option<&str> {
         self.version.as_deref()
     }
+
+    /// Set whether the read content should be verified against the
+    /// returned content md5.
+    pub fn with_verify_checksum(mut self, verify_checksum: bool) -> Self {
+        self.verify_checksum = verify_checksum;
+        self
+    }
+
+    /// Get whether the read content should be verified.
+    pub fn verify_checksum(&self) -> bool {
+        self.verify_checksum
+    }
+
+    /// Add a custom header that will be sent along with the request.
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Get custom headers from option.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Set whether custom headers should be ignored instead of returning
+    /// an error if service doesn't support them.
+    pub fn with_ignore_unsupported_headers(mut self, v: bool) -> Self {
+        self.ignore_unsupported_headers = v;
+        self
+    }
+
+    /// Get whether custom headers should be ignored if not supported.
+    pub fn ignore_unsupported_headers(&self) -> bool {
+        self.ignore_unsupported_headers
+    }
+
+    /// Set whether the check of returned content against the content
+    /// length should be skipped while reading the whole file.
+    pub fn with_skip_content_length_check(mut self, v: bool) -> Self {
+        self.skip_content_length_check = v;
+        self
+    }
+
+    /// Get whether the content length check should be skipped.
+    pub fn skip_content_length_check(&self) -> bool {
+        self.skip_content_length_check
+    }
+
+    /// Set the progress callback of option.
+    pub fn with_progress(mut self, progress: ProgressCallback) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Get the progress callback from option.
+    pub fn progress(&self) -> Option<&ProgressCallback> {
+        self.progress.as_ref()
+    }
+
+    /// Set the read ahead buffer size of option.
+    pub fn with_buffer(mut self, size: usize) -> Self {
+        self.buffer = Some(size);
+        self
+    }
+
+    /// Get the read ahead buffer size from option.
+    pub fn buffer(&self) -> Option<usize> {
+        self.buffer
+    }
+
+    /// Set the chunk size of option.
+    ///
+    /// Data will be fetched by one range request for every chunk.
+    pub fn with_chunk(mut self, size: usize) -> Self {
+        self.chunk = Some(size);
+        self
+    }
+
+    /// Get the chunk size from option.
+    pub fn chunk(&self) -> Option<usize> {
+        self.chunk
+    }
 }
 
 /// Args for `stat` operation.
@@ -327,6 +596,8 @@ pub struct OpStat {
     if_match: Option<String>,
     if_none_match: Option<String>,
     version: Option<String>,
+    treat_prefix_as_dir: bool,
+    metakey: Option<FlagSet<Metakey>>,
 }
 
 impl OpStat {
@@ -367,6 +638,45 @@ impl OpStat {
     pub fn version(&self) -> Option<&str> {
         self.version.as_deref()
     }
+
+    /// Set whether a non-empty prefix should be treated as a dir.
+    ///
+    /// If true, a path that doesn't exist but has children will be
+    /// returned as a dir instead of `NotFound`.
+    pub fn with_treat_prefix_as_dir(mut self, v: bool) -> Self {
+        self.treat_prefix_as_dir = v;
+        self
+    }
+
+    /// Get whether a non-empty prefix should be treated as a dir.
+    pub fn treat_prefix_as_dir(&self) -> bool {
+        self.treat_prefix_as_dir
+    }
+
+    /// Set the metadata fields that users need.
+    ///
+    /// Services could skip fetching or assembling other fields.
+    pub fn with_metakey(mut self, metakey: impl Into<FlagSet<Metakey>>) -> Self {
+        self.metakey = Some(metakey.into());
+        self
+    }
+
+    /// Get the metadata fields that users need, [`Metakey::Complete`] by
+    /// default.
+    pub fn metakey(&self) -> FlagSet<Metakey> {
+        self.metakey.unwrap_or_else(|| Metakey::Complete.into())
+    }
+
+    /// Check if users have declared the metadata fields they need.
+    pub fn has_metakey(&self) -> bool {
+        self.metakey.is_some()
+    }
+
+    /// Check if users need the given metadata field.
+    pub fn need(&self, key: Metakey) -> bool {
+        let metakey = self.metakey();
+        metakey.contains(key) || metakey.contains(Metakey::Complete)
+    }
 }
 
 /// Args for `write` operation.
@@ -376,6 +686,27 @@ pub struct OpWrite {
     content_type: Option<String>,
     content_disposition: Option<String>,
     cache_control: Option<String>,
+    content_md5: Option<String>,
+    ttl: Option<Duration>,
+    append: bool,
+    part_callback: Option<WritePartCallback>,
+    progress: Option<ProgressCallback>,
+    tags: Option<HashMap<String, String>>,
+    user_metadata: Option<HashMap<String, String>>,
+    resume: Option<WriteCheckpoint>,
+    range: Option<BytesRange>,
+    if_match: Option<String>,
+    if_none_match: Option<String>,
+    idempotency_key: Option<String>,
+    checksum: Option<ChecksumAlgo>,
+    content_encoding: Option<String>,
+    content_language: Option<String>,
+    headers: Vec<(String, String)>,
+    ignore_unsupported_headers: bool,
+    storage_class: Option<String>,
+    object_lock: ObjectLock,
+    chunk: Option<usize>,
+    concurrent: usize,
 }
 
 impl OpWrite {
@@ -434,6 +765,285 @@ impl OpWrite {
         self.cache_control = Some(cache_control.to_string());
         self
     }
+
+    /// Get the content encoding from option
+    pub fn content_encoding(&self) -> Option<&str> {
+        self.content_encoding.as_deref()
+    }
+
+    /// Set the content encoding of option
+    pub fn with_content_encoding(mut self, content_encoding: &str) -> Self {
+        self.content_encoding = Some(content_encoding.to_string());
+        self
+    }
+
+    /// Get the content language from option
+    pub fn content_language(&self) -> Option<&str> {
+        self.content_language.as_deref()
+    }
+
+    /// Set the content language of option
+    pub fn with_content_language(mut self, content_language: &str) -> Self {
+        self.content_language = Some(content_language.to_string());
+        self
+    }
+
+    /// Get the storage class from option
+    pub fn storage_class(&self) -> Option<&str> {
+        self.storage_class.as_deref()
+    }
+
+    /// Set the storage class of option
+    pub fn with_storage_class(mut self, storage_class: &str) -> Self {
+        self.storage_class = Some(storage_class.to_string());
+        self
+    }
+
+    /// Get the object lock from option
+    pub fn object_lock(&self) -> &ObjectLock {
+        &self.object_lock
+    }
+
+    /// Set the retention of object lock of option
+    pub fn with_object_lock(mut self, mode: ObjectLockMode, retain_until: DateTime<Utc>) -> Self {
+        self.object_lock = self.object_lock.with_retention(mode, retain_until);
+        self
+    }
+
+    /// Set the legal hold of object lock of option
+    pub fn with_legal_hold(mut self, on: bool) -> Self {
+        self.object_lock = self.object_lock.with_legal_hold(on);
+        self
+    }
+
+    /// Add a custom header that will be sent along with the request.
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Get custom headers from option.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Set whether custom headers should be ignored instead of returning
+    /// an error if service doesn't support them.
+    pub fn with_ignore_unsupported_headers(mut self, v: bool) -> Self {
+        self.ignore_unsupported_headers = v;
+        self
+    }
+
+    /// Get whether custom headers should be ignored if not supported.
+    pub fn ignore_unsupported_headers(&self) -> bool {
+        self.ignore_unsupported_headers
+    }
+
+    /// Get the content md5 from option
+    pub fn content_md5(&self) -> Option<&str> {
+        self.content_md5.as_deref()
+    }
+
+    /// Set the content md5 of option
+    ///
+    /// The value should be the base64 encoded md5 digest of the content.
+    pub fn with_content_md5(mut self, content_md5: &str) -> Self {
+        self.content_md5 = Some(content_md5.to_string());
+        self
+    }
+
+    /// Get the ttl from option
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl
+    }
+
+    /// Set the ttl of option
+    ///
+    /// The written content will expire after the given duration.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Get the append from option
+    pub fn append(&self) -> bool {
+        self.append
+    }
+
+    /// Set the append of option
+    ///
+    /// If append is true, the content will be appended to the end of
+    /// existing file instead of overwriting it.
+    pub fn with_append(mut self, append: bool) -> Self {
+        self.append = append;
+        self
+    }
+
+    /// Get the part callback from option
+    pub fn part_callback(&self) -> Option<&WritePartCallback> {
+        self.part_callback.as_ref()
+    }
+
+    /// Set the part callback of option
+    ///
+    /// Services that upload data in parts will call it every time a part
+    /// has been flushed.
+    pub fn with_part_callback(mut self, callback: WritePartCallback) -> Self {
+        self.part_callback = Some(callback);
+        self
+    }
+
+    /// Get the progress callback from option
+    pub fn progress(&self) -> Option<&ProgressCallback> {
+        self.progress.as_ref()
+    }
+
+    /// Set the progress callback of option
+    pub fn with_progress(mut self, progress: ProgressCallback) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Get the tags from option
+    pub fn tags(&self) -> Option<&HashMap<String, String>> {
+        self.tags.as_ref()
+    }
+
+    /// Set the tags of option
+    ///
+    /// Tags are different from user metadata, they can be updated
+    /// without rewriting the file.
+    pub fn with_tags(mut self, tags: HashMap<String, String>) -> Self {
+        self.tags = Some(tags);
+        self
+    }
+
+    /// Get the user metadata from option
+    pub fn user_metadata(&self) -> Option<&HashMap<String, String>> {
+        self.user_metadata.as_ref()
+    }
+
+    /// Set the user metadata of option
+    ///
+    /// User metadata is stored along with the file and returned by `stat`,
+    /// it can only be changed by rewriting the file.
+    pub fn with_user_metadata(mut self, user_metadata: HashMap<String, String>) -> Self {
+        self.user_metadata = Some(user_metadata);
+        self
+    }
+
+    /// Get the checkpoint to resume from option
+    pub fn resume(&self) -> Option<&WriteCheckpoint> {
+        self.resume.as_ref()
+    }
+
+    /// Set the checkpoint to resume from
+    ///
+    /// The upload will continue from given checkpoint instead of starting
+    /// a new one.
+    pub fn with_resume(mut self, checkpoint: WriteCheckpoint) -> Self {
+        self.resume = Some(checkpoint);
+        self
+    }
+
+    /// Get the range to overwrite from option
+    pub fn range(&self) -> Option<BytesRange> {
+        self.range
+    }
+
+    /// Set the range to overwrite
+    ///
+    /// If range is set, the content will be written into the given range
+    /// of existing file in place instead of replacing the whole file.
+    pub fn with_range(mut self, range: BytesRange) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    /// Get the If-Match from option
+    pub fn if_match(&self) -> Option<&str> {
+        self.if_match.as_deref()
+    }
+
+    /// Set the If-Match of option
+    ///
+    /// The write will only succeed if the etag of existing file matches.
+    pub fn with_if_match(mut self, if_match: &str) -> Self {
+        self.if_match = Some(if_match.to_string());
+        self
+    }
+
+    /// Get the If-None-Match from option
+    pub fn if_none_match(&self) -> Option<&str> {
+        self.if_none_match.as_deref()
+    }
+
+    /// Set the If-None-Match of option
+    ///
+    /// `*` means the write will only succeed if the file doesn't exist.
+    pub fn with_if_none_match(mut self, if_none_match: &str) -> Self {
+        self.if_none_match = Some(if_none_match.to_string());
+        self
+    }
+
+    /// Check if this write will only create a new file.
+    pub fn is_create_only(&self) -> bool {
+        self.if_none_match.as_deref() == Some("*")
+    }
+
+    /// Get the idempotency key from option
+    pub fn idempotency_key(&self) -> Option<&str> {
+        self.idempotency_key.as_deref()
+    }
+
+    /// Set the idempotency key of option
+    ///
+    /// Services will apply writes with the same key only once, so that
+    /// the write can be retried safely.
+    pub fn with_idempotency_key(mut self, key: &str) -> Self {
+        self.idempotency_key = Some(key.to_string());
+        self
+    }
+
+    /// Get the checksum algorithm from option
+    pub fn checksum(&self) -> Option<ChecksumAlgo> {
+        self.checksum
+    }
+
+    /// Set the checksum algorithm of option
+    ///
+    /// The checksum will be computed over the written bytes and returned
+    /// in the metadata after the write has been closed. Blocking writes and
+    /// resumed writes will return `Unsupported` error.
+    pub fn with_checksum(mut self, algo: ChecksumAlgo) -> Self {
+        self.checksum = Some(algo);
+        self
+    }
+
+    /// Get the chunk size from option.
+    pub fn chunk(&self) -> Option<usize> {
+        self.chunk
+    }
+
+    /// Set the chunk size of option.
+    ///
+    /// Services that upload data in parts will use it as the part size
+    /// instead of their `write_min_size`.
+    pub fn with_chunk(mut self, size: usize) -> Self {
+        self.chunk = Some(size);
+        self
+    }
+
+    /// Get the max number of parts to upload concurrently, at least `1`.
+    pub fn concurrent(&self) -> usize {
+        self.concurrent.max(1)
+    }
+
+    /// Set the max number of parts to upload concurrently.
+    pub fn with_concurrent(mut self, concurrent: usize) -> Self {
+        self.concurrent = concurrent;
+        self
+    }
 }
 
 /// Args for `append` operation.
@@ -486,13 +1096,154 @@ impl OpAppend {
 
 /// Args for `copy` operation.
 #[derive(Debug, Clone, Default)]
-pub struct OpCopy {}
+pub struct OpCopy {
+    metadata_directive: MetadataDirective,
+    content_type: Option<String>,
+    content_disposition: Option<String>,
+    cache_control: Option<String>,
+    user_metadata: Option<HashMap<String, String>>,
+    if_source_match: Option<String>,
+}
 
 impl OpCopy {
     /// Create a new `OpCopy`.
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Get the metadata directive from option.
+    pub fn metadata_directive(&self) -> MetadataDirective {
+        self.metadata_directive
+    }
+
+    /// Set the metadata directive of the option.
+    pub fn with_metadata_directive(mut self, directive: MetadataDirective) -> Self {
+        self.metadata_directive = directive;
+        self
+    }
+
+    /// Get the content type from option.
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Set the content type of option.
+    ///
+    /// Requires metadata directive to be `Replace`.
+    pub fn with_content_type(mut self, content_type: &str) -> Self {
+        self.content_type = Some(content_type.to_string());
+        self
+    }
+
+    /// Get the content disposition from option.
+    pub fn content_disposition(&self) -> Option<&str> {
+        self.content_disposition.as_deref()
+    }
+
+    /// Set the content disposition of option.
+    ///
+    /// Requires metadata directive to be `Replace`.
+    pub fn with_content_disposition(mut self, content_disposition: &str) -> Self {
+        self.content_disposition = Some(content_disposition.to_string());
+        self
+    }
+
+    /// Get the cache control from option.
+    pub fn cache_control(&self) -> Option<&str> {
+        self.cache_control.as_deref()
+    }
+
+    /// Set the cache control of option.
+    ///
+    /// Requires metadata directive to be `Replace`.
+    pub fn with_cache_control(mut self, cache_control: &str) -> Self {
+        self.cache_control = Some(cache_control.to_string());
+        self
+    }
+
+    /// Get the user metadata from option.
+    pub fn user_metadata(&self) -> Option<&HashMap<String, String>> {
+        self.user_metadata.as_ref()
+    }
+
+    /// Set the user metadata of option.
+    ///
+    /// Requires metadata directive to be `Replace`.
+    pub fn with_user_metadata(mut self, user_metadata: HashMap<String, String>) -> Self {
+        self.user_metadata = Some(user_metadata);
+        self
+    }
+
+    /// Get the if source match from option.
+    pub fn if_source_match(&self) -> Option<&str> {
+        self.if_source_match.as_deref()
+    }
+
+    /// Set the if source match of option.
+    ///
+    /// The copy will only be performed if the etag of source file matches.
+    pub fn with_if_source_match(mut self, if_source_match: &str) -> Self {
+        self.if_source_match = Some(if_source_match.to_string());
+        self
+    }
+}
+
+/// Args for `get_tags` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpGetTags {}
+
+impl OpGetTags {
+    /// Create a new `OpGetTags`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Args for `put_tags` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpPutTags {
+    tags: HashMap<String, String>,
+}
+
+impl OpPutTags {
+    /// Create a new `OpPutTags` with given tags.
+    pub fn new(tags: HashMap<String, String>) -> Self {
+        Self { tags }
+    }
+
+    /// Get the tags from option.
+    pub fn tags(&self) -> &HashMap<String, String> {
+        &self.tags
+    }
+}
+
+/// Args for `get_object_lock` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpGetObjectLock {}
+
+impl OpGetObjectLock {
+    /// Create a new `OpGetObjectLock`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Args for `put_object_lock` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpPutObjectLock {
+    object_lock: ObjectLock,
+}
+
+impl OpPutObjectLock {
+    /// Create a new `OpPutObjectLock` with given object lock.
+    pub fn new(object_lock: ObjectLock) -> Self {
+        Self { object_lock }
+    }
+
+    /// Get the object lock from option.
+    pub fn object_lock(&self) -> &ObjectLock {
+        &self.object_lock
+    }
 }
 
 /// Args for `rename` operation.
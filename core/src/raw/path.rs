@@ -98,7 +98,7 @@ pub fn normalize_path(path: &str) -> String {
     // - all leading `/` has been trimmed.
     let path = path.trim().trim_start_matches('/');
 
-    // Fast line for empty path.
+    // Fast path for empty path.
     if path.is_empty() {
         return "/".to_string();
     }
@@ -119,6 +119,51 @@ pub fn normalize_path(path: &str) -> String {
     p
 }
 
+/// Resolve `.` and `..` segments in a normalized path.
+///
+/// Input path must be normalized by [`normalize_path`] first.
+///
+/// # Resolve Rules
+///
+/// - `.` will be removed: `abc/./def` => `abc/def`
+/// - `..` will remove the previous segment: `abc/../def` => `def`
+/// - `..` can't go above root: `../abc` => `abc`
+/// - Path ends with `.` or `..` is a dir path: `abc/def/..` => `abc/`
+/// - Empty path will be `/`: `abc/..` => `/`
+pub fn resolve_dot_segments(path: &str) -> String {
+    // Fast path for path that doesn't have dot segments at all.
+    if !path.split('/').any(|v| v == "." || v == "..") {
+        return path.to_string();
+    }
+
+    let mut segs: Vec<&str> = Vec::new();
+    let mut is_dir = false;
+    for seg in path.split('/').filter(|v| !v.is_empty()) {
+        match seg {
+            "." => is_dir = true,
+            ".." => {
+                segs.pop();
+                is_dir = true;
+            }
+            v => {
+                segs.push(v);
+                is_dir = false;
+            }
+        }
+    }
+    is_dir = is_dir || path.ends_with('/');
+
+    if segs.is_empty() {
+        return "/".to_string();
+    }
+
+    let mut p = segs.join("/");
+    if is_dir {
+        p.push('/');
+    }
+    p
+}
+
 /// Make sure root is normalized to style like `/abc/def/`.
 ///
 /// # Normalize Rules
@@ -221,6 +266,97 @@ pub fn validate_path(path: &str, mode: EntryMode) -> bool {
     }
 }
 
+/// Get the literal dir prefix of given glob pattern.
+///
+/// The prefix contains all segments before the first segment that has
+/// wildcards (`*`, `?`). The last segment will never be included since
+/// it's the name of entries to match.
+///
+/// - `logs/2023-*/**/*.json` => `logs/`
+/// - `logs/2023/01/*.json` => `logs/2023/01/`
+/// - `**/*.json` => ``
+pub fn get_glob_prefix(pattern: &str) -> &str {
+    let mut idx = 0;
+    for (i, c) in pattern.char_indices() {
+        match c {
+            '/' => idx = i + 1,
+            '*' | '?' => break,
+            _ => continue,
+        }
+    }
+    &pattern[..idx]
+}
+
+/// Check if given path matches the glob pattern.
+///
+/// # Rules
+///
+/// - `*` matches any sequence of characters within one segment.
+/// - `?` matches any single character within one segment.
+/// - `**` as a whole segment matches zero or more segments.
+/// - Trailing `/` of dir path will be ignored: `abc/` matches `abc`.
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.trim_end_matches('/').split('/').collect::<Vec<_>>();
+    let path = path.trim_end_matches('/').split('/').collect::<Vec<_>>();
+
+    glob_match_segments(&pattern, &path)
+}
+
+fn glob_match_segments(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.first() {
+        None => path.is_empty(),
+        Some(&"**") => (0..=path.len()).any(|i| glob_match_segments(&pattern[1..], &path[i..])),
+        Some(p) => {
+            !path.is_empty()
+                && glob_match_segment(p.as_bytes(), path[0].as_bytes())
+                && glob_match_segments(&pattern[1..], &path[1..])
+        }
+    }
+}
+
+fn glob_match_segment(pattern: &[u8], name: &[u8]) -> bool {
+    let (mut p, mut n) = (0, 0);
+    // The position to retry while meeting a mismatch after `*`.
+    let mut star: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(b'?') => {
+                // `?` should match a whole utf-8 char instead of a byte.
+                n += utf8_char_width(name[n]);
+                p += 1;
+            }
+            Some(c) if *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((sp, sn)) => {
+                    p = sp + 1;
+                    n = sn + 1;
+                    star = Some((sp, sn + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == b'*')
+}
+
+fn utf8_char_width(b: u8) -> usize {
+    match b {
+        0x00..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        _ => 4,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -247,6 +383,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_resolve_dot_segments() {
+        let cases = vec![
+            ("file path", "abc/def", "abc/def"),
+            ("dir path", "abc/def/", "abc/def/"),
+            ("root path", "/", "/"),
+            ("current dir", "abc/./def", "abc/def"),
+            ("parent dir", "abc/../def", "def"),
+            ("parent dir above root", "../../abc", "abc"),
+            ("ends with current dir", "abc/.", "abc/"),
+            ("ends with parent dir", "abc/def/..", "abc/"),
+            ("resolve to root", "abc/..", "/"),
+            ("dir path with parent dir", "abc/../def/", "def/"),
+            ("dot in file name", "abc/.def", "abc/.def"),
+        ];
+
+        for (name, input, expect) in cases {
+            assert_eq!(resolve_dot_segments(input), expect, "{name}")
+        }
+    }
+
     #[test]
     fn test_normalize_root() {
         let cases = vec![
@@ -377,4 +534,74 @@ mod tests {
             assert_eq!(actual, expect, "{name}")
         }
     }
+
+    #[test]
+    fn test_get_glob_prefix() {
+        let cases = vec![
+            ("no wildcard", "abc/def", "abc/"),
+            ("wildcard in name", "abc/*.json", "abc/"),
+            ("wildcard in dir", "logs/2023-*/**/*.json", "logs/"),
+            (
+                "nested literal dirs",
+                "logs/2023/01/*.json",
+                "logs/2023/01/",
+            ),
+            ("leading wildcard", "**/*.json", ""),
+            ("single name", "abc", ""),
+        ];
+
+        for (name, input, expect) in cases {
+            assert_eq!(get_glob_prefix(input), expect, "{name}")
+        }
+    }
+
+    #[test]
+    fn test_glob_match() {
+        let cases = vec![
+            ("exact", "abc/def", "abc/def", true),
+            ("exact mismatch", "abc/def", "abc/deg", false),
+            ("star in name", "abc/*.json", "abc/def.json", true),
+            (
+                "star not cross segment",
+                "abc/*.json",
+                "abc/d/e.json",
+                false,
+            ),
+            ("question mark", "abc/d?f", "abc/def", true),
+            ("question mark utf-8", "abc/d?f", "abc/d你f", true),
+            ("question mark length", "abc/d?f", "abc/deef", false),
+            (
+                "double star zero segment",
+                "logs/**/*.json",
+                "logs/a.json",
+                true,
+            ),
+            (
+                "double star many segments",
+                "logs/**/*.json",
+                "logs/a/b/c.json",
+                true,
+            ),
+            ("double star suffix", "logs/**", "logs/a/b", true),
+            (
+                "complex",
+                "logs/2023-*/**/*.json",
+                "logs/2023-01/a/b.json",
+                true,
+            ),
+            (
+                "complex mismatch",
+                "logs/2023-*/**/*.json",
+                "logs/2022-01/a/b.json",
+                false,
+            ),
+            ("dir path", "abc/*", "abc/def/", true),
+            ("multiple stars", "*a*b*", "xxaxxbxx", true),
+            ("multiple stars mismatch", "*a*b*", "xxbxxaxx", false),
+        ];
+
+        for (name, pattern, path, expect) in cases {
+            assert_eq!(glob_match(pattern, path), expect, "{name}")
+        }
+    }
 }
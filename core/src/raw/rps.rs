@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
+
 use http::Request;
 
 use crate::*;
@@ -217,6 +219,64 @@ impl RpCopy {
     }
 }
 
+/// Reply for `get_tags` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpGetTags {
+    tags: HashMap<String, String>,
+}
+
+impl RpGetTags {
+    /// Create a new reply for `get_tags`.
+    pub fn new(tags: HashMap<String, String>) -> Self {
+        Self { tags }
+    }
+
+    /// Consume reply to get the tags.
+    pub fn into_tags(self) -> HashMap<String, String> {
+        self.tags
+    }
+}
+
+/// Reply for `put_tags` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpPutTags {}
+
+impl RpPutTags {
+    /// Create a new reply for `put_tags`.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+/// Reply for `get_object_lock` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpGetObjectLock {
+    object_lock: ObjectLock,
+}
+
+impl RpGetObjectLock {
+    /// Create a new reply for `get_object_lock`.
+    pub fn new(object_lock: ObjectLock) -> Self {
+        Self { object_lock }
+    }
+
+    /// Consume reply to get the object lock.
+    pub fn into_object_lock(self) -> ObjectLock {
+        self.object_lock
+    }
+}
+
+/// Reply for `put_object_lock` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpPutObjectLock {}
+
+impl RpPutObjectLock {
+    /// Create a new reply for `put_object_lock`.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
 /// Reply for `rename` operation.
 #[derive(Debug, Clone, Default)]
 pub struct RpRename {}
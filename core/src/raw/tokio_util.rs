@@ -0,0 +1,83 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::future::Future;
+use std::thread;
+
+use tokio::runtime::Handle;
+use tokio::runtime::Runtime;
+
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+
+/// Drive the future to completion on given runtime in blocking way.
+///
+/// `Runtime::block_on` will panic if it's called inside an async runtime.
+/// To avoid this, we will drive the future in a new thread while we are
+/// inside a runtime already. The current thread will still be blocked,
+/// users should call blocking APIs via `tokio::task::spawn_blocking` in
+/// async context.
+pub fn block_on<F, T>(runtime: &Runtime, fut: F) -> Result<T>
+where
+    F: Future<Output = Result<T>> + Send,
+    T: Send,
+{
+    if Handle::try_current().is_err() {
+        return runtime.block_on(fut);
+    }
+
+    thread::scope(|s| {
+        s.spawn(move || runtime.block_on(fut))
+            .join()
+            .unwrap_or_else(|_| {
+                Err(
+                    Error::new(ErrorKind::Unexpected, "blocking operation panicked")
+                        .with_operation("raw::block_on"),
+                )
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_runtime() -> Runtime {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime must build")
+    }
+
+    #[test]
+    fn test_block_on() {
+        let rt = new_runtime();
+        let v = block_on(&rt, async { Ok(42) }).unwrap();
+        assert_eq!(v, 42);
+    }
+
+    #[tokio::test]
+    async fn test_block_on_inside_runtime() {
+        let rt = new_runtime();
+        let v = block_on(&rt, async { Ok(42) }).unwrap();
+        assert_eq!(v, 42);
+
+        // Drop the runtime outside of async context.
+        tokio::task::spawn_blocking(move || drop(rt)).await.unwrap();
+    }
+}
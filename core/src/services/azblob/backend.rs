@@ -39,6 +39,7 @@ use super::pager::AzblobPager;
 use super::writer::AzblobWriter;
 use crate::raw::*;
 use crate::services::azblob::core::AzblobCore;
+use crate::services::azblob::core::UserDelegationKeyCache;
 use crate::types::Metadata;
 use crate::*;
 
@@ -66,6 +67,7 @@ pub struct AzblobBuilder {
     encryption_key_sha256: Option<String>,
     encryption_algorithm: Option<String>,
     sas_token: Option<String>,
+    client_id: Option<String>,
     http_client: Option<HttpClient>,
     batch_max_operations: Option<usize>,
 }
@@ -87,6 +89,9 @@ impl Debug for AzblobBuilder {
         if self.sas_token.is_some() {
             ds.field("sas_token", &"<redacted>");
         }
+        if self.client_id.is_some() {
+            ds.field("client_id", &self.client_id);
+        }
 
         ds.finish()
     }
@@ -236,9 +241,13 @@ impl AzblobBuilder {
     /// - If sas_token is set, we will take user's input first.
     /// - If not, we will try to load it from environment.
     ///
+    /// The leading `?` copied from Azure Portal will be trimmed. `sas_token`
+    /// can't be used together with `account_key` or `client_id`.
+    ///
     /// See [Grant limited access to Azure Storage resources using shared access signatures (SAS)](https://learn.microsoft.com/en-us/azure/storage/common/storage-sas-overview)
     /// for more info.
     pub fn sas_token(&mut self, sas_token: &str) -> &mut Self {
+        let sas_token = sas_token.trim_start_matches('?');
         if !sas_token.is_empty() {
             self.sas_token = Some(sas_token.to_string());
         }
@@ -246,6 +255,22 @@ impl AzblobBuilder {
         self
     }
 
+    /// Set client_id of the managed identity used to fetch AAD token.
+    ///
+    /// Presign will sign a user delegation SAS with the AAD credential if
+    /// client_id is set. `client_id` can't be used together with
+    /// `account_key` or `sas_token`.
+    ///
+    /// See [Create a user delegation SAS](https://learn.microsoft.com/en-us/rest/api/storageservices/create-user-delegation-sas)
+    /// for more info.
+    pub fn client_id(&mut self, client_id: &str) -> &mut Self {
+        if !client_id.is_empty() {
+            self.client_id = Some(client_id.to_string());
+        }
+
+        self
+    }
+
     /// Specify the http client that used by this service.
     ///
     /// # Notes
@@ -351,6 +376,19 @@ impl AzblobBuilder {
 
 impl Builder for AzblobBuilder {
     const SCHEME: Scheme = Scheme::Azblob;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&[
+        "root",
+        "container",
+        "endpoint",
+        "account_name",
+        "account_key",
+        "encryption_key",
+        "encryption_key_sha256",
+        "encryption_algorithm",
+        "sas_token",
+        "client_id",
+        "batch_max_operations",
+    ]);
     type Accessor = AzblobBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
@@ -367,34 +405,59 @@ impl Builder for AzblobBuilder {
         map.get("encryption_algorithm")
             .map(|v| builder.encryption_algorithm(v));
         map.get("sas_token").map(|v| builder.sas_token(v));
+        map.get("client_id").map(|v| builder.client_id(v));
         map.get("batch_max_operations")
             .map(|v| builder.batch_max_operations(v.parse::<usize>().unwrap()));
 
         builder
     }
 
+    fn validate(&self) -> Result<()> {
+        if self.container.is_empty() {
+            return Err(Error::new(ErrorKind::ConfigInvalid, "container is empty")
+                .with_operation("Builder::validate")
+                .with_context("service", Scheme::Azblob)
+                .with_context("key", "container"));
+        }
+
+        if self.endpoint.is_none() {
+            return Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+                .with_operation("Builder::validate")
+                .with_context("service", Scheme::Azblob)
+                .with_context("key", "endpoint"));
+        }
+
+        // Only one credential type is allowed to avoid ambiguous signing.
+        let credentials = [
+            self.account_key.is_some(),
+            self.sas_token.is_some(),
+            self.client_id.is_some(),
+        ];
+        if credentials.iter().filter(|v| **v).count() > 1 {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "only one of account_key, sas_token and client_id can be set",
+            )
+            .with_operation("Builder::validate")
+            .with_context("service", Scheme::Azblob)
+            .with_context("key", "sas_token"));
+        }
+
+        Ok(())
+    }
+
     fn build(&mut self) -> Result<Self::Accessor> {
         debug!("backend build started: {:?}", &self);
+        self.validate()?;
 
         let root = normalize_root(&self.root.take().unwrap_or_default());
         debug!("backend use root {}", root);
 
-        // Handle endpoint, region and container name.
-        let container = match self.container.is_empty() {
-            false => Ok(&self.container),
-            true => Err(Error::new(ErrorKind::ConfigInvalid, "container is empty")
-                .with_operation("Builder::build")
-                .with_context("service", Scheme::Azblob)),
-        }?;
+        let container = &self.container;
         debug!("backend use container {}", &container);
 
-        let endpoint = match &self.endpoint {
-            Some(endpoint) => Ok(endpoint.clone()),
-            None => Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
-                .with_operation("Builder::build")
-                .with_context("service", Scheme::Azblob)),
-        }?;
-        debug!("backend use endpoint {}", &container);
+        let endpoint = self.endpoint.clone().unwrap_or_default();
+        debug!("backend use endpoint {}", &endpoint);
 
         let client = if let Some(client) = self.http_client.take() {
             client
@@ -405,13 +468,16 @@ impl Builder for AzblobBuilder {
             })?
         };
 
+        let account_name = self
+            .account_name
+            .clone()
+            .or_else(|| infer_storage_name_from_endpoint(endpoint.as_str()));
+
         let config_loader = AzureStorageConfig {
-            account_name: self
-                .account_name
-                .clone()
-                .or_else(|| infer_storage_name_from_endpoint(endpoint.as_str())),
+            account_name: account_name.clone(),
             account_key: self.account_key.clone(),
             sas_token: self.sas_token.clone(),
+            client_id: self.client_id.clone(),
             ..Default::default()
         };
 
@@ -455,6 +521,7 @@ impl Builder for AzblobBuilder {
         debug!("backend build finished: {:?}", &self);
         Ok(AzblobBackend {
             core: Arc::new(AzblobCore {
+                account_name,
                 root,
                 endpoint,
                 encryption_key,
@@ -466,8 +533,13 @@ impl Builder for AzblobBuilder {
                 loader: cred_loader,
                 signer,
                 batch_max_operations,
+                user_delegation_key: UserDelegationKeyCache::default(),
             }),
-            has_sas_token: self.sas_token.is_some(),
+            // Presign requires a signing credential: sas token, account sas
+            // signed by account key, or user delegation sas signed via AAD.
+            can_presign: self.sas_token.is_some()
+                || self.account_key.is_some()
+                || self.client_id.is_some(),
         })
     }
 }
@@ -500,7 +572,7 @@ fn infer_storage_name_from_endpoint(endpoint: &str) -> Option<String> {
 #[derive(Debug, Clone)]
 pub struct AzblobBackend {
     core: Arc<AzblobCore>,
-    has_sas_token: bool,
+    can_presign: bool,
 }
 
 #[async_trait]
@@ -533,6 +605,8 @@ impl Accessor for AzblobBackend {
                 write: true,
                 write_can_sink: true,
                 write_with_cache_control: true,
+                write_with_content_encoding: true,
+                write_with_content_language: true,
                 write_with_content_type: true,
 
                 append: true,
@@ -540,17 +614,20 @@ impl Accessor for AzblobBackend {
                 append_with_content_type: true,
 
                 delete: true,
+                delete_with_version: true,
                 create_dir: true,
                 copy: true,
 
                 list: true,
                 list_with_delimiter_slash: true,
+                list_with_metadata: true,
+                list_with_sorted: true,
                 list_without_delimiter: true,
 
-                presign: self.has_sas_token,
-                presign_stat: self.has_sas_token,
-                presign_read: self.has_sas_token,
-                presign_write: self.has_sas_token,
+                presign: self.can_presign,
+                presign_stat: self.can_presign,
+                presign_read: self.can_presign,
+                presign_write: self.can_presign,
 
                 batch: true,
                 batch_delete: true,
@@ -563,9 +640,12 @@ impl Accessor for AzblobBackend {
     }
 
     async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
-        let mut req =
-            self.core
-                .azblob_put_blob_request(path, Some(0), None, None, AsyncBody::Empty)?;
+        let mut req = self.core.azblob_put_blob_request(
+            path,
+            Some(0),
+            &OpWrite::default(),
+            AsyncBody::Empty,
+        )?;
 
         self.core.sign(&mut req).await?;
 
@@ -663,8 +743,8 @@ impl Accessor for AzblobBackend {
         }
     }
 
-    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
-        let resp = self.core.azblob_delete_blob(path).await?;
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let resp = self.core.azblob_delete_blob(path, args.version()).await?;
 
         let status = resp.status();
 
@@ -698,13 +778,15 @@ impl Accessor for AzblobBackend {
                 v.if_match(),
                 v.override_content_disposition(),
             )?,
-            PresignOperation::Write(_) => {
-                self.core
-                    .azblob_put_blob_request(path, None, None, None, AsyncBody::Empty)?
-            }
+            PresignOperation::Write(_) => self.core.azblob_put_blob_request(
+                path,
+                None,
+                &OpWrite::default(),
+                AsyncBody::Empty,
+            )?,
         };
 
-        self.core.sign_query(&mut req).await?;
+        self.core.sign_query(path, &mut req).await?;
 
         let (parts, _) = req.into_parts();
 
@@ -775,9 +857,13 @@ impl Accessor for AzblobBackend {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use super::AzblobBuilder;
+    use crate::raw::Accessor;
     use crate::services::azblob::backend::infer_storage_name_from_endpoint;
     use crate::Builder;
+    use crate::ErrorKind;
 
     #[test]
     fn test_infer_storage_name_from_endpoint() {
@@ -841,7 +927,6 @@ mod tests {
         azblob_builder.endpoint("https://storagesample.blob.core.usgovcloudapi.net");
         azblob_builder.container("container");
         azblob_builder.account_name("storagesample");
-        azblob_builder.account_key("account-key");
         azblob_builder.sas_token("sas");
         let azblob = azblob_builder
             .build()
@@ -854,11 +939,7 @@ mod tests {
 
         assert_eq!(azblob.core.container, "container".to_string());
 
-        assert_eq!(
-            azblob_builder.account_key.unwrap(),
-            "account-key".to_string()
-        );
-
+        assert_eq!(azblob_builder.account_key, None);
         assert_eq!(azblob_builder.sas_token.unwrap(), "sas".to_string());
     }
 
@@ -939,4 +1020,60 @@ SharedAccessSignature=sv=2021-01-01&ss=b&srt=c&sp=rwdlaciytfx&se=2022-01-01T11:0
         assert_eq!(builder.account_name, None);
         assert_eq!(builder.account_key, None);
     }
+
+    #[test]
+    pub fn test_sas_and_account_key_conflict() {
+        let mut builder = AzblobBuilder::default();
+        builder
+            .endpoint("http://127.0.0.1:10000/devstoreaccount1")
+            .container("test")
+            .account_name("devstoreaccount1")
+            .account_key("account-key")
+            .sas_token("?sv=2021-01-01&sig=xxx");
+        assert_eq!(builder.sas_token.as_deref(), Some("sv=2021-01-01&sig=xxx"));
+
+        let err = builder.build().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
+    #[test]
+    pub fn test_sas_and_client_id_conflict() {
+        let mut builder = AzblobBuilder::default();
+        builder
+            .endpoint("http://127.0.0.1:10000/devstoreaccount1")
+            .container("test")
+            .sas_token("sv=2021-01-01&sig=xxx")
+            .client_id("client-id");
+
+        let err = builder.build().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
+    #[test]
+    pub fn test_presign_requires_credential() {
+        let mut builder = AzblobBuilder::default();
+        builder
+            .endpoint("http://127.0.0.1:10000/devstoreaccount1")
+            .container("test");
+        let backend = builder.build().unwrap();
+        assert!(!backend.info().capability().presign);
+
+        for (key, value) in [
+            ("account_key", "account-key"),
+            ("sas_token", "sv=2021-01-01&sig=xxx"),
+            ("client_id", "client-id"),
+        ] {
+            let backend = AzblobBuilder::from_map(HashMap::from([
+                (
+                    "endpoint".to_string(),
+                    "http://127.0.0.1:10000/devstoreaccount1".to_string(),
+                ),
+                ("container".to_string(), "test".to_string()),
+                (key.to_string(), value.to_string()),
+            ]))
+            .build()
+            .unwrap();
+            assert!(backend.info().capability().presign, "{key} should presign");
+        }
+    }
 }
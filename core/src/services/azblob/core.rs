@@ -19,29 +19,51 @@ use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::fmt::Write;
+use std::sync::Mutex;
 use std::time::Duration;
 
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use bytes::Buf;
+use bytes::Bytes;
+use chrono::DateTime;
+use chrono::Utc;
+use hmac::Hmac;
+use hmac::Mac;
 use http::header::HeaderName;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
 use http::header::IF_MATCH;
 use http::header::IF_NONE_MATCH;
 use http::HeaderValue;
+use http::Method;
 use http::Request;
 use http::Response;
+use http::StatusCode;
+use percent_encoding::percent_decode_str;
+use quick_xml::de;
 use reqsign::AzureStorageCredential;
 use reqsign::AzureStorageLoader;
 use reqsign::AzureStorageSigner;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Sha256;
+
+use super::error::parse_error;
 
 use crate::raw::*;
 use crate::*;
 
 mod constants {
     pub const X_MS_VERSION: &str = "x-ms-version";
+    /// The service version used to sign user delegation SAS.
+    pub const SAS_VERSION: &str = "2022-11-02";
 
     pub const X_MS_BLOB_TYPE: &str = "x-ms-blob-type";
     pub const X_MS_COPY_SOURCE: &str = "x-ms-copy-source";
     pub const X_MS_BLOB_CACHE_CONTROL: &str = "x-ms-blob-cache-control";
+    pub const X_MS_BLOB_CONTENT_ENCODING: &str = "x-ms-blob-content-encoding";
+    pub const X_MS_BLOB_CONTENT_LANGUAGE: &str = "x-ms-blob-content-language";
     pub const X_MS_BLOB_CONDITION_APPENDPOS: &str = "x-ms-blob-condition-appendpos";
 
     // Server-side encryption with customer-provided headers
@@ -51,6 +73,7 @@ mod constants {
 }
 
 pub struct AzblobCore {
+    pub account_name: Option<String>,
     pub container: String,
     pub root: String,
     pub endpoint: String,
@@ -61,6 +84,7 @@ pub struct AzblobCore {
     pub loader: AzureStorageLoader,
     pub signer: AzureStorageSigner,
     pub batch_max_operations: usize,
+    pub user_delegation_key: UserDelegationKeyCache,
 }
 
 impl Debug for AzblobCore {
@@ -91,14 +115,96 @@ impl AzblobCore {
         }
     }
 
-    pub async fn sign_query<T>(&self, req: &mut Request<T>) -> Result<()> {
+    pub async fn sign_query<T>(&self, path: &str, req: &mut Request<T>) -> Result<()> {
         let cred = self.load_credential().await?;
+        let expire = Duration::from_secs(3600);
+
+        // AAD tokens can't be used in query string, we need to sign
+        // a user delegation SAS with the user delegation key instead.
+        if let AzureStorageCredential::BearerToken(_) = cred {
+            return self
+                .sign_query_with_user_delegation_key(path, req, expire)
+                .await;
+        }
 
         self.signer
-            .sign_query(req, Duration::from_secs(3600), &cred)
+            .sign_query(req, expire, &cred)
             .map_err(new_request_sign_error)
     }
 
+    async fn sign_query_with_user_delegation_key<T>(
+        &self,
+        path: &str,
+        req: &mut Request<T>,
+        expire: Duration,
+    ) -> Result<()> {
+        let account_name = self.account_name.as_deref().ok_or_else(|| {
+            Error::new(
+                ErrorKind::ConfigInvalid,
+                "account_name is required to sign user delegation sas",
+            )
+            .with_context("service", Scheme::Azblob)
+        })?;
+
+        let now = Utc::now();
+        // Start a bit earlier to tolerate clock skew between client and azure.
+        let start = now - chrono::Duration::minutes(5);
+        let expiry = now
+            + chrono::Duration::from_std(expire).map_err(|err| {
+                Error::new(ErrorKind::Unexpected, "presign expire is out of range").set_source(err)
+            })?;
+        let key = match self.user_delegation_key.get(expiry) {
+            Some(key) => key,
+            None => {
+                // Request a key that outlives this SAS so that it can be
+                // reused by later presigned requests.
+                let key_expiry =
+                    expiry.max(now + chrono::Duration::hours(USER_DELEGATION_KEY_LIFETIME_HOURS));
+                let key = self
+                    .azblob_get_user_delegation_key(start, key_expiry)
+                    .await?;
+                self.user_delegation_key.set(key_expiry, key.clone());
+                key
+            }
+        };
+
+        let permissions = if req.method() == Method::PUT {
+            "cw"
+        } else {
+            "r"
+        };
+        let content_disposition = req.uri().query().and_then(|query| {
+            query
+                .split('&')
+                .find_map(|kv| kv.strip_prefix("rscd="))
+                .map(|v| percent_decode_str(v).decode_utf8_lossy().to_string())
+        });
+
+        let sas = UserDelegationSas {
+            permissions,
+            start,
+            expiry,
+            resource: format!(
+                "/blob/{}/{}/{}",
+                account_name,
+                self.container,
+                build_abs_path(&self.root, path)
+            ),
+            content_disposition,
+        }
+        .token(&key)?;
+
+        let uri = match req.uri().query() {
+            Some(_) => format!("{}&{}", req.uri(), sas),
+            None => format!("{}?{}", req.uri(), sas),
+        };
+        *req.uri_mut() = uri.parse().map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "build presigned uri").set_source(err)
+        })?;
+
+        Ok(())
+    }
+
     pub async fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
         let cred = self.load_credential().await?;
         // Insert x-ms-version header for normal requests.
@@ -245,8 +351,7 @@ impl AzblobCore {
         &self,
         path: &str,
         size: Option<u64>,
-        content_type: Option<&str>,
-        cache_control: Option<&str>,
+        args: &OpWrite,
         body: AsyncBody,
     ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
@@ -263,14 +368,20 @@ impl AzblobCore {
         // Set SSE headers.
         req = self.insert_sse_headers(req);
 
-        if let Some(cache_control) = cache_control {
+        if let Some(cache_control) = args.cache_control() {
             req = req.header(constants::X_MS_BLOB_CACHE_CONTROL, cache_control);
         }
+        if let Some(encoding) = args.content_encoding() {
+            req = req.header(constants::X_MS_BLOB_CONTENT_ENCODING, encoding);
+        }
+        if let Some(language) = args.content_language() {
+            req = req.header(constants::X_MS_BLOB_CONTENT_LANGUAGE, language);
+        }
         if let Some(size) = size {
             req = req.header(CONTENT_LENGTH, size)
         }
 
-        if let Some(ty) = content_type {
+        if let Some(ty) = args.content_type() {
             req = req.header(CONTENT_TYPE, ty)
         }
 
@@ -444,15 +555,23 @@ impl AzblobCore {
         self.send(req).await
     }
 
-    pub fn azblob_delete_blob_request(&self, path: &str) -> Result<Request<AsyncBody>> {
+    pub fn azblob_delete_blob_request(
+        &self,
+        path: &str,
+        version: Option<&str>,
+    ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!(
+        let mut url = format!(
             "{}/{}/{}",
             self.endpoint,
             self.container,
             percent_encode_path(&p)
         );
+        if let Some(version) = version {
+            write!(url, "?versionid={}", percent_encode_path(version))
+                .expect("write into string must succeed");
+        }
 
         let req = Request::delete(&url);
 
@@ -461,8 +580,12 @@ impl AzblobCore {
             .map_err(new_request_build_error)
     }
 
-    pub async fn azblob_delete_blob(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
-        let mut req = self.azblob_delete_blob_request(path)?;
+    pub async fn azblob_delete_blob(
+        &self,
+        path: &str,
+        version: Option<&str>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = self.azblob_delete_blob_request(path, version)?;
 
         self.sign(&mut req).await?;
         self.send(req).await
@@ -534,6 +657,36 @@ impl AzblobCore {
         self.send(req).await
     }
 
+    pub async fn azblob_get_user_delegation_key(
+        &self,
+        start: DateTime<Utc>,
+        expiry: DateTime<Utc>,
+    ) -> Result<UserDelegationKey> {
+        let url = format!("{}/?restype=service&comp=userdelegationkey", self.endpoint);
+
+        let content = quick_xml::se::to_string(&KeyInfo {
+            start: format_sas_datetime(start),
+            expiry: format_sas_datetime(expiry),
+        })
+        .map_err(new_xml_deserialize_error)?;
+
+        let mut req = Request::post(&url)
+            .header(CONTENT_LENGTH, content.len())
+            .header(CONTENT_TYPE, "application/xml")
+            .body(AsyncBody::Bytes(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        let resp = self.send(req).await?;
+
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+        de::from_reader(bs.reader()).map_err(new_xml_deserialize_error)
+    }
+
     pub async fn azblob_batch_delete(
         &self,
         paths: &[String],
@@ -546,7 +699,7 @@ impl AzblobCore {
         let mut multipart = Multipart::new();
 
         for (idx, path) in paths.iter().enumerate() {
-            let mut req = self.azblob_delete_blob_request(path)?;
+            let mut req = self.azblob_delete_blob_request(path, None)?;
             self.batch_sign(&mut req).await?;
 
             multipart = multipart.part(
@@ -561,3 +714,239 @@ impl AzblobCore {
         self.send(req).await
     }
 }
+
+/// Request body of [Get User Delegation Key](https://learn.microsoft.com/en-us/rest/api/storageservices/get-user-delegation-key).
+#[derive(Default, Debug, Serialize)]
+#[serde(default, rename = "KeyInfo", rename_all = "PascalCase")]
+pub struct KeyInfo {
+    pub start: String,
+    pub expiry: String,
+}
+
+/// Response of [Get User Delegation Key](https://learn.microsoft.com/en-us/rest/api/storageservices/get-user-delegation-key).
+#[derive(Default, Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct UserDelegationKey {
+    pub signed_oid: String,
+    pub signed_tid: String,
+    pub signed_start: String,
+    pub signed_expiry: String,
+    pub signed_service: String,
+    pub signed_version: String,
+    pub value: String,
+}
+
+/// The lifetime in hours of user delegation keys requested by us.
+const USER_DELEGATION_KEY_LIFETIME_HOURS: i64 = 24;
+
+/// Cache of the user delegation key shared by all presigned requests.
+///
+/// The key will be refreshed once it's going to expire before the SAS
+/// signed by it, with a few minutes left as margin.
+#[derive(Default)]
+pub struct UserDelegationKeyCache(Mutex<Option<(DateTime<Utc>, UserDelegationKey)>>);
+
+impl UserDelegationKeyCache {
+    /// Get the cached key if it's still valid at `until`.
+    fn get(&self, until: DateTime<Utc>) -> Option<UserDelegationKey> {
+        let cache = self.0.lock().expect("lock must succeed");
+        match cache.as_ref() {
+            Some((expiry, key)) if *expiry - chrono::Duration::minutes(5) >= until => {
+                Some(key.clone())
+            }
+            _ => None,
+        }
+    }
+
+    fn set(&self, expiry: DateTime<Utc>, key: UserDelegationKey) {
+        *self.0.lock().expect("lock must succeed") = Some((expiry, key));
+    }
+}
+
+/// A blob service SAS signed by user delegation key.
+///
+/// ref: <https://learn.microsoft.com/en-us/rest/api/storageservices/create-user-delegation-sas>
+struct UserDelegationSas {
+    permissions: &'static str,
+    start: DateTime<Utc>,
+    expiry: DateTime<Utc>,
+    /// Canonicalized resource in `/blob/<account>/<container>/<blob>` format.
+    resource: String,
+    content_disposition: Option<String>,
+}
+
+impl UserDelegationSas {
+    fn string_to_sign(&self, key: &UserDelegationKey) -> String {
+        [
+            self.permissions,
+            &format_sas_datetime(self.start),
+            &format_sas_datetime(self.expiry),
+            &self.resource,
+            &key.signed_oid,
+            &key.signed_tid,
+            &key.signed_start,
+            &key.signed_expiry,
+            &key.signed_service,
+            &key.signed_version,
+            // signedAuthorizedUserObjectId
+            "",
+            // signedUnauthorizedUserObjectId
+            "",
+            // signedCorrelationId
+            "",
+            // signedIP
+            "",
+            // signedProtocol
+            "",
+            constants::SAS_VERSION,
+            // signedResource
+            "b",
+            // signedSnapshotTime
+            "",
+            // signedEncryptionScope
+            "",
+            // rscc
+            "",
+            self.content_disposition.as_deref().unwrap_or_default(),
+            // rsce
+            "",
+            // rscl
+            "",
+            // rsct
+            "",
+        ]
+        .join("\n")
+    }
+
+    /// Build the query string of this SAS, `rscd` is expected to be
+    /// present in the request already.
+    fn token(&self, key: &UserDelegationKey) -> Result<String> {
+        let secret = BASE64_STANDARD.decode(&key.value).map_err(|err| {
+            Error::new(
+                ErrorKind::Unexpected,
+                "user delegation key is not valid base64",
+            )
+            .set_source(err)
+        })?;
+        let mut mac = Hmac::<Sha256>::new_from_slice(&secret).map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "user delegation key is invalid").set_source(err)
+        })?;
+        mac.update(self.string_to_sign(key).as_bytes());
+        let signature = BASE64_STANDARD.encode(mac.finalize().into_bytes());
+
+        let query = [
+            ("sp", self.permissions),
+            ("st", &format_sas_datetime(self.start)),
+            ("se", &format_sas_datetime(self.expiry)),
+            ("skoid", &key.signed_oid),
+            ("sktid", &key.signed_tid),
+            ("skt", &key.signed_start),
+            ("ske", &key.signed_expiry),
+            ("sks", &key.signed_service),
+            ("skv", &key.signed_version),
+            ("sv", constants::SAS_VERSION),
+            ("sr", "b"),
+            ("sig", &signature),
+        ]
+        .iter()
+        .map(|(k, v)| format!("{k}={}", percent_encode_path(v)))
+        .collect::<Vec<_>>()
+        .join("&");
+
+        Ok(query)
+    }
+}
+
+fn format_sas_datetime(t: DateTime<Utc>) -> String {
+    t.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_user_delegation_key() {
+        let bs = Bytes::from(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<UserDelegationKey>
+    <SignedOid>oid</SignedOid>
+    <SignedTid>tid</SignedTid>
+    <SignedStart>2023-07-01T00:00:00Z</SignedStart>
+    <SignedExpiry>2023-07-02T00:00:00Z</SignedExpiry>
+    <SignedService>b</SignedService>
+    <SignedVersion>2022-11-02</SignedVersion>
+    <Value>a2V5</Value>
+</UserDelegationKey>"#,
+        );
+
+        let out: UserDelegationKey = de::from_reader(bs.reader()).expect("must success");
+
+        assert_eq!(out.signed_oid, "oid");
+        assert_eq!(out.signed_tid, "tid");
+        assert_eq!(out.signed_start, "2023-07-01T00:00:00Z");
+        assert_eq!(out.signed_expiry, "2023-07-02T00:00:00Z");
+        assert_eq!(out.signed_service, "b");
+        assert_eq!(out.signed_version, "2022-11-02");
+        assert_eq!(out.value, "a2V5");
+    }
+
+    #[test]
+    fn test_user_delegation_key_cache() {
+        let cache = UserDelegationKeyCache::default();
+        let now = Utc::now();
+        assert!(cache.get(now).is_none());
+
+        let key = UserDelegationKey {
+            value: "a2V5".to_string(),
+            ..Default::default()
+        };
+        cache.set(now + chrono::Duration::hours(2), key);
+
+        assert!(cache.get(now + chrono::Duration::hours(1)).is_some());
+        // Keys that expire soon after the SAS should be refreshed.
+        assert!(cache.get(now + chrono::Duration::minutes(118)).is_none());
+        assert!(cache.get(now + chrono::Duration::hours(3)).is_none());
+    }
+
+    #[test]
+    fn test_user_delegation_sas() {
+        let key = UserDelegationKey {
+            signed_oid: "oid".to_string(),
+            signed_tid: "tid".to_string(),
+            signed_start: "2023-07-01T00:00:00Z".to_string(),
+            signed_expiry: "2023-07-02T00:00:00Z".to_string(),
+            signed_service: "b".to_string(),
+            signed_version: "2022-11-02".to_string(),
+            value: BASE64_STANDARD.encode("key"),
+        };
+        let sas = UserDelegationSas {
+            permissions: "r",
+            start: parse_datetime_from_rfc3339("2023-07-01T08:00:00Z").unwrap(),
+            expiry: parse_datetime_from_rfc3339("2023-07-01T09:00:00Z").unwrap(),
+            resource: "/blob/account/container/dir/file".to_string(),
+            content_disposition: Some("attachment".to_string()),
+        };
+
+        assert_eq!(
+            sas.string_to_sign(&key),
+            "r\n2023-07-01T08:00:00Z\n2023-07-01T09:00:00Z\n/blob/account/container/dir/file\n\
+             oid\ntid\n2023-07-01T00:00:00Z\n2023-07-02T00:00:00Z\nb\n2022-11-02\n\
+             \n\n\n\n\n2022-11-02\nb\n\n\n\nattachment\n\n\n"
+        );
+
+        let token = sas.token(&key).expect("must success");
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"key").unwrap();
+        mac.update(sas.string_to_sign(&key).as_bytes());
+        let signature = BASE64_STANDARD.encode(mac.finalize().into_bytes());
+        assert_eq!(
+            token,
+            format!(
+                "sp=r&st=2023-07-01T08%3A00%3A00Z&se=2023-07-01T09%3A00%3A00Z&skoid=oid&sktid=tid\
+                 &skt=2023-07-01T00%3A00%3A00Z&ske=2023-07-02T00%3A00%3A00Z&sks=b&skv=2022-11-02\
+                 &sv=2022-11-02&sr=b&sig={}",
+                percent_encode_path(&signature)
+            )
+        );
+    }
+}
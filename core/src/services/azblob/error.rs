@@ -63,15 +63,19 @@ impl Debug for AzblobError {
 pub fn parse_http_error(status: StatusCode, body: &str) -> Result<Error> {
     let (kind, retryable) = match status {
         StatusCode::FORBIDDEN => (ErrorKind::PermissionDenied, false),
+        StatusCode::TOO_MANY_REQUESTS => (ErrorKind::RateLimited, true),
         StatusCode::INTERNAL_SERVER_ERROR
         | StatusCode::BAD_GATEWAY
         | StatusCode::SERVICE_UNAVAILABLE
         | StatusCode::GATEWAY_TIMEOUT => (ErrorKind::Unexpected, true),
         _ => (ErrorKind::Unexpected, false),
     };
-    let message = match de::from_str::<AzblobError>(body) {
-        Ok(err) => format!("{err:?}"),
-        Err(_) => body.to_string(),
+    let (kind, retryable, message) = match de::from_str::<AzblobError>(body) {
+        Ok(err) => {
+            let (kind, retryable) = parse_azblob_error_code(&err.code).unwrap_or((kind, retryable));
+            (kind, retryable, format!("{err:?}"))
+        }
+        Err(_) => (kind, retryable, body.to_string()),
     };
     let mut err = Error::new(kind, &message).with_context("response", body.to_string());
 
@@ -87,12 +91,13 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     let (parts, body) = resp.into_parts();
     let bs = body.bytes().await?;
 
-    let (kind, retryable) = match parts.status {
+    let (mut kind, mut retryable) = match parts.status {
         StatusCode::NOT_FOUND => (ErrorKind::NotFound, false),
         StatusCode::FORBIDDEN => (ErrorKind::PermissionDenied, false),
         StatusCode::PRECONDITION_FAILED | StatusCode::NOT_MODIFIED => {
             (ErrorKind::ConditionNotMatch, false)
         }
+        StatusCode::TOO_MANY_REQUESTS => (ErrorKind::RateLimited, true),
         StatusCode::INTERNAL_SERVER_ERROR
         | StatusCode::BAD_GATEWAY
         | StatusCode::SERVICE_UNAVAILABLE
@@ -101,7 +106,11 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     };
 
     let mut message = match de::from_reader::<_, AzblobError>(bs.clone().reader()) {
-        Ok(azblob_err) => format!("{azblob_err:?}"),
+        Ok(azblob_err) => {
+            (kind, retryable) =
+                parse_azblob_error_code(&azblob_err.code).unwrap_or((kind, retryable));
+            format!("{azblob_err:?}")
+        }
         Err(_) => String::from_utf8_lossy(&bs).into_owned(),
     };
 
@@ -109,6 +118,7 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     if message.is_empty() {
         if let Some(v) = parts.headers.get("x-ms-error-code") {
             if let Ok(code) = v.to_str() {
+                (kind, retryable) = parse_azblob_error_code(code).unwrap_or((kind, retryable));
                 message = format!(
                     "{:?}",
                     AzblobError {
@@ -123,6 +133,7 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     let mut err = Error::new(kind, &message);
 
     err = with_error_response_context(err, parts);
+    err = with_error_response_body(err, &bs);
 
     if retryable {
         err = err.set_temporary();
@@ -131,6 +142,22 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     Ok(err)
 }
 
+/// Returns the `Error kind` of this code and whether the error is retryable.
+/// All possible error code: <https://learn.microsoft.com/en-us/rest/api/storageservices/common-rest-api-error-codes>
+fn parse_azblob_error_code(code: &str) -> Option<(ErrorKind, bool)> {
+    match code {
+        // > The server is currently unable to receive requests. Please retry
+        // > your request.
+        //
+        // Azure returns it with 503 while the account or partition is
+        // throttled, so we should slow down.
+        "ServerBusy" => Some((ErrorKind::RateLimited, true)),
+        // > The operation could not be completed within the permitted time.
+        "OperationTimedOut" => Some((ErrorKind::Unexpected, true)),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -184,4 +211,21 @@ mod tests {
         );
         assert_eq!(out.reason, "invalid receipt format");
     }
+
+    #[test]
+    fn test_parse_http_error_server_busy() {
+        let body = r#"<?xml version="1.0" encoding="utf-8"?>
+<Error>
+  <Code>ServerBusy</Code>
+  <Message>Egress is over the account limit.</Message>
+</Error>"#;
+
+        let err = parse_http_error(StatusCode::SERVICE_UNAVAILABLE, body).unwrap();
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+        assert!(err.is_temporary());
+
+        let err = parse_http_error(StatusCode::SERVICE_UNAVAILABLE, "").unwrap();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.is_temporary());
+    }
 }
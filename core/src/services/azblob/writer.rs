@@ -39,13 +39,9 @@ impl AzblobWriter {
     }
 
     async fn write_oneshot(&self, size: u64, body: AsyncBody) -> Result<()> {
-        let mut req = self.core.azblob_put_blob_request(
-            &self.path,
-            Some(size),
-            self.op.content_type(),
-            self.op.cache_control(),
-            body,
-        )?;
+        let mut req = self
+            .core
+            .azblob_put_blob_request(&self.path, Some(size), &self.op, body)?;
 
         self.core.sign(&mut req).await?;
 
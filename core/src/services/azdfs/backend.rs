@@ -148,29 +148,44 @@ impl AzdfsBuilder {
 impl Builder for AzdfsBuilder {
     type Accessor = AzdfsBackend;
     const SCHEME: Scheme = Scheme::Azdfs;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&[
+        "root",
+        "filesystem",
+        "endpoint",
+        "account_name",
+        "account_key",
+    ]);
+
+    fn validate(&self) -> Result<()> {
+        if self.filesystem.is_empty() {
+            return Err(Error::new(ErrorKind::ConfigInvalid, "filesystem is empty")
+                .with_operation("Builder::validate")
+                .with_context("service", Scheme::Azdfs)
+                .with_context("key", "filesystem"));
+        }
+
+        if self.endpoint.is_none() {
+            return Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+                .with_operation("Builder::validate")
+                .with_context("service", Scheme::Azdfs)
+                .with_context("key", "endpoint"));
+        }
+
+        Ok(())
+    }
 
     fn build(&mut self) -> Result<Self::Accessor> {
         debug!("backend build started: {:?}", &self);
+        self.validate()?;
 
         let root = normalize_root(&self.root.take().unwrap_or_default());
         debug!("backend use root {}", root);
 
-        // Handle endpoint, region and container name.
-        let filesystem = match self.filesystem.is_empty() {
-            false => Ok(&self.filesystem),
-            true => Err(Error::new(ErrorKind::ConfigInvalid, "filesystem is empty")
-                .with_operation("Builder::build")
-                .with_context("service", Scheme::Azdfs)),
-        }?;
+        let filesystem = &self.filesystem;
         debug!("backend use filesystem {}", &filesystem);
 
-        let endpoint = match &self.endpoint {
-            Some(endpoint) => Ok(endpoint.clone()),
-            None => Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
-                .with_operation("Builder::build")
-                .with_context("service", Scheme::Azdfs)),
-        }?;
-        debug!("backend use endpoint {}", &filesystem);
+        let endpoint = self.endpoint.clone().unwrap_or_default();
+        debug!("backend use endpoint {}", &endpoint);
 
         let client = if let Some(client) = self.http_client.take() {
             client
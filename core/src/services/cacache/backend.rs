@@ -48,6 +48,7 @@ impl CacacheBuilder {
 
 impl Builder for CacacheBuilder {
     const SCHEME: Scheme = Scheme::Cacache;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&["datadir"]);
     type Accessor = CacacheBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
@@ -58,11 +59,22 @@ impl Builder for CacacheBuilder {
         builder
     }
 
+    fn validate(&self) -> Result<()> {
+        if self.datadir.is_none() {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "datadir is required but not set")
+                    .with_operation("Builder::validate")
+                    .with_context("service", Scheme::Cacache)
+                    .with_context("key", "datadir"),
+            );
+        }
+
+        Ok(())
+    }
+
     fn build(&mut self) -> Result<Self::Accessor> {
-        let datadir_path = self.datadir.take().ok_or_else(|| {
-            Error::new(ErrorKind::ConfigInvalid, "datadir is required but not set")
-                .with_context("service", Scheme::Cacache)
-        })?;
+        self.validate()?;
+        let datadir_path = self.datadir.take().unwrap_or_default();
 
         Ok(CacacheBackend::new(Adapter {
             datadir: datadir_path,
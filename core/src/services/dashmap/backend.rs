@@ -41,6 +41,7 @@ impl DashmapBuilder {
 
 impl Builder for DashmapBuilder {
     const SCHEME: Scheme = Scheme::Dashmap;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&["root"]);
     type Accessor = DashmapBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
@@ -176,6 +176,13 @@ impl DropboxBuilder {
 
 impl Builder for DropboxBuilder {
     const SCHEME: Scheme = Scheme::Dropbox;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&[
+        "root",
+        "access_token",
+        "refresh_token",
+        "client_id",
+        "client_secret",
+    ]);
     type Accessor = DropboxBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
@@ -188,7 +195,44 @@ impl Builder for DropboxBuilder {
         builder
     }
 
+    fn validate(&self) -> Result<()> {
+        let err = |msg: &'static str, key: &'static str| {
+            Error::new(ErrorKind::ConfigInvalid, msg)
+                .with_operation("Builder::validate")
+                .with_context("service", Scheme::Dropbox)
+                .with_context("key", key)
+        };
+
+        match (&self.access_token, &self.refresh_token) {
+            (Some(_), None) => Ok(()),
+            (None, Some(_)) => {
+                if self.client_id.is_none() {
+                    return Err(err(
+                        "client_id must be set when refresh_token is set",
+                        "client_id",
+                    ));
+                }
+                if self.client_secret.is_none() {
+                    return Err(err(
+                        "client_secret must be set when refresh_token is set",
+                        "client_secret",
+                    ));
+                }
+                Ok(())
+            }
+            (Some(_), Some(_)) => Err(err(
+                "access_token and refresh_token can not be set at the same time",
+                "refresh_token",
+            )),
+            (None, None) => Err(err(
+                "access_token or refresh_token must be set",
+                "access_token",
+            )),
+        }
+    }
+
     fn build(&mut self) -> Result<Self::Accessor> {
+        self.validate()?;
         let root = normalize_root(&self.root.take().unwrap_or_default());
         let client = if let Some(client) = self.http_client.take() {
             client
@@ -199,50 +243,19 @@ impl Builder for DropboxBuilder {
             })?
         };
 
-        let signer = match (self.access_token.take(), self.refresh_token.take()) {
-            (Some(access_token), None) => DropboxSigner {
+        let signer = match self.access_token.take() {
+            Some(access_token) => DropboxSigner {
                 access_token,
                 // We will never expire user specified token.
                 expires_in: DateTime::<Utc>::MAX_UTC,
                 ..Default::default()
             },
-            (None, Some(refresh_token)) => {
-                let client_id = self.client_id.take().ok_or_else(|| {
-                    Error::new(
-                        ErrorKind::ConfigInvalid,
-                        "client_id must be set when refresh_token is set",
-                    )
-                    .with_context("service", Scheme::Dropbox)
-                })?;
-                let client_secret = self.client_secret.take().ok_or_else(|| {
-                    Error::new(
-                        ErrorKind::ConfigInvalid,
-                        "client_secret must be set when refresh_token is set",
-                    )
-                    .with_context("service", Scheme::Dropbox)
-                })?;
-
-                DropboxSigner {
-                    refresh_token,
-                    client_id,
-                    client_secret,
-                    ..Default::default()
-                }
-            }
-            (Some(_), Some(_)) => {
-                return Err(Error::new(
-                    ErrorKind::ConfigInvalid,
-                    "access_token and refresh_token can not be set at the same time",
-                )
-                .with_context("service", Scheme::Dropbox))
-            }
-            (None, None) => {
-                return Err(Error::new(
-                    ErrorKind::ConfigInvalid,
-                    "access_token or refresh_token must be set",
-                )
-                .with_context("service", Scheme::Dropbox))
-            }
+            None => DropboxSigner {
+                refresh_token: self.refresh_token.take().unwrap_or_default(),
+                client_id: self.client_id.take().unwrap_or_default(),
+                client_secret: self.client_secret.take().unwrap_or_default(),
+                ..Default::default()
+            },
         };
 
         Ok(DropboxBackend {
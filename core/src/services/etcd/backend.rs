@@ -162,6 +162,15 @@ impl EtcdBuilder {
 
 impl Builder for EtcdBuilder {
     const SCHEME: Scheme = Scheme::Etcd;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&[
+        "root",
+        "endpoints",
+        "username",
+        "password",
+        "ca_path",
+        "cert_path",
+        "key_path",
+    ]);
     type Accessor = EtcdBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
@@ -178,7 +187,30 @@ impl Builder for EtcdBuilder {
         builder
     }
 
+    fn validate(&self) -> Result<()> {
+        let tls = [
+            ("ca_path", &self.ca_path),
+            ("cert_path", &self.cert_path),
+            ("key_path", &self.key_path),
+        ];
+        // TLS is enabled only when all of them are set.
+        if tls.iter().any(|(_, v)| v.is_some()) {
+            if let Some((key, _)) = tls.iter().find(|(_, v)| v.is_none()) {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "ca_path, cert_path and key_path must be set together",
+                )
+                .with_operation("Builder::validate")
+                .with_context("service", Scheme::Etcd)
+                .with_context("key", *key));
+            }
+        }
+
+        Ok(())
+    }
+
     fn build(&mut self) -> Result<Self::Accessor> {
+        self.validate()?;
         let endpoints = self
             .endpoints
             .clone()
@@ -188,10 +220,12 @@ impl Builder for EtcdBuilder {
 
         let mut options = ConnectOptions::new();
 
-        if self.ca_path.is_some() && self.cert_path.is_some() && self.key_path.is_some() {
-            let ca = self.load_pem(self.ca_path.clone().unwrap().as_str())?;
-            let key = self.load_pem(self.key_path.clone().unwrap().as_str())?;
-            let cert = self.load_pem(self.cert_path.clone().unwrap().as_str())?;
+        if let (Some(ca_path), Some(cert_path), Some(key_path)) =
+            (&self.ca_path, &self.cert_path, &self.key_path)
+        {
+            let ca = self.load_pem(ca_path)?;
+            let key = self.load_pem(key_path)?;
+            let cert = self.load_pem(cert_path)?;
 
             let tls_options = TlsOptions::default()
                 .ca_certificate(Certificate::from_pem(ca))
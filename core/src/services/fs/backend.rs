@@ -18,6 +18,10 @@
 use std::cmp::min;
 use std::collections::HashMap;
 use std::io::SeekFrom;
+#[cfg(unix)]
+use std::os::unix::fs::DirBuilderExt;
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -25,11 +29,16 @@ use async_compat::Compat;
 use async_trait::async_trait;
 use chrono::DateTime;
 use log::debug;
+use log::warn;
 use uuid::Uuid;
 
 use super::appender::FsAppender;
 use super::error::parse_io_error;
 use super::pager::FsPager;
+use super::user_metadata::blocking_get_user_metadata;
+use super::user_metadata::blocking_set_user_metadata;
+use super::user_metadata::get_user_metadata;
+use super::user_metadata::set_user_metadata;
 use super::writer::FsWriter;
 use crate::raw::*;
 use crate::*;
@@ -40,7 +49,11 @@ use crate::*;
 pub struct FsBuilder {
     root: Option<PathBuf>,
     atomic_write_dir: Option<PathBuf>,
+    atomic_write: bool,
     enable_path_check: bool,
+    fsync_on_close: Option<bool>,
+    file_mode: Option<u32>,
+    dir_mode: Option<u32>,
 }
 
 impl FsBuilder {
@@ -55,7 +68,28 @@ impl FsBuilder {
         self
     }
 
+    /// Enable atomic write.
+    ///
+    /// Data will be written into a temp file in the same directory of the
+    /// target file first, and renamed into place on `close`. So readers will
+    /// only observe complete files. The temp file will be removed on abort
+    /// or failed close.
+    ///
+    /// Temp files are named like `.<name>.<uuid>.tmp`, which could be listed
+    /// while writing.
+    ///
+    /// If [`FsBuilder::atomic_write_dir`] is set, temp files will be created
+    /// there instead.
+    pub fn atomic_write(&mut self, enabled: bool) -> &mut Self {
+        self.atomic_write = enabled;
+
+        self
+    }
+
     /// Set temp dir for atomic write.
+    ///
+    /// The dir must be on the same filesystem with root, otherwise the
+    /// rename will fail.
     pub fn atomic_write_dir(&mut self, dir: &str) -> &mut Self {
         self.atomic_write_dir = if dir.is_empty() {
             None
@@ -77,10 +111,63 @@ impl FsBuilder {
 
         self
     }
+
+    /// Set whether to call `fsync` on files before `close` returns.
+    ///
+    /// With fsync enabled, a successful `close` guarantees the data has been
+    /// persisted to disk. Disable it to trade durability for throughput.
+    ///
+    /// Default to `true`.
+    pub fn fsync_on_close(&mut self, enabled: bool) -> &mut Self {
+        self.fsync_on_close = Some(enabled);
+
+        self
+    }
+
+    /// Set the unix permissions (like `0o644`) of created files.
+    ///
+    /// The mode is applied while creating files, so it's still subject to
+    /// the process's umask. Existing files will not be changed.
+    ///
+    /// This option is a no-op on non-unix platforms like Windows.
+    pub fn file_mode(&mut self, mode: u32) -> &mut Self {
+        self.file_mode = Some(mode);
+
+        self
+    }
+
+    /// Set the unix permissions (like `0o755`) of created directories.
+    ///
+    /// The mode is applied while creating directories, so it's still subject
+    /// to the process's umask. Existing directories will not be changed.
+    ///
+    /// This option is a no-op on non-unix platforms like Windows.
+    pub fn dir_mode(&mut self, mode: u32) -> &mut Self {
+        self.dir_mode = Some(mode);
+
+        self
+    }
+}
+
+/// Parse unix mode like `644` or `0o644` in octal.
+fn parse_mode(key: &str, v: &str) -> Option<u32> {
+    let mode = u32::from_str_radix(v.trim_start_matches("0o"), 8);
+    if mode.is_err() {
+        warn!("fs: invalid {key} {v}, ignored");
+    }
+    mode.ok()
 }
 
 impl Builder for FsBuilder {
     const SCHEME: Scheme = Scheme::Fs;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&[
+        "root",
+        "atomic_write_dir",
+        "atomic_write",
+        "fsync_on_close",
+        "file_mode",
+        "dir_mode",
+    ]);
     type Accessor = FsBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
@@ -89,20 +176,38 @@ impl Builder for FsBuilder {
         map.get("root").map(|v| builder.root(v));
         map.get("atomic_write_dir")
             .map(|v| builder.atomic_write_dir(v));
+        map.get("atomic_write")
+            .map(|v| builder.atomic_write(v == "on" || v == "true"));
+        map.get("fsync_on_close")
+            .map(|v| builder.fsync_on_close(v == "on" || v == "true"));
+        map.get("file_mode")
+            .and_then(|v| parse_mode("file_mode", v))
+            .map(|v| builder.file_mode(v));
+        map.get("dir_mode")
+            .and_then(|v| parse_mode("dir_mode", v))
+            .map(|v| builder.dir_mode(v));
 
         builder
     }
 
+    fn validate(&self) -> Result<()> {
+        if self.root.is_none() {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "root is not specified")
+                    .with_operation("Builder::validate")
+                    .with_context("service", Scheme::Fs)
+                    .with_context("key", "root"),
+            );
+        }
+
+        Ok(())
+    }
+
     fn build(&mut self) -> Result<Self::Accessor> {
         debug!("backend build started: {:?}", &self);
+        self.validate()?;
 
-        let root = match self.root.take() {
-            Some(root) => Ok(root),
-            None => Err(Error::new(
-                ErrorKind::ConfigInvalid,
-                "root is not specified",
-            )),
-        }?;
+        let root = self.root.take().unwrap_or_default();
         debug!("backend use root {}", root.to_string_lossy());
 
         // If root dir is not exist, we must create it.
@@ -165,7 +270,11 @@ impl Builder for FsBuilder {
         Ok(FsBackend {
             root,
             atomic_write_dir,
+            atomic_write: self.atomic_write,
             enable_path_check: self.enable_path_check,
+            fsync_on_close: self.fsync_on_close.unwrap_or(true),
+            file_mode: self.file_mode,
+            dir_mode: self.dir_mode,
         })
     }
 }
@@ -175,7 +284,17 @@ impl Builder for FsBuilder {
 pub struct FsBackend {
     root: PathBuf,
     atomic_write_dir: Option<PathBuf>,
+    atomic_write: bool,
     enable_path_check: bool,
+    fsync_on_close: bool,
+    file_mode: Option<u32>,
+    dir_mode: Option<u32>,
+}
+
+/// Check if the write may overwrite an existing file in place without
+/// user metadata, whose stale user metadata must be cleaned up.
+fn needs_overwrite_check(args: &OpWrite, tmp_path: &Option<PathBuf>) -> bool {
+    cfg!(unix) && tmp_path.is_none() && args.user_metadata().is_none() && !args.is_create_only()
 }
 
 #[inline]
@@ -186,9 +305,52 @@ fn tmp_file_of(path: &str) -> String {
     format!("{name}.{uuid}")
 }
 
+/// Build the temp path in the same directory of target path, so that the
+/// rename will be atomic.
+#[inline]
+fn sibling_tmp_path_of(target_path: &Path, path: &str) -> PathBuf {
+    target_path.with_file_name(format!(".{}.tmp", tmp_file_of(path)))
+}
+
 impl FsBackend {
+    // Create dir and all its parents with configured dir mode.
+    async fn create_dir_all(&self, p: &Path) -> Result<()> {
+        let mut builder = tokio::fs::DirBuilder::new();
+        builder.recursive(true);
+        #[cfg(unix)]
+        if let Some(mode) = self.dir_mode {
+            builder.mode(mode);
+        }
+
+        builder.create(p).await.map_err(parse_io_error)
+    }
+
+    // Synchronously create dir and all its parents with configured dir mode.
+    fn blocking_create_dir_all(&self, p: &Path) -> Result<()> {
+        let mut builder = std::fs::DirBuilder::new();
+        builder.recursive(true);
+        #[cfg(unix)]
+        if let Some(mode) = self.dir_mode {
+            builder.mode(mode);
+        }
+
+        builder.create(p).map_err(parse_io_error)
+    }
+
+    // Build open options for write with configured file mode.
+    fn open_options(&self) -> std::fs::OpenOptions {
+        let mut options = std::fs::OpenOptions::new();
+        options.create(true).write(true);
+        #[cfg(unix)]
+        if let Some(mode) = self.file_mode {
+            options.mode(mode);
+        }
+
+        options
+    }
+
     // Synchronously build write path and ensure the parent dirs created
-    fn blocking_ensure_write_abs_path(parent: &Path, path: &str) -> Result<PathBuf> {
+    fn blocking_ensure_write_abs_path(&self, parent: &Path, path: &str) -> Result<PathBuf> {
         let p = parent.join(path);
 
         // Create dir before write path.
@@ -208,13 +370,13 @@ impl FsBackend {
             })?
             .to_path_buf();
 
-        std::fs::create_dir_all(parent).map_err(parse_io_error)?;
+        self.blocking_create_dir_all(&parent)?;
 
         Ok(p)
     }
 
     // Build write path and ensure the parent dirs created
-    async fn ensure_write_abs_path(parent: &Path, path: &str) -> Result<PathBuf> {
+    async fn ensure_write_abs_path(&self, parent: &Path, path: &str) -> Result<PathBuf> {
         let p = parent.join(path);
 
         // Create dir before write path.
@@ -234,9 +396,7 @@ impl FsBackend {
             })?
             .to_path_buf();
 
-        tokio::fs::create_dir_all(&parent)
-            .await
-            .map_err(parse_io_error)?;
+        self.create_dir_all(&parent).await?;
 
         Ok(p)
     }
@@ -266,6 +426,9 @@ impl Accessor for FsBackend {
                 write: true,
                 write_can_sink: true,
                 write_without_content_length: true,
+                write_with_range: true,
+                write_with_if_none_match: true,
+                write_with_user_metadata: cfg!(unix),
                 create_dir: true,
                 delete: true,
 
@@ -273,6 +436,7 @@ impl Accessor for FsBackend {
 
                 list: true,
                 list_with_delimiter_slash: true,
+                list_with_metadata: true,
 
                 copy: true,
                 rename: true,
@@ -287,9 +451,7 @@ impl Accessor for FsBackend {
     async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
         let p = self.root.join(path.trim_end_matches('/'));
 
-        tokio::fs::create_dir_all(&p)
-            .await
-            .map_err(parse_io_error)?;
+        self.create_dir_all(&p).await?;
 
         Ok(RpCreateDir::default())
     }
@@ -366,35 +528,92 @@ impl Accessor for FsBackend {
         Ok((RpRead::new(end - start), r))
     }
 
-    async fn write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        if args.if_none_match().is_some() && !args.is_create_only() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "fs only supports if_none_match with `*`",
+            ));
+        }
+
+        // Range write will update the file in place, so atomic write
+        // doesn't apply.
+        if let Some(range) = args.range() {
+            let p = self.ensure_write_abs_path(&self.root, path).await?;
+            let f = tokio::fs::OpenOptions::from(self.open_options())
+                .create_new(args.is_create_only())
+                .open(&p)
+                .await
+                .map_err(parse_io_error)?;
+            if let Some(data) = args.user_metadata() {
+                set_user_metadata(p.clone(), data.clone()).await;
+            }
+            let offset = range.offset().unwrap_or_default();
+
+            return Ok((
+                RpWrite::new(),
+                FsWriter::new(p, None, f)
+                    .with_offset(offset)
+                    .with_fsync(self.fsync_on_close),
+            ));
+        }
+
         let (target_path, tmp_path) = if let Some(atomic_write_dir) = &self.atomic_write_dir {
-            let target_path = Self::ensure_write_abs_path(&self.root, path).await?;
-            let tmp_path =
-                Self::ensure_write_abs_path(atomic_write_dir, &tmp_file_of(path)).await?;
+            let target_path = self.ensure_write_abs_path(&self.root, path).await?;
+            let tmp_path = self
+                .ensure_write_abs_path(atomic_write_dir, &tmp_file_of(path))
+                .await?;
+            (target_path, Some(tmp_path))
+        } else if self.atomic_write {
+            let target_path = self.ensure_write_abs_path(&self.root, path).await?;
+            let tmp_path = sibling_tmp_path_of(&target_path, path);
             (target_path, Some(tmp_path))
         } else {
-            let p = Self::ensure_write_abs_path(&self.root, path).await?;
+            let p = self.ensure_write_abs_path(&self.root, path).await?;
 
             (p, None)
         };
 
-        let f = tokio::fs::OpenOptions::new()
-            .create(true)
-            .truncate(true)
-            .write(true)
-            .open(tmp_path.as_ref().unwrap_or(&target_path))
-            .await
-            .map_err(parse_io_error)?;
+        let p = tmp_path.as_ref().unwrap_or(&target_path);
+        let mut opts = tokio::fs::OpenOptions::from(self.open_options());
+        opts.truncate(true);
+        // Without atomic write, the file is overwritten in place and keeps
+        // its xattrs. Try to create a new file first, so that xattrs are only
+        // touched while overwriting an existing file without user metadata.
+        let (f, overwritten) = if needs_overwrite_check(&args, &tmp_path) {
+            match opts.clone().create_new(true).open(p).await {
+                Ok(f) => (f, false),
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {
+                    (opts.open(p).await.map_err(parse_io_error)?, true)
+                }
+                Err(err) => return Err(parse_io_error(err)),
+            }
+        } else {
+            let f = opts
+                .create_new(args.is_create_only() && tmp_path.is_none())
+                .open(p)
+                .await
+                .map_err(parse_io_error)?;
+            (f, false)
+        };
+        match args.user_metadata() {
+            Some(data) => set_user_metadata(p.clone(), data.clone()).await,
+            None if overwritten => set_user_metadata(p.clone(), HashMap::new()).await,
+            None => {}
+        }
 
-        Ok((RpWrite::new(), FsWriter::new(target_path, tmp_path, f)))
+        Ok((
+            RpWrite::new(),
+            FsWriter::new(target_path, tmp_path, f)
+                .with_fsync(self.fsync_on_close)
+                .with_create_new(args.is_create_only()),
+        ))
     }
 
     async fn append(&self, path: &str, _: OpAppend) -> Result<(RpAppend, Self::Appender)> {
-        let path = Self::ensure_write_abs_path(&self.root, path).await?;
+        let path = self.ensure_write_abs_path(&self.root, path).await?;
 
-        let f = tokio::fs::OpenOptions::new()
-            .create(true)
-            .write(true)
+        let f = tokio::fs::OpenOptions::from(self.open_options())
             .append(true)
             .open(&path)
             .await
@@ -409,7 +628,9 @@ impl Accessor for FsBackend {
         // try to get the metadata of the source file to ensure it exists
         tokio::fs::metadata(&from).await.map_err(parse_io_error)?;
 
-        let to = Self::ensure_write_abs_path(&self.root, to.trim_end_matches('/')).await?;
+        let to = self
+            .ensure_write_abs_path(&self.root, to.trim_end_matches('/'))
+            .await?;
 
         tokio::fs::copy(from, to).await.map_err(parse_io_error)?;
 
@@ -422,14 +643,16 @@ impl Accessor for FsBackend {
         // try to get the metadata of the source file to ensure it exists
         tokio::fs::metadata(&from).await.map_err(parse_io_error)?;
 
-        let to = Self::ensure_write_abs_path(&self.root, to.trim_end_matches('/')).await?;
+        let to = self
+            .ensure_write_abs_path(&self.root, to.trim_end_matches('/'))
+            .await?;
 
         tokio::fs::rename(from, to).await.map_err(parse_io_error)?;
 
         Ok(RpRename::default())
     }
 
-    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         let p = self.root.join(path.trim_end_matches('/'));
 
         let meta = tokio::fs::metadata(&p).await.map_err(parse_io_error)?;
@@ -448,13 +671,18 @@ impl Accessor for FsBackend {
         } else {
             EntryMode::Unknown
         };
-        let m = Metadata::new(mode)
+        let mut m = Metadata::new(mode)
             .with_content_length(meta.len())
             .with_last_modified(
                 meta.modified()
                     .map(DateTime::from)
                     .map_err(parse_io_error)?,
             );
+        if mode.is_file() && args.need(Metakey::UserMetadata) {
+            if let Some(data) = get_user_metadata(p.clone()).await {
+                m.set_user_metadata(data);
+            }
+        }
 
         Ok(RpStat::new(m))
     }
@@ -493,7 +721,7 @@ impl Accessor for FsBackend {
             }
         };
 
-        let rd = FsPager::new(&self.root, f, args.limit());
+        let rd = FsPager::new(&self.root, f, args.limit(), args.metadata());
 
         Ok((RpList::default(), Some(rd)))
     }
@@ -501,7 +729,7 @@ impl Accessor for FsBackend {
     fn blocking_create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
         let p = self.root.join(path.trim_end_matches('/'));
 
-        std::fs::create_dir_all(p).map_err(parse_io_error)?;
+        self.blocking_create_dir_all(&p)?;
 
         Ok(RpCreateDir::default())
     }
@@ -566,26 +794,84 @@ impl Accessor for FsBackend {
         Ok((RpRead::new(end - start), r))
     }
 
-    fn blocking_write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        if args.if_none_match().is_some() && !args.is_create_only() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "fs only supports if_none_match with `*`",
+            ));
+        }
+
+        // Range write will update the file in place, so atomic write
+        // doesn't apply.
+        if let Some(range) = args.range() {
+            let p = self.blocking_ensure_write_abs_path(&self.root, path)?;
+            let f = self
+                .open_options()
+                .create_new(args.is_create_only())
+                .open(&p)
+                .map_err(parse_io_error)?;
+            if let Some(data) = args.user_metadata() {
+                blocking_set_user_metadata(&p, data);
+            }
+            let offset = range.offset().unwrap_or_default();
+
+            return Ok((
+                RpWrite::new(),
+                FsWriter::new(p, None, f)
+                    .with_offset(offset)
+                    .with_fsync(self.fsync_on_close),
+            ));
+        }
+
         let (target_path, tmp_path) = if let Some(atomic_write_dir) = &self.atomic_write_dir {
-            let target_path = Self::blocking_ensure_write_abs_path(&self.root, path)?;
+            let target_path = self.blocking_ensure_write_abs_path(&self.root, path)?;
             let tmp_path =
-                Self::blocking_ensure_write_abs_path(atomic_write_dir, &tmp_file_of(path))?;
+                self.blocking_ensure_write_abs_path(atomic_write_dir, &tmp_file_of(path))?;
+            (target_path, Some(tmp_path))
+        } else if self.atomic_write {
+            let target_path = self.blocking_ensure_write_abs_path(&self.root, path)?;
+            let tmp_path = sibling_tmp_path_of(&target_path, path);
             (target_path, Some(tmp_path))
         } else {
-            let p = Self::blocking_ensure_write_abs_path(&self.root, path)?;
+            let p = self.blocking_ensure_write_abs_path(&self.root, path)?;
 
             (p, None)
         };
 
-        let f = std::fs::OpenOptions::new()
-            .create(true)
-            .truncate(true)
-            .write(true)
-            .open(tmp_path.as_ref().unwrap_or(&target_path))
-            .map_err(parse_io_error)?;
+        let p = tmp_path.as_ref().unwrap_or(&target_path);
+        let mut opts = self.open_options();
+        opts.truncate(true);
+        // Without atomic write, the file is overwritten in place and keeps
+        // its xattrs. Try to create a new file first, so that xattrs are only
+        // touched while overwriting an existing file without user metadata.
+        let (f, overwritten) = if needs_overwrite_check(&args, &tmp_path) {
+            match opts.clone().create_new(true).open(p) {
+                Ok(f) => (f, false),
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {
+                    (opts.open(p).map_err(parse_io_error)?, true)
+                }
+                Err(err) => return Err(parse_io_error(err)),
+            }
+        } else {
+            let f = opts
+                .create_new(args.is_create_only() && tmp_path.is_none())
+                .open(p)
+                .map_err(parse_io_error)?;
+            (f, false)
+        };
+        match args.user_metadata() {
+            Some(data) => blocking_set_user_metadata(p, data),
+            None if overwritten => blocking_set_user_metadata(p, &HashMap::new()),
+            None => {}
+        }
 
-        Ok((RpWrite::new(), FsWriter::new(target_path, tmp_path, f)))
+        Ok((
+            RpWrite::new(),
+            FsWriter::new(target_path, tmp_path, f)
+                .with_fsync(self.fsync_on_close)
+                .with_create_new(args.is_create_only()),
+        ))
     }
 
     fn blocking_copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
@@ -594,7 +880,7 @@ impl Accessor for FsBackend {
         // try to get the metadata of the source file to ensure it exists
         std::fs::metadata(&from).map_err(parse_io_error)?;
 
-        let to = Self::blocking_ensure_write_abs_path(&self.root, to.trim_end_matches('/'))?;
+        let to = self.blocking_ensure_write_abs_path(&self.root, to.trim_end_matches('/'))?;
 
         std::fs::copy(from, to).map_err(parse_io_error)?;
 
@@ -607,17 +893,17 @@ impl Accessor for FsBackend {
         // try to get the metadata of the source file to ensure it exists
         std::fs::metadata(&from).map_err(parse_io_error)?;
 
-        let to = Self::blocking_ensure_write_abs_path(&self.root, to.trim_end_matches('/'))?;
+        let to = self.blocking_ensure_write_abs_path(&self.root, to.trim_end_matches('/'))?;
 
         std::fs::rename(from, to).map_err(parse_io_error)?;
 
         Ok(RpRename::default())
     }
 
-    fn blocking_stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         let p = self.root.join(path.trim_end_matches('/'));
 
-        let meta = std::fs::metadata(p).map_err(parse_io_error)?;
+        let meta = std::fs::metadata(&p).map_err(parse_io_error)?;
 
         if self.enable_path_check && meta.is_dir() != path.ends_with('/') {
             return Err(Error::new(
@@ -633,13 +919,18 @@ impl Accessor for FsBackend {
         } else {
             EntryMode::Unknown
         };
-        let m = Metadata::new(mode)
+        let mut m = Metadata::new(mode)
             .with_content_length(meta.len())
             .with_last_modified(
                 meta.modified()
                     .map(DateTime::from)
                     .map_err(parse_io_error)?,
             );
+        if mode.is_file() && args.need(Metakey::UserMetadata) {
+            if let Some(data) = blocking_get_user_metadata(&p) {
+                m.set_user_metadata(data);
+            }
+        }
 
         Ok(RpStat::new(m))
     }
@@ -678,7 +969,7 @@ impl Accessor for FsBackend {
             }
         };
 
-        let rd = FsPager::new(&self.root, f, args.limit());
+        let rd = FsPager::new(&self.root, f, args.limit(), args.metadata());
 
         Ok((RpList::default(), Some(rd)))
     }
@@ -702,4 +993,59 @@ mod tests {
             assert!(tmp_file.starts_with(expected_prefix));
         }
     }
+
+    #[test]
+    fn test_parse_mode() {
+        assert_eq!(parse_mode("file_mode", "644"), Some(0o644));
+        assert_eq!(parse_mode("file_mode", "0o755"), Some(0o755));
+        assert_eq!(parse_mode("file_mode", "999"), None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_file_and_dir_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = std::env::temp_dir().join(Uuid::new_v4().to_string());
+        let mut builder = FsBuilder::default();
+        builder
+            .root(&root.to_string_lossy())
+            .file_mode(0o600)
+            .dir_mode(0o700)
+            .fsync_on_close(false);
+        let op = Operator::new(builder).unwrap().finish();
+
+        op.write("dir/file", "hello").await.unwrap();
+
+        let meta = std::fs::metadata(root.join("dir")).unwrap();
+        assert_eq!(meta.permissions().mode() & 0o777, 0o700);
+        let meta = std::fs::metadata(root.join("dir/file")).unwrap();
+        assert_eq!(meta.permissions().mode() & 0o777, 0o600);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_atomic_write() {
+        let root = std::env::temp_dir().join(Uuid::new_v4().to_string());
+        let mut builder = FsBuilder::default();
+        builder.root(&root.to_string_lossy()).atomic_write(true);
+        let op = Operator::new(builder).unwrap().finish();
+
+        let mut w = op.writer("dir/file").await.unwrap();
+        w.write("hello").await.unwrap();
+        // The file should not be visible before close.
+        assert!(!root.join("dir/file").exists());
+        w.close().await.unwrap();
+        assert_eq!(std::fs::read(root.join("dir/file")).unwrap(), b"hello");
+
+        let mut w = op.writer("dir/file").await.unwrap();
+        w.write("world").await.unwrap();
+        w.abort().await.unwrap();
+        // Abort should keep the old content and remove the temp file.
+        assert_eq!(std::fs::read(root.join("dir/file")).unwrap(), b"hello");
+        assert_eq!(std::fs::read_dir(root.join("dir")).unwrap().count(), 1);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
 }
@@ -21,4 +21,5 @@ pub use backend::FsBuilder as Fs;
 mod appender;
 mod error;
 mod pager;
+mod user_metadata;
 mod writer;
@@ -19,6 +19,7 @@ use std::path::Path;
 use std::path::PathBuf;
 
 use async_trait::async_trait;
+use chrono::DateTime;
 
 use super::error::parse_io_error;
 use crate::raw::*;
@@ -31,14 +32,17 @@ pub struct FsPager<P> {
 
     size: usize,
     rd: P,
+    /// Whether to fetch content length and last modified for files.
+    metadata: bool,
 }
 
 impl<P> FsPager<P> {
-    pub fn new(root: &Path, rd: P, limit: Option<usize>) -> Self {
+    pub fn new(root: &Path, rd: P, limit: Option<usize>, metadata: bool) -> Self {
         Self {
             root: root.to_owned(),
             size: limit.unwrap_or(1000),
             rd,
+            metadata,
         }
     }
 }
@@ -70,7 +74,15 @@ impl oio::Page for FsPager<tokio::fs::ReadDir> {
             let file_type = de.file_type().await.map_err(parse_io_error)?;
 
             let d = if file_type.is_file() {
-                oio::Entry::new(&rel_path, Metadata::new(EntryMode::FILE))
+                let mut meta = Metadata::new(EntryMode::FILE);
+                if self.metadata {
+                    let fm = de.metadata().await.map_err(parse_io_error)?;
+                    meta.set_content_length(fm.len());
+                    meta.set_last_modified(
+                        fm.modified().map(DateTime::from).map_err(parse_io_error)?,
+                    );
+                }
+                oio::Entry::new(&rel_path, meta)
             } else if file_type.is_dir() {
                 // Make sure we are returning the correct path.
                 oio::Entry::new(&format!("{rel_path}/"), Metadata::new(EntryMode::DIR))
@@ -111,7 +123,15 @@ impl oio::BlockingPage for FsPager<std::fs::ReadDir> {
             let file_type = de.file_type().map_err(parse_io_error)?;
 
             let d = if file_type.is_file() {
-                oio::Entry::new(&rel_path, Metadata::new(EntryMode::FILE))
+                let mut meta = Metadata::new(EntryMode::FILE);
+                if self.metadata {
+                    let fm = de.metadata().map_err(parse_io_error)?;
+                    meta.set_content_length(fm.len());
+                    meta.set_last_modified(
+                        fm.modified().map(DateTime::from).map_err(parse_io_error)?,
+                    );
+                }
+                oio::Entry::new(&rel_path, meta)
             } else if file_type.is_dir() {
                 // Make sure we are returning the correct path.
                 oio::Entry::new(&format!("{rel_path}/"), Metadata::new(EntryMode::DIR))
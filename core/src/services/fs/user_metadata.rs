@@ -0,0 +1,155 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Map user metadata to `user.*` extended attributes of files.
+//!
+//! Not all filesystems support xattr, we will ignore the user metadata
+//! silently if xattr is not supported, and warn on other errors instead of
+//! failing the whole operation.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use log::warn;
+
+/// The namespace of xattr that can be set by users without privilege.
+#[cfg(unix)]
+const USER_XATTR_PREFIX: &str = "user.";
+
+/// Check if the error is returned because the filesystem doesn't support
+/// xattr.
+#[cfg(unix)]
+fn is_unsupported(err: &std::io::Error) -> bool {
+    match err.raw_os_error() {
+        Some(v) => v == libc::ENOTSUP || v == libc::EOPNOTSUPP,
+        None => err.kind() == std::io::ErrorKind::Unsupported,
+    }
+}
+
+/// Replace the user metadata of file with given.
+///
+/// xattr calls are blocking, so they will be made in a blocking thread.
+pub async fn set_user_metadata(path: PathBuf, data: HashMap<String, String>) {
+    let res = tokio::task::spawn_blocking(move || blocking_set_user_metadata(&path, &data)).await;
+    if let Err(err) = res {
+        warn!("fs failed to set user metadata: {err:?}");
+    }
+}
+
+/// Get the user metadata of file.
+///
+/// xattr calls are blocking, so they will be made in a blocking thread.
+pub async fn get_user_metadata(path: PathBuf) -> Option<HashMap<String, String>> {
+    match tokio::task::spawn_blocking(move || blocking_get_user_metadata(&path)).await {
+        Ok(data) => data,
+        Err(err) => {
+            warn!("fs failed to get user metadata: {err:?}");
+            None
+        }
+    }
+}
+
+/// Replace the user metadata of file with given.
+///
+/// Passing an empty map will clean up the old user metadata.
+#[cfg(unix)]
+pub fn blocking_set_user_metadata(path: &Path, data: &HashMap<String, String>) {
+    // A write replaces the whole file, so the old user metadata must be
+    // cleaned up first.
+    let names = match xattr::list(path) {
+        Ok(names) => names,
+        Err(err) if is_unsupported(&err) => return,
+        Err(err) => {
+            warn!("fs failed to list xattr of {path:?}, user metadata is ignored: {err:?}");
+            return;
+        }
+    };
+    for name in names {
+        let is_user = name
+            .to_str()
+            .map(|v| v.starts_with(USER_XATTR_PREFIX))
+            .unwrap_or_default();
+        if is_user {
+            if let Err(err) = xattr::remove(path, &name) {
+                warn!("fs failed to remove xattr {name:?} of {path:?}: {err:?}");
+            }
+        }
+    }
+
+    for (key, value) in data {
+        let name = format!("{USER_XATTR_PREFIX}{key}");
+        match xattr::set(path, &name, value.as_bytes()) {
+            Ok(()) => {}
+            Err(err) if is_unsupported(&err) => return,
+            Err(err) => warn!("fs failed to set xattr {name} of {path:?}: {err:?}"),
+        }
+    }
+}
+
+/// Replace the user metadata of file with given.
+#[cfg(not(unix))]
+pub fn blocking_set_user_metadata(path: &Path, data: &HashMap<String, String>) {
+    if !data.is_empty() {
+        warn!("fs doesn't support xattr on this platform, user metadata of {path:?} is ignored");
+    }
+}
+
+/// Get the user metadata of file.
+///
+/// Returns `None` if the xattr of file can't be read.
+#[cfg(unix)]
+pub fn blocking_get_user_metadata(path: &Path) -> Option<HashMap<String, String>> {
+    let names = match xattr::list(path) {
+        Ok(names) => names,
+        Err(err) if is_unsupported(&err) => return None,
+        Err(err) => {
+            warn!("fs failed to list xattr of {path:?}: {err:?}");
+            return None;
+        }
+    };
+
+    let mut data = HashMap::new();
+    for name in names {
+        let Some(key) = name
+            .to_str()
+            .and_then(|v| v.strip_prefix(USER_XATTR_PREFIX))
+        else {
+            continue;
+        };
+        match xattr::get(path, &name) {
+            Ok(Some(value)) => {
+                data.insert(
+                    key.to_string(),
+                    String::from_utf8_lossy(&value).into_owned(),
+                );
+            }
+            // The xattr could be removed after listed.
+            Ok(None) => {}
+            Err(err) => warn!("fs failed to get xattr {name:?} of {path:?}: {err:?}"),
+        }
+    }
+
+    Some(data)
+}
+
+/// Get the user metadata of file.
+#[cfg(not(unix))]
+pub fn blocking_get_user_metadata(path: &Path) -> Option<HashMap<String, String>> {
+    let _ = path;
+    None
+}
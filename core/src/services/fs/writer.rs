@@ -35,6 +35,8 @@ pub struct FsWriter<F> {
     tmp_path: Option<PathBuf>,
     f: F,
     pos: u64,
+    fsync: bool,
+    create_new: bool,
 }
 
 impl<F> FsWriter<F> {
@@ -44,8 +46,29 @@ impl<F> FsWriter<F> {
             tmp_path,
             f,
             pos: 0,
+            fsync: true,
+            create_new: false,
         }
     }
+
+    /// Start writing at given offset instead of the beginning of file.
+    pub fn with_offset(mut self, offset: u64) -> Self {
+        self.pos = offset;
+        self
+    }
+
+    /// Set whether to call `fsync` before `close` returns.
+    pub fn with_fsync(mut self, fsync: bool) -> Self {
+        self.fsync = fsync;
+        self
+    }
+
+    /// Set whether to fail if the target file exists while moving the
+    /// temp file into place.
+    pub fn with_create_new(mut self, create_new: bool) -> Self {
+        self.create_new = create_new;
+        self
+    }
 }
 
 #[async_trait]
@@ -79,23 +102,46 @@ impl oio::Write for FsWriter<tokio::fs::File> {
         Ok(())
     }
 
+    /// Abort is only supported for atomic write, the temp file will be
+    /// removed so the target file is untouched.
     async fn abort(&mut self) -> Result<()> {
-        Err(Error::new(
-            ErrorKind::Unsupported,
-            "output writer doesn't support abort",
-        ))
+        match &self.tmp_path {
+            Some(tmp_path) => tokio::fs::remove_file(tmp_path)
+                .await
+                .map_err(parse_io_error),
+            None => Err(Error::new(
+                ErrorKind::Unsupported,
+                "output writer doesn't support abort",
+            )),
+        }
     }
 
     async fn close(&mut self) -> Result<()> {
-        self.f.sync_all().await.map_err(parse_io_error)?;
+        let res = if self.fsync {
+            self.f.sync_all().await
+        } else {
+            self.f.flush().await
+        };
 
         if let Some(tmp_path) = &self.tmp_path {
-            tokio::fs::rename(tmp_path, &self.target_path)
-                .await
-                .map_err(parse_io_error)?;
+            let res = match res {
+                // `hard_link` fails if the target exists, so no one else
+                // could be overwritten.
+                Ok(()) if self.create_new => {
+                    tokio::fs::hard_link(tmp_path, &self.target_path).await
+                }
+                Ok(()) => tokio::fs::rename(tmp_path, &self.target_path).await,
+                Err(err) => Err(err),
+            };
+            // Clean up the temp file if we failed to move it into place or
+            // it has been linked.
+            if res.is_err() || self.create_new {
+                let _ = tokio::fs::remove_file(tmp_path).await;
+            }
+            res.map_err(parse_io_error)
+        } else {
+            res.map_err(parse_io_error)
         }
-
-        Ok(())
     }
 }
 
@@ -115,12 +161,28 @@ impl oio::BlockingWrite for FsWriter<std::fs::File> {
     }
 
     fn close(&mut self) -> Result<()> {
-        self.f.sync_all().map_err(parse_io_error)?;
+        let res = if self.fsync {
+            self.f.sync_all()
+        } else {
+            Ok(())
+        };
 
         if let Some(tmp_path) = &self.tmp_path {
-            std::fs::rename(tmp_path, &self.target_path).map_err(parse_io_error)?;
+            let res = res.and_then(|_| {
+                if self.create_new {
+                    std::fs::hard_link(tmp_path, &self.target_path)
+                } else {
+                    std::fs::rename(tmp_path, &self.target_path)
+                }
+            });
+            // Clean up the temp file if we failed to move it into place or
+            // it has been linked.
+            if res.is_err() || self.create_new {
+                let _ = std::fs::remove_file(tmp_path);
+            }
+            res.map_err(parse_io_error)
+        } else {
+            res.map_err(parse_io_error)
         }
-
-        Ok(())
     }
 }
@@ -21,6 +21,7 @@ use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::str;
 use std::str::FromStr;
+use std::time::Duration;
 
 use async_tls::TlsConnector;
 use async_trait::async_trait;
@@ -28,6 +29,7 @@ use bb8::PooledConnection;
 use bb8::RunError;
 use futures::AsyncRead;
 use futures::AsyncReadExt;
+use futures::Future;
 use http::Uri;
 use log::debug;
 use suppaftp::list::File;
@@ -35,9 +37,12 @@ use suppaftp::types::FileType;
 use suppaftp::types::Response;
 use suppaftp::FtpError;
 use suppaftp::FtpStream;
+use suppaftp::Mode;
 use suppaftp::Status;
 use tokio::sync::OnceCell;
 
+use super::err::new_data_channel_error;
+use super::err::new_data_channel_timeout_error;
 use super::pager::FtpPager;
 use super::util::FtpReader;
 use super::writer::FtpWriter;
@@ -52,6 +57,39 @@ pub struct FtpBuilder {
     root: Option<String>,
     user: Option<String>,
     password: Option<String>,
+    passive: Option<bool>,
+    tls: Option<FtpTlsMode>,
+    timeout: Option<Duration>,
+    /// The first invalid config value, reported by `validate`.
+    invalid_config: Option<(&'static str, String)>,
+}
+
+/// TLS mode used by [`FtpBuilder`] to connect with server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FtpTlsMode {
+    /// Plain FTP without TLS.
+    None,
+    /// Explicit FTPS: connect in plain text and upgrade via `AUTH TLS`.
+    Explicit,
+    /// Implicit FTPS: establish TLS right after the connection is
+    /// created, usually on port 990.
+    Implicit,
+}
+
+impl FromStr for FtpTlsMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(FtpTlsMode::None),
+            "explicit" => Ok(FtpTlsMode::Explicit),
+            "implicit" => Ok(FtpTlsMode::Implicit),
+            _ => {
+                Err(Error::new(ErrorKind::ConfigInvalid, "unknown ftp tls mode")
+                    .with_context("tls", s))
+            }
+        }
+    }
 }
 
 impl Debug for FtpBuilder {
@@ -107,48 +145,118 @@ impl FtpBuilder {
 
         self
     }
-}
 
-impl Builder for FtpBuilder {
-    const SCHEME: Scheme = Scheme::Ftp;
-    type Accessor = FtpBackend;
+    /// set passive mode for ftp backend.
+    ///
+    /// Passive mode is enabled by default, which is required by most
+    /// servers behind NAT or firewalls. Set to `false` to use active mode.
+    pub fn passive(&mut self, passive: bool) -> &mut Self {
+        self.passive = Some(passive);
+        self
+    }
 
-    fn build(&mut self) -> Result<Self::Accessor> {
-        debug!("ftp backend build started: {:?}", &self);
-        let endpoint = match &self.endpoint {
-            None => return Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")),
-            Some(v) => v,
-        };
+    /// set tls mode for ftp backend.
+    ///
+    /// If not set, tls mode will be decided by the scheme of endpoint:
+    /// `ftp://` uses [`FtpTlsMode::None`] while `ftps://` or no scheme
+    /// uses [`FtpTlsMode::Explicit`].
+    pub fn tls(&mut self, tls: FtpTlsMode) -> &mut Self {
+        self.tls = Some(tls);
+        self
+    }
 
-        let endpoint_uri = match endpoint.parse::<Uri>() {
-            Err(e) => {
-                return Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is invalid")
-                    .with_context("endpoint", endpoint)
-                    .set_source(e));
-            }
-            Ok(uri) => uri,
-        };
+    /// set timeout for both control and data channel of ftp backend.
+    ///
+    /// Control channel timeout applies to establishing the connection.
+    /// Data channel timeout applies to opening the data stream, every write
+    /// into it, and every read that waits for data from it.
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
 
-        let host = endpoint_uri.host().unwrap_or("127.0.0.1");
-        let port = endpoint_uri.port_u16().unwrap_or(21);
+impl FtpBuilder {
+    /// Check the config values set via `from_map`.
+    fn check_invalid_config(&self) -> Result<()> {
+        if let Some((key, value)) = &self.invalid_config {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "config value is invalid")
+                    .with_operation("Builder::validate")
+                    .with_context("service", Scheme::Ftp)
+                    .with_context("key", *key)
+                    .with_context("value", value),
+            );
+        }
 
-        let endpoint = format!("{host}:{port}");
+        Ok(())
+    }
 
-        let enable_secure = match endpoint_uri.scheme_str() {
-            Some("ftp") => false,
+    /// Parse the endpoint into uri and the tls mode implied by its scheme.
+    fn parse_endpoint(&self) -> Result<(Uri, FtpTlsMode)> {
+        let endpoint = self.endpoint.as_deref().ok_or_else(|| {
+            Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+                .with_operation("Builder::validate")
+                .with_context("service", Scheme::Ftp)
+                .with_context("key", "endpoint")
+        })?;
+
+        let endpoint_uri = endpoint.parse::<Uri>().map_err(|e| {
+            Error::new(ErrorKind::ConfigInvalid, "endpoint is invalid")
+                .with_operation("Builder::validate")
+                .with_context("service", Scheme::Ftp)
+                .with_context("key", "endpoint")
+                .with_context("endpoint", endpoint)
+                .set_source(e)
+        })?;
+
+        let scheme_tls = match endpoint_uri.scheme_str() {
+            Some("ftp") => FtpTlsMode::None,
             // if the user forgot to add a scheme prefix
             // treat it as using secured scheme
-            Some("ftps") | None => true,
+            Some("ftps") | None => FtpTlsMode::Explicit,
 
             Some(s) => {
                 return Err(Error::new(
                     ErrorKind::ConfigInvalid,
                     "endpoint is unsupported or invalid",
                 )
+                .with_operation("Builder::validate")
+                .with_context("service", Scheme::Ftp)
+                .with_context("key", "endpoint")
                 .with_context("endpoint", s));
             }
         };
 
+        Ok((endpoint_uri, scheme_tls))
+    }
+}
+
+impl Builder for FtpBuilder {
+    const SCHEME: Scheme = Scheme::Ftp;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&[
+        "root", "endpoint", "user", "password", "passive", "tls", "timeout",
+    ]);
+    type Accessor = FtpBackend;
+
+    fn validate(&self) -> Result<()> {
+        self.check_invalid_config()?;
+        self.parse_endpoint().map(|_| ())
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        debug!("ftp backend build started: {:?}", &self);
+        // Same as validate, but keep the parsed endpoint.
+        self.check_invalid_config()?;
+        let (endpoint_uri, scheme_tls) = self.parse_endpoint()?;
+        let tls = self.tls.unwrap_or(scheme_tls);
+
+        let host = endpoint_uri.host().unwrap_or("127.0.0.1");
+        let default_port = if tls == FtpTlsMode::Implicit { 990 } else { 21 };
+        let port = endpoint_uri.port_u16().unwrap_or(default_port);
+
+        let endpoint = format!("{host}:{port}");
+
         let root = normalize_root(&self.root.take().unwrap_or_default());
 
         let user = match &self.user {
@@ -168,7 +276,9 @@ impl Builder for FtpBuilder {
             root,
             user,
             password,
-            enable_secure,
+            tls,
+            passive: self.passive.unwrap_or(true),
+            timeout: self.timeout,
             pool: OnceCell::new(),
         })
     }
@@ -180,6 +290,34 @@ impl Builder for FtpBuilder {
         map.get("endpoint").map(|v| builder.endpoint(v));
         map.get("user").map(|v| builder.user(v));
         map.get("password").map(|v| builder.password(v));
+        if let Some(v) = map.get("passive") {
+            match v.parse::<bool>() {
+                Ok(v) => {
+                    builder.passive(v);
+                }
+                Err(_) => builder.invalid_config = Some(("passive", v.clone())),
+            }
+        }
+        if let Some(v) = map.get("tls") {
+            match v.parse::<FtpTlsMode>() {
+                Ok(v) => {
+                    builder.tls(v);
+                }
+                Err(_) => {
+                    builder.invalid_config.get_or_insert(("tls", v.clone()));
+                }
+            }
+        }
+        if let Some(v) = map.get("timeout") {
+            match v.parse::<u64>() {
+                Ok(v) => {
+                    builder.timeout(Duration::from_secs(v));
+                }
+                Err(_) => {
+                    builder.invalid_config.get_or_insert(("timeout", v.clone()));
+                }
+            }
+        }
 
         builder
     }
@@ -190,7 +328,32 @@ pub struct Manager {
     root: String,
     user: String,
     password: String,
-    enable_secure: bool,
+    tls: FtpTlsMode,
+    passive: bool,
+    timeout: Option<Duration>,
+}
+
+impl Manager {
+    async fn open(&self) -> std::result::Result<FtpStream, FtpError> {
+        match self.tls {
+            FtpTlsMode::None => FtpStream::connect(&self.endpoint).await,
+            // switch to secure mode via `AUTH TLS`.
+            FtpTlsMode::Explicit => {
+                FtpStream::connect(&self.endpoint)
+                    .await?
+                    .into_secure(TlsConnector::default().into(), &self.endpoint)
+                    .await
+            }
+            FtpTlsMode::Implicit => {
+                FtpStream::connect_secure_implicit(
+                    &self.endpoint,
+                    TlsConnector::default().into(),
+                    &self.endpoint,
+                )
+                .await
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -199,16 +362,21 @@ impl bb8::ManageConnection for Manager {
     type Error = FtpError;
 
     async fn connect(&self) -> std::result::Result<Self::Connection, Self::Error> {
-        let stream = FtpStream::connect(&self.endpoint).await?;
+        let mut ftp_stream = match self.timeout {
+            Some(dur) => tokio::time::timeout(dur, self.open()).await.map_err(|_| {
+                FtpError::ConnectionError(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "connect to ftp server timeout",
+                ))
+            })??,
+            None => self.open().await?,
+        };
 
-        // switch to secure mode if ssl/tls is on.
-        let mut ftp_stream = if self.enable_secure {
-            stream
-                .into_secure(TlsConnector::default().into(), &self.endpoint)
-                .await?
+        ftp_stream.set_mode(if self.passive {
+            Mode::Passive
         } else {
-            stream
-        };
+            Mode::Active
+        });
 
         // login if needed
         if !self.user.is_empty() {
@@ -250,7 +418,9 @@ pub struct FtpBackend {
     root: String,
     user: String,
     password: String,
-    enable_secure: bool,
+    tls: FtpTlsMode,
+    passive: bool,
+    timeout: Option<Duration>,
     pool: OnceCell<bb8::Pool<Manager>>,
 }
 
@@ -327,28 +497,40 @@ impl Accessor for FtpBackend {
         let (r, size): (Box<dyn AsyncRead + Send + Unpin>, _) = match (br.offset(), br.size()) {
             (Some(offset), Some(size)) => {
                 ftp_stream.resume_transfer(offset as usize).await?;
-                let ds = ftp_stream.retr_as_stream(path).await?.take(size);
+                let ds = self
+                    .ftp_data_channel(ftp_stream.retr_as_stream(path))
+                    .await?
+                    .take(size);
                 (Box::new(ds), min(size, meta.size() as u64 - offset))
             }
             (Some(offset), None) => {
                 ftp_stream.resume_transfer(offset as usize).await?;
-                let ds = ftp_stream.retr_as_stream(path).await?;
+                let ds = self
+                    .ftp_data_channel(ftp_stream.retr_as_stream(path))
+                    .await?;
                 (Box::new(ds), meta.size() as u64 - offset)
             }
             (None, Some(size)) => {
                 ftp_stream
                     .resume_transfer((meta.size() as u64 - size) as usize)
                     .await?;
-                let ds = ftp_stream.retr_as_stream(path).await?;
+                let ds = self
+                    .ftp_data_channel(ftp_stream.retr_as_stream(path))
+                    .await?;
                 (Box::new(ds), size)
             }
             (None, None) => {
-                let ds = ftp_stream.retr_as_stream(path).await?;
+                let ds = self
+                    .ftp_data_channel(ftp_stream.retr_as_stream(path))
+                    .await?;
                 (Box::new(ds), meta.size() as u64)
             }
         };
 
-        Ok((RpRead::new(size), FtpReader::new(r, ftp_stream)))
+        Ok((
+            RpRead::new(size),
+            FtpReader::new(r, ftp_stream, self.timeout),
+        ))
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
@@ -436,7 +618,7 @@ impl Accessor for FtpBackend {
         let mut ftp_stream = self.ftp_connect(Operation::List).await?;
 
         let pathname = if path == "/" { None } else { Some(path) };
-        let files = ftp_stream.list(pathname).await?;
+        let files = self.ftp_data_channel(ftp_stream.list(pathname)).await?;
 
         Ok((
             RpList::default(),
@@ -457,20 +639,40 @@ impl FtpBackend {
                         root: self.root.to_string(),
                         user: self.user.to_string(),
                         password: self.password.to_string(),
-                        enable_secure: self.enable_secure,
+                        tls: self.tls,
+                        passive: self.passive,
+                        timeout: self.timeout,
                     })
                     .await
             })
             .await?;
 
         pool.get_owned().await.map_err(|err| match err {
-            RunError::User(err) => err.into(),
+            RunError::User(err) => Error::from(err).with_context("channel", "control"),
             RunError::TimedOut => {
                 Error::new(ErrorKind::Unexpected, "connection request: timeout").set_temporary()
             }
         })
     }
 
+    /// Run an operation that transfers data via the data channel.
+    ///
+    /// Errors returned will carry the `channel: data` context so that
+    /// users can tell them from control channel failures.
+    pub async fn ftp_data_channel<T, F>(&self, f: F) -> Result<T>
+    where
+        F: Future<Output = std::result::Result<T, FtpError>>,
+    {
+        let res = match self.timeout {
+            Some(dur) => tokio::time::timeout(dur, f)
+                .await
+                .map_err(|_| new_data_channel_timeout_error(dur))?,
+            None => f.await,
+        };
+
+        res.map_err(new_data_channel_error)
+    }
+
     async fn ftp_stat(&self, path: &str) -> Result<File> {
         let mut ftp_stream = self.ftp_connect(Operation::Stat).await?;
 
@@ -478,7 +680,7 @@ impl FtpBackend {
 
         let pathname = if parent == "/" { None } else { Some(parent) };
 
-        let resp = ftp_stream.list(pathname).await?;
+        let resp = self.ftp_data_channel(ftp_stream.list(pathname)).await?;
 
         // Get stat of file.
         let mut files = resp
@@ -500,7 +702,10 @@ impl FtpBackend {
 
 #[cfg(test)]
 mod build_test {
+    use std::collections::HashMap;
+
     use super::FtpBuilder;
+    use super::FtpTlsMode;
     use crate::*;
 
     #[test]
@@ -531,4 +736,46 @@ mod build_test {
         let e = b.unwrap_err();
         assert_eq!(e.kind(), ErrorKind::ConfigInvalid);
     }
+
+    #[test]
+    fn test_build_with_tls() {
+        // implicit tls, should suffix with default port 990
+        let mut builder = FtpBuilder::default();
+        builder
+            .endpoint("ftp_server.local")
+            .tls(FtpTlsMode::Implicit);
+        let b = builder.build().unwrap();
+        assert_eq!(b.endpoint, "ftp_server.local:990");
+        assert_eq!(b.tls, FtpTlsMode::Implicit);
+        assert!(b.passive);
+
+        // explicit tls takes precedence over scheme
+        let mut builder = FtpBuilder::default();
+        builder
+            .endpoint("ftp://ftp_server.local")
+            .tls(FtpTlsMode::Explicit)
+            .passive(false);
+        let b = builder.build().unwrap();
+        assert_eq!(b.endpoint, "ftp_server.local:21");
+        assert_eq!(b.tls, FtpTlsMode::Explicit);
+        assert!(!b.passive);
+
+        assert_eq!(
+            "IMPLICIT".parse::<FtpTlsMode>().unwrap(),
+            FtpTlsMode::Implicit
+        );
+        assert!("tls".parse::<FtpTlsMode>().is_err());
+    }
+
+    #[test]
+    fn test_from_map_with_invalid_value() {
+        for (key, value) in [("tls", "tls"), ("timeout", "10s"), ("passive", "yes")] {
+            let mut builder = FtpBuilder::from_map(HashMap::from([
+                ("endpoint".to_string(), "ftp_server.local".to_string()),
+                (key.to_string(), value.to_string()),
+            ]));
+            let err = builder.build().unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        }
+    }
 }
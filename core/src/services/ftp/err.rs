@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::time::Duration;
+
 use suppaftp::FtpError;
 use suppaftp::Status;
 
@@ -47,3 +49,16 @@ impl From<FtpError> for Error {
         err
     }
 }
+
+/// Create a new error happened on the data channel.
+pub(super) fn new_data_channel_error(e: FtpError) -> Error {
+    Error::from(e).with_context("channel", "data")
+}
+
+/// Create a new error for data channel operations that timed out.
+pub(super) fn new_data_channel_timeout_error(dur: Duration) -> Error {
+    Error::new(ErrorKind::Unexpected, "ftp data channel timeout")
+        .with_context("channel", "data")
+        .with_context("timeout", format!("{dur:?}"))
+        .set_temporary()
+}
@@ -17,6 +17,7 @@
 
 mod backend;
 pub use backend::FtpBuilder as Ftp;
+pub use backend::FtpTlsMode;
 
 mod err;
 mod pager;
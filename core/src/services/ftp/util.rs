@@ -20,14 +20,17 @@ use std::pin::Pin;
 use std::task::ready;
 use std::task::Context;
 use std::task::Poll;
+use std::time::Duration;
 
 use bb8::PooledConnection;
 use futures::future::BoxFuture;
 use futures::AsyncRead;
 use futures::FutureExt;
 use suppaftp::Status;
+use tokio::time::Sleep;
 
 use super::backend::Manager;
+use super::err::new_data_channel_timeout_error;
 use crate::raw::*;
 use crate::*;
 
@@ -35,6 +38,10 @@ use crate::*;
 pub struct FtpReader {
     reader: Box<dyn AsyncRead + Send + Unpin>,
     state: State,
+
+    /// The data channel timeout, applied to every read that waits for data.
+    timeout: Option<Duration>,
+    sleep: Option<Pin<Box<Sleep>>>,
 }
 
 unsafe impl Sync for FtpReader {}
@@ -49,10 +56,40 @@ impl FtpReader {
     pub fn new(
         r: Box<dyn AsyncRead + Send + Unpin>,
         c: PooledConnection<'static, Manager>,
+        timeout: Option<Duration>,
     ) -> Self {
         Self {
             reader: r,
             state: State::Reading(Some(c)),
+            timeout,
+            sleep: None,
+        }
+    }
+
+    /// Check whether the data channel has been idle for longer than timeout.
+    ///
+    /// The timer starts at the first pending poll and is reset once the
+    /// poll is ready.
+    fn poll_timeout<T>(&mut self, cx: &mut Context<'_>, poll: &Poll<T>) -> Result<()> {
+        let dur = match self.timeout {
+            Some(dur) => dur,
+            None => return Ok(()),
+        };
+
+        if poll.is_ready() {
+            self.sleep = None;
+            return Ok(());
+        }
+
+        let sleep = self
+            .sleep
+            .get_or_insert_with(|| Box::pin(tokio::time::sleep(dur)));
+        match sleep.poll_unpin(cx) {
+            Poll::Ready(()) => {
+                self.sleep = None;
+                Err(new_data_channel_timeout_error(dur))
+            }
+            Poll::Pending => Ok(()),
         }
     }
 }
@@ -60,6 +97,9 @@ impl FtpReader {
 impl oio::Read for FtpReader {
     fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
         let data = Pin::new(&mut self.reader).poll_read(cx, buf);
+        if let State::Reading(_) = self.state {
+            self.poll_timeout(cx, &data)?;
+        }
 
         match &mut self.state {
             // Reading state, try to poll some data.
@@ -96,10 +136,15 @@ impl oio::Read for FtpReader {
             }
 
             // Finalize state, wait for finalization of stream.
-            State::Finalize(fut) => match ready!(Pin::new(fut).poll_unpin(cx)) {
-                Ok(_) => Poll::Ready(Ok(0)),
-                Err(e) => Poll::Ready(Err(e)),
-            },
+            State::Finalize(fut) => {
+                let res = fut.poll_unpin(cx);
+                self.poll_timeout(cx, &res)?;
+
+                match ready!(res) {
+                    Ok(_) => Poll::Ready(Ok(0)),
+                    Err(e) => Poll::Ready(Err(e)),
+                }
+            }
         }
     }
 
@@ -18,6 +18,7 @@
 use async_trait::async_trait;
 use bytes::Bytes;
 use futures::AsyncWriteExt;
+use suppaftp::FtpError;
 
 use super::backend::FtpBackend;
 use crate::raw::*;
@@ -43,12 +44,22 @@ impl FtpWriter {
 impl oio::Write for FtpWriter {
     async fn write(&mut self, bs: Bytes) -> Result<()> {
         let mut ftp_stream = self.backend.ftp_connect(Operation::Write).await?;
-        let mut data_stream = ftp_stream.append_with_stream(&self.path).await?;
-        data_stream.write_all(&bs).await.map_err(|err| {
-            Error::new(ErrorKind::Unexpected, "copy from ftp stream").set_source(err)
-        })?;
+        let mut data_stream = self
+            .backend
+            .ftp_data_channel(ftp_stream.append_with_stream(&self.path))
+            .await?;
+        self.backend
+            .ftp_data_channel(async {
+                data_stream
+                    .write_all(&bs)
+                    .await
+                    .map_err(FtpError::ConnectionError)
+            })
+            .await?;
 
-        ftp_stream.finalize_put_stream(data_stream).await?;
+        self.backend
+            .ftp_data_channel(ftp_stream.finalize_put_stream(data_stream))
+            .await?;
 
         Ok(())
     }
@@ -21,6 +21,8 @@ use std::fmt::Formatter;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use base64::engine::general_purpose;
+use base64::Engine;
 use http::header::HOST;
 use http::StatusCode;
 use log::debug;
@@ -32,6 +34,7 @@ use serde::Deserialize;
 use serde_json;
 
 use super::core::GcsCore;
+use super::credential::CredentialProviderLoad;
 use super::error::parse_error;
 use super::pager::GcsPager;
 use super::writer::GcsWriter;
@@ -65,12 +68,32 @@ const DEFAULT_WRITE_FIXED_SIZE: usize = 8 * 1024 * 1024;
 /// - `root`: Set the work directory for backend
 /// - `bucket`: Set the container name for backend
 /// - `endpoint`: Customizable endpoint setting
-/// - `credentials`: Credential string for GCS OAuth2
+/// - `credential`: Service account credential for GCS OAuth2, could be
+///   the JSON content or base64 encoded JSON content
+/// - `credential_path`: Path to the service account credential JSON file
+/// - `service_account`: Service account used to fetch token from VM metadata
 /// - `predefined_acl`: Predefined ACL for GCS
 /// - `default_storage_class`: Default storage class for GCS
 ///
 /// You can refer to [`GcsBuilder`]'s docs for more information
 ///
+/// # Credential
+///
+/// Credential will be loaded in the following order, the first found wins:
+///
+/// 1. customed token loader set via [`GcsBuilder::customed_token_loader`]
+///    or [`GcsBuilder::credential_provider`] (only one of them can be set)
+/// 2. `credential` set via [`GcsBuilder::credential`]
+/// 3. `credential_path` set via [`GcsBuilder::credential_path`]
+/// 4. file pointed by env `GOOGLE_APPLICATION_CREDENTIALS`
+/// 5. gcloud's well-known credential file
+/// 6. token fetched from GCE/GKE metadata server (workload identity) with
+///    the `service_account` set via [`GcsBuilder::service_account`] or
+///    `default`
+///
+/// Tokens are cached and refreshed automatically before expiry, so it's
+/// safe to run on GKE without baking long-lived keys into the image.
+///
 /// # Example
 ///
 /// ## Via Builder
@@ -122,6 +145,7 @@ pub struct GcsBuilder {
 
     http_client: Option<HttpClient>,
     customed_token_loader: Option<Box<dyn GoogleTokenLoad>>,
+    credential_provider: Option<Box<dyn CredentialProvider<String>>>,
     predefined_acl: Option<String>,
     default_storage_class: Option<String>,
 
@@ -182,7 +206,10 @@ impl GcsBuilder {
         self
     }
 
-    /// set the base64 hashed credentials string used for OAuth2
+    /// set the credentials string used for OAuth2.
+    ///
+    /// Both the service account JSON content and the base64 encoded
+    /// JSON content are accepted.
     pub fn credential(&mut self, credential: &str) -> &mut Self {
         if !credential.is_empty() {
             self.credential = Some(credential.to_string())
@@ -191,6 +218,8 @@ impl GcsBuilder {
     }
 
     /// set the credentials path of GCS.
+    ///
+    /// The path should point to a service account JSON file.
     pub fn credential_path(&mut self, path: &str) -> &mut Self {
         if !path.is_empty() {
             self.credential_path = Some(path.to_string())
@@ -215,6 +244,22 @@ impl GcsBuilder {
         self
     }
 
+    /// Use given [`CredentialProvider`] to load OAuth access tokens for
+    /// service.
+    ///
+    /// Tokens returned by the provider will be cached until they are about
+    /// to expire. [`GcpMetadataCredentialProvider`] is available as a
+    /// built-in provider.
+    ///
+    /// This can't be used together with [`GcsBuilder::customed_token_loader`],
+    /// `build` will return a `ConfigInvalid` error if both of them are set.
+    ///
+    /// [`GcpMetadataCredentialProvider`]: crate::services::GcpMetadataCredentialProvider
+    pub fn credential_provider(&mut self, provider: impl CredentialProvider<String>) -> &mut Self {
+        self.credential_provider = Some(Box::new(provider));
+        self
+    }
+
     /// Set the predefined acl for GCS.
     ///
     /// Available values are:
@@ -276,6 +321,17 @@ impl Debug for GcsBuilder {
 
 impl Builder for GcsBuilder {
     const SCHEME: Scheme = Scheme::Gcs;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&[
+        "root",
+        "bucket",
+        "endpoint",
+        "credential",
+        "credential_path",
+        "service_account",
+        "scope",
+        "predefined_acl",
+        "default_storage_class",
+    ]);
     type Accessor = GcsBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
@@ -285,6 +341,10 @@ impl Builder for GcsBuilder {
         map.get("bucket").map(|v| builder.bucket(v));
         map.get("endpoint").map(|v| builder.endpoint(v));
         map.get("credential").map(|v| builder.credential(v));
+        map.get("credential_path")
+            .map(|v| builder.credential_path(v));
+        map.get("service_account")
+            .map(|v| builder.service_account(v));
         map.get("scope").map(|v| builder.scope(v));
         map.get("predefined_acl").map(|v| builder.predefined_acl(v));
         map.get("default_storage_class")
@@ -293,21 +353,40 @@ impl Builder for GcsBuilder {
         builder
     }
 
+    fn validate(&self) -> Result<()> {
+        if self.bucket.is_empty() {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "The bucket is misconfigured")
+                    .with_operation("Builder::validate")
+                    .with_context("service", Scheme::Gcs)
+                    .with_context("key", "bucket"),
+            );
+        }
+
+        let write_fixed_size = self.write_fixed_size.unwrap_or(DEFAULT_WRITE_FIXED_SIZE);
+        // GCS requires write must align with 256 KiB.
+        if write_fixed_size % (256 * 1024) != 0 {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "The write fixed buffer size is misconfigured",
+            )
+            .with_operation("Builder::validate")
+            .with_context("service", Scheme::Gcs)
+            .with_context("key", "write_fixed_size")
+            .with_context("write_fixed_size", write_fixed_size.to_string()));
+        }
+
+        Ok(())
+    }
+
     fn build(&mut self) -> Result<Self::Accessor> {
         debug!("backend build started: {:?}", self);
+        self.validate()?;
 
         let root = normalize_root(&self.root.take().unwrap_or_default());
         debug!("backend use root {}", root);
 
-        // Handle endpoint and bucket name
-        let bucket = match self.bucket.is_empty() {
-            false => Ok(&self.bucket),
-            true => Err(
-                Error::new(ErrorKind::ConfigInvalid, "The bucket is misconfigured")
-                    .with_operation("Builder::build")
-                    .with_context("service", Scheme::Gcs),
-            ),
-        }?;
+        let bucket = &self.bucket;
 
         // TODO: server side encryption
 
@@ -328,9 +407,23 @@ impl Builder for GcsBuilder {
 
         let mut cred_loader = GoogleCredentialLoader::default();
         if let Some(cred) = &self.credential {
-            cred_loader = cred_loader.with_content(cred);
+            // Accept raw JSON content by encoding it into base64.
+            let cred = if cred.trim_start().starts_with('{') {
+                general_purpose::STANDARD.encode(cred.trim())
+            } else {
+                cred.clone()
+            };
+            check_credential(
+                GoogleCredentialLoader::default().with_content(&cred),
+                "credential",
+            )?;
+            cred_loader = cred_loader.with_content(&cred);
         }
         if let Some(cred) = &self.credential_path {
+            check_credential(
+                GoogleCredentialLoader::default().with_path(cred),
+                "credential_path",
+            )?;
             cred_loader = cred_loader.with_path(cred);
         }
 
@@ -344,25 +437,40 @@ impl Builder for GcsBuilder {
         if let Some(account) = &self.service_account {
             token_loader = token_loader.with_service_account(account);
         }
-        if let Ok(Some(cred)) = cred_loader.load() {
-            token_loader = token_loader.with_credentials(cred)
+        match cred_loader.load() {
+            Ok(Some(cred)) => token_loader = token_loader.with_credentials(cred),
+            // Fallback to fetch token from VM metadata server.
+            Ok(None) => {}
+            // Credentials configured explicitly have been checked, errors
+            // here come from env or well-known location.
+            Err(err) => {
+                debug!("load credential failed, fallback to vm metadata: {err:?}")
+            }
         }
-        if let Some(loader) = self.customed_token_loader.take() {
-            token_loader = token_loader.with_customed_token_loader(loader)
+        match (
+            self.credential_provider.take(),
+            self.customed_token_loader.take(),
+        ) {
+            (Some(_), Some(_)) => {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "credential_provider can't be used together with customed_token_loader",
+                )
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Gcs))
+            }
+            (Some(provider), None) => {
+                token_loader = token_loader.with_customed_token_loader(Box::new(
+                    CredentialProviderLoad::new(provider, scope),
+                ))
+            }
+            (None, Some(loader)) => token_loader = token_loader.with_customed_token_loader(loader),
+            (None, None) => {}
         }
 
         let signer = GoogleSigner::new("storage");
 
         let write_fixed_size = self.write_fixed_size.unwrap_or(DEFAULT_WRITE_FIXED_SIZE);
-        // GCS requires write must align with 256 KiB.
-        if write_fixed_size % (256 * 1024) != 0 {
-            return Err(Error::new(
-                ErrorKind::ConfigInvalid,
-                "The write fixed buffer size is misconfigured",
-            )
-            .with_context("service", Scheme::Gcs)
-            .with_context("write_fixed_size", write_fixed_size.to_string()));
-        }
 
         let backend = GcsBackend {
             core: Arc::new(GcsCore {
@@ -383,6 +491,30 @@ impl Builder for GcsBuilder {
     }
 }
 
+/// Make sure the credential configured explicitly could be loaded.
+///
+/// reqsign skips the credential sources that fail to load and tries the
+/// next one, so an invalid credential would fall back to env or the VM
+/// metadata server silently. Load it alone to report the error instead.
+fn check_credential(loader: GoogleCredentialLoader, key: &'static str) -> Result<()> {
+    let res = loader
+        .with_disable_env()
+        .with_disable_well_known_location()
+        .load();
+
+    let err = match res {
+        Ok(Some(_)) => return Ok(()),
+        Ok(None) => Error::new(ErrorKind::ConfigInvalid, "the credential is invalid"),
+        Err(err) => {
+            Error::new(ErrorKind::ConfigInvalid, "the credential is invalid").set_source(err)
+        }
+    };
+    Err(err
+        .with_operation("Builder::build")
+        .with_context("service", Scheme::Gcs)
+        .with_context("key", key))
+}
+
 /// GCS storage backend
 #[derive(Clone, Debug)]
 pub struct GcsBackend {
@@ -420,14 +552,21 @@ impl Accessor for GcsBackend {
                 write: true,
                 write_can_sink: true,
                 write_with_content_type: true,
+                write_with_content_encoding: true,
+                write_with_content_language: true,
+                write_with_storage_class: true,
                 write_without_content_length: true,
+                write_multi_min_size: Some(256 * 1024),
                 delete: true,
+                delete_with_version: true,
                 copy: true,
 
                 list: true,
                 list_with_limit: true,
                 list_with_start_after: true,
                 list_with_delimiter_slash: true,
+                list_with_metadata: true,
+                list_with_sorted: true,
                 list_without_delimiter: true,
 
                 batch: true,
@@ -443,9 +582,12 @@ impl Accessor for GcsBackend {
     }
 
     async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
-        let mut req = self
-            .core
-            .gcs_insert_object_request(path, Some(0), None, AsyncBody::Empty)?;
+        let mut req = self.core.gcs_insert_object_request(
+            path,
+            Some(0),
+            &OpWrite::default(),
+            AsyncBody::Empty,
+        )?;
 
         self.core.sign(&mut req).await?;
 
@@ -474,6 +616,17 @@ impl Accessor for GcsBackend {
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        if let Some(chunk) = args.chunk() {
+            if chunk % (256 * 1024) != 0 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "chunk must be multiple of 256 KiB",
+                )
+                .with_operation(Operation::Write)
+                .with_context("chunk", chunk.to_string()));
+            }
+        }
+
         Ok((
             RpWrite::default(),
             GcsWriter::new(self.core.clone(), path, args),
@@ -518,6 +671,9 @@ impl Accessor for GcsBackend {
 
             m.set_etag(&meta.etag);
             m.set_content_md5(&meta.md5_hash);
+            if !meta.crc32c.is_empty() {
+                m.set_checksum_crc32c(&meta.crc32c);
+            }
 
             let size = meta
                 .size
@@ -527,6 +683,16 @@ impl Accessor for GcsBackend {
             if !meta.content_type.is_empty() {
                 m.set_content_type(&meta.content_type);
             }
+            if !meta.content_encoding.is_empty() {
+                m.set_content_encoding(&meta.content_encoding);
+            }
+            if !meta.content_language.is_empty() {
+                m.set_content_language(&meta.content_language);
+            }
+
+            if !meta.storage_class.is_empty() {
+                m.set_storage_class(&meta.storage_class);
+            }
 
             m.set_last_modified(parse_datetime_from_rfc3339(&meta.updated)?);
 
@@ -538,8 +704,8 @@ impl Accessor for GcsBackend {
         }
     }
 
-    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
-        let resp = self.core.gcs_delete_object(path).await?;
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let resp = self.core.gcs_delete_object(path, args.version()).await?;
 
         // deleting not existing objects is ok
         if resp.status().is_success() || resp.status() == StatusCode::NOT_FOUND {
@@ -680,16 +846,86 @@ struct GetObjectJsonResponse {
     ///
     /// For example: `"md5Hash": "fHcEH1vPwA6eTPqxuasXcg=="`
     md5_hash: String,
+    /// Base64 encoded big-endian CRC32C of the content.
+    ///
+    /// For example: `"crc32c": "j/un9g=="`
+    crc32c: String,
     /// Content type of this object.
     ///
     /// For example: `"contentType": "image/png",`
     content_type: String,
+    /// Content encoding of this object.
+    ///
+    /// For example: `"contentEncoding": "gzip",`
+    content_encoding: String,
+    /// Content language of this object.
+    ///
+    /// For example: `"contentLanguage": "en",`
+    content_language: String,
+    /// Storage class of this object.
+    ///
+    /// For example: `"storageClass": "STANDARD",`
+    storage_class: String,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_invalid_credential() {
+        let mut builder = GcsBuilder::default();
+        builder.bucket("test").credential(
+            r#"{"type": "service_account", "private_key": "key", "client_email": "test@example.com"}"#,
+        );
+        assert!(builder.build().is_ok());
+
+        let mut builder = GcsBuilder::default();
+        builder.bucket("test").credential("{\"type\": \"unknown\"}");
+        let err = builder.build().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        let mut builder = GcsBuilder::default();
+        builder.bucket("test").credential("not base64 content");
+        let err = builder.build().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        let mut builder = GcsBuilder::default();
+        builder
+            .bucket("test")
+            .credential_path("/path/to/not_exist_credential.json");
+        let err = builder.build().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
+    #[test]
+    fn test_credential_provider_conflicts_with_token_loader() {
+        #[derive(Debug)]
+        struct StaticProvider;
+
+        #[async_trait]
+        impl CredentialProvider<String> for StaticProvider {
+            async fn get_credential(&self) -> Result<Option<Credential<String>>> {
+                Ok(Some(Credential::new("token".to_string())))
+            }
+        }
+
+        let mut builder = GcsBuilder::default();
+        builder.bucket("test").credential_provider(StaticProvider);
+        assert!(builder.build().is_ok());
+
+        let mut builder = GcsBuilder::default();
+        builder
+            .bucket("test")
+            .credential_provider(StaticProvider)
+            .customed_token_loader(Box::new(CredentialProviderLoad::new(
+                StaticProvider,
+                "scope",
+            )));
+        let err = builder.build().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
     #[test]
     fn test_deserialize_get_object_json_response() {
         let content = r#"{
@@ -718,7 +954,9 @@ mod tests {
         assert_eq!(meta.size, "56535");
         assert_eq!(meta.updated, "2022-08-15T11:33:34.866Z");
         assert_eq!(meta.md5_hash, "fHcEH1vPwA6eTPqxuasXcg==");
+        assert_eq!(meta.crc32c, "j/un9g==");
         assert_eq!(meta.etag, "CKWasoTgyPkCEAE=");
         assert_eq!(meta.content_type, "image/png");
+        assert_eq!(meta.storage_class, "STANDARD");
     }
 }
@@ -37,6 +37,7 @@ use reqsign::GoogleCredentialLoader;
 use reqsign::GoogleSigner;
 use reqsign::GoogleToken;
 use reqsign::GoogleTokenLoader;
+use serde::Serialize;
 
 use super::uri::percent_encode_path;
 use crate::raw::*;
@@ -208,19 +209,21 @@ impl GcsCore {
         &self,
         path: &str,
         size: Option<u64>,
-        content_type: Option<&str>,
+        args: &OpWrite,
         body: AsyncBody,
     ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
+        let content_type = args.content_type();
+        let metadata = self.insert_request_metadata(args);
 
         let mut url = format!(
             "{}/upload/storage/v1/b/{}/o?uploadType={}&name={}",
             self.endpoint,
             self.bucket,
-            if self.default_storage_class.is_some() {
-                "multipart"
-            } else {
+            if metadata.is_empty() {
                 "media"
+            } else {
+                "multipart"
             },
             percent_encode_path(&p)
         );
@@ -231,16 +234,16 @@ impl GcsCore {
 
         let mut req = Request::post(&url);
 
-        req = req.header(CONTENT_LENGTH, size.unwrap_or_default());
-
-        if let Some(storage_class) = &self.default_storage_class {
+        if !metadata.is_empty() {
             req = req.header(CONTENT_TYPE, "multipart/related; boundary=my-boundary");
 
+            let metadata = serde_json::to_string(&metadata).map_err(new_json_serialize_error)?;
+
             let mut req_body = BytesMut::with_capacity(100);
             write!(
                 &mut req_body,
-                "--my-boundary\nContent-Type: application/json; charset=UTF-8\n\n{{\"storageClass\": \"{}\"}}\n\n--my-boundary\n",
-                storage_class
+                "--my-boundary\nContent-Type: application/json; charset=UTF-8\n\n{}\n\n--my-boundary\n",
+                metadata
             ).unwrap();
 
             if let Some(mime) = content_type {
@@ -254,10 +257,15 @@ impl GcsCore {
             }
             write!(&mut req_body, "\n--my-boundary").unwrap();
 
+            // The multipart body is larger than the content itself.
+            req = req.header(CONTENT_LENGTH, req_body.len());
+
             let req_body = AsyncBody::Bytes(req_body.freeze());
             let req = req.body(req_body).map_err(new_request_build_error)?;
             Ok(req)
         } else {
+            req = req.header(CONTENT_LENGTH, size.unwrap_or_default());
+
             if let Some(content_type) = content_type {
                 req = req.header(CONTENT_TYPE, content_type);
             }
@@ -267,6 +275,17 @@ impl GcsCore {
         }
     }
 
+    /// Object metadata that can't be sent as headers of a media upload.
+    fn insert_request_metadata<'a>(&'a self, args: &'a OpWrite) -> InsertRequestMetadata<'a> {
+        InsertRequestMetadata {
+            storage_class: args
+                .storage_class()
+                .or(self.default_storage_class.as_deref()),
+            content_encoding: args.content_encoding(),
+            content_language: args.content_language(),
+        }
+    }
+
     // It's for presign operation. Gcs only supports query sign over XML API.
     pub fn gcs_insert_object_xml_request(
         &self,
@@ -370,22 +389,35 @@ impl GcsCore {
         self.send(req).await
     }
 
-    pub async fn gcs_delete_object(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
-        let mut req = self.gcs_delete_object_request(path)?;
+    pub async fn gcs_delete_object(
+        &self,
+        path: &str,
+        version: Option<&str>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = self.gcs_delete_object_request(path, version)?;
 
         self.sign(&mut req).await?;
         self.send(req).await
     }
 
-    pub fn gcs_delete_object_request(&self, path: &str) -> Result<Request<AsyncBody>> {
+    pub fn gcs_delete_object_request(
+        &self,
+        path: &str,
+        version: Option<&str>,
+    ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!(
+        let mut url = format!(
             "{}/storage/v1/b/{}/o/{}",
             self.endpoint,
             self.bucket,
             percent_encode_path(&p)
         );
+        // GCS uses the object generation as version.
+        if let Some(version) = version {
+            write!(url, "?generation={}", percent_encode_path(version))
+                .expect("write into string must succeed");
+        }
 
         Request::delete(&url)
             .body(AsyncBody::Empty)
@@ -401,7 +433,7 @@ impl GcsCore {
         let mut multipart = Multipart::new();
 
         for (idx, path) in paths.iter().enumerate() {
-            let req = self.gcs_delete_object_request(path)?;
+            let req = self.gcs_delete_object_request(path, None)?;
 
             multipart = multipart.part(
                 MixedPart::from_request(req).part_header("content-id".parse().unwrap(), idx.into()),
@@ -492,6 +524,7 @@ impl GcsCore {
     pub async fn gcs_initiate_resumable_upload(
         &self,
         path: &str,
+        args: &OpWrite,
     ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
         let url = format!(
@@ -499,10 +532,22 @@ impl GcsCore {
             self.endpoint, self.bucket, p
         );
 
-        let mut req = Request::post(&url)
-            .header(CONTENT_LENGTH, 0)
-            .body(AsyncBody::Empty)
-            .map_err(new_request_build_error)?;
+        let metadata = self.insert_request_metadata(args);
+
+        let mut req = if metadata.is_empty() {
+            Request::post(&url)
+                .header(CONTENT_LENGTH, 0)
+                .body(AsyncBody::Empty)
+        } else {
+            // Object metadata could be sent along with the initiate request.
+            let bs = serde_json::to_vec(&metadata).map_err(new_json_serialize_error)?;
+            let bs = Bytes::from(bs);
+            Request::post(&url)
+                .header(CONTENT_LENGTH, bs.len())
+                .header(CONTENT_TYPE, "application/json; charset=UTF-8")
+                .body(AsyncBody::Bytes(bs))
+        }
+        .map_err(new_request_build_error)?;
 
         self.sign(&mut req).await?;
         self.send(req).await
@@ -579,3 +624,24 @@ impl GcsCore {
         self.send(req).await
     }
 }
+
+/// Object metadata sent along with insert and resumable upload requests.
+#[derive(Default, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InsertRequestMetadata<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_class: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_encoding: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_language: Option<&'a str>,
+}
+
+impl InsertRequestMetadata<'_> {
+    /// Check if there is no metadata to send.
+    pub fn is_empty(&self) -> bool {
+        self.storage_class.is_none()
+            && self.content_encoding.is_none()
+            && self.content_language.is_none()
+    }
+}
@@ -0,0 +1,252 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Buf;
+use chrono::Duration;
+use chrono::Utc;
+use http::Request;
+use http::StatusCode;
+use reqsign::GoogleToken;
+use reqsign::GoogleTokenLoad;
+use serde::Deserialize;
+
+use super::uri::percent_encode_path;
+use crate::raw::*;
+use crate::*;
+
+const DEFAULT_METADATA_ENDPOINT: &str = "http://metadata.google.internal";
+
+/// Adapt a [`CredentialProvider`] of OAuth access tokens into reqsign's
+/// [`GoogleTokenLoad`].
+pub(super) struct CredentialProviderLoad {
+    provider: Arc<dyn CredentialProvider<String>>,
+    scope: String,
+}
+
+impl CredentialProviderLoad {
+    pub(super) fn new(provider: impl CredentialProvider<String>, scope: &str) -> Self {
+        Self {
+            provider: Arc::new(CachedCredentialProvider::new(provider)),
+            scope: scope.to_string(),
+        }
+    }
+}
+
+impl Debug for CredentialProviderLoad {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CredentialProviderLoad")
+            .field("provider", &"<redacted>")
+            .field("scope", &self.scope)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl GoogleTokenLoad for CredentialProviderLoad {
+    async fn load(&self, _: reqwest::Client) -> anyhow::Result<Option<GoogleToken>> {
+        let cred = match self.provider.get_credential().await? {
+            Some(cred) => cred,
+            None => return Ok(None),
+        };
+
+        // Tokens without expiry time are treated as valid for an hour which
+        // is the default lifetime of google OAuth access tokens.
+        let expires_in = match cred.expires_at() {
+            Some(expires_at) => (expires_at - Utc::now()).num_seconds().max(0) as usize,
+            None => 3600,
+        };
+        Ok(Some(GoogleToken::new(
+            cred.value(),
+            expires_in,
+            &self.scope,
+        )))
+    }
+}
+
+/// Load OAuth access tokens from the GCE metadata server.
+///
+/// # Example
+///
+/// ```no_run
+/// use opendal::raw::HttpClient;
+/// use opendal::services::GcpMetadataCredentialProvider;
+/// use opendal::services::Gcs;
+///
+/// # fn main() -> opendal::Result<()> {
+/// let mut builder = Gcs::default();
+/// builder.credential_provider(GcpMetadataCredentialProvider::new(HttpClient::new()?));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct GcpMetadataCredentialProvider {
+    client: HttpClient,
+    endpoint: String,
+    service_account: String,
+    scope: Option<String>,
+}
+
+impl Debug for GcpMetadataCredentialProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GcpMetadataCredentialProvider")
+            .field("endpoint", &self.endpoint)
+            .field("service_account", &self.service_account)
+            .field("scope", &self.scope)
+            .finish_non_exhaustive()
+    }
+}
+
+impl GcpMetadataCredentialProvider {
+    /// Create a new provider that loads tokens of the default service
+    /// account.
+    pub fn new(client: HttpClient) -> Self {
+        Self {
+            client,
+            endpoint: DEFAULT_METADATA_ENDPOINT.to_string(),
+            service_account: "default".to_string(),
+            scope: None,
+        }
+    }
+
+    /// Set the endpoint of the metadata server.
+    pub fn with_endpoint(mut self, endpoint: &str) -> Self {
+        self.endpoint = endpoint.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Set the service account to load tokens for.
+    pub fn with_service_account(mut self, service_account: &str) -> Self {
+        self.service_account = service_account.to_string();
+        self
+    }
+
+    /// Set the scope of the token.
+    pub fn with_scope(mut self, scope: &str) -> Self {
+        self.scope = Some(scope.to_string());
+        self
+    }
+}
+
+#[async_trait]
+impl CredentialProvider<String> for GcpMetadataCredentialProvider {
+    async fn get_credential(&self) -> Result<Option<Credential<String>>> {
+        let mut url = format!(
+            "{}/computeMetadata/v1/instance/service-accounts/{}/token",
+            self.endpoint,
+            percent_encode_path(&self.service_account)
+        );
+        if let Some(scope) = &self.scope {
+            url.push_str(&format!("?scopes={}", percent_encode_path(scope)));
+        }
+
+        let req = Request::get(&url)
+            .header("Metadata-Flavor", "Google")
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        let resp = self.client.send(req).await?;
+        let status = resp.status();
+        let bs = resp.into_body().bytes().await?;
+
+        match status {
+            StatusCode::OK => {}
+            StatusCode::NOT_FOUND => return Ok(None),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "metadata server returned unexpected status",
+                )
+                .with_operation("GcpMetadataCredentialProvider::get_credential")
+                .with_context("status", status.as_str())
+                .with_context("response", String::from_utf8_lossy(&bs))
+                .set_temporary())
+            }
+        }
+
+        let resp: MetadataTokenResponse =
+            serde_json::from_reader(bs.reader()).map_err(new_json_deserialize_error)?;
+        let expires_at = Utc::now() + Duration::seconds(resp.expires_in);
+        Ok(Some(
+            Credential::new(resp.access_token).with_expires_at(expires_at),
+        ))
+    }
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct MetadataTokenResponse {
+    access_token: String,
+    expires_in: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::matchers::header;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_metadata_credential_provider() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(
+                "/computeMetadata/v1/instance/service-accounts/default/token",
+            ))
+            .and(header("Metadata-Flavor", "Google"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"access_token":"access_token","expires_in":3599,"token_type":"Bearer"}"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let provider = GcpMetadataCredentialProvider::new(HttpClient::new()?)
+            .with_endpoint(&mock_server.uri());
+        let cred = provider
+            .get_credential()
+            .await?
+            .expect("must have credential");
+        assert_eq!(cred.value(), "access_token");
+        assert!(cred.expires_at().expect("must have expiry") > Utc::now());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_metadata_credential_provider_not_found() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+
+        let provider = GcpMetadataCredentialProvider::new(HttpClient::new()?)
+            .with_endpoint(&mock_server.uri())
+            .with_service_account("not-exist@example.com");
+        assert!(provider.get_credential().await?.is_none());
+
+        Ok(())
+    }
+}
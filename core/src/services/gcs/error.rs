@@ -54,12 +54,13 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     let (parts, body) = resp.into_parts();
     let bs = body.bytes().await?;
 
-    let (kind, retryable) = match parts.status {
+    let (mut kind, mut retryable) = match parts.status {
         StatusCode::NOT_FOUND => (ErrorKind::NotFound, false),
         StatusCode::FORBIDDEN => (ErrorKind::PermissionDenied, false),
         StatusCode::PRECONDITION_FAILED | StatusCode::NOT_MODIFIED => {
             (ErrorKind::ConditionNotMatch, false)
         }
+        StatusCode::TOO_MANY_REQUESTS => (ErrorKind::RateLimited, true),
         StatusCode::INTERNAL_SERVER_ERROR
         | StatusCode::BAD_GATEWAY
         | StatusCode::SERVICE_UNAVAILABLE
@@ -68,13 +69,28 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     };
 
     let message = match de::from_slice::<GcsErrorResponse>(&bs) {
-        Ok(gcs_err) => format!("{gcs_err:?}"),
+        Ok(gcs_err) => {
+            // GCS returns `403` or `429` with reasons like `rateLimitExceeded`
+            // while requests are throttled.
+            //
+            // ref: <https://cloud.google.com/storage/docs/json_api/v1/status-codes>
+            if gcs_err.error.errors.iter().any(|e| {
+                matches!(
+                    e.reason.as_str(),
+                    "rateLimitExceeded" | "userRateLimitExceeded"
+                )
+            }) {
+                (kind, retryable) = (ErrorKind::RateLimited, true);
+            }
+            format!("{gcs_err:?}")
+        }
         Err(_) => String::from_utf8_lossy(&bs).into_owned(),
     };
 
     let mut err = Error::new(kind, &message);
 
     err = with_error_response_context(err, parts);
+    err = with_error_response_body(err, &bs);
 
     if retryable {
         err = err.set_temporary();
@@ -85,11 +101,14 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
 
 #[cfg(test)]
 mod tests {
+    use bytes::Bytes;
+    use futures::stream;
+
     use super::*;
 
     #[test]
     fn test_parse_error() {
-        let bs = bytes::Bytes::from(
+        let bs = Bytes::from(
             r#"
 {
 "error": {
@@ -120,4 +139,28 @@ mod tests {
         assert_eq!(out.error.errors[0].location_type, "header");
         assert_eq!(out.error.errors[0].location, "Authorization");
     }
+
+    #[tokio::test]
+    async fn test_parse_error_rate_limited() -> Result<()> {
+        let body = IncomingAsyncBody::new(
+            Box::new(oio::into_stream(stream::iter(vec![Ok(Bytes::from(
+                r#"{"error":{"errors":[{"domain":"usageLimits","reason":"rateLimitExceeded","message":"Rate Limit Exceeded"}],"code":403,"message":"Rate Limit Exceeded"}}"#,
+            ))]))),
+            None,
+        );
+        let resp = Response::builder()
+            .status(StatusCode::FORBIDDEN)
+            .body(body)
+            .unwrap();
+
+        let err = parse_error(resp).await?;
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+        assert!(err.is_temporary());
+        assert_eq!(err.http_status(), Some(403));
+        assert!(err
+            .response_body()
+            .map_or(false, |v| v.contains("rateLimitExceeded")));
+
+        Ok(())
+    }
 }
@@ -19,6 +19,8 @@ mod backend;
 pub use backend::GcsBuilder as Gcs;
 
 mod core;
+mod credential;
+pub use credential::GcpMetadataCredentialProvider;
 mod error;
 mod pager;
 mod uri;
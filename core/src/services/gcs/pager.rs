@@ -125,6 +125,9 @@ impl oio::Page for GcsPager {
             // set metadata fields
             meta.set_content_md5(object.md5_hash.as_str());
             meta.set_etag(object.etag.as_str());
+            if !object.crc32c.is_empty() {
+                meta.set_checksum_crc32c(object.crc32c.as_str());
+            }
 
             let size = object.size.parse().map_err(|e| {
                 Error::new(ErrorKind::Unexpected, "parse u64 from list response").set_source(e)
@@ -170,6 +173,7 @@ struct ListResponseItem {
     // metadata
     etag: String,
     md5_hash: String,
+    crc32c: String,
     updated: String,
     content_type: String,
 }
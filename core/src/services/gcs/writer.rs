@@ -16,6 +16,7 @@
 // under the License.
 
 use std::sync::Arc;
+use std::time::Instant;
 
 use async_trait::async_trait;
 use bytes::Bytes;
@@ -39,7 +40,7 @@ pub struct GcsWriter {
 
 impl GcsWriter {
     pub fn new(core: Arc<GcsCore>, path: &str, op: OpWrite) -> Self {
-        let write_fixed_size = core.write_fixed_size;
+        let write_fixed_size = op.chunk().unwrap_or(core.write_fixed_size);
         GcsWriter {
             core,
             path: path.to_string(),
@@ -56,7 +57,7 @@ impl GcsWriter {
         let mut req = self.core.gcs_insert_object_request(
             &percent_encode_path(&self.path),
             Some(size),
-            self.op.content_type(),
+            &self.op,
             body,
         )?;
 
@@ -76,7 +77,10 @@ impl GcsWriter {
     }
 
     async fn initiate_upload(&self) -> Result<String> {
-        let resp = self.core.gcs_initiate_resumable_upload(&self.path).await?;
+        let resp = self
+            .core
+            .gcs_initiate_resumable_upload(&self.path, &self.op)
+            .await?;
         let status = resp.status();
 
         match status {
@@ -148,10 +152,19 @@ impl oio::Write for GcsWriter {
         }
 
         let bs = self.buffer.peak_exact(self.write_fixed_size);
+        let start = Instant::now();
 
         match self.write_part(location, bs).await {
             Ok(_) => {
                 self.buffer.take(self.write_fixed_size);
+                oio::report_part(
+                    self.op.part_callback(),
+                    (self.written / self.write_fixed_size as u64) as usize,
+                    self.write_fixed_size as u64,
+                    start,
+                    0,
+                    self.buffer.len() as u64,
+                );
                 self.written += self.write_fixed_size as u64;
                 Ok(())
             }
@@ -198,6 +211,8 @@ impl oio::Write for GcsWriter {
         };
 
         let bs = self.buffer.peak_exact(self.buffer.len());
+        let size = bs.len() as u64;
+        let start = Instant::now();
 
         let resp = self
             .core
@@ -209,8 +224,18 @@ impl oio::Write for GcsWriter {
             StatusCode::OK => {
                 resp.into_body().consume().await?;
 
-                self.location = None;
                 self.buffer.clear();
+                if size > 0 {
+                    oio::report_part(
+                        self.op.part_callback(),
+                        (self.written / self.write_fixed_size as u64) as usize,
+                        size,
+                        start,
+                        0,
+                        self.buffer.len() as u64,
+                    );
+                }
+                self.location = None;
                 Ok(())
             }
             _ => Err(parse_error(resp).await?),
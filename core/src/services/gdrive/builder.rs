@@ -117,6 +117,7 @@ impl GdriveBuilder {
 
 impl Builder for GdriveBuilder {
     const SCHEME: Scheme = Scheme::Gdrive;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&["root", "access_token"]);
 
     type Accessor = GdriveBackend;
 
@@ -129,7 +130,19 @@ impl Builder for GdriveBuilder {
         builder
     }
 
+    fn validate(&self) -> Result<()> {
+        if self.access_token.is_none() {
+            return Err(Error::new(ErrorKind::ConfigInvalid, "access_token not set")
+                .with_operation("Builder::validate")
+                .with_context("service", Scheme::Gdrive)
+                .with_context("key", "access_token"));
+        }
+
+        Ok(())
+    }
+
     fn build(&mut self) -> Result<Self::Accessor> {
+        self.validate()?;
         let root = normalize_root(&self.root.take().unwrap_or_default());
         debug!("backend use root {}", root);
 
@@ -142,9 +155,7 @@ impl Builder for GdriveBuilder {
             })?
         };
 
-        match self.access_token.clone() {
-            Some(access_token) => Ok(GdriveBackend::new(root, access_token, client)),
-            None => Err(Error::new(ErrorKind::ConfigInvalid, "access_token not set")),
-        }
+        let access_token = self.access_token.clone().unwrap_or_default();
+        Ok(GdriveBackend::new(root, access_token, client))
     }
 }
@@ -205,6 +205,8 @@ impl GhacBuilder {
 
 impl Builder for GhacBuilder {
     const SCHEME: Scheme = Scheme::Ghac;
+    const CONFIG_KEYS: Option<&'static [&'static str]> =
+        Some(&["root", "version", "enable_create_simulation"]);
     type Accessor = GhacBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
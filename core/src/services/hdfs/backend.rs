@@ -41,6 +41,8 @@ pub struct HdfsBuilder {
     name_node: Option<String>,
     kerberos_ticket_cache_path: Option<String>,
     user: Option<String>,
+    replication: Option<usize>,
+    block_size: Option<usize>,
 }
 
 impl HdfsBuilder {
@@ -89,10 +91,40 @@ impl HdfsBuilder {
         }
         self
     }
+
+    /// Set replication of this backend.
+    ///
+    /// The replication factor will be used while creating new files.
+    /// If not set, the cluster's default (`dfs.replication`) will be used.
+    pub fn replication(&mut self, replication: usize) -> &mut Self {
+        if replication > 0 {
+            self.replication = Some(replication)
+        }
+        self
+    }
+
+    /// Set block_size of this backend.
+    ///
+    /// The block size in bytes will be used while creating new files.
+    /// If not set, the cluster's default (`dfs.blocksize`) will be used.
+    pub fn block_size(&mut self, block_size: usize) -> &mut Self {
+        if block_size > 0 {
+            self.block_size = Some(block_size)
+        }
+        self
+    }
 }
 
 impl Builder for HdfsBuilder {
     const SCHEME: Scheme = Scheme::Hdfs;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&[
+        "root",
+        "name_node",
+        "kerberos_ticket_cache_path",
+        "user",
+        "replication",
+        "block_size",
+    ]);
     type Accessor = HdfsBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
@@ -103,20 +135,32 @@ impl Builder for HdfsBuilder {
         map.get("kerberos_ticket_cache_path")
             .map(|v| builder.kerberos_ticket_cache_path(v));
         map.get("user").map(|v| builder.user(v));
+        map.get("replication")
+            .and_then(|v| v.parse::<usize>().ok())
+            .map(|v| builder.replication(v));
+        map.get("block_size")
+            .and_then(|v| v.parse::<usize>().ok())
+            .map(|v| builder.block_size(v));
 
         builder
     }
 
+    fn validate(&self) -> Result<()> {
+        if self.name_node.is_none() {
+            return Err(Error::new(ErrorKind::ConfigInvalid, "name node is empty")
+                .with_operation("Builder::validate")
+                .with_context("service", Scheme::Hdfs)
+                .with_context("key", "name_node"));
+        }
+
+        Ok(())
+    }
+
     fn build(&mut self) -> Result<Self::Accessor> {
         debug!("backend build started: {:?}", &self);
+        self.validate()?;
 
-        let name_node = match &self.name_node {
-            Some(v) => v,
-            None => {
-                return Err(Error::new(ErrorKind::ConfigInvalid, "name node is empty")
-                    .with_context("service", Scheme::Hdfs))
-            }
-        };
+        let name_node = self.name_node.as_deref().unwrap_or_default();
 
         let root = normalize_root(&self.root.take().unwrap_or_default());
         debug!("backend use root {}", root);
@@ -144,6 +188,8 @@ impl Builder for HdfsBuilder {
         Ok(HdfsBackend {
             root,
             client: Arc::new(client),
+            replication: self.replication,
+            block_size: self.block_size,
         })
     }
 }
@@ -153,6 +199,31 @@ impl Builder for HdfsBuilder {
 pub struct HdfsBackend {
     root: String,
     client: Arc<hdrs::Client>,
+    replication: Option<usize>,
+    block_size: Option<usize>,
+}
+
+impl HdfsBackend {
+    /// Build open options for writing.
+    ///
+    /// The file will be created if not exist. `replication` and `block_size`
+    /// only take effect while creating new files.
+    fn open_options(&self, append: bool) -> hdrs::OpenOptions {
+        let mut opts = self.client.open_file();
+        opts.create(true);
+        if append {
+            opts.append(true);
+        } else {
+            opts.write(true);
+        }
+        if let Some(v) = self.replication {
+            opts.with_replication(v);
+        }
+        if let Some(v) = self.block_size {
+            opts.with_blocksize(v);
+        }
+        opts
+    }
 }
 
 /// hdrs::Client is thread-safe.
@@ -181,6 +252,8 @@ impl Accessor for HdfsBackend {
                 read_with_range: true,
 
                 write: true,
+                write_with_append: true,
+                append: true,
                 create_dir: true,
                 delete: true,
 
@@ -256,10 +329,7 @@ impl Accessor for HdfsBackend {
             .map_err(parse_io_error)?;
 
         let f = self
-            .client
-            .open_file()
-            .create(true)
-            .append(true)
+            .open_options(true)
             .async_open(&p)
             .await
             .map_err(parse_io_error)?;
@@ -267,7 +337,7 @@ impl Accessor for HdfsBackend {
         Ok((RpAppend::new(), HdfsAppender::new(f)))
     }
 
-    async fn write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
         let p = build_rooted_abs_path(&self.root, path);
 
         let parent = PathBuf::from(&p)
@@ -286,10 +356,7 @@ impl Accessor for HdfsBackend {
             .map_err(parse_io_error)?;
 
         let f = self
-            .client
-            .open_file()
-            .create(true)
-            .write(true)
+            .open_options(args.append())
             .async_open(&p)
             .await
             .map_err(parse_io_error)?;
@@ -404,7 +471,7 @@ impl Accessor for HdfsBackend {
         Ok((RpRead::new(end - start), r))
     }
 
-    fn blocking_write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
         let p = build_rooted_abs_path(&self.root, path);
 
         let parent = PathBuf::from(&p)
@@ -423,10 +490,7 @@ impl Accessor for HdfsBackend {
             .map_err(parse_io_error)?;
 
         let f = self
-            .client
-            .open_file()
-            .create(true)
-            .write(true)
+            .open_options(args.append())
             .open(&p)
             .map_err(parse_io_error)?;
 
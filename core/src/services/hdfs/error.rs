@@ -31,6 +31,8 @@ pub fn parse_io_error(err: io::Error) -> Error {
     let (kind, retryable) = match err.kind() {
         NotFound => (ErrorKind::NotFound, false),
         PermissionDenied => (ErrorKind::PermissionDenied, false),
+        // Returned while appending to a cluster with append disabled.
+        Unsupported => (ErrorKind::Unsupported, false),
         Interrupted | UnexpectedEof | TimedOut | WouldBlock => (ErrorKind::Unexpected, true),
         _ => (ErrorKind::Unexpected, true),
     };
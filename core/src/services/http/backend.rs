@@ -23,16 +23,21 @@ use async_trait::async_trait;
 use http::header;
 use http::header::IF_MATCH;
 use http::header::IF_NONE_MATCH;
+use http::HeaderMap;
+use http::HeaderName;
+use http::HeaderValue;
+use http::Method;
 use http::Request;
 use http::Response;
 use http::StatusCode;
 use log::debug;
 
 use super::error::parse_error;
+use super::writer::HttpWriter;
 use crate::raw::*;
 use crate::*;
 
-/// HTTP Read-only service support like Nginx and Caddy.
+/// HTTP service support like Nginx and Caddy.
 ///
 /// # Capabilities
 ///
@@ -40,7 +45,7 @@ use crate::*;
 ///
 /// - [x] stat
 /// - [x] read
-/// - [ ] ~~write~~
+/// - [x] write (requires `write_method`)
 /// - [ ] ~~create_dir~~
 /// - [ ] ~~delete~~
 /// - [ ] ~~copy~~
@@ -52,13 +57,37 @@ use crate::*;
 ///
 /// # Notes
 ///
-/// Only `read` ans `stat` are supported. We can use this service to visit any
+/// Only `read` ans `stat` are supported by default. We can use this service to visit any
 /// HTTP Server like nginx, caddy.
 ///
+/// `write` will be enabled if `write_method` is set, content will be uploaded
+/// in a single `PUT` or `POST` request. This makes it possible to work with
+/// simple REST object stores.
+///
+/// Redirects are followed by the underlying http client.
+///
 /// # Configuration
 ///
 /// - `endpoint`: set the endpoint for http
 /// - `root`: Set the work directory for backend
+/// - `username`, `password`: set the basic auth for http
+/// - `token`: set the bearer token for http
+/// - `write_method`: set the method used by write, `PUT` or `POST`
+/// - `accept_encoding`: decode gzip/deflate response bodies transparently
+///
+/// Static headers could be attached to every request via [`HttpBuilder::header`].
+///
+/// # Errors
+///
+/// Response status codes are mapped into [`ErrorKind`] as:
+///
+/// - `404 Not Found`: [`ErrorKind::NotFound`]
+/// - `401 Unauthorized`, `403 Forbidden`: [`ErrorKind::PermissionDenied`]
+/// - `405 Method Not Allowed`: [`ErrorKind::Unsupported`]
+/// - `304 Not Modified`, `412 Precondition Failed`: [`ErrorKind::ConditionNotMatch`]
+/// - `429 Too Many Requests`: [`ErrorKind::RateLimited`] and temporary
+/// - `500`, `502`, `503`, `504`: [`ErrorKind::Unexpected`] and temporary
+/// - others: [`ErrorKind::Unexpected`]
 ///
 /// You can refer to [`HttpBuilder`]'s docs for more information
 ///
@@ -89,7 +118,12 @@ pub struct HttpBuilder {
     password: Option<String>,
     token: Option<String>,
     root: Option<String>,
+    headers: Vec<(String, String)>,
+    write_method: Option<Method>,
+    accept_encoding: bool,
     http_client: Option<HttpClient>,
+    /// The first invalid config value, reported by `validate`.
+    invalid_config: Option<(&'static str, String)>,
 }
 
 impl Debug for HttpBuilder {
@@ -157,6 +191,46 @@ impl HttpBuilder {
         self
     }
 
+    /// Add a static header which will be sent with every request.
+    ///
+    /// This function can be called multiple times to add more headers,
+    /// for example: `builder.header("x-api-key", "xxx")`.
+    pub fn header(&mut self, name: &str, value: &str) -> &mut Self {
+        if !name.is_empty() {
+            self.headers.push((name.to_string(), value.to_string()));
+        }
+        self
+    }
+
+    /// Set the method used by write, available values are `PUT` and `POST`.
+    ///
+    /// Write is disabled if not set.
+    pub fn write_method(&mut self, method: &str) -> &mut Self {
+        match method.to_uppercase().as_str() {
+            "" => {}
+            "PUT" => self.write_method = Some(Method::PUT),
+            "POST" => self.write_method = Some(Method::POST),
+            _ => self.invalid_config = Some(("write_method", method.to_string())),
+        }
+        self
+    }
+
+    /// Enable transparent decompression of response bodies.
+    ///
+    /// With this enabled, `Accept-Encoding: gzip, deflate` will be sent in
+    /// read requests, and bodies will be decoded while reading. Refer to
+    /// [`decode_content_encoding`](crate::raw::decode_content_encoding) for
+    /// details.
+    ///
+    /// Reading with range (including seeking on reader) will return an
+    /// `Unsupported` error while this is enabled.
+    ///
+    /// default: false
+    pub fn accept_encoding(&mut self, enabled: bool) -> &mut Self {
+        self.accept_encoding = enabled;
+        self
+    }
+
     /// Specify the http client that used by this service.
     ///
     /// # Notes
@@ -171,6 +245,15 @@ impl HttpBuilder {
 
 impl Builder for HttpBuilder {
     const SCHEME: Scheme = Scheme::Http;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&[
+        "root",
+        "endpoint",
+        "username",
+        "password",
+        "token",
+        "write_method",
+        "accept_encoding",
+    ]);
     type Accessor = HttpBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
@@ -181,20 +264,39 @@ impl Builder for HttpBuilder {
         map.get("username").map(|v| builder.username(v));
         map.get("password").map(|v| builder.password(v));
         map.get("token").map(|v| builder.token(v));
+        map.get("write_method").map(|v| builder.write_method(v));
+        map.get("accept_encoding")
+            .map(|v| builder.accept_encoding(v == "on" || v == "true"));
 
         builder
     }
 
+    fn validate(&self) -> Result<()> {
+        if self.endpoint.is_none() {
+            return Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+                .with_operation("Builder::validate")
+                .with_context("service", Scheme::Http)
+                .with_context("key", "endpoint"));
+        }
+
+        if let Some((key, value)) = &self.invalid_config {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "config value is invalid")
+                    .with_operation("Builder::validate")
+                    .with_context("service", Scheme::Http)
+                    .with_context("key", *key)
+                    .with_context("value", value),
+            );
+        }
+
+        Ok(())
+    }
+
     fn build(&mut self) -> Result<Self::Accessor> {
         debug!("backend build started: {:?}", &self);
+        self.validate()?;
 
-        let endpoint = match &self.endpoint {
-            Some(v) => v,
-            None => {
-                return Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
-                    .with_context("service", Scheme::Http))
-            }
-        };
+        let endpoint = self.endpoint.as_deref().unwrap_or_default();
 
         let root = normalize_root(&self.root.take().unwrap_or_default());
         debug!("backend use root {}", root);
@@ -219,10 +321,29 @@ impl Builder for HttpBuilder {
             auth = Some(format_authorization_by_bearer(token)?)
         }
 
+        let mut headers = HeaderMap::with_capacity(self.headers.len());
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.as_bytes()).map_err(|err| {
+                Error::new(ErrorKind::ConfigInvalid, "header name is invalid")
+                    .with_context("service", Scheme::Http)
+                    .with_context("name", name)
+                    .set_source(err)
+            })?;
+            let mut value = build_header_value(value).map_err(|err| {
+                err.with_context("service", Scheme::Http)
+                    .with_context("name", name.as_str())
+            })?;
+            value.set_sensitive(true);
+            headers.append(name, value);
+        }
+
         debug!("backend build finished: {:?}", &self);
         Ok(HttpBackend {
             endpoint: endpoint.to_string(),
             authorization: auth,
+            headers,
+            write_method: self.write_method.clone(),
+            accept_encoding: self.accept_encoding,
             root,
             client,
         })
@@ -237,6 +358,9 @@ pub struct HttpBackend {
     client: HttpClient,
 
     authorization: Option<String>,
+    headers: HeaderMap<HeaderValue>,
+    write_method: Option<Method>,
+    accept_encoding: bool,
 }
 
 impl Debug for HttpBackend {
@@ -253,7 +377,7 @@ impl Debug for HttpBackend {
 impl Accessor for HttpBackend {
     type Reader = IncomingAsyncBody;
     type BlockingReader = ();
-    type Writer = ();
+    type Writer = HttpWriter;
     type BlockingWriter = ();
     type Appender = ();
     type Pager = ();
@@ -273,6 +397,10 @@ impl Accessor for HttpBackend {
                 read_with_range: true,
                 read_with_if_match: true,
                 read_with_if_none_match: true,
+                read_with_headers: true,
+
+                write: self.write_method.is_some(),
+                write_with_content_type: self.write_method.is_some(),
 
                 ..Default::default()
             });
@@ -281,14 +409,27 @@ impl Accessor for HttpBackend {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let resp = self
-            .http_get(path, args.range(), args.if_match(), args.if_none_match())
-            .await?;
+        if self.accept_encoding && !args.range().is_full() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "read with range is not supported while accept_encoding is enabled",
+            )
+            .with_operation(Operation::Read)
+            .with_context("service", Scheme::Http)
+            .with_context("range", args.range().to_string()));
+        }
+
+        let resp = self.http_get(path, &args).await?;
 
         let status = resp.status();
 
         match status {
             StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
+                let resp = if self.accept_encoding {
+                    decode_content_encoding(resp)?
+                } else {
+                    resp
+                };
                 let meta = parse_into_metadata(path, resp.headers())?;
                 Ok((RpRead::with_metadata(meta), resp.into_body()))
             }
@@ -318,38 +459,66 @@ impl Accessor for HttpBackend {
             _ => Err(parse_error(resp).await?),
         }
     }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        if args.content_length().is_none() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "write without content length is not supported",
+            ));
+        }
+
+        Ok((
+            RpWrite::default(),
+            HttpWriter::new(self.clone(), args, path.to_string()),
+        ))
+    }
 }
 
 impl HttpBackend {
-    async fn http_get(
-        &self,
-        path: &str,
-        range: BytesRange,
-        if_match: Option<&str>,
-        if_none_match: Option<&str>,
-    ) -> Result<Response<IncomingAsyncBody>> {
+    /// Attach static headers and authorization to request.
+    fn apply_headers(&self, mut req: http::request::Builder) -> http::request::Builder {
+        for (name, value) in &self.headers {
+            req = req.header(name, value.clone());
+        }
+
+        if let Some(auth) = &self.authorization {
+            req = req.header(header::AUTHORIZATION, auth.clone())
+        }
+
+        req
+    }
+
+    async fn http_get(&self, path: &str, args: &OpRead) -> Result<Response<IncomingAsyncBody>> {
         let p = build_rooted_abs_path(&self.root, path);
 
         let url = format!("{}{}", self.endpoint, percent_encode_path(&p));
 
         let mut req = Request::get(&url);
 
-        if let Some(if_match) = if_match {
+        if let Some(if_match) = args.if_match() {
             req = req.header(IF_MATCH, if_match);
         }
 
-        if let Some(if_none_match) = if_none_match {
+        if let Some(if_none_match) = args.if_none_match() {
             req = req.header(IF_NONE_MATCH, if_none_match);
         }
 
-        if let Some(auth) = &self.authorization {
-            req = req.header(header::AUTHORIZATION, auth.clone())
-        }
+        req = self.apply_headers(req);
 
+        let range = args.range();
         if !range.is_full() {
             req = req.header(header::RANGE, range.to_header());
         }
 
+        if self.accept_encoding {
+            req = req.header(header::ACCEPT_ENCODING, "gzip, deflate");
+        }
+
+        for (name, value) in args.headers() {
+            req = req.header(name.as_str(), value.as_str());
+        }
+
         let req = req
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
@@ -377,9 +546,7 @@ impl HttpBackend {
             req = req.header(IF_NONE_MATCH, if_none_match);
         }
 
-        if let Some(auth) = &self.authorization {
-            req = req.header(header::AUTHORIZATION, auth.clone())
-        }
+        req = self.apply_headers(req);
 
         let req = req
             .body(AsyncBody::Empty)
@@ -387,6 +554,38 @@ impl HttpBackend {
 
         self.client.send(req).await
     }
+
+    pub async fn http_write(
+        &self,
+        path: &str,
+        size: Option<u64>,
+        content_type: Option<&str>,
+        body: AsyncBody,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_rooted_abs_path(&self.root, path);
+
+        let url = format!("{}{}", self.endpoint, percent_encode_path(&p));
+
+        let method = self
+            .write_method
+            .clone()
+            .ok_or_else(|| Error::new(ErrorKind::Unsupported, "write_method is not configured"))?;
+        let mut req = Request::builder().method(method).uri(&url);
+
+        req = self.apply_headers(req);
+
+        if let Some(size) = size {
+            req = req.header(header::CONTENT_LENGTH, size)
+        }
+
+        if let Some(mime) = content_type {
+            req = req.header(header::CONTENT_TYPE, mime)
+        }
+
+        let req = req.body(body).map_err(new_request_build_error)?;
+
+        self.client.send(req).await
+    }
 }
 
 #[cfg(test)]
@@ -430,6 +629,47 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_read_with_accept_encoding() -> Result<()> {
+        use std::io::Write;
+
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+        let content = b"Hello, World!".repeat(128);
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&content)?;
+        let encoded = encoder.finish()?;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/hello"))
+            .and(headers("accept-encoding", vec!["gzip", "deflate"]))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-encoding", "gzip")
+                    .insert_header("content-length", encoded.len().to_string().as_str())
+                    .set_body_bytes(encoded),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = HttpBuilder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.root("/");
+        builder.accept_encoding(true);
+        let op = Operator::new(builder)?.finish();
+
+        let bs = op.read("hello").await?;
+        assert_eq!(bs, content);
+
+        let err = op.range_read("hello", 0..10).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_read_via_basic_auth() -> Result<()> {
         let _ = tracing_subscriber::fmt().with_test_writer().try_init();
@@ -490,6 +730,83 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_read_with_custom_header() -> Result<()> {
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/hello"))
+            .and(headers("x-api-key", vec!["secret"]))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "13")
+                    .set_body_string("Hello, World!"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = HttpBuilder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.root("/");
+        builder.header("x-api-key", "secret");
+        let op = Operator::new(builder)?.finish();
+
+        let bs = op.read("hello").await?;
+
+        assert_eq!(bs, b"Hello, World!");
+
+        Mock::given(method("GET"))
+            .and(path("/vendor"))
+            .and(headers("x-api-key", vec!["secret"]))
+            .and(headers("x-vendor-namespace", vec!["test"]))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "13")
+                    .set_body_string("Hello, World!"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let bs = op
+            .read_with("vendor")
+            .header("x-vendor-namespace", "test")
+            .await?;
+
+        assert_eq!(bs, b"Hello, World!");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write() -> Result<()> {
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/hello"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        // write is not supported without write_method.
+        let mut builder = HttpBuilder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.root("/");
+        let op = Operator::new(builder)?.finish();
+        let err = op.write("hello", "Hello, World!").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        let mut builder = HttpBuilder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.root("/");
+        builder.write_method("put");
+        let op = Operator::new(builder)?.finish();
+
+        op.write("hello", "Hello, World!").await?;
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_stat() -> Result<()> {
         let _ = tracing_subscriber::fmt().with_test_writer().try_init();
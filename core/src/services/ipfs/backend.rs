@@ -91,6 +91,7 @@ impl IpfsBuilder {
 
 impl Builder for IpfsBuilder {
     const SCHEME: Scheme = Scheme::Ipfs;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&["root", "endpoint"]);
     type Accessor = IpfsBackend;
     fn from_map(map: HashMap<String, String>) -> Self {
         let mut builder = IpfsBuilder::default();
@@ -101,26 +102,37 @@ impl Builder for IpfsBuilder {
         builder
     }
 
-    fn build(&mut self) -> Result<Self::Accessor> {
-        debug!("backend build started: {:?}", &self);
-
-        let root = normalize_root(&self.root.take().unwrap_or_default());
+    fn validate(&self) -> Result<()> {
+        let root = normalize_root(self.root.as_deref().unwrap_or_default());
         if !root.starts_with("/ipfs/") && !root.starts_with("/ipns/") {
             return Err(Error::new(
                 ErrorKind::ConfigInvalid,
                 "root must start with /ipfs/ or /ipns/",
             )
+            .with_operation("Builder::validate")
             .with_context("service", Scheme::Ipfs)
+            .with_context("key", "root")
             .with_context("root", &root));
         }
-        debug!("backend use root {}", root);
 
-        let endpoint = match &self.endpoint {
-            Some(endpoint) => Ok(endpoint.clone()),
-            None => Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+        if self.endpoint.is_none() {
+            return Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+                .with_operation("Builder::validate")
                 .with_context("service", Scheme::Ipfs)
-                .with_context("root", &root)),
-        }?;
+                .with_context("key", "endpoint"));
+        }
+
+        Ok(())
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        debug!("backend build started: {:?}", &self);
+        self.validate()?;
+
+        let root = normalize_root(&self.root.take().unwrap_or_default());
+        debug!("backend use root {}", root);
+
+        let endpoint = self.endpoint.clone().unwrap_or_default();
         debug!("backend use endpoint {}", &endpoint);
 
         let client = if let Some(client) = self.http_client.take() {
@@ -110,6 +110,7 @@ impl IpmfsBuilder {
 
 impl Builder for IpmfsBuilder {
     const SCHEME: Scheme = Scheme::Ipmfs;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&["root", "endpoint"]);
     type Accessor = IpmfsBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
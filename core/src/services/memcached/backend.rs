@@ -115,27 +115,20 @@ impl MemcachedBuilder {
     }
 }
 
-impl Builder for MemcachedBuilder {
-    const SCHEME: Scheme = Scheme::Memcached;
-    type Accessor = MemcachedBackend;
-
-    fn from_map(map: HashMap<String, String>) -> Self {
-        let mut builder = MemcachedBuilder::default();
-
-        map.get("root").map(|v| builder.root(v));
-        map.get("endpoint").map(|v| builder.endpoint(v));
-
-        builder
-    }
-
-    fn build(&mut self) -> Result<Self::Accessor> {
+impl MemcachedBuilder {
+    /// Parse the endpoint into `host:port` that can be connected directly.
+    fn parse_endpoint(&self) -> Result<String> {
         let endpoint = self.endpoint.clone().ok_or_else(|| {
             Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+                .with_operation("Builder::validate")
                 .with_context("service", Scheme::Memcached)
+                .with_context("key", "endpoint")
         })?;
         let uri = http::Uri::try_from(&endpoint).map_err(|err| {
             Error::new(ErrorKind::ConfigInvalid, "endpoint is invalid")
+                .with_operation("Builder::validate")
                 .with_context("service", Scheme::Memcached)
+                .with_context("key", "endpoint")
                 .with_context("endpoint", &endpoint)
                 .set_source(err)
         })?;
@@ -150,7 +143,9 @@ impl Builder for MemcachedBuilder {
                         ErrorKind::ConfigInvalid,
                         "endpoint is using invalid scheme",
                     )
+                    .with_operation("Builder::validate")
                     .with_context("service", Scheme::Memcached)
+                    .with_context("key", "endpoint")
                     .with_context("endpoint", &endpoint)
                     .with_context("scheme", scheme.to_string()));
                 }
@@ -162,7 +157,9 @@ impl Builder for MemcachedBuilder {
         } else {
             return Err(
                 Error::new(ErrorKind::ConfigInvalid, "endpoint doesn't have host")
+                    .with_operation("Builder::validate")
                     .with_context("service", Scheme::Memcached)
+                    .with_context("key", "endpoint")
                     .with_context("endpoint", &endpoint),
             );
         };
@@ -171,11 +168,38 @@ impl Builder for MemcachedBuilder {
         } else {
             return Err(
                 Error::new(ErrorKind::ConfigInvalid, "endpoint doesn't have port")
+                    .with_operation("Builder::validate")
                     .with_context("service", Scheme::Memcached)
+                    .with_context("key", "endpoint")
                     .with_context("endpoint", &endpoint),
             );
         };
-        let endpoint = format!("{host}:{port}",);
+
+        Ok(format!("{host}:{port}"))
+    }
+}
+
+impl Builder for MemcachedBuilder {
+    const SCHEME: Scheme = Scheme::Memcached;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&["root", "endpoint"]);
+    type Accessor = MemcachedBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = MemcachedBuilder::default();
+
+        map.get("root").map(|v| builder.root(v));
+        map.get("endpoint").map(|v| builder.endpoint(v));
+
+        builder
+    }
+
+    fn validate(&self) -> Result<()> {
+        self.parse_endpoint().map(|_| ())
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        // Parsing endpoint is all that validate does.
+        let endpoint = self.parse_endpoint()?;
 
         let root = normalize_root(
             self.root
@@ -236,6 +260,7 @@ impl kv::Adapter for Adapter {
             Capability {
                 read: true,
                 write: true,
+                write_with_ttl: true,
                 create_dir: true,
 
                 ..Default::default()
@@ -256,13 +281,21 @@ impl kv::Adapter for Adapter {
             &percent_encode_path(key),
             value,
             // Set expiration to 0 if ttl not set.
-            self.default_ttl
-                .map(|v| v.as_secs() as u32)
-                .unwrap_or_default(),
+            match self.default_ttl {
+                Some(ttl) => parse_expiration(ttl)?,
+                None => 0,
+            },
         )
         .await
     }
 
+    async fn set_with_ttl(&self, key: &str, value: &[u8], ttl: Duration) -> Result<()> {
+        let mut conn = self.conn().await?;
+
+        conn.set(&percent_encode_path(key), value, parse_expiration(ttl)?)
+            .await
+    }
+
     async fn delete(&self, key: &str) -> Result<()> {
         let mut conn = self.conn().await?;
 
@@ -309,3 +342,38 @@ impl bb8::ManageConnection for MemcacheConnectionManager {
 pub fn parse_io_error(err: std::io::Error) -> Error {
     Error::new(ErrorKind::Unexpected, &err.kind().to_string()).set_source(err)
 }
+
+/// Memcached treats expiration larger than 30 days as an unix timestamp.
+const MAX_TTL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Convert ttl into the expiration of memcached in seconds.
+///
+/// Expiration `0` means never expire, and larger than 30 days means an unix
+/// timestamp, so both of them are rejected.
+fn parse_expiration(ttl: Duration) -> Result<u32> {
+    if ttl > MAX_TTL {
+        return Err(
+            Error::new(ErrorKind::InvalidInput, "ttl must be no more than 30 days")
+                .with_context("service", Scheme::Memcached)
+                .with_context("ttl", format!("{ttl:?}")),
+        );
+    }
+
+    Ok(kv::parse_ttl(Scheme::Memcached, ttl)? as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_expiration() {
+        assert_eq!(parse_expiration(Duration::from_millis(100)).unwrap(), 1);
+        assert_eq!(parse_expiration(MAX_TTL).unwrap(), 30 * 24 * 60 * 60);
+
+        for ttl in [Duration::ZERO, MAX_TTL + Duration::from_secs(1)] {
+            let err = parse_expiration(ttl).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        }
+    }
+}
@@ -18,6 +18,7 @@
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -31,6 +32,36 @@ use crate::*;
 #[derive(Default)]
 pub struct MemoryBuilder {
     root: Option<String>,
+    max_capacity: Option<usize>,
+    eviction_policy: Option<MemoryEvictionPolicy>,
+
+    /// The first invalid config value passed to `from_map` with its key.
+    invalid_config: Option<(&'static str, String)>,
+}
+
+/// Policy used by [`MemoryBuilder`] when `max_capacity` is reached.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MemoryEvictionPolicy {
+    /// Reject the write with [`ErrorKind::QuotaExceeded`].
+    #[default]
+    Reject,
+    /// Evict the least recently used entries until the new value fits.
+    Lru,
+}
+
+impl FromStr for MemoryEvictionPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "reject" => Ok(MemoryEvictionPolicy::Reject),
+            "lru" => Ok(MemoryEvictionPolicy::Lru),
+            _ => Err(
+                Error::new(ErrorKind::ConfigInvalid, "unknown memory eviction policy")
+                    .with_context("eviction_policy", s),
+            ),
+        }
+    }
 }
 
 impl MemoryBuilder {
@@ -39,23 +70,80 @@ impl MemoryBuilder {
         self.root = Some(path.into());
         self
     }
+
+    /// Set the max bytes this service could hold.
+    ///
+    /// Size of every entry is counted as its content length plus the size
+    /// of its metadata. Unlimited by default.
+    pub fn max_capacity(&mut self, bytes: usize) -> &mut Self {
+        self.max_capacity = Some(bytes);
+        self
+    }
+
+    /// Set the policy used when `max_capacity` is reached.
+    ///
+    /// Default to [`MemoryEvictionPolicy::Reject`].
+    pub fn eviction_policy(&mut self, policy: MemoryEvictionPolicy) -> &mut Self {
+        self.eviction_policy = Some(policy);
+        self
+    }
 }
 
 impl Builder for MemoryBuilder {
     const SCHEME: Scheme = Scheme::Memory;
+    const CONFIG_KEYS: Option<&'static [&'static str]> =
+        Some(&["root", "max_capacity", "eviction_policy"]);
     type Accessor = MemoryBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
         let mut builder = Self::default();
 
         map.get("root").map(|v| builder.root(v));
+        if let Some(v) = map.get("max_capacity") {
+            match v.parse::<usize>() {
+                Ok(v) => {
+                    builder.max_capacity(v);
+                }
+                Err(_) => builder.invalid_config = Some(("max_capacity", v.clone())),
+            }
+        }
+        if let Some(v) = map.get("eviction_policy") {
+            match v.parse::<MemoryEvictionPolicy>() {
+                Ok(v) => {
+                    builder.eviction_policy(v);
+                }
+                Err(_) => {
+                    builder
+                        .invalid_config
+                        .get_or_insert(("eviction_policy", v.clone()));
+                }
+            }
+        }
 
         builder
     }
 
+    fn validate(&self) -> Result<()> {
+        if let Some((key, value)) = &self.invalid_config {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "config value is invalid")
+                    .with_operation("Builder::validate")
+                    .with_context("service", Scheme::Memory)
+                    .with_context("key", *key)
+                    .with_context("value", value),
+            );
+        }
+
+        Ok(())
+    }
+
     fn build(&mut self) -> Result<Self::Accessor> {
+        self.validate()?;
+
         let adapter = Adapter {
-            inner: Arc::new(Mutex::new(BTreeMap::default())),
+            inner: Arc::new(Mutex::new(Store::default())),
+            max_capacity: self.max_capacity,
+            eviction_policy: self.eviction_policy.unwrap_or_default(),
         };
 
         Ok(MemoryBackend::new(adapter).with_root(self.root.as_deref().unwrap_or_default()))
@@ -65,14 +153,71 @@ impl Builder for MemoryBuilder {
 /// Backend is used to serve `Accessor` support in memory.
 pub type MemoryBackend = typed_kv::Backend<Adapter>;
 
+/// Store keeps all values and tracks their total size and access order.
+#[derive(Default)]
+struct Store {
+    /// path -> (last access tick, value)
+    map: BTreeMap<String, (u64, typed_kv::Value)>,
+    /// last access tick -> path, used to find the least recently used entry.
+    ticks: BTreeMap<u64, String>,
+    tick: u64,
+    size: usize,
+}
+
+impl Store {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn get(&mut self, path: &str) -> Option<typed_kv::Value> {
+        let tick = self.next_tick();
+        let (old, value) = self.map.get_mut(path)?;
+        let old = std::mem::replace(old, tick);
+        let value = value.clone();
+
+        self.ticks.remove(&old);
+        self.ticks.insert(tick, path.to_string());
+        Some(value)
+    }
+
+    fn insert(&mut self, path: &str, value: typed_kv::Value) {
+        let tick = self.next_tick();
+        self.size += value.size();
+        self.ticks.insert(tick, path.to_string());
+        self.map.insert(path.to_string(), (tick, value));
+    }
+
+    fn remove(&mut self, path: &str) -> Option<typed_kv::Value> {
+        let (tick, value) = self.map.remove(path)?;
+        self.ticks.remove(&tick);
+        self.size -= value.size();
+        Some(value)
+    }
+
+    fn evict_one(&mut self) -> bool {
+        let path = match self.ticks.values().next() {
+            Some(path) => path.clone(),
+            None => return false,
+        };
+        self.remove(&path);
+        true
+    }
+}
+
 #[derive(Clone)]
 pub struct Adapter {
-    inner: Arc<Mutex<BTreeMap<String, typed_kv::Value>>>,
+    inner: Arc<Mutex<Store>>,
+    max_capacity: Option<usize>,
+    eviction_policy: MemoryEvictionPolicy,
 }
 
 impl Debug for Adapter {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("MemoryBackend").finish_non_exhaustive()
+        f.debug_struct("MemoryBackend")
+            .field("max_capacity", &self.max_capacity)
+            .field("eviction_policy", &self.eviction_policy)
+            .finish_non_exhaustive()
     }
 }
 
@@ -96,10 +241,7 @@ impl typed_kv::Adapter for Adapter {
     }
 
     fn blocking_get(&self, path: &str) -> Result<Option<typed_kv::Value>> {
-        match self.inner.lock().get(path) {
-            None => Ok(None),
-            Some(bs) => Ok(Some(bs.to_owned())),
-        }
+        Ok(self.inner.lock().get(path))
     }
 
     async fn set(&self, path: &str, value: typed_kv::Value) -> Result<()> {
@@ -107,8 +249,38 @@ impl typed_kv::Adapter for Adapter {
     }
 
     fn blocking_set(&self, path: &str, value: typed_kv::Value) -> Result<()> {
-        self.inner.lock().insert(path.to_string(), value);
+        let mut store = self.inner.lock();
+
+        let max_capacity = match self.max_capacity {
+            None => {
+                store.remove(path);
+                store.insert(path, value);
+                return Ok(());
+            }
+            Some(v) => v,
+        };
+
+        let new_size = value.size();
+        if new_size > max_capacity {
+            return Err(quota_exceeded(path, max_capacity, new_size));
+        }
+
+        // The old value will be replaced, so it doesn't count.
+        let old_size = store.map.get(path).map(|(_, v)| v.size()).unwrap_or(0);
+        if store.size - old_size + new_size > max_capacity {
+            match self.eviction_policy {
+                MemoryEvictionPolicy::Reject => {
+                    return Err(quota_exceeded(path, max_capacity, new_size));
+                }
+                MemoryEvictionPolicy::Lru => {
+                    store.remove(path);
+                    while store.size + new_size > max_capacity && store.evict_one() {}
+                }
+            }
+        }
 
+        store.remove(path);
+        store.insert(path, value);
         Ok(())
     }
 
@@ -127,12 +299,13 @@ impl typed_kv::Adapter for Adapter {
     }
 
     fn blocking_scan(&self, path: &str) -> Result<Vec<String>> {
-        let inner = self.inner.lock();
+        let store = self.inner.lock();
         let keys: Vec<_> = if path.is_empty() {
-            inner.keys().cloned().collect()
+            store.map.keys().cloned().collect()
         } else {
             let right_range = format!("{}0", &path[..path.len() - 1]);
-            inner
+            store
+                .map
                 .range(path.to_string()..right_range)
                 .map(|(k, _)| k.to_string())
                 .collect()
@@ -142,11 +315,38 @@ impl typed_kv::Adapter for Adapter {
     }
 }
 
+fn quota_exceeded(path: &str, max_capacity: usize, size: usize) -> Error {
+    Error::new(ErrorKind::QuotaExceeded, "memory capacity exceeded")
+        .with_operation("set")
+        .with_context("service", Scheme::Memory)
+        .with_context("path", path)
+        .with_context("max_capacity", max_capacity.to_string())
+        .with_context("size", size.to_string())
+}
+
 #[cfg(test)]
 mod tests {
+    use bytes::Bytes;
+
     use super::*;
+    use crate::raw::adapters::typed_kv::Adapter as _;
     use crate::raw::*;
 
+    fn new_value(size: usize) -> typed_kv::Value {
+        typed_kv::Value {
+            metadata: Metadata::new(EntryMode::FILE).with_content_length(size as u64),
+            value: Bytes::from(vec![0; size]),
+        }
+    }
+
+    fn new_adapter(max_capacity: usize, eviction_policy: MemoryEvictionPolicy) -> Adapter {
+        Adapter {
+            inner: Arc::new(Mutex::new(Store::default())),
+            max_capacity: Some(max_capacity),
+            eviction_policy,
+        }
+    }
+
     #[test]
     fn test_accessor_metadata_name() {
         let b1 = MemoryBuilder::default().build().unwrap();
@@ -155,4 +355,61 @@ mod tests {
         let b2 = MemoryBuilder::default().build().unwrap();
         assert_ne!(b1.info().name(), b2.info().name())
     }
+
+    #[test]
+    fn test_from_map_invalid_value() {
+        let map = HashMap::from([("max_capacity".to_string(), "1GiB".to_string())]);
+        let err = MemoryBuilder::from_map(map).build().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        assert!(err.to_string().contains("max_capacity"));
+
+        let map = HashMap::from([("eviction_policy".to_string(), "fifo".to_string())]);
+        let err = MemoryBuilder::from_map(map).build().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        assert!(err.to_string().contains("eviction_policy"));
+
+        let map = HashMap::from([
+            ("max_capacity".to_string(), "1024".to_string()),
+            ("eviction_policy".to_string(), "LRU".to_string()),
+        ]);
+        assert!(MemoryBuilder::from_map(map).build().is_ok());
+    }
+
+    #[test]
+    fn test_reject_on_full() {
+        let size = new_value(100).size();
+        let adapter = new_adapter(size * 2, MemoryEvictionPolicy::Reject);
+
+        adapter.blocking_set("a", new_value(100)).unwrap();
+        adapter.blocking_set("b", new_value(100)).unwrap();
+        // Overwrite should not count the old value.
+        adapter.blocking_set("a", new_value(100)).unwrap();
+
+        let err = adapter.blocking_set("c", new_value(100)).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::QuotaExceeded);
+
+        adapter.blocking_delete("a").unwrap();
+        adapter.blocking_set("c", new_value(100)).unwrap();
+        assert_eq!(adapter.inner.lock().size, size * 2);
+    }
+
+    #[test]
+    fn test_evict_lru() {
+        let size = new_value(100).size();
+        let adapter = new_adapter(size * 2, MemoryEvictionPolicy::Lru);
+
+        adapter.blocking_set("a", new_value(100)).unwrap();
+        adapter.blocking_set("b", new_value(100)).unwrap();
+        // Access a so that b becomes the least recently used one.
+        assert!(adapter.blocking_get("a").unwrap().is_some());
+
+        adapter.blocking_set("c", new_value(100)).unwrap();
+        assert!(adapter.blocking_get("a").unwrap().is_some());
+        assert!(adapter.blocking_get("b").unwrap().is_none());
+        assert!(adapter.blocking_get("c").unwrap().is_some());
+        assert_eq!(adapter.inner.lock().size, size * 2);
+
+        let err = adapter.blocking_set("d", new_value(size * 2)).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::QuotaExceeded);
+    }
 }
@@ -17,3 +17,4 @@
 
 mod backend;
 pub use backend::MemoryBuilder as Memory;
+pub use backend::MemoryEvictionPolicy;
@@ -99,6 +99,8 @@ impl MiniMokaBuilder {
 
 impl Builder for MiniMokaBuilder {
     const SCHEME: Scheme = Scheme::MiniMoka;
+    const CONFIG_KEYS: Option<&'static [&'static str]> =
+        Some(&["max_capacity", "time_to_live", "time_to_idle"]);
     type Accessor = MiniMokaBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
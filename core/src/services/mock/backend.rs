@@ -0,0 +1,648 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::Utc;
+use parking_lot::Mutex;
+
+use crate::raw::adapters::typed_kv;
+use crate::raw::*;
+use crate::*;
+
+/// Mock service support for unit tests.
+#[doc = include_str!("docs.md")]
+#[derive(Default, Clone)]
+pub struct MockBuilder {
+    root: Option<String>,
+    files: Vec<(String, Bytes)>,
+    state: Arc<Mutex<State>>,
+}
+
+impl Debug for MockBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockBuilder")
+            .field("root", &self.root)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MockBuilder {
+    /// Set the root for mock.
+    pub fn root(&mut self, path: &str) -> &mut Self {
+        self.root = Some(path.into());
+        self
+    }
+
+    /// Preload a file with given content.
+    ///
+    /// Files are loaded while building, so this must be called before the
+    /// operator is built.
+    pub fn file(&mut self, path: &str, content: impl Into<Bytes>) -> &mut Self {
+        self.files.push((path.to_string(), content.into()));
+        self
+    }
+
+    /// Make all following calls of `op` on `path` fail with given error kind.
+    ///
+    /// The path is the normalized path passed to the service, for example
+    /// `dir/` for directories. The error is permanent, so it won't be
+    /// retried by `RetryLayer`.
+    pub fn fail(&mut self, op: Operation, path: &str, kind: ErrorKind) -> &mut Self {
+        self.state.lock().failures.insert(
+            (op, path.to_string()),
+            Failure {
+                kind,
+                temporary: false,
+                times: None,
+            },
+        );
+        self
+    }
+
+    /// Make the next `times` calls of `op` on `path` fail with a temporary
+    /// error of given kind, calls after that will succeed.
+    ///
+    /// This is useful to test retry logic.
+    pub fn fail_temporarily(
+        &mut self,
+        op: Operation,
+        path: &str,
+        kind: ErrorKind,
+        times: usize,
+    ) -> &mut Self {
+        self.state.lock().failures.insert(
+            (op, path.to_string()),
+            Failure {
+                kind,
+                temporary: true,
+                times: Some(times),
+            },
+        );
+        self
+    }
+
+    /// Get all calls that have been made to the service in order.
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.state.lock().calls.clone()
+    }
+
+    /// Clear all recorded calls.
+    pub fn clear_calls(&mut self) -> &mut Self {
+        self.state.lock().calls.clear();
+        self
+    }
+}
+
+impl Builder for MockBuilder {
+    const SCHEME: Scheme = Scheme::Mock;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&["root"]);
+    type Accessor = MockBackend;
+
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = Self::default();
+
+        map.get("root").map(|v| builder.root(v));
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        let root = normalize_root(self.root.as_deref().unwrap_or_default());
+
+        {
+            let mut state = self.state.lock();
+            for (path, content) in self.files.drain(..) {
+                let value = typed_kv::Value {
+                    metadata: Metadata::new(EntryMode::FILE)
+                        .with_content_length(content.len() as u64)
+                        .with_last_modified(Utc::now()),
+                    value: content,
+                };
+                state
+                    .files
+                    .insert(build_abs_path(&root, &normalize_path(&path)), value);
+            }
+        }
+
+        Ok(MockBackend {
+            inner: typed_kv::Backend::new(Adapter {
+                state: self.state.clone(),
+            })
+            .with_root(&root),
+            state: self.state.clone(),
+        })
+    }
+}
+
+/// MockCall is a call that has been made to the mock service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MockCall {
+    /// The operation of this call.
+    pub operation: Operation,
+    /// The path of this call, `from` for `copy` and `rename`.
+    pub path: String,
+    /// The `to` path for `copy` and `rename`.
+    pub target: Option<String>,
+}
+
+#[derive(Default)]
+struct State {
+    calls: Vec<MockCall>,
+    failures: HashMap<(Operation, String), Failure>,
+    /// absolute path -> value
+    files: BTreeMap<String, typed_kv::Value>,
+    /// idempotency keys of the writes that have been applied.
+    idempotency_keys: HashSet<String>,
+}
+
+struct Failure {
+    kind: ErrorKind,
+    temporary: bool,
+    /// `None` means fail forever.
+    times: Option<usize>,
+}
+
+/// Backend is used to serve `Accessor` support for mock.
+#[derive(Debug, Clone)]
+pub struct MockBackend {
+    inner: typed_kv::Backend<Adapter>,
+    state: Arc<Mutex<State>>,
+}
+
+impl Debug for State {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("State")
+            .field("calls", &self.calls.len())
+            .field("files", &self.files.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl MockBackend {
+    /// Record the call and return the programmed error if any.
+    fn record(&self, op: Operation, path: &str, target: Option<&str>) -> Result<()> {
+        let mut state = self.state.lock();
+        state.calls.push(MockCall {
+            operation: op,
+            path: path.to_string(),
+            target: target.map(|v| v.to_string()),
+        });
+
+        let key = (op, path.to_string());
+        let failure = match state.failures.get_mut(&key) {
+            Some(failure) => failure,
+            None => return Ok(()),
+        };
+        match &mut failure.times {
+            Some(0) => return Ok(()),
+            Some(times) => *times -= 1,
+            None => {}
+        }
+
+        let err = Error::new(failure.kind, "error programmed by mock service")
+            .with_operation(op)
+            .with_context("service", Scheme::Mock)
+            .with_context("path", path);
+        Err(if failure.temporary {
+            err.set_temporary()
+        } else {
+            err
+        })
+    }
+
+    /// Check whether the write with given idempotency key has been applied.
+    fn is_applied(&self, key: Option<&str>) -> bool {
+        match key {
+            Some(key) => self.state.lock().idempotency_keys.contains(key),
+            None => false,
+        }
+    }
+}
+
+/// MockWriter applies the write only once for the same idempotency key.
+///
+/// The key is recorded after the write has been closed successfully, so
+/// failed writes can be retried with the same key.
+pub struct MockWriter<W> {
+    /// `None` means the write with the same key has been applied before,
+    /// all content written will be dropped.
+    inner: Option<W>,
+    key: Option<String>,
+    state: Arc<Mutex<State>>,
+}
+
+impl<W> MockWriter<W> {
+    fn new(inner: W, key: Option<String>, state: Arc<Mutex<State>>) -> Self {
+        Self {
+            inner: Some(inner),
+            key,
+            state,
+        }
+    }
+
+    fn applied(state: Arc<Mutex<State>>) -> Self {
+        Self {
+            inner: None,
+            key: None,
+            state,
+        }
+    }
+
+    fn record_key(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.state.lock().idempotency_keys.insert(key);
+        }
+    }
+}
+
+#[async_trait]
+impl<W: oio::Write> oio::Write for MockWriter<W> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        match &mut self.inner {
+            Some(w) => w.write(bs).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn sink(&mut self, size: u64, s: oio::Streamer) -> Result<()> {
+        match &mut self.inner {
+            Some(w) => w.sink(size, s).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        match &mut self.inner {
+            Some(w) => w.abort().await,
+            None => Ok(()),
+        }
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        if let Some(w) = &mut self.inner {
+            w.close().await?;
+            self.record_key();
+        }
+        Ok(())
+    }
+}
+
+impl<W: oio::BlockingWrite> oio::BlockingWrite for MockWriter<W> {
+    fn write(&mut self, bs: Bytes) -> Result<()> {
+        match &mut self.inner {
+            Some(w) => w.write(bs),
+            None => Ok(()),
+        }
+    }
+
+    fn close(&mut self) -> Result<()> {
+        if let Some(w) = &mut self.inner {
+            w.close()?;
+            self.record_key();
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Accessor for MockBackend {
+    type Reader = <typed_kv::Backend<Adapter> as Accessor>::Reader;
+    type BlockingReader = <typed_kv::Backend<Adapter> as Accessor>::BlockingReader;
+    type Writer = MockWriter<<typed_kv::Backend<Adapter> as Accessor>::Writer>;
+    type BlockingWriter = MockWriter<<typed_kv::Backend<Adapter> as Accessor>::BlockingWriter>;
+    type Appender = <typed_kv::Backend<Adapter> as Accessor>::Appender;
+    type Pager = <typed_kv::Backend<Adapter> as Accessor>::Pager;
+    type BlockingPager = <typed_kv::Backend<Adapter> as Accessor>::BlockingPager;
+
+    fn info(&self) -> AccessorInfo {
+        let mut am = self.inner.info();
+        am.capability_mut().write_with_idempotency_key = true;
+        am
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.record(Operation::CreateDir, path, None)?;
+        self.inner.create_dir(path, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.record(Operation::Read, path, None)?;
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.record(Operation::Write, path, None)?;
+        let key = args.idempotency_key().map(|v| v.to_string());
+        if self.is_applied(key.as_deref()) {
+            return Ok((RpWrite::new(), MockWriter::applied(self.state.clone())));
+        }
+
+        let (rp, w) = self.inner.write(path, args).await?;
+        Ok((rp, MockWriter::new(w, key, self.state.clone())))
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        self.record(Operation::Append, path, None)?;
+        self.inner.append(path, args).await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.record(Operation::Copy, from, Some(to))?;
+        self.inner.copy(from, to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.record(Operation::Rename, from, Some(to))?;
+        self.inner.rename(from, to, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.record(Operation::Stat, path, None)?;
+        self.inner.stat(path, args).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.record(Operation::Delete, path, None)?;
+        self.inner.delete(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.record(Operation::List, path, None)?;
+        self.inner.list(path, args).await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        self.record(Operation::Batch, "", None)?;
+        self.inner.batch(args).await
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.record(Operation::Presign, path, None)?;
+        self.inner.presign(path, args).await
+    }
+
+    async fn get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        self.record(Operation::GetTags, path, None)?;
+        self.inner.get_tags(path, args).await
+    }
+
+    async fn put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        self.record(Operation::PutTags, path, None)?;
+        self.inner.put_tags(path, args).await
+    }
+
+    async fn get_object_lock(&self, path: &str, args: OpGetObjectLock) -> Result<RpGetObjectLock> {
+        self.record(Operation::GetObjectLock, path, None)?;
+        self.inner.get_object_lock(path, args).await
+    }
+
+    async fn put_object_lock(&self, path: &str, args: OpPutObjectLock) -> Result<RpPutObjectLock> {
+        self.record(Operation::PutObjectLock, path, None)?;
+        self.inner.put_object_lock(path, args).await
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.record(Operation::BlockingCreateDir, path, None)?;
+        self.inner.blocking_create_dir(path, args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.record(Operation::BlockingRead, path, None)?;
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.record(Operation::BlockingWrite, path, None)?;
+        let key = args.idempotency_key().map(|v| v.to_string());
+        if self.is_applied(key.as_deref()) {
+            return Ok((RpWrite::new(), MockWriter::applied(self.state.clone())));
+        }
+
+        let (rp, w) = self.inner.blocking_write(path, args)?;
+        Ok((rp, MockWriter::new(w, key, self.state.clone())))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.record(Operation::BlockingCopy, from, Some(to))?;
+        self.inner.blocking_copy(from, to, args)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.record(Operation::BlockingRename, from, Some(to))?;
+        self.inner.blocking_rename(from, to, args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.record(Operation::BlockingStat, path, None)?;
+        self.inner.blocking_stat(path, args)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.record(Operation::BlockingDelete, path, None)?;
+        self.inner.blocking_delete(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.record(Operation::BlockingList, path, None)?;
+        self.inner.blocking_list(path, args)
+    }
+
+    fn blocking_get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        self.record(Operation::BlockingGetTags, path, None)?;
+        self.inner.blocking_get_tags(path, args)
+    }
+
+    fn blocking_put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        self.record(Operation::BlockingPutTags, path, None)?;
+        self.inner.blocking_put_tags(path, args)
+    }
+}
+
+#[derive(Clone)]
+pub struct Adapter {
+    state: Arc<Mutex<State>>,
+}
+
+impl Debug for Adapter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockAdapter").finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl typed_kv::Adapter for Adapter {
+    fn info(&self) -> typed_kv::Info {
+        typed_kv::Info::new(
+            Scheme::Mock,
+            &format!("{:?}", Arc::as_ptr(&self.state)),
+            typed_kv::Capability {
+                get: true,
+                set: true,
+                delete: true,
+                scan: true,
+            },
+        )
+    }
+
+    async fn get(&self, path: &str) -> Result<Option<typed_kv::Value>> {
+        self.blocking_get(path)
+    }
+
+    fn blocking_get(&self, path: &str) -> Result<Option<typed_kv::Value>> {
+        Ok(self.state.lock().files.get(path).cloned())
+    }
+
+    async fn set(&self, path: &str, value: typed_kv::Value) -> Result<()> {
+        self.blocking_set(path, value)
+    }
+
+    fn blocking_set(&self, path: &str, value: typed_kv::Value) -> Result<()> {
+        self.state.lock().files.insert(path.to_string(), value);
+        Ok(())
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        self.blocking_delete(path)
+    }
+
+    fn blocking_delete(&self, path: &str) -> Result<()> {
+        self.state.lock().files.remove(path);
+        Ok(())
+    }
+
+    async fn scan(&self, path: &str) -> Result<Vec<String>> {
+        self.blocking_scan(path)
+    }
+
+    fn blocking_scan(&self, path: &str) -> Result<Vec<String>> {
+        let state = self.state.lock();
+        let keys = state
+            .files
+            .keys()
+            .filter(|k| k.starts_with(path))
+            .cloned()
+            .collect();
+        Ok(keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::layers::RetryLayer;
+
+    #[tokio::test]
+    async fn test_record_calls() {
+        let mut mock = MockBuilder::default();
+        mock.file("a", "hello");
+        let op = Operator::new(mock.clone()).unwrap().finish();
+
+        assert_eq!(op.read("a").await.unwrap(), b"hello");
+        op.copy("a", "b").await.unwrap();
+
+        let calls = mock.calls();
+        assert_eq!(
+            calls.last().unwrap(),
+            &MockCall {
+                operation: Operation::Copy,
+                path: "a".to_string(),
+                target: Some("b".to_string()),
+            }
+        );
+        assert!(calls
+            .iter()
+            .any(|c| c.operation == Operation::Read && c.path == "a"));
+
+        mock.clear_calls();
+        assert!(mock.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fail() {
+        let mut mock = MockBuilder::default();
+        mock.file("a", "hello");
+        let op = Operator::new(mock.clone()).unwrap().finish();
+
+        mock.fail(Operation::Read, "a", ErrorKind::PermissionDenied);
+        let err = op.read("a").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        assert!(!err.is_temporary());
+
+        // Other operations and paths are not affected.
+        assert_eq!(op.stat("a").await.unwrap().content_length(), 5);
+        op.write("b", "world").await.unwrap();
+        assert_eq!(op.read("b").await.unwrap(), b"world");
+    }
+
+    #[tokio::test]
+    async fn test_fail_temporarily() {
+        let mut mock = MockBuilder::default();
+        mock.file("a", "hello");
+        mock.fail_temporarily(Operation::Stat, "a", ErrorKind::Unexpected, 2);
+        let op = Operator::new(mock.clone())
+            .unwrap()
+            .layer(RetryLayer::new().with_min_delay(Duration::from_millis(1)))
+            .finish();
+
+        assert_eq!(op.stat("a").await.unwrap().content_length(), 5);
+        let stats = mock
+            .calls()
+            .into_iter()
+            .filter(|c| c.operation == Operation::Stat)
+            .count();
+        assert_eq!(stats, 3);
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key() {
+        let mock = MockBuilder::default();
+        let op = Operator::new(mock.clone()).unwrap().finish();
+        assert!(op.info().capability().write_with_idempotency_key);
+
+        // Key of writes that are not closed won't be recorded.
+        let mut w = op.writer_with("a").idempotency_key("key").await.unwrap();
+        w.write("dropped").await.unwrap();
+        w.abort().await.unwrap();
+
+        op.write_with("a", "hello")
+            .idempotency_key("key")
+            .await
+            .unwrap();
+        op.write_with("a", "world")
+            .idempotency_key("key")
+            .await
+            .unwrap();
+        assert_eq!(op.read("a").await.unwrap(), b"hello");
+
+        op.write_with("a", "world")
+            .idempotency_key("other")
+            .await
+            .unwrap();
+        assert_eq!(op.read("a").await.unwrap(), b"world");
+    }
+}
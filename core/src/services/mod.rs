@@ -53,10 +53,14 @@ pub use fs::Fs;
 mod ftp;
 #[cfg(feature = "services-ftp")]
 pub use ftp::Ftp;
+#[cfg(feature = "services-ftp")]
+pub use ftp::FtpTlsMode;
 
 #[cfg(feature = "services-gcs")]
 mod gcs;
 #[cfg(feature = "services-gcs")]
+pub use gcs::GcpMetadataCredentialProvider;
+#[cfg(feature = "services-gcs")]
 pub use gcs::Gcs;
 
 #[cfg(feature = "services-ghac")]
@@ -93,12 +97,21 @@ pub use memcached::Memcached;
 mod memory;
 #[cfg(feature = "services-memory")]
 pub use memory::Memory;
+#[cfg(feature = "services-memory")]
+pub use memory::MemoryEvictionPolicy;
 
 #[cfg(feature = "services-mini-moka")]
 mod mini_moka;
 #[cfg(feature = "services-mini-moka")]
 pub use self::mini_moka::MiniMoka;
 
+#[cfg(feature = "services-mock")]
+mod mock;
+#[cfg(feature = "services-mock")]
+pub use mock::Mock;
+#[cfg(feature = "services-mock")]
+pub use mock::MockCall;
+
 #[cfg(feature = "services-moka")]
 mod moka;
 #[cfg(feature = "services-moka")]
@@ -137,6 +150,12 @@ pub use self::rocksdb::Rocksdb;
 #[cfg(feature = "services-s3")]
 mod s3;
 #[cfg(feature = "services-s3")]
+pub use s3::AwsAssumeRoleCredentialProvider;
+#[cfg(feature = "services-s3")]
+pub use s3::AwsCredential;
+#[cfg(feature = "services-s3")]
+pub use s3::AwsImdsCredentialProvider;
+#[cfg(feature = "services-s3")]
 pub use s3::S3;
 
 #[cfg(feature = "services-sftp")]
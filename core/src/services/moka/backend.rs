@@ -129,6 +129,14 @@ impl MokaBuilder {
 
 impl Builder for MokaBuilder {
     const SCHEME: Scheme = Scheme::Moka;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&[
+        "name",
+        "max_capacity",
+        "time_to_live",
+        "time_to_idle",
+        "num_segments",
+        "thread_pool_enabled",
+    ]);
     type Accessor = MokaBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
@@ -203,6 +203,14 @@ impl ObsBuilder {
 
 impl Builder for ObsBuilder {
     const SCHEME: Scheme = Scheme::Obs;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&[
+        "root",
+        "bucket",
+        "endpoint",
+        "access_key_id",
+        "secret_access_key",
+        "write_min_size",
+    ]);
     type Accessor = ObsBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
@@ -220,30 +228,52 @@ impl Builder for ObsBuilder {
         builder
     }
 
+    fn validate(&self) -> Result<()> {
+        if self.bucket.is_none() {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "The bucket is misconfigured")
+                    .with_operation("Builder::validate")
+                    .with_context("service", Scheme::Obs)
+                    .with_context("key", "bucket"),
+            );
+        }
+
+        if self.endpoint.is_none() {
+            return Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+                .with_operation("Builder::validate")
+                .with_context("service", Scheme::Obs)
+                .with_context("key", "endpoint"));
+        }
+
+        if self.write_min_size.unwrap_or(DEFAULT_WRITE_MIN_SIZE) < 100 * 1024 {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "The write minimum buffer size is misconfigured",
+            )
+            .with_operation("Builder::validate")
+            .with_context("service", Scheme::Obs)
+            .with_context("key", "write_min_size"));
+        }
+
+        Ok(())
+    }
+
     fn build(&mut self) -> Result<Self::Accessor> {
         debug!("backend build started: {:?}", &self);
+        self.validate()?;
 
         let root = normalize_root(&self.root.take().unwrap_or_default());
         debug!("backend use root {}", root);
 
-        let bucket = match &self.bucket {
-            Some(bucket) => Ok(bucket.to_string()),
-            None => Err(
-                Error::new(ErrorKind::ConfigInvalid, "The bucket is misconfigured")
-                    .with_context("service", Scheme::Obs),
-            ),
-        }?;
+        let bucket = self.bucket.clone().unwrap_or_default();
         debug!("backend use bucket {}", &bucket);
 
-        let uri = match &self.endpoint {
-            Some(endpoint) => endpoint.parse::<Uri>().map_err(|err| {
-                Error::new(ErrorKind::ConfigInvalid, "endpoint is invalid")
-                    .with_context("service", Scheme::Obs)
-                    .set_source(err)
-            }),
-            None => Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
-                .with_context("service", Scheme::Obs)),
-        }?;
+        let endpoint = self.endpoint.clone().unwrap_or_default();
+        let uri = endpoint.parse::<Uri>().map_err(|err| {
+            Error::new(ErrorKind::ConfigInvalid, "endpoint is invalid")
+                .with_context("service", Scheme::Obs)
+                .set_source(err)
+        })?;
 
         let scheme = match uri.scheme_str() {
             Some(scheme) => scheme.to_string(),
@@ -292,13 +322,6 @@ impl Builder for ObsBuilder {
             }
         });
         let write_min_size = self.write_min_size.unwrap_or(DEFAULT_WRITE_MIN_SIZE);
-        if write_min_size < 100 * 1024 {
-            return Err(Error::new(
-                ErrorKind::ConfigInvalid,
-                "The write minimum buffer size is misconfigured",
-            )
-            .with_context("service", Scheme::Obs));
-        }
 
         debug!("backend build finished");
         Ok(ObsBackend {
@@ -351,7 +374,12 @@ impl Accessor for ObsBackend {
                 write_can_sink: true,
                 write_with_content_type: true,
                 write_with_cache_control: true,
+                write_with_content_encoding: true,
+                write_with_content_language: true,
                 write_without_content_length: true,
+                write_with_resume: true,
+                write_multi_min_size: Some(100 * 1024),
+                write_multi_max_size: Some(5 * 1024 * 1024 * 1024),
 
                 append: true,
                 append_with_cache_control: true,
@@ -359,11 +387,14 @@ impl Accessor for ObsBackend {
                 append_with_content_disposition: true,
 
                 delete: true,
+                delete_with_version: true,
                 create_dir: true,
                 copy: true,
 
                 list: true,
                 list_with_delimiter_slash: true,
+                list_with_metadata: true,
+                list_with_sorted: true,
                 list_without_delimiter: true,
 
                 presign: true,
@@ -389,13 +420,10 @@ impl Accessor for ObsBackend {
                 v.if_match(),
                 v.if_none_match(),
             )?,
-            PresignOperation::Write(v) => self.core.obs_put_object_request(
-                path,
-                None,
-                v.content_type(),
-                v.cache_control(),
-                AsyncBody::Empty,
-            )?,
+            PresignOperation::Write(v) => {
+                self.core
+                    .obs_put_object_request(path, None, v, AsyncBody::Empty)?
+            }
         };
         self.core.sign_query(&mut req, args.expire()).await?;
 
@@ -410,9 +438,12 @@ impl Accessor for ObsBackend {
     }
 
     async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
-        let mut req =
-            self.core
-                .obs_put_object_request(path, Some(0), None, None, AsyncBody::Empty)?;
+        let mut req = self.core.obs_put_object_request(
+            path,
+            Some(0),
+            &OpWrite::default(),
+            AsyncBody::Empty,
+        )?;
 
         self.core.sign(&mut req).await?;
 
@@ -497,8 +528,8 @@ impl Accessor for ObsBackend {
         }
     }
 
-    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
-        let resp = self.core.obs_delete_object(path).await?;
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let resp = self.core.obs_delete_object(path, args.version()).await?;
 
         let status = resp.status();
 
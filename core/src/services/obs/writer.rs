@@ -16,6 +16,7 @@
 // under the License.
 
 use std::sync::Arc;
+use std::time::Instant;
 
 use async_trait::async_trait;
 use bytes::Buf;
@@ -35,13 +36,16 @@ pub struct ObsWriter {
     upload_id: Option<String>,
 
     parts: Vec<CompleteMultipartUploadRequestPart>,
+    /// The total size of uploaded parts.
+    uploaded: u64,
     buffer: oio::VectorCursor,
     buffer_size: usize,
 }
 
 impl ObsWriter {
     pub fn new(core: Arc<ObsCore>, path: &str, op: OpWrite) -> Self {
-        let buffer_size = core.write_min_size;
+        let buffer_size = op.chunk().unwrap_or(core.write_min_size);
+        let checkpoint = op.resume().cloned();
         ObsWriter {
             core,
             path: path.to_string(),
@@ -49,19 +53,37 @@ impl ObsWriter {
 
             upload_id: None,
             parts: vec![],
+            uploaded: 0,
             buffer: oio::VectorCursor::new(),
             buffer_size,
         }
+        .with_checkpoint(checkpoint)
+    }
+
+    /// Resume the multipart upload from given checkpoint.
+    ///
+    /// Parts recorded in checkpoint will be reused, and new parts will be
+    /// appended after them.
+    fn with_checkpoint(mut self, checkpoint: Option<WriteCheckpoint>) -> Self {
+        if let Some(checkpoint) = checkpoint {
+            self.upload_id = Some(checkpoint.upload_id().to_string());
+            self.parts = checkpoint
+                .parts()
+                .iter()
+                .map(|(part_number, etag)| CompleteMultipartUploadRequestPart {
+                    part_number: *part_number,
+                    etag: etag.clone(),
+                })
+                .collect();
+            self.uploaded = checkpoint.size();
+        }
+        self
     }
 
     async fn write_oneshot(&self, size: u64, body: AsyncBody) -> Result<()> {
-        let mut req = self.core.obs_put_object_request(
-            &self.path,
-            Some(size),
-            self.op.content_type(),
-            self.op.cache_control(),
-            body,
-        )?;
+        let mut req = self
+            .core
+            .obs_put_object_request(&self.path, Some(size), &self.op, body)?;
 
         self.core.sign(&mut req).await?;
 
@@ -81,7 +103,7 @@ impl ObsWriter {
     async fn initiate_upload(&self) -> Result<String> {
         let resp = self
             .core
-            .obs_initiate_multipart_upload(&self.path, self.op.content_type())
+            .obs_initiate_multipart_upload(&self.path, &self.op)
             .await?;
 
         let status = resp.status();
@@ -171,11 +193,21 @@ impl oio::Write for ObsWriter {
 
         let bs = self.buffer.peak_at_least(self.buffer_size);
         let size = bs.len();
+        let start = Instant::now();
 
         match self.write_part(upload_id, bs).await {
             Ok(part) => {
                 self.buffer.take(size);
+                oio::report_part(
+                    self.op.part_callback(),
+                    self.parts.len(),
+                    size as u64,
+                    start,
+                    0,
+                    self.buffer.len() as u64,
+                );
                 self.parts.push(part);
+                self.uploaded += size as u64;
                 Ok(())
             }
             Err(e) => {
@@ -230,11 +262,22 @@ impl oio::Write for ObsWriter {
         // Make sure internal buffer has been flushed.
         if !self.buffer.is_empty() {
             let bs = self.buffer.peak_exact(self.buffer.len());
+            let size = bs.len() as u64;
+            let start = Instant::now();
 
             match self.write_part(upload_id, bs).await {
                 Ok(part) => {
                     self.buffer.clear();
+                    oio::report_part(
+                        self.op.part_callback(),
+                        self.parts.len(),
+                        size,
+                        start,
+                        0,
+                        self.buffer.len() as u64,
+                    );
                     self.parts.push(part);
+                    self.uploaded += size;
                 }
                 Err(e) => {
                     return Err(e);
@@ -258,4 +301,14 @@ impl oio::Write for ObsWriter {
             _ => Err(parse_error(resp).await?),
         }
     }
+
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        let upload_id = self.upload_id.as_deref()?;
+        let parts = self
+            .parts
+            .iter()
+            .map(|p| (p.part_number, p.etag.clone()))
+            .collect();
+        Some(WriteCheckpoint::new(upload_id, parts, self.uploaded))
+    }
 }
@@ -115,6 +115,7 @@ impl OnedriveBuilder {
 
 impl Builder for OnedriveBuilder {
     const SCHEME: Scheme = Scheme::Onedrive;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&["root", "access_token"]);
 
     type Accessor = OnedriveBackend;
 
@@ -127,7 +128,19 @@ impl Builder for OnedriveBuilder {
         builder
     }
 
+    fn validate(&self) -> Result<()> {
+        if self.access_token.is_none() {
+            return Err(Error::new(ErrorKind::ConfigInvalid, "access_token not set")
+                .with_operation("Builder::validate")
+                .with_context("service", Scheme::Onedrive)
+                .with_context("key", "access_token"));
+        }
+
+        Ok(())
+    }
+
     fn build(&mut self) -> Result<Self::Accessor> {
+        self.validate()?;
         let root = normalize_root(&self.root.take().unwrap_or_default());
         debug!("backend use root {}", root);
 
@@ -140,9 +153,7 @@ impl Builder for OnedriveBuilder {
             })?
         };
 
-        match self.access_token.clone() {
-            Some(access_token) => Ok(OnedriveBackend::new(root, access_token, client)),
-            None => Err(Error::new(ErrorKind::ConfigInvalid, "access_token not set")),
-        }
+        let access_token = self.access_token.clone().unwrap_or_default();
+        Ok(OnedriveBackend::new(root, access_token, client))
     }
 }
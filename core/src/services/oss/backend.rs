@@ -252,6 +252,18 @@ impl OssBuilder {
 
 impl Builder for OssBuilder {
     const SCHEME: Scheme = Scheme::Oss;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&[
+        "root",
+        "bucket",
+        "endpoint",
+        "presign_endpoint",
+        "access_key_id",
+        "access_key_secret",
+        "server_side_encryption",
+        "server_side_encryption_key_id",
+        "write_min_size",
+        "batch_max_operations",
+    ]);
     type Accessor = OssBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
@@ -276,20 +288,45 @@ impl Builder for OssBuilder {
         builder
     }
 
+    fn validate(&self) -> Result<()> {
+        if self.bucket.is_empty() {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "The bucket is misconfigured")
+                    .with_operation("Builder::validate")
+                    .with_context("service", Scheme::Oss)
+                    .with_context("key", "bucket"),
+            );
+        }
+
+        if self.endpoint.is_none() {
+            return Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+                .with_operation("Builder::validate")
+                .with_context("service", Scheme::Oss)
+                .with_context("key", "endpoint"));
+        }
+
+        if self.write_min_size.unwrap_or(DEFAULT_WRITE_MIN_SIZE) < 5 * 1024 * 1024 {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "The write minimum buffer size is misconfigured",
+            )
+            .with_operation("Builder::validate")
+            .with_context("service", Scheme::Oss)
+            .with_context("key", "write_min_size"));
+        }
+
+        Ok(())
+    }
+
     fn build(&mut self) -> Result<Self::Accessor> {
         debug!("backend build started: {:?}", &self);
+        self.validate()?;
 
         let root = normalize_root(&self.root.clone().unwrap_or_default());
         debug!("backend use root {}", &root);
 
         // Handle endpoint, region and bucket name.
-        let bucket = match self.bucket.is_empty() {
-            false => Ok(&self.bucket),
-            true => Err(
-                Error::new(ErrorKind::ConfigInvalid, "The bucket is misconfigured")
-                    .with_context("service", Scheme::Oss),
-            ),
-        }?;
+        let bucket = &self.bucket;
 
         let client = if let Some(client) = self.http_client.take() {
             client
@@ -345,13 +382,6 @@ impl Builder for OssBuilder {
         let signer = AliyunOssSigner::new(bucket);
 
         let write_min_size = self.write_min_size.unwrap_or(DEFAULT_WRITE_MIN_SIZE);
-        if write_min_size < 5 * 1024 * 1024 {
-            return Err(Error::new(
-                ErrorKind::ConfigInvalid,
-                "The write minimum buffer size is misconfigured",
-            )
-            .with_context("service", Scheme::Oss));
-        }
         let batch_max_operations = self
             .batch_max_operations
             .unwrap_or(DEFAULT_BATCH_MAX_OPERATIONS);
@@ -411,9 +441,15 @@ impl Accessor for OssBackend {
                 write: true,
                 write_can_sink: true,
                 write_with_cache_control: true,
+                write_with_content_encoding: true,
+                write_with_content_language: true,
                 write_with_content_type: true,
                 write_without_content_length: true,
+                write_with_resume: true,
+                write_multi_min_size: Some(100 * 1024),
+                write_multi_max_size: Some(5 * 1024 * 1024 * 1024),
                 delete: true,
+                delete_with_version: true,
                 create_dir: true,
                 copy: true,
 
@@ -424,6 +460,8 @@ impl Accessor for OssBackend {
 
                 list: true,
                 list_with_delimiter_slash: true,
+                list_with_metadata: true,
+                list_with_sorted: true,
                 list_without_delimiter: true,
 
                 presign: true,
@@ -443,7 +481,7 @@ impl Accessor for OssBackend {
     async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
         let resp = self
             .core
-            .oss_put_object(path, None, None, None, None, AsyncBody::Empty)
+            .oss_put_object(path, None, &OpWrite::default(), AsyncBody::Empty)
             .await?;
         let status = resp.status();
 
@@ -530,8 +568,8 @@ impl Accessor for OssBackend {
         }
     }
 
-    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
-        let resp = self.core.oss_delete_object(path).await?;
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let resp = self.core.oss_delete_object(path, args.version()).await?;
         let status = resp.status();
         match status {
             StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => {
@@ -564,15 +602,10 @@ impl Accessor for OssBackend {
                 v.if_none_match(),
                 v.override_content_disposition(),
             )?,
-            PresignOperation::Write(v) => self.core.oss_put_object_request(
-                path,
-                None,
-                v.content_type(),
-                v.content_disposition(),
-                v.cache_control(),
-                AsyncBody::Empty,
-                true,
-            )?,
+            PresignOperation::Write(v) => {
+                self.core
+                    .oss_put_object_request(path, None, v, AsyncBody::Empty, true)?
+            }
         };
 
         self.core.sign_query(&mut req, args.expire()).await?;
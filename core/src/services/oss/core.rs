@@ -22,6 +22,8 @@ use std::time::Duration;
 use bytes::Bytes;
 use http::header::CACHE_CONTROL;
 use http::header::CONTENT_DISPOSITION;
+use http::header::CONTENT_ENCODING;
+use http::header::CONTENT_LANGUAGE;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
 use http::header::IF_MATCH;
@@ -148,14 +150,39 @@ impl OssCore {
 }
 
 impl OssCore {
-    #[allow(clippy::too_many_arguments)]
+    /// Set the object headers that can be specified on write.
+    fn insert_write_headers(
+        mut req: http::request::Builder,
+        args: &OpWrite,
+    ) -> http::request::Builder {
+        if let Some(mime) = args.content_type() {
+            req = req.header(CONTENT_TYPE, mime);
+        }
+
+        if let Some(pos) = args.content_disposition() {
+            req = req.header(CONTENT_DISPOSITION, pos);
+        }
+
+        if let Some(encoding) = args.content_encoding() {
+            req = req.header(CONTENT_ENCODING, encoding);
+        }
+
+        if let Some(language) = args.content_language() {
+            req = req.header(CONTENT_LANGUAGE, language);
+        }
+
+        if let Some(cache_control) = args.cache_control() {
+            req = req.header(CACHE_CONTROL, cache_control)
+        }
+
+        req
+    }
+
     pub fn oss_put_object_request(
         &self,
         path: &str,
         size: Option<u64>,
-        content_type: Option<&str>,
-        content_disposition: Option<&str>,
-        cache_control: Option<&str>,
+        args: &OpWrite,
         body: AsyncBody,
         is_presign: bool,
     ) -> Result<Request<AsyncBody>> {
@@ -167,17 +194,7 @@ impl OssCore {
 
         req = req.header(CONTENT_LENGTH, size.unwrap_or_default());
 
-        if let Some(mime) = content_type {
-            req = req.header(CONTENT_TYPE, mime);
-        }
-
-        if let Some(pos) = content_disposition {
-            req = req.header(CONTENT_DISPOSITION, pos);
-        }
-
-        if let Some(cache_control) = cache_control {
-            req = req.header(CACHE_CONTROL, cache_control)
-        }
+        req = Self::insert_write_headers(req, args);
 
         // set sse headers
         req = self.insert_sse_headers(req);
@@ -277,10 +294,17 @@ impl OssCore {
         Ok(req)
     }
 
-    fn oss_delete_object_request(&self, path: &str) -> Result<Request<AsyncBody>> {
+    fn oss_delete_object_request(
+        &self,
+        path: &str,
+        version: Option<&str>,
+    ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
         let endpoint = self.get_endpoint(false);
-        let url = format!("{}/{}", endpoint, percent_encode_path(&p));
+        let mut url = format!("{}/{}", endpoint, percent_encode_path(&p));
+        if let Some(version) = version {
+            url.push_str(&format!("?versionId={}", percent_encode_path(version)));
+        }
         let req = Request::delete(&url);
 
         let req = req
@@ -377,20 +401,10 @@ impl OssCore {
         &self,
         path: &str,
         size: Option<u64>,
-        content_type: Option<&str>,
-        content_disposition: Option<&str>,
-        cache_control: Option<&str>,
+        args: &OpWrite,
         body: AsyncBody,
     ) -> Result<Response<IncomingAsyncBody>> {
-        let mut req = self.oss_put_object_request(
-            path,
-            size,
-            content_type,
-            content_disposition,
-            cache_control,
-            body,
-            false,
-        )?;
+        let mut req = self.oss_put_object_request(path, size, args, body, false)?;
 
         self.sign(&mut req).await?;
         self.send(req).await
@@ -438,8 +452,12 @@ impl OssCore {
         self.send(req).await
     }
 
-    pub async fn oss_delete_object(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
-        let mut req = self.oss_delete_object_request(path)?;
+    pub async fn oss_delete_object(
+        &self,
+        path: &str,
+        version: Option<&str>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = self.oss_delete_object_request(path, version)?;
         self.sign(&mut req).await?;
         self.send(req).await
     }
@@ -491,9 +509,8 @@ impl OssCore {
         path: &str,
         args: &OpWrite,
     ) -> Result<Response<IncomingAsyncBody>> {
-        let cache_control = args.cache_control();
         let req = self
-            .oss_initiate_upload_request(path, None, None, cache_control, AsyncBody::Empty, false)
+            .oss_initiate_upload_request(path, args, AsyncBody::Empty, false)
             .await?;
         self.send(req).await
     }
@@ -502,9 +519,7 @@ impl OssCore {
     async fn oss_initiate_upload_request(
         &self,
         path: &str,
-        content_type: Option<&str>,
-        content_disposition: Option<&str>,
-        cache_control: Option<&str>,
+        args: &OpWrite,
         body: AsyncBody,
         is_presign: bool,
     ) -> Result<Request<AsyncBody>> {
@@ -512,15 +527,7 @@ impl OssCore {
         let endpoint = self.get_endpoint(is_presign);
         let url = format!("{}/{}?uploads", endpoint, percent_encode_path(&path));
         let mut req = Request::post(&url);
-        if let Some(mime) = content_type {
-            req = req.header(CONTENT_TYPE, mime);
-        }
-        if let Some(disposition) = content_disposition {
-            req = req.header(CONTENT_DISPOSITION, disposition);
-        }
-        if let Some(cache_control) = cache_control {
-            req = req.header(CACHE_CONTROL, cache_control);
-        }
+        req = Self::insert_write_headers(req, args);
         req = self.insert_sse_headers(req);
         let mut req = req.body(body).map_err(new_request_build_error)?;
         self.sign(&mut req).await?;
@@ -64,6 +64,7 @@ impl PersyBuilder {
 
 impl Builder for PersyBuilder {
     const SCHEME: Scheme = Scheme::Persy;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&["datafile", "segment", "index"]);
     type Accessor = PersyBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
@@ -76,23 +77,35 @@ impl Builder for PersyBuilder {
         builder
     }
 
-    fn build(&mut self) -> Result<Self::Accessor> {
-        let datafile_path = self.datafile.take().ok_or_else(|| {
-            Error::new(ErrorKind::ConfigInvalid, "datafile is required but not set")
+    fn validate(&self) -> Result<()> {
+        for (key, value) in [
+            ("datafile", &self.datafile),
+            ("segment", &self.segment),
+            ("index", &self.index),
+        ] {
+            if value.is_none() {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    &format!("{key} is required but not set"),
+                )
+                .with_operation("Builder::validate")
                 .with_context("service", Scheme::Persy)
-        })?;
+                .with_context("key", key));
+            }
+        }
 
-        let segment_name = self.segment.take().ok_or_else(|| {
-            Error::new(ErrorKind::ConfigInvalid, "segment is required but not set")
-                .with_context("service", Scheme::Persy)
-        })?;
+        Ok(())
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        self.validate()?;
+        let datafile_path = self.datafile.take().unwrap_or_default();
+
+        let segment_name = self.segment.take().unwrap_or_default();
 
         let segment = segment_name.clone();
 
-        let index_name = self.index.take().ok_or_else(|| {
-            Error::new(ErrorKind::ConfigInvalid, "index is required but not set")
-                .with_context("service", Scheme::Persy)
-        })?;
+        let index_name = self.index.take().unwrap_or_default();
 
         let index = index_name.clone();
 
@@ -62,6 +62,7 @@ impl RedbBuilder {
 
 impl Builder for RedbBuilder {
     const SCHEME: Scheme = Scheme::Redb;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&["datadir", "table", "root"]);
     type Accessor = RedbBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
@@ -74,16 +75,27 @@ impl Builder for RedbBuilder {
         builder
     }
 
-    fn build(&mut self) -> Result<Self::Accessor> {
-        let datadir_path = self.datadir.take().ok_or_else(|| {
-            Error::new(ErrorKind::ConfigInvalid, "datadir is required but not set")
+    fn validate(&self) -> Result<()> {
+        for (key, value) in [("datadir", &self.datadir), ("table", &self.table)] {
+            if value.is_none() {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    &format!("{key} is required but not set"),
+                )
+                .with_operation("Builder::validate")
                 .with_context("service", Scheme::Redb)
-        })?;
+                .with_context("key", key));
+            }
+        }
 
-        let table_name = self.table.take().ok_or_else(|| {
-            Error::new(ErrorKind::ConfigInvalid, "table is required but not set")
-                .with_context("service", Scheme::Redb)
-        })?;
+        Ok(())
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        self.validate()?;
+        let datadir_path = self.datadir.take().unwrap_or_default();
+
+        let table_name = self.table.take().unwrap_or_default();
 
         let db = redb::Database::create(&datadir_path).map_err(parse_database_error)?;
 
@@ -19,17 +19,28 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::path::PathBuf;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
 use http::Uri;
+use redis::aio::ConnectionLike;
 use redis::aio::ConnectionManager;
+use redis::cluster::ClusterClient;
+use redis::cluster::ClusterClientBuilder;
+use redis::cluster_async::ClusterConnection;
 use redis::AsyncCommands;
 use redis::Client;
+use redis::Cmd;
 use redis::ConnectionAddr;
 use redis::ConnectionInfo;
+use redis::Pipeline;
 use redis::RedisConnectionInfo;
 use redis::RedisError;
+use redis::RedisFuture;
+use redis::Value;
 use tokio::sync::OnceCell;
 
 use crate::raw::adapters::kv;
@@ -65,6 +76,14 @@ pub struct RedisBuilder {
     db: i64,
     /// The default ttl for put operations.
     default_ttl: Option<Duration>,
+    /// network addresses of the Redis Cluster nodes, separated by `,`.
+    ///
+    /// default is None, which means cluster mode is disabled.
+    cluster_endpoints: Option<String>,
+    /// the number of connections to the redis service.
+    ///
+    /// default is 1
+    pool_size: Option<usize>,
 }
 
 impl Debug for RedisBuilder {
@@ -75,6 +94,12 @@ impl Debug for RedisBuilder {
         if let Some(endpoint) = self.endpoint.clone() {
             ds.field("endpoint", &endpoint);
         }
+        if let Some(cluster_endpoints) = self.cluster_endpoints.clone() {
+            ds.field("cluster_endpoints", &cluster_endpoints);
+        }
+        if let Some(pool_size) = self.pool_size {
+            ds.field("pool_size", &pool_size);
+        }
         if let Some(username) = self.username.clone() {
             ds.field("username", &username);
         }
@@ -99,6 +124,37 @@ impl RedisBuilder {
         self
     }
 
+    /// set the network addresses of the Redis Cluster nodes, separated by `,`.
+    ///
+    /// For example: "tcp://127.0.0.1:7000,tcp://127.0.0.1:7001"
+    ///
+    /// If set, cluster mode will be enabled and `endpoint` will be ignored.
+    /// Redis Cluster only supports db 0.
+    ///
+    /// default: no cluster endpoints
+    pub fn cluster_endpoints(&mut self, cluster_endpoints: &str) -> &mut Self {
+        if !cluster_endpoints.is_empty() {
+            self.cluster_endpoints = Some(cluster_endpoints.to_owned());
+        }
+        self
+    }
+
+    /// set the number of connections to the redis service.
+    ///
+    /// Every connection is multiplexed and requests will be spread over
+    /// connections in round-robin. So requests will never wait for an idle
+    /// connection or fail because the pool is exhausted, they are pipelined
+    /// on the chosen connection instead. Broken connections will be
+    /// reconnected automatically on the next request.
+    ///
+    /// default: 1
+    pub fn pool_size(&mut self, pool_size: usize) -> &mut Self {
+        if pool_size > 0 {
+            self.pool_size = Some(pool_size);
+        }
+        self
+    }
+
     /// set the username for redis
     ///
     /// default: no username
@@ -148,6 +204,15 @@ impl RedisBuilder {
 
 impl Builder for RedisBuilder {
     const SCHEME: Scheme = Scheme::Redis;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&[
+        "root",
+        "endpoint",
+        "cluster_endpoints",
+        "username",
+        "password",
+        "db",
+        "pool_size",
+    ]);
     type Accessor = RedisBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
@@ -159,76 +224,30 @@ impl Builder for RedisBuilder {
         map.get("password").map(|v| builder.password(v));
         map.get("db")
             .map(|v| v.parse::<i64>().map(|v| builder.db(v)));
+        map.get("cluster_endpoints")
+            .map(|v| builder.cluster_endpoints(v));
+        map.get("pool_size")
+            .map(|v| v.parse::<usize>().map(|v| builder.pool_size(v)));
 
         builder
     }
 
-    fn build(&mut self) -> Result<Self::Accessor> {
-        let endpoint = self
-            .endpoint
-            .clone()
-            .unwrap_or_else(|| DEFAULT_REDIS_ENDPOINT.to_string());
-
-        let ep_url = endpoint.parse::<Uri>().map_err(|e| {
-            Error::new(ErrorKind::ConfigInvalid, "endpoint is invalid")
-                .with_context("service", Scheme::Redis)
-                .with_context("endpoint", endpoint)
-                .set_source(e)
-        })?;
-
-        let con_addr = match ep_url.scheme_str() {
-            Some("tcp") | Some("redis") | None => {
-                let host = ep_url
-                    .host()
-                    .map(|h| h.to_string())
-                    .unwrap_or_else(|| "127.0.0.1".to_string());
-                let port = ep_url.port_u16().unwrap_or(DEFAULT_REDIS_PORT);
-                ConnectionAddr::Tcp(host, port)
-            }
-            Some("rediss") => {
-                let host = ep_url
-                    .host()
-                    .map(|h| h.to_string())
-                    .unwrap_or_else(|| "127.0.0.1".to_string());
-                let port = ep_url.port_u16().unwrap_or(DEFAULT_REDIS_PORT);
-                ConnectionAddr::TcpTls {
-                    host,
-                    port,
-                    insecure: false,
-                }
-            }
-            Some("unix") | Some("redis+unix") => {
-                let path = PathBuf::from(ep_url.path());
-                ConnectionAddr::Unix(path)
-            }
-            Some(s) => {
-                return Err(
-                    Error::new(ErrorKind::ConfigInvalid, "invalid or unsupported scheme")
-                        .with_context("service", Scheme::Redis)
-                        .with_context("scheme", s),
-                )
-            }
-        };
-
-        let redis_info = RedisConnectionInfo {
-            db: self.db,
-            username: self.username.clone(),
-            password: self.password.clone(),
-        };
-
-        let con_info = ConnectionInfo {
-            addr: con_addr,
-            redis: redis_info,
-        };
+    fn validate(&self) -> Result<()> {
+        if self.cluster_endpoints.is_some() && self.db != 0 {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "redis cluster only supports db 0")
+                    .with_operation("Builder::validate")
+                    .with_context("service", Scheme::Redis)
+                    .with_context("key", "db")
+                    .with_context("db", self.db.to_string()),
+            );
+        }
 
-        let client = Client::open(con_info).map_err(|e| {
-            Error::new(ErrorKind::ConfigInvalid, "invalid or unsupported scheme")
-                .with_context("service", Scheme::Redis)
-                .with_context("endpoint", self.endpoint.as_ref().unwrap())
-                .with_context("db", self.db.to_string())
-                .set_source(e)
-        })?;
+        Ok(())
+    }
 
+    fn build(&mut self) -> Result<Self::Accessor> {
+        self.validate()?;
         let root = normalize_root(
             self.root
                 .clone()
@@ -236,23 +255,175 @@ impl Builder for RedisBuilder {
                 .as_str(),
         );
 
-        let conn = OnceCell::new();
+        let client = if let Some(cluster_endpoints) = &self.cluster_endpoints {
+            let mut nodes = Vec::new();
+            for endpoint in cluster_endpoints
+                .split(',')
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty())
+            {
+                nodes.push(ConnectionInfo {
+                    addr: parse_connection_addr(endpoint)?,
+                    redis: self.redis_connection_info(),
+                });
+            }
+
+            let mut builder = ClusterClientBuilder::new(nodes);
+            if let Some(username) = &self.username {
+                builder = builder.username(username.clone());
+            }
+            if let Some(password) = &self.password {
+                builder = builder.password(password.clone());
+            }
+            let client = builder.build().map_err(|e| {
+                Error::new(ErrorKind::ConfigInvalid, "invalid cluster endpoints")
+                    .with_context("service", Scheme::Redis)
+                    .with_context("cluster_endpoints", cluster_endpoints)
+                    .set_source(e)
+            })?;
+
+            RedisClient::Cluster(cluster_endpoints.clone(), client)
+        } else {
+            let endpoint = self
+                .endpoint
+                .clone()
+                .unwrap_or_else(|| DEFAULT_REDIS_ENDPOINT.to_string());
+
+            let con_info = ConnectionInfo {
+                addr: parse_connection_addr(&endpoint)?,
+                redis: self.redis_connection_info(),
+            };
+
+            let client = Client::open(con_info).map_err(|e| {
+                Error::new(ErrorKind::ConfigInvalid, "invalid or unsupported scheme")
+                    .with_context("service", Scheme::Redis)
+                    .with_context("endpoint", &endpoint)
+                    .with_context("db", self.db.to_string())
+                    .set_source(e)
+            })?;
+
+            RedisClient::Normal(client)
+        };
+
         Ok(RedisBackend::new(Adapter {
             client,
-            conn,
+            pool_size: self.pool_size.unwrap_or(1),
+            conns: OnceCell::new(),
+            next: Arc::new(AtomicUsize::new(0)),
             default_ttl: self.default_ttl,
         })
         .with_root(&root))
     }
 }
 
+impl RedisBuilder {
+    fn redis_connection_info(&self) -> RedisConnectionInfo {
+        RedisConnectionInfo {
+            db: self.db,
+            username: self.username.clone(),
+            password: self.password.clone(),
+        }
+    }
+}
+
+/// Parse the connection address from given endpoint.
+fn parse_connection_addr(endpoint: &str) -> Result<ConnectionAddr> {
+    let ep_url = endpoint.parse::<Uri>().map_err(|e| {
+        Error::new(ErrorKind::ConfigInvalid, "endpoint is invalid")
+            .with_context("service", Scheme::Redis)
+            .with_context("endpoint", endpoint)
+            .set_source(e)
+    })?;
+
+    let con_addr = match ep_url.scheme_str() {
+        Some("tcp") | Some("redis") | None => {
+            let host = ep_url
+                .host()
+                .map(|h| h.to_string())
+                .unwrap_or_else(|| "127.0.0.1".to_string());
+            let port = ep_url.port_u16().unwrap_or(DEFAULT_REDIS_PORT);
+            ConnectionAddr::Tcp(host, port)
+        }
+        Some("rediss") => {
+            let host = ep_url
+                .host()
+                .map(|h| h.to_string())
+                .unwrap_or_else(|| "127.0.0.1".to_string());
+            let port = ep_url.port_u16().unwrap_or(DEFAULT_REDIS_PORT);
+            ConnectionAddr::TcpTls {
+                host,
+                port,
+                insecure: false,
+            }
+        }
+        Some("unix") | Some("redis+unix") => {
+            let path = PathBuf::from(ep_url.path());
+            ConnectionAddr::Unix(path)
+        }
+        Some(s) => {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "invalid or unsupported scheme")
+                    .with_context("service", Scheme::Redis)
+                    .with_context("scheme", s),
+            )
+        }
+    };
+
+    Ok(con_addr)
+}
+
 /// Backend for redis services.
 pub type RedisBackend = kv::Backend<Adapter>;
 
+#[derive(Clone)]
+enum RedisClient {
+    Normal(Client),
+    /// Cluster client with its endpoints.
+    Cluster(String, ClusterClient),
+}
+
+/// RedisConnection is the connection to either a single redis server
+/// or a redis cluster.
+#[derive(Clone)]
+enum RedisConnection {
+    Normal(ConnectionManager),
+    Cluster(ClusterConnection),
+}
+
+impl ConnectionLike for RedisConnection {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
+        match self {
+            RedisConnection::Normal(conn) => conn.req_packed_command(cmd),
+            RedisConnection::Cluster(conn) => conn.req_packed_command(cmd),
+        }
+    }
+
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        cmd: &'a Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> RedisFuture<'a, Vec<Value>> {
+        match self {
+            RedisConnection::Normal(conn) => conn.req_packed_commands(cmd, offset, count),
+            RedisConnection::Cluster(conn) => conn.req_packed_commands(cmd, offset, count),
+        }
+    }
+
+    fn get_db(&self) -> i64 {
+        match self {
+            RedisConnection::Normal(conn) => conn.get_db(),
+            RedisConnection::Cluster(conn) => conn.get_db(),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Adapter {
-    client: Client,
-    conn: OnceCell<ConnectionManager>,
+    client: RedisClient,
+    pool_size: usize,
+    conns: OnceCell<Vec<RedisConnection>>,
+    next: Arc<AtomicUsize>,
 
     default_ttl: Option<Duration>,
 }
@@ -262,21 +433,55 @@ impl Debug for Adapter {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut ds = f.debug_struct("Adapter");
 
-        let info = self.client.get_connection_info();
-        ds.field("addr", &info.addr);
-        ds.field("db", &info.redis.db);
-        ds.field("user", &info.redis.username);
+        match &self.client {
+            RedisClient::Normal(client) => {
+                let info = client.get_connection_info();
+                ds.field("addr", &info.addr);
+                ds.field("db", &info.redis.db);
+                ds.field("user", &info.redis.username);
+            }
+            RedisClient::Cluster(endpoints, _) => {
+                ds.field("cluster_endpoints", endpoints);
+            }
+        }
+        ds.field("pool_size", &self.pool_size);
         ds.finish()
     }
 }
 
 impl Adapter {
-    async fn conn(&self) -> Result<ConnectionManager> {
-        Ok(self
-            .conn
-            .get_or_try_init(|| async { ConnectionManager::new(self.client.clone()).await })
-            .await?
-            .clone())
+    fn addr(&self) -> String {
+        match &self.client {
+            RedisClient::Normal(client) => client.get_connection_info().addr.to_string(),
+            RedisClient::Cluster(endpoints, _) => endpoints.clone(),
+        }
+    }
+
+    /// Pick a connection from the pool in round-robin.
+    ///
+    /// Connections will be established at the first call.
+    async fn conn(&self) -> Result<RedisConnection> {
+        let conns = self
+            .conns
+            .get_or_try_init(|| async {
+                let mut conns = Vec::with_capacity(self.pool_size);
+                for _ in 0..self.pool_size {
+                    let conn = match &self.client {
+                        RedisClient::Normal(client) => {
+                            RedisConnection::Normal(ConnectionManager::new(client.clone()).await?)
+                        }
+                        RedisClient::Cluster(_, client) => {
+                            RedisConnection::Cluster(client.get_async_connection().await?)
+                        }
+                    };
+                    conns.push(conn);
+                }
+                Ok::<_, Error>(conns)
+            })
+            .await?;
+
+        let idx = self.next.fetch_add(1, Ordering::Relaxed) % conns.len();
+        Ok(conns[idx].clone())
     }
 }
 
@@ -285,10 +490,11 @@ impl kv::Adapter for Adapter {
     fn metadata(&self) -> kv::Metadata {
         kv::Metadata::new(
             Scheme::Redis,
-            &self.client.get_connection_info().addr.to_string(),
+            &self.addr(),
             Capability {
                 read: true,
                 write: true,
+                write_with_ttl: true,
                 create_dir: true,
 
                 ..Default::default()
@@ -302,12 +508,34 @@ impl kv::Adapter for Adapter {
         Ok(bs)
     }
 
+    async fn size(&self, key: &str) -> Result<Option<u64>> {
+        let mut conn = self.conn().await?;
+        // `STRLEN` returns 0 for absent keys, so we check `EXISTS` together.
+        let (exists, size): (bool, u64) = redis::pipe()
+            .exists(key)
+            .strlen(key)
+            .query_async(&mut conn)
+            .await?;
+        Ok(exists.then_some(size))
+    }
+
     async fn set(&self, key: &str, value: &[u8]) -> Result<()> {
         let mut conn = self.conn().await?;
-        match self.default_ttl {
-            Some(ttl) => conn.set_ex(key, value, ttl.as_secs() as usize).await?,
+        let _: () = match self.default_ttl {
+            Some(ttl) => {
+                conn.set_ex(key, value, kv::parse_ttl(Scheme::Redis, ttl)? as usize)
+                    .await?
+            }
             None => conn.set(key, value).await?,
-        }
+        };
+        Ok(())
+    }
+
+    async fn set_with_ttl(&self, key: &str, value: &[u8], ttl: Duration) -> Result<()> {
+        let mut conn = self.conn().await?;
+        let _: () = conn
+            .set_ex(key, value, kv::parse_ttl(Scheme::Redis, ttl)? as usize)
+            .await?;
         Ok(())
     }
 
@@ -317,9 +545,16 @@ impl kv::Adapter for Adapter {
         Ok(())
     }
 
+    async fn ttl(&self, key: &str) -> Result<Option<Duration>> {
+        let mut conn = self.conn().await?;
+        // `PTTL` returns -1 for keys without expiration and -2 for absent keys.
+        let ttl: i64 = conn.pttl(key).await?;
+        Ok((ttl >= 0).then(|| Duration::from_millis(ttl as u64)))
+    }
+
     async fn append(&self, key: &str, value: &[u8]) -> Result<()> {
         let mut conn = self.conn().await?;
-        conn.append(key, value).await?;
+        let _: () = conn.append(key, value).await?;
         Ok(())
     }
 }
@@ -59,6 +59,7 @@ impl RocksdbBuilder {
 
 impl Builder for RocksdbBuilder {
     const SCHEME: Scheme = Scheme::Rocksdb;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&["datadir"]);
     type Accessor = RocksdbBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
@@ -69,11 +70,22 @@ impl Builder for RocksdbBuilder {
         builder
     }
 
+    fn validate(&self) -> Result<()> {
+        if self.datadir.is_none() {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "datadir is required but not set")
+                    .with_operation("Builder::validate")
+                    .with_context("service", Scheme::Rocksdb)
+                    .with_context("key", "datadir"),
+            );
+        }
+
+        Ok(())
+    }
+
     fn build(&mut self) -> Result<Self::Accessor> {
-        let path = self.datadir.take().ok_or_else(|| {
-            Error::new(ErrorKind::ConfigInvalid, "datadir is required but not set")
-                .with_context("service", Scheme::Rocksdb)
-        })?;
+        self.validate()?;
+        let path = self.datadir.take().unwrap_or_default();
         let db = DB::open_default(&path).map_err(|e| {
             Error::new(ErrorKind::ConfigInvalid, "open default transaction db")
                 .with_context("service", Scheme::Rocksdb)
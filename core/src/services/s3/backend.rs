@@ -20,6 +20,7 @@ use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::fmt::Write;
 use std::sync::Arc;
+use std::sync::RwLock;
 
 use async_trait::async_trait;
 use base64::prelude::BASE64_STANDARD;
@@ -35,10 +36,12 @@ use reqsign::AwsAssumeRoleLoader;
 use reqsign::AwsConfig;
 use reqsign::AwsCredentialLoad;
 use reqsign::AwsDefaultLoader;
-use reqsign::AwsV4Signer;
 
 use super::core::*;
+use super::credential::AwsCredential;
+use super::credential::CredentialProviderLoad;
 use super::error::parse_error;
+use super::error::parse_object_lock_error;
 use super::error::parse_s3_error_code;
 use super::pager::S3Pager;
 use super::writer::S3Writer;
@@ -81,6 +84,7 @@ pub struct S3Builder {
     disable_ec2_metadata: bool,
     allow_anonymous: bool,
     customed_credential_load: Option<Box<dyn AwsCredentialLoad>>,
+    credential_provider: Option<Box<dyn CredentialProvider<AwsCredential>>>,
 
     // S3 features flags
     server_side_encryption: Option<String>,
@@ -90,6 +94,8 @@ pub struct S3Builder {
     server_side_encryption_customer_key_md5: Option<String>,
     default_storage_class: Option<String>,
     enable_virtual_host_style: bool,
+    force_path_style: bool,
+    enable_request_payer: bool,
 
     /// the part size of s3 multipart upload, which should be 5 MiB to 5 GiB.
     /// There is no minimum size limit on the last part of your multipart upload
@@ -431,11 +437,56 @@ impl S3Builder {
     ///
     /// - By default, opendal will send API to `https://s3.us-east-1.amazonaws.com/bucket_name`
     /// - Enabled, opendal will send API to `https://bucket_name.s3.us-east-1.amazonaws.com`
+    ///
+    /// If neither `enable_virtual_host_style` nor `force_path_style` is set, opendal
+    /// will use virtual host style only if the endpoint already contains the bucket
+    /// name like `https://bucket_name.s3.us-east-1.amazonaws.com`.
     pub fn enable_virtual_host_style(&mut self) -> &mut Self {
         self.enable_virtual_host_style = true;
         self
     }
 
+    /// Force path style so that opendal will always send API requests in path
+    /// style like `https://s3.us-east-1.amazonaws.com/bucket_name`, even if the
+    /// endpoint contains the bucket name.
+    ///
+    /// Most S3 compatible services like MinIO require path style. This option
+    /// conflicts with `enable_virtual_host_style`.
+    pub fn force_path_style(&mut self) -> &mut Self {
+        self.force_path_style = true;
+        self
+    }
+
+    /// Decide whether to use virtual host style.
+    fn is_virtual_host_style(&self) -> bool {
+        if self.force_path_style {
+            return false;
+        }
+        if self.enable_virtual_host_style {
+            return true;
+        }
+
+        // Auto detect: use virtual host style if user's endpoint contains
+        // bucket name already.
+        match &self.endpoint {
+            Some(endpoint) if !self.bucket.is_empty() => {
+                let host = endpoint.split("://").last().unwrap_or(endpoint);
+                host.starts_with(&format!("{}.", self.bucket))
+            }
+            _ => false,
+        }
+    }
+
+    /// Enable request payer so that opendal will send `x-amz-request-payer: requester`
+    /// in every request.
+    ///
+    /// This is required to access buckets with [Requester Pays](https://docs.aws.amazon.com/AmazonS3/latest/userguide/RequesterPaysBuckets.html)
+    /// enabled, the requester will be charged for the request and data transfer.
+    pub fn enable_request_payer(&mut self) -> &mut Self {
+        self.enable_request_payer = true;
+        self
+    }
+
     /// Adding a customed credential load for service.
     ///
     /// If customed_credential_load has been set, we will ignore all other
@@ -445,6 +496,28 @@ impl S3Builder {
         self
     }
 
+    /// Use given [`CredentialProvider`] to load credentials for service.
+    ///
+    /// Credentials returned by the provider will be cached until they are
+    /// about to expire. Like `customed_credential_load`, all other credential
+    /// load methods except `role_arn` will be ignored.
+    ///
+    /// This can't be used together with `customed_credential_load`, `build`
+    /// will return a `ConfigInvalid` error if both of them are set.
+    ///
+    /// Built-in providers [`AwsImdsCredentialProvider`] and
+    /// [`AwsAssumeRoleCredentialProvider`] are available.
+    ///
+    /// [`AwsImdsCredentialProvider`]: crate::services::AwsImdsCredentialProvider
+    /// [`AwsAssumeRoleCredentialProvider`]: crate::services::AwsAssumeRoleCredentialProvider
+    pub fn credential_provider(
+        &mut self,
+        provider: impl CredentialProvider<AwsCredential>,
+    ) -> &mut Self {
+        self.credential_provider = Some(Box::new(provider));
+        self
+    }
+
     /// Specify the http client that used by this service.
     ///
     /// # Notes
@@ -466,7 +539,7 @@ impl S3Builder {
         // If enable virtual host style, `bucket` will reside in domain part,
         // for example `https://bucket_name.s3.us-east-1.amazonaws.com`,
         // so `bucket` with dot can't be recognized correctly for this format.
-        if self.enable_virtual_host_style && self.bucket.contains('.') {
+        if self.is_virtual_host_style() && self.bucket.contains('.') {
             return false;
         }
         true
@@ -505,7 +578,7 @@ impl S3Builder {
         };
 
         // Apply virtual host style.
-        if self.enable_virtual_host_style {
+        if self.is_virtual_host_style() {
             endpoint = endpoint.replace("//", &format!("//{bucket}."))
         } else {
             write!(endpoint, "/{bucket}").expect("write into string must succeed");
@@ -641,6 +714,31 @@ impl S3Builder {
 
 impl Builder for S3Builder {
     const SCHEME: Scheme = Scheme::S3;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&[
+        "root",
+        "bucket",
+        "endpoint",
+        "region",
+        "access_key_id",
+        "secret_access_key",
+        "security_token",
+        "role_arn",
+        "external_id",
+        "server_side_encryption",
+        "server_side_encryption_aws_kms_key_id",
+        "server_side_encryption_customer_algorithm",
+        "server_side_encryption_customer_key",
+        "server_side_encryption_customer_key_md5",
+        "disable_config_load",
+        "disable_ec2_metadata",
+        "enable_virtual_host_style",
+        "force_path_style",
+        "enable_request_payer",
+        "allow_anonymous",
+        "default_storage_class",
+        "write_min_size",
+        "batch_max_operations",
+    ]);
     type Accessor = S3Backend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
@@ -675,6 +773,12 @@ impl Builder for S3Builder {
         map.get("enable_virtual_host_style")
             .filter(|v| *v == "on" || *v == "true")
             .map(|_| builder.enable_virtual_host_style());
+        map.get("force_path_style")
+            .filter(|v| *v == "on" || *v == "true")
+            .map(|_| builder.force_path_style());
+        map.get("enable_request_payer")
+            .filter(|v| *v == "on" || *v == "true")
+            .map(|_| builder.enable_request_payer());
         map.get("allow_anonymous")
             .filter(|v| *v == "on" || *v == "true")
             .map(|_| builder.allow_anonymous());
@@ -688,21 +792,63 @@ impl Builder for S3Builder {
         builder
     }
 
+    fn validate(&self) -> Result<()> {
+        if self.enable_virtual_host_style && self.force_path_style {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "enable_virtual_host_style and force_path_style can't be set at the same time",
+            )
+            .with_operation("Builder::validate")
+            .with_context("service", Scheme::S3)
+            .with_context("key", "force_path_style"));
+        }
+
+        if !self.is_bucket_valid() {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "The bucket is misconfigured")
+                    .with_operation("Builder::validate")
+                    .with_context("service", Scheme::S3)
+                    .with_context("key", "bucket")
+                    .with_context("bucket", &self.bucket),
+            );
+        }
+
+        // aws kms key id only works with `aws:kms`, S3 will reject the write
+        // request otherwise.
+        if self.server_side_encryption_aws_kms_key_id.is_some()
+            && self.server_side_encryption.as_deref() != Some("aws:kms")
+        {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "server_side_encryption_aws_kms_key_id requires server_side_encryption to be aws:kms",
+            )
+            .with_operation("Builder::validate")
+            .with_context("service", Scheme::S3)
+            .with_context("key", "server_side_encryption")
+            .with_context("server_side_encryption", self.server_side_encryption.clone().unwrap_or_default()));
+        }
+
+        if self.write_min_size.unwrap_or(DEFAULT_WRITE_MIN_SIZE) < 5 * 1024 * 1024 {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "The write minimum buffer size is misconfigured",
+            )
+            .with_operation("Builder::validate")
+            .with_context("service", Scheme::S3)
+            .with_context("key", "write_min_size"));
+        }
+
+        Ok(())
+    }
+
     fn build(&mut self) -> Result<Self::Accessor> {
         debug!("backend build started: {:?}", &self);
+        self.validate()?;
 
         let root = normalize_root(&self.root.take().unwrap_or_default());
         debug!("backend use root {}", &root);
 
-        // Handle bucket name.
-        let bucket = if self.is_bucket_valid() {
-            Ok(&self.bucket)
-        } else {
-            Err(
-                Error::new(ErrorKind::ConfigInvalid, "The bucket is misconfigured")
-                    .with_context("service", Scheme::S3),
-            )
-        }?;
+        let bucket = &self.bucket;
         debug!("backend use bucket {}", &bucket);
 
         let default_storage_class = match &self.default_storage_class {
@@ -810,6 +956,18 @@ impl Builder for S3Builder {
         if let Some(v) = self.customed_credential_load.take() {
             loader = Some(v);
         }
+        // If credential_provider is set, we will use it.
+        if let Some(v) = self.credential_provider.take() {
+            if loader.is_some() {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "credential_provider can't be used together with customed_credential_load",
+                )
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::S3));
+            }
+            loader = Some(Box::new(CredentialProviderLoad::new(v)));
+        }
 
         // If role_arn is set, we must use AssumeRoleLoad.
         if let Some(role_arn) = self.role_arn.take() {
@@ -852,16 +1010,7 @@ impl Builder for S3Builder {
             }
         };
 
-        let signer = AwsV4Signer::new("s3", &region);
-
         let write_min_size = self.write_min_size.unwrap_or(DEFAULT_WRITE_MIN_SIZE);
-        if write_min_size < 5 * 1024 * 1024 {
-            return Err(Error::new(
-                ErrorKind::ConfigInvalid,
-                "The write minimum buffer size is misconfigured",
-            )
-            .with_context("service", Scheme::S3));
-        }
         let batch_max_operations = self
             .batch_max_operations
             .unwrap_or(DEFAULT_BATCH_MAX_OPERATIONS);
@@ -870,11 +1019,16 @@ impl Builder for S3Builder {
         let runtime = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()
-            .unwrap();
+            .map_err(|err| {
+                Error::new(ErrorKind::Unexpected, "build runtime for blocking api")
+                    .with_operation("Builder::build")
+                    .with_context("service", Scheme::S3)
+                    .set_source(err)
+            })?;
         Ok(S3Backend {
             core: Arc::new(S3Core {
                 bucket: bucket.to_string(),
-                endpoint,
+                region: RwLock::new(S3Region::new(&region, endpoint)),
                 root,
                 server_side_encryption,
                 server_side_encryption_aws_kms_key_id,
@@ -883,7 +1037,7 @@ impl Builder for S3Builder {
                 server_side_encryption_customer_key_md5,
                 default_storage_class,
                 allow_anonymous: self.allow_anonymous,
-                signer,
+                enable_request_payer: self.enable_request_payer,
                 loader,
                 client,
                 write_min_size,
@@ -928,21 +1082,49 @@ impl Accessor for S3Backend {
                 read_with_if_none_match: true,
                 read_with_override_cache_control: true,
                 read_with_override_content_disposition: true,
+                read_with_headers: true,
 
                 write: true,
                 write_can_sink: true,
                 write_with_cache_control: true,
+                write_with_content_encoding: true,
+                write_with_content_language: true,
+                write_with_headers: true,
+                write_with_storage_class: true,
                 write_with_content_type: true,
+                write_with_content_md5: true,
                 write_without_content_length: true,
+                write_multi_min_size: Some(5 * 1024 * 1024),
+                write_multi_max_size: Some(5 * 1024 * 1024 * 1024),
+                write_with_tags: true,
+                write_with_user_metadata: true,
+                write_with_resume: true,
+                write_with_if_match: true,
+                write_with_if_none_match: true,
                 create_dir: true,
                 delete: true,
+                delete_with_version: true,
+                delete_with_if_match: true,
                 copy: true,
+                copy_with_metadata_directive: true,
+                copy_with_if_source_match: true,
+
+                get_tags: true,
+                put_tags: true,
+
+                write_with_object_lock: true,
+                get_object_lock: true,
+                put_object_lock: true,
 
                 list: true,
                 list_with_limit: true,
                 list_with_start_after: true,
+                list_with_continuation_token: true,
+                list_with_versions: true,
                 list_without_delimiter: true,
                 list_with_delimiter_slash: true,
+                list_with_metadata: true,
+                list_with_sorted: true,
 
                 presign: true,
                 presign_stat: true,
@@ -959,9 +1141,12 @@ impl Accessor for S3Backend {
     }
 
     async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
-        let mut req =
-            self.core
-                .s3_put_object_request(path, Some(0), None, None, None, AsyncBody::Empty)?;
+        let mut req = self.core.s3_put_object_request(
+            path,
+            Some(0),
+            &OpWrite::default(),
+            AsyncBody::Empty,
+        )?;
 
         self.core.sign(&mut req).await?;
 
@@ -979,22 +1164,14 @@ impl Accessor for S3Backend {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let resp = self
-            .core
-            .s3_get_object(
-                path,
-                args.range(),
-                args.if_none_match(),
-                args.if_match(),
-                args.override_content_disposition(),
-            )
-            .await?;
+        let resp = self.core.s3_get_object(path, &args).await?;
 
         let status = resp.status();
 
         match status {
             StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
-                let meta = parse_into_metadata(path, resp.headers())?;
+                let mut meta = parse_into_metadata(path, resp.headers())?;
+                parse_s3_content_md5(resp.headers(), &mut meta);
                 Ok((RpRead::with_metadata(meta), resp.into_body()))
             }
             _ => Err(parse_error(resp).await?),
@@ -1008,8 +1185,8 @@ impl Accessor for S3Backend {
         ))
     }
 
-    async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
-        let resp = self.core.s3_copy_object(from, to).await?;
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let resp = self.core.s3_copy_object(from, to, &args).await?;
 
         let status = resp.status();
 
@@ -1039,7 +1216,11 @@ impl Accessor for S3Backend {
         let status = resp.status();
 
         match status {
-            StatusCode::OK => parse_into_metadata(path, resp.headers()).map(RpStat::new),
+            StatusCode::OK => {
+                let mut meta = parse_into_metadata(path, resp.headers())?;
+                parse_s3_metadata(resp.headers(), &mut meta)?;
+                Ok(RpStat::new(meta))
+            }
             StatusCode::NOT_FOUND if path.ends_with('/') => {
                 Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
             }
@@ -1047,8 +1228,11 @@ impl Accessor for S3Backend {
         }
     }
 
-    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
-        let resp = self.core.s3_delete_object(path).await?;
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let resp = self
+            .core
+            .s3_delete_object(path, args.version(), args.if_match())
+            .await?;
 
         let status = resp.status();
 
@@ -1061,16 +1245,116 @@ impl Accessor for S3Backend {
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
         Ok((
             RpList::default(),
-            S3Pager::new(
-                self.core.clone(),
-                path,
-                args.delimiter(),
-                args.limit(),
-                args.start_after(),
-            ),
+            S3Pager::new(self.core.clone(), path, &args),
         ))
     }
 
+    async fn get_tags(&self, path: &str, _: OpGetTags) -> Result<RpGetTags> {
+        let resp = self.core.s3_get_object_tagging(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+
+                let out: Tagging =
+                    quick_xml::de::from_reader(bs.reader()).map_err(new_xml_deserialize_error)?;
+
+                Ok(RpGetTags::new(
+                    out.tag_set
+                        .tag
+                        .into_iter()
+                        .map(|v| (v.key, v.value))
+                        .collect(),
+                ))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        let resp = self.core.s3_put_object_tagging(path, args.tags()).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK | StatusCode::NO_CONTENT => {
+                resp.into_body().consume().await?;
+                Ok(RpPutTags::new())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn get_object_lock(&self, path: &str, _: OpGetObjectLock) -> Result<RpGetObjectLock> {
+        let mut lock = ObjectLock::new();
+
+        let resp = self.core.s3_get_object_retention(path).await?;
+        match resp.status() {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+
+                let out: Retention =
+                    quick_xml::de::from_reader(bs.reader()).map_err(new_xml_deserialize_error)?;
+
+                lock = lock.with_retention(
+                    parse_object_lock_mode(&out.mode)?,
+                    parse_datetime_from_rfc3339(&out.retain_until_date)?,
+                );
+            }
+            _ => {
+                if let Some(err) = parse_object_lock_error(resp).await? {
+                    return Err(err);
+                }
+            }
+        }
+
+        let resp = self.core.s3_get_object_legal_hold(path).await?;
+        match resp.status() {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+
+                let out: LegalHold =
+                    quick_xml::de::from_reader(bs.reader()).map_err(new_xml_deserialize_error)?;
+
+                lock = lock.with_legal_hold(out.status == "ON");
+            }
+            _ => {
+                if let Some(err) = parse_object_lock_error(resp).await? {
+                    return Err(err);
+                }
+            }
+        }
+
+        Ok(RpGetObjectLock::new(lock))
+    }
+
+    async fn put_object_lock(&self, path: &str, args: OpPutObjectLock) -> Result<RpPutObjectLock> {
+        let lock = args.object_lock();
+
+        if let Some((mode, retain_until)) = lock.retention() {
+            let resp = self
+                .core
+                .s3_put_object_retention(path, mode, retain_until)
+                .await?;
+            match resp.status() {
+                StatusCode::OK | StatusCode::NO_CONTENT => resp.into_body().consume().await?,
+                _ => return Err(parse_error(resp).await?),
+            }
+        }
+
+        if let Some(on) = lock.legal_hold() {
+            let resp = self.core.s3_put_object_legal_hold(path, on).await?;
+            match resp.status() {
+                StatusCode::OK | StatusCode::NO_CONTENT => resp.into_body().consume().await?,
+                _ => return Err(parse_error(resp).await?),
+            }
+        }
+
+        Ok(RpPutObjectLock::new())
+    }
+
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
         // We will not send this request out, just for signing.
         let mut req = match args.operation() {
@@ -1078,18 +1362,13 @@ impl Accessor for S3Backend {
                 self.core
                     .s3_head_object_request(path, v.if_none_match(), v.if_match())?
             }
-            PresignOperation::Read(v) => self.core.s3_get_object_request(
+            PresignOperation::Read(v) => self.core.s3_get_object_request(path, v)?,
+            PresignOperation::Write(_) => self.core.s3_put_object_request(
                 path,
-                v.range(),
-                v.override_content_disposition(),
-                v.override_cache_control(),
-                v.if_none_match(),
-                v.if_match(),
+                None,
+                &OpWrite::default(),
+                AsyncBody::Empty,
             )?,
-            PresignOperation::Write(_) => {
-                self.core
-                    .s3_put_object_request(path, None, None, None, None, AsyncBody::Empty)?
-            }
         };
 
         self.core.sign_query(&mut req, args.expire()).await?;
@@ -1152,11 +1431,11 @@ impl Accessor for S3Backend {
     }
 
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
-        self.runtime.block_on(self.create_dir(path, args))
+        block_on(&self.runtime, self.create_dir(path, args))
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
-        self.runtime.block_on(async {
+        block_on(&self.runtime, async {
             let (rp, reader) = self.read(path, args).await?;
             let body_bytes = reader.bytes().await?;
 
@@ -1165,7 +1444,7 @@ impl Accessor for S3Backend {
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
-        self.runtime.block_on(async {
+        block_on(&self.runtime, async {
             let (rp, mut writer) = self.write(path, args).await?;
             writer.set_runtime(self.runtime.clone());
             Ok((rp, writer))
@@ -1173,34 +1452,116 @@ impl Accessor for S3Backend {
     }
 
     fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
-        self.runtime.block_on(self.copy(from, to, args))
+        block_on(&self.runtime, self.copy(from, to, args))
     }
 
     fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
-        self.runtime.block_on(self.rename(from, to, args))
+        block_on(&self.runtime, self.rename(from, to, args))
     }
 
     fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        self.runtime.block_on(self.stat(path, args))
+        block_on(&self.runtime, self.stat(path, args))
     }
 
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        self.runtime.block_on(self.delete(path, args))
+        block_on(&self.runtime, self.delete(path, args))
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
-        self.runtime.block_on(async {
+        block_on(&self.runtime, async {
             let (rp, mut pager) = self.list(path, args).await?;
             pager.set_runtime(self.runtime.clone());
             Ok((rp, pager))
         })
     }
+
+    fn blocking_get_tags(&self, path: &str, args: OpGetTags) -> Result<RpGetTags> {
+        block_on(&self.runtime, self.get_tags(path, args))
+    }
+
+    fn blocking_put_tags(&self, path: &str, args: OpPutTags) -> Result<RpPutTags> {
+        block_on(&self.runtime, self.put_tags(path, args))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Mutex;
+
+    use bytes::Bytes;
+    use futures::stream;
+    use http::Request;
+    use http::Response;
+
     use super::*;
 
+    /// MockFetcher pretends the bucket is located in `eu-west-1`.
+    #[derive(Clone, Default)]
+    struct MockFetcher {
+        uris: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl HttpFetch for MockFetcher {
+        async fn fetch(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
+            let uri = req.uri().to_string();
+            self.uris.lock().unwrap().push(uri.clone());
+
+            let resp = if uri.contains(".eu-west-1.") {
+                Response::builder()
+                    .status(StatusCode::OK)
+                    .header(http::header::CONTENT_LENGTH, "3")
+            } else {
+                Response::builder()
+                    .status(StatusCode::MOVED_PERMANENTLY)
+                    .header("x-amz-bucket-region", "eu-west-1")
+            };
+            let stream = oio::into_stream(stream::iter(vec![Ok(Bytes::new())]));
+
+            Ok(resp
+                .extension(req.uri().clone())
+                .body(IncomingAsyncBody::new(Box::new(stream), None))
+                .expect("response must build succeed"))
+        }
+    }
+
+    /// S3 backend owns a runtime for blocking API which can't be dropped
+    /// inside an async context, so we drive the test by another runtime.
+    #[test]
+    fn test_region_redirect() {
+        let fetcher = MockFetcher::default();
+
+        let mut builder = S3Builder::default();
+        builder
+            .bucket("test")
+            .region("us-east-1")
+            .allow_anonymous()
+            .disable_config_load()
+            .disable_ec2_metadata()
+            .http_client(HttpClient::with_fetcher(fetcher.clone()).unwrap());
+        let op = Operator::new(builder).unwrap().finish();
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let meta = op.stat("file").await.expect("stat must succeed");
+            assert_eq!(meta.content_length(), 3);
+            // The discovered region should be cached.
+            let _ = op.stat("file").await.expect("stat must succeed");
+        });
+
+        assert_eq!(
+            *fetcher.uris.lock().unwrap(),
+            vec![
+                "https://s3.us-east-1.amazonaws.com/test/file",
+                "https://s3.eu-west-1.amazonaws.com/test/file",
+                "https://s3.eu-west-1.amazonaws.com/test/file",
+            ]
+        );
+    }
+
     #[test]
     fn test_is_valid_bucket() {
         let bucket_cases = vec![
@@ -1257,6 +1618,80 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_build_endpoint_detect_style() {
+        // Endpoint contains bucket name, use virtual host style.
+        let mut b = S3Builder::default();
+        b.bucket("test");
+        b.endpoint("https://test.s3.us-east-2.amazonaws.com");
+        assert_eq!(
+            b.build_endpoint("us-east-2"),
+            "https://test.s3.us-east-2.amazonaws.com"
+        );
+
+        // Force path style even if endpoint contains bucket name.
+        let mut b = S3Builder::default();
+        b.bucket("test");
+        b.endpoint("https://test.s3.us-east-2.amazonaws.com");
+        b.force_path_style();
+        assert_eq!(
+            b.build_endpoint("us-east-2"),
+            "https://s3.us-east-2.amazonaws.com/test"
+        );
+
+        // Custom endpoint like minio uses path style by default.
+        let mut b = S3Builder::default();
+        b.bucket("test.xyz");
+        b.endpoint("http://127.0.0.1:9000");
+        assert!(b.is_bucket_valid());
+        assert_eq!(
+            b.build_endpoint("us-east-1"),
+            "http://127.0.0.1:9000/test.xyz"
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let b = S3Builder::default();
+        let err = b.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        assert!(err.to_string().contains("bucket"));
+
+        let mut b = S3Builder::default();
+        b.bucket("test");
+        b.server_side_encryption_aws_kms_key_id("key");
+        let err = b.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        let mut b = S3Builder::default();
+        b.bucket("test");
+        b.server_side_encryption_with_aws_managed_kms_key();
+        assert!(b.validate().is_ok());
+    }
+
+    #[test]
+    fn test_credential_provider_conflicts_with_credential_load() {
+        #[derive(Debug)]
+        struct StaticProvider;
+
+        #[async_trait]
+        impl CredentialProvider<AwsCredential> for StaticProvider {
+            async fn get_credential(&self) -> Result<Option<Credential<AwsCredential>>> {
+                Ok(Some(Credential::new(AwsCredential::new("ak", "sk"))))
+            }
+        }
+
+        let mut b = S3Builder::default();
+        b.bucket("test").region("us-east-1");
+        b.credential_provider(StaticProvider);
+        b.customed_credential_load(Box::new(AwsDefaultLoader::new(
+            reqwest::Client::new(),
+            AwsConfig::default(),
+        )));
+        let err = b.build().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
     #[tokio::test]
     async fn test_detect_region() {
         let cases = vec![
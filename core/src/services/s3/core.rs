@@ -15,23 +15,38 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::fmt::Write;
+use std::sync::RwLock;
 use std::time::Duration;
 
 use bytes::Bytes;
+use chrono::DateTime;
+use chrono::SecondsFormat;
+use chrono::Utc;
 use http::header::HeaderName;
+use http::header::AUTHORIZATION;
 use http::header::CACHE_CONTROL;
 use http::header::CONTENT_DISPOSITION;
+use http::header::CONTENT_ENCODING;
+use http::header::CONTENT_LANGUAGE;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
+use http::header::ETAG;
+use http::header::HOST;
 use http::header::IF_MATCH;
 use http::header::IF_NONE_MATCH;
+use http::HeaderMap;
 use http::HeaderValue;
 use http::Request;
 use http::Response;
+use http::StatusCode;
+use log::debug;
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::NON_ALPHANUMERIC;
 use reqsign::AwsCredential;
 use reqsign::AwsCredentialLoad;
 use reqsign::AwsV4Signer;
@@ -43,6 +58,17 @@ use crate::*;
 
 mod constants {
     pub const X_AMZ_COPY_SOURCE: &str = "x-amz-copy-source";
+    pub const X_AMZ_COPY_SOURCE_IF_MATCH: &str = "x-amz-copy-source-if-match";
+    pub const X_AMZ_BUCKET_REGION: &str = "x-amz-bucket-region";
+    pub const X_AMZ_CONTENT_SHA256: &str = "x-amz-content-sha256";
+    pub const X_AMZ_DATE: &str = "x-amz-date";
+    pub const X_AMZ_METADATA_DIRECTIVE: &str = "x-amz-metadata-directive";
+    pub const X_AMZ_META_PREFIX: &str = "x-amz-meta-";
+    pub const X_AMZ_OBJECT_LOCK_MODE: &str = "x-amz-object-lock-mode";
+    pub const X_AMZ_OBJECT_LOCK_RETAIN_UNTIL_DATE: &str = "x-amz-object-lock-retain-until-date";
+    pub const X_AMZ_OBJECT_LOCK_LEGAL_HOLD: &str = "x-amz-object-lock-legal-hold";
+    pub const X_AMZ_REQUEST_PAYER: &str = "x-amz-request-payer";
+    pub const X_AMZ_SECURITY_TOKEN: &str = "x-amz-security-token";
 
     pub const X_AMZ_SERVER_SIDE_ENCRYPTION: &str = "x-amz-server-side-encryption";
     pub const X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM: &str =
@@ -54,6 +80,11 @@ mod constants {
     pub const X_AMZ_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID: &str =
         "x-amz-server-side-encryption-aws-kms-key-id";
     pub const X_AMZ_STORAGE_CLASS: &str = "x-amz-storage-class";
+    pub const X_AMZ_TAGGING: &str = "x-amz-tagging";
+    pub const X_AMZ_VERSION_ID: &str = "x-amz-version-id";
+    pub const X_AMZ_CHECKSUM_MODE: &str = "x-amz-checksum-mode";
+    pub const X_AMZ_CHECKSUM_CRC32C: &str = "x-amz-checksum-crc32c";
+    pub const X_AMZ_CHECKSUM_SHA256: &str = "x-amz-checksum-sha256";
 
     pub const X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM: &str =
         "x-amz-copy-source-server-side-encryption-customer-algorithm";
@@ -68,7 +99,7 @@ mod constants {
 
 pub struct S3Core {
     pub bucket: String,
-    pub endpoint: String,
+    pub region: RwLock<S3Region>,
     pub root: String,
     pub server_side_encryption: Option<HeaderValue>,
     pub server_side_encryption_aws_kms_key_id: Option<HeaderValue>,
@@ -77,8 +108,8 @@ pub struct S3Core {
     pub server_side_encryption_customer_key_md5: Option<HeaderValue>,
     pub default_storage_class: Option<HeaderValue>,
     pub allow_anonymous: bool,
+    pub enable_request_payer: bool,
 
-    pub signer: AwsV4Signer,
     pub loader: Box<dyn AwsCredentialLoad>,
     pub client: HttpClient,
     pub write_min_size: usize,
@@ -89,13 +120,42 @@ impl Debug for S3Core {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("S3Core")
             .field("bucket", &self.bucket)
-            .field("endpoint", &self.endpoint)
+            .field("endpoint", &self.endpoint())
             .field("root", &self.root)
             .finish_non_exhaustive()
     }
 }
 
+/// S3Region is the region related states of [`S3Core`].
+///
+/// It will be updated once we find out that the bucket is located in
+/// another region.
+pub struct S3Region {
+    pub region: String,
+    pub endpoint: String,
+    pub signer: AwsV4Signer,
+}
+
+impl S3Region {
+    pub fn new(region: &str, endpoint: String) -> Self {
+        Self {
+            region: region.to_string(),
+            endpoint,
+            signer: AwsV4Signer::new("s3", region),
+        }
+    }
+}
+
 impl S3Core {
+    /// Get the endpoint of current region.
+    pub fn endpoint(&self) -> String {
+        self.region
+            .read()
+            .expect("lock must be valid")
+            .endpoint
+            .clone()
+    }
+
     /// If credential is not found, we will not sign the request.
     async fn load_credential(&self) -> Result<Option<AwsCredential>> {
         let cred = self
@@ -120,32 +180,129 @@ impl S3Core {
     }
 
     pub async fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
+        // Request payer header must be signed, so we insert it before signing.
+        self.insert_request_payer_header(req);
+
         let cred = if let Some(cred) = self.load_credential().await? {
             cred
         } else {
             return Ok(());
         };
 
-        self.signer.sign(req, &cred).map_err(new_request_sign_error)
+        self.region
+            .read()
+            .expect("lock must be valid")
+            .signer
+            .sign(req, &cred)
+            .map_err(new_request_sign_error)
     }
 
     pub async fn sign_query<T>(&self, req: &mut Request<T>, duration: Duration) -> Result<()> {
+        self.insert_request_payer_header(req);
+
         let cred = if let Some(cred) = self.load_credential().await? {
             cred
         } else {
             return Ok(());
         };
 
-        self.signer
+        self.region
+            .read()
+            .expect("lock must be valid")
+            .signer
             .sign_query(req, duration, &cred)
             .map_err(new_request_sign_error)
     }
 
-    #[inline]
+    /// Send the request.
+    ///
+    /// If the bucket is located in another region, s3 will respond with the
+    /// correct region in `x-amz-bucket-region`. We will switch to the new
+    /// region and send the request again, so only the first access will
+    /// take an extra round trip.
+    ///
+    /// Requests with streaming body can't be sent again, a temporary error
+    /// will be returned instead and the retry will go to the new region.
     pub async fn send(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
+        let (region, endpoint) = {
+            let r = self.region.read().expect("lock must be valid");
+            (r.region.clone(), r.endpoint.clone())
+        };
+        let replay = match req.body() {
+            AsyncBody::Empty => Some(AsyncBody::Empty),
+            AsyncBody::Bytes(bs) => Some(AsyncBody::Bytes(bs.clone())),
+            AsyncBody::Stream(_) => None,
+        };
+        let method = req.method().clone();
+        let uri = req.uri().to_string();
+        let version = req.version();
+        let headers = req.headers().clone();
+
+        let resp = self.client.send(req).await?;
+
+        let new_region = match parse_redirect_region(&resp) {
+            Some(v) if v != region => v,
+            _ => return Ok(resp),
+        };
+        let new_endpoint = self.switch_region(&region, &new_region);
+        debug!("s3 bucket is located in region {new_region}, switch to endpoint {new_endpoint}");
+
+        let body = match replay {
+            Some(body) => body,
+            None => {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "bucket is located in another region",
+                )
+                .with_context("region", new_region)
+                .set_temporary())
+            }
+        };
+
+        let uri = match uri.strip_prefix(&endpoint) {
+            Some(rest) => format!("{new_endpoint}{rest}"),
+            None => uri,
+        };
+
+        let mut req = Request::new(body);
+        *req.method_mut() = method;
+        *req.uri_mut() = uri.parse().map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "redirect uri is invalid").set_source(err)
+        })?;
+        *req.version_mut() = version;
+        *req.headers_mut() = headers;
+        clear_signed_headers(req.headers_mut());
+
+        self.sign(&mut req).await?;
         self.client.send(req).await
     }
 
+    /// Switch to the new region and return the new endpoint.
+    ///
+    /// Region in the endpoint like `s3.{region}.amazonaws.com` will be
+    /// replaced, other endpoints will be kept as is.
+    fn switch_region(&self, old: &str, new: &str) -> String {
+        let mut r = self.region.write().expect("lock must be valid");
+        // Region could have been switched by other requests.
+        if r.region == old {
+            let endpoint = r
+                .endpoint
+                .replacen(&format!(".{old}."), &format!(".{new}."), 1);
+            *r = S3Region::new(new, endpoint);
+        }
+        r.endpoint.clone()
+    }
+
+    /// Insert `x-amz-request-payer: requester` if request payer is enabled.
+    fn insert_request_payer_header<T>(&self, req: &mut Request<T>) {
+        if self.enable_request_payer {
+            req.headers_mut().insert(
+                HeaderName::from_static(constants::X_AMZ_REQUEST_PAYER),
+                HeaderValue::from_static("requester"),
+            );
+        }
+    }
+
     /// # Note
     ///
     /// header like X_AMZ_SERVER_SIDE_ENCRYPTION doesn't need to set while
@@ -217,12 +374,18 @@ impl S3Core {
     ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+        let url = format!("{}/{}", self.endpoint(), percent_encode_path(&p));
 
         let mut req = Request::head(&url);
 
         req = self.insert_sse_headers(req, false);
 
+        // Ask s3 to return the additional checksums of this object.
+        req = req.header(
+            HeaderName::from_static(constants::X_AMZ_CHECKSUM_MODE),
+            "ENABLED",
+        );
+
         if let Some(if_none_match) = if_none_match {
             req = req.header(IF_NONE_MATCH, if_none_match);
         }
@@ -238,30 +401,22 @@ impl S3Core {
         Ok(req)
     }
 
-    pub fn s3_get_object_request(
-        &self,
-        path: &str,
-        range: BytesRange,
-        override_content_disposition: Option<&str>,
-        override_cache_control: Option<&str>,
-        if_none_match: Option<&str>,
-        if_match: Option<&str>,
-    ) -> Result<Request<AsyncBody>> {
+    pub fn s3_get_object_request(&self, path: &str, args: &OpRead) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
         // Construct headers to add to the request
-        let mut url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+        let mut url = format!("{}/{}", self.endpoint(), percent_encode_path(&p));
 
         // Add query arguments to the URL based on response overrides
         let mut query_args = Vec::new();
-        if let Some(override_content_disposition) = override_content_disposition {
+        if let Some(override_content_disposition) = args.override_content_disposition() {
             query_args.push(format!(
                 "{}={}",
                 constants::RESPONSE_CONTENT_DISPOSITION,
                 percent_encode_path(override_content_disposition)
             ))
         }
-        if let Some(override_cache_control) = override_cache_control {
+        if let Some(override_cache_control) = args.override_cache_control() {
             query_args.push(format!(
                 "{}={}",
                 constants::RESPONSE_CACHE_CONTROL,
@@ -274,17 +429,22 @@ impl S3Core {
 
         let mut req = Request::get(&url);
 
+        let range = args.range();
         if !range.is_full() {
             req = req.header(http::header::RANGE, range.to_header());
         }
 
-        if let Some(if_none_match) = if_none_match {
+        if let Some(if_none_match) = args.if_none_match() {
             req = req.header(IF_NONE_MATCH, if_none_match);
         }
 
-        if let Some(if_match) = if_match {
+        if let Some(if_match) = args.if_match() {
             req = req.header(IF_MATCH, if_match);
         }
+
+        for (name, value) in args.headers() {
+            req = req.header(name.as_str(), value.as_str());
+        }
         // Set SSE headers.
         // TODO: how will this work with presign?
         req = self.insert_sse_headers(req, false);
@@ -299,61 +459,156 @@ impl S3Core {
     pub async fn s3_get_object(
         &self,
         path: &str,
-        range: BytesRange,
-        if_none_match: Option<&str>,
-        if_match: Option<&str>,
-        override_content_disposition: Option<&str>,
+        args: &OpRead,
     ) -> Result<Response<IncomingAsyncBody>> {
-        let mut req = self.s3_get_object_request(
-            path,
-            range,
-            override_content_disposition,
-            None,
-            if_none_match,
-            if_match,
-        )?;
+        let mut req = self.s3_get_object_request(path, args)?;
 
         self.sign(&mut req).await?;
 
         self.send(req).await
     }
 
+    /// Set the object lock headers of write requests, parts that are not
+    /// set will be left to the bucket's default retention.
+    pub fn insert_object_lock_headers(
+        &self,
+        mut req: http::request::Builder,
+        lock: &ObjectLock,
+    ) -> http::request::Builder {
+        if let Some((mode, retain_until)) = lock.retention() {
+            req = req
+                .header(
+                    HeaderName::from_static(constants::X_AMZ_OBJECT_LOCK_MODE),
+                    format_object_lock_mode(mode),
+                )
+                .header(
+                    HeaderName::from_static(constants::X_AMZ_OBJECT_LOCK_RETAIN_UNTIL_DATE),
+                    format_object_lock_date(retain_until),
+                );
+        }
+        if let Some(on) = lock.legal_hold() {
+            req = req.header(
+                HeaderName::from_static(constants::X_AMZ_OBJECT_LOCK_LEGAL_HOLD),
+                format_legal_hold_status(on),
+            );
+        }
+
+        req
+    }
+
     pub fn s3_put_object_request(
         &self,
         path: &str,
         size: Option<u64>,
-        content_type: Option<&str>,
-        content_disposition: Option<&str>,
-        cache_control: Option<&str>,
+        args: &OpWrite,
         body: AsyncBody,
     ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+        let url = format!("{}/{}", self.endpoint(), percent_encode_path(&p));
 
         let mut req = Request::put(&url);
 
+        if let Some(if_match) = args.if_match() {
+            req = req.header(IF_MATCH, if_match);
+        }
+
+        if let Some(if_none_match) = args.if_none_match() {
+            req = req.header(IF_NONE_MATCH, if_none_match);
+        }
+
         if let Some(size) = size {
             req = req.header(CONTENT_LENGTH, size)
         }
 
-        if let Some(mime) = content_type {
+        if let Some(mime) = args.content_type() {
             req = req.header(CONTENT_TYPE, mime)
         }
 
-        if let Some(pos) = content_disposition {
+        if let Some(pos) = args.content_disposition() {
             req = req.header(CONTENT_DISPOSITION, pos)
         }
 
-        if let Some(cache_control) = cache_control {
+        if let Some(encoding) = args.content_encoding() {
+            req = req.header(CONTENT_ENCODING, encoding)
+        }
+
+        if let Some(language) = args.content_language() {
+            req = req.header(CONTENT_LANGUAGE, language)
+        }
+
+        if let Some(cache_control) = args.cache_control() {
             req = req.header(CACHE_CONTROL, cache_control)
         }
 
+        let mut has_checksum = false;
+        if let Some(content_md5) = args.content_md5() {
+            req = req.header("CONTENT-MD5", content_md5);
+            has_checksum = true;
+        }
+
+        // Let s3 verify and store the checksum, we can only do this while
+        // the whole content is known.
+        if let (Some(algo), AsyncBody::Bytes(bs)) = (args.checksum(), &body) {
+            let mut hasher = Hasher::new(algo);
+            hasher.update(bs);
+            let name = match algo {
+                ChecksumAlgo::Md5 if args.content_md5().is_none() => Some("CONTENT-MD5"),
+                #[cfg(feature = "checksum-sha256")]
+                ChecksumAlgo::Sha256 => Some(constants::X_AMZ_CHECKSUM_SHA256),
+                ChecksumAlgo::Crc32c => Some(constants::X_AMZ_CHECKSUM_CRC32C),
+                _ => None,
+            };
+            if let Some(name) = name {
+                req = req.header(name, hasher.digest());
+                has_checksum = true;
+            }
+        }
+
+        // S3 requires a checksum while writing with object lock.
+        if args.object_lock() != &ObjectLock::default() {
+            req = self.insert_object_lock_headers(req, args.object_lock());
+
+            match (has_checksum, &body) {
+                (true, _) => {}
+                (false, AsyncBody::Bytes(bs)) => {
+                    req = req.header("CONTENT-MD5", format_content_md5(bs));
+                }
+                (false, AsyncBody::Stream(_)) => {
+                    return Err(new_object_lock_stream_error(
+                        "S3Core::s3_put_object_request",
+                    ))
+                }
+                (false, AsyncBody::Empty) => {
+                    req = req.header("CONTENT-MD5", format_content_md5(&[]));
+                }
+            }
+        }
+
+        if let Some(tags) = args.tags() {
+            req = req.header(
+                HeaderName::from_static(constants::X_AMZ_TAGGING),
+                format_tagging(tags),
+            )
+        }
+
+        if let Some(user_metadata) = args.user_metadata() {
+            for (key, value) in user_metadata {
+                req = req.header(format!("{}{key}", constants::X_AMZ_META_PREFIX), value)
+            }
+        }
+
         // Set storage class header
-        if let Some(v) = &self.default_storage_class {
+        if let Some(v) = args.storage_class() {
+            req = req.header(HeaderName::from_static(constants::X_AMZ_STORAGE_CLASS), v);
+        } else if let Some(v) = &self.default_storage_class {
             req = req.header(HeaderName::from_static(constants::X_AMZ_STORAGE_CLASS), v);
         }
 
+        for (name, value) in args.headers() {
+            req = req.header(name.as_str(), value.as_str());
+        }
+
         // Set SSE headers.
         req = self.insert_sse_headers(req, true);
 
@@ -376,12 +631,27 @@ impl S3Core {
         self.send(req).await
     }
 
-    pub async fn s3_delete_object(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+    pub async fn s3_delete_object(
+        &self,
+        path: &str,
+        version: Option<&str>,
+        if_match: Option<&str>,
+    ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+        let mut url = format!("{}/{}", self.endpoint(), percent_encode_path(&p));
+        if let Some(version) = version {
+            write!(url, "?versionId={}", percent_encode_path(version))
+                .expect("write into string must succeed");
+        }
+
+        let mut req = Request::delete(&url);
 
-        let mut req = Request::delete(&url)
+        if let Some(if_match) = if_match {
+            req = req.header(IF_MATCH, if_match);
+        }
+
+        let mut req = req
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
@@ -394,12 +664,13 @@ impl S3Core {
         &self,
         from: &str,
         to: &str,
+        args: &OpCopy,
     ) -> Result<Response<IncomingAsyncBody>> {
         let from = build_abs_path(&self.root, from);
         let to = build_abs_path(&self.root, to);
 
         let source = format!("{}/{}", self.bucket, percent_encode_path(&from));
-        let target = format!("{}/{}", self.endpoint, percent_encode_path(&to));
+        let target = format!("{}/{}", self.endpoint(), percent_encode_path(&to));
 
         let mut req = Request::put(&target);
 
@@ -442,6 +713,29 @@ impl S3Core {
             )
         }
 
+        if args.metadata_directive() == MetadataDirective::Replace {
+            req = req.header(constants::X_AMZ_METADATA_DIRECTIVE, "REPLACE");
+
+            if let Some(v) = args.content_type() {
+                req = req.header(CONTENT_TYPE, v)
+            }
+            if let Some(v) = args.content_disposition() {
+                req = req.header(CONTENT_DISPOSITION, v)
+            }
+            if let Some(v) = args.cache_control() {
+                req = req.header(CACHE_CONTROL, v)
+            }
+            if let Some(user_metadata) = args.user_metadata() {
+                for (key, value) in user_metadata {
+                    req = req.header(format!("{}{key}", constants::X_AMZ_META_PREFIX), value)
+                }
+            }
+        }
+
+        if let Some(v) = args.if_source_match() {
+            req = req.header(constants::X_AMZ_COPY_SOURCE_IF_MATCH, v);
+        }
+
         let mut req = req
             .header(constants::X_AMZ_COPY_SOURCE, percent_encode_path(&source))
             .body(AsyncBody::Empty)
@@ -462,7 +756,7 @@ impl S3Core {
     ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let mut url = format!("{}?list-type=2", self.endpoint);
+        let mut url = format!("{}?list-type=2", self.endpoint());
         if !p.is_empty() {
             write!(url, "&prefix={}", percent_encode_path(&p))
                 .expect("write into string must succeed");
@@ -500,34 +794,110 @@ impl S3Core {
         self.send(req).await
     }
 
+    /// List versions of objects via ListObjectVersions.
+    ///
+    /// `key_marker` and `version_id_marker` are used for pagination, they
+    /// come from `NextKeyMarker` and `NextVersionIdMarker` of last response.
+    pub async fn s3_list_object_versions(
+        &self,
+        path: &str,
+        key_marker: &str,
+        version_id_marker: &str,
+        delimiter: &str,
+        limit: Option<usize>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = format!("{}?versions", self.endpoint());
+        if !p.is_empty() {
+            write!(url, "&prefix={}", percent_encode_path(&p))
+                .expect("write into string must succeed");
+        }
+        if !delimiter.is_empty() {
+            write!(url, "&delimiter={delimiter}").expect("write into string must succeed");
+        }
+        if let Some(limit) = limit {
+            write!(url, "&max-keys={limit}").expect("write into string must succeed");
+        }
+        if !key_marker.is_empty() {
+            write!(url, "&key-marker={}", percent_encode_path(key_marker))
+                .expect("write into string must succeed");
+        }
+        if !version_id_marker.is_empty() {
+            // Version id could contain `+` and `=`, encode them to make
+            // sure our signer will not be confused.
+            write!(
+                url,
+                "&version-id-marker={}",
+                percent_encode_path(version_id_marker)
+            )
+            .expect("write into string must succeed");
+        }
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
     pub async fn s3_initiate_multipart_upload(
         &self,
         path: &str,
-        content_type: Option<&str>,
-        content_disposition: Option<&str>,
-        cache_control: Option<&str>,
+        args: &OpWrite,
     ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!("{}/{}?uploads", self.endpoint, percent_encode_path(&p));
+        let url = format!("{}/{}?uploads", self.endpoint(), percent_encode_path(&p));
 
         let mut req = Request::post(&url);
 
-        if let Some(mime) = content_type {
+        if let Some(mime) = args.content_type() {
             req = req.header(CONTENT_TYPE, mime)
         }
 
-        if let Some(content_disposition) = content_disposition {
+        if let Some(content_disposition) = args.content_disposition() {
             req = req.header(CONTENT_DISPOSITION, content_disposition)
         }
 
-        if let Some(cache_control) = cache_control {
+        if let Some(encoding) = args.content_encoding() {
+            req = req.header(CONTENT_ENCODING, encoding)
+        }
+
+        if let Some(language) = args.content_language() {
+            req = req.header(CONTENT_LANGUAGE, language)
+        }
+
+        if let Some(cache_control) = args.cache_control() {
             req = req.header(CACHE_CONTROL, cache_control)
         }
 
+        if let Some(tags) = args.tags() {
+            req = req.header(
+                HeaderName::from_static(constants::X_AMZ_TAGGING),
+                format_tagging(tags),
+            )
+        }
+
+        if let Some(user_metadata) = args.user_metadata() {
+            for (key, value) in user_metadata {
+                req = req.header(format!("{}{key}", constants::X_AMZ_META_PREFIX), value)
+            }
+        }
+
         // Set storage class header
-        if let Some(v) = &self.default_storage_class {
+        if let Some(v) = args.storage_class() {
             req = req.header(HeaderName::from_static(constants::X_AMZ_STORAGE_CLASS), v);
+        } else if let Some(v) = &self.default_storage_class {
+            req = req.header(HeaderName::from_static(constants::X_AMZ_STORAGE_CLASS), v);
+        }
+
+        req = self.insert_object_lock_headers(req, args.object_lock());
+
+        for (name, value) in args.headers() {
+            req = req.header(name.as_str(), value.as_str());
         }
 
         // Set SSE headers.
@@ -548,13 +918,14 @@ impl S3Core {
         upload_id: &str,
         part_number: usize,
         size: u64,
+        args: &OpWrite,
         body: AsyncBody,
     ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!(
             "{}/{}?partNumber={}&uploadId={}",
-            self.endpoint,
+            self.endpoint(),
             percent_encode_path(&p),
             part_number,
             percent_encode_path(upload_id)
@@ -564,6 +935,23 @@ impl S3Core {
 
         req = req.header(CONTENT_LENGTH, size);
 
+        // S3 requires a checksum for every part while writing with object lock.
+        if args.object_lock() != &ObjectLock::default() {
+            match &body {
+                AsyncBody::Bytes(bs) => {
+                    req = req.header("CONTENT-MD5", format_content_md5(bs));
+                }
+                AsyncBody::Stream(_) => {
+                    return Err(new_object_lock_stream_error(
+                        "S3Core::s3_upload_part_request",
+                    ))
+                }
+                AsyncBody::Empty => {
+                    req = req.header("CONTENT-MD5", format_content_md5(&[]));
+                }
+            }
+        }
+
         // Set SSE headers.
         req = self.insert_sse_headers(req, true);
 
@@ -578,17 +966,27 @@ impl S3Core {
         path: &str,
         upload_id: &str,
         parts: Vec<CompleteMultipartUploadRequestPart>,
+        if_match: Option<&str>,
+        if_none_match: Option<&str>,
     ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!(
             "{}/{}?uploadId={}",
-            self.endpoint,
+            self.endpoint(),
             percent_encode_path(&p),
             percent_encode_path(upload_id)
         );
 
-        let req = Request::post(&url);
+        let mut req = Request::post(&url);
+
+        if let Some(if_match) = if_match {
+            req = req.header(IF_MATCH, if_match);
+        }
+
+        if let Some(if_none_match) = if_none_match {
+            req = req.header(IF_NONE_MATCH, if_none_match);
+        }
 
         // Set SSE headers.
         let req = self.insert_sse_headers(req, true);
@@ -619,7 +1017,7 @@ impl S3Core {
 
         let url = format!(
             "{}/{}?uploadId={}",
-            self.endpoint,
+            self.endpoint(),
             percent_encode_path(&p),
             percent_encode_path(upload_id)
         );
@@ -631,11 +1029,154 @@ impl S3Core {
         self.send(req).await
     }
 
+    pub async fn s3_get_object_tagging(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}?tagging", self.endpoint(), percent_encode_path(&p));
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
+    pub async fn s3_put_object_tagging(
+        &self,
+        path: &str,
+        tags: &HashMap<String, String>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}?tagging", self.endpoint(), percent_encode_path(&p));
+
+        let mut tag: Vec<_> = tags
+            .iter()
+            .map(|(k, v)| Tag {
+                key: k.to_string(),
+                value: v.to_string(),
+            })
+            .collect();
+        tag.sort_by(|a, b| a.key.cmp(&b.key));
+
+        let content = quick_xml::se::to_string(&Tagging {
+            tag_set: TagSet { tag },
+        })
+        .map_err(new_xml_deserialize_error)?;
+
+        let req = Request::put(&url);
+
+        let req = req.header(CONTENT_LENGTH, content.len());
+        // Set content-md5 as required by API.
+        let req = req.header("CONTENT-MD5", format_content_md5(content.as_bytes()));
+
+        let mut req = req
+            .body(AsyncBody::Bytes(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
+    pub async fn s3_get_object_retention(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}?retention", self.endpoint(), percent_encode_path(&p));
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
+    pub async fn s3_put_object_retention(
+        &self,
+        path: &str,
+        mode: ObjectLockMode,
+        retain_until: DateTime<Utc>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}?retention", self.endpoint(), percent_encode_path(&p));
+
+        let content = quick_xml::se::to_string(&Retention {
+            mode: format_object_lock_mode(mode).to_string(),
+            retain_until_date: format_object_lock_date(retain_until),
+        })
+        .map_err(new_xml_deserialize_error)?;
+
+        let req = Request::put(&url);
+
+        let req = req.header(CONTENT_LENGTH, content.len());
+        // Set content-md5 as required by API.
+        let req = req.header("CONTENT-MD5", format_content_md5(content.as_bytes()));
+
+        let mut req = req
+            .body(AsyncBody::Bytes(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
+    pub async fn s3_get_object_legal_hold(
+        &self,
+        path: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}?legal-hold", self.endpoint(), percent_encode_path(&p));
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
+    pub async fn s3_put_object_legal_hold(
+        &self,
+        path: &str,
+        on: bool,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}?legal-hold", self.endpoint(), percent_encode_path(&p));
+
+        let content = quick_xml::se::to_string(&LegalHold {
+            status: format_legal_hold_status(on).to_string(),
+        })
+        .map_err(new_xml_deserialize_error)?;
+
+        let req = Request::put(&url);
+
+        let req = req.header(CONTENT_LENGTH, content.len());
+        // Set content-md5 as required by API.
+        let req = req.header("CONTENT-MD5", format_content_md5(content.as_bytes()));
+
+        let mut req = req
+            .body(AsyncBody::Bytes(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
     pub async fn s3_delete_objects(
         &self,
         paths: Vec<String>,
     ) -> Result<Response<IncomingAsyncBody>> {
-        let url = format!("{}/?delete", self.endpoint);
+        let url = format!("{}/?delete", self.endpoint());
 
         let req = Request::post(&url);
 
@@ -666,6 +1207,207 @@ impl S3Core {
     }
 }
 
+/// Parse the region of bucket if s3 asks us to redirect to another region.
+///
+/// S3 will return `301 Moved Permanently` or `307 Temporary Redirect` while
+/// accessing the wrong regional endpoint, and `400 Bad Request` if the
+/// request is signed with the wrong region.
+pub fn parse_redirect_region(resp: &Response<IncomingAsyncBody>) -> Option<String> {
+    match resp.status() {
+        StatusCode::MOVED_PERMANENTLY
+        | StatusCode::TEMPORARY_REDIRECT
+        | StatusCode::BAD_REQUEST => {}
+        _ => return None,
+    }
+
+    resp.headers()
+        .get(constants::X_AMZ_BUCKET_REGION)
+        .and_then(|v| v.to_str().ok())
+        .filter(|v| !v.is_empty())
+        .map(|v| v.to_string())
+}
+
+/// Remove headers inserted by the signer, so that the request can be signed
+/// again for another endpoint and region.
+pub fn clear_signed_headers(headers: &mut HeaderMap) {
+    headers.remove(AUTHORIZATION);
+    headers.remove(HOST);
+    headers.remove(constants::X_AMZ_DATE);
+    headers.remove(constants::X_AMZ_CONTENT_SHA256);
+    headers.remove(constants::X_AMZ_SECURITY_TOKEN);
+}
+
+/// Parse the s3 specific headers like checksums and storage class into
+/// metadata.
+pub fn parse_s3_metadata(headers: &HeaderMap, meta: &mut Metadata) -> Result<()> {
+    let parse = |name: &str| {
+        headers
+            .get(name)
+            .map(|v| {
+                v.to_str().map_err(|e| {
+                    Error::new(
+                        ErrorKind::Unexpected,
+                        "header value is not valid utf-8 string",
+                    )
+                    .with_operation("S3::parse_s3_metadata")
+                    .set_source(e)
+                })
+            })
+            .transpose()
+    };
+
+    let user_metadata: HashMap<_, _> = headers
+        .iter()
+        .filter_map(|(name, value)| {
+            let key = name.as_str().strip_prefix(constants::X_AMZ_META_PREFIX)?;
+            Some((key.to_string(), value.to_str().ok()?.to_string()))
+        })
+        .collect();
+    meta.set_user_metadata(user_metadata);
+
+    if let Some(v) = parse(constants::X_AMZ_CHECKSUM_CRC32C)? {
+        meta.set_checksum_crc32c(v);
+    }
+    if let Some(v) = parse(constants::X_AMZ_CHECKSUM_SHA256)? {
+        meta.set_checksum_sha256(v);
+    }
+    // s3 returns version id `null` for objects written before versioning
+    // is enabled, which is not a real version.
+    if let Some(v) = parse(constants::X_AMZ_VERSION_ID)? {
+        if v != "null" {
+            meta.set_version(v);
+        }
+    }
+    // s3 returns storage class for all objects except `STANDARD` ones.
+    meta.set_storage_class(parse(constants::X_AMZ_STORAGE_CLASS)?.unwrap_or("STANDARD"));
+    parse_s3_content_md5(headers, meta);
+    Ok(())
+}
+
+/// Use etag as content md5 only while s3 guarantees that it's the md5 of
+/// content.
+///
+/// Etags of objects uploaded by multipart upload look like `<md5>-<parts>`,
+/// and etags of objects encrypted by SSE-KMS or SSE-C are not md5 of
+/// content even they look like one.
+///
+/// Reference: <https://docs.aws.amazon.com/AmazonS3/latest/API/API_Object.html>
+pub fn parse_s3_content_md5(headers: &HeaderMap, meta: &mut Metadata) {
+    if meta.bit().contains(Metakey::ContentMd5) {
+        return;
+    }
+    if headers.contains_key(constants::X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM) {
+        return;
+    }
+    let sse = headers
+        .get(constants::X_AMZ_SERVER_SIDE_ENCRYPTION)
+        .and_then(|v| v.to_str().ok());
+    if matches!(sse, Some(v) if v != "AES256") {
+        return;
+    }
+
+    let etag = match headers.get(ETAG).and_then(|v| v.to_str().ok()) {
+        Some(v) => v.trim_start_matches("W/").trim_matches('"'),
+        None => return,
+    };
+    if is_md5_hex(etag) {
+        meta.set_content_md5(etag);
+    }
+}
+
+/// Format tags into the value of `x-amz-tagging` header which is encoded
+/// as url query parameters.
+fn format_tagging(tags: &HashMap<String, String>) -> String {
+    let mut tags: Vec<_> = tags.iter().collect();
+    tags.sort();
+
+    tags.into_iter()
+        .map(|(k, v)| {
+            format!(
+                "{}={}",
+                utf8_percent_encode(k, NON_ALPHANUMERIC),
+                utf8_percent_encode(v, NON_ALPHANUMERIC)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Request and result of PutObjectTagging and GetObjectTagging.
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename = "Tagging", rename_all = "PascalCase")]
+pub struct Tagging {
+    pub tag_set: TagSet,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct TagSet {
+    pub tag: Vec<Tag>,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct Tag {
+    pub key: String,
+    pub value: String,
+}
+
+/// S3 rejects object lock writes without a checksum, but we can't compute
+/// one for a streaming body without buffering it.
+fn new_object_lock_stream_error(operation: &'static str) -> Error {
+    Error::new(
+        ErrorKind::Unsupported,
+        "writing with object lock requires the whole content to compute checksum, streaming body is not supported",
+    )
+    .with_operation(operation)
+}
+
+fn format_object_lock_mode(mode: ObjectLockMode) -> &'static str {
+    match mode {
+        ObjectLockMode::Governance => "GOVERNANCE",
+        ObjectLockMode::Compliance => "COMPLIANCE",
+    }
+}
+
+pub fn parse_object_lock_mode(s: &str) -> Result<ObjectLockMode> {
+    match s {
+        "GOVERNANCE" => Ok(ObjectLockMode::Governance),
+        "COMPLIANCE" => Ok(ObjectLockMode::Compliance),
+        v => Err(Error::new(
+            ErrorKind::Unexpected,
+            &format!("object lock mode {v} is not supported"),
+        )),
+    }
+}
+
+fn format_object_lock_date(t: DateTime<Utc>) -> String {
+    t.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn format_legal_hold_status(on: bool) -> &'static str {
+    if on {
+        "ON"
+    } else {
+        "OFF"
+    }
+}
+
+/// Request and result of PutObjectRetention and GetObjectRetention.
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename = "Retention", rename_all = "PascalCase")]
+pub struct Retention {
+    pub mode: String,
+    pub retain_until_date: String,
+}
+
+/// Request and result of PutObjectLegalHold and GetObjectLegalHold.
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename = "LegalHold", rename_all = "PascalCase")]
+pub struct LegalHold {
+    pub status: String,
+}
+
 /// Result of CreateMultipartUpload
 #[derive(Default, Debug, Deserialize)]
 #[serde(default, rename_all = "PascalCase")]
@@ -759,6 +1501,146 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_format_tagging() {
+        let tags = HashMap::from([
+            ("project".to_string(), "open dal".to_string()),
+            ("expire".to_string(), "30d".to_string()),
+        ]);
+
+        assert_eq!(format_tagging(&tags), "expire=30d&project=open%20dal");
+    }
+
+    #[test]
+    fn test_parse_s3_content_md5() {
+        let md5 = "9bb58f26192e4ba00f01e2e7b136bbd8";
+        let cases = vec![
+            ("plain", format!("\"{md5}\""), None, None, Some(md5)),
+            (
+                "sse-s3",
+                format!("\"{md5}\""),
+                Some("AES256"),
+                None,
+                Some(md5),
+            ),
+            ("sse-kms", format!("\"{md5}\""), Some("aws:kms"), None, None),
+            ("sse-c", format!("\"{md5}\""), None, Some("AES256"), None),
+            ("multipart", format!("\"{md5}-2\""), None, None, None),
+        ];
+
+        for (name, etag, sse, sse_c, expected) in cases {
+            let mut headers = HeaderMap::new();
+            headers.insert(ETAG, etag.parse().unwrap());
+            if let Some(v) = sse {
+                headers.insert(constants::X_AMZ_SERVER_SIDE_ENCRYPTION, v.parse().unwrap());
+            }
+            if let Some(v) = sse_c {
+                headers.insert(
+                    constants::X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM,
+                    v.parse().unwrap(),
+                );
+            }
+
+            let mut meta = Metadata::new(EntryMode::FILE);
+            parse_s3_content_md5(&headers, &mut meta);
+            let actual = meta
+                .bit()
+                .contains(Metakey::ContentMd5)
+                .then(|| meta.content_md5().unwrap().to_string());
+            assert_eq!(actual.as_deref(), expected, "{name}");
+        }
+    }
+
+    /// This example is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_GetObjectTagging.html#API_GetObjectTagging_Examples
+    #[test]
+    fn test_deserialize_tagging() {
+        let bs = Bytes::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <Tagging xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+              <TagSet>
+                 <Tag>
+                   <Key>tag1</Key>
+                   <Value>val1</Value>
+                 </Tag>
+                 <Tag>
+                   <Key>tag2</Key>
+                   <Value>val2</Value>
+                 </Tag>
+              </TagSet>
+            </Tagging>"#,
+        );
+
+        let out: Tagging = quick_xml::de::from_reader(bs.reader()).expect("must success");
+
+        assert_eq!(out.tag_set.tag.len(), 2);
+        assert_eq!(out.tag_set.tag[0].key, "tag1");
+        assert_eq!(out.tag_set.tag[0].value, "val1");
+        assert_eq!(out.tag_set.tag[1].key, "tag2");
+        assert_eq!(out.tag_set.tag[1].value, "val2");
+    }
+
+    #[test]
+    fn test_serialize_tagging() {
+        let req = Tagging {
+            tag_set: TagSet {
+                tag: vec![Tag {
+                    key: "tag1".to_string(),
+                    value: "val1".to_string(),
+                }],
+            },
+        };
+
+        let actual = quick_xml::se::to_string(&req).expect("must succeed");
+
+        assert_eq!(
+            actual,
+            "<Tagging><TagSet><Tag><Key>tag1</Key><Value>val1</Value></Tag></TagSet></Tagging>"
+        );
+    }
+
+    /// This example is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_GetObjectRetention.html#API_GetObjectRetention_ResponseSyntax
+    #[test]
+    fn test_deserialize_retention() {
+        let bs = Bytes::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<Retention>
+   <Mode>GOVERNANCE</Mode>
+   <RetainUntilDate>2024-01-01T00:00:00.000Z</RetainUntilDate>
+</Retention>"#,
+        );
+
+        let out: Retention = quick_xml::de::from_reader(bs.reader()).expect("must success");
+
+        assert_eq!(
+            parse_object_lock_mode(&out.mode).expect("must success"),
+            ObjectLockMode::Governance
+        );
+        assert_eq!(
+            parse_datetime_from_rfc3339(&out.retain_until_date).expect("must success"),
+            parse_datetime_from_rfc3339("2024-01-01T00:00:00Z").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_serialize_object_lock() {
+        let retain_until = parse_datetime_from_rfc3339("2024-01-01T00:00:00Z").unwrap();
+        let req = Retention {
+            mode: format_object_lock_mode(ObjectLockMode::Compliance).to_string(),
+            retain_until_date: format_object_lock_date(retain_until),
+        };
+        let actual = quick_xml::se::to_string(&req).expect("must succeed");
+        assert_eq!(
+            actual,
+            "<Retention><Mode>COMPLIANCE</Mode><RetainUntilDate>2024-01-01T00:00:00.000Z</RetainUntilDate></Retention>"
+        );
+
+        let req = LegalHold {
+            status: format_legal_hold_status(true).to_string(),
+        };
+        let actual = quick_xml::se::to_string(&req).expect("must succeed");
+        assert_eq!(actual, "<LegalHold><Status>ON</Status></LegalHold>");
+    }
+
     /// This example is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_CreateMultipartUpload.html#API_CreateMultipartUpload_Examples
     #[test]
     fn test_deserialize_initiate_multipart_upload_result() {
@@ -883,4 +1765,49 @@ mod tests {
         assert_eq!(out.error[0].code, "AccessDenied");
         assert_eq!(out.error[0].message, "Access Denied");
     }
+
+    #[test]
+    fn test_clear_signed_headers() {
+        let cred = |token: &str| AwsCredential {
+            access_key_id: "access_key_id".to_string(),
+            secret_access_key: "secret_access_key".to_string(),
+            session_token: Some(token.to_string()),
+            expires_in: None,
+        };
+
+        let mut req = Request::new(AsyncBody::Empty);
+        *req.uri_mut() = "https://s3.us-east-1.amazonaws.com/bucket/path"
+            .parse()
+            .unwrap();
+        AwsV4Signer::new("s3", "us-east-1")
+            .sign(&mut req, &cred("old_token"))
+            .expect("sign must succeed");
+
+        let (mut parts, body) = req.into_parts();
+        parts.uri = "https://s3.us-west-2.amazonaws.com/bucket/path"
+            .parse()
+            .unwrap();
+        clear_signed_headers(&mut parts.headers);
+        let mut req = Request::from_parts(parts, body);
+        AwsV4Signer::new("s3", "us-west-2")
+            .sign(&mut req, &cred("new_token"))
+            .expect("sign must succeed");
+
+        let headers = req.headers();
+        assert_eq!(headers[HOST], "s3.us-west-2.amazonaws.com");
+        assert_eq!(headers[constants::X_AMZ_SECURITY_TOKEN], "new_token");
+        assert!(headers[AUTHORIZATION]
+            .to_str()
+            .unwrap()
+            .contains("/us-west-2/s3/aws4_request"));
+        for name in [
+            AUTHORIZATION.as_str(),
+            HOST.as_str(),
+            constants::X_AMZ_DATE,
+            constants::X_AMZ_CONTENT_SHA256,
+            constants::X_AMZ_SECURITY_TOKEN,
+        ] {
+            assert_eq!(headers.get_all(name).iter().count(), 1, "{name}");
+        }
+    }
 }
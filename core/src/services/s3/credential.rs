@@ -0,0 +1,446 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Buf;
+use http::Request;
+use http::StatusCode;
+use reqsign::AwsAssumeRoleLoader;
+use reqsign::AwsConfig;
+use reqsign::AwsCredentialLoad;
+use reqsign::AwsDefaultLoader;
+use serde::Deserialize;
+
+use crate::raw::*;
+use crate::*;
+
+const DEFAULT_IMDS_ENDPOINT: &str = "http://169.254.169.254";
+const IMDS_TOKEN_TTL_SECONDS: &str = "21600";
+
+/// AwsCredential is the credential returned by AWS credential providers.
+///
+/// The expiry time is carried by [`Credential`] instead.
+#[derive(Clone)]
+pub struct AwsCredential {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+}
+
+impl Debug for AwsCredential {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AwsCredential")
+            .field("access_key_id", &self.access_key_id)
+            .field("secret_access_key", &"<redacted>")
+            .field(
+                "session_token",
+                &self.session_token.as_ref().map(|_| "<redacted>"),
+            )
+            .finish()
+    }
+}
+
+impl AwsCredential {
+    /// Create a new credential with given access key id and secret access key.
+    pub fn new(access_key_id: &str, secret_access_key: &str) -> Self {
+        Self {
+            access_key_id: access_key_id.to_string(),
+            secret_access_key: secret_access_key.to_string(),
+            session_token: None,
+        }
+    }
+
+    /// Set the session token of temporary credentials.
+    pub fn with_session_token(mut self, session_token: &str) -> Self {
+        self.session_token = Some(session_token.to_string());
+        self
+    }
+
+    /// Get the access key id.
+    pub fn access_key_id(&self) -> &str {
+        &self.access_key_id
+    }
+
+    /// Get the secret access key.
+    pub fn secret_access_key(&self) -> &str {
+        &self.secret_access_key
+    }
+
+    /// Get the session token.
+    pub fn session_token(&self) -> Option<&str> {
+        self.session_token.as_deref()
+    }
+}
+
+/// Adapt a [`CredentialProvider`] into reqsign's [`AwsCredentialLoad`].
+pub(super) struct CredentialProviderLoad {
+    provider: Arc<dyn CredentialProvider<AwsCredential>>,
+}
+
+impl CredentialProviderLoad {
+    pub(super) fn new(provider: impl CredentialProvider<AwsCredential>) -> Self {
+        Self {
+            provider: Arc::new(CachedCredentialProvider::new(provider)),
+        }
+    }
+}
+
+#[async_trait]
+impl AwsCredentialLoad for CredentialProviderLoad {
+    async fn load_credential(
+        &self,
+        _: reqwest::Client,
+    ) -> anyhow::Result<Option<reqsign::AwsCredential>> {
+        let cred = self.provider.get_credential().await?;
+        Ok(cred.map(|cred| {
+            let expires_in = cred.expires_at();
+            let cred = cred.into_value();
+            reqsign::AwsCredential {
+                access_key_id: cred.access_key_id,
+                secret_access_key: cred.secret_access_key,
+                session_token: cred.session_token,
+                expires_in,
+            }
+        }))
+    }
+}
+
+fn into_credential(cred: reqsign::AwsCredential) -> Credential<AwsCredential> {
+    let v = AwsCredential {
+        access_key_id: cred.access_key_id,
+        secret_access_key: cred.secret_access_key,
+        session_token: cred.session_token,
+    };
+    match cred.expires_in {
+        Some(expires_at) => Credential::new(v).with_expires_at(expires_at),
+        None => Credential::new(v),
+    }
+}
+
+/// Load credentials from the EC2 instance metadata service (IMDSv2).
+///
+/// # Example
+///
+/// ```no_run
+/// use opendal::raw::HttpClient;
+/// use opendal::services::AwsImdsCredentialProvider;
+/// use opendal::services::S3;
+///
+/// # fn main() -> opendal::Result<()> {
+/// let mut builder = S3::default();
+/// builder.credential_provider(AwsImdsCredentialProvider::new(HttpClient::new()?));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct AwsImdsCredentialProvider {
+    client: HttpClient,
+    endpoint: String,
+}
+
+impl Debug for AwsImdsCredentialProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AwsImdsCredentialProvider")
+            .field("endpoint", &self.endpoint)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AwsImdsCredentialProvider {
+    /// Create a new provider with the default IMDS endpoint.
+    pub fn new(client: HttpClient) -> Self {
+        Self {
+            client,
+            endpoint: DEFAULT_IMDS_ENDPOINT.to_string(),
+        }
+    }
+
+    /// Set the endpoint of the metadata service.
+    pub fn with_endpoint(mut self, endpoint: &str) -> Self {
+        self.endpoint = endpoint.trim_end_matches('/').to_string();
+        self
+    }
+
+    async fn fetch(&self, req: Request<AsyncBody>) -> Result<Option<bytes::Bytes>> {
+        let resp = self.client.send(req).await?;
+        let status = resp.status();
+        let bs = resp.into_body().bytes().await?;
+
+        match status {
+            StatusCode::OK => Ok(Some(bs)),
+            StatusCode::NOT_FOUND => Ok(None),
+            _ => Err(Error::new(
+                ErrorKind::Unexpected,
+                "instance metadata service returned unexpected status",
+            )
+            .with_operation("AwsImdsCredentialProvider::get_credential")
+            .with_context("status", status.as_str())
+            .with_context("response", String::from_utf8_lossy(&bs))
+            .set_temporary()),
+        }
+    }
+}
+
+#[async_trait]
+impl CredentialProvider<AwsCredential> for AwsImdsCredentialProvider {
+    async fn get_credential(&self) -> Result<Option<Credential<AwsCredential>>> {
+        let req = Request::put(format!("{}/latest/api/token", self.endpoint))
+            .header(
+                "x-aws-ec2-metadata-token-ttl-seconds",
+                IMDS_TOKEN_TTL_SECONDS,
+            )
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        let token = match self.fetch(req).await? {
+            Some(bs) => String::from_utf8_lossy(&bs).trim().to_string(),
+            None => return Ok(None),
+        };
+
+        let url = format!(
+            "{}/latest/meta-data/iam/security-credentials/",
+            self.endpoint
+        );
+        let req = Request::get(&url)
+            .header("x-aws-ec2-metadata-token", &token)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        let role = match self.fetch(req).await? {
+            Some(bs) => String::from_utf8_lossy(&bs).trim().to_string(),
+            // No IAM role attached to this instance.
+            None => return Ok(None),
+        };
+        // Only the first role will be used, EC2 allows only one role per instance.
+        let role = match role.lines().next() {
+            Some(v) if !v.is_empty() => v.to_string(),
+            _ => return Ok(None),
+        };
+
+        let req = Request::get(format!("{url}{role}"))
+            .header("x-aws-ec2-metadata-token", &token)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        let bs = match self.fetch(req).await? {
+            Some(bs) => bs,
+            None => return Ok(None),
+        };
+
+        let resp: ImdsCredentialResponse =
+            serde_json::from_reader(bs.reader()).map_err(new_json_deserialize_error)?;
+        if resp.code != "Success" {
+            return Err(Error::new(
+                ErrorKind::PermissionDenied,
+                "instance metadata service failed to provide credential",
+            )
+            .with_operation("AwsImdsCredentialProvider::get_credential")
+            .with_context("code", resp.code));
+        }
+
+        let expires_at = parse_datetime_from_rfc3339(&resp.expiration)?;
+        let cred = AwsCredential::new(&resp.access_key_id, &resp.secret_access_key)
+            .with_session_token(&resp.token);
+        Ok(Some(Credential::new(cred).with_expires_at(expires_at)))
+    }
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct ImdsCredentialResponse {
+    code: String,
+    access_key_id: String,
+    secret_access_key: String,
+    token: String,
+    expiration: String,
+}
+
+/// Load credentials by assuming a role via AWS STS.
+///
+/// The source credential used to call STS is loaded from the environment
+/// in the same way as the S3 service does by default.
+pub struct AwsAssumeRoleCredentialProvider {
+    client: HttpClient,
+    role_arn: String,
+    loader: AwsAssumeRoleLoader,
+}
+
+impl Debug for AwsAssumeRoleCredentialProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AwsAssumeRoleCredentialProvider")
+            .field("role_arn", &self.role_arn)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AwsAssumeRoleCredentialProvider {
+    /// Create a new provider that assumes `role_arn` in `region`.
+    pub fn new(
+        client: HttpClient,
+        region: &str,
+        role_arn: &str,
+        external_id: Option<&str>,
+    ) -> Result<Self> {
+        let source = AwsDefaultLoader::new(client.client(), AwsConfig::default().from_env());
+        let cfg = AwsConfig {
+            region: Some(region.to_string()),
+            role_arn: Some(role_arn.to_string()),
+            external_id: external_id.map(|v| v.to_string()),
+            sts_regional_endpoints: "regional".to_string(),
+            ..Default::default()
+        };
+        let loader =
+            AwsAssumeRoleLoader::new(client.client(), cfg, Box::new(source)).map_err(|err| {
+                Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "the assume role provider is misconfigured",
+                )
+                .with_context("role_arn", role_arn)
+                .set_source(err)
+            })?;
+
+        Ok(Self {
+            client,
+            role_arn: role_arn.to_string(),
+            loader,
+        })
+    }
+}
+
+#[async_trait]
+impl CredentialProvider<AwsCredential> for AwsAssumeRoleCredentialProvider {
+    async fn get_credential(&self) -> Result<Option<Credential<AwsCredential>>> {
+        let cred = self
+            .loader
+            .load_credential(self.client.client())
+            .await
+            .map_err(new_request_credential_error)?;
+
+        Ok(cred.map(into_credential))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::matchers::header;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_imds_credential_provider() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/latest/api/token"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("imds-token"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/latest/meta-data/iam/security-credentials/"))
+            .and(header("x-aws-ec2-metadata-token", "imds-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("test-role\n"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/latest/meta-data/iam/security-credentials/test-role"))
+            .and(header("x-aws-ec2-metadata-token", "imds-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{
+  "Code": "Success",
+  "AccessKeyId": "access_key_id",
+  "SecretAccessKey": "secret_access_key",
+  "Token": "session_token",
+  "Expiration": "2030-01-01T00:00:00Z"
+}"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let provider =
+            AwsImdsCredentialProvider::new(HttpClient::new()?).with_endpoint(&mock_server.uri());
+        let cred = provider
+            .get_credential()
+            .await?
+            .expect("must have credential");
+        assert_eq!(
+            cred.expires_at(),
+            Some(parse_datetime_from_rfc3339("2030-01-01T00:00:00Z")?)
+        );
+        assert_eq!(cred.value().access_key_id(), "access_key_id");
+        assert_eq!(cred.value().secret_access_key(), "secret_access_key");
+        assert_eq!(cred.value().session_token(), Some("session_token"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_imds_credential_provider_without_role() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/latest/api/token"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("imds-token"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/latest/meta-data/iam/security-credentials/"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+
+        let provider =
+            AwsImdsCredentialProvider::new(HttpClient::new()?).with_endpoint(&mock_server.uri());
+        assert!(provider.get_credential().await?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_credential_provider_load() {
+        #[derive(Debug)]
+        struct StaticProvider;
+
+        #[async_trait]
+        impl CredentialProvider<AwsCredential> for StaticProvider {
+            async fn get_credential(&self) -> Result<Option<Credential<AwsCredential>>> {
+                let expires_at = parse_datetime_from_rfc3339("2030-01-01T00:00:00Z")?;
+                Ok(Some(
+                    Credential::new(AwsCredential::new("ak", "sk").with_session_token("token"))
+                        .with_expires_at(expires_at),
+                ))
+            }
+        }
+
+        let load = CredentialProviderLoad::new(StaticProvider);
+        let cred = load
+            .load_credential(reqwest::Client::new())
+            .await
+            .unwrap()
+            .expect("must have credential");
+        assert_eq!(cred.access_key_id, "ak");
+        assert_eq!(cred.secret_access_key, "sk");
+        assert_eq!(cred.session_token.as_deref(), Some("token"));
+        assert_eq!(
+            cred.expires_in,
+            Some(parse_datetime_from_rfc3339("2030-01-01T00:00:00Z").unwrap())
+        );
+    }
+}
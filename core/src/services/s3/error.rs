@@ -16,6 +16,8 @@
 // under the License.
 
 use bytes::Buf;
+use bytes::Bytes;
+use http::response::Parts;
 use http::Response;
 use quick_xml::de;
 use serde::Deserialize;
@@ -40,6 +42,24 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     let (parts, body) = resp.into_parts();
     let bs = body.bytes().await?;
 
+    Ok(build_error(parts, bs).0)
+}
+
+/// Parse error response of GetObjectRetention and GetObjectLegalHold.
+///
+/// Returns `None` if the object doesn't have retention or legal hold
+/// configured, which is not an error for us.
+pub async fn parse_object_lock_error(resp: Response<IncomingAsyncBody>) -> Result<Option<Error>> {
+    let (parts, body) = resp.into_parts();
+    let bs = body.bytes().await?;
+
+    match build_error(parts, bs) {
+        (_, Some(s3_err)) if s3_err.code == "NoSuchObjectLockConfiguration" => Ok(None),
+        (err, _) => Ok(Some(err)),
+    }
+}
+
+fn build_error(parts: Parts, bs: Bytes) -> (Error, Option<S3Error>) {
     let (mut kind, mut retryable) = match parts.status.as_u16() {
         403 => (ErrorKind::PermissionDenied, false),
         404 => (ErrorKind::NotFound, false),
@@ -47,6 +67,7 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
         // Service like R2 could return 499 error with a message like:
         // Client Disconnect, we should retry it.
         499 => (ErrorKind::Unexpected, true),
+        429 => (ErrorKind::RateLimited, true),
         500 | 502 | 503 | 504 => (ErrorKind::Unexpected, true),
         _ => (ErrorKind::Unexpected, false),
     };
@@ -55,19 +76,52 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
         .map(|s3_err| (format!("{s3_err:?}"), Some(s3_err)))
         .unwrap_or_else(|_| (String::from_utf8_lossy(&bs).into_owned(), None));
 
-    if let Some(s3_err) = s3_err {
+    if let Some(s3_err) = &s3_err {
         (kind, retryable) = parse_s3_error_code(s3_err.code.as_str()).unwrap_or((kind, retryable));
+
+        if is_object_locked(parts.status.as_u16(), s3_err) {
+            (kind, retryable) = (ErrorKind::ObjectLocked, false);
+        }
     }
 
     let mut err = Error::new(kind, &message);
 
     err = with_error_response_context(err, parts);
+    err = with_error_response_body(err, &bs);
+
+    // Fallback to the request id in the response body if the header is missing.
+    if let Some(s3_err) = &s3_err {
+        if err.request_id().is_none() && !s3_err.request_id.is_empty() {
+            err = err.with_context("request_id", &s3_err.request_id);
+        }
+    }
 
     if retryable {
         err = err.set_temporary();
     }
 
-    Ok(err)
+    (err, s3_err)
+}
+
+/// Check whether the error is returned because the object is protected by
+/// object lock.
+fn is_object_locked(status: u16, s3_err: &S3Error) -> bool {
+    match (status, s3_err.code.as_str()) {
+        // Returned by s3 compatible services like minio.
+        (_, "ObjectLocked") => true,
+        // > InvalidRequest: Object is WORM protected and cannot be overwritten.
+        //
+        // Returned while overwriting a locked object or shortening the
+        // retention of an object locked in compliance mode.
+        (403, "InvalidRequest") => true,
+        // S3 denies deleting a locked object version with a plain
+        // `AccessDenied`, only the message tells it from others.
+        (403, "AccessDenied") => {
+            let message = s3_err.message.to_lowercase();
+            message.contains("object lock") || message.contains("worm protected")
+        }
+        _ => false,
+    }
 }
 
 /// Returns the `Error kind` of this code and whether the error is retryable.
@@ -94,14 +148,36 @@ pub fn parse_s3_error_code(code: &str) -> Option<(ErrorKind, bool)> {
         // indicates a temporary issue with the service or server, such as high load,
         // maintenance, or an internal problem.
         "ServiceUnavailable" => Some((ErrorKind::Unexpected, true)),
+        // Returned by s3 compatible services like minio while deleting or
+        // overwriting a locked object.
+        "ObjectLocked" => Some((ErrorKind::ObjectLocked, false)),
+        // > A conflicting operation occurred. If using PutObject you can
+        // > retry the request.
+        //
+        // Returned with 409 when a conditional write races with another
+        // one, which means the precondition doesn't hold anymore.
+        "ConditionalRequestConflict" => Some((ErrorKind::ConditionNotMatch, false)),
         _ => None,
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use futures::stream;
+    use http::StatusCode;
+
     use super::*;
 
+    fn new_response(status: StatusCode, content: &'static str) -> Response<IncomingAsyncBody> {
+        let body = IncomingAsyncBody::new(
+            Box::new(oio::into_stream(stream::iter(vec![Ok(Bytes::from(
+                content,
+            ))]))),
+            None,
+        );
+        Response::builder().status(status).body(body).unwrap()
+    }
+
     /// Error response example is from https://docs.aws.amazon.com/AmazonS3/latest/API/ErrorResponses.html
     #[test]
     fn test_parse_error() {
@@ -125,4 +201,105 @@ mod tests {
         assert_eq!(out.resource, "/mybucket/myfoto.jpg");
         assert_eq!(out.request_id, "4442587FB7D0A2F9");
     }
+
+    #[tokio::test]
+    async fn test_parse_error_conditional_request_conflict() -> Result<()> {
+        let resp = new_response(
+            StatusCode::CONFLICT,
+            r#"<Error>
+  <Code>ConditionalRequestConflict</Code>
+  <Message>A conflicting operation occurred.</Message>
+  <RequestId>4442587FB7D0A2F9</RequestId>
+</Error>"#,
+        );
+        let err = parse_error(resp).await?;
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+        assert!(!err.is_temporary());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_parse_error_object_locked() -> Result<()> {
+        let resp = new_response(
+            StatusCode::FORBIDDEN,
+            r#"<Error>
+  <Code>AccessDenied</Code>
+  <Message>Access Denied because object protected by object lock.</Message>
+  <RequestId>4442587FB7D0A2F9</RequestId>
+</Error>"#,
+        );
+        let err = parse_error(resp).await?;
+        assert_eq!(err.kind(), ErrorKind::ObjectLocked);
+
+        let resp = new_response(
+            StatusCode::FORBIDDEN,
+            r#"<Error>
+  <Code>AccessDenied</Code>
+  <Message>Access Denied</Message>
+  <RequestId>4442587FB7D0A2F9</RequestId>
+</Error>"#,
+        );
+        let err = parse_error(resp).await?;
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        let resp = new_response(
+            StatusCode::FORBIDDEN,
+            r#"<Error>
+  <Code>InvalidRequest</Code>
+  <Message>Object is WORM protected and cannot be overwritten.</Message>
+  <RequestId>4442587FB7D0A2F9</RequestId>
+</Error>"#,
+        );
+        let err = parse_error(resp).await?;
+        assert_eq!(err.kind(), ErrorKind::ObjectLocked);
+
+        let resp = new_response(
+            StatusCode::BAD_REQUEST,
+            r#"<Error>
+  <Code>InvalidRequest</Code>
+  <Message>Bucket is missing Object Lock Configuration</Message>
+  <RequestId>4442587FB7D0A2F9</RequestId>
+</Error>"#,
+        );
+        let err = parse_error(resp).await?;
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        let resp = new_response(
+            StatusCode::BAD_REQUEST,
+            r#"<Error>
+  <Code>ObjectLocked</Code>
+  <Message>Object is WORM protected and cannot be overwritten</Message>
+  <RequestId>4442587FB7D0A2F9</RequestId>
+</Error>"#,
+        );
+        let err = parse_error(resp).await?;
+        assert_eq!(err.kind(), ErrorKind::ObjectLocked);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_parse_object_lock_error() -> Result<()> {
+        let resp = new_response(
+            StatusCode::NOT_FOUND,
+            r#"<Error>
+  <Code>NoSuchObjectLockConfiguration</Code>
+  <Message>The specified object does not have a ObjectLock configuration</Message>
+</Error>"#,
+        );
+        assert!(parse_object_lock_error(resp).await?.is_none());
+
+        let resp = new_response(
+            StatusCode::NOT_FOUND,
+            r#"<Error>
+  <Code>NoSuchKey</Code>
+  <Message>The specified key does not exist.</Message>
+</Error>"#,
+        );
+        let err = parse_object_lock_error(resp).await?.expect("must be error");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        Ok(())
+    }
 }
@@ -19,6 +19,10 @@ mod backend;
 pub use backend::S3Builder as S3;
 
 mod core;
+mod credential;
+pub use credential::AwsAssumeRoleCredentialProvider;
+pub use credential::AwsCredential;
+pub use credential::AwsImdsCredentialProvider;
 mod error;
 mod pager;
 mod writer;
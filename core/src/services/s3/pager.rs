@@ -18,14 +18,19 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
 use bytes::Buf;
 use quick_xml::de;
 use serde::Deserialize;
 
 use super::core::S3Core;
 use super::error::parse_error;
+use crate::is_md5_hex;
 use crate::raw::*;
 use crate::EntryMode;
+use crate::Error;
+use crate::ErrorKind;
 use crate::Metadata;
 use crate::Result;
 
@@ -39,6 +44,11 @@ pub struct S3Pager {
     /// Amazon S3 starts listing **after** this specified key
     start_after: Option<String>,
 
+    /// List all versions of objects via ListObjectVersions.
+    versions: bool,
+    /// Return delete markers along with versions.
+    deleted: bool,
+
     token: String,
     done: bool,
 
@@ -46,22 +56,18 @@ pub struct S3Pager {
 }
 
 impl S3Pager {
-    pub fn new(
-        core: Arc<S3Core>,
-        path: &str,
-        delimiter: &str,
-        limit: Option<usize>,
-        start_after: Option<&str>,
-    ) -> Self {
+    pub fn new(core: Arc<S3Core>, path: &str, args: &OpList) -> Self {
         Self {
             core,
 
             path: path.to_string(),
-            delimiter: delimiter.to_string(),
-            limit,
-            start_after: start_after.map(String::from),
+            delimiter: args.delimiter().to_string(),
+            limit: args.limit(),
+            start_after: args.start_after().map(String::from),
+            versions: args.versions(),
+            deleted: args.deleted(),
 
-            token: "".to_string(),
+            token: args.continuation_token().unwrap_or_default().to_string(),
             done: false,
 
             runtime: None,
@@ -71,6 +77,146 @@ impl S3Pager {
     pub fn set_runtime(&mut self, runtime: Arc<tokio::runtime::Runtime>) {
         self.runtime = Some(runtime);
     }
+
+    /// Fetch the next page of versions.
+    ///
+    /// ListObjectVersions is paginated by both `NextKeyMarker` and
+    /// `NextVersionIdMarker`, we will encode them into one token.
+    async fn next_versions(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        let (key_marker, version_id_marker) = if self.token.is_empty() {
+            // Versions of objects after `start_after` will be listed.
+            let key_marker = match &self.start_after {
+                Some(v) => build_abs_path(&self.core.root, v),
+                None => String::new(),
+            };
+            (key_marker, String::new())
+        } else {
+            decode_versions_token(&self.token)?
+        };
+
+        let resp = self
+            .core
+            .s3_list_object_versions(
+                &self.path,
+                &key_marker,
+                &version_id_marker,
+                &self.delimiter,
+                self.limit,
+            )
+            .await?;
+
+        if resp.status() != http::StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body().bytes().await?;
+
+        let output: VersionsOutput =
+            de::from_reader(bs.reader()).map_err(new_xml_deserialize_error)?;
+
+        self.done = !output.is_truncated.unwrap_or_default();
+        self.token = match (&output.next_key_marker, &output.next_version_id_marker) {
+            (Some(key), version) if !self.done => {
+                encode_versions_token(key, version.as_deref().unwrap_or_default())
+            }
+            _ => String::new(),
+        };
+        if self.token.is_empty() {
+            self.done = true;
+        }
+
+        let mut entries = Vec::with_capacity(output.common_prefixes.len() + output.version.len());
+
+        for prefix in output.common_prefixes {
+            let de = oio::Entry::new(
+                &build_rel_path(&self.core.root, &prefix.prefix),
+                Metadata::new(EntryMode::DIR),
+            );
+
+            entries.push(de);
+        }
+
+        let mut files = Vec::with_capacity(output.version.len());
+
+        for object in output.version {
+            if object.key.ends_with('/') {
+                continue;
+            }
+
+            let mut meta = Metadata::new(EntryMode::FILE);
+
+            meta.set_version(&object.version_id);
+            meta.set_etag(&object.etag);
+            set_content_md5_from_etag(&mut meta, &object.etag);
+            meta.set_content_length(object.size);
+            meta.set_last_modified(parse_datetime_from_rfc3339(object.last_modified.as_str())?);
+
+            files.push((object.key, meta));
+        }
+
+        if self.deleted {
+            for marker in output.delete_marker {
+                if marker.key.ends_with('/') {
+                    continue;
+                }
+
+                let mut meta = Metadata::new(EntryMode::FILE);
+
+                meta.set_version(&marker.version_id);
+                meta.set_is_deleted(true);
+                meta.set_last_modified(parse_datetime_from_rfc3339(marker.last_modified.as_str())?);
+
+                files.push((marker.key, meta));
+            }
+
+            // Versions and delete markers are returned in separate lists,
+            // keep versions of the same key together from the latest to the
+            // oldest. The sort is stable, so ties keep the service's order.
+            files.sort_by(|(lk, lm), (rk, rm)| {
+                lk.cmp(rk)
+                    .then_with(|| rm.last_modified().cmp(&lm.last_modified()))
+            });
+        }
+
+        for (key, meta) in files {
+            entries.push(oio::Entry::new(
+                &build_rel_path(&self.core.root, &key),
+                meta,
+            ));
+        }
+
+        Ok(Some(entries))
+    }
+}
+
+/// Encode key marker and version id marker into one continuation token.
+fn encode_versions_token(key_marker: &str, version_id_marker: &str) -> String {
+    format!(
+        "{}.{}",
+        URL_SAFE_NO_PAD.encode(key_marker),
+        URL_SAFE_NO_PAD.encode(version_id_marker)
+    )
+}
+
+fn decode_versions_token(token: &str) -> Result<(String, String)> {
+    let new_invalid_error = || {
+        Error::new(
+            ErrorKind::InvalidInput,
+            "continuation token is not a valid versions token",
+        )
+        .with_context("token", token)
+    };
+
+    let (key, version) = token.split_once('.').ok_or_else(new_invalid_error)?;
+    let decode = |v: &str| {
+        URL_SAFE_NO_PAD
+            .decode(v)
+            .ok()
+            .and_then(|v| String::from_utf8(v).ok())
+            .ok_or_else(new_invalid_error)
+    };
+
+    Ok((decode(key)?, decode(version)?))
 }
 
 #[async_trait]
@@ -79,6 +225,9 @@ impl oio::Page for S3Pager {
         if self.done {
             return Ok(None);
         }
+        if self.versions {
+            return self.next_versions().await;
+        }
 
         let resp = self
             .core
@@ -135,7 +284,7 @@ impl oio::Page for S3Pager {
             let mut meta = Metadata::new(EntryMode::FILE);
 
             meta.set_etag(&object.etag);
-            meta.set_content_md5(object.etag.trim_matches('"'));
+            set_content_md5_from_etag(&mut meta, &object.etag);
             meta.set_content_length(object.size);
 
             // object.last_modified provides more precious time that contains
@@ -149,14 +298,26 @@ impl oio::Page for S3Pager {
 
         Ok(Some(entries))
     }
+
+    fn continuation_token(&self) -> Option<String> {
+        if self.done || self.token.is_empty() {
+            None
+        } else {
+            Some(self.token.clone())
+        }
+    }
 }
 
 impl oio::BlockingPage for S3Pager {
     fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
-        self.runtime
-            .clone()
-            .unwrap()
-            .block_on(oio::Page::next(self))
+        let runtime = self.runtime.clone().ok_or_else(|| {
+            Error::new(
+                ErrorKind::Unsupported,
+                "blocking list is not supported without runtime",
+            )
+            .with_operation("S3Pager::blocking_next")
+        })?;
+        block_on(&runtime, oio::Page::next(self))
     }
 }
 
@@ -188,15 +349,61 @@ struct OutputContent {
     etag: String,
 }
 
+/// Output of ListObjectVersions.
+///
+/// Delete markers are interleaved with versions in the response, but
+/// deserialized into their own list.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct VersionsOutput {
+    is_truncated: Option<bool>,
+    next_key_marker: Option<String>,
+    next_version_id_marker: Option<String>,
+    common_prefixes: Vec<OutputCommonPrefix>,
+    version: Vec<OutputVersion>,
+    delete_marker: Vec<OutputDeleteMarker>,
+}
+
+#[derive(Default, Debug, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct OutputVersion {
+    key: String,
+    version_id: String,
+    size: u64,
+    last_modified: String,
+    #[serde(rename = "ETag")]
+    etag: String,
+}
+
+#[derive(Default, Debug, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct OutputDeleteMarker {
+    key: String,
+    version_id: String,
+    last_modified: String,
+}
+
 #[derive(Default, Debug, Eq, PartialEq, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 struct OutputCommonPrefix {
     prefix: String,
 }
 
+/// Set the etag as content md5 only if it looks like a md5 digest.
+///
+/// Etags of multipart uploads end with `-N` and are not the md5 of the
+/// content, they must not be compared as content md5.
+fn set_content_md5_from_etag(meta: &mut Metadata, etag: &str) {
+    let etag = etag.trim_matches('"');
+    if is_md5_hex(etag) {
+        meta.set_content_md5(etag);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Metakey;
 
     #[test]
     fn test_parse_list_output() {
@@ -261,4 +468,104 @@ mod tests {
             ]
         )
     }
+
+    #[test]
+    fn test_set_content_md5_from_etag() {
+        let mut meta = Metadata::new(EntryMode::FILE);
+        set_content_md5_from_etag(&mut meta, "\"d41d8cd98f00b204e9800998ecf8427e\"");
+        assert_eq!(meta.content_md5(), Some("d41d8cd98f00b204e9800998ecf8427e"));
+
+        let mut meta = Metadata::new(EntryMode::FILE);
+        set_content_md5_from_etag(&mut meta, "\"d41d8cd98f00b204e9800998ecf8427e-2\"");
+        assert!(!meta.bit().contains(Metakey::ContentMd5));
+    }
+
+    #[test]
+    fn test_parse_list_versions_output() {
+        let bs = bytes::Bytes::from(
+            r#"<ListVersionsResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>bucket</Name>
+  <Prefix>my</Prefix>
+  <KeyMarker/>
+  <VersionIdMarker/>
+  <NextKeyMarker>my-second-image.jpg</NextKeyMarker>
+  <NextVersionIdMarker>3/L4kqtJlcpXroDTDmJ+rmSpXd3dIbrHY</NextVersionIdMarker>
+  <MaxKeys>5</MaxKeys>
+  <IsTruncated>true</IsTruncated>
+  <Version>
+    <Key>my-image.jpg</Key>
+    <VersionId>3/L4kqtJl40Nr8X8gdRQBpUMLUo</VersionId>
+    <IsLatest>true</IsLatest>
+    <LastModified>2009-10-12T17:50:30.000Z</LastModified>
+    <ETag>"fba9dede5f27731c9771645a39863328"</ETag>
+    <Size>434234</Size>
+    <StorageClass>STANDARD</StorageClass>
+  </Version>
+  <DeleteMarker>
+    <Key>my-second-image.jpg</Key>
+    <VersionId>03jpff543dhffds434rfdsFDN943fdsFkdmqnh892</VersionId>
+    <IsLatest>true</IsLatest>
+    <LastModified>2009-11-12T17:50:30.000Z</LastModified>
+  </DeleteMarker>
+  <Version>
+    <Key>my-second-image.jpg</Key>
+    <VersionId>QUpfdndhfd8438MNFDN93jdnJFkdmqnh893</VersionId>
+    <IsLatest>false</IsLatest>
+    <LastModified>2009-10-10T17:50:30.000Z</LastModified>
+    <ETag>"9b2cf535f27731c974343645a3985328"</ETag>
+    <Size>166434</Size>
+    <StorageClass>STANDARD</StorageClass>
+  </Version>
+</ListVersionsResult>"#,
+        );
+
+        let out: VersionsOutput = de::from_reader(bs.reader()).expect("must success");
+
+        assert!(out.is_truncated.unwrap());
+        assert_eq!(out.next_key_marker.as_deref(), Some("my-second-image.jpg"));
+        assert_eq!(
+            out.next_version_id_marker.as_deref(),
+            Some("3/L4kqtJlcpXroDTDmJ+rmSpXd3dIbrHY")
+        );
+        assert_eq!(
+            out.version
+                .iter()
+                .map(|v| (v.key.as_str(), v.version_id.as_str(), v.size))
+                .collect::<Vec<_>>(),
+            vec![
+                ("my-image.jpg", "3/L4kqtJl40Nr8X8gdRQBpUMLUo", 434234),
+                (
+                    "my-second-image.jpg",
+                    "QUpfdndhfd8438MNFDN93jdnJFkdmqnh893",
+                    166434
+                ),
+            ]
+        );
+        assert_eq!(
+            out.delete_marker,
+            vec![OutputDeleteMarker {
+                key: "my-second-image.jpg".to_string(),
+                version_id: "03jpff543dhffds434rfdsFDN943fdsFkdmqnh892".to_string(),
+                last_modified: "2009-11-12T17:50:30.000Z".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_versions_token() {
+        let cases = vec![
+            ("", ""),
+            ("path/to/file", "3/L4kqtJlcpXroDTDmJ+rmSpXd3dIbrHY="),
+            ("path.with.dot", "null"),
+        ];
+
+        for (key, version) in cases {
+            let token = encode_versions_token(key, version);
+            let (k, v) = decode_versions_token(&token).expect("decode must succeed");
+            assert_eq!((k.as_str(), v.as_str()), (key, version));
+        }
+
+        let err = decode_versions_token("invalid").expect_err("decode must fail");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
 }
@@ -19,6 +19,7 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use bytes::Buf;
+use http::Response;
 use http::StatusCode;
 
 use super::core::*;
@@ -35,30 +36,48 @@ pub struct S3Writer {
 
 impl S3Writer {
     pub fn new(core: Arc<S3Core>, path: &str, op: OpWrite) -> oio::MultipartUploadWriter<Self> {
-        let write_min_size = core.write_min_size;
+        let write_min_size = op.chunk().unwrap_or(core.write_min_size);
+        let concurrent = op.concurrent();
 
         let total_size = op.content_length();
+        let part_callback = op.part_callback().cloned();
+        let checkpoint = op.resume().cloned();
         let s3_writer = S3Writer {
             core,
             path: path.to_string(),
             op,
         };
 
-        oio::MultipartUploadWriter::new(s3_writer, total_size).with_write_min_size(write_min_size)
+        oio::MultipartUploadWriter::new(s3_writer, total_size)
+            .with_write_min_size(write_min_size)
+            .with_concurrent(concurrent)
+            .with_part_callback(part_callback)
+            .with_checkpoint(checkpoint)
+    }
+}
+
+impl S3Writer {
+    /// S3 returns `412 Precondition Failed` if the file exists while
+    /// writing with `If-None-Match: *`, we turn it into `AlreadyExists`.
+    async fn parse_write_error(&self, resp: Response<IncomingAsyncBody>) -> Result<Error> {
+        let err = parse_error(resp).await?;
+
+        if self.op.is_create_only() && err.kind() == ErrorKind::ConditionNotMatch {
+            return Ok(Error::new(ErrorKind::AlreadyExists, "file already exists")
+                .with_operation("S3Writer::write")
+                .set_source(err));
+        }
+
+        Ok(err)
     }
 }
 
 #[async_trait]
 impl oio::MultipartUploadWrite for S3Writer {
     async fn write_once(&self, size: u64, body: AsyncBody) -> Result<()> {
-        let mut req = self.core.s3_put_object_request(
-            &self.path,
-            Some(size),
-            self.op.content_type(),
-            self.op.content_disposition(),
-            self.op.cache_control(),
-            body,
-        )?;
+        let mut req = self
+            .core
+            .s3_put_object_request(&self.path, Some(size), &self.op, body)?;
 
         self.core.sign(&mut req).await?;
 
@@ -71,19 +90,25 @@ impl oio::MultipartUploadWrite for S3Writer {
                 resp.into_body().consume().await?;
                 Ok(())
             }
-            _ => Err(parse_error(resp).await?),
+            _ => Err(self.parse_write_error(resp).await?),
         }
     }
 
     async fn initiate_part(&self) -> Result<String> {
+        // Content md5 describes the whole object, but s3 can only verify
+        // it for a single put. Reject it instead of ignoring it silently.
+        if self.op.content_md5().is_some() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "content md5 can't be verified by multipart upload, write in one request instead",
+            )
+            .with_operation("S3Writer::initiate_part")
+            .with_context("path", &self.path));
+        }
+
         let resp = self
             .core
-            .s3_initiate_multipart_upload(
-                &self.path,
-                self.op.content_type(),
-                self.op.content_disposition(),
-                self.op.cache_control(),
-            )
+            .s3_initiate_multipart_upload(&self.path, &self.op)
             .await?;
 
         let status = resp.status();
@@ -111,9 +136,14 @@ impl oio::MultipartUploadWrite for S3Writer {
         // AWS S3 requires part number must between [1..=10000]
         let part_number = part_number + 1;
 
-        let mut req =
-            self.core
-                .s3_upload_part_request(&self.path, upload_id, part_number, size, body)?;
+        let mut req = self.core.s3_upload_part_request(
+            &self.path,
+            upload_id,
+            part_number,
+            size,
+            &self.op,
+            body,
+        )?;
 
         self.core.sign(&mut req).await?;
 
@@ -155,7 +185,13 @@ impl oio::MultipartUploadWrite for S3Writer {
 
         let resp = self
             .core
-            .s3_complete_multipart_upload(&self.path, upload_id, parts)
+            .s3_complete_multipart_upload(
+                &self.path,
+                upload_id,
+                parts,
+                self.op.if_match(),
+                self.op.if_none_match(),
+            )
             .await?;
 
         let status = resp.status();
@@ -166,7 +202,7 @@ impl oio::MultipartUploadWrite for S3Writer {
 
                 Ok(())
             }
-            _ => Err(parse_error(resp).await?),
+            _ => Err(self.parse_write_error(resp).await?),
         }
     }
 
@@ -19,14 +19,22 @@ use std::cmp::min;
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::ops::Deref;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
 
 use async_trait::async_trait;
+use bb8::PooledConnection;
+use bb8::RunError;
 use futures::StreamExt;
 use log::debug;
 use openssh::KnownHosts;
 use openssh::SessionBuilder;
+use openssh_sftp_client::Error as SftpClientError;
 use openssh_sftp_client::Sftp;
 use openssh_sftp_client::SftpOptions;
 
@@ -53,6 +61,8 @@ pub struct SftpBuilder {
     key: Option<String>,
     known_hosts_strategy: Option<String>,
     enable_copy: bool,
+    keepalive_interval: Option<Duration>,
+    max_connections: Option<u32>,
 }
 
 impl Debug for SftpBuilder {
@@ -132,23 +142,72 @@ impl SftpBuilder {
 
         self
     }
+
+    /// set keepalive interval for sftp backend.
+    ///
+    /// If set, the ssh session will send keepalive messages to the server
+    /// every `interval` so idle connections won't be dropped by the server
+    /// or middle boxes. Connections that are found broken will be
+    /// re-established transparently on the next operation.
+    pub fn keepalive_interval(&mut self, interval: Duration) -> &mut Self {
+        self.keepalive_interval = Some(interval);
+
+        self
+    }
+
+    /// set the maximum number of connections in the pool.
+    ///
+    /// All operations share one ssh session by default. Every connection is
+    /// an ssh session, please make sure the server allows this number of
+    /// sessions (`MaxSessions` and `MaxStartups` of sshd) before increasing
+    /// it.
+    ///
+    /// default: 1
+    pub fn max_connections(&mut self, max_connections: u32) -> &mut Self {
+        if max_connections > 0 {
+            self.max_connections = Some(max_connections);
+        }
+
+        self
+    }
 }
 
 impl Builder for SftpBuilder {
     const SCHEME: Scheme = Scheme::Sftp;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&[
+        "root",
+        "endpoint",
+        "user",
+        "key",
+        "known_hosts_strategy",
+        "keepalive_interval",
+        "max_connections",
+    ]);
     type Accessor = SftpBackend;
 
+    fn validate(&self) -> Result<()> {
+        if self.endpoint.is_none() {
+            return Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+                .with_operation("Builder::validate")
+                .with_context("service", Scheme::Sftp)
+                .with_context("key", "endpoint"));
+        }
+
+        if self.user.is_none() {
+            return Err(Error::new(ErrorKind::ConfigInvalid, "user is empty")
+                .with_operation("Builder::validate")
+                .with_context("service", Scheme::Sftp)
+                .with_context("key", "user"));
+        }
+
+        Ok(())
+    }
+
     fn build(&mut self) -> Result<Self::Accessor> {
         debug!("sftp backend build started: {:?}", &self);
-        let endpoint = match self.endpoint.clone() {
-            Some(v) => v,
-            None => return Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")),
-        };
-
-        let user = match self.user.clone() {
-            Some(v) => v,
-            None => return Err(Error::new(ErrorKind::ConfigInvalid, "user is empty")),
-        };
+        self.validate()?;
+        let endpoint = self.endpoint.clone().unwrap_or_default();
+        let user = self.user.clone().unwrap_or_default();
 
         let root = self
             .root
@@ -184,7 +243,9 @@ impl Builder for SftpBuilder {
             key: self.key.clone(),
             known_hosts_strategy,
             copyable: self.enable_copy,
-            client: tokio::sync::OnceCell::new(),
+            keepalive_interval: self.keepalive_interval,
+            max_connections: self.max_connections.unwrap_or(1),
+            pool: tokio::sync::OnceCell::new(),
         })
     }
 
@@ -197,6 +258,12 @@ impl Builder for SftpBuilder {
         map.get("key").map(|v| builder.key(v));
         map.get("known_hosts_strategy")
             .map(|v| builder.known_hosts_strategy(v));
+        map.get("keepalive_interval")
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(|v| builder.keepalive_interval(Duration::from_secs(v)));
+        map.get("max_connections")
+            .and_then(|v| v.parse::<u32>().ok())
+            .map(|v| builder.max_connections(v));
 
         builder
     }
@@ -210,7 +277,9 @@ pub struct SftpBackend {
     key: Option<String>,
     known_hosts_strategy: KnownHosts,
     copyable: bool,
-    client: tokio::sync::OnceCell<Sftp>,
+    keepalive_interval: Option<Duration>,
+    max_connections: u32,
+    pool: tokio::sync::OnceCell<bb8::Pool<Manager>>,
 }
 
 impl Debug for SftpBackend {
@@ -268,7 +337,7 @@ impl Accessor for SftpBackend {
         let mut current = PathBuf::from(&self.root);
         for p in paths {
             current = current.join(p);
-            let res = fs.create_dir(p).await;
+            let res = client.check(fs.create_dir(p).await);
 
             if let Err(e) = res {
                 // ignore error if dir already exists
@@ -287,14 +356,17 @@ impl Accessor for SftpBackend {
 
         let mut fs = client.fs();
         fs.set_cwd(&self.root);
-        let path = fs.canonicalize(path).await?;
+        let path = client.check(fs.canonicalize(path).await)?;
 
-        let mut file = client.open(path.as_path()).await?;
+        let mut file = client.check(client.open(path.as_path()).await)?;
 
-        let total_length = file.metadata().await?.len().ok_or(Error::new(
-            ErrorKind::NotFound,
-            format!("file not found: {}", path.to_str().unwrap()).as_str(),
-        ))?;
+        let total_length = client
+            .check(file.metadata().await)?
+            .len()
+            .ok_or(Error::new(
+                ErrorKind::NotFound,
+                format!("file not found: {}", path.to_str().unwrap()).as_str(),
+            ))?;
 
         let br = args.range();
         let (start, end) = match (br.offset(), br.size()) {
@@ -329,9 +401,9 @@ impl Accessor for SftpBackend {
 
         let mut fs = client.fs();
         fs.set_cwd(&self.root);
-        let path = fs.canonicalize(path).await?;
+        let path = client.check(fs.canonicalize(path).await)?;
 
-        let file = client.create(&path).await?;
+        let file = client.check(client.create(&path).await)?;
 
         Ok((RpWrite::new(), SftpWriter::new(file)))
     }
@@ -345,46 +417,51 @@ impl Accessor for SftpBackend {
 
         let mut fs = client.fs();
         fs.set_cwd(&self.root);
-        let path = fs.canonicalize(path).await?;
+        let path = client.check(fs.canonicalize(path).await)?;
 
         let mut option = client.options();
         option.append(true).create(true);
 
-        let file = option.open(path).await?;
+        let file = client.check(option.open(path).await)?;
 
         Ok((RpAppend::new(), SftpWriter::new(file)))
     }
 
     async fn copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
+        // Create parent dir before taking the connection, the pool may only
+        // have one connection.
+        if let Some((dir, _)) = to.rsplit_once('/') {
+            self.create_dir(dir, OpCreateDir::default()).await?;
+        }
+
         let client = self.connect().await?;
 
         let mut fs = client.fs();
         fs.set_cwd(&self.root);
 
-        if let Some((dir, _)) = to.rsplit_once('/') {
-            self.create_dir(dir, OpCreateDir::default()).await?;
-        }
-
-        let src = fs.canonicalize(from).await?;
-        let dst = fs.canonicalize(to).await?;
-        let mut src_file = client.open(&src).await?;
-        let mut dst_file = client.create(dst).await?;
+        let src = client.check(fs.canonicalize(from).await)?;
+        let dst = client.check(fs.canonicalize(to).await)?;
+        let mut src_file = client.check(client.open(&src).await)?;
+        let mut dst_file = client.check(client.create(dst).await)?;
 
-        src_file.copy_all_to(&mut dst_file).await?;
+        client.check(src_file.copy_all_to(&mut dst_file).await)?;
 
         Ok(RpCopy::default())
     }
 
     async fn rename(&self, from: &str, to: &str, _: OpRename) -> Result<RpRename> {
+        // Create parent dir before taking the connection, the pool may only
+        // have one connection.
+        if let Some((dir, _)) = to.rsplit_once('/') {
+            self.create_dir(dir, OpCreateDir::default()).await?;
+        }
+
         let client = self.connect().await?;
 
         let mut fs = client.fs();
         fs.set_cwd(&self.root);
 
-        if let Some((dir, _)) = to.rsplit_once('/') {
-            self.create_dir(dir, OpCreateDir::default()).await?;
-        }
-        fs.rename(from, to).await?;
+        client.check(fs.rename(from, to).await)?;
 
         Ok(RpRename::default())
     }
@@ -394,39 +471,47 @@ impl Accessor for SftpBackend {
         let mut fs = client.fs();
         fs.set_cwd(&self.root);
 
-        let meta = fs.metadata(path).await?;
+        let meta = client.check(fs.metadata(path).await)?;
 
         Ok(RpStat::new(meta.into()))
     }
 
     async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
-        let client = self.connect().await?;
-
-        let mut fs = client.fs();
-        fs.set_cwd(&self.root);
-
         if path.ends_with('/') {
-            let file_path = format!("./{}", path);
-            let mut dir = match fs.open_dir(&file_path).await {
-                Ok(dir) => dir,
-                Err(e) => {
-                    if is_not_found(&e) {
-                        return Ok(RpDelete::default());
-                    } else {
-                        return Err(e.into());
+            // Collect children first and release the connection before
+            // deleting them, the pool may only have one connection.
+            let children = {
+                let client = self.connect().await?;
+                let mut fs = client.fs();
+                fs.set_cwd(&self.root);
+
+                let file_path = format!("./{}", path);
+                let mut dir = match client.check(fs.open_dir(&file_path).await) {
+                    Ok(dir) => dir,
+                    Err(e) => {
+                        if is_not_found(&e) {
+                            return Ok(RpDelete::default());
+                        } else {
+                            return Err(e.into());
+                        }
                     }
                 }
-            }
-            .read_dir()
-            .boxed();
-
-            while let Some(file) = dir.next().await {
-                let file = file?;
-                let file_name = file.filename().to_str();
-                if file_name == Some(".") || file_name == Some("..") {
-                    continue;
+                .read_dir()
+                .boxed();
+
+                let mut children = Vec::new();
+                while let Some(file) = dir.next().await {
+                    let file = client.check(file)?;
+                    let file_name = file.filename().to_str();
+                    if file_name == Some(".") || file_name == Some("..") {
+                        continue;
+                    }
+                    children.push(Path::new(&self.root).join(file.filename()));
                 }
-                let file_path = Path::new(&self.root).join(file.filename());
+                children
+            };
+
+            for file_path in children {
                 self.delete(
                     file_path.to_str().ok_or(Error::new(
                         ErrorKind::Unexpected,
@@ -437,14 +522,22 @@ impl Accessor for SftpBackend {
                 .await?;
             }
 
-            match fs.remove_dir(path).await {
+            let client = self.connect().await?;
+            let mut fs = client.fs();
+            fs.set_cwd(&self.root);
+
+            match client.check(fs.remove_dir(path).await) {
                 Err(e) if !is_not_found(&e) => {
                     return Err(e.into());
                 }
                 _ => {}
             }
         } else {
-            match fs.remove_file(path).await {
+            let client = self.connect().await?;
+            let mut fs = client.fs();
+            fs.set_cwd(&self.root);
+
+            match client.check(fs.remove_file(path).await) {
                 Err(e) if !is_not_found(&e) => {
                     return Err(e.into());
                 }
@@ -462,7 +555,7 @@ impl Accessor for SftpBackend {
 
         let file_path = format!("./{}", path);
 
-        let dir = match fs.open_dir(&file_path).await {
+        let dir = match client.check(fs.open_dir(&file_path).await) {
             Ok(dir) => dir,
             Err(e) => {
                 if is_not_found(&e) {
@@ -482,21 +575,133 @@ impl Accessor for SftpBackend {
 }
 
 impl SftpBackend {
-    async fn connect(&self) -> Result<&Sftp> {
-        let sftp = self
-            .client
-            .get_or_try_init(|| {
-                Box::pin(connect_sftp(
-                    self.endpoint.as_str(),
-                    self.root.clone(),
-                    self.user.clone(),
-                    self.key.clone(),
-                    self.known_hosts_strategy.clone(),
-                ))
+    async fn connect(&self) -> Result<PooledConnection<'static, Manager>> {
+        let pool = self
+            .pool
+            .get_or_try_init(|| async {
+                bb8::Pool::builder()
+                    .max_size(self.max_connections)
+                    .build(Manager {
+                        endpoint: self.endpoint.clone(),
+                        root: self.root.clone(),
+                        user: self.user.clone(),
+                        key: self.key.clone(),
+                        known_hosts_strategy: self.known_hosts_strategy.clone(),
+                        keepalive_interval: self.keepalive_interval,
+                    })
+                    .await
             })
             .await?;
 
-        Ok(sftp)
+        pool.get_owned().await.map_err(|err| match err {
+            RunError::User(err) => err,
+            RunError::TimedOut => {
+                Error::new(ErrorKind::Unexpected, "connection request: timeout").set_temporary()
+            }
+        })
+    }
+}
+
+/// Connections that have been used within this duration are assumed to be
+/// alive and won't be checked with a request before reuse.
+const CONNECTION_CHECK_IDLE: Duration = Duration::from_secs(10);
+
+/// SftpConnection is the sftp connection kept in pool.
+pub struct SftpConnection {
+    sftp: Sftp,
+    /// The time when this connection has been returned to pool or checked.
+    last_used: Instant,
+    /// Set while an operation failed because of the session instead of
+    /// the request, the connection will be dropped by pool then.
+    broken: AtomicBool,
+}
+
+impl SftpConnection {
+    /// Check the result of an operation on this connection.
+    ///
+    /// Errors outside of sftp protocol means the underlying session is
+    /// broken, mark this connection so that it won't be reused.
+    fn check<T>(
+        &self,
+        res: std::result::Result<T, SftpClientError>,
+    ) -> std::result::Result<T, SftpClientError> {
+        if let Err(err) = &res {
+            if !is_sftp_protocol_error(err) {
+                self.broken.store(true, Ordering::Relaxed);
+            }
+        }
+        res
+    }
+}
+
+impl Deref for SftpConnection {
+    type Target = Sftp;
+
+    fn deref(&self) -> &Self::Target {
+        &self.sftp
+    }
+}
+
+pub struct Manager {
+    endpoint: String,
+    root: String,
+    user: String,
+    key: Option<String>,
+    known_hosts_strategy: KnownHosts,
+    keepalive_interval: Option<Duration>,
+}
+
+#[async_trait]
+impl bb8::ManageConnection for Manager {
+    type Connection = SftpConnection;
+    type Error = Error;
+
+    async fn connect(&self) -> std::result::Result<Self::Connection, Self::Error> {
+        let sftp = connect_sftp(
+            self.endpoint.as_str(),
+            self.root.clone(),
+            self.user.clone(),
+            self.key.clone(),
+            self.known_hosts_strategy.clone(),
+            self.keepalive_interval,
+        )
+        .await?;
+
+        Ok(SftpConnection {
+            sftp,
+            last_used: Instant::now(),
+            broken: AtomicBool::new(false),
+        })
+    }
+
+    /// Check the session of connections that have been idle for a while
+    /// with a request, so that dropped connections will be replaced by a
+    /// new one instead of returning an error.
+    ///
+    /// Recently used connections are assumed to be alive, so that most
+    /// operations won't pay an extra round trip.
+    async fn is_valid(&self, conn: &mut Self::Connection) -> std::result::Result<(), Self::Error> {
+        if conn.last_used.elapsed() < CONNECTION_CHECK_IDLE {
+            return Ok(());
+        }
+
+        conn.check(conn.fs().metadata(".").await)?;
+        conn.last_used = Instant::now();
+        Ok(())
+    }
+
+    /// This is called while the connection is returned to pool.
+    ///
+    /// Connections whose session failed during the operation are dropped,
+    /// otherwise record the time so that following `is_valid` can skip
+    /// the check.
+    fn has_broken(&self, conn: &mut Self::Connection) -> bool {
+        if *conn.broken.get_mut() {
+            return true;
+        }
+
+        conn.last_used = Instant::now();
+        false
     }
 }
 
@@ -506,6 +711,7 @@ async fn connect_sftp(
     user: String,
     key: Option<String>,
     known_hosts_strategy: KnownHosts,
+    keepalive_interval: Option<Duration>,
 ) -> Result<Sftp> {
     let mut session = SessionBuilder::default();
 
@@ -515,6 +721,10 @@ async fn connect_sftp(
         session.keyfile(key);
     }
 
+    if let Some(interval) = keepalive_interval {
+        session.server_alive_interval(interval);
+    }
+
     // set control directory to avoid temp files in root directory when panic
     if let Some(dir) = dirs::runtime_dir() {
         session.control_directory(dir);
@@ -41,7 +41,16 @@ impl From<SftpClientError> for Error {
             _ => ErrorKind::Unexpected,
         };
 
-        Error::new(kind, "sftp error").set_source(e)
+        let err = Error::new(kind, "sftp error");
+        // Errors outside of sftp protocol are most likely caused by broken
+        // connections which will be re-established on the next operation.
+        let err = match &e {
+            SftpClientError::SftpError(_, _)
+            | SftpClientError::UnsupportedSftpProtocol { version: _ } => err,
+            _ => err.set_temporary(),
+        };
+
+        err.set_source(e)
     }
 }
 
@@ -64,6 +64,7 @@ impl SledBuilder {
 
 impl Builder for SledBuilder {
     const SCHEME: Scheme = Scheme::Sled;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&["datadir", "root", "tree"]);
     type Accessor = SledBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
@@ -76,11 +77,22 @@ impl Builder for SledBuilder {
         builder
     }
 
+    fn validate(&self) -> Result<()> {
+        if self.datadir.is_none() {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "datadir is required but not set")
+                    .with_operation("Builder::validate")
+                    .with_context("service", Scheme::Sled)
+                    .with_context("key", "datadir"),
+            );
+        }
+
+        Ok(())
+    }
+
     fn build(&mut self) -> Result<Self::Accessor> {
-        let datadir_path = self.datadir.take().ok_or_else(|| {
-            Error::new(ErrorKind::ConfigInvalid, "datadir is required but not set")
-                .with_context("service", Scheme::Sled)
-        })?;
+        self.validate()?;
+        let datadir_path = self.datadir.take().unwrap_or_default();
 
         let db = sled::open(&datadir_path).map_err(|e| {
             Error::new(ErrorKind::ConfigInvalid, "open db")
@@ -157,6 +157,8 @@ impl SupabaseBuilder {
 
 impl Builder for SupabaseBuilder {
     const SCHEME: Scheme = Scheme::Supabase;
+    const CONFIG_KEYS: Option<&'static [&'static str]> =
+        Some(&["root", "bucket", "endpoint", "key"]);
     type Accessor = SupabaseBackend;
 
     fn from_map(map: std::collections::HashMap<String, String>) -> Self {
@@ -170,7 +172,26 @@ impl Builder for SupabaseBuilder {
         builder
     }
 
+    fn validate(&self) -> Result<()> {
+        if self.bucket.is_empty() {
+            return Err(Error::new(ErrorKind::ConfigInvalid, "bucket is empty")
+                .with_operation("Builder::validate")
+                .with_context("service", Scheme::Supabase)
+                .with_context("key", "bucket"));
+        }
+
+        if self.endpoint.is_none() {
+            return Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+                .with_operation("Builder::validate")
+                .with_context("service", Scheme::Supabase)
+                .with_context("key", "endpoint"));
+        }
+
+        Ok(())
+    }
+
     fn build(&mut self) -> Result<Self::Accessor> {
+        self.validate()?;
         let root = normalize_root(&self.root.take().unwrap_or_default());
         debug!("backend use root {}", &root);
 
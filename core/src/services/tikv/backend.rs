@@ -90,6 +90,8 @@ impl TikvBuilder {
 
 impl Builder for TikvBuilder {
     const SCHEME: Scheme = Scheme::Tikv;
+    const CONFIG_KEYS: Option<&'static [&'static str]> =
+        Some(&["endpoints", "insecure", "ca_path", "cert_path", "key_path"]);
     type Accessor = Backend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
@@ -108,25 +110,35 @@ impl Builder for TikvBuilder {
         builder
     }
 
-    fn build(&mut self) -> Result<Self::Accessor> {
-        let endpoints = self.endpoints.take().ok_or_else(|| {
-            Error::new(
+    fn validate(&self) -> Result<()> {
+        if self.endpoints.is_none() {
+            return Err(Error::new(
                 ErrorKind::ConfigInvalid,
                 "endpoints is required but not set",
             )
+            .with_operation("Builder::validate")
             .with_context("service", Scheme::Tikv)
-        })?;
+            .with_context("key", "endpoints"));
+        }
 
         if self.insecure
             && (self.ca_path.is_some() || self.key_path.is_some() || self.cert_path.is_some())
         {
             return Err(
                 Error::new(ErrorKind::ConfigInvalid, "invalid tls configuration")
+                    .with_operation("Builder::validate")
                     .with_context("service", Scheme::Tikv)
-                    .with_context("endpoints", format!("{:?}", endpoints)),
-            )?;
+                    .with_context("key", "insecure"),
+            );
         }
 
+        Ok(())
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        self.validate()?;
+        let endpoints = self.endpoints.take().unwrap_or_default();
+
         Ok(Backend::new(Adapter {
             client: OnceCell::new(),
             endpoints,
@@ -96,6 +96,7 @@ impl VercelArtifactsBuilder {
 
 impl Builder for VercelArtifactsBuilder {
     const SCHEME: Scheme = Scheme::VercelArtifacts;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&["access_token"]);
 
     type Accessor = VercelArtifactsBackend;
 
@@ -105,7 +106,19 @@ impl Builder for VercelArtifactsBuilder {
         builder
     }
 
+    fn validate(&self) -> Result<()> {
+        if self.access_token.is_none() {
+            return Err(Error::new(ErrorKind::ConfigInvalid, "access_token not set")
+                .with_operation("Builder::validate")
+                .with_context("service", Scheme::VercelArtifacts)
+                .with_context("key", "access_token"));
+        }
+
+        Ok(())
+    }
+
     fn build(&mut self) -> Result<Self::Accessor> {
+        self.validate()?;
         let client = if let Some(client) = self.http_client.take() {
             client
         } else {
@@ -115,12 +128,9 @@ impl Builder for VercelArtifactsBuilder {
             })?
         };
 
-        match self.access_token.clone() {
-            Some(access_token) => Ok(VercelArtifactsBackend {
-                access_token,
-                client,
-            }),
-            None => Err(Error::new(ErrorKind::ConfigInvalid, "access_token not set")),
-        }
+        Ok(VercelArtifactsBackend {
+            access_token: self.access_token.clone().unwrap_or_default(),
+            client,
+        })
     }
 }
@@ -700,6 +700,26 @@ impl WasabiBuilder {
 
 impl Builder for WasabiBuilder {
     const SCHEME: Scheme = Scheme::Wasabi;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&[
+        "root",
+        "bucket",
+        "endpoint",
+        "region",
+        "access_key_id",
+        "secret_access_key",
+        "security_token",
+        "role_arn",
+        "external_id",
+        "server_side_encryption",
+        "server_side_encryption_aws_kms_key_id",
+        "server_side_encryption_customer_algorithm",
+        "server_side_encryption_customer_key",
+        "server_side_encryption_customer_key_md5",
+        "disable_config_load",
+        "disable_ec2_metadata",
+        "enable_virtual_host_style",
+        "default_storage_class",
+    ]);
     type Accessor = WasabiBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
@@ -740,21 +760,27 @@ impl Builder for WasabiBuilder {
         builder
     }
 
+    fn validate(&self) -> Result<()> {
+        if !self.is_bucket_valid() {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "The bucket is misconfigured")
+                    .with_operation("Builder::validate")
+                    .with_context("service", Scheme::Wasabi)
+                    .with_context("key", "bucket"),
+            );
+        }
+
+        Ok(())
+    }
+
     fn build(&mut self) -> Result<Self::Accessor> {
         debug!("backend build started: {:?}", &self);
+        self.validate()?;
 
         let root = normalize_root(&self.root.take().unwrap_or_default());
         debug!("backend use root {}", &root);
 
-        // Handle bucket name.
-        let bucket = if self.is_bucket_valid() {
-            Ok(&self.bucket)
-        } else {
-            Err(
-                Error::new(ErrorKind::ConfigInvalid, "The bucket is misconfigured")
-                    .with_context("service", Scheme::S3),
-            )
-        }?;
+        let bucket = &self.bucket;
         debug!("backend use bucket {}", &bucket);
 
         let default_storage_class = match &self.default_storage_class {
@@ -920,6 +946,8 @@ impl Accessor for WasabiBackend {
                 list: true,
                 list_without_delimiter: true,
                 list_with_delimiter_slash: true,
+                list_with_metadata: true,
+                list_with_sorted: true,
 
                 presign: true,
                 presign_stat: true,
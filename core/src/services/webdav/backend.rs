@@ -64,6 +64,7 @@ use crate::*;
 ///
 /// - `endpoint`: set the endpoint for webdav
 /// - `root`: Set the work directory for backend
+/// - `accept_encoding`: decode gzip/deflate response bodies transparently
 ///
 /// You can refer to [`WebdavBuilder`]'s docs for more information
 ///
@@ -97,6 +98,7 @@ pub struct WebdavBuilder {
     password: Option<String>,
     token: Option<String>,
     root: Option<String>,
+    accept_encoding: bool,
     http_client: Option<HttpClient>,
 }
 
@@ -165,6 +167,22 @@ impl WebdavBuilder {
         self
     }
 
+    /// Enable transparent decompression of response bodies.
+    ///
+    /// With this enabled, `Accept-Encoding: gzip, deflate` will be sent in
+    /// read requests, and bodies will be decoded while reading. Refer to
+    /// [`decode_content_encoding`](crate::raw::decode_content_encoding) for
+    /// details.
+    ///
+    /// Reading with range (including seeking on reader) will return an
+    /// `Unsupported` error while this is enabled.
+    ///
+    /// default: false
+    pub fn accept_encoding(&mut self, enabled: bool) -> &mut Self {
+        self.accept_encoding = enabled;
+        self
+    }
+
     /// Specify the http client that used by this service.
     ///
     /// # Notes
@@ -179,6 +197,14 @@ impl WebdavBuilder {
 
 impl Builder for WebdavBuilder {
     const SCHEME: Scheme = Scheme::Webdav;
+    const CONFIG_KEYS: Option<&'static [&'static str]> = Some(&[
+        "root",
+        "endpoint",
+        "username",
+        "password",
+        "token",
+        "accept_encoding",
+    ]);
     type Accessor = WebdavBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
@@ -189,20 +215,28 @@ impl Builder for WebdavBuilder {
         map.get("username").map(|v| builder.username(v));
         map.get("password").map(|v| builder.password(v));
         map.get("token").map(|v| builder.token(v));
+        map.get("accept_encoding")
+            .map(|v| builder.accept_encoding(v == "on" || v == "true"));
 
         builder
     }
 
+    fn validate(&self) -> Result<()> {
+        if self.endpoint.is_none() {
+            return Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
+                .with_operation("Builder::validate")
+                .with_context("service", Scheme::Webdav)
+                .with_context("key", "endpoint"));
+        }
+
+        Ok(())
+    }
+
     fn build(&mut self) -> Result<Self::Accessor> {
         debug!("backend build started: {:?}", &self);
+        self.validate()?;
 
-        let endpoint = match &self.endpoint {
-            Some(v) => v,
-            None => {
-                return Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
-                    .with_context("service", Scheme::Webdav));
-            }
-        };
+        let endpoint = self.endpoint.as_deref().unwrap_or_default();
 
         let uri = http::Uri::from_str(endpoint).map_err(|err| {
             Error::new(ErrorKind::ConfigInvalid, "endpoint is invalid")
@@ -241,6 +275,7 @@ impl Builder for WebdavBuilder {
             endpoint: endpoint.to_string(),
             base_dir: base_dir.to_string(),
             authorization: auth,
+            accept_encoding: self.accept_encoding,
             root,
             client,
         })
@@ -256,6 +291,7 @@ pub struct WebdavBackend {
     client: HttpClient,
 
     authorization: Option<String>,
+    accept_encoding: bool,
 }
 
 impl Debug for WebdavBackend {
@@ -288,9 +324,11 @@ impl Accessor for WebdavBackend {
                 read: true,
                 read_can_next: true,
                 read_with_range: true,
+                read_with_headers: true,
 
                 write: true,
                 write_can_sink: true,
+                write_with_headers: true,
 
                 create_dir: true,
                 delete: true,
@@ -301,6 +339,7 @@ impl Accessor for WebdavBackend {
 
                 list: true,
                 list_with_delimiter_slash: true,
+                list_with_metadata: true,
 
                 ..Default::default()
             });
@@ -316,10 +355,25 @@ impl Accessor for WebdavBackend {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let resp = self.webdav_get(path, args.range()).await?;
+        if self.accept_encoding && !args.range().is_full() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "read with range is not supported while accept_encoding is enabled",
+            )
+            .with_operation(Operation::Read)
+            .with_context("service", Scheme::Webdav)
+            .with_context("range", args.range().to_string()));
+        }
+
+        let resp = self.webdav_get(path, &args).await?;
         let status = resp.status();
         match status {
             StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
+                let resp = if self.accept_encoding {
+                    decode_content_encoding(resp)?
+                } else {
+                    resp
+                };
                 let meta = parse_into_metadata(path, resp.headers())?;
                 Ok((RpRead::with_metadata(meta), resp.into_body()))
             }
@@ -453,11 +507,7 @@ impl Accessor for WebdavBackend {
 }
 
 impl WebdavBackend {
-    async fn webdav_get(
-        &self,
-        path: &str,
-        range: BytesRange,
-    ) -> Result<Response<IncomingAsyncBody>> {
+    async fn webdav_get(&self, path: &str, args: &OpRead) -> Result<Response<IncomingAsyncBody>> {
         let p = build_rooted_abs_path(&self.root, path);
         let url: String = format!("{}{}", self.endpoint, percent_encode_path(&p));
 
@@ -467,10 +517,19 @@ impl WebdavBackend {
             req = req.header(header::AUTHORIZATION, auth.clone())
         }
 
+        let range = args.range();
         if !range.is_full() {
             req = req.header(header::RANGE, range.to_header());
         }
 
+        if self.accept_encoding {
+            req = req.header(header::ACCEPT_ENCODING, "gzip, deflate");
+        }
+
+        for (name, value) in args.headers() {
+            req = req.header(name.as_str(), value.as_str());
+        }
+
         let req = req
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
@@ -482,8 +541,7 @@ impl WebdavBackend {
         &self,
         abs_path: &str,
         size: Option<u64>,
-        content_type: Option<&str>,
-        content_disposition: Option<&str>,
+        args: &OpWrite,
         body: AsyncBody,
     ) -> Result<Response<IncomingAsyncBody>> {
         let url = format!("{}/{}", self.endpoint, percent_encode_path(abs_path));
@@ -498,14 +556,18 @@ impl WebdavBackend {
             req = req.header(header::CONTENT_LENGTH, size)
         }
 
-        if let Some(mime) = content_type {
+        if let Some(mime) = args.content_type() {
             req = req.header(header::CONTENT_TYPE, mime)
         }
 
-        if let Some(cd) = content_disposition {
+        if let Some(cd) = args.content_disposition() {
             req = req.header(header::CONTENT_DISPOSITION, cd)
         }
 
+        for (name, value) in args.headers() {
+            req = req.header(name.as_str(), value.as_str());
+        }
+
         // Set body
         let req = req.body(body).map_err(new_request_build_error)?;
 
@@ -18,6 +18,7 @@
 use std::mem;
 
 use async_trait::async_trait;
+use percent_encoding::percent_decode_str;
 use serde::Deserialize;
 
 use crate::raw::*;
@@ -32,7 +33,9 @@ pub struct WebdavPager {
 impl WebdavPager {
     pub fn new(base_dir: &str, root: &str, path: &str, multistates: Multistatus) -> Self {
         Self {
-            base_dir: base_dir.to_string(),
+            // base_dir comes from the endpoint which could be percent-encoded,
+            // decode it to compare with the decoded href.
+            base_dir: percent_decode_str(base_dir).decode_utf8_lossy().to_string(),
             root: root.into(),
             path: path.into(),
             multistates,
@@ -51,23 +54,26 @@ impl oio::Page for WebdavPager {
         let mut entries = Vec::with_capacity(oes.len());
 
         for res in oes {
-            let path = res
-                .href
-                .strip_prefix(&self.base_dir)
-                .unwrap_or(res.href.as_str());
+            let href = res.decoded_href();
+            let path = href.strip_prefix(&self.base_dir).unwrap_or(href.as_str());
 
             // Ignore the root path itself.
             if self.root == path {
                 continue;
             }
 
-            let normalized_path = build_rel_path(&self.root, path);
+            let meta = res.parse_into_metadata()?;
+
+            let mut normalized_path = build_rel_path(&self.root, path);
+            // Some servers don't append `/` to collections' href.
+            if meta.mode().is_dir() && !normalized_path.ends_with('/') {
+                normalized_path.push('/');
+            }
             if normalized_path == self.path {
                 // WebDav server may return the current path as an entry.
                 continue;
             }
 
-            let meta = res.parse_into_metadata()?;
             entries.push(oio::Entry::new(&normalized_path, meta))
         }
 
@@ -83,58 +89,90 @@ pub struct Multistatus {
 #[derive(Deserialize, Debug, PartialEq, Eq)]
 pub struct ListOpResponse {
     pub href: String,
-    pub propstat: Propstat,
+    /// Servers could return multiple propstat for one response, for
+    /// example, one with `200 OK` for the found properties and another
+    /// with `404 Not Found` for the missing ones.
+    pub propstat: Vec<Propstat>,
 }
 
 impl ListOpResponse {
+    /// Return the href with percent-encoded chars decoded and the scheme
+    /// and authority stripped if server returns an absolute URL.
+    pub fn decoded_href(&self) -> String {
+        let href = self.href.as_str();
+        let href = match href.find("://") {
+            Some(idx) => {
+                let rest = &href[idx + 3..];
+                rest.find('/').map(|idx| &rest[idx..]).unwrap_or("/")
+            }
+            None => href,
+        };
+
+        percent_decode_str(href).decode_utf8_lossy().to_string()
+    }
+
     pub fn parse_into_metadata(&self) -> Result<Metadata> {
-        let ListOpResponse {
-            href,
-            propstat:
-                Propstat {
-                    prop:
-                        Prop {
-                            getlastmodified,
-                            getcontentlength,
-                            getcontenttype,
-                            getetag,
-                            ..
-                        },
-                    status,
-                },
-        } = self;
-        if let [_, code, text] = status.split(' ').collect::<Vec<_>>()[..3] {
+        let mut is_collection = self.href.ends_with('/');
+        let mut content_length = None;
+        let mut content_type = None;
+        let mut etag = None;
+        let mut last_modified = None;
+
+        for propstat in &self.propstat {
             // As defined in https://tools.ietf.org/html/rfc2068#section-6.1
-            let code = code.parse::<u16>().unwrap();
-            if code >= 400 {
-                return Err(Error::new(
-                    ErrorKind::Unexpected,
-                    &format!("Invalid response: {} {}", code, text),
-                ));
+            //
+            // Properties in the propstat with a failed status are missing,
+            // we should skip them instead of returning an error.
+            if !propstat.is_success() {
+                continue;
             }
+
+            let prop = &propstat.prop;
+            if prop.resourcetype.value == Some(ResourceType::Collection) {
+                is_collection = true;
+            }
+            content_length = content_length.or(prop.getcontentlength.as_deref());
+            content_type = content_type.or(prop.getcontenttype.as_deref());
+            etag = etag.or(prop.getetag.as_deref());
+            last_modified = last_modified.or(prop.getlastmodified.as_deref());
         }
 
-        let mode: EntryMode = if href.ends_with('/') {
+        if !self.propstat.is_empty() && self.propstat.iter().all(|v| !v.is_success()) {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                &format!("Invalid response: {}", self.propstat[0].status),
+            ));
+        }
+
+        let mode: EntryMode = if is_collection {
             EntryMode::DIR
         } else {
             EntryMode::FILE
         };
         let mut m = Metadata::new(mode);
 
-        if let Some(v) = getcontentlength {
-            m.set_content_length(v.parse::<u64>().unwrap());
+        if let Some(v) = content_length {
+            let v = v.trim().parse::<u64>().map_err(|e| {
+                Error::new(ErrorKind::Unexpected, "getcontentlength is not valid u64")
+                    .with_context("value", v)
+                    .set_source(e)
+            })?;
+            m.set_content_length(v);
         }
 
-        if let Some(v) = getcontenttype {
+        if let Some(v) = content_type {
             m.set_content_type(v);
         }
 
-        if let Some(v) = getetag {
+        if let Some(v) = etag {
             m.set_etag(v);
         }
 
         // https://www.rfc-editor.org/rfc/rfc4918#section-14.18
-        m.set_last_modified(parse_datetime_from_rfc2822(getlastmodified)?);
+        if let Some(v) = last_modified {
+            m.set_last_modified(parse_datetime_from_rfc2822(v.trim())?);
+        }
+
         Ok(m)
     }
 }
@@ -145,18 +183,37 @@ pub struct Propstat {
     pub status: String,
 }
 
+impl Propstat {
+    /// Check if the status line (like `HTTP/1.1 200 OK`) is success.
+    ///
+    /// Status that can't be parsed will be treated as success so that
+    /// non-standard servers are still supported.
+    pub fn is_success(&self) -> bool {
+        match self
+            .status
+            .split_whitespace()
+            .nth(1)
+            .and_then(|v| v.parse::<u16>().ok())
+        {
+            Some(code) => (200..300).contains(&code),
+            None => true,
+        }
+    }
+}
+
 #[derive(Deserialize, Debug, PartialEq, Eq)]
 pub struct Prop {
     #[serde(default)]
     pub displayname: String,
-    pub getlastmodified: String,
+    pub getlastmodified: Option<String>,
     pub getetag: Option<String>,
     pub getcontentlength: Option<String>,
     pub getcontenttype: Option<String>,
+    #[serde(default)]
     pub resourcetype: ResourceTypeContainer,
 }
 
-#[derive(Deserialize, Debug, PartialEq, Eq)]
+#[derive(Deserialize, Debug, Default, PartialEq, Eq)]
 pub struct ResourceTypeContainer {
     #[serde(rename = "$value")]
     pub value: Option<ResourceType>,
@@ -166,6 +223,9 @@ pub struct ResourceTypeContainer {
 #[serde(rename_all = "lowercase")]
 pub enum ResourceType {
     Collection,
+    /// Non-standard resource types returned by some servers.
+    #[serde(other)]
+    Other,
 }
 
 #[cfg(test)]
@@ -194,8 +254,8 @@ mod tests {
 
         let propstat = from_str::<Propstat>(xml).unwrap();
         assert_eq!(
-            propstat.prop.getlastmodified,
-            "Tue, 01 May 2022 06:39:47 GMT"
+            propstat.prop.getlastmodified.as_deref(),
+            Some("Tue, 01 May 2022 06:39:47 GMT")
         );
         assert_eq!(
             propstat.prop.resourcetype.value.unwrap(),
@@ -229,17 +289,17 @@ mod tests {
         let response = from_str::<ListOpResponse>(xml).unwrap();
         assert_eq!(response.href, "/");
 
-        assert_eq!(response.propstat.prop.displayname, "/");
+        assert_eq!(response.propstat[0].prop.displayname, "/");
 
         assert_eq!(
-            response.propstat.prop.getlastmodified,
-            "Tue, 01 May 2022 06:39:47 GMT"
+            response.propstat[0].prop.getlastmodified.as_deref(),
+            Some("Tue, 01 May 2022 06:39:47 GMT")
         );
         assert_eq!(
-            response.propstat.prop.resourcetype.value.unwrap(),
-            ResourceType::Collection
+            response.propstat[0].prop.resourcetype.value.as_ref(),
+            Some(&ResourceType::Collection)
         );
-        assert_eq!(response.propstat.status, "HTTP/1.1 200 OK");
+        assert_eq!(response.propstat[0].status, "HTTP/1.1 200 OK");
     }
 
     #[test]
@@ -271,12 +331,15 @@ mod tests {
         let response = from_str::<ListOpResponse>(xml).unwrap();
         assert_eq!(response.href, "/test_file");
         assert_eq!(
-            response.propstat.prop.getlastmodified,
-            "Tue, 07 May 2022 05:52:22 GMT"
+            response.propstat[0].prop.getlastmodified.as_deref(),
+            Some("Tue, 07 May 2022 05:52:22 GMT")
+        );
+        assert_eq!(
+            response.propstat[0].prop.getcontentlength.as_deref(),
+            Some("1")
         );
-        assert_eq!(response.propstat.prop.getcontentlength.unwrap(), "1");
-        assert_eq!(response.propstat.prop.resourcetype.value, None);
-        assert_eq!(response.propstat.status, "HTTP/1.1 200 OK");
+        assert_eq!(response.propstat[0].prop.resourcetype.value, None);
+        assert_eq!(response.propstat[0].status, "HTTP/1.1 200 OK");
     }
 
     #[test]
@@ -324,8 +387,11 @@ mod tests {
         assert_eq!(multistatus.response.len(), 2);
         assert_eq!(multistatus.response[0].href, "/");
         assert_eq!(
-            multistatus.response[0].propstat.prop.getlastmodified,
-            "Tue, 01 May 2022 06:39:47 GMT"
+            multistatus.response[0].propstat[0]
+                .prop
+                .getlastmodified
+                .as_deref(),
+            Some("Tue, 01 May 2022 06:39:47 GMT")
         );
     }
 
@@ -412,22 +478,22 @@ mod tests {
         let first_response = &multistatus.response[0];
         assert_eq!(first_response.href, "/");
         assert_eq!(
-            first_response.propstat.prop.getlastmodified,
-            "Tue, 07 May 2022 06:39:47 GMT"
+            first_response.propstat[0].prop.getlastmodified.as_deref(),
+            Some("Tue, 07 May 2022 06:39:47 GMT")
         );
 
         let second_response = &multistatus.response[1];
         assert_eq!(second_response.href, "/testdir/");
         assert_eq!(
-            second_response.propstat.prop.getlastmodified,
-            "Tue, 07 May 2022 06:40:10 GMT"
+            second_response.propstat[0].prop.getlastmodified.as_deref(),
+            Some("Tue, 07 May 2022 06:40:10 GMT")
         );
 
         let third_response = &multistatus.response[2];
         assert_eq!(third_response.href, "/test_file");
         assert_eq!(
-            third_response.propstat.prop.getlastmodified,
-            "Tue, 07 May 2022 05:52:22 GMT"
+            third_response.propstat[0].prop.getlastmodified.as_deref(),
+            Some("Tue, 07 May 2022 05:52:22 GMT")
         );
     }
 
@@ -545,8 +611,106 @@ mod tests {
         let first_response = &multistatus.response[0];
         assert_eq!(first_response.href, "/");
         assert_eq!(
-            first_response.propstat.prop.getlastmodified,
-            "Fri, 17 Feb 2023 03:37:22 GMT"
+            first_response.propstat[0].prop.getlastmodified.as_deref(),
+            Some("Fri, 17 Feb 2023 03:37:22 GMT")
+        );
+    }
+
+    #[test]
+    fn test_response_with_multiple_propstat() {
+        let xml = r#"<d:response xmlns:d="DAV:" xmlns:oc="http://owncloud.org/ns">
+          <d:href>http://127.0.0.1:8080/remote.php/dav/files/admin/dir%20a/</d:href>
+          <d:propstat>
+            <d:prop>
+              <d:getetag>"64e1b6a1c9b6c"</d:getetag>
+              <d:resourcetype><d:collection/></d:resourcetype>
+              <oc:size>163</oc:size>
+            </d:prop>
+            <d:status>HTTP/1.1 200 OK</d:status>
+          </d:propstat>
+          <d:propstat>
+            <d:prop>
+              <d:getcontentlength/>
+              <d:getlastmodified/>
+            </d:prop>
+            <d:status>HTTP/1.1 404 Not Found</d:status>
+          </d:propstat>
+        </d:response>"#;
+
+        let response = from_str::<ListOpResponse>(xml).unwrap();
+        assert_eq!(response.propstat.len(), 2);
+        assert_eq!(
+            response.decoded_href(),
+            "/remote.php/dav/files/admin/dir a/"
+        );
+
+        let meta = response.parse_into_metadata().unwrap();
+        assert!(meta.mode().is_dir());
+        assert_eq!(meta.etag(), Some("\"64e1b6a1c9b6c\""));
+        assert_eq!(meta.content_length_raw(), None);
+        assert_eq!(meta.last_modified(), None);
+    }
+
+    #[test]
+    fn test_response_file_metadata() {
+        let xml = r#"<D:response>
+          <D:href>/dir/test%20file</D:href>
+          <D:propstat>
+            <D:prop>
+              <D:getcontentlength>1024</D:getcontentlength>
+              <D:getcontenttype>text/plain</D:getcontenttype>
+              <D:getetag>"abc"</D:getetag>
+              <D:getlastmodified>Sat, 07 May 2022 05:52:22 GMT</D:getlastmodified>
+              <D:resourcetype/>
+            </D:prop>
+            <D:status>HTTP/1.1 200 OK</D:status>
+          </D:propstat>
+        </D:response>"#;
+
+        let response = from_str::<ListOpResponse>(xml).unwrap();
+        assert_eq!(response.decoded_href(), "/dir/test file");
+
+        let meta = response.parse_into_metadata().unwrap();
+        assert!(meta.mode().is_file());
+        assert_eq!(meta.content_length(), 1024);
+        assert_eq!(meta.content_type(), Some("text/plain"));
+        assert_eq!(meta.etag(), Some("\"abc\""));
+        assert_eq!(
+            meta.last_modified(),
+            Some(parse_datetime_from_rfc2822("Sat, 07 May 2022 05:52:22 GMT").unwrap())
         );
     }
+
+    #[tokio::test]
+    async fn test_pager_with_encoded_base_dir() {
+        let xml = r#"<D:multistatus xmlns:D="DAV:">
+          <D:response>
+            <D:href>/dav/files/user%20name/dir/</D:href>
+            <D:propstat>
+              <D:prop>
+                <D:displayname>dir</D:displayname>
+                <D:resourcetype><D:collection/></D:resourcetype>
+              </D:prop>
+              <D:status>HTTP/1.1 200 OK</D:status>
+            </D:propstat>
+          </D:response>
+          <D:response>
+            <D:href>/dav/files/user%20name/dir/test%20file</D:href>
+            <D:propstat>
+              <D:prop>
+                <D:displayname>test file</D:displayname>
+                <D:getcontentlength>1024</D:getcontentlength>
+                <D:resourcetype/>
+              </D:prop>
+              <D:status>HTTP/1.1 200 OK</D:status>
+            </D:propstat>
+          </D:response>
+        </D:multistatus>"#;
+
+        let multistatus = from_str::<Multistatus>(xml).unwrap();
+        let mut pager = WebdavPager::new("/dav/files/user%20name", "/", "dir/", multistatus);
+        let entries = oio::Page::next(&mut pager).await.unwrap().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path(), "dir/test file");
+    }
 }
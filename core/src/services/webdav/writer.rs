@@ -39,13 +39,7 @@ impl WebdavWriter {
     async fn write_oneshot(&mut self, size: u64, body: AsyncBody) -> Result<()> {
         let resp = self
             .backend
-            .webdav_put(
-                &self.path,
-                Some(size),
-                self.op.content_type(),
-                self.op.content_disposition(),
-                body,
-            )
+            .webdav_put(&self.path, Some(size), &self.op, body)
             .await?;
 
         let status = resp.status();
@@ -48,6 +48,8 @@ pub struct WebhdfsBuilder {
     endpoint: Option<String>,
     delegation: Option<String>,
     disable_list_batch: bool,
+
+    http_client: Option<HttpClient>,
 }
 
 impl Debug for WebhdfsBuilder {
@@ -116,10 +118,23 @@ impl WebhdfsBuilder {
         self.disable_list_batch = true;
         self
     }
+
+    /// Specify the http client that used by this service.
+    ///
+    /// # Notes
+    ///
+    /// This API is part of OpenDAL's Raw API. `HttpClient` could be changed
+    /// during minor updates.
+    pub fn http_client(&mut self, client: HttpClient) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
 }
 
 impl Builder for WebhdfsBuilder {
     const SCHEME: Scheme = Scheme::Webhdfs;
+    const CONFIG_KEYS: Option<&'static [&'static str]> =
+        Some(&["root", "endpoint", "delegation", "disable_list_batch"]);
     type Accessor = WebhdfsBackend;
 
     fn from_map(map: HashMap<String, String>) -> Self {
@@ -166,7 +181,14 @@ impl Builder for WebhdfsBuilder {
             .take()
             .map(|dt| format!("delegation_token={dt}"));
 
-        let client = HttpClient::new()?;
+        let client = if let Some(client) = self.http_client.take() {
+            client
+        } else {
+            HttpClient::new().map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::Webhdfs)
+            })?
+        };
 
         let backend = WebhdfsBackend {
             root,
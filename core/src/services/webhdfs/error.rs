@@ -53,6 +53,7 @@ fn parse_error_msg(parts: Parts, body: &str) -> Result<Error> {
         // passing invalid arguments will return BAD_REQUEST
         // should be un-retryable
         StatusCode::BAD_REQUEST => (ErrorKind::Unexpected, false),
+        StatusCode::TOO_MANY_REQUESTS => (ErrorKind::RateLimited, true),
         StatusCode::INTERNAL_SERVER_ERROR
         | StatusCode::BAD_GATEWAY
         | StatusCode::SERVICE_UNAVAILABLE
@@ -68,6 +69,7 @@ fn parse_error_msg(parts: Parts, body: &str) -> Result<Error> {
     let mut err = Error::new(kind, &message);
 
     err = with_error_response_context(err, parts);
+    err = with_error_response_body(err, body.as_bytes());
 
     if retryable {
         err = err.set_temporary();
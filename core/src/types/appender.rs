@@ -21,12 +21,14 @@ use std::pin::Pin;
 use std::task::ready;
 use std::task::Context;
 use std::task::Poll;
+use std::time::Instant;
 
 use bytes::Bytes;
 use futures::future::BoxFuture;
 use futures::AsyncWrite;
 use futures::FutureExt;
 
+use super::deadline::DeadlineWrapper;
 use crate::raw::oio::Append;
 use crate::raw::*;
 use crate::*;
@@ -71,6 +73,16 @@ impl Appender {
         })
     }
 
+    /// Make all following operations fail once the deadline is exceeded.
+    pub(crate) fn with_deadline(mut self, deadline: Instant) -> Self {
+        if let State::Idle(a) = &mut self.state {
+            *a = a
+                .take()
+                .map(|a| Box::new(DeadlineWrapper::new(a, deadline)) as oio::Appender);
+        }
+        self
+    }
+
     /// Write into inner appender.
     pub async fn append(&mut self, bs: impl Into<Bytes>) -> Result<()> {
         if let State::Idle(Some(a)) = &mut self.state {
@@ -38,12 +38,31 @@ use crate::*;
 pub trait Builder: Default {
     /// Associated scheme for this builder. It indicates what underlying service is.
     const SCHEME: Scheme;
+    /// Config keys that can be recognized by [`Builder::from_map`].
+    ///
+    /// `None` means this builder doesn't declare its keys, so unknown keys
+    /// can't be detected by [`Operator::from_map_strict`].
+    const CONFIG_KEYS: Option<&'static [&'static str]> = None;
     /// The accessor that built by this builder.
     type Accessor: Accessor;
 
     /// Construct a builder from given map which contains several parameters needed by underlying service.
     fn from_map(map: HashMap<String, String>) -> Self;
 
+    /// Check the configuration of this builder without building the service.
+    ///
+    /// Services should check required fields and conflicting options here,
+    /// and return [`ErrorKind::ConfigInvalid`] with the offending field
+    /// in the `key` context. Validation must not send any request.
+    ///
+    /// Services with required config should call it at the start of
+    /// [`Builder::build`] instead of checking the same fields again. The
+    /// default implementation accepts everything, which is only suitable
+    /// for services that work without any config.
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+
     /// Consume the accessor builder to build a service.
     fn build(&mut self) -> Result<Self::Accessor>;
 }
@@ -25,6 +25,7 @@ use std::fmt::Debug;
 /// - Whether current Operator supports read or not.
 /// - Whether current Operator supports read with if match or not.
 /// - What's current Operator max supports batch operations count.
+/// - What's the min and max part size of current Operator's multipart write.
 ///
 /// Add fields of Capabilities with be public and can be accessed directly.
 ///
@@ -71,6 +72,10 @@ pub struct Capability {
     pub read_with_override_cache_control: bool,
     /// if operator supports read with override content disposition natively, it will be true.
     pub read_with_override_content_disposition: bool,
+    /// If operator supports read with custom headers, it will be true.
+    ///
+    /// Only S3, WebDAV and HTTP support it by now.
+    pub read_with_headers: bool,
 
     /// If operator supports write natively, it will be true.
     pub write: bool,
@@ -87,6 +92,56 @@ pub struct Capability {
     pub write_with_content_disposition: bool,
     /// If operator supports write with cache control natively, it will be true.
     pub write_with_cache_control: bool,
+    /// If operator supports write with content encoding natively, it will be true.
+    pub write_with_content_encoding: bool,
+    /// If operator supports write with content language natively, it will be true.
+    pub write_with_content_language: bool,
+    /// If operator supports write with custom headers, it will be true.
+    ///
+    /// Only S3 and WebDAV support it by now.
+    pub write_with_headers: bool,
+    /// If operator supports write with storage class, it will be true.
+    pub write_with_storage_class: bool,
+    /// If operator supports write with object lock (retention and legal
+    /// hold), it will be true.
+    pub write_with_object_lock: bool,
+    /// If operator supports write with content md5 natively, it will be true.
+    ///
+    /// Only writes finished in a single request will be checked.
+    pub write_with_content_md5: bool,
+    /// If operator supports write with ttl natively, it will be true.
+    pub write_with_ttl: bool,
+    /// If operator supports write with append natively, it will be true.
+    pub write_with_append: bool,
+    /// If operator supports write with tags natively, it will be true.
+    pub write_with_tags: bool,
+    /// If operator supports resuming write from a checkpoint, it will be true.
+    pub write_with_resume: bool,
+    /// If operator supports overwriting a range of existing file in place,
+    /// it will be true.
+    ///
+    /// Most object storage services can only replace the whole object, so
+    /// they will never support this.
+    pub write_with_range: bool,
+    /// If operator supports write with if match, it will be true.
+    pub write_with_if_match: bool,
+    /// If operator supports write with user metadata, it will be true.
+    pub write_with_user_metadata: bool,
+    /// If operator supports write with if none match, it will be true.
+    ///
+    /// Some services only support `*` which means the file must not exist.
+    pub write_with_if_none_match: bool,
+    /// If operator supports write with idempotency key, it will be true.
+    pub write_with_idempotency_key: bool,
+    /// The min size of every part except the last one in a multipart write.
+    ///
+    /// Users can size their chunks with this limit before sinking a stream.
+    /// `None` means there is no such limit or it's unknown.
+    pub write_multi_min_size: Option<u64>,
+    /// The max size of one part in a multipart write.
+    ///
+    /// `None` means there is no such limit or it's unknown.
+    pub write_multi_max_size: Option<u64>,
 
     /// If operator supports append natively, it will be true.
     pub append: bool,
@@ -98,27 +153,68 @@ pub struct Capability {
     pub append_with_cache_control: bool,
 
     /// If operator supports create dir natively, it will be true.
+    ///
+    /// Object storage services will create a zero-byte marker object
+    /// ending in `/` to represent the dir.
     pub create_dir: bool,
 
     /// If operator supports delete natively, it will be true.
     pub delete: bool,
+    /// If operator supports delete with version natively, it will be true.
+    pub delete_with_version: bool,
+    /// If operator supports delete with if match natively, it will be true.
+    pub delete_with_if_match: bool,
 
     /// If operator supports copy natively, it will be true.
     pub copy: bool,
+    /// If operator supports copy with metadata directive natively, it will be true.
+    ///
+    /// Services without it always preserve the metadata of the source file.
+    pub copy_with_metadata_directive: bool,
+    /// If operator supports copy with if source match natively, it will be true.
+    pub copy_with_if_source_match: bool,
 
     /// If operator supports rename natively, it will be true.
     pub rename: bool,
 
+    /// If operator supports get tags natively, it will be true.
+    pub get_tags: bool,
+    /// If operator supports put tags natively, it will be true.
+    ///
+    /// Tags can be updated without rewriting the file.
+    pub put_tags: bool,
+
+    /// If operator supports get object lock natively, it will be true.
+    pub get_object_lock: bool,
+    /// If operator supports put object lock natively, it will be true.
+    ///
+    /// Retention and legal hold can be updated without rewriting the file.
+    pub put_object_lock: bool,
+
     /// If operator supports list natively, it will be true.
     pub list: bool,
     /// If backend supports list with limit, it will be true.
     pub list_with_limit: bool,
     /// If backend supports list with start after, it will be true.
     pub list_with_start_after: bool,
+    /// If backend supports resuming list from a continuation token, it will be true.
+    pub list_with_continuation_token: bool,
     /// If backend support list with using slash as delimiter.
     pub list_with_delimiter_slash: bool,
     /// If backend supports list without delimiter.
     pub list_without_delimiter: bool,
+    /// If backend returns list pages in lexicographic order, it will be true.
+    ///
+    /// Entries within one page could still be unordered, but every page is
+    /// greater than the previous one.
+    pub list_with_sorted: bool,
+    /// If backend can return content length and last modified of files in
+    /// list, it will be true.
+    ///
+    /// Some services only return them while `OpList::metadata` is enabled.
+    pub list_with_metadata: bool,
+    /// If backend supports listing all versions of files, it will be true.
+    pub list_with_versions: bool,
 
     /// If operator supports presign natively, it will be true.
     pub presign: bool,
@@ -174,6 +270,18 @@ impl Debug for Capability {
         if self.presign {
             s.push("Presign");
         }
+        if self.get_tags {
+            s.push("GetTags");
+        }
+        if self.put_tags {
+            s.push("PutTags");
+        }
+        if self.get_object_lock {
+            s.push("GetObjectLock");
+        }
+        if self.put_object_lock {
+            s.push("PutObjectLock");
+        }
         if self.batch {
             s.push("Batch");
         }
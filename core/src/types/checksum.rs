@@ -0,0 +1,219 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use base64::engine::general_purpose;
+use base64::Engine;
+use bytes::Bytes;
+use futures::TryStreamExt;
+use md5::Digest;
+
+use crate::raw::*;
+use crate::*;
+
+/// ChecksumAlgo is the algorithm used to compute the checksum of the
+/// content while writing.
+///
+/// The checksum is computed by OpenDAL on the client side over the bytes
+/// that have been written, so it's available on all services. Services
+/// like s3 will also send it along with single request writes, so that
+/// the content will be verified and the checksum will be stored by them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ChecksumAlgo {
+    /// MD5, returned via [`Metadata::content_md5`].
+    Md5,
+    /// SHA256, returned via [`Metadata::checksum_sha256`].
+    ///
+    /// Only available with feature `checksum-sha256` enabled.
+    #[cfg(feature = "checksum-sha256")]
+    Sha256,
+    /// CRC32C, returned via [`Metadata::checksum_crc32c`].
+    Crc32c,
+}
+
+/// Check whether the checksum requested in given args can be computed.
+///
+/// The checksum is computed over the bytes that pass through the writer,
+/// so it can't cover the parts uploaded before resuming. Blocking writers
+/// don't support it for now.
+pub(crate) fn validate_checksum(op: Operation, args: &OpWrite) -> Result<()> {
+    if args.checksum().is_none() {
+        return Ok(());
+    }
+
+    if op == Operation::BlockingWrite {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "checksum is not supported by blocking writer",
+        )
+        .with_operation(op));
+    }
+    if args.resume().is_some() {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "checksum can't be computed while resuming a write",
+        )
+        .with_operation(op));
+    }
+    Ok(())
+}
+
+/// Hasher computes the checksum incrementally.
+#[derive(Clone)]
+pub(crate) enum Hasher {
+    Md5(md5::Md5),
+    #[cfg(feature = "checksum-sha256")]
+    Sha256(sha2::Sha256),
+    Crc32c(u32),
+}
+
+impl Hasher {
+    pub(crate) fn new(algo: ChecksumAlgo) -> Self {
+        match algo {
+            ChecksumAlgo::Md5 => Hasher::Md5(md5::Md5::new()),
+            #[cfg(feature = "checksum-sha256")]
+            ChecksumAlgo::Sha256 => Hasher::Sha256(sha2::Sha256::new()),
+            ChecksumAlgo::Crc32c => Hasher::Crc32c(0),
+        }
+    }
+
+    pub(crate) fn update(&mut self, bs: &[u8]) {
+        match self {
+            Hasher::Md5(h) => h.update(bs),
+            #[cfg(feature = "checksum-sha256")]
+            Hasher::Sha256(h) => h.update(bs),
+            Hasher::Crc32c(crc) => *crc = crc32c::crc32c_append(*crc, bs),
+        }
+    }
+
+    /// Return the base64 encoded checksum.
+    pub(crate) fn digest(self) -> String {
+        match self {
+            Hasher::Md5(h) => general_purpose::STANDARD.encode(h.finalize()),
+            #[cfg(feature = "checksum-sha256")]
+            Hasher::Sha256(h) => general_purpose::STANDARD.encode(h.finalize()),
+            Hasher::Crc32c(crc) => general_purpose::STANDARD.encode(crc.to_be_bytes()),
+        }
+    }
+
+    /// Set the base64 encoded checksum into given metadata.
+    pub(crate) fn finish(self, meta: &mut Metadata) {
+        match self {
+            Hasher::Md5(_) => meta.set_content_md5(&self.digest()),
+            #[cfg(feature = "checksum-sha256")]
+            Hasher::Sha256(_) => meta.set_checksum_sha256(&self.digest()),
+            Hasher::Crc32c(_) => meta.set_checksum_crc32c(&self.digest()),
+        };
+    }
+}
+
+/// ChecksumWriter will update the hasher with the bytes that have been
+/// written.
+///
+/// The hasher is shared with [`Writer`] so that it can build the metadata
+/// after `close`.
+pub(crate) struct ChecksumWriter<W> {
+    inner: W,
+    hasher: Arc<Mutex<Hasher>>,
+}
+
+impl<W> ChecksumWriter<W> {
+    pub(crate) fn new(inner: W, hasher: Arc<Mutex<Hasher>>) -> Self {
+        Self { inner, hasher }
+    }
+}
+
+#[async_trait]
+impl<W: oio::Write> oio::Write for ChecksumWriter<W> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.inner.write(bs.clone()).await?;
+        self.hasher.lock().expect("lock must succeed").update(&bs);
+        Ok(())
+    }
+
+    async fn sink(&mut self, size: u64, s: oio::Streamer) -> Result<()> {
+        let hasher = self.hasher.clone();
+        let s = s.inspect_ok(move |bs| hasher.lock().expect("lock must succeed").update(bs));
+        self.inner.sink(size, Box::new(oio::into_stream(s))).await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.inner.close().await
+    }
+
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        self.inner.checkpoint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hasher() {
+        #[allow(unused_mut)]
+        let mut cases = vec![
+            (ChecksumAlgo::Md5, "XUFAKrxLKna5cZ2REBfFkg=="),
+            (ChecksumAlgo::Crc32c, "mnG7TA=="),
+        ];
+        #[cfg(feature = "checksum-sha256")]
+        cases.push((
+            ChecksumAlgo::Sha256,
+            "LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ=",
+        ));
+
+        for (algo, expected) in cases {
+            let mut hasher = Hasher::new(algo);
+            hasher.update(b"hel");
+            hasher.update(b"lo");
+
+            let mut meta = Metadata::new(EntryMode::FILE);
+            hasher.finish(&mut meta);
+            let actual = match algo {
+                ChecksumAlgo::Md5 => meta.content_md5(),
+                #[cfg(feature = "checksum-sha256")]
+                ChecksumAlgo::Sha256 => meta.checksum_sha256(),
+                ChecksumAlgo::Crc32c => meta.checksum_crc32c(),
+            };
+            assert_eq!(actual, Some(expected), "{algo:?}");
+        }
+    }
+
+    #[test]
+    fn test_validate_checksum() {
+        let args = OpWrite::new();
+        assert!(validate_checksum(Operation::BlockingWrite, &args).is_ok());
+
+        let args = OpWrite::new().with_checksum(ChecksumAlgo::Md5);
+        assert!(validate_checksum(Operation::Write, &args).is_ok());
+        let err = validate_checksum(Operation::BlockingWrite, &args).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        let args = args.with_resume(WriteCheckpoint::new("upload", vec![], 0));
+        let err = validate_checksum(Operation::Write, &args).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+}
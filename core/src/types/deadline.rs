@@ -0,0 +1,342 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::future::Future;
+use std::io::SeekFrom;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::FutureExt;
+
+use crate::raw::oio::AppendOperation;
+use crate::raw::oio::PageOperation;
+use crate::raw::oio::ReadOperation;
+use crate::raw::oio::WriteOperation;
+use crate::raw::*;
+use crate::*;
+
+/// Build the error returned while the deadline of an operation is exceeded.
+pub(crate) fn new_deadline_exceeded_error(op: impl Into<&'static str>, deadline: Instant) -> Error {
+    Error::new(ErrorKind::DeadlineExceeded, "operation deadline exceeded")
+        .with_operation(op)
+        .with_context(
+            "deadline",
+            format!(
+                "exceeded by {:?}",
+                Instant::now().saturating_duration_since(deadline)
+            ),
+        )
+        .set_temporary()
+}
+
+/// Drive given future until it's ready or the deadline is exceeded.
+///
+/// Like `TimeoutLayer`, the future is raced against a tokio timer, so
+/// a future that never wakes up will still be aborted at the deadline.
+pub(crate) async fn run_with_deadline<T>(
+    fut: impl Future<Output = Result<T>>,
+    op: impl Into<&'static str>,
+    deadline: Instant,
+) -> Result<T> {
+    // `timeout_at` polls the future first, check here to make sure the
+    // exceeded deadline is respected even if the future is ready.
+    if Instant::now() >= deadline {
+        return Err(new_deadline_exceeded_error(op, deadline));
+    }
+
+    tokio::time::timeout_at(deadline.into(), fut)
+        .await
+        .map_err(|_| new_deadline_exceeded_error(op, deadline))?
+}
+
+/// DeadlineWrapper makes sure the readers, writers, appenders and pagers returned by
+/// an operation with deadline will fail after the deadline is exceeded.
+///
+/// Data that has been returned before the deadline is kept by the caller,
+/// only the following calls will fail.
+pub(crate) struct DeadlineWrapper<R> {
+    inner: R,
+    deadline: Instant,
+    /// The timer will be created while first polled so that we can
+    /// make sure it's created inside the tokio runtime.
+    sleep: Option<Pin<Box<tokio::time::Sleep>>>,
+}
+
+impl<R> DeadlineWrapper<R> {
+    pub(crate) fn new(inner: R, deadline: Instant) -> Self {
+        Self {
+            inner,
+            deadline,
+            sleep: None,
+        }
+    }
+
+    /// Poll the timer so that we will be woken up at the deadline even if
+    /// the inner reader is pending forever.
+    fn poll_check(&mut self, cx: &mut Context<'_>, op: impl Into<&'static str>) -> Result<()> {
+        let deadline = self.deadline;
+        let sleep = self
+            .sleep
+            .get_or_insert_with(|| Box::pin(tokio::time::sleep_until(deadline.into())));
+
+        match sleep.poll_unpin(cx) {
+            Poll::Ready(()) => Err(new_deadline_exceeded_error(op, deadline)),
+            Poll::Pending => Ok(()),
+        }
+    }
+}
+
+impl<R: oio::Read> oio::Read for DeadlineWrapper<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        self.poll_check(cx, ReadOperation::Read)?;
+        self.inner.poll_read(cx, buf)
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        self.poll_check(cx, ReadOperation::Seek)?;
+        self.inner.poll_seek(cx, pos)
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        if let Err(err) = self.poll_check(cx, ReadOperation::Next) {
+            return Poll::Ready(Some(Err(err)));
+        }
+        self.inner.poll_next(cx)
+    }
+}
+
+#[async_trait]
+impl<W: oio::Write> oio::Write for DeadlineWrapper<W> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        run_with_deadline(self.inner.write(bs), WriteOperation::Write, self.deadline).await
+    }
+
+    async fn sink(&mut self, size: u64, s: oio::Streamer) -> Result<()> {
+        run_with_deadline(
+            self.inner.sink(size, s),
+            WriteOperation::Sink,
+            self.deadline,
+        )
+        .await
+    }
+
+    /// Abort is used to clean up, so it's not limited by the deadline.
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        run_with_deadline(self.inner.close(), WriteOperation::Close, self.deadline).await
+    }
+
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        self.inner.checkpoint()
+    }
+}
+
+#[async_trait]
+impl<A: oio::Append> oio::Append for DeadlineWrapper<A> {
+    async fn append(&mut self, bs: Bytes) -> Result<()> {
+        run_with_deadline(
+            self.inner.append(bs),
+            AppendOperation::Append,
+            self.deadline,
+        )
+        .await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        run_with_deadline(self.inner.close(), AppendOperation::Close, self.deadline).await
+    }
+}
+
+#[async_trait]
+impl<P: oio::Page> oio::Page for DeadlineWrapper<P> {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        run_with_deadline(self.inner.next(), PageOperation::Next, self.deadline).await
+    }
+
+    fn continuation_token(&self) -> Option<String> {
+        self.inner.continuation_token()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::future::pending;
+
+    use super::*;
+    use crate::raw::oio::ReadExt;
+
+    #[tokio::test]
+    async fn test_run_with_deadline() {
+        let res = run_with_deadline(
+            async { Ok(1) },
+            Operation::Stat,
+            Instant::now() + Duration::from_secs(60),
+        )
+        .await;
+        assert_eq!(res.unwrap(), 1);
+
+        let err = run_with_deadline(
+            pending::<Result<()>>(),
+            Operation::Stat,
+            Instant::now() - Duration::from_millis(1),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DeadlineExceeded);
+        assert!(err.is_temporary());
+        assert!(err.to_string().contains("deadline"));
+
+        // Future that never wakes up should be aborted at the deadline.
+        let err = run_with_deadline(
+            pending::<Result<()>>(),
+            Operation::Stat,
+            Instant::now() + Duration::from_millis(50),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DeadlineExceeded);
+    }
+
+    #[tokio::test]
+    async fn test_deadline_reader_pending() {
+        struct MockReader;
+
+        impl oio::Read for MockReader {
+            fn poll_read(&mut self, _: &mut Context<'_>, _: &mut [u8]) -> Poll<Result<usize>> {
+                Poll::Pending
+            }
+
+            fn poll_seek(&mut self, _: &mut Context<'_>, _: SeekFrom) -> Poll<Result<u64>> {
+                Poll::Pending
+            }
+
+            fn poll_next(&mut self, _: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+                Poll::Pending
+            }
+        }
+
+        let mut r = DeadlineWrapper::new(MockReader, Instant::now() + Duration::from_millis(50));
+        let err = r.read(&mut [0; 4]).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DeadlineExceeded);
+    }
+
+    #[tokio::test]
+    async fn test_deadline_reader_keeps_read_data() {
+        let deadline = Instant::now() + Duration::from_millis(100);
+        let mut r = DeadlineWrapper::new(oio::Cursor::from(vec![1; 10]), deadline);
+
+        let mut buf = vec![0; 4];
+        assert_eq!(r.read(&mut buf).await.unwrap(), 4);
+        assert_eq!(buf, vec![1; 4]);
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        let err = r.read(&mut buf).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DeadlineExceeded);
+        assert!(err.is_temporary());
+        // Data read before the deadline is still there.
+        assert_eq!(buf, vec![1; 4]);
+    }
+
+    #[tokio::test]
+    async fn test_deadline_page() {
+        struct MockPager;
+
+        #[async_trait]
+        impl oio::Page for MockPager {
+            async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+                Ok(None)
+            }
+        }
+
+        let mut p = DeadlineWrapper::new(MockPager, Instant::now() - Duration::from_millis(1));
+        let err = oio::Page::next(&mut p).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DeadlineExceeded);
+    }
+
+    #[tokio::test]
+    async fn test_deadline_append() {
+        struct MockAppender;
+
+        #[async_trait]
+        impl oio::Append for MockAppender {
+            async fn append(&mut self, _: Bytes) -> Result<()> {
+                Ok(())
+            }
+
+            async fn close(&mut self) -> Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut a = DeadlineWrapper::new(MockAppender, Instant::now() + Duration::from_millis(50));
+        oio::Append::append(&mut a, Bytes::from("hello"))
+            .await
+            .expect("append must succeed");
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let err = oio::Append::append(&mut a, Bytes::from("world"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DeadlineExceeded);
+        let err = oio::Append::close(&mut a).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DeadlineExceeded);
+    }
+
+    #[tokio::test]
+    async fn test_operator_deadline() {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .finish();
+        op.write("file", "hello").await.expect("write must succeed");
+
+        let err = op
+            .stat_with("file")
+            .deadline(Instant::now() - Duration::from_millis(1))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DeadlineExceeded);
+        assert!(err.is_temporary());
+
+        let mut r = op
+            .reader_with("file")
+            .deadline(Instant::now() + Duration::from_millis(100))
+            .await
+            .expect("reader must be created");
+        let mut buf = bytes::BytesMut::with_capacity(2);
+        r.read_into(&mut buf).await.expect("read must succeed");
+        assert_eq!(&buf[..], b"he");
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        let err = r
+            .read_into(&mut bytes::BytesMut::with_capacity(2))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DeadlineExceeded);
+        assert!(err.is_temporary());
+        // Data read before the deadline is still there.
+        assert_eq!(&buf[..], b"he");
+    }
+}
@@ -67,6 +67,19 @@ impl Entry {
         get_basename(&self.path)
     }
 
+    /// Mode of entry.
+    ///
+    /// Returns the mode returned by the service while listing if any.
+    /// Otherwise, the mode will be classified by path: a path ending with
+    /// `/` is a dir and others are files.
+    pub fn mode(&self) -> EntryMode {
+        match &self.metadata {
+            Some(meta) if meta.mode() != EntryMode::Unknown => meta.mode(),
+            _ if self.path.ends_with('/') => EntryMode::DIR,
+            _ => EntryMode::FILE,
+        }
+    }
+
     /// Get the cached metadata of entry.
     ///
     /// # Notes
@@ -40,6 +40,7 @@ use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::io;
+use std::time::Duration;
 
 /// Result that is a wrapper of `Result<T, opendal::Error>`
 pub type Result<T> = std::result::Result<T, Error>;
@@ -67,6 +68,9 @@ pub enum ErrorKind {
     /// The given path already exists thus we failed to the specified operation on it.
     AlreadyExists,
     /// Requests that sent to this path is over the limit, please slow down.
+    ///
+    /// Use [`Error::retry_after`] to check how long the service asked us to
+    /// wait if it's provided.
     RateLimited,
     /// The given file paths are same.
     IsSameFile,
@@ -103,6 +107,21 @@ pub enum ErrorKind {
     ///
     /// For example, user try to seek to a negative position
     InvalidInput,
+    /// The quota of this service has been exceeded.
+    ///
+    /// For example, writing into a memory service that has reached its
+    /// `max_capacity`.
+    QuotaExceeded,
+    /// The file is protected by object lock.
+    ///
+    /// For example, deleting a file that is under retention or legal hold
+    /// on s3.
+    ObjectLocked,
+    /// The deadline of this operation has been exceeded.
+    ///
+    /// For example, the service didn't respond before the deadline set
+    /// by `deadline` of operations like `stat_with`.
+    DeadlineExceeded,
 }
 
 impl ErrorKind {
@@ -135,6 +154,9 @@ impl From<ErrorKind> for &'static str {
             ErrorKind::ContentTruncated => "ContentTruncated",
             ErrorKind::ContentIncomplete => "ContentIncomplete",
             ErrorKind::InvalidInput => "InvalidInput",
+            ErrorKind::QuotaExceeded => "QuotaExceeded",
+            ErrorKind::ObjectLocked => "ObjectLocked",
+            ErrorKind::DeadlineExceeded => "DeadlineExceeded",
         }
     }
 }
@@ -301,6 +323,32 @@ impl Error {
         self
     }
 
+    /// Rewrite all texts carried by error, including message, context
+    /// values and source.
+    ///
+    /// The source will be replaced by its rewritten message if changed,
+    /// so the original source error could not be downcasted anymore.
+    #[cfg(feature = "layers-redact")]
+    pub(crate) fn map_text<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&str) -> String,
+    {
+        self.message = f(&self.message);
+        for (_, v) in self.context.iter_mut() {
+            *v = f(v);
+        }
+        if let Some(source) = self.source.take() {
+            let text = format!("{source:#}");
+            let redacted = f(&text);
+            self.source = if redacted == text {
+                Some(source)
+            } else {
+                Some(anyhow::anyhow!(redacted))
+            };
+        }
+        self
+    }
+
     /// Operate on error with map.
     pub fn map<F>(self, f: F) -> Self
     where
@@ -340,6 +388,55 @@ impl Error {
     pub fn is_temporary(&self) -> bool {
         self.status == ErrorStatus::Temporary
     }
+
+    /// Check if this error is persistent.
+    ///
+    /// Persistent errors have been retried already and should not be
+    /// retried again.
+    pub fn is_persistent(&self) -> bool {
+        self.status == ErrorStatus::Persistent
+    }
+
+    /// Return the HTTP status code of the response that caused this error.
+    ///
+    /// Returns `None` if this error is not caused by an HTTP response.
+    pub fn http_status(&self) -> Option<u16> {
+        self.context_value("status").and_then(|v| v.parse().ok())
+    }
+
+    /// Return the request id returned by the storage provider.
+    ///
+    /// Returns `None` if the service doesn't return a request id.
+    pub fn request_id(&self) -> Option<&str> {
+        self.context_value("request_id")
+    }
+
+    /// Return how long the service asked us to wait before retrying.
+    ///
+    /// This is usually parsed from the `Retry-After` header of a throttled
+    /// response, which will be reported as [`ErrorKind::RateLimited`].
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.context_value("retry_after")
+            .and_then(|v| v.strip_suffix('s'))
+            .and_then(|v| v.parse().ok())
+            .map(Duration::from_secs)
+    }
+
+    /// Return a snippet of the response body returned by the storage provider.
+    ///
+    /// The body will be truncated if it's too large.
+    pub fn response_body(&self) -> Option<&str> {
+        self.context_value("response_body")
+    }
+
+    /// Return the latest value of given context key.
+    fn context_value(&self, key: &str) -> Option<&str> {
+        self.context
+            .iter()
+            .rev()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v.as_str())
+    }
 }
 
 impl From<Error> for io::Error {
@@ -348,6 +445,7 @@ impl From<Error> for io::Error {
             ErrorKind::NotFound => io::ErrorKind::NotFound,
             ErrorKind::PermissionDenied => io::ErrorKind::PermissionDenied,
             ErrorKind::InvalidInput => io::ErrorKind::InvalidInput,
+            ErrorKind::DeadlineExceeded => io::ErrorKind::TimedOut,
             _ => io::ErrorKind::Other,
         };
 
@@ -398,4 +496,22 @@ Source: networking error
 "#
         )
     }
+
+    #[test]
+    fn test_error_http_context() {
+        let err = Error::new(ErrorKind::NotFound, "not found");
+        assert_eq!(err.http_status(), None);
+        assert_eq!(err.request_id(), None);
+        assert_eq!(err.response_body(), None);
+
+        let err = err
+            .with_context("status", "404")
+            .with_context("request_id", "4442587FB7D0A2F9")
+            .with_context("retry_after", "5s")
+            .with_context("response_body", "<Error></Error>");
+        assert_eq!(err.http_status(), Some(404));
+        assert_eq!(err.request_id(), Some("4442587FB7D0A2F9"));
+        assert_eq!(err.retry_after(), Some(Duration::from_secs(5)));
+        assert_eq!(err.response_body(), Some("<Error></Error>"));
+    }
 }
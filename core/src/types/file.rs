@@ -0,0 +1,203 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::Bytes;
+use bytes::BytesMut;
+use futures::AsyncRead;
+use futures::AsyncSeek;
+use futures::Stream;
+
+use crate::*;
+
+/// File is a file-like handle that combines read, seek and metadata.
+///
+/// File is created by [`Operator::open`], it's designed for users who are
+/// porting `std::fs::File` based code to OpenDAL.
+///
+/// # Usage
+///
+/// File implements the same read APIs as [`Reader`]:
+///
+/// - `AsyncRead`
+/// - `AsyncSeek`
+/// - `Stream<Item = <io::Result<Bytes>>>`
+///
+/// Metadata will be fetched lazily by `stat` at the first call of
+/// [`File::metadata`] or [`File::len`], and cached for later calls.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use anyhow::Result;
+/// use futures::AsyncReadExt;
+/// use futures::AsyncSeekExt;
+/// use opendal::Operator;
+/// use std::io::SeekFrom;
+///
+/// # #[tokio::main]
+/// # async fn test(op: Operator) -> Result<()> {
+/// let mut f = op.open("path/to/file").await?;
+/// let size = f.len().await?;
+/// f.seek(SeekFrom::Start(size / 2)).await?;
+/// let mut buf = vec![];
+/// f.read_to_end(&mut buf).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct File {
+    op: Operator,
+    path: String,
+    reader: Reader,
+    metadata: Option<Metadata>,
+}
+
+impl File {
+    pub(crate) fn new(op: Operator, path: &str, reader: Reader) -> Self {
+        Self {
+            op,
+            path: path.to_string(),
+            reader,
+            metadata: None,
+        }
+    }
+
+    /// Get the path of this file.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Get the metadata of this file.
+    ///
+    /// Metadata will be fetched at the first call and cached.
+    pub async fn metadata(&mut self) -> Result<Metadata> {
+        if let Some(meta) = &self.metadata {
+            return Ok(meta.clone());
+        }
+
+        let meta = self.op.stat(&self.path).await?;
+        self.metadata = Some(meta.clone());
+        Ok(meta)
+    }
+
+    /// Get the content length of this file.
+    ///
+    /// The length comes from cached metadata, read [`File::metadata`] for
+    /// more details.
+    pub async fn len(&mut self) -> Result<u64> {
+        Ok(self.metadata().await?.content_length())
+    }
+
+    /// Check if this file is empty.
+    pub async fn is_empty(&mut self) -> Result<bool> {
+        Ok(self.len().await? == 0)
+    }
+
+    /// Read data into the spare capacity of given buffer.
+    ///
+    /// Read [`Reader::read_into`] for more details.
+    pub async fn read_into(&mut self, buf: &mut BytesMut) -> Result<usize> {
+        self.reader.read_into(buf).await
+    }
+
+    /// Convert this file into [`Reader`].
+    pub fn into_reader(self) -> Reader {
+        self.reader
+    }
+}
+
+impl AsyncRead for File {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.reader).poll_read(cx, buf)
+    }
+}
+
+impl AsyncSeek for File {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: io::SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        Pin::new(&mut self.reader).poll_seek(cx, pos)
+    }
+}
+
+impl tokio::io::AsyncRead for File {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        tokio::io::AsyncRead::poll_read(Pin::new(&mut self.reader), cx, buf)
+    }
+}
+
+impl tokio::io::AsyncSeek for File {
+    fn start_seek(mut self: Pin<&mut Self>, pos: io::SeekFrom) -> io::Result<()> {
+        tokio::io::AsyncSeek::start_seek(Pin::new(&mut self.reader), pos)
+    }
+
+    fn poll_complete(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        tokio::io::AsyncSeek::poll_complete(Pin::new(&mut self.reader), cx)
+    }
+}
+
+impl Stream for File {
+    type Item = io::Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.reader).poll_next(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::SeekFrom;
+
+    use futures::AsyncReadExt;
+    use futures::AsyncSeekExt;
+
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_file() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        op.write("test", "Hello, World!").await?;
+
+        let mut f = op.open("test").await?;
+        assert_eq!(f.len().await?, 13);
+        assert_eq!(f.metadata().await?.mode(), EntryMode::FILE);
+
+        f.seek(SeekFrom::Start(7)).await.unwrap();
+        let mut buf = String::new();
+        f.read_to_string(&mut buf).await.unwrap();
+        assert_eq!(buf, "World!");
+
+        let err = op.open("not_exist").await.err().expect("open must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        Ok(())
+    }
+}
@@ -21,11 +21,13 @@ use std::pin::Pin;
 use std::task::ready;
 use std::task::Context;
 use std::task::Poll;
+use std::time::Instant;
 
 use futures::future::BoxFuture;
 use futures::FutureExt;
 use futures::Stream;
 
+use super::deadline::DeadlineWrapper;
 use crate::raw::*;
 use crate::*;
 
@@ -36,6 +38,34 @@ use crate::*;
 ///
 /// User can use lister as `Stream<Item = Result<Entry>>` or
 /// call `next_page` directly.
+///
+/// # Stream
+///
+/// Lister implements [`Stream`], so it can be composed with all combinators
+/// provided by [`futures::StreamExt`] and [`futures::TryStreamExt`].
+///
+/// Pages are fetched lazily: a new page will only be requested from the
+/// underlying service after all entries of the previous page have been
+/// consumed. So taking the first few entries of a huge dir only costs
+/// one request.
+///
+/// ```no_run
+/// # use anyhow::Result;
+/// use futures::StreamExt;
+/// use futures::TryStreamExt;
+/// use opendal::Operator;
+/// # #[tokio::main]
+/// # async fn test(op: Operator) -> Result<()> {
+/// let entries: Vec<_> = op
+///     .list("path/to/dir/")
+///     .await?
+///     .try_filter(|de| futures::future::ready(de.path().ends_with(".json")))
+///     .take(10)
+///     .try_collect()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
 pub struct Lister {
     pager: Option<oio::Pager>,
 
@@ -62,6 +92,15 @@ impl Lister {
         }
     }
 
+    /// Make all following operations fail once the deadline is exceeded.
+    pub(crate) fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.pager = self
+            .pager
+            .take()
+            .map(|p| Box::new(DeadlineWrapper::new(p, deadline)) as oio::Pager);
+        self
+    }
+
     /// has_next can be used to check if there are more pages.
     pub async fn has_next(&mut self) -> Result<bool> {
         debug_assert!(
@@ -124,6 +163,26 @@ impl Lister {
 
         Ok(Some(entries.into_iter().map(|v| v.into_entry()).collect()))
     }
+
+    /// Get the continuation token of the next page.
+    ///
+    /// The token can be passed to `list_with(path).continuation_token(token)`
+    /// to resume listing from the next page later, for example after a
+    /// process restart.
+    ///
+    /// # Notes
+    ///
+    /// The token is only available at page boundaries: it returns `None` if
+    /// there are buffered entries not consumed yet, a page is being fetched,
+    /// the listing has finished or the underlying service doesn't support
+    /// resuming. Use `next_page` to consume entries page by page.
+    pub fn continuation_token(&self) -> Option<String> {
+        if !self.buf.is_empty() {
+            return None;
+        }
+
+        self.pager.as_ref().and_then(|p| p.continuation_token())
+    }
 }
 
 impl Stream for Lister {
@@ -166,6 +225,14 @@ impl Stream for Lister {
 /// manner.
 ///
 /// Users can construct Lister by `blocking_list` or `blocking_scan`.
+///
+/// User can use lister as `Iterator<Item = Result<Entry>>` or call
+/// `next_page` directly. Pages are fetched lazily: a new page will only be
+/// requested after all entries of the previous one have been consumed.
+///
+/// Only services with `blocking` capability can be listed in blocking way.
+/// Most of them list natively, while `s3` drives its async pager on its own
+/// runtime page by page. Other services will return an `Unsupported` error.
 pub struct BlockingLister {
     pager: oio::BlockingPager,
     buf: VecDeque<oio::Entry>,
@@ -224,3 +291,50 @@ impl Iterator for BlockingLister {
         self.next()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
+    use super::*;
+
+    struct MockPager {
+        pages: usize,
+        fetched: Arc<AtomicUsize>,
+    }
+
+    impl oio::BlockingPage for MockPager {
+        fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+            let idx = self.fetched.fetch_add(1, Ordering::SeqCst);
+            if idx >= self.pages {
+                return Ok(None);
+            }
+
+            let entries = (0..2)
+                .map(|i| oio::Entry::new(&format!("dir/{idx}-{i}"), Metadata::new(EntryMode::FILE)))
+                .collect();
+            Ok(Some(entries))
+        }
+    }
+
+    #[test]
+    fn test_blocking_lister_fetch_lazily() {
+        let fetched = Arc::new(AtomicUsize::new(0));
+        let mut lister = BlockingLister::new(Box::new(MockPager {
+            pages: 2,
+            fetched: fetched.clone(),
+        }));
+        assert_eq!(fetched.load(Ordering::SeqCst), 0);
+
+        assert_eq!(lister.next().unwrap().unwrap().path(), "dir/0-0");
+        assert_eq!(lister.next().unwrap().unwrap().path(), "dir/0-1");
+        assert_eq!(fetched.load(Ordering::SeqCst), 1);
+
+        assert_eq!(lister.next().unwrap().unwrap().path(), "dir/1-0");
+        assert_eq!(fetched.load(Ordering::SeqCst), 2);
+
+        assert_eq!(lister.count(), 1);
+    }
+}
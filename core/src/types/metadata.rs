@@ -15,6 +15,11 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
+use std::time::Duration;
+
+use base64::engine::general_purpose;
+use base64::Engine;
 use chrono::prelude::*;
 use flagset::flags;
 use flagset::FlagSet;
@@ -37,14 +42,22 @@ pub struct Metadata {
     mode: EntryMode,
 
     cache_control: Option<String>,
+    checksum_crc32c: Option<String>,
+    checksum_sha256: Option<String>,
     content_disposition: Option<String>,
+    content_encoding: Option<String>,
+    content_language: Option<String>,
     content_length: Option<u64>,
     content_md5: Option<String>,
     content_range: Option<BytesContentRange>,
     content_type: Option<String>,
     etag: Option<String>,
     last_modified: Option<DateTime<Utc>>,
+    storage_class: Option<String>,
+    ttl: Option<Duration>,
     version: Option<String>,
+    is_deleted: bool,
+    user_metadata: Option<HashMap<String, String>>,
 }
 
 impl Metadata {
@@ -63,6 +76,8 @@ impl Metadata {
             mode,
 
             cache_control: None,
+            checksum_crc32c: None,
+            checksum_sha256: None,
             content_length: None,
             content_md5: None,
             content_type: None,
@@ -70,7 +85,13 @@ impl Metadata {
             last_modified: None,
             etag: None,
             content_disposition: None,
+            content_encoding: None,
+            content_language: None,
+            storage_class: None,
+            ttl: None,
             version: None,
+            is_deleted: false,
+            user_metadata: None,
         }
     }
 
@@ -79,6 +100,17 @@ impl Metadata {
         self.bit
     }
 
+    /// Get the metadata fields that have been populated.
+    ///
+    /// Fields are populated if they are set by services or requested via
+    /// [`FutureStat::metakey`](crate::operator_futures::FutureStat::metakey).
+    /// A populated field could still be `None` which means the service
+    /// doesn't have it. Metadata returned by a full `stat` contains
+    /// [`Metakey::Complete`].
+    pub fn metakey(&self) -> FlagSet<Metakey> {
+        self.bit
+    }
+
     /// Set bit with given.
     pub(crate) fn with_bit(mut self, bit: impl Into<FlagSet<Metakey>>) -> Self {
         self.bit = bit.into();
@@ -151,10 +183,74 @@ impl Metadata {
         self
     }
 
+    /// CRC32C checksum of this entry.
+    ///
+    /// The value is the base64 encoded big-endian CRC32C of the whole content.
+    pub fn checksum_crc32c(&self) -> Option<&str> {
+        debug_assert!(
+            self.bit.contains(Metakey::ChecksumCrc32c) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: checksum_crc32c, maybe a bug"
+        );
+
+        self.checksum_crc32c.as_deref()
+    }
+
+    /// Set CRC32C checksum of this entry.
+    ///
+    /// The value should be the base64 encoded big-endian CRC32C of the whole content.
+    pub fn set_checksum_crc32c(&mut self, v: &str) -> &mut Self {
+        self.checksum_crc32c = Some(v.to_string());
+        self.bit |= Metakey::ChecksumCrc32c;
+        self
+    }
+
+    /// Set CRC32C checksum of this entry.
+    ///
+    /// The value should be the base64 encoded big-endian CRC32C of the whole content.
+    pub fn with_checksum_crc32c(mut self, v: String) -> Self {
+        self.checksum_crc32c = Some(v);
+        self.bit |= Metakey::ChecksumCrc32c;
+        self
+    }
+
+    /// SHA256 checksum of this entry.
+    ///
+    /// The value is the base64 encoded SHA256 digest of the whole content.
+    pub fn checksum_sha256(&self) -> Option<&str> {
+        debug_assert!(
+            self.bit.contains(Metakey::ChecksumSha256) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: checksum_sha256, maybe a bug"
+        );
+
+        self.checksum_sha256.as_deref()
+    }
+
+    /// Set SHA256 checksum of this entry.
+    ///
+    /// The value should be the base64 encoded SHA256 digest of the whole content.
+    pub fn set_checksum_sha256(&mut self, v: &str) -> &mut Self {
+        self.checksum_sha256 = Some(v.to_string());
+        self.bit |= Metakey::ChecksumSha256;
+        self
+    }
+
+    /// Set SHA256 checksum of this entry.
+    ///
+    /// The value should be the base64 encoded SHA256 digest of the whole content.
+    pub fn with_checksum_sha256(mut self, v: String) -> Self {
+        self.checksum_sha256 = Some(v);
+        self.bit |= Metakey::ChecksumSha256;
+        self
+    }
+
     /// Content length of this entry.
     ///
     /// `Content-Length` is defined by [RFC 7230](https://httpwg.org/specs/rfc7230.html#header.content-length)
     /// Refer to [MDN Content-Length](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Length) for more information.
+    ///
+    /// While reading the whole file, the reader is guaranteed to return
+    /// exactly `content_length` bytes, or an `Unexpected` error will be
+    /// returned instead of a silently truncated content.
     pub fn content_length(&self) -> u64 {
         debug_assert!(
             self.bit.contains(Metakey::ContentLength) || self.bit.contains(Metakey::Complete),
@@ -421,6 +517,127 @@ impl Metadata {
         self
     }
 
+    /// Content-Encoding of this entry.
+    ///
+    /// `Content-Encoding` is defined by [RFC 9110](https://httpwg.org/specs/rfc9110.html#field.content-encoding).
+    /// Refer to [MDN Content-Encoding](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Encoding) for more information.
+    pub fn content_encoding(&self) -> Option<&str> {
+        debug_assert!(
+            self.bit.contains(Metakey::ContentEncoding) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: content_encoding, maybe a bug"
+        );
+
+        self.content_encoding.as_deref()
+    }
+
+    /// Set Content-Encoding of this entry.
+    ///
+    /// `Content-Encoding` is defined by [RFC 9110](https://httpwg.org/specs/rfc9110.html#field.content-encoding).
+    /// Refer to [MDN Content-Encoding](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Encoding) for more information.
+    pub fn set_content_encoding(&mut self, v: &str) -> &mut Self {
+        self.content_encoding = Some(v.to_string());
+        self.bit |= Metakey::ContentEncoding;
+        self
+    }
+
+    /// Set Content-Encoding of this entry.
+    ///
+    /// `Content-Encoding` is defined by [RFC 9110](https://httpwg.org/specs/rfc9110.html#field.content-encoding).
+    /// Refer to [MDN Content-Encoding](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Encoding) for more information.
+    pub fn with_content_encoding(mut self, v: String) -> Self {
+        self.content_encoding = Some(v);
+        self.bit |= Metakey::ContentEncoding;
+        self
+    }
+
+    /// Content-Language of this entry.
+    ///
+    /// `Content-Language` is defined by [RFC 9110](https://httpwg.org/specs/rfc9110.html#field.content-language).
+    /// Refer to [MDN Content-Language](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Language) for more information.
+    pub fn content_language(&self) -> Option<&str> {
+        debug_assert!(
+            self.bit.contains(Metakey::ContentLanguage) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: content_language, maybe a bug"
+        );
+
+        self.content_language.as_deref()
+    }
+
+    /// Set Content-Language of this entry.
+    ///
+    /// `Content-Language` is defined by [RFC 9110](https://httpwg.org/specs/rfc9110.html#field.content-language).
+    /// Refer to [MDN Content-Language](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Language) for more information.
+    pub fn set_content_language(&mut self, v: &str) -> &mut Self {
+        self.content_language = Some(v.to_string());
+        self.bit |= Metakey::ContentLanguage;
+        self
+    }
+
+    /// Set Content-Language of this entry.
+    ///
+    /// `Content-Language` is defined by [RFC 9110](https://httpwg.org/specs/rfc9110.html#field.content-language).
+    /// Refer to [MDN Content-Language](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Language) for more information.
+    pub fn with_content_language(mut self, v: String) -> Self {
+        self.content_language = Some(v);
+        self.bit |= Metakey::ContentLanguage;
+        self
+    }
+
+    /// Storage class of this entry.
+    ///
+    /// The value is returned by service as is, like `STANDARD_IA` on s3 or
+    /// `NEARLINE` on gcs.
+    pub fn storage_class(&self) -> Option<&str> {
+        debug_assert!(
+            self.bit.contains(Metakey::StorageClass) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: storage_class, maybe a bug"
+        );
+
+        self.storage_class.as_deref()
+    }
+
+    /// Set storage class of this entry.
+    pub fn set_storage_class(&mut self, v: &str) -> &mut Self {
+        self.storage_class = Some(v.to_string());
+        self.bit |= Metakey::StorageClass;
+        self
+    }
+
+    /// Set storage class of this entry.
+    pub fn with_storage_class(mut self, v: String) -> Self {
+        self.storage_class = Some(v);
+        self.bit |= Metakey::StorageClass;
+        self
+    }
+
+    /// Remaining time to live of this entry.
+    ///
+    /// `None` means this entry doesn't expire or the service can't report
+    /// it. Only key-value services that support `write_with_ttl` could
+    /// return this.
+    pub fn ttl(&self) -> Option<Duration> {
+        debug_assert!(
+            self.bit.contains(Metakey::Ttl) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: ttl, maybe a bug"
+        );
+
+        self.ttl
+    }
+
+    /// Set remaining time to live of this entry.
+    pub fn set_ttl(&mut self, v: Duration) -> &mut Self {
+        self.ttl = Some(v);
+        self.bit |= Metakey::Ttl;
+        self
+    }
+
+    /// Set remaining time to live of this entry.
+    pub fn with_ttl(mut self, v: Duration) -> Self {
+        self.ttl = Some(v);
+        self.bit |= Metakey::Ttl;
+        self
+    }
+
     /// Version of this entry.
     ///
     /// Version is a string that can be used to identify the version of this entry.
@@ -456,6 +673,116 @@ impl Metadata {
         self.bit |= Metakey::Version;
         self
     }
+
+    /// Check if this entry is a delete marker.
+    ///
+    /// Delete markers are only returned while listing with both `versions`
+    /// and `deleted` enabled, they have a version but no content.
+    pub fn is_deleted(&self) -> bool {
+        self.is_deleted
+    }
+
+    /// Set whether this entry is a delete marker.
+    pub fn with_is_deleted(mut self, v: bool) -> Self {
+        self.is_deleted = v;
+        self
+    }
+
+    /// Set whether this entry is a delete marker.
+    pub fn set_is_deleted(&mut self, v: bool) -> &mut Self {
+        self.is_deleted = v;
+        self
+    }
+
+    /// User defined metadata of this entry.
+    ///
+    /// User metadata is set while writing via
+    /// [`FutureWrite::user_metadata`](crate::operator_futures::FutureWrite::user_metadata),
+    /// like `x-amz-meta-*` on s3 or `user.*` xattrs on fs.
+    pub fn user_metadata(&self) -> Option<&HashMap<String, String>> {
+        debug_assert!(
+            self.bit.contains(Metakey::UserMetadata) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: user_metadata, maybe a bug"
+        );
+
+        self.user_metadata.as_ref()
+    }
+
+    /// Set user defined metadata of this entry.
+    pub fn with_user_metadata(mut self, v: HashMap<String, String>) -> Self {
+        self.user_metadata = Some(v);
+        self.bit |= Metakey::UserMetadata;
+        self
+    }
+
+    /// Set user defined metadata of this entry.
+    pub fn set_user_metadata(&mut self, v: HashMap<String, String>) -> &mut Self {
+        self.user_metadata = Some(v);
+        self.bit |= Metakey::UserMetadata;
+        self
+    }
+
+    /// Check if this metadata and other one likely refer to the same content.
+    ///
+    /// # Behavior
+    ///
+    /// - If both etags are present and equal, returns `true`.
+    /// - If both content md5 are present, returns whether they are equal.
+    /// - Otherwise, falls back to compare content length and last modified,
+    ///   returns `false` if any of them is missing.
+    ///
+    /// # Notes
+    ///
+    /// Etag is not always the md5 of content even if it looks like one.
+    /// For example, etag of objects uploaded by multipart upload on s3
+    /// looks like `"<md5>-<parts>"`, and objects encrypted by SSE-KMS or
+    /// SSE-C have etags that are not md5 at all. So content equality is
+    /// never inferred from etag, services will set content md5 only while
+    /// they can guarantee it.
+    pub fn is_same_content(&self, other: &Metadata) -> bool {
+        if let (Some(l), Some(r)) = (self.etag.as_deref(), other.etag.as_deref()) {
+            if l == r {
+                return true;
+            }
+        }
+
+        if let (Some(l), Some(r)) = (
+            self.content_md5.as_deref().and_then(normalize_md5),
+            other.content_md5.as_deref().and_then(normalize_md5),
+        ) {
+            return l == r;
+        }
+
+        match (
+            self.content_length,
+            other.content_length,
+            self.last_modified,
+            other.last_modified,
+        ) {
+            (Some(ll), Some(rl), Some(lm), Some(rm)) => ll == rl && lm == rm,
+            _ => false,
+        }
+    }
+}
+
+/// Check if given value looks like a hex encoded md5 digest.
+pub(crate) fn is_md5_hex(v: &str) -> bool {
+    v.len() == 32 && v.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Normalize content md5 into a lowercase hex encoded digest.
+///
+/// Content md5 could be hex encoded like s3 etags or base64 encoded like
+/// the `Content-MD5` header. Returns `None` if it's neither of them.
+pub(crate) fn normalize_md5(v: &str) -> Option<String> {
+    if is_md5_hex(v) {
+        return Some(v.to_ascii_lowercase());
+    }
+
+    match general_purpose::STANDARD.decode(v) {
+        Ok(bs) if bs.len() == 16 => Some(bs.iter().map(|b| format!("{b:02x}")).collect()),
+        _ => None,
+    }
 }
 
 flags! {
@@ -481,8 +808,16 @@ flags! {
         Mode,
         /// Key for cache control.
         CacheControl,
+        /// Key for crc32c checksum.
+        ChecksumCrc32c,
+        /// Key for sha256 checksum.
+        ChecksumSha256,
         /// Key for content disposition.
         ContentDisposition,
+        /// Key for content encoding.
+        ContentEncoding,
+        /// Key for content language.
+        ContentLanguage,
         /// Key for content length.
         ContentLength,
         /// Key for content md5.
@@ -495,7 +830,87 @@ flags! {
         Etag,
         /// Key for last last modified.
         LastModified,
+        /// Key for storage class.
+        StorageClass,
+        /// Key for ttl.
+        Ttl,
         /// Key for version.
         Version,
+        /// Key for user metadata.
+        UserMetadata,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_same_content() {
+        let ts = Utc.timestamp_opt(1_000_000, 0).unwrap();
+        let base = Metadata::new(EntryMode::FILE)
+            .with_content_length(1024)
+            .with_last_modified(ts);
+
+        let cases = vec![
+            (
+                "same etag",
+                base.clone()
+                    .with_etag("\"9bb58f26192e4ba00f01e2e7b136bbd8\"".to_string()),
+                Metadata::new(EntryMode::FILE)
+                    .with_etag("\"9bb58f26192e4ba00f01e2e7b136bbd8\"".to_string()),
+                true,
+            ),
+            (
+                "same content md5 in hex and base64",
+                Metadata::new(EntryMode::FILE)
+                    .with_content_md5("9BB58F26192E4BA00F01E2E7B136BBD8".to_string()),
+                Metadata::new(EntryMode::FILE)
+                    .with_content_md5("m7WPJhkuS6APAeLnsTa72A==".to_string()),
+                true,
+            ),
+            (
+                "different content md5",
+                base.clone()
+                    .with_content_md5("9bb58f26192e4ba00f01e2e7b136bbd8".to_string()),
+                base.clone()
+                    .with_content_md5("c4ca4238a0b923820dcc509a6f75849b".to_string()),
+                false,
+            ),
+            (
+                "md5 like etags fall back to length and last modified",
+                base.clone()
+                    .with_etag("\"9bb58f26192e4ba00f01e2e7b136bbd8\"".to_string()),
+                base.clone()
+                    .with_etag("\"c4ca4238a0b923820dcc509a6f75849b\"".to_string()),
+                true,
+            ),
+            (
+                "multipart etag falls back to length and last modified",
+                base.clone()
+                    .with_etag("\"9bb58f26192e4ba00f01e2e7b136bbd8\"".to_string()),
+                base.clone()
+                    .with_etag("\"d41d8cd98f00b204e9800998ecf8427e-2\"".to_string()),
+                true,
+            ),
+            ("no etag", base.clone(), base.clone(), true),
+            (
+                "different length",
+                base.clone(),
+                base.clone().with_content_length(2048),
+                false,
+            ),
+            (
+                "missing last modified",
+                base.clone(),
+                Metadata::new(EntryMode::FILE).with_content_length(1024),
+                false,
+            ),
+        ];
+
+        for (name, l, r, expected) in cases {
+            assert_eq!(l.is_same_content(&r), expected, "{name}");
+            assert_eq!(r.is_same_content(&l), expected, "{name}");
+        }
     }
 }
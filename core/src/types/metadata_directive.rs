@@ -0,0 +1,32 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+/// MetadataDirective decides how metadata is handled while copying.
+///
+/// Only the metadata stored along with the file is affected, like
+/// `content-type`, `content-disposition` and `cache-control`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MetadataDirective {
+    /// Preserve the metadata of the source file.
+    #[default]
+    Copy,
+    /// Replace the metadata with the ones given in the copy request.
+    ///
+    /// Metadata that is not given will be left empty.
+    Replace,
+}
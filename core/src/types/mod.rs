@@ -22,17 +22,49 @@ mod entry;
 pub use entry::Entry;
 
 mod metadata;
+#[cfg(feature = "services-s3")]
+pub(crate) use metadata::is_md5_hex;
+pub(crate) use metadata::normalize_md5;
 pub use metadata::Metadata;
 pub use metadata::Metakey;
 
 mod reader;
 pub use reader::BlockingReader;
+pub use reader::Chunks;
+pub use reader::Lines;
 pub use reader::Reader;
 
+mod file;
+pub use file::File;
+
+mod deadline;
+pub(crate) use deadline::run_with_deadline;
+
+mod progress;
+pub use progress::ProgressCallback;
+
+mod checksum;
+pub use checksum::ChecksumAlgo;
+#[cfg(feature = "services-s3")]
+pub(crate) use checksum::Hasher;
+
+mod metadata_directive;
+pub use metadata_directive::MetadataDirective;
+
+mod object_lock;
+pub use object_lock::ObjectLock;
+pub use object_lock::ObjectLockMode;
+
 mod writer;
 pub use writer::BlockingWriter;
+pub use writer::WriteCheckpoint;
+pub use writer::WritePartCallback;
+pub use writer::WritePartStats;
 pub use writer::Writer;
 
+mod write_options;
+pub use write_options::WriteOptions;
+
 mod appender;
 pub use appender::Appender;
 
@@ -47,6 +79,7 @@ pub use operator::BlockingOperator;
 pub use operator::Operator;
 pub use operator::OperatorBuilder;
 pub use operator::OperatorInfo;
+pub use operator::SyncStatus;
 
 mod builder;
 pub use builder::Builder;
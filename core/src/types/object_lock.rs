@@ -0,0 +1,77 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use chrono::DateTime;
+use chrono::Utc;
+
+/// ObjectLockMode is the retention mode of a locked file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ObjectLockMode {
+    /// Users with special permissions can still overwrite or delete the
+    /// file and alter the retention.
+    Governance,
+    /// No one, including the root user, can overwrite or delete the file
+    /// until the retention expires.
+    Compliance,
+}
+
+/// ObjectLock carries the retention and legal hold of a file, like the
+/// Object Lock of s3.
+///
+/// A locked file can't be deleted or overwritten, operations on it will
+/// return [`ErrorKind::ObjectLocked`](crate::ErrorKind::ObjectLocked).
+///
+/// Fields that are `None` mean they are not set or should be kept as is
+/// while updating.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ObjectLock {
+    retention: Option<(ObjectLockMode, DateTime<Utc>)>,
+    legal_hold: Option<bool>,
+}
+
+impl ObjectLock {
+    /// Create a new empty `ObjectLock`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the retention mode and the time until which the file is retained.
+    pub fn with_retention(mut self, mode: ObjectLockMode, retain_until: DateTime<Utc>) -> Self {
+        self.retention = Some((mode, retain_until));
+        self
+    }
+
+    /// Get the retention mode and the time until which the file is retained.
+    pub fn retention(&self) -> Option<(ObjectLockMode, DateTime<Utc>)> {
+        self.retention
+    }
+
+    /// Set whether the legal hold is on.
+    ///
+    /// A file under legal hold can't be deleted or overwritten no matter
+    /// what its retention is, until the legal hold is turned off.
+    pub fn with_legal_hold(mut self, on: bool) -> Self {
+        self.legal_hold = Some(on);
+        self
+    }
+
+    /// Get whether the legal hold is on.
+    pub fn legal_hold(&self) -> Option<bool> {
+        self.legal_hold
+    }
+}
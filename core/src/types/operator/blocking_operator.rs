@@ -15,14 +15,18 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::io::Read;
 use std::ops::RangeBounds;
+use std::sync::Arc;
 
 use bytes::Bytes;
 use flagset::FlagSet;
 
+use super::operator::blocking_has_children;
 use super::operator_functions::*;
 use crate::raw::*;
+use crate::types::checksum::validate_checksum;
 use crate::*;
 
 /// BlockingOperator is the entry for all public blocking APIs.
@@ -53,11 +57,37 @@ use crate::*;
 ///     Ok(())
 /// }
 /// ```
+///
+/// # Notes
+///
+/// Blocking APIs will block the current thread. Calling them inside an
+/// async runtime will not panic, but the runtime's worker thread will be
+/// blocked until the operation finished. Please move blocking calls into
+/// `tokio::task::spawn_blocking` in async context:
+///
+/// ```
+/// # use anyhow::Result;
+/// use opendal::BlockingOperator;
+///
+/// # async fn test(op: BlockingOperator) -> Result<()> {
+/// let bs = tokio::task::spawn_blocking(move || op.read("path/to/file")).await??;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Clone
+///
+/// Like [`Operator`], cloning a `BlockingOperator` is a cheap `Arc` clone
+/// sharing the same accessor, connection pool and layer states.
 #[derive(Clone, Debug)]
 pub struct BlockingOperator {
     accessor: FusedAccessor,
 
     limit: usize,
+
+    path_normalization: bool,
+
+    default_write: Arc<WriteOptions>,
 }
 
 impl BlockingOperator {
@@ -75,7 +105,12 @@ impl BlockingOperator {
             .capability()
             .batch_max_operations
             .unwrap_or(1000);
-        Self { accessor, limit }
+        Self {
+            accessor,
+            limit,
+            path_normalization: false,
+            default_write: Arc::new(WriteOptions::default()),
+        }
     }
 
     /// Get current operator's limit
@@ -92,6 +127,46 @@ impl BlockingOperator {
         op
     }
 
+    /// Check if path normalization is enabled.
+    pub fn path_normalization(&self) -> bool {
+        self.path_normalization
+    }
+
+    /// Specify whether to resolve `.` and `..` in input paths.
+    ///
+    /// Read [`Operator::with_path_normalization`] for more details.
+    ///
+    /// Default: false
+    pub fn with_path_normalization(&self, enabled: bool) -> Self {
+        let mut op = self.clone();
+        op.path_normalization = enabled;
+        op
+    }
+
+    /// Get the default options of write operations.
+    pub fn default_write_options(&self) -> &WriteOptions {
+        &self.default_write
+    }
+
+    /// Specify the default options of write operations.
+    ///
+    /// Read [`Operator::with_default_write_options`] for more details.
+    pub fn with_default_write_options(&self, opts: WriteOptions) -> Self {
+        let mut op = self.clone();
+        op.default_write = Arc::new(opts);
+        op
+    }
+
+    /// Normalize input path into the canonical form.
+    fn normalize(&self, path: &str) -> String {
+        let path = normalize_path(path);
+        if self.path_normalization {
+            resolve_dot_segments(&path)
+        } else {
+            path
+        }
+    }
+
     /// Get information of underlying accessor.
     ///
     /// # Examples
@@ -145,12 +220,54 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn stat(&self, path: &str) -> Result<Metadata> {
-        let path = normalize_path(path);
+        self.stat_with(path).call()
+    }
 
-        let rp = self.inner().blocking_stat(&path, OpStat::new())?;
-        let meta = rp.into_metadata();
+    /// Get current path's metadata **without cache** directly with extra
+    /// options.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::BlockingOperator;
+    /// # fn test(op: BlockingOperator) -> Result<()> {
+    /// let meta = op
+    ///     .stat_with("path/to/dir")
+    ///     .treat_prefix_as_dir(true)
+    ///     .call()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stat_with(&self, path: &str) -> FunctionStat {
+        let path = self.normalize(path);
 
-        Ok(meta)
+        FunctionStat(OperatorFunction::new(
+            self.inner().clone(),
+            path,
+            OpStat::new(),
+            |inner, path, args| {
+                let treat_prefix_as_dir = args.treat_prefix_as_dir();
+
+                match inner.blocking_stat(&path, args) {
+                    Ok(rp) => Ok(rp.into_metadata()),
+                    Err(err)
+                        if err.kind() == ErrorKind::NotFound
+                            && treat_prefix_as_dir
+                            && !path.ends_with('/')
+                            && inner.info().capability().list =>
+                    {
+                        match blocking_has_children(&inner, &format!("{path}/")) {
+                            Ok(true) => Ok(Metadata::new(EntryMode::DIR)),
+                            Ok(false) => Err(err),
+                            Err(e) if e.kind() == ErrorKind::NotFound => Err(err),
+                            Err(e) => Err(e),
+                        }
+                    }
+                    Err(err) => Err(err),
+                }
+            },
+        ))
     }
 
     /// Get current metadata with cache in blocking way.
@@ -280,8 +397,15 @@ impl BlockingOperator {
     ///
     /// # Behavior
     ///
-    /// - Create on existing dir will succeed.
+    /// - Create on existing dir will succeed on all services.
     /// - Create dir is always recursive, works like `mkdir -p`
+    /// - On hierarchical services like `fs` and `hdfs`, a real dir will be
+    ///   created.
+    /// - On object storage services like `s3` and `gcs`, a zero-byte marker
+    ///   object with key ending in `/` will be created, which follows the
+    ///   convention of their consoles. Listing its parent will return the
+    ///   path as a dir entry, and the path keeps existing as a dir even if
+    ///   it has no children.
     ///
     /// # Examples
     ///
@@ -295,7 +419,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn create_dir(&self, path: &str) -> Result<()> {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         if !validate_path(&path, EntryMode::DIR) {
             return Err(Error::new(
@@ -351,7 +475,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn range_read(&self, path: &str, range: impl RangeBounds<u64>) -> Result<Vec<u8>> {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         if !validate_path(&path, EntryMode::FILE) {
             return Err(
@@ -411,7 +535,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn range_reader(&self, path: &str, range: impl RangeBounds<u64>) -> Result<BlockingReader> {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         if !validate_path(&path, EntryMode::FILE) {
             return Err(
@@ -451,6 +575,123 @@ impl BlockingOperator {
         self.write_with(path, bs).call()
     }
 
+    /// Update the content of path with given function via compare-and-swap.
+    ///
+    /// This is a shortcut of [`BlockingOperator::update_with_retries`] which
+    /// will retry at most 3 times.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::BlockingOperator;
+    /// use bytes::Bytes;
+    ///
+    /// # fn test(op: BlockingOperator) -> Result<()> {
+    /// op.update("path/to/counter", |bs| {
+    ///     let n: u64 = String::from_utf8_lossy(&bs).parse().unwrap_or_default();
+    ///     Bytes::from((n + 1).to_string())
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn update(&self, path: &str, f: impl FnMut(Bytes) -> Bytes) -> Result<()> {
+        self.update_with_retries(path, 3, f)
+    }
+
+    /// Update the content of path with given function via compare-and-swap,
+    /// retrying at most `max_retries` times on conflicts.
+    ///
+    /// Refer to [`Operator::update_with_retries`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::BlockingOperator;
+    /// use bytes::Bytes;
+    ///
+    /// # fn test(op: BlockingOperator) -> Result<()> {
+    /// op.update_with_retries("path/to/file", 10, |bs| {
+    ///     let mut bs = bs.to_vec();
+    ///     bs.extend_from_slice(b"hello");
+    ///     Bytes::from(bs)
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn update_with_retries(
+        &self,
+        path: &str,
+        max_retries: usize,
+        mut f: impl FnMut(Bytes) -> Bytes,
+    ) -> Result<()> {
+        let read_with_if_match = self.info().capability().read_with_if_match;
+
+        let mut retries = 0;
+        loop {
+            let meta = self.stat(path)?;
+            let etag = meta
+                .etag()
+                .ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::Unsupported,
+                        "service doesn't return etag which is required by update",
+                    )
+                    .with_operation("BlockingOperator::update")
+                    .with_context("service", self.info().scheme().into_static())
+                    .with_context("path", path)
+                })?
+                .to_string();
+
+            let res = self.read_if_match(path, read_with_if_match.then_some(etag.as_str()));
+            let res = match res {
+                Ok(bs) => {
+                    let bs = f(Bytes::from(bs));
+                    self.write_with(path, bs).if_match(&etag).call()
+                }
+                Err(err) => Err(err),
+            };
+
+            match res {
+                Err(err) if err.kind() == ErrorKind::ConditionNotMatch && retries < max_retries => {
+                    retries += 1;
+                }
+                Err(err) => {
+                    return Err(err
+                        .with_operation("BlockingOperator::update")
+                        .with_context("retries", retries.to_string()))
+                }
+                Ok(()) => return Ok(()),
+            }
+        }
+    }
+
+    /// Read the whole path, making sure it still matches given etag if any.
+    fn read_if_match(&self, path: &str, etag: Option<&str>) -> Result<Vec<u8>> {
+        let path = self.normalize(path);
+
+        let mut args = OpRead::new();
+        if let Some(etag) = etag {
+            args = args.with_if_match(etag);
+        }
+
+        let (rp, mut s) = self.inner().blocking_read(&path, args)?;
+
+        let mut buffer = Vec::with_capacity(
+            rp.into_metadata().content_length_raw().unwrap_or_default() as usize,
+        );
+        s.read_to_end(&mut buffer).map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "blocking read failed")
+                .with_operation("BlockingOperator::update")
+                .with_context("service", self.info().scheme().into_static())
+                .with_context("path", path)
+                .set_source(err)
+        })?;
+
+        Ok(buffer)
+    }
+
     /// Copy a file from `from` to `to`.
     ///
     /// # Notes
@@ -472,7 +713,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn copy(&self, from: &str, to: &str) -> Result<()> {
-        let from = normalize_path(from);
+        let from = self.normalize(from);
 
         if !validate_path(&from, EntryMode::FILE) {
             return Err(
@@ -483,7 +724,7 @@ impl BlockingOperator {
             );
         }
 
-        let to = normalize_path(to);
+        let to = self.normalize(to);
 
         if !validate_path(&to, EntryMode::FILE) {
             return Err(
@@ -529,7 +770,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn rename(&self, from: &str, to: &str) -> Result<()> {
-        let from = normalize_path(from);
+        let from = self.normalize(from);
 
         if !validate_path(&from, EntryMode::FILE) {
             return Err(
@@ -540,7 +781,7 @@ impl BlockingOperator {
             );
         }
 
-        let to = normalize_path(to);
+        let to = self.normalize(to);
 
         if !validate_path(&to, EntryMode::FILE) {
             return Err(
@@ -566,6 +807,72 @@ impl BlockingOperator {
         Ok(())
     }
 
+    /// Get tags of given file.
+    ///
+    /// Services that don't support tags will return `Unsupported` error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::Result;
+    /// # use opendal::BlockingOperator;
+    /// # fn test(op: BlockingOperator) -> Result<()> {
+    /// let tags = op.get_tags("path/to/file")?;
+    /// println!("expire: {:?}", tags.get("expire"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_tags(&self, path: &str) -> Result<HashMap<String, String>> {
+        let path = self.normalize(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "get tags path is a directory")
+                    .with_operation("BlockingOperator::get_tags")
+                    .with_context("service", self.info().scheme())
+                    .with_context("path", path),
+            );
+        }
+
+        let rp = self.inner().blocking_get_tags(&path, OpGetTags::new())?;
+
+        Ok(rp.into_tags())
+    }
+
+    /// Replace tags of given file with the given tags.
+    ///
+    /// Services that don't support tags will return `Unsupported` error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::collections::HashMap;
+    /// # use opendal::Result;
+    /// # use opendal::BlockingOperator;
+    /// # fn test(op: BlockingOperator) -> Result<()> {
+    /// let tags = HashMap::from([("expire".to_string(), "30d".to_string())]);
+    /// op.put_tags("path/to/file", tags)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn put_tags(&self, path: &str, tags: HashMap<String, String>) -> Result<()> {
+        let path = self.normalize(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "put tags path is a directory")
+                    .with_operation("BlockingOperator::put_tags")
+                    .with_context("service", self.info().scheme())
+                    .with_context("path", path),
+            );
+        }
+
+        self.inner()
+            .blocking_put_tags(&path, OpPutTags::new(tags))?;
+
+        Ok(())
+    }
+
     /// Write data with option described in OpenDAL [rfc-0661](../../docs/rfcs/0661-path-in-accessor.md)
     ///
     /// # Notes
@@ -589,14 +896,19 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn write_with(&self, path: &str, bs: impl Into<Bytes>) -> FunctionWrite {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         let bs = bs.into();
 
         FunctionWrite(OperatorFunction::new(
             self.inner().clone(),
             path,
-            (OpWrite::default().with_content_length(bs.len() as u64), bs),
+            (
+                self.default_write
+                    .to_op_write()
+                    .with_content_length(bs.len() as u64),
+                bs,
+            ),
             |inner, path, (args, bs)| {
                 if !validate_path(&path, EntryMode::FILE) {
                     return Err(
@@ -607,6 +919,8 @@ impl BlockingOperator {
                     );
                 }
 
+                validate_checksum(Operation::BlockingWrite, &args)?;
+
                 let (_, mut w) = inner.blocking_write(&path, args)?;
                 w.write(bs)?;
                 w.close()?;
@@ -640,7 +954,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn writer(&self, path: &str) -> Result<BlockingWriter> {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         if !validate_path(&path, EntryMode::FILE) {
             return Err(
@@ -651,7 +965,7 @@ impl BlockingOperator {
             );
         }
 
-        let op = OpWrite::default();
+        let op = self.default_write.to_op_write();
         BlockingWriter::create(self.inner().clone(), &path, op)
     }
 
@@ -699,7 +1013,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn delete_with(&self, path: &str) -> FunctionDelete {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         FunctionDelete(OperatorFunction::new(
             self.inner().clone(),
@@ -823,8 +1137,7 @@ impl BlockingOperator {
     /// # use opendal::BlockingOperator;
     /// # use opendal::EntryMode;
     /// # fn test(op: BlockingOperator) -> Result<()> {
-    /// let mut ds = op.list("path/to/dir/")?;
-    /// while let Some(mut de) = ds.next() {
+    /// for de in op.list("path/to/dir/")? {
     ///     let meta = op.metadata(&de?, {
     ///         use opendal::Metakey::*;
     ///         Mode
@@ -843,7 +1156,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn list(&self, path: &str) -> Result<BlockingLister> {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         if !validate_path(&path, EntryMode::DIR) {
             return Err(Error::new(
@@ -859,6 +1172,68 @@ impl BlockingOperator {
         Ok(BlockingLister::new(pager))
     }
 
+    /// List current dir path with extra options.
+    ///
+    /// An error will be returned if path doesn't end with `/`.
+    ///
+    /// # Examples
+    ///
+    /// ## List entries in lexicographic order
+    ///
+    /// Some services return entries in arbitrary order. By enabling `sorted`,
+    /// entries will be returned in lexicographic order of their paths.
+    ///
+    /// - If service returns pages in order (like s3), only entries within
+    ///   one page will be buffered and sorted.
+    /// - Otherwise, the whole listing will be buffered in memory and sorted
+    ///   before returning the first entry, so memory usage grows with the
+    ///   number of entries under the dir. Avoid `sorted` while listing a
+    ///   huge dir on such services.
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use opendal::BlockingOperator;
+    /// # fn test(op: BlockingOperator) -> Result<()> {
+    /// let ds = op.list_with("path/to/dir/").sorted(true).call()?;
+    /// for de in ds {
+    ///     println!("{}", de?.path());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_with(&self, path: &str) -> FunctionList {
+        let path = self.normalize(path);
+
+        FunctionList(OperatorFunction::new(
+            self.inner().clone(),
+            path,
+            OpList::new(),
+            |inner, path, args| {
+                if !validate_path(&path, EntryMode::DIR) {
+                    return Err(Error::new(
+                        ErrorKind::NotADirectory,
+                        "the path trying to list should end with `/`",
+                    )
+                    .with_operation("BlockingOperator::list_with")
+                    .with_context("service", inner.info().scheme().into_static())
+                    .with_context("path", &path));
+                }
+
+                let sorted = args.sorted();
+                let (_, pager) = inner.blocking_list(&path, args)?;
+
+                let pager: oio::BlockingPager = if sorted {
+                    let ordered_pages = inner.info().capability().list_with_sorted;
+                    Box::new(oio::into_sorted_page(pager, ordered_pages))
+                } else {
+                    pager
+                };
+
+                Ok(BlockingLister::new(pager))
+            },
+        ))
+    }
+
     /// List dir in flat way.
     ///
     /// Also, this function can be used to list a prefix.
@@ -898,7 +1273,7 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn scan(&self, path: &str) -> Result<BlockingLister> {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         if !validate_path(&path, EntryMode::DIR) {
             return Err(Error::new(
@@ -16,6 +16,7 @@
 // under the License.
 
 use std::collections::HashMap;
+use std::ffi::OsString;
 use std::sync::Arc;
 
 use crate::layers::*;
@@ -115,10 +116,70 @@ impl Operator {
     pub fn from_map<B: Builder>(
         map: HashMap<String, String>,
     ) -> Result<OperatorBuilder<impl Accessor>> {
-        let acc = B::from_map(map).build()?;
+        let mut ab = B::from_map(map);
+        let acc = ab.build()?;
         Ok(OperatorBuilder::new(acc))
     }
 
+    /// Create a new operator from given map, and reject config keys that
+    /// are not recognized by the builder.
+    ///
+    /// # Notes
+    ///
+    /// [`Operator::from_map`] ignores unknown keys silently, which makes a
+    /// typo like `regon` hard to find. from_map_strict will return a
+    /// [`ErrorKind::ConfigInvalid`] error listing all unknown keys instead.
+    ///
+    /// Builders that don't declare [`Builder::CONFIG_KEYS`] will return
+    /// [`ErrorKind::Unsupported`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use std::collections::HashMap;
+    ///
+    /// use opendal::services::Fs;
+    /// use opendal::Operator;
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let map = HashMap::from([("rooot".to_string(), "/tmp".to_string())]);
+    ///
+    ///     // `rooot` is not a valid config key of fs.
+    ///     let res = Operator::from_map_strict::<Fs>(map);
+    ///     assert!(res.is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn from_map_strict<B: Builder>(
+        map: HashMap<String, String>,
+    ) -> Result<OperatorBuilder<impl Accessor>> {
+        let keys = B::CONFIG_KEYS.ok_or_else(|| {
+            Error::new(
+                ErrorKind::Unsupported,
+                "builder doesn't declare its config keys",
+            )
+            .with_operation("Operator::from_map_strict")
+            .with_context("service", B::SCHEME.into_static())
+        })?;
+
+        let mut unknown: Vec<&str> = map
+            .keys()
+            .map(|k| k.as_str())
+            .filter(|k| !keys.contains(k))
+            .collect();
+        if !unknown.is_empty() {
+            unknown.sort_unstable();
+            return Err(Error::new(ErrorKind::ConfigInvalid, "unknown config keys")
+                .with_operation("Operator::from_map_strict")
+                .with_context("service", B::SCHEME.into_static())
+                .with_context("keys", unknown.join(",")));
+        }
+
+        Self::from_map::<B>(map)
+    }
+
     /// Create a new operator from given scheme and map.
     ///
     /// # Notes
@@ -187,6 +248,8 @@ impl Operator {
             Scheme::Memory => Self::from_map::<services::Memory>(map)?.finish(),
             #[cfg(feature = "services-mini-moka")]
             Scheme::MiniMoka => Self::from_map::<services::MiniMoka>(map)?.finish(),
+            #[cfg(feature = "services-mock")]
+            Scheme::Mock => Self::from_map::<services::Mock>(map)?.finish(),
             #[cfg(feature = "services-moka")]
             Scheme::Moka => Self::from_map::<services::Moka>(map)?.finish(),
             #[cfg(feature = "services-obs")]
@@ -233,6 +296,46 @@ impl Operator {
         Ok(op)
     }
 
+    /// Create a new operator from given scheme and environment variables.
+    ///
+    /// All env vars starting with `{PREFIX}_` will be collected, the prefix
+    /// will be stripped and the rest will be lowercased as config key. For
+    /// example, with prefix `MY_S3`, `MY_S3_BUCKET=test` will be converted
+    /// into `bucket=test`. The built map will be passed to
+    /// [`Operator::via_map`].
+    ///
+    /// Besides, some services also read their provider standard env vars:
+    ///
+    /// - `s3`: `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY`, `AWS_SESSION_TOKEN`,
+    ///   `AWS_REGION` (or `AWS_DEFAULT_REGION`) and `AWS_ENDPOINT_URL`
+    /// - `azblob`: `AZURE_STORAGE_ACCOUNT_NAME` and `AZURE_STORAGE_ACCOUNT_KEY`
+    /// - `cos`: `TENCENTCLOUD_SECRET_ID` and `TENCENTCLOUD_SECRET_KEY`
+    ///
+    /// # Notes
+    ///
+    /// - prefix is case insensitive and could be empty, in which case only
+    ///   provider standard env vars will be used.
+    /// - If both prefixed env and provider standard env are set for the
+    ///   same key, the prefixed one takes precedence.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use opendal::Operator;
+    /// use opendal::Scheme;
+    ///
+    /// # fn test() -> Result<()> {
+    /// // Read `MY_S3_BUCKET`, `MY_S3_ROOT`, ... and `AWS_*` envs.
+    /// let op: Operator = Operator::from_env(Scheme::S3, "MY_S3")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_env(scheme: Scheme, prefix: &str) -> Result<Operator> {
+        let map = env_to_map(scheme, prefix, std::env::vars_os());
+        Self::via_map(scheme, map)
+    }
+
     /// Create a new layer with dynamic dispatch.
     ///
     /// # Notes
@@ -264,9 +367,12 @@ impl Operator {
     /// ```
     #[must_use]
     pub fn layer<L: Layer<FusedAccessor>>(self, layer: L) -> Self {
-        Self::from_inner(Arc::new(
-            TypeEraseLayer.layer(layer.layer(self.into_inner())),
-        ))
+        let mut layers = self.layer_names().to_vec();
+        layers.push(layer_name::<L>());
+        let accessor = Arc::new(TypeEraseLayer.layer(layer.layer(self.inner().clone())));
+
+        // Settings like limit and path normalization are kept as is.
+        self.with_inner(accessor).with_layer_names(layers)
     }
 }
 
@@ -316,6 +422,8 @@ impl Operator {
 /// ```
 pub struct OperatorBuilder<A: Accessor> {
     accessor: A,
+    layers: Vec<&'static str>,
+    default_write: WriteOptions,
 }
 
 impl<A: Accessor> OperatorBuilder<A> {
@@ -323,9 +431,88 @@ impl<A: Accessor> OperatorBuilder<A> {
     #[allow(clippy::new_ret_no_self)]
     pub fn new(accessor: A) -> OperatorBuilder<impl Accessor> {
         // Make sure error context layer has been attached.
-        OperatorBuilder { accessor }
-            .layer(ErrorContextLayer)
-            .layer(CompleteLayer)
+        OperatorBuilder {
+            accessor,
+            layers: Vec::new(),
+            default_write: WriteOptions::default(),
+        }
+        .layer(ErrorContextLayer)
+        .layer(CompleteLayer::default())
+    }
+
+    /// Create a new operator builder that only serves operations natively
+    /// supported by the service.
+    ///
+    /// By default, OpenDAL will emulate some features that the service
+    /// doesn't support natively, like seeking via range reads and listing
+    /// recursively via walking dirs. These emulations could be much more
+    /// expensive than users expected. Operator built by this function will
+    /// return `Unsupported` error instead.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use opendal::services::S3;
+    /// use opendal::Builder;
+    /// use opendal::OperatorBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let mut builder = S3::default();
+    /// builder.bucket("example");
+    ///
+    /// let op = OperatorBuilder::new_native_only(builder.build()?).finish();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_native_only(accessor: A) -> OperatorBuilder<impl Accessor> {
+        OperatorBuilder {
+            accessor,
+            layers: Vec::new(),
+            default_write: WriteOptions::default(),
+        }
+        .layer(ErrorContextLayer)
+        .layer(CompleteLayer::default().with_native_only())
+    }
+
+    /// Create a new operator builder without any implicit layers.
+    ///
+    /// # Warning
+    ///
+    /// **This is an advanced API for benchmarking and profiling only, DO NOT
+    /// use it in production.**
+    ///
+    /// [`OperatorBuilder::new`] always attaches `ErrorContextLayer` and
+    /// `CompleteLayer`, while operator built by this function talks to the
+    /// accessor directly. This is useful to measure the overhead of layers
+    /// against the raw accessor, but the operator will:
+    ///
+    /// - return errors without context like `service` and `path`.
+    /// - skip all capability checks, so unsupported operations or options
+    ///   could be silently ignored.
+    /// - not emulate features like seeking, recursive listing and
+    ///   writing without content length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use opendal::services::Memory;
+    /// use opendal::Builder;
+    /// use opendal::OperatorBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// // Only for benchmarking the raw accessor!
+    /// let op = OperatorBuilder::new_raw(Memory::default().build()?).finish();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_raw(accessor: A) -> OperatorBuilder<A> {
+        OperatorBuilder {
+            accessor,
+            layers: Vec::new(),
+            default_write: WriteOptions::default(),
+        }
     }
 
     /// Create a new layer with static dispatch.
@@ -360,15 +547,328 @@ impl<A: Accessor> OperatorBuilder<A> {
     /// ```
     #[must_use]
     pub fn layer<L: Layer<A>>(self, layer: L) -> OperatorBuilder<L::LayeredAccessor> {
+        let mut layers = self.layers;
+        layers.push(layer_name::<L>());
+
         OperatorBuilder {
             accessor: layer.layer(self.accessor),
+            layers,
+            default_write: self.default_write,
         }
     }
 
+    /// Specify the default options of write operations.
+    ///
+    /// All `write_with` and `writer_with` calls on the built operator will
+    /// inherit these options and can override them. Read
+    /// [`Operator::with_default_write_options`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use opendal::services;
+    /// use opendal::Operator;
+    /// use opendal::WriteOptions;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let op = Operator::new(services::Memory::default())?
+    ///     .default_write_options(WriteOptions::new().with_content_type("text/plain"))
+    ///     .finish();
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn default_write_options(mut self, opts: WriteOptions) -> Self {
+        self.default_write = opts;
+        self
+    }
+
     /// Finish the building to construct an Operator.
     pub fn finish(self) -> Operator {
-        let ob = self.layer(TypeEraseLayer);
+        // TypeEraseLayer only erases the accessor type, so we don't record it.
+        let layers = self.layers;
+        let accessor = TypeEraseLayer.layer(self.accessor);
+
+        Operator::from_inner(Arc::new(accessor) as FusedAccessor)
+            .with_layer_names(layers)
+            .with_default_write_options(self.default_write)
+    }
+}
+
+/// Get the short name of layer type like `LoggingLayer`.
+fn layer_name<L>() -> &'static str {
+    let name = std::any::type_name::<L>();
+    // Strip generic parameters and module path.
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Provider standard env vars of given scheme, in `(env, key)` form.
+///
+/// Env listed earlier wins if several env vars map to the same key.
+fn standard_envs(scheme: Scheme) -> &'static [(&'static str, &'static str)] {
+    match scheme {
+        Scheme::S3 => &[
+            ("AWS_ACCESS_KEY_ID", "access_key_id"),
+            ("AWS_SECRET_ACCESS_KEY", "secret_access_key"),
+            ("AWS_SESSION_TOKEN", "security_token"),
+            ("AWS_REGION", "region"),
+            ("AWS_DEFAULT_REGION", "region"),
+            ("AWS_ENDPOINT_URL", "endpoint"),
+        ],
+        Scheme::Azblob => &[
+            ("AZURE_STORAGE_ACCOUNT_NAME", "account_name"),
+            ("AZURE_STORAGE_ACCOUNT_KEY", "account_key"),
+        ],
+        Scheme::Cos => &[
+            ("TENCENTCLOUD_SECRET_ID", "secret_id"),
+            ("TENCENTCLOUD_SECRET_KEY", "secret_key"),
+        ],
+        _ => &[],
+    }
+}
+
+/// Build config map from env vars for [`Operator::from_env`].
+fn env_to_map(
+    scheme: Scheme,
+    prefix: &str,
+    envs: impl Iterator<Item = (OsString, OsString)>,
+) -> HashMap<String, String> {
+    // Env vars that are not valid unicode can't be config values, skip them
+    // instead of panicking like `std::env::vars()`.
+    let envs: HashMap<String, String> = envs
+        .filter_map(|(k, v)| Some((k.into_string().ok()?, v.into_string().ok()?)))
+        .collect();
+    let mut map = HashMap::new();
+
+    for (env, key) in standard_envs(scheme).iter().rev() {
+        if let Some(v) = envs.get(*env) {
+            map.insert(key.to_string(), v.to_string());
+        }
+    }
+
+    if prefix.is_empty() {
+        return map;
+    }
+
+    let prefix = format!("{}_", prefix.to_uppercase());
+    for (k, v) in envs {
+        if let Some(key) = k.to_uppercase().strip_prefix(&prefix) {
+            if !key.is_empty() {
+                map.insert(key.to_lowercase(), v);
+            }
+        }
+    }
+
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_to_map() {
+        let envs = vec![
+            ("AWS_ACCESS_KEY_ID", "aws_ak"),
+            ("AWS_DEFAULT_REGION", "us-west-1"),
+            ("AWS_REGION", "us-east-1"),
+            ("MY_S3_BUCKET", "test"),
+            ("MY_S3_ACCESS_KEY_ID", "my_ak"),
+            ("my_s3_root", "/path"),
+            ("MY_S3_", "ignored"),
+            ("OTHER_BUCKET", "other"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (OsString::from(k), OsString::from(v)));
+
+        let map = env_to_map(Scheme::S3, "my_s3", envs.clone());
+        assert_eq!(
+            map,
+            HashMap::from([
+                ("access_key_id".to_string(), "my_ak".to_string()),
+                ("region".to_string(), "us-east-1".to_string()),
+                ("bucket".to_string(), "test".to_string()),
+                ("root".to_string(), "/path".to_string()),
+            ])
+        );
+
+        let map = env_to_map(Scheme::S3, "", envs);
+        assert_eq!(
+            map,
+            HashMap::from([
+                ("access_key_id".to_string(), "aws_ak".to_string()),
+                ("region".to_string(), "us-east-1".to_string()),
+            ])
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_env_to_map_skip_non_unicode() {
+        use std::os::unix::ffi::OsStringExt;
+
+        let envs = vec![
+            (OsString::from("MY_S3_BUCKET"), OsString::from("test")),
+            (
+                OsString::from("MY_S3_ROOT"),
+                OsString::from_vec(vec![0x2f, 0xff, 0xfe]),
+            ),
+            (
+                OsString::from_vec(vec![0x4d, 0x59, 0x5f, 0xff]),
+                OsString::from("ignored"),
+            ),
+        ];
+
+        let map = env_to_map(Scheme::S3, "my_s3", envs.into_iter());
+        assert_eq!(
+            map,
+            HashMap::from([("bucket".to_string(), "test".to_string())])
+        );
+    }
+
+    /// Extract keys read by `from_map` in given builder source.
+    fn from_map_keys(src: &str) -> Vec<&str> {
+        let start = src.find("fn from_map(").expect("from_map must exist");
+        let body = &src[start..];
+        let end = body.find("\n    }\n").expect("from_map must end");
+
+        let mut keys: Vec<&str> = body[..end]
+            .split("map.get(\"")
+            .skip(1)
+            .map(|s| &s[..s.find('"').expect("key must be quoted")])
+            .collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    /// `CONFIG_KEYS` is maintained by hand, make sure it doesn't drift
+    /// from the keys `from_map` actually reads.
+    #[test]
+    fn test_config_keys_match_from_map() {
+        macro_rules! assert_config_keys {
+            ($feature:literal, $service:ident, $builder:ident, $file:literal) => {
+                #[cfg(feature = $feature)]
+                {
+                    let mut expected: Vec<&str> = services::$builder::CONFIG_KEYS
+                        .expect("CONFIG_KEYS must be declared")
+                        .to_vec();
+                    expected.sort_unstable();
+                    assert_eq!(
+                        from_map_keys(include_str!(concat!(
+                            "../../services/",
+                            stringify!($service),
+                            "/",
+                            $file
+                        ))),
+                        expected,
+                        "CONFIG_KEYS of {} drifts from from_map",
+                        stringify!($builder)
+                    );
+                }
+            };
+        }
+
+        assert_config_keys!("services-azblob", azblob, Azblob, "backend.rs");
+        assert_config_keys!("services-azdfs", azdfs, Azdfs, "backend.rs");
+        assert_config_keys!("services-cacache", cacache, Cacache, "backend.rs");
+        assert_config_keys!("services-cos", cos, Cos, "backend.rs");
+        assert_config_keys!("services-dashmap", dashmap, Dashmap, "backend.rs");
+        assert_config_keys!("services-dropbox", dropbox, Dropbox, "builder.rs");
+        assert_config_keys!("services-etcd", etcd, Etcd, "backend.rs");
+        assert_config_keys!("services-fs", fs, Fs, "backend.rs");
+        assert_config_keys!("services-ftp", ftp, Ftp, "backend.rs");
+        assert_config_keys!("services-gcs", gcs, Gcs, "backend.rs");
+        assert_config_keys!("services-gdrive", gdrive, Gdrive, "builder.rs");
+        assert_config_keys!("services-ghac", ghac, Ghac, "backend.rs");
+        assert_config_keys!("services-hdfs", hdfs, Hdfs, "backend.rs");
+        assert_config_keys!("services-http", http, Http, "backend.rs");
+        assert_config_keys!("services-ipfs", ipfs, Ipfs, "backend.rs");
+        assert_config_keys!("services-ipmfs", ipmfs, Ipmfs, "builder.rs");
+        assert_config_keys!("services-memcached", memcached, Memcached, "backend.rs");
+        assert_config_keys!("services-memory", memory, Memory, "backend.rs");
+        assert_config_keys!("services-mini-moka", mini_moka, MiniMoka, "backend.rs");
+        assert_config_keys!("services-mock", mock, Mock, "backend.rs");
+        assert_config_keys!("services-moka", moka, Moka, "backend.rs");
+        assert_config_keys!("services-obs", obs, Obs, "backend.rs");
+        assert_config_keys!("services-onedrive", onedrive, Onedrive, "builder.rs");
+        assert_config_keys!("services-oss", oss, Oss, "backend.rs");
+        assert_config_keys!("services-persy", persy, Persy, "backend.rs");
+        assert_config_keys!("services-redb", redb, Redb, "backend.rs");
+        assert_config_keys!("services-redis", redis, Redis, "backend.rs");
+        assert_config_keys!("services-rocksdb", rocksdb, Rocksdb, "backend.rs");
+        assert_config_keys!("services-s3", s3, S3, "backend.rs");
+        assert_config_keys!("services-sftp", sftp, Sftp, "backend.rs");
+        assert_config_keys!("services-sled", sled, Sled, "backend.rs");
+        assert_config_keys!("services-supabase", supabase, Supabase, "backend.rs");
+        assert_config_keys!("services-tikv", tikv, Tikv, "backend.rs");
+        assert_config_keys!(
+            "services-vercel-artifacts",
+            vercel_artifacts,
+            VercelArtifacts,
+            "builder.rs"
+        );
+        assert_config_keys!("services-wasabi", wasabi, Wasabi, "backend.rs");
+        assert_config_keys!("services-webdav", webdav, Webdav, "backend.rs");
+        assert_config_keys!("services-webhdfs", webhdfs, Webhdfs, "backend.rs");
+    }
+
+    #[test]
+    fn test_layer_keeps_settings() {
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .finish()
+            .with_limit(7)
+            .with_path_normalization(true)
+            .layer(LoggingLayer::default());
+
+        assert_eq!(op.limit(), 7);
+        assert!(op.path_normalization());
+    }
+
+    #[test]
+    fn test_default_write_options() {
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .default_write_options(
+                WriteOptions::new()
+                    .with_content_type("text/plain")
+                    .with_chunk(1024)
+                    .with_concurrent(4),
+            )
+            .finish()
+            .layer(LoggingLayer::default());
+
+        let opts = op.default_write_options();
+        assert_eq!(opts.content_type(), Some("text/plain"));
+
+        let args = opts.to_op_write();
+        assert_eq!(args.content_type(), Some("text/plain"));
+        assert_eq!(args.chunk(), Some(1024));
+        assert_eq!(args.concurrent(), 4);
+        assert_eq!(args.content_length(), None);
+        assert!(!args.append());
+
+        let bop = op.blocking();
+        assert_eq!(
+            bop.default_write_options().content_type(),
+            Some("text/plain")
+        );
+    }
+
+    #[test]
+    fn test_new_raw() {
+        let acc = services::Memory::default().build().unwrap();
+        let op = OperatorBuilder::new(acc).finish();
+        assert_eq!(
+            op.layers_debug(),
+            vec!["CompleteLayer", "ErrorContextLayer"]
+        );
 
-        Operator::from_inner(Arc::new(ob.accessor) as FusedAccessor)
+        let acc = services::Memory::default().build().unwrap();
+        let op = OperatorBuilder::new_raw(acc).finish();
+        assert!(op.layers_debug().is_empty());
+        assert_eq!(op.info().scheme(), Scheme::Memory);
     }
 }
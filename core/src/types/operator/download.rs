@@ -0,0 +1,295 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io;
+use std::io::SeekFrom;
+use std::path::Path;
+use std::path::PathBuf;
+
+use tokio::io::AsyncSeekExt;
+use tokio::io::AsyncWriteExt;
+
+use crate::raw::oio::ReadExt;
+use crate::*;
+
+impl Operator {
+    /// Download file at `path` into given local file with resume support.
+    ///
+    /// If the local file already has partial bytes, the download will resume
+    /// from its length via a ranged read. Returns the size of the file.
+    ///
+    /// # Notes
+    ///
+    /// - This function is only available with feature `download` enabled.
+    /// - The etag of remote file will be persisted in `<local>.etag` while
+    ///   downloading and removed after finished.
+    /// - Partial bytes will be kept only if the persisted etag is the same
+    ///   as remote. If service supports `read_with_if_match`, the ranged read
+    ///   will carry the persisted etag so that changed files won't be
+    ///   stitched.
+    /// - The final size of local file will be verified against `stat`.
+    /// - The download will restart from scratch once if the local file is
+    ///   larger than remote, the remote file changed or the final size
+    ///   doesn't match.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let size = op.download_to("path/to/file", "/tmp/file").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_to(&self, path: &str, local: impl AsRef<Path>) -> Result<u64> {
+        let local = local.as_ref();
+
+        match self.download_to_once(path, local, false).await {
+            Ok(Some(size)) => return Ok(size),
+            Ok(None) => {}
+            Err(err) if err.kind() == ErrorKind::ConditionNotMatch => {}
+            Err(err) => return Err(err),
+        }
+
+        match self.download_to_once(path, local, true).await? {
+            Some(size) => Ok(size),
+            None => Err(Error::new(
+                ErrorKind::Unexpected,
+                "downloaded file size doesn't match remote",
+            )
+            .with_operation("Operator::download_to")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", path)
+            .with_context("local", local.to_string_lossy())),
+        }
+    }
+
+    /// Download once, returns `None` if the final size doesn't match.
+    async fn download_to_once(
+        &self,
+        path: &str,
+        local: &Path,
+        restart: bool,
+    ) -> Result<Option<u64>> {
+        let new_local_error = |err: io::Error| {
+            Error::new(ErrorKind::Unexpected, "access local file failed")
+                .with_operation("Operator::download_to")
+                .with_context("path", path)
+                .with_context("local", local.to_string_lossy())
+                .set_source(err)
+        };
+
+        let meta = self.stat(path).await?;
+        let size = meta.content_length();
+        let etag = meta.etag();
+
+        let etag_path = etag_path(local);
+        let persisted = match tokio::fs::read_to_string(&etag_path).await {
+            Ok(v) => Some(v),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+            Err(err) => return Err(new_local_error(err)),
+        };
+
+        let mut f = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(false)
+            .open(local)
+            .await
+            .map_err(new_local_error)?;
+        let mut offset = f.metadata().await.map_err(new_local_error)?.len();
+        // Partial bytes can't be trusted if we don't know which version
+        // they come from.
+        if restart || offset > size || persisted.is_none() || persisted.as_deref() != etag {
+            f.set_len(0).await.map_err(new_local_error)?;
+            offset = 0;
+        }
+        match etag {
+            Some(v) => tokio::fs::write(&etag_path, v)
+                .await
+                .map_err(new_local_error)?,
+            None => remove_if_exists(&etag_path)
+                .await
+                .map_err(new_local_error)?,
+        }
+        f.seek(SeekFrom::Start(offset))
+            .await
+            .map_err(new_local_error)?;
+
+        if offset < size {
+            let mut fut = self.reader_with(path).range(offset..size);
+            if let Some(etag) = etag {
+                if self.info().capability().read_with_if_match {
+                    fut = fut.if_match(etag);
+                }
+            }
+            let mut r = fut.await?;
+
+            // Read chunks via opendal's reader instead of `tokio::io::copy`
+            // so that errors of the ranged read like `ConditionNotMatch`
+            // keep their kinds, only errors of local file are mapped.
+            while let Some(bs) = r.next().await {
+                f.write_all(&bs?).await.map_err(new_local_error)?;
+            }
+            f.flush().await.map_err(new_local_error)?;
+        }
+
+        let len = f.metadata().await.map_err(new_local_error)?.len();
+        if len != size {
+            return Ok(None);
+        }
+        remove_if_exists(&etag_path)
+            .await
+            .map_err(new_local_error)?;
+        Ok(Some(size))
+    }
+}
+
+/// Path of the file that persists the etag of an on-going download.
+fn etag_path(local: &Path) -> PathBuf {
+    let mut p = local.as_os_str().to_owned();
+    p.push(".etag");
+    PathBuf::from(p)
+}
+
+async fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match tokio::fs::remove_file(path).await {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+    use std::task::Context;
+    use std::task::Poll;
+
+    use async_trait::async_trait;
+    use bytes::Bytes;
+
+    use super::*;
+    use crate::raw::*;
+
+    /// MockService changes the file while it's being read for the first time.
+    #[derive(Debug, Default)]
+    struct MockService {
+        reads: Arc<AtomicUsize>,
+    }
+
+    impl MockService {
+        fn version(&self) -> usize {
+            self.reads.load(Ordering::SeqCst).min(1)
+        }
+    }
+
+    #[async_trait]
+    impl Accessor for MockService {
+        type Reader = MockReader;
+        type BlockingReader = ();
+        type Writer = ();
+        type BlockingWriter = ();
+        type Appender = ();
+        type Pager = ();
+        type BlockingPager = ();
+
+        fn info(&self) -> AccessorInfo {
+            let mut am = AccessorInfo::default();
+            am.set_capability(Capability {
+                stat: true,
+                read: true,
+                read_can_next: true,
+                read_with_range: true,
+                read_with_if_match: true,
+                ..Default::default()
+            });
+            am
+        }
+
+        async fn stat(&self, _: &str, _: OpStat) -> Result<RpStat> {
+            let meta = Metadata::new(EntryMode::FILE)
+                .with_content_length(6)
+                .with_etag(format!("v{}", self.version()));
+            Ok(RpStat::new(meta))
+        }
+
+        async fn read(&self, _: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            let chunks = if self.reads.fetch_add(1, Ordering::SeqCst) == 0 {
+                assert_eq!(args.if_match(), Some("v0"));
+                vec![
+                    Ok(Bytes::from("hel")),
+                    Err(Error::new(ErrorKind::ConditionNotMatch, "file changed")),
+                ]
+            } else {
+                assert_eq!(args.if_match(), Some("v1"));
+                vec![Ok(Bytes::from("world!"))]
+            };
+            Ok((RpRead::new(6), MockReader(chunks)))
+        }
+    }
+
+    struct MockReader(Vec<Result<Bytes>>);
+
+    impl oio::Read for MockReader {
+        fn poll_read(&mut self, _: &mut Context<'_>, _: &mut [u8]) -> Poll<Result<usize>> {
+            Poll::Ready(Err(Error::new(
+                ErrorKind::Unsupported,
+                "read is not supported by mock reader",
+            )))
+        }
+
+        fn poll_seek(&mut self, _: &mut Context<'_>, _: SeekFrom) -> Poll<Result<u64>> {
+            Poll::Ready(Err(Error::new(
+                ErrorKind::Unsupported,
+                "seek is not supported by mock reader",
+            )))
+        }
+
+        fn poll_next(&mut self, _: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+            if self.0.is_empty() {
+                return Poll::Ready(None);
+            }
+            Poll::Ready(Some(self.0.remove(0)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_download_to_restart_on_changed() -> Result<()> {
+        let srv = MockService::default();
+        let reads = srv.reads.clone();
+        let op = OperatorBuilder::new(srv).finish();
+
+        let local = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let size = op.download_to("test", &local).await?;
+        assert_eq!(size, 6);
+        assert_eq!(reads.load(Ordering::SeqCst), 2);
+
+        let bs = std::fs::read(&local).expect("read local file must succeed");
+        assert_eq!(bs, b"world!");
+        assert!(
+            !etag_path(&local).exists(),
+            "persisted etag must be removed"
+        );
+
+        std::fs::remove_file(&local).expect("remove local file must succeed");
+        Ok(())
+    }
+}
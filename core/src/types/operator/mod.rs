@@ -30,5 +30,11 @@ pub use builder::OperatorBuilder;
 mod metadata;
 pub use metadata::OperatorInfo;
 
+#[cfg(feature = "download")]
+mod download;
+
+mod sync;
+pub use sync::SyncStatus;
+
 pub mod operator_functions;
 pub mod operator_futures;
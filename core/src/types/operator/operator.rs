@@ -15,21 +15,37 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::ops::RangeBounds;
+use std::sync::Arc;
 use std::time::Duration;
 
 use bytes::Bytes;
+use chrono::DateTime;
+use chrono::Utc;
 use flagset::FlagSet;
+use futures::future;
 use futures::stream;
+use futures::AsyncRead;
 use futures::AsyncReadExt;
+use futures::FutureExt;
 use futures::Stream;
 use futures::StreamExt;
 use futures::TryStreamExt;
+use md5::Digest;
 use tokio::io::ReadBuf;
 
+use super::sync::contains_sync_metakey;
+use super::sync::need_sync;
 use super::BlockingOperator;
+use crate::layers::NormalizeRootLayer;
 use crate::operator_futures::*;
 use crate::raw::*;
+use crate::types::checksum::validate_checksum;
+use crate::types::checksum::Hasher;
+use crate::types::progress::ProgressReader;
+use crate::types::progress::ProgressWriter;
+use crate::types::reader::read_in_chunks;
 use crate::*;
 
 /// Operator is the entry for all public async APIs.
@@ -63,6 +79,33 @@ use crate::*;
 ///     Ok(())
 /// }
 /// ```
+///
+/// # Clone
+///
+/// Cloning an `Operator` is cheap: it only bumps a few `Arc` counters. All
+/// clones share the same accessor and everything inside it, like the
+/// underlying http client with its connection pool and the states of
+/// layers (concurrency limiters, caches, metrics and so on). It's fine to
+/// give every spawned task its own clone:
+///
+/// ```
+/// # use anyhow::Result;
+/// use opendal::Operator;
+///
+/// # async fn test(op: Operator) -> Result<()> {
+/// let mut tasks = Vec::new();
+/// for i in 0..1000 {
+///     let op = op.clone();
+///     tasks.push(tokio::spawn(async move {
+///         op.write(&format!("path/to/file-{i}"), "hello").await
+///     }));
+/// }
+/// for task in tasks {
+///     task.await??;
+/// }
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Clone, Debug)]
 pub struct Operator {
     // accessor is what Operator delegates for
@@ -70,6 +113,15 @@ pub struct Operator {
 
     // limit is usually the maximum size of data that operator will handle in one operation
     limit: usize,
+
+    // path_normalization will resolve `.` and `..` in input path if enabled
+    path_normalization: bool,
+
+    // layers records the names of attached layers from innermost to outermost
+    layers: Arc<Vec<&'static str>>,
+
+    // default_write is the options that all write operations start with
+    default_write: Arc<WriteOptions>,
 }
 
 /// # Operator basic API.
@@ -84,10 +136,32 @@ impl Operator {
             .capability()
             .batch_max_operations
             .unwrap_or(1000);
-        Self { accessor, limit }
+        Self {
+            accessor,
+            limit,
+            path_normalization: false,
+            layers: Arc::new(Vec::new()),
+            default_write: Arc::new(WriteOptions::default()),
+        }
+    }
+
+    /// Replace the accessor while keeping all other settings of this
+    /// operator.
+    pub(super) fn with_inner(mut self, accessor: FusedAccessor) -> Self {
+        self.accessor = accessor;
+        self
+    }
+
+    pub(super) fn with_layer_names(mut self, layers: Vec<&'static str>) -> Self {
+        self.layers = Arc::new(layers);
+        self
+    }
+
+    pub(super) fn layer_names(&self) -> &[&'static str] {
+        &self.layers
     }
 
-    pub(super) fn into_inner(self) -> FusedAccessor {
+    pub(crate) fn into_inner(self) -> FusedAccessor {
         self.accessor
     }
 
@@ -106,6 +180,139 @@ impl Operator {
         op
     }
 
+    /// Check if path normalization is enabled.
+    pub fn path_normalization(&self) -> bool {
+        self.path_normalization
+    }
+
+    /// Specify whether to resolve `.` and `..` in input paths.
+    ///
+    /// All input paths will always be normalized into the canonical form:
+    ///
+    /// - Leading `/` will be trimmed: `/abc/def` => `abc/def`
+    /// - Duplicate `/` will be collapsed: `abc//def` => `abc/def`
+    /// - Path ends with `/` means it's a dir, otherwise it's a file.
+    /// - Empty path means root: `` => `/`
+    ///
+    /// With path normalization enabled, `.` and `..` segments will also be
+    /// resolved before dispatching to services, so that `abc/./def` and
+    /// `abc/xyz/../def` refer to `abc/def` on all services. `..` will never
+    /// go above operator's root. Path ends with `.` or `..` is a dir path.
+    ///
+    /// Default: false
+    pub fn with_path_normalization(&self, enabled: bool) -> Self {
+        let mut op = self.clone();
+        op.path_normalization = enabled;
+        op
+    }
+
+    /// Get the default options of write operations.
+    pub fn default_write_options(&self) -> &WriteOptions {
+        &self.default_write
+    }
+
+    /// Specify the default options of write operations.
+    ///
+    /// `write_with` and `writer_with` will start with these options, and
+    /// options set on them will override the defaults. Read [`WriteOptions`]
+    /// for the options that could be set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use opendal::services;
+    /// use opendal::Operator;
+    /// use opendal::WriteOptions;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let op = Operator::new(services::Memory::default())?
+    ///     .finish()
+    ///     .with_default_write_options(
+    ///         WriteOptions::new()
+    ///             .with_content_type("application/octet-stream")
+    ///             .with_cache_control("no-cache")
+    ///             .with_chunk(16 * 1024 * 1024)
+    ///             .with_concurrent(4),
+    ///     );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_default_write_options(&self, opts: WriteOptions) -> Self {
+        let mut op = self.clone();
+        op.default_write = Arc::new(opts);
+        op
+    }
+
+    /// Create a view of this operator scoped to the given prefix.
+    ///
+    /// This operation is nearly no cost, the returned operator shares the
+    /// same underlying accessor with this one instead of rebuilding it.
+    ///
+    /// All paths of the returned operator are relative to `prefix`, and
+    /// paths returned by `list` will be relative to `prefix` again. Paths
+    /// that escape the prefix via `..` will be rejected with
+    /// `PermissionDenied`, read [`NormalizeRootLayer`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let acme = op.scoped("tenants/acme/");
+    /// // Write into `tenants/acme/config.json` of `op`.
+    /// acme.write("config.json", "{}").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn scoped(&self, prefix: &str) -> Self {
+        self.clone().layer(NormalizeRootLayer::new(prefix))
+    }
+
+    /// Normalize input path into the canonical form.
+    fn normalize(&self, path: &str) -> String {
+        let path = normalize_path(path);
+        if self.path_normalization {
+            resolve_dot_segments(&path)
+        } else {
+            path
+        }
+    }
+
+    /// List the names of layers attached to this operator, from outermost
+    /// to innermost.
+    ///
+    /// Layers attached implicitly are included too: `ErrorContextLayer` and
+    /// `CompleteLayer` are always the innermost layers added by
+    /// [`OperatorBuilder::new`]. This is purely diagnostic, the output format
+    /// could be changed at any time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use opendal::layers::LoggingLayer;
+    /// use opendal::services::Memory;
+    /// use opendal::Operator;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let op = Operator::new(Memory::default())?
+    ///     .layer(LoggingLayer::default())
+    ///     .finish();
+    /// assert_eq!(
+    ///     op.layers_debug(),
+    ///     vec!["LoggingLayer", "CompleteLayer", "ErrorContextLayer"]
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn layers_debug(&self) -> Vec<&'static str> {
+        self.layers.iter().rev().copied().collect()
+    }
+
     /// Get information of underlying accessor.
     ///
     /// # Examples
@@ -128,8 +335,35 @@ impl Operator {
     /// Create a new blocking operator.
     ///
     /// This operation is nearly no cost.
+    ///
+    /// The returned [`BlockingOperator`] shares the same underlying accessor
+    /// (including all layers) with this operator, but only exposes blocking
+    /// APIs. Code that is entirely blocking should hold a `BlockingOperator`
+    /// instead of calling `blocking_xxx` like methods by convention.
+    ///
+    /// # Notes
+    ///
+    /// Not all services support blocking operations, please check
+    /// [`Capability::blocking`] before using it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use opendal::BlockingOperator;
+    /// use opendal::Operator;
+    ///
+    /// # fn test(op: Operator) -> Result<()> {
+    /// let bop: BlockingOperator = op.blocking();
+    /// let bs = bop.read("path/to/file")?;
+    /// # Ok(())
+    /// # }
+    /// ```
     pub fn blocking(&self) -> BlockingOperator {
-        BlockingOperator::from_inner(self.accessor.clone()).with_limit(self.limit)
+        BlockingOperator::from_inner(self.accessor.clone())
+            .with_limit(self.limit)
+            .with_path_normalization(self.path_normalization)
+            .with_default_write_options(self.default_write.as_ref().clone())
     }
 }
 
@@ -226,7 +460,7 @@ impl Operator {
     /// # }
     /// ```
     pub fn stat_with(&self, path: &str) -> FutureStat {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         let fut = FutureStat(OperatorFuture::new(
             self.inner().clone(),
@@ -234,8 +468,25 @@ impl Operator {
             OpStat::default(),
             |inner, path, args| {
                 let fut = async move {
-                    let rp = inner.stat(&path, args).await?;
-                    Ok(rp.into_metadata())
+                    let treat_prefix_as_dir = args.treat_prefix_as_dir();
+
+                    match inner.stat(&path, args).await {
+                        Ok(rp) => Ok(rp.into_metadata()),
+                        Err(err)
+                            if err.kind() == ErrorKind::NotFound
+                                && treat_prefix_as_dir
+                                && !path.ends_with('/')
+                                && inner.info().capability().list =>
+                        {
+                            match has_children(&inner, &format!("{path}/")).await {
+                                Ok(true) => Ok(Metadata::new(EntryMode::DIR)),
+                                Ok(false) => Err(err),
+                                Err(e) if e.kind() == ErrorKind::NotFound => Err(err),
+                                Err(e) => Err(e),
+                            }
+                        }
+                        Err(err) => Err(err),
+                    }
                 };
 
                 Box::pin(fut)
@@ -346,6 +597,52 @@ impl Operator {
         Ok(meta)
     }
 
+    /// Stat given paths concurrently and yield the results as they complete.
+    ///
+    /// At most 16 stat requests will be sent at the same time. The results
+    /// are not in the same order of input, please use the returning path to
+    /// associate them.
+    ///
+    /// # Notes
+    ///
+    /// For services like s3 that returns metadata while listing, it could be
+    /// much cheaper to list the parent dir with [`Operator::list_with`] and
+    /// `metakey` if most of the files under the dir are needed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use futures::StreamExt;
+    /// use opendal::Operator;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let paths = vec!["a".to_string(), "b".to_string()];
+    /// let mut results = op.stat_many(paths);
+    /// while let Some((path, meta)) = results.next().await {
+    ///     println!("{path}: {:?}", meta.map(|m| m.content_length()));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stat_many(
+        &self,
+        paths: Vec<String>,
+    ) -> impl Stream<Item = (String, Result<Metadata>)> + Send + Unpin + 'static {
+        let op = self.clone();
+
+        stream::iter(paths)
+            .map(move |path| {
+                let op = op.clone();
+                async move {
+                    let res = op.stat(&path).await;
+                    (path, res)
+                }
+                .boxed()
+            })
+            .buffer_unordered(16)
+    }
+
     /// Check if this path exists or not.
     ///
     /// # Example
@@ -373,6 +670,56 @@ impl Operator {
         }
     }
 
+    /// Check if this path is a dir or not.
+    ///
+    /// The path will be classified as a dir if:
+    ///
+    /// - `stat` returns a dir, or
+    /// - `path/` has any children, which is how dirs exist on object storage
+    ///   services that don't have dir marker objects.
+    ///
+    /// Returns `false` if the path is a file or doesn't exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use anyhow::Result;
+    /// use opendal::Operator;
+    ///
+    /// #[tokio::main]
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     let _ = op.is_dir("path/to/dir").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn is_dir(&self, path: &str) -> Result<bool> {
+        let path = self.normalize(path);
+
+        match self.stat(&path).await {
+            Ok(meta) if meta.mode().is_dir() => return Ok(true),
+            Ok(meta) if meta.mode().is_file() => return Ok(false),
+            Ok(_) => {}
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+
+        if !self.info().capability().list {
+            return Ok(false);
+        }
+
+        let dir = if path.ends_with('/') {
+            path
+        } else {
+            format!("{path}/")
+        };
+        match has_children(self.inner(), &dir).await {
+            Ok(v) => Ok(v),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
     /// Create a dir at given path.
     ///
     /// # Notes
@@ -383,8 +730,15 @@ impl Operator {
     ///
     /// # Behavior
     ///
-    /// - Create on existing dir will succeed.
+    /// - Create on existing dir will succeed on all services.
     /// - Create dir is always recursive, works like `mkdir -p`
+    /// - On hierarchical services like `fs` and `hdfs`, a real dir will be
+    ///   created.
+    /// - On object storage services like `s3` and `gcs`, a zero-byte marker
+    ///   object with key ending in `/` will be created, which follows the
+    ///   convention of their consoles. Listing its parent will return the
+    ///   path as a dir entry, and the path keeps existing as a dir even if
+    ///   it has no children.
     ///
     /// # Examples
     ///
@@ -399,7 +753,7 @@ impl Operator {
     /// # }
     /// ```
     pub async fn create_dir(&self, path: &str) -> Result<()> {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         if !validate_path(&path, EntryMode::DIR) {
             return Err(Error::new(
@@ -437,6 +791,43 @@ impl Operator {
         self.range_read(path, ..).await
     }
 
+    /// Read the whole path as a stream of [`Bytes`] chunks without copying.
+    ///
+    /// Unlike [`Operator::read`] which copies all data into one contiguous
+    /// buffer, chunks are returned as they are received from the service.
+    /// For HTTP based services like s3, every chunk shares the allocation of
+    /// the HTTP body, so they can be passed to downstream consumers without
+    /// any copy.
+    ///
+    /// # Notes
+    ///
+    /// A copy is unavoidable for services that read data via file or socket
+    /// APIs like `fs`, `hdfs` and `ftp`, data will be copied from the kernel
+    /// into newly allocated chunks.
+    ///
+    /// Chunks are read lazily, so the memory usage is bounded by one chunk
+    /// regardless of the file size. Refer to [`Chunks`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use futures::TryStreamExt;
+    /// use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut chunks = op.read_chunks("path/to/file").await?;
+    /// let mut size = 0;
+    /// while let Some(bs) = chunks.try_next().await? {
+    ///     size += bs.len();
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_chunks(&self, path: &str) -> Result<Chunks> {
+        Ok(self.reader(path).await?.chunks())
+    }
+
     /// Read the whole path into a bytes with extra options.
     ///
     /// This function will allocate a new bytes internally. For more precise memory control or
@@ -454,8 +845,25 @@ impl Operator {
     /// # Ok(())
     /// # }
     /// ```
+    ///
+    /// # Read with metadata
+    ///
+    /// Use [`FutureRead::with_metadata`] to get the [`Metadata`] returned
+    /// along with the content, which saves a `stat` request for services
+    /// like s3 that return all headers in the `GET` response.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let (meta, bs) = op.read_with("path/to/file").with_metadata().await?;
+    /// println!("etag: {:?}, size: {}", meta.etag(), bs.len());
+    /// # Ok(())
+    /// # }
+    /// ```
     pub fn read_with(&self, path: &str) -> FutureRead {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         let fut = FutureRead(OperatorFuture::new(
             self.inner().clone(),
@@ -474,31 +882,90 @@ impl Operator {
                     }
 
                     let br = args.range();
-                    let (rp, mut s) = inner.read(&path, args).await?;
-
-                    let length = rp.into_metadata().content_length() as usize;
-                    let mut buffer = Vec::with_capacity(length);
-
-                    let dst = buffer.spare_capacity_mut();
-                    let mut buf = ReadBuf::uninit(dst);
-
-                    // Safety: the input buffer is created with_capacity(length).
-                    unsafe { buf.assume_init(length) };
+                    // Nothing to read, no need to send a request.
+                    if br.size() == Some(0) {
+                        return Ok((
+                            Metadata::new(EntryMode::FILE).with_content_length(0),
+                            vec![],
+                        ));
+                    }
 
-                    // TODO: use native read api
-                    s.read_exact(buf.initialized_mut()).await.map_err(|err| {
+                    let verify_checksum = args.verify_checksum();
+                    let progress = args.progress().cloned();
+                    let res = match args.chunk() {
+                        Some(chunk) => read_in_chunks(inner.clone(), &path, args, chunk).await,
+                        None => inner.read(&path, args).await,
+                    };
+                    let (rp, s) = match res {
+                        Ok(v) => v,
+                        // Services will return `416 Range Not Satisfiable` while reading
+                        // an empty file from the start, which is not an error for us.
+                        Err(err)
+                            if err.http_status() == Some(416)
+                                && br.offset().unwrap_or_default() == 0 =>
+                        {
+                            return Ok((
+                                Metadata::new(EntryMode::FILE).with_content_length(0),
+                                vec![],
+                            ));
+                        }
+                        Err(err) => return Err(err),
+                    };
+
+                    let mut meta = rp.into_metadata();
+                    let length = meta.content_length_raw();
+                    let mut s = match progress {
+                        Some(cb) => Box::new(ProgressReader::new(s, cb, length)) as oio::Reader,
+                        None => s,
+                    };
+                    let read_err = |err| {
                         Error::new(ErrorKind::Unexpected, "read from storage")
                             .with_operation("range_read")
                             .with_context("service", inner.info().scheme().into_static())
                             .with_context("path", &path)
                             .with_context("range", br.to_string())
                             .set_source(err)
-                    })?;
-
-                    // Safety: read_exact makes sure this buffer has been filled.
-                    unsafe { buffer.set_len(length) }
+                    };
+
+                    let buffer = match length {
+                        Some(length) => {
+                            let length = length as usize;
+                            let mut buffer = Vec::with_capacity(length);
+
+                            let dst = buffer.spare_capacity_mut();
+                            let mut buf = ReadBuf::uninit(dst);
+
+                            // Safety: the input buffer is created with_capacity(length).
+                            unsafe { buf.assume_init(length) };
+
+                            // TODO: use native read api
+                            s.read_exact(buf.initialized_mut())
+                                .await
+                                .map_err(read_err)?;
+
+                            // Safety: read_exact makes sure this buffer has been filled.
+                            unsafe { buffer.set_len(length) }
+                            buffer
+                        }
+                        // Content length is unknown while the body is decoded
+                        // on the fly, read until EOF instead.
+                        None => {
+                            let mut buffer = Vec::new();
+                            s.read_to_end(&mut buffer).await.map_err(read_err)?;
+                            meta.set_content_length(buffer.len() as u64);
+                            buffer
+                        }
+                    };
+
+                    if verify_checksum && br.is_full() {
+                        verify_content_md5(&meta, &buffer).map_err(|err| {
+                            err.with_operation("Operator::read_with")
+                                .with_context("service", inner.info().scheme().into_static())
+                                .with_context("path", &path)
+                        })?;
+                    }
 
-                    Ok(buffer)
+                    Ok((meta, buffer))
                 };
 
                 Box::pin(fut)
@@ -533,6 +1000,35 @@ impl Operator {
         self.read_with(path).range(range).await
     }
 
+    /// Read the whole path into a string.
+    ///
+    /// This function will allocate a new string internally. An error will be
+    /// returned if the content is not valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let s = op.read_to_string("path/to/file").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_to_string(&self, path: &str) -> Result<String> {
+        let bs = self.read(path).await?;
+
+        String::from_utf8(bs).map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "read content is not valid utf-8")
+                .with_operation("Operator::read_to_string")
+                .with_context("service", self.info().scheme().into_static())
+                .with_context("path", path)
+                .with_context("valid_up_to", err.utf8_error().valid_up_to().to_string())
+                .set_source(err)
+        })
+    }
+
     /// Create a new reader which can read the whole path.
     ///
     /// # Examples
@@ -552,6 +1048,28 @@ impl Operator {
         self.reader_with(path).await
     }
 
+    /// Open a file-like handle which combines read, seek and metadata.
+    ///
+    /// Metadata of the returned [`File`] will be fetched lazily and cached,
+    /// read [`File`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut f = op.open("path/to/file").await?;
+    /// let size = f.len().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn open(&self, path: &str) -> Result<File> {
+        let r = self.reader(path).await?;
+        Ok(File::new(self.clone(), path, r))
+    }
+
     /// Create a new reader which can read the specified range.
     ///
     /// # Notes
@@ -590,7 +1108,7 @@ impl Operator {
     /// # }
     /// ```
     pub fn reader_with(&self, path: &str) -> FutureReader {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         let fut = FutureReader(OperatorFuture::new(
             self.inner().clone(),
@@ -669,6 +1187,119 @@ impl Operator {
         self.append_with(path, bs).await
     }
 
+    /// Update the content of path with given function via compare-and-swap.
+    ///
+    /// This is a shortcut of [`Operator::update_with_retries`] which will
+    /// retry at most 3 times.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// use bytes::Bytes;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.update("path/to/counter", |bs| {
+    ///     let n: u64 = String::from_utf8_lossy(&bs).parse().unwrap_or_default();
+    ///     Bytes::from((n + 1).to_string())
+    /// })
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn update(&self, path: &str, f: impl FnMut(Bytes) -> Bytes) -> Result<()> {
+        self.update_with_retries(path, 3, f).await
+    }
+
+    /// Update the content of path with given function via compare-and-swap,
+    /// retrying at most `max_retries` times on conflicts.
+    ///
+    /// The file will be read together with its etag, then the output of `f`
+    /// will be written back with `If-Match` set to that etag. If the file
+    /// has been changed by others in the meantime, the whole cycle will be
+    /// started over, so `f` could be called more than once.
+    ///
+    /// # Notes
+    ///
+    /// - The file must exist, `NotFound` will be returned otherwise.
+    /// - Services must return etag in `stat` and support `write_with_if_match`,
+    ///   `Unsupported` will be returned otherwise.
+    /// - `ConditionNotMatch` will be returned if conflicts still happen
+    ///   after all retries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// use bytes::Bytes;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.update_with_retries("path/to/file", 10, |bs| {
+    ///     let mut bs = bs.to_vec();
+    ///     bs.extend_from_slice(b"hello");
+    ///     Bytes::from(bs)
+    /// })
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn update_with_retries(
+        &self,
+        path: &str,
+        max_retries: usize,
+        mut f: impl FnMut(Bytes) -> Bytes,
+    ) -> Result<()> {
+        let read_with_if_match = self.info().capability().read_with_if_match;
+
+        let mut retries = 0;
+        loop {
+            let meta = self.stat(path).await?;
+            let etag = meta
+                .etag()
+                .ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::Unsupported,
+                        "service doesn't return etag which is required by update",
+                    )
+                    .with_operation("Operator::update")
+                    .with_context("service", self.info().scheme().into_static())
+                    .with_context("path", path)
+                })?
+                .to_string();
+
+            // Make sure the content we read is exactly the one of this etag
+            // if possible, a mismatch will be caught by write anyway.
+            let mut fut = self.read_with(path);
+            if read_with_if_match {
+                fut = fut.if_match(&etag);
+            }
+
+            let res = match fut.await {
+                Ok(bs) => {
+                    let bs = f(Bytes::from(bs));
+                    self.write_with(path, bs).if_match(&etag).await
+                }
+                Err(err) => Err(err),
+            };
+
+            match res {
+                Err(err) if err.kind() == ErrorKind::ConditionNotMatch && retries < max_retries => {
+                    retries += 1;
+                }
+                Err(err) => {
+                    return Err(err
+                        .with_operation("Operator::update")
+                        .with_context("retries", retries.to_string()))
+                }
+                Ok(()) => return Ok(()),
+            }
+        }
+    }
+
     /// Copy a file from `from` to `to`.
     ///
     /// # Notes
@@ -691,41 +1322,85 @@ impl Operator {
     /// # }
     /// ```
     pub async fn copy(&self, from: &str, to: &str) -> Result<()> {
-        let from = normalize_path(from);
+        self.copy_with(from, to).await
+    }
 
-        if !validate_path(&from, EntryMode::FILE) {
-            return Err(
-                Error::new(ErrorKind::IsADirectory, "from path is a directory")
-                    .with_operation("Operator::copy")
-                    .with_context("service", self.info().scheme())
-                    .with_context("from", from),
-            );
-        }
+    /// Copy a file from `from` to `to` with extra options.
+    ///
+    /// The metadata of `from` like `content-type` will be preserved by
+    /// default. Use `metadata_directive(MetadataDirective::Replace)` to
+    /// replace them with the ones given in this request instead.
+    ///
+    /// # Notes
+    ///
+    /// Refer to [`Operator::copy`] for the requirements of `from` and `to`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::MetadataDirective;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.copy_with("path/to/file", "path/to/file2")
+    ///     .metadata_directive(MetadataDirective::Replace)
+    ///     .content_type("text/plain")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn copy_with(&self, from: &str, to: &str) -> FutureCopy {
+        let from = self.normalize(from);
+        let to = self.normalize(to);
 
-        let to = normalize_path(to);
+        let fut = FutureCopy(OperatorFuture::new(
+            self.inner().clone(),
+            from,
+            (OpCopy::new(), to),
+            |inner, from, (args, to)| {
+                let fut = async move {
+                    if !validate_path(&from, EntryMode::FILE) {
+                        return Err(Error::new(
+                            ErrorKind::IsADirectory,
+                            "from path is a directory",
+                        )
+                        .with_operation("Operator::copy")
+                        .with_context("service", inner.info().scheme())
+                        .with_context("from", from));
+                    }
 
-        if !validate_path(&to, EntryMode::FILE) {
-            return Err(
-                Error::new(ErrorKind::IsADirectory, "to path is a directory")
-                    .with_operation("Operator::copy")
-                    .with_context("service", self.info().scheme())
-                    .with_context("to", to),
-            );
-        }
+                    if !validate_path(&to, EntryMode::FILE) {
+                        return Err(
+                            Error::new(ErrorKind::IsADirectory, "to path is a directory")
+                                .with_operation("Operator::copy")
+                                .with_context("service", inner.info().scheme())
+                                .with_context("to", to),
+                        );
+                    }
 
-        if from == to {
-            return Err(
-                Error::new(ErrorKind::IsSameFile, "from and to paths are same")
-                    .with_operation("Operator::copy")
-                    .with_context("service", self.info().scheme())
-                    .with_context("from", from)
-                    .with_context("to", to),
-            );
-        }
+                    if from == to {
+                        return Err(Error::new(
+                            ErrorKind::IsSameFile,
+                            "from and to paths are same",
+                        )
+                        .with_operation("Operator::copy")
+                        .with_context("service", inner.info().scheme())
+                        .with_context("from", from)
+                        .with_context("to", to));
+                    }
 
-        self.inner().copy(&from, &to, OpCopy::new()).await?;
+                    inner.copy(&from, &to, args).await?;
 
-        Ok(())
+                    Ok(())
+                };
+
+                Box::pin(fut)
+            },
+        ));
+
+        fut
     }
 
     /// Rename a file from `from` to `to`.
@@ -749,7 +1424,7 @@ impl Operator {
     /// # }
     /// ```
     pub async fn rename(&self, from: &str, to: &str) -> Result<()> {
-        let from = normalize_path(from);
+        let from = self.normalize(from);
 
         if !validate_path(&from, EntryMode::FILE) {
             return Err(
@@ -760,7 +1435,7 @@ impl Operator {
             );
         }
 
-        let to = normalize_path(to);
+        let to = self.normalize(to);
 
         if !validate_path(&to, EntryMode::FILE) {
             return Err(
@@ -786,6 +1461,157 @@ impl Operator {
         Ok(())
     }
 
+    /// Get tags of given file.
+    ///
+    /// Tags (like object tagging on s3) are different from user metadata:
+    /// they are mainly used by lifecycle and access policies, and can be
+    /// updated without rewriting the file.
+    ///
+    /// Services that don't support tags will return `Unsupported` error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let tags = op.get_tags("path/to/file").await?;
+    /// println!("expire: {:?}", tags.get("expire"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_tags(&self, path: &str) -> Result<HashMap<String, String>> {
+        let path = self.normalize(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "get tags path is a directory")
+                    .with_operation("Operator::get_tags")
+                    .with_context("service", self.info().scheme())
+                    .with_context("path", path),
+            );
+        }
+
+        let rp = self.inner().get_tags(&path, OpGetTags::new()).await?;
+
+        Ok(rp.into_tags())
+    }
+
+    /// Replace tags of given file with the given tags.
+    ///
+    /// Services that don't support tags will return `Unsupported` error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::collections::HashMap;
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let tags = HashMap::from([("expire".to_string(), "30d".to_string())]);
+    /// op.put_tags("path/to/file", tags).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn put_tags(&self, path: &str, tags: HashMap<String, String>) -> Result<()> {
+        let path = self.normalize(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "put tags path is a directory")
+                    .with_operation("Operator::put_tags")
+                    .with_context("service", self.info().scheme())
+                    .with_context("path", path),
+            );
+        }
+
+        self.inner().put_tags(&path, OpPutTags::new(tags)).await?;
+
+        Ok(())
+    }
+
+    /// Get object lock (retention and legal hold) of given file.
+    ///
+    /// Services that don't support object lock will return `Unsupported` error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let lock = op.get_object_lock("path/to/file").await?;
+    /// println!("retention: {:?}", lock.retention());
+    /// println!("legal hold: {:?}", lock.legal_hold());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_object_lock(&self, path: &str) -> Result<ObjectLock> {
+        let path = self.normalize(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(Error::new(
+                ErrorKind::IsADirectory,
+                "get object lock path is a directory",
+            )
+            .with_operation("Operator::get_object_lock")
+            .with_context("service", self.info().scheme())
+            .with_context("path", path));
+        }
+
+        let rp = self
+            .inner()
+            .get_object_lock(&path, OpGetObjectLock::new())
+            .await?;
+
+        Ok(rp.into_object_lock())
+    }
+
+    /// Update object lock (retention and legal hold) of given file.
+    ///
+    /// Only the retention or legal hold that is set in `lock` will be
+    /// updated. Services usually only allow extending the retention of
+    /// files in compliance mode.
+    ///
+    /// Services that don't support object lock will return `Unsupported` error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use chrono::Duration;
+    /// use chrono::Utc;
+    /// use opendal::ObjectLock;
+    /// use opendal::ObjectLockMode;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let lock = ObjectLock::new()
+    ///     .with_retention(ObjectLockMode::Compliance, Utc::now() + Duration::days(365))
+    ///     .with_legal_hold(true);
+    /// op.put_object_lock("path/to/file", lock).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn put_object_lock(&self, path: &str, lock: ObjectLock) -> Result<()> {
+        let path = self.normalize(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(Error::new(
+                ErrorKind::IsADirectory,
+                "put object lock path is a directory",
+            )
+            .with_operation("Operator::put_object_lock")
+            .with_context("service", self.info().scheme())
+            .with_context("path", path));
+        }
+
+        self.inner()
+            .put_object_lock(&path, OpPutObjectLock::new(lock))
+            .await?;
+
+        Ok(())
+    }
+
     /// Write multiple bytes into path.
     ///
     /// Refer to [`Writer`] for more details.
@@ -838,12 +1664,12 @@ impl Operator {
     /// # }
     /// ```
     pub fn writer_with(&self, path: &str) -> FutureWriter {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         let fut = FutureWriter(OperatorFuture::new(
             self.inner().clone(),
             path,
-            OpWrite::default(),
+            self.default_write.to_op_write(),
             |inner, path, args| {
                 let fut = async move {
                     if !validate_path(&path, EntryMode::FILE) {
@@ -889,13 +1715,18 @@ impl Operator {
     /// # }
     /// ```
     pub fn write_with(&self, path: &str, bs: impl Into<Bytes>) -> FutureWrite {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
         let bs = bs.into();
 
         let fut = FutureWrite(OperatorFuture::new(
             self.inner().clone(),
             path,
-            (OpWrite::default().with_content_length(bs.len() as u64), bs),
+            (
+                self.default_write
+                    .to_op_write()
+                    .with_content_length(bs.len() as u64),
+                bs,
+            ),
             |inner, path, (args, bs)| {
                 let fut = async move {
                     if !validate_path(&path, EntryMode::FILE) {
@@ -908,16 +1739,201 @@ impl Operator {
                         .with_context("path", &path));
                     }
 
-                    let (_, mut w) = inner.write(&path, args).await?;
-                    w.write(bs).await?;
-                    w.close().await?;
+                    validate_checksum(Operation::Write, &args)?;
+
+                    let progress = args.progress().cloned();
+                    let total = args.content_length();
+                    let checksum = args.checksum().map(|algo| {
+                        let mut hasher = Hasher::new(algo);
+                        hasher.update(&bs);
+                        hasher
+                    });
+                    let (_, w) = inner.write(&path, args).await?;
+                    let mut w = match progress {
+                        Some(cb) => Box::new(ProgressWriter::new(w, cb, total)) as oio::Writer,
+                        None => w,
+                    };
+                    w.write(bs).await?;
+                    w.close().await?;
+
+                    let mut meta = Metadata::new(EntryMode::FILE);
+                    if let Some(hasher) = checksum {
+                        hasher.finish(&mut meta);
+                    }
+                    Ok(meta)
+                };
+                Box::pin(fut)
+            },
+        ));
+        fut
+    }
+
+    /// Write data from given reader into path.
+    ///
+    /// `write_from` will stream data from reader into storage with only one
+    /// constant in-memory buffer, the whole payload will never be loaded
+    /// into memory.
+    ///
+    /// # Notes
+    ///
+    /// - Services must support `write_without_content_length`, otherwise an
+    ///   `Unsupported` error will be returned.
+    /// - The writer will be aborted if any error happens while reading or
+    ///   writing.
+    /// - The buffer is 256 KiB by default, it can be changed via
+    ///   [`FutureWriteFrom::buffer`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// use futures::io::Cursor;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let r = Cursor::new(vec![0; 4096]);
+    /// op.write_from("path/to/file", r).buffer(1024).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_from<R>(&self, path: &str, r: R) -> FutureWriteFrom<R>
+    where
+        R: AsyncRead + Unpin + Send + 'static,
+    {
+        let path = self.normalize(path);
+
+        let fut = FutureWriteFrom(OperatorFuture::new(
+            self.inner().clone(),
+            path,
+            (self.default_write.to_op_write(), 256 * 1024, r),
+            |inner, path, (args, size, mut r)| {
+                let fut = async move {
+                    if !inner.info().capability().write_without_content_length {
+                        return Err(Error::new(
+                            ErrorKind::Unsupported,
+                            "service doesn't support write without content length",
+                        )
+                        .with_operation("Operator::write_from")
+                        .with_context("service", inner.info().scheme().into_static())
+                        .with_context("path", &path));
+                    }
+                    if !validate_path(&path, EntryMode::FILE) {
+                        return Err(Error::new(
+                            ErrorKind::IsADirectory,
+                            "write path is a directory",
+                        )
+                        .with_operation("Operator::write_from")
+                        .with_context("service", inner.info().scheme().into_static())
+                        .with_context("path", &path));
+                    }
+
+                    let scheme = inner.info().scheme();
+                    let mut w = Writer::create(inner, &path, args).await?;
+
+                    let mut buf = vec![0; size.max(1)];
+                    loop {
+                        let n = match r.read(&mut buf).await {
+                            Ok(n) => n,
+                            Err(err) => {
+                                let _ = w.abort().await;
+                                return Err(Error::new(
+                                    ErrorKind::Unexpected,
+                                    "read from reader failed",
+                                )
+                                .with_operation("Operator::write_from")
+                                .with_context("service", scheme.into_static())
+                                .with_context("path", &path)
+                                .set_source(err));
+                            }
+                        };
+                        if n == 0 {
+                            break;
+                        }
+
+                        if let Err(err) = w.write(Bytes::copy_from_slice(&buf[..n])).await {
+                            let _ = w.abort().await;
+                            return Err(err);
+                        }
+                    }
+
+                    if let Err(err) = w.close().await {
+                        let _ = w.abort().await;
+                        return Err(err);
+                    }
+                    Ok(())
+                };
+                Box::pin(fut)
+            },
+        ));
+
+        fut
+    }
+
+    /// Write data from given stream into path.
+    ///
+    /// `write_stream` will drive the stream into storage chunk by chunk, the
+    /// next chunk will only be polled after the previous one has been
+    /// written. So a slow upload will slow down the producer instead of
+    /// buffering the whole payload in memory.
+    ///
+    /// # Notes
+    ///
+    /// - Services must support `write_without_content_length`, otherwise an
+    ///   `Unsupported` error will be returned.
+    /// - The writer (and the multipart upload if any) will be aborted if the
+    ///   input stream or the underlying write returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// use bytes::Bytes;
+    /// use futures::stream;
+    /// use futures::StreamExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let s = stream::iter(vec![vec![0; 4096], vec![1; 4096]]).map(Ok);
+    /// op.write_stream("path/to/file", s).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_stream<S, T>(&self, path: &str, mut s: S) -> Result<()>
+    where
+        S: Stream<Item = Result<T>> + Unpin,
+        T: Into<Bytes>,
+    {
+        if !self.info().capability().write_without_content_length {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "service doesn't support write without content length",
+            )
+            .with_operation("Operator::write_stream")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", path));
+        }
+
+        let mut w = self.writer(path).await?;
+
+        while let Some(bs) = s.next().await {
+            let res = match bs {
+                Ok(bs) => w.write(bs.into()).await,
+                Err(err) => Err(err
+                    .with_operation("Operator::write_stream")
+                    .with_context("service", self.info().scheme().into_static())
+                    .with_context("path", path)),
+            };
 
-                    Ok(())
-                };
-                Box::pin(fut)
-            },
-        ));
-        fut
+            if let Err(err) = res {
+                let _ = w.abort().await;
+                return Err(err);
+            }
+        }
+
+        w.close().await?;
+        Ok(())
     }
 
     /// Append multiple bytes into path.
@@ -968,7 +1984,7 @@ impl Operator {
     /// # }
     /// ```
     pub fn appender_with(&self, path: &str) -> FutureAppender {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         let fut = FutureAppender(OperatorFuture::new(
             self.inner().clone(),
@@ -1022,7 +2038,7 @@ impl Operator {
     /// # }
     /// ```
     pub fn append_with(&self, path: &str, bs: impl Into<Bytes>) -> FutureAppend {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
         let bs = bs.into();
 
         let fut = FutureAppend(OperatorFuture::new(
@@ -1082,6 +2098,21 @@ impl Operator {
     ///
     /// - Deleting a file that does not exist won't return errors.
     ///
+    /// # Recursive
+    ///
+    /// With `recursive(true)`, all entries under the given path will be
+    /// listed and deleted, then the path itself:
+    ///
+    /// - Services that support batch will delete entries in batch.
+    /// - Other services will delete files concurrently, and dirs after
+    ///   all their children have been deleted.
+    /// - Failures won't stop the delete. All failed paths will be
+    ///   collected and returned in one error.
+    ///
+    /// Recursive delete on root (`/` or empty path) will remove everything
+    /// of this operator, so it will return [`ErrorKind::InvalidInput`]
+    /// unless `allow_root(true)` is set.
+    ///
     /// # Examples
     ///
     /// ```
@@ -1092,18 +2123,26 @@ impl Operator {
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
     /// op.delete_with("test").await?;
+    /// op.delete_with("path/to/dir/").recursive(true).await?;
     /// # Ok(())
     /// # }
     /// ```
     pub fn delete_with(&self, path: &str) -> FutureDelete {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         let fut = FutureDelete(OperatorFuture::new(
             self.inner().clone(),
             path,
-            OpDelete::default(),
-            |inner, path, args| {
+            (OpDelete::default(), self.limit),
+            |inner, path, (args, limit)| {
                 let fut = async move {
+                    if args.recursive() {
+                        return Operator::from_inner(inner)
+                            .with_limit(limit)
+                            .delete_recursive(&path, args.allow_root())
+                            .await;
+                    }
+
                     let _ = inner.delete(&path, args).await?;
                     Ok(())
                 };
@@ -1196,12 +2235,39 @@ impl Operator {
         Ok(())
     }
 
+    /// Refuse recursive delete on root unless allowed, then reuse
+    /// [`Operator::remove_all`].
+    async fn delete_recursive(&self, path: &str, allow_root: bool) -> Result<()> {
+        if path == "/" && !allow_root {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "recursive delete on root is refused, set allow_root to confirm",
+            )
+            .with_operation("Operator::delete_with")
+            .with_context("service", self.info().scheme())
+            .with_context("path", path));
+        }
+
+        self.remove_all(path).await
+    }
+
     /// Remove the path and all nested dirs and files recursively.
     ///
     /// # Notes
     ///
     /// If underlying services support delete in batch, we will use batch
-    /// delete instead.
+    /// delete instead. Otherwise, files will be deleted concurrently, and
+    /// dirs after all their children have been deleted.
+    ///
+    /// Failures won't stop the remove. All failed paths will be collected
+    /// and returned in one error.
+    ///
+    /// The root dir itself won't be deleted.
+    ///
+    /// On services with versioning enabled, only the latest versions are
+    /// deleted and noncurrent versions are kept. Use
+    /// [`Operator::list_with`] with `versions(true)` and `deleted(true)`
+    /// to find them and delete them one by one via `delete_with(..).version(..)`.
     ///
     /// # Examples
     ///
@@ -1217,53 +2283,237 @@ impl Operator {
     /// # }
     /// ```
     pub async fn remove_all(&self, path: &str) -> Result<()> {
-        let meta = match self.stat(path).await {
-            // If object exists.
-            Ok(metadata) => metadata,
+        let path = self.normalize(path);
+        let path = path.as_str();
 
-            // If object not found, return success.
+        let meta = match self.stat(path).await {
+            Ok(meta) => meta,
             Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
-
-            // Pass on any other error.
             Err(e) => return Err(e),
         };
-
-        if meta.mode() != EntryMode::DIR {
-            return self.delete(path).await;
+        if !meta.is_dir() {
+            let _ = self.inner().delete(path, OpDelete::new()).await?;
+            return Ok(());
         }
 
-        let obs = self.scan(path).await?;
+        let mut failed: Vec<(String, Error)> = Vec::new();
+        let mut entries = self.scan(path).await?.try_chunks(self.limit());
 
-        if self.info().can_batch() {
-            let mut obs = obs.try_chunks(self.limit());
+        while let Some(chunk) = entries.next().await {
+            let chunk = chunk.map_err(|err| err.1)?;
 
-            while let Some(batches) = obs.next().await {
-                let batches = batches
-                    .map_err(|err| err.1)?
+            if self.info().can_batch() {
+                let batches = chunk
                     .into_iter()
-                    .map(|v| (v.path().to_string(), OpDelete::default().into()))
+                    .map(|v| (v.path().to_string(), OpDelete::new().into()))
                     .collect();
-
                 let results = self
                     .inner()
                     .batch(OpBatch::new(batches))
                     .await?
                     .into_results();
+                for (path, result) in results {
+                    if let Err(err) = result {
+                        failed.push((path, err));
+                    }
+                }
+                continue;
+            }
 
-                // TODO: return error here directly seems not a good idea?
-                for (_, result) in results {
-                    let _ = result?;
+            // Dirs always show up after their children while scanning, so
+            // we delete files concurrently first, and then dirs in order.
+            let (dirs, files): (Vec<_>, Vec<_>) =
+                chunk.into_iter().partition(|v| v.path().ends_with('/'));
+            let results: Vec<_> = stream::iter(files)
+                .map(|v| async move {
+                    let res = self.inner().delete(v.path(), OpDelete::new()).await;
+                    (v.path().to_string(), res)
+                })
+                .buffer_unordered(self.limit())
+                .collect()
+                .await;
+            for (path, result) in results {
+                if let Err(err) = result {
+                    failed.push((path, err));
+                }
+            }
+            for v in dirs {
+                if let Err(err) = self.inner().delete(v.path(), OpDelete::new()).await {
+                    failed.push((v.path().to_string(), err));
                 }
             }
+        }
+
+        if failed.is_empty() {
+            // Remove the directory itself.
+            if path != "/" {
+                let _ = self.inner().delete(path, OpDelete::new()).await?;
+            }
+            return Ok(());
+        }
+
+        let paths = failed
+            .iter()
+            .map(|(p, _)| p.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+        let (_, source) = failed.swap_remove(0);
+        Err(
+            Error::new(ErrorKind::Unexpected, "remove all failed partially")
+                .with_operation("Operator::remove_all")
+                .with_context("service", self.info().scheme())
+                .with_context("path", path)
+                .with_context("failed", paths)
+                .set_source(source),
+        )
+    }
+
+    /// Sync all files under `from` of this operator into `to` of `dst`.
+    ///
+    /// Every file under `from` will be copied to the same relative path
+    /// under `to`, files that are not changed will be skipped:
+    ///
+    /// - If both sides have a content md5, skip if they are the same. Etags
+    ///   are never compared since they are not always the md5 of content.
+    /// - Otherwise, skip if content length is the same and destination is
+    ///   not older than source.
+    ///
+    /// # Notes
+    ///
+    /// - Both `from` and `to` must be dirs which end with `/`.
+    /// - Content will be streamed from source to destination without
+    ///   buffering the whole object.
+    /// - At most 16 objects will be synced concurrently.
+    /// - Files that only exist in destination will not be removed.
+    ///
+    /// Returns the sync result of every file under `from`. Failure of one
+    /// file won't stop syncing others, only list errors will be returned
+    /// directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(src: Operator, dst: Operator) -> Result<()> {
+    /// let results = src.sync_to(&dst, "path/to/src/", "path/to/dst/").await?;
+    /// for (path, res) in results {
+    ///     println!("{path}: {:?}", res?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn sync_to(
+        &self,
+        dst: &Operator,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<(String, Result<SyncStatus>)>> {
+        let from = self.normalize(from);
+        let to = dst.normalize(to);
+        for (path, op) in [(&from, self), (&to, dst)] {
+            if !validate_path(path, EntryMode::DIR) {
+                return Err(
+                    Error::new(ErrorKind::NotADirectory, "sync path is not a directory")
+                        .with_operation("Operator::sync_to")
+                        .with_context("service", op.info().scheme().into_static())
+                        .with_context("path", path),
+                );
+            }
+        }
+
+        // Root is normalized as `/`, but entries never start with `/`.
+        let from_prefix = from.trim_start_matches('/');
+        let to_prefix = to.trim_start_matches('/');
+
+        self.scan(&from)
+            .await?
+            .map_ok(|entry| async move {
+                let meta = self
+                    .metadata(
+                        &entry,
+                        Metakey::Mode
+                            | Metakey::ContentLength
+                            | Metakey::LastModified
+                            | Metakey::ContentMd5,
+                    )
+                    .await;
+                let meta = match meta {
+                    Ok(meta) if !meta.mode().is_file() => return Ok(None),
+                    Ok(meta) => meta,
+                    Err(err) => return Ok(Some((entry.path().to_string(), Err(err)))),
+                };
+
+                let rel = entry
+                    .path()
+                    .strip_prefix(from_prefix)
+                    .unwrap_or(entry.path());
+                let target = format!("{to_prefix}{rel}");
+                let res = self.sync_file(dst, entry.path(), &meta, &target).await;
+                Ok::<_, Error>(Some((entry.path().to_string(), res)))
+            })
+            .try_buffer_unordered(16)
+            .try_filter_map(|v| future::ready(Ok(v)))
+            .try_collect()
+            .await
+    }
+
+    /// Sync file at `path` with given metadata into `target` of `dst`.
+    async fn sync_file(
+        &self,
+        dst: &Operator,
+        path: &str,
+        meta: &Metadata,
+        target: &str,
+    ) -> Result<SyncStatus> {
+        let stated;
+        let meta = if contains_sync_metakey(meta) {
+            meta
         } else {
-            obs.try_for_each(|v| async move { self.delete(v.path()).await })
-                .await?;
+            stated = self.stat(path).await?;
+            &stated
+        };
+
+        match dst.stat(target).await {
+            Ok(dst_meta) if !need_sync(meta, &dst_meta) => return Ok(SyncStatus::Skipped),
+            Ok(_) => {}
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
         }
 
-        // Remove the directory itself.
-        self.delete(path).await?;
+        let size = meta.content_length();
+        let mut r = self.reader(path).await?.map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "read from source")
+                .with_operation("Operator::sync_to")
+                .set_source(err)
+        });
 
-        Ok(())
+        let can_sink = dst.info().capability().write_can_sink;
+        let mut w = if can_sink {
+            dst.writer_with(target).content_length(size).await?
+        } else {
+            dst.writer(target).await?
+        };
+        let res = if can_sink {
+            w.sink(size, r).await
+        } else {
+            // Fallback to write chunks one by one.
+            async {
+                while let Some(bs) = r.try_next().await? {
+                    w.write(bs).await?;
+                }
+                Ok(())
+            }
+            .await
+        };
+        if let Err(err) = res {
+            let _ = w.abort().await;
+            return Err(err);
+        }
+        w.close().await?;
+
+        Ok(SyncStatus::Copied)
     }
 
     /// List given path.
@@ -1343,6 +2593,145 @@ impl Operator {
     /// # }
     /// ```
     ///
+    /// ## List the first few entries
+    ///
+    /// `max_entries` caps the total count of returned entries. Listing stops
+    /// as soon as the count is reached, so grabbing a handful of entries from
+    /// a huge prefix only costs one request on services like s3. It works
+    /// together with `delimiter("")`, `start_after` and `glob`.
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use futures::TryStreamExt;
+    /// use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let entries: Vec<_> = op
+    ///     .list_with("path/to/dir/")
+    ///     .delimiter("")
+    ///     .max_entries(5)
+    ///     .await?
+    ///     .try_collect()
+    ///     .await?;
+    /// assert!(entries.len() <= 5);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## List entries in lexicographic order
+    ///
+    /// Some services return entries in arbitrary order. By enabling `sorted`,
+    /// entries will be returned in lexicographic order of their paths.
+    ///
+    /// - If service returns pages in order (like s3), only entries within
+    ///   one page will be buffered and sorted.
+    /// - Otherwise, the whole listing will be buffered in memory and sorted
+    ///   before returning the first entry, so memory usage grows with the
+    ///   number of entries under the dir. Avoid `sorted` while listing a
+    ///   huge dir on such services.
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use futures::TryStreamExt;
+    /// use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut ds = op.list_with("path/to/dir/").sorted(true).await?;
+    /// while let Some(de) = ds.try_next().await? {
+    ///     println!("{}", de.path());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## List entries with metadata
+    ///
+    /// Services like s3 return content length and last modified in list
+    /// response, they will be cached in returned entries. Services like fs
+    /// will fetch them only if `metadata` is enabled. So we can query them
+    /// via [`Operator::metadata`] without sending an extra `stat`.
+    ///
+    /// Services that don't have `list_with_metadata` capability still
+    /// require a `stat` to get them.
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use futures::TryStreamExt;
+    /// use opendal::Metakey;
+    /// use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut ds = op.list_with("path/to/dir/").metadata(true).await?;
+    /// while let Some(de) = ds.try_next().await? {
+    ///     let meta = op
+    ///         .metadata(&de, Metakey::Mode | Metakey::ContentLength)
+    ///         .await?;
+    ///     println!("{} {}", de.path(), meta.content_length());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## List entries matching a glob pattern
+    ///
+    /// The glob pattern is relative to the listing path:
+    ///
+    /// - `*` matches any sequence of characters within one segment.
+    /// - `?` matches any single character within one segment.
+    /// - `**` as a whole segment matches zero or more segments.
+    ///
+    /// Only the dir segments **before the first segment containing a
+    /// wildcard** will be pushed down to services as the listing path. For
+    /// example, `logs/2023-*/**/*.json` lists `logs/`, not `logs/2023-`. All
+    /// remaining parts are matched at client side while streaming.
+    ///
+    /// The listing will be recursive if the remaining parts contain more than
+    /// one segment. So a leading wildcard like `**/*.json` will scan the
+    /// whole dir recursively, while `*.json` only lists the current dir.
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use futures::TryStreamExt;
+    /// use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut ds = op.list_with("/").glob("logs/2023-*/**/*.json").await?;
+    /// while let Some(de) = ds.try_next().await? {
+    ///     println!("{}", de.path());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## List common prefixes only
+    ///
+    /// With the default delimiter `/`, a list returns both the files and the
+    /// common prefixes (dirs whose [`Entry::mode`] is `DIR`) right under the
+    /// path, just like `Contents` and `CommonPrefixes` of s3. By enabling
+    /// `prefixes_only`, files will be skipped so that a file browser can
+    /// expand dirs lazily.
+    ///
+    /// Files are filtered at client side, services like s3 still return
+    /// them in the response.
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use futures::TryStreamExt;
+    /// use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut ds = op
+    ///     .list_with("path/to/dir/")
+    ///     .delimiter("/")
+    ///     .prefixes_only(true)
+    ///     .await?;
+    /// while let Some(de) = ds.try_next().await? {
+    ///     println!("dir: {}", de.path());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
     /// ## List all files recursively
     ///
     /// We can use `op.scan()` as a shorter alias.
@@ -1372,8 +2761,41 @@ impl Operator {
     /// # Ok(())
     /// # }
     /// ```
+    ///
+    /// ## List all versions recursively
+    ///
+    /// With `versions(true)`, every version of files will be returned as an
+    /// entry, and the version id can be got via [`Metadata::version`].
+    /// Versions of the same path are returned together, from the latest to
+    /// the oldest. Delete markers are not returned unless `deleted(true)` is
+    /// set, and they can be checked via [`Metadata::is_deleted`].
+    ///
+    /// It composes with `recursive`, `limit` and `continuation_token`. Only
+    /// one page is kept in memory, so it's fine to list millions of versions.
+    /// Continuation tokens of version listing are different from the normal
+    /// ones, please don't mix them up.
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use futures::TryStreamExt;
+    /// use opendal::Metakey;
+    /// use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut ds = op
+    ///     .list_with("path/to/dir/")
+    ///     .versions(true)
+    ///     .recursive(true)
+    ///     .await?;
+    /// while let Some(de) = ds.try_next().await? {
+    ///     let meta = op.metadata(&de, Metakey::Version).await?;
+    ///     println!("{} {:?}", de.path(), meta.version());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
     pub fn list_with(&self, path: &str) -> FutureList {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         let fut = FutureList(OperatorFuture::new(
             self.inner().clone(),
@@ -1391,8 +2813,71 @@ impl Operator {
                         .with_context("path", &path));
                     }
 
+                    // Push the literal prefix of glob pattern down to services
+                    // and list recursively if pattern could match nested entries.
+                    let glob = args.glob().map(|v| {
+                        let base = if path == "/" { "" } else { path.as_str() };
+                        format!("{base}{}", v.trim_start_matches('/'))
+                    });
+                    let (path, args) = match &glob {
+                        Some(pattern) => {
+                            let prefix = get_glob_prefix(pattern);
+                            let rest = &pattern[prefix.len()..];
+                            let path = if prefix.is_empty() { "/" } else { prefix };
+                            if rest.contains('/') || rest.contains("**") {
+                                (path.to_string(), args.with_delimiter(""))
+                            } else {
+                                (path.to_string(), args)
+                            }
+                        }
+                        None => (path, args),
+                    };
+
+                    // Shrink the page size to max_entries so that services
+                    // like s3 can return the wanted entries in one request.
+                    // Glob and prefixes_only filter entries at client side,
+                    // so we keep the page size as is.
+                    let max_entries = args.max_entries();
+                    let prefixes_only = args.prefixes_only();
+                    let args = match max_entries {
+                        Some(n)
+                            if n > 0
+                                && glob.is_none()
+                                && !prefixes_only
+                                && inner.info().capability().list_with_limit
+                                && args.limit().map_or(true, |v| v > n) =>
+                        {
+                            args.with_limit(n)
+                        }
+                        _ => args,
+                    };
+
+                    let sorted = args.sorted();
                     let (_, pager) = inner.list(&path, args).await?;
 
+                    let pager: oio::Pager = match glob {
+                        Some(pattern) => Box::new(oio::into_glob_page(pager, &pattern)),
+                        None => pager,
+                    };
+
+                    let pager: oio::Pager = if prefixes_only {
+                        Box::new(oio::into_prefixes_page(pager))
+                    } else {
+                        pager
+                    };
+
+                    let pager: oio::Pager = if sorted {
+                        let ordered_pages = inner.info().capability().list_with_sorted;
+                        Box::new(oio::into_sorted_page(pager, ordered_pages))
+                    } else {
+                        pager
+                    };
+
+                    let pager: oio::Pager = match max_entries {
+                        Some(n) => Box::new(oio::into_limit_page(pager, n)),
+                        None => pager,
+                    };
+
                     Ok(Lister::new(pager))
                 };
                 Box::pin(fut)
@@ -1443,6 +2928,65 @@ impl Operator {
     pub async fn scan(&self, path: &str) -> Result<Lister> {
         self.list_with(path).delimiter("").await
     }
+
+    /// List all files under given path recursively that modified after
+    /// `since`.
+    ///
+    /// Returned entries always carry their last modified, so querying it via
+    /// [`Operator::metadata`] won't send extra requests. It can be used to
+    /// record the watermark of next incremental scan.
+    ///
+    /// # Notes
+    ///
+    /// No service can filter entries by last modified natively, so the whole
+    /// dir will be listed and filtered at client side. Services that have
+    /// `list_with_metadata` capability return last modified in listing
+    /// directly, others require an extra `stat` for every file.
+    ///
+    /// Services that don't track last modified of files (like memory) will
+    /// return an `Unsupported` error while listing.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use chrono::Duration;
+    /// use chrono::Utc;
+    /// use futures::TryStreamExt;
+    /// use opendal::Metakey;
+    /// use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let since = Utc::now() - Duration::hours(1);
+    /// let mut ds = op.last_modified_since("path/to/dir/", since).await?;
+    /// while let Some(de) = ds.try_next().await? {
+    ///     let meta = op.metadata(&de, Metakey::LastModified).await?;
+    ///     println!("{} {:?}", de.path(), meta.last_modified());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn last_modified_since(&self, path: &str, since: DateTime<Utc>) -> Result<Lister> {
+        let path = self.normalize(path);
+
+        if !validate_path(&path, EntryMode::DIR) {
+            return Err(Error::new(
+                ErrorKind::NotADirectory,
+                "the path trying to list should end with `/`",
+            )
+            .with_operation("Operator::last_modified_since")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
+        let (_, pager) = self
+            .inner()
+            .list(&path, OpList::new().with_delimiter("").with_metadata(true))
+            .await?;
+        let pager = oio::into_modified_since_page(self.inner().clone(), pager, since);
+
+        Ok(Lister::new(Box::new(pager)))
+    }
 }
 /// Operator presign API.
 impl Operator {
@@ -1468,7 +3012,7 @@ impl Operator {
     /// # }
     /// ```
     pub async fn presign_stat(&self, path: &str, expire: Duration) -> Result<PresignedRequest> {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         let op = OpPresign::new(OpStat::new(), expire);
 
@@ -1503,7 +3047,7 @@ impl Operator {
     /// curl "https://s3.amazonaws.com/examplebucket/test.txt?X-Amz-Algorithm=AWS4-HMAC-SHA256&X-Amz-Credential=access_key_id/20130721/us-east-1/s3/aws4_request&X-Amz-Date=20130721T201207Z&X-Amz-Expires=86400&X-Amz-SignedHeaders=host&X-Amz-Signature=<signature-value>" -O /tmp/test.txt
     /// ```
     pub async fn presign_read(&self, path: &str, expire: Duration) -> Result<PresignedRequest> {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         let op = OpPresign::new(OpRead::new(), expire);
 
@@ -1533,7 +3077,7 @@ impl Operator {
     /// # }
     /// ```
     pub fn presign_read_with(&self, path: &str, expire: Duration) -> FuturePresignRead {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         let fut = FuturePresignRead(OperatorFuture::new(
             self.inner().clone(),
@@ -1606,7 +3150,7 @@ impl Operator {
     /// # }
     /// ```
     pub fn presign_write_with(&self, path: &str, expire: Duration) -> FuturePresignWrite {
-        let path = normalize_path(path);
+        let path = self.normalize(path);
 
         let fut = FuturePresignWrite(OperatorFuture::new(
             self.inner().clone(),
@@ -1624,3 +3168,124 @@ impl Operator {
         fut
     }
 }
+
+/// Verify content against the md5 carried by metadata.
+///
+/// Only content md5 is used, etag is never trusted as md5 here since it's
+/// not md5 for multipart uploaded or SSE-KMS / SSE-C encrypted objects.
+/// Services set content md5 only while they can guarantee it. Nothing will
+/// be checked if no md5 is available.
+fn verify_content_md5(meta: &Metadata, bs: &[u8]) -> Result<()> {
+    let bit = meta.bit();
+    if !bit.contains(Metakey::ContentMd5) && !bit.contains(Metakey::Complete) {
+        return Ok(());
+    }
+    let expected = match meta.content_md5().and_then(normalize_md5) {
+        Some(v) => v,
+        None => return Ok(()),
+    };
+
+    let mut hasher = md5::Md5::new();
+    hasher.update(bs);
+    let actual = format!("{:x}", hasher.finalize());
+
+    if actual != expected {
+        return Err(Error::new(ErrorKind::Unexpected, "content md5 mismatch")
+            .with_context("expected", expected)
+            .with_context("actual", actual)
+            .set_permanent());
+    }
+
+    Ok(())
+}
+
+/// Check if given dir has any child by listing it.
+pub(crate) async fn has_children(inner: &FusedAccessor, dir: &str) -> Result<bool> {
+    use oio::Page;
+
+    let (_, mut pager) = inner.list(dir, OpList::new().with_limit(1)).await?;
+    while let Some(entries) = pager.next().await? {
+        if !entries.is_empty() {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Check if given dir has any child by listing it in blocking way.
+pub(crate) fn blocking_has_children(inner: &FusedAccessor, dir: &str) -> Result<bool> {
+    use oio::BlockingPage;
+
+    let (_, mut pager) = inner.blocking_list(dir, OpList::new().with_limit(1))?;
+    while let Some(entries) = pager.next()? {
+        if !entries.is_empty() {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::task::Context;
+    use std::task::Poll;
+
+    use futures::task::noop_waker;
+
+    use super::*;
+    use crate::layers::ConcurrentLimitLayer;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_scoped() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        let acme = op.scoped("tenants/acme");
+
+        acme.write("dir/file", "hello").await?;
+        assert_eq!(op.read("tenants/acme/dir/file").await?, b"hello");
+        assert_eq!(acme.read("/dir/file").await?, b"hello");
+
+        let entries: Vec<_> = acme.list("dir/").await?.try_collect().await?;
+        let paths: Vec<_> = entries.iter().map(|e| e.path()).collect();
+        assert_eq!(paths, vec!["dir/file"]);
+
+        let err = acme.read("../other/file").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        // Scoped operators can be nested.
+        let nested = acme.scoped("dir/");
+        assert_eq!(nested.read("file").await?, b"hello");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_clone_shares_state() -> Result<()> {
+        let op = Operator::new(Memory::default())?
+            .layer(ConcurrentLimitLayer::new(1))
+            .finish();
+        let cloned = op.clone();
+
+        op.write("file", "hello").await?;
+
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        // Hold the only permit by a reader created from the original one.
+        let r = op.reader("file").await?;
+        // The clone shares the same limiter, so it has to wait no matter
+        // how many times it's polled.
+        let mut fut = Box::pin(cloned.reader("file"));
+        for _ in 0..16 {
+            assert!(fut.poll_unpin(&mut cx).is_pending());
+        }
+
+        // Release the permit, the clone can go on now.
+        drop(r);
+        let res = (0..16).find_map(|_| match fut.poll_unpin(&mut cx) {
+            Poll::Ready(v) => Some(v),
+            Poll::Pending => None,
+        });
+        assert!(res.expect("reader must be ready").is_ok());
+        Ok(())
+    }
+}
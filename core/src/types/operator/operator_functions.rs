@@ -19,6 +19,8 @@
 //!
 //! By using functions, users can add more options for operation.
 
+use std::ops::RangeBounds;
+
 use bytes::Bytes;
 
 use crate::raw::*;
@@ -63,6 +65,28 @@ impl<T, R> OperatorFunction<T, R> {
     }
 }
 
+/// Function that generated by [`BlockingOperator::stat_with`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub struct FunctionStat(pub(crate) OperatorFunction<OpStat, Metadata>);
+
+impl FunctionStat {
+    /// Treat a non-empty prefix as a dir.
+    ///
+    /// Refer to [`FutureStat::treat_prefix_as_dir`](crate::operator_futures::FutureStat::treat_prefix_as_dir)
+    /// for more details.
+    pub fn treat_prefix_as_dir(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_treat_prefix_as_dir(v));
+        self
+    }
+
+    /// Call the function to consume all the input and generate a
+    /// result.
+    pub fn call(self) -> Result<Metadata> {
+        self.0.call()
+    }
+}
+
 /// Function that generated by [`BlockingOperator::write_with`].
 ///
 /// Users can add more options by public functions provided by this struct.
@@ -89,6 +113,23 @@ impl FunctionWrite {
         self
     }
 
+    /// Overwrite the given range of an existing file in place.
+    ///
+    /// Only services that support `write_with_range` (like fs) accept this
+    /// option, others will return an `Unsupported` error.
+    pub fn range(mut self, range: impl RangeBounds<u64>) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_range(range.into()), bs));
+        self
+    }
+
+    /// Set the If-Match for this operation.
+    pub fn if_match(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|(args, bs)| (args.with_if_match(v), bs));
+        self
+    }
+
     /// Call the function to consume all the input and generate a
     /// result.
     pub fn call(self) -> Result<()> {
@@ -96,6 +137,33 @@ impl FunctionWrite {
     }
 }
 
+/// Function that generated by [`BlockingOperator::list_with`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub struct FunctionList(pub(crate) OperatorFunction<OpList, BlockingLister>);
+
+impl FunctionList {
+    /// Change the delimiter. The default delimiter is "/"
+    pub fn delimiter(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_delimiter(v));
+        self
+    }
+
+    /// Make sure entries are returned in lexicographic order.
+    ///
+    /// Refer to [`BlockingOperator::list_with`] for more details.
+    pub fn sorted(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_sorted(v));
+        self
+    }
+
+    /// Call the function to consume all the input and generate a
+    /// result.
+    pub fn call(self) -> Result<BlockingLister> {
+        self.0.call()
+    }
+}
+
 /// Function that generated by [`BlockingOperator::delete_with`].
 ///
 /// Users can add more options by public functions provided by this struct.
@@ -25,8 +25,12 @@ use std::pin::Pin;
 use std::task::Context;
 use std::task::Poll;
 use std::time::Duration;
+use std::time::Instant;
 
 use bytes::Bytes;
+use chrono::DateTime;
+use chrono::Utc;
+use flagset::FlagSet;
 use futures::future::BoxFuture;
 use futures::Future;
 use futures::FutureExt;
@@ -48,9 +52,11 @@ pub(crate) enum OperatorFuture<T, F> {
         T,
         /// The function which will move all the args and return a static future
         fn(FusedAccessor, String, T) -> BoxFuture<'static, Result<F>>,
+        /// The deadline of the whole operation
+        Option<(Operation, Instant)>,
     ),
     /// Polling state, waiting for the future to be ready
-    Poll(BoxFuture<'static, Result<F>>),
+    Poll(BoxFuture<'static, Result<F>>, Option<(Operation, Instant)>),
     /// Empty state, the future has been polled and completed or
     /// something is broken during state switch.
     Empty,
@@ -63,23 +69,41 @@ impl<T, F> OperatorFuture<T, F> {
         args: T,
         f: fn(FusedAccessor, String, T) -> BoxFuture<'static, Result<F>>,
     ) -> Self {
-        OperatorFuture::Idle(inner, path, args, f)
+        OperatorFuture::Idle(inner, path, args, f, None)
     }
 
     fn map_args(self, f: impl FnOnce(T) -> T) -> Self {
         match self {
-            OperatorFuture::Idle(inner, path, args, func) => {
-                OperatorFuture::Idle(inner, path, f(args), func)
+            OperatorFuture::Idle(inner, path, args, func, deadline) => {
+                OperatorFuture::Idle(inner, path, f(args), func, deadline)
             }
             _ => unreachable!("future has been polled and should not be changed again"),
         }
     }
+
+    fn with_deadline(self, op: Operation, deadline: Instant) -> Self {
+        match self {
+            OperatorFuture::Idle(inner, path, args, func, _) => {
+                OperatorFuture::Idle(inner, path, args, func, Some((op, deadline)))
+            }
+            _ => unreachable!("future has been polled and should not be changed again"),
+        }
+    }
+
+    /// Get the deadline of this operation.
+    fn deadline(&self) -> Option<Instant> {
+        match self {
+            OperatorFuture::Idle(_, _, _, _, deadline) => deadline.map(|(_, v)| v),
+            OperatorFuture::Poll(_, deadline) => deadline.map(|(_, v)| v),
+            OperatorFuture::Empty => None,
+        }
+    }
 }
 
 impl<T, F> Future for OperatorFuture<T, F>
 where
     T: Unpin,
-    F: Unpin,
+    F: Unpin + 'static,
 {
     type Output = Result<F>;
 
@@ -92,18 +116,25 @@ where
     ///     self state is `Empty`
     ///   - If future is `Pending`, we will set self state to `Poll`
     ///     and wait for next poll
+    /// - If the deadline is set, the inner future will be raced against
+    ///   a timer and return an error once the deadline is exceeded.
     ///
     /// In general, `Empty` state should not be polled.
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         *self = match mem::replace(self.as_mut().get_mut(), OperatorFuture::Empty) {
-            OperatorFuture::Idle(inner, path, args, f) => {
+            OperatorFuture::Idle(inner, path, args, f, deadline) => {
                 // Wake up to make sure the future is ready after the
                 // future has been built.
                 cx.waker().wake_by_ref();
-                OperatorFuture::Poll(f(inner, path, args))
+                let fut = f(inner, path, args);
+                let fut = match deadline {
+                    Some((op, v)) => run_with_deadline(fut, op, v).boxed(),
+                    None => fut,
+                };
+                OperatorFuture::Poll(fut, deadline)
             }
-            OperatorFuture::Poll(mut fut) => match fut.as_mut().poll(cx) {
-                Poll::Pending => OperatorFuture::Poll(fut),
+            OperatorFuture::Poll(mut fut, deadline) => match fut.as_mut().poll(cx) {
+                Poll::Pending => OperatorFuture::Poll(fut, deadline),
                 Poll::Ready(v) => return Poll::Ready(v),
             },
             OperatorFuture::Empty => {
@@ -120,6 +151,15 @@ where
 pub struct FutureStat(pub(crate) OperatorFuture<OpStat, Metadata>);
 
 impl FutureStat {
+    /// Set the deadline of this operation.
+    ///
+    /// The operation will be aborted with [`ErrorKind::DeadlineExceeded`] once
+    /// the deadline is exceeded.
+    pub fn deadline(mut self, v: Instant) -> Self {
+        self.0 = self.0.with_deadline(Operation::Stat, v);
+        self
+    }
+
     /// Set the If-Match for this operation.
     pub fn if_match(mut self, v: &str) -> Self {
         self.0 = self.0.map_args(|args| args.with_if_match(v));
@@ -137,6 +177,56 @@ impl FutureStat {
         self.0 = self.0.map_args(|args| args.with_version(v));
         self
     }
+
+    /// Treat a non-empty prefix as a dir.
+    ///
+    /// On object storage services, a "dir" that only exists as the common
+    /// prefix of other files has no object of its own, so stat it without
+    /// the trailing `/` returns `NotFound`. With this option enabled, a
+    /// `NotFound` will be checked by listing `path/` and a dir metadata
+    /// will be returned if it has any child.
+    ///
+    /// # Notes
+    ///
+    /// This may send an extra `list` request for every path that is not
+    /// found. Services that don't support `list` will return the `NotFound`
+    /// as is.
+    pub fn treat_prefix_as_dir(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_treat_prefix_as_dir(v));
+        self
+    }
+
+    /// Set the metadata fields that users need, all fields by default.
+    ///
+    /// Services could use this to make a cheaper probe, for example, kv
+    /// services can get the size without reading the whole value.
+    ///
+    /// Only the given fields (and the fields that services returned anyway)
+    /// will be populated in the returned metadata, check them via
+    /// [`Metadata::metakey`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// use opendal::Metakey;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let meta = op
+    ///     .stat_with("path/to/file")
+    ///     .metakey(Metakey::ContentLength | Metakey::Etag)
+    ///     .await?;
+    /// let size = meta.content_length();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn metakey(mut self, v: impl Into<FlagSet<Metakey>>) -> Self {
+        let v = v.into();
+        self.0 = self.0.map_args(|args| args.with_metakey(v));
+        self
+    }
 }
 
 impl Future for FutureStat {
@@ -153,6 +243,15 @@ impl Future for FutureStat {
 pub struct FutureAppend(pub(crate) OperatorFuture<(OpAppend, Bytes), ()>);
 
 impl FutureAppend {
+    /// Set the deadline of this operation.
+    ///
+    /// The operation will be aborted with [`ErrorKind::DeadlineExceeded`] once
+    /// the deadline is exceeded.
+    pub fn deadline(mut self, v: Instant) -> Self {
+        self.0 = self.0.with_deadline(Operation::Append, v);
+        self
+    }
+
     /// Set the content type of option
     pub fn content_type(mut self, v: &str) -> Self {
         self.0 = self
@@ -192,6 +291,18 @@ impl Future for FutureAppend {
 pub struct FutureAppender(pub(crate) OperatorFuture<OpAppend, Appender>);
 
 impl FutureAppender {
+    /// Set the deadline of this operation.
+    ///
+    /// The operation will be aborted with [`ErrorKind::DeadlineExceeded`] once
+    /// the deadline is exceeded.
+    ///
+    /// The deadline covers creating the appender and all operations on the
+    /// returned appender.
+    pub fn deadline(mut self, v: Instant) -> Self {
+        self.0 = self.0.with_deadline(Operation::Append, v);
+        self
+    }
+
     /// Set the content type for this operation.
     pub fn content_type(mut self, content_type: &str) -> Self {
         self.0 = self.0.map_args(|args| args.with_content_type(content_type));
@@ -219,7 +330,13 @@ impl Future for FutureAppender {
     type Output = Result<Appender>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        self.0.poll_unpin(cx)
+        let deadline = self.0.deadline();
+        self.0.poll_unpin(cx).map(|res| {
+            res.map(|v| match deadline {
+                Some(deadline) => v.with_deadline(deadline),
+                None => v,
+            })
+        })
     }
 }
 
@@ -327,15 +444,56 @@ impl Future for FuturePresignWrite {
 /// Future that generated by [`Operator::read_with`].
 ///
 /// Users can add more options by public functions provided by this struct.
-pub struct FutureRead(pub(crate) OperatorFuture<OpRead, Vec<u8>>);
+pub struct FutureRead(pub(crate) OperatorFuture<OpRead, (Metadata, Vec<u8>)>);
 
 impl FutureRead {
+    /// Set the deadline of this operation.
+    ///
+    /// The operation will be aborted with [`ErrorKind::DeadlineExceeded`] once
+    /// the deadline is exceeded.
+    ///
+    /// Data read before the deadline will be dropped. Please use
+    /// [`Operator::reader_with`] instead if partial content is wanted,
+    /// data returned by the reader before the deadline will be kept.
+    pub fn deadline(mut self, v: Instant) -> Self {
+        self.0 = self.0.with_deadline(Operation::Read, v);
+        self
+    }
+
     /// Set the range header for this operation.
     pub fn range(mut self, range: impl RangeBounds<u64>) -> Self {
         self.0 = self.0.map_args(|args| args.with_range(range.into()));
         self
     }
 
+    /// Read at most `size` bytes.
+    ///
+    /// Data will be read from the start of the file, or from the offset set
+    /// by [`FutureRead::range`] if it's called before. This will be sent as
+    /// a range request like `bytes=0-(size-1)`, so we don't need to fetch
+    /// the whole file to peek at its header.
+    ///
+    /// If a suffix range like `..=-n` has been set, it stays a suffix range
+    /// and at most `min(n, size)` bytes at the end of the file will be read.
+    ///
+    /// `size(0)` returns empty content without sending any request.
+    pub fn size(mut self, size: u64) -> Self {
+        self.0 = self.0.map_args(|args| {
+            let br = args.range();
+            let size = match br.size() {
+                Some(v) => v.min(size),
+                None => size,
+            };
+            let offset = match (br.offset(), br.size()) {
+                // Suffix range, keep it as is.
+                (None, Some(_)) => None,
+                (offset, _) => Some(offset.unwrap_or_default()),
+            };
+            args.with_range(BytesRange::new(offset, Some(size)))
+        });
+        self
+    }
+
     /// Sets the content-disposition header that should be send back by the remote read operation.
     pub fn override_content_disposition(mut self, content_disposition: &str) -> Self {
         self.0 = self
@@ -369,13 +527,143 @@ impl FutureRead {
         self.0 = self.0.map_args(|args| args.with_version(v));
         self
     }
+
+    /// Add a custom header to the request of this operation.
+    ///
+    /// This is an escape hatch for vendor-specific features of HTTP based
+    /// services. Only S3, WebDAV and HTTP support `read_with_headers` by now, other
+    /// services will return an `Unsupported` error unless
+    /// `ignore_unsupported_headers` is set.
+    ///
+    /// Headers managed by OpenDAL or the signer like `Authorization`,
+    /// `Host` and `Range` are rejected with an `InvalidInput` error.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_header(name, value));
+        self
+    }
+
+    /// Ignore custom headers instead of returning an error if the service
+    /// doesn't support them.
+    pub fn ignore_unsupported_headers(mut self, v: bool) -> Self {
+        self.0 = self
+            .0
+            .map_args(|args| args.with_ignore_unsupported_headers(v));
+        self
+    }
+
+    /// Fetch data by sending one range request for every `size` bytes.
+    ///
+    /// Larger chunks fit high-bandwidth-high-latency links better, smaller
+    /// chunks limit the data in flight for memory-constrained environments.
+    /// The range will be resolved by an extra `stat` first.
+    ///
+    /// `size` must be greater than 0, otherwise an
+    /// [`ErrorKind::InvalidInput`] error will be returned. Services that
+    /// can't read with range will ignore this option.
+    pub fn chunk(mut self, size: usize) -> Self {
+        self.0 = self.0.map_args(|args| args.with_chunk(size));
+        self
+    }
+
+    /// Skip checking the returned content against the content length.
+    ///
+    /// By default, while reading the whole file, an `Unexpected` error will
+    /// be returned if the service delivers fewer or more bytes than its
+    /// content length. The error is temporary
+    /// so that it can be retried by [`RetryLayer`](crate::layers::RetryLayer).
+    ///
+    /// Only use this for services that are known to report a wrong
+    /// content length, like those serving transparently compressed content.
+    pub fn skip_content_length_check(mut self, v: bool) -> Self {
+        self.0 = self
+            .0
+            .map_args(|args| args.with_skip_content_length_check(v));
+        self
+    }
+
+    /// Verify the read content against the md5 returned by service.
+    ///
+    /// The check only happens while reading the whole file and the
+    /// service returns `Content-MD5` or an etag that is guaranteed to be
+    /// md5 (like single-part objects on s3 without SSE-KMS or SSE-C).
+    /// Mismatched content will return an `Unexpected` error which should
+    /// not be retried.
+    pub fn verify_checksum(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_verify_checksum(v));
+        self
+    }
+
+    /// Set the progress callback of this operation.
+    ///
+    /// The callback will be called with the bytes read so far and the total
+    /// bytes to read if known. It's called in the IO loop, so it should
+    /// return quickly.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::Operator;
+    /// # use opendal::Result;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let bs = op
+    ///     .read_with("path/to/file")
+    ///     .on_progress(|n, total| println!("read {n} of {total:?} bytes"))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_progress(mut self, f: impl Fn(u64, Option<u64>) + Send + Sync + 'static) -> Self {
+        let progress = ProgressCallback::new(f);
+        self.0 = self.0.map_args(|args| args.with_progress(progress));
+        self
+    }
+}
+
+impl FutureRead {
+    /// Return the metadata returned by the read response along with the
+    /// content, so that no extra `stat` is needed.
+    ///
+    /// # Notes
+    ///
+    /// Only the fields carried by the read response are available:
+    ///
+    /// - HTTP based services like s3 return the same headers as `stat`,
+    ///   like `etag`, `last_modified`, `content_type` and `content_md5`.
+    /// - Services like fs, hdfs, ftp and sftp only return `content_length`.
+    ///
+    /// Fields not carried by the read response will be `None`.
+    ///
+    /// For ranged reads, `content_length` is the length of the range and
+    /// `content_range` describes the range within the whole file. Other
+    /// fields like `etag` and `content_md5` still describe the whole file.
+    pub fn with_metadata(self) -> FutureReadWithMetadata {
+        FutureReadWithMetadata(self.0)
+    }
 }
 
 impl Future for FutureRead {
     type Output = Result<Vec<u8>>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        self.0.poll_unpin(cx)
+        self.0.poll_unpin(cx).map(|res| res.map(|(_, bs)| bs))
+    }
+}
+
+/// Future that generated by [`FutureRead::with_metadata`].
+pub struct FutureReadWithMetadata(OperatorFuture<OpRead, (Metadata, Vec<u8>)>);
+
+impl Future for FutureReadWithMetadata {
+    type Output = Result<(Metadata, Vec<u8>)>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.poll_unpin(cx).map(|res| {
+            res.map(|(meta, bs)| {
+                // The read response is all we have, mark it as complete so
+                // that fields not returned by services can be visited as `None`.
+                let bit = meta.bit() | Metakey::Complete;
+                (meta.with_bit(bit), bs)
+            })
+        })
     }
 }
 
@@ -385,6 +673,18 @@ impl Future for FutureRead {
 pub struct FutureReader(pub(crate) OperatorFuture<OpRead, Reader>);
 
 impl FutureReader {
+    /// Set the deadline of this operation.
+    ///
+    /// The operation will be aborted with [`ErrorKind::DeadlineExceeded`] once
+    /// the deadline is exceeded.
+    ///
+    /// The deadline covers creating the reader and all operations on the
+    /// returned reader.
+    pub fn deadline(mut self, v: Instant) -> Self {
+        self.0 = self.0.with_deadline(Operation::Read, v);
+        self
+    }
+
     /// Set the range header for this operation.
     pub fn range(mut self, range: impl RangeBounds<u64>) -> Self {
         self.0 = self.0.map_args(|args| args.with_range(range.into()));
@@ -418,22 +718,164 @@ impl FutureReader {
         self.0 = self.0.map_args(|args| args.with_if_none_match(v));
         self
     }
+
+    /// Add a custom header to the request of this operation.
+    ///
+    /// This is an escape hatch for vendor-specific features of HTTP based
+    /// services. Only S3, WebDAV and HTTP support `read_with_headers` by now, other
+    /// services will return an `Unsupported` error unless
+    /// `ignore_unsupported_headers` is set.
+    ///
+    /// Headers managed by OpenDAL or the signer like `Authorization`,
+    /// `Host` and `Range` are rejected with an `InvalidInput` error.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_header(name, value));
+        self
+    }
+
+    /// Ignore custom headers instead of returning an error if the service
+    /// doesn't support them.
+    pub fn ignore_unsupported_headers(mut self, v: bool) -> Self {
+        self.0 = self
+            .0
+            .map_args(|args| args.with_ignore_unsupported_headers(v));
+        self
+    }
+
+    /// Set the progress callback of this operation.
+    ///
+    /// The callback will be called with the bytes read so far and the total
+    /// bytes to read if known. It's called in the IO loop, so it should
+    /// return quickly.
+    pub fn on_progress(mut self, f: impl Fn(u64, Option<u64>) + Send + Sync + 'static) -> Self {
+        let progress = ProgressCallback::new(f);
+        self.0 = self.0.map_args(|args| args.with_progress(progress));
+        self
+    }
+
+    /// Set the read ahead buffer size of the reader.
+    ///
+    /// The reader will prefetch the next chunk while users are consuming
+    /// the current one, and at most `size` bytes will be buffered in memory.
+    /// This is useful for sequential reads in small increments like reading
+    /// lines or records.
+    ///
+    /// # Notes
+    ///
+    /// No task will be spawned, the prefetch only makes progress while the
+    /// returning reader is polled.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use futures::io::AsyncBufReadExt;
+    /// use futures::io::BufReader;
+    /// use futures::TryStreamExt;
+    /// # use opendal::Operator;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let r = op
+    ///     .reader_with("path/to/file")
+    ///     .buffer(8 * 1024 * 1024)
+    ///     .await?;
+    /// let lines: Vec<String> = BufReader::new(r).lines().try_collect().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn buffer(mut self, size: usize) -> Self {
+        self.0 = self.0.map_args(|args| args.with_buffer(size));
+        self
+    }
+
+    /// Fetch data by sending one range request for every `size` bytes.
+    ///
+    /// Larger chunks fit high-bandwidth-high-latency links better, smaller
+    /// chunks limit the data in flight for memory-constrained environments.
+    /// The range will be resolved by an extra `stat` first.
+    ///
+    /// Works well together with [`FutureReader::buffer`]: the read ahead
+    /// buffer pulls from chunks, so the next range request will be sent
+    /// while users are consuming the current one. Set `buffer`
+    /// to twice the chunk size to keep one chunk in flight.
+    ///
+    /// `size` must be greater than 0, otherwise an
+    /// [`ErrorKind::InvalidInput`] error will be returned. Services that
+    /// can't read with range will ignore this option.
+    pub fn chunk(mut self, size: usize) -> Self {
+        self.0 = self.0.map_args(|args| args.with_chunk(size));
+        self
+    }
+
+    /// Skip checking the returned content against the content length.
+    ///
+    /// By default, while reading the whole file, an `Unexpected` error will
+    /// be returned if the service delivers fewer or more bytes than its
+    /// content length. The error is temporary
+    /// so that it can be retried by [`RetryLayer`](crate::layers::RetryLayer).
+    ///
+    /// Only use this for services that are known to report a wrong
+    /// content length, like those serving transparently compressed content.
+    pub fn skip_content_length_check(mut self, v: bool) -> Self {
+        self.0 = self
+            .0
+            .map_args(|args| args.with_skip_content_length_check(v));
+        self
+    }
+
+    /// Read the file as a stream of newline-delimited records.
+    ///
+    /// The reader will be opened while polling the stream at the first
+    /// time, and errors of opening will be returned as the first item.
+    /// Refer to [`Lines`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use futures::TryStreamExt;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let lines: Vec<String> = op.reader_with("path/to/file").lines().try_collect().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn lines(self) -> Lines {
+        Lines::opening(Box::pin(self))
+    }
 }
 
 impl Future for FutureReader {
     type Output = Result<Reader>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        self.0.poll_unpin(cx)
+        let deadline = self.0.deadline();
+        self.0.poll_unpin(cx).map(|res| {
+            res.map(|v| match deadline {
+                Some(deadline) => v.with_deadline(deadline),
+                None => v,
+            })
+        })
     }
 }
 
 /// Future that generated by [`Operator::write_with`].
 ///
 /// Users can add more options by public functions provided by this struct.
-pub struct FutureWrite(pub(crate) OperatorFuture<(OpWrite, Bytes), ()>);
+pub struct FutureWrite(pub(crate) OperatorFuture<(OpWrite, Bytes), Metadata>);
 
 impl FutureWrite {
+    /// Set the deadline of this operation.
+    ///
+    /// The operation will be aborted with [`ErrorKind::DeadlineExceeded`] once
+    /// the deadline is exceeded.
+    pub fn deadline(mut self, v: Instant) -> Self {
+        self.0 = self.0.with_deadline(Operation::Write, v);
+        self
+    }
+
     /// Set the content length of op.
     ///
     /// If the content length is not set, the content length will be
@@ -468,108 +910,1030 @@ impl FutureWrite {
             .map_args(|(args, bs)| (args.with_cache_control(v), bs));
         self
     }
-}
-
-impl Future for FutureWrite {
-    type Output = Result<()>;
 
-    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        self.0.poll_unpin(cx)
+    /// Set the content encoding of option
+    ///
+    /// The content is stored as is, OpenDAL won't compress it. Only services
+    /// that support `write_with_content_encoding` accept this option, others
+    /// will return an `Unsupported` error.
+    pub fn content_encoding(mut self, v: &str) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_content_encoding(v), bs));
+        self
     }
-}
 
-/// Future that generated by [`Operator::writer_with`].
-///
-/// Users can add more options by public functions provided by this struct.
-pub struct FutureWriter(pub(crate) OperatorFuture<OpWrite, Writer>);
-
-impl FutureWriter {
-    /// Set the content length of op.
+    /// Set the content language of option
     ///
-    /// If the content length is not set, the content length will be
-    /// calculated automatically by buffering part of data.
-    pub fn content_length(mut self, v: u64) -> Self {
-        self.0 = self.0.map_args(|args| args.with_content_length(v));
+    /// Only services that support `write_with_content_language` accept this
+    /// option, others will return an `Unsupported` error.
+    pub fn content_language(mut self, v: &str) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_content_language(v), bs));
         self
     }
 
-    /// Set the content type of option
-    pub fn content_type(mut self, v: &str) -> Self {
-        self.0 = self.0.map_args(|args| args.with_content_type(v));
+    /// Add a custom header to the request of this operation.
+    ///
+    /// This is an escape hatch for vendor-specific features of HTTP based
+    /// services. Only S3 and WebDAV support `write_with_headers` by now, other
+    /// services will return an `Unsupported` error unless
+    /// `ignore_unsupported_headers` is set.
+    ///
+    /// Headers managed by OpenDAL or the signer like `Authorization`,
+    /// `Host` and `Range` are rejected with an `InvalidInput` error.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_header(name, value), bs));
         self
     }
 
-    /// Set the content disposition of option
-    pub fn content_disposition(mut self, v: &str) -> Self {
-        self.0 = self.0.map_args(|args| args.with_content_disposition(v));
+    /// Ignore custom headers instead of returning an error if the service
+    /// doesn't support them.
+    pub fn ignore_unsupported_headers(mut self, v: bool) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_ignore_unsupported_headers(v), bs));
         self
     }
 
-    /// Set the content type of option
-    pub fn cache_control(mut self, v: &str) -> Self {
-        self.0 = self.0.map_args(|args| args.with_cache_control(v));
+    /// Set the storage class of option
+    ///
+    /// The value will be passed to service as is without validation, like
+    /// `STANDARD_IA` on s3 or `NEARLINE` on gcs. Only services that support
+    /// `write_with_storage_class` accept this option, others will return an
+    /// `Unsupported` error.
+    pub fn storage_class(mut self, v: &str) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_storage_class(v), bs));
         self
     }
-}
 
-impl Future for FutureWriter {
-    type Output = Result<Writer>;
-
-    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        self.0.poll_unpin(cx)
+    /// Set the chunk size of this write.
+    ///
+    /// Services that upload data in parts (s3, gcs, oss, cos and obs) will
+    /// upload a part every `size` bytes instead of their `write_min_size`.
+    /// The size must satisfy the part size limits of the service, for
+    /// example, at least 5 MiB on s3.
+    pub fn chunk(mut self, size: usize) -> Self {
+        self.0 = self.0.map_args(|(args, bs)| (args.with_chunk(size), bs));
+        self
     }
-}
 
-/// Future that generated by [`Operator::delete_with`].
-///
-/// Users can add more options by public functions provided by this struct.
-pub struct FutureDelete(pub(crate) OperatorFuture<OpDelete, ()>);
-
-impl FutureDelete {
-    /// Change the version of this delete operation.
-    pub fn version(mut self, v: &str) -> Self {
-        self.0 = self.0.map_args(|args| args.with_version(v));
+    /// Set the max number of parts to upload concurrently, `1` by default.
+    ///
+    /// Only services that upload data via `MultipartUploadWriter` (s3 and
+    /// s3 compatible services) support concurrent uploads, others will
+    /// upload parts one by one. Up to `n` parts will be buffered in memory.
+    ///
+    /// # Notes
+    ///
+    /// No task will be spawned, parts in flight only make progress while
+    /// the writer is written or closed.
+    pub fn concurrent(mut self, n: usize) -> Self {
+        self.0 = self.0.map_args(|(args, bs)| (args.with_concurrent(n), bs));
         self
     }
-}
 
-impl Future for FutureDelete {
-    type Output = Result<()>;
+    /// Lock the file with given retention mode until `retain_until`.
+    ///
+    /// The file can't be deleted or overwritten before `retain_until`. Only
+    /// services that support `write_with_object_lock` accept this option,
+    /// others will return an `Unsupported` error.
+    pub fn object_lock(mut self, mode: ObjectLockMode, retain_until: DateTime<Utc>) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_object_lock(mode, retain_until), bs));
+        self
+    }
 
-    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        self.0.poll_unpin(cx)
+    /// Set whether the legal hold of the file is on.
+    ///
+    /// Only services that support `write_with_object_lock` accept this
+    /// option, others will return an `Unsupported` error.
+    pub fn legal_hold(mut self, on: bool) -> Self {
+        self.0 = self.0.map_args(|(args, bs)| (args.with_legal_hold(on), bs));
+        self
     }
-}
 
-/// Future that generated by [`Operator::list_with`].
-///
-/// Users can add more options by public functions provided by this struct.
-pub struct FutureList(pub(crate) OperatorFuture<OpList, Lister>);
+    /// Set the content md5 of option
+    ///
+    /// The value should be the base64 encoded md5 digest of the content,
+    /// services that support `write_with_content_md5` will reject
+    /// the upload if it doesn't match. Others will return an `Unsupported`
+    /// error.
+    pub fn content_md5(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|(args, bs)| (args.with_content_md5(v), bs));
+        self
+    }
 
-impl FutureList {
-    /// Change the limit of this list operation.
-    pub fn limit(mut self, v: usize) -> Self {
-        self.0 = self.0.map_args(|args| args.with_limit(v));
+    /// Set the ttl of option
+    ///
+    /// The written content will expire after the given duration. Only
+    /// services that support `write_with_ttl` accept this option, others
+    /// will return an `Unsupported` error.
+    ///
+    /// The remaining ttl is reported by [`Metadata::ttl`] in `stat` if
+    /// the service can tell it.
+    pub fn ttl(mut self, v: Duration) -> Self {
+        self.0 = self.0.map_args(|(args, bs)| (args.with_ttl(v), bs));
         self
     }
 
-    /// Change the start_after of this list operation.
-    pub fn start_after(mut self, v: &str) -> Self {
-        self.0 = self.0.map_args(|args| args.with_start_after(v));
+    /// Set the append of option
+    ///
+    /// If append is true, the content will be appended to the end of
+    /// existing file (the file will be created if not exist) instead of
+    /// overwriting it. Only services that support `write_with_append`
+    /// accept this option, others will return an `Unsupported` error.
+    pub fn append(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|(args, bs)| (args.with_append(v), bs));
         self
     }
 
-    /// Change the delimiter. The default delimiter is "/"
-    pub fn delimiter(mut self, v: &str) -> Self {
-        self.0 = self.0.map_args(|args| args.with_delimiter(v));
+    /// Set the callback that will be called every time a part has been flushed.
+    ///
+    /// Only services that upload data in parts (s3, gcs, oss, cos and obs)
+    /// will call it. The callback is called in the IO loop, so it should
+    /// return quickly.
+    pub fn on_part(mut self, f: impl Fn(WritePartStats) + Send + Sync + 'static) -> Self {
+        let callback = WritePartCallback::new(f);
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_part_callback(callback), bs));
         self
     }
-}
 
-impl Future for FutureList {
-    type Output = Result<Lister>;
+    /// Resume the upload from given checkpoint.
+    ///
+    /// The given data should start from [`WriteCheckpoint::size`].
+    ///
+    /// Only services with [`Capability::write_with_resume`] support this,
+    /// others will return [`ErrorKind::Unsupported`].
+    pub fn resume(mut self, checkpoint: WriteCheckpoint) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_resume(checkpoint), bs));
+        self
+    }
+
+    /// Overwrite the given range of an existing file in place.
+    ///
+    /// Only the start of range is used to locate the write position, the
+    /// rest of the file will be kept as is. The file will be created if it
+    /// doesn't exist.
+    ///
+    /// This is fundamentally unavailable on most object storage services
+    /// which can only replace the whole object. Only services that support
+    /// `write_with_range` (like fs) accept this option, others will return
+    /// an `Unsupported` error.
+    pub fn range(mut self, range: impl RangeBounds<u64>) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_range(range.into()), bs));
+        self
+    }
+
+    /// Set the If-Match for this operation.
+    ///
+    /// The write will fail with `ConditionNotMatch` if the etag of
+    /// existing file doesn't match.
+    pub fn if_match(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|(args, bs)| (args.with_if_match(v), bs));
+        self
+    }
+
+    /// Set the If-None-Match for this operation.
+    ///
+    /// The write will fail with `ConditionNotMatch` if the etag of
+    /// existing file matches.
+    pub fn if_none_match(mut self, v: &str) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_if_none_match(v), bs));
+        self
+    }
+
+    /// Only create the file if it doesn't exist.
+    ///
+    /// This is the same as `if_none_match("*")`, the write will fail
+    /// with `AlreadyExists` if the file is present. It's useful to build
+    /// locks or first-writer-wins patterns on object storage.
+    pub fn create(mut self, v: bool) -> Self {
+        if v {
+            self = self.if_none_match("*");
+        }
+        self
+    }
+
+    /// Set the idempotency key for this operation.
+    ///
+    /// Services will apply writes with the same key only once, so that
+    /// this write can be retried without duplicating side effects.
+    ///
+    /// Services without [`Capability::write_with_idempotency_key`] will
+    /// return [`ErrorKind::Unsupported`].
+    pub fn idempotency_key(mut self, v: &str) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_idempotency_key(v), bs));
+        self
+    }
+
+    /// Compute the checksum of the content with given algorithm.
+    ///
+    /// The checksum is computed by OpenDAL over the written bytes, use
+    /// [`FutureWrite::with_metadata`] to get it after the write succeeded.
+    ///
+    /// Services like s3 will verify and store the checksum too if the
+    /// content is written in a single request, it will be returned by
+    /// following `stat` via [`Metadata::checksum_crc32c`] or
+    /// [`Metadata::checksum_sha256`].
+    pub fn checksum(mut self, algo: ChecksumAlgo) -> Self {
+        self.0 = self.0.map_args(|(args, bs)| (args.with_checksum(algo), bs));
+        self
+    }
+
+    /// Return the metadata of the written file instead of `()`.
+    ///
+    /// Only `mode` and the checksum requested via [`FutureWrite::checksum`]
+    /// are set in the returned metadata.
+    pub fn with_metadata(self) -> FutureWriteWithMetadata {
+        FutureWriteWithMetadata(self.0)
+    }
+
+    /// Set the progress callback of this operation.
+    ///
+    /// The callback will be called with the bytes written so far and the
+    /// content length if known (`None` for streaming writes), and once
+    /// more after the write has completed. It's called in the IO loop, so
+    /// it should return quickly.
+    pub fn on_progress(mut self, f: impl Fn(u64, Option<u64>) + Send + Sync + 'static) -> Self {
+        let progress = ProgressCallback::new(f);
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_progress(progress), bs));
+        self
+    }
+
+    /// Set the tags of this file.
+    ///
+    /// Tags are different from user metadata, they can be updated later via
+    /// [`Operator::put_tags`] without rewriting the file. Only services that
+    /// support `write_with_tags` accept this option, others will return an
+    /// `Unsupported` error.
+    pub fn tags<K, V>(mut self, tags: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let tags = tags
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        self.0 = self.0.map_args(|(args, bs)| (args.with_tags(tags), bs));
+        self
+    }
+
+    /// Set the user metadata of the file.
+    ///
+    /// User metadata will be returned by [`Metadata::user_metadata`] while
+    /// `stat`, like `x-amz-meta-*` on s3 or `user.*` xattrs on fs. Only
+    /// services that support `write_with_user_metadata` accept this option,
+    /// others will return an `Unsupported` error.
+    pub fn user_metadata<K, V>(mut self, data: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let data = data
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_user_metadata(data), bs));
+        self
+    }
+}
+
+impl Future for FutureWrite {
+    type Output = Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.poll_unpin(cx).map(|res| res.map(|_| ()))
+    }
+}
+
+/// Future that generated by [`FutureWrite::with_metadata`].
+pub struct FutureWriteWithMetadata(OperatorFuture<(OpWrite, Bytes), Metadata>);
+
+impl Future for FutureWriteWithMetadata {
+    type Output = Result<Metadata>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.poll_unpin(cx)
+    }
+}
+
+/// Future that generated by [`Operator::writer_with`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub struct FutureWriter(pub(crate) OperatorFuture<OpWrite, Writer>);
+
+impl FutureWriter {
+    /// Set the deadline of this operation.
+    ///
+    /// The operation will be aborted with [`ErrorKind::DeadlineExceeded`] once
+    /// the deadline is exceeded.
+    ///
+    /// The deadline covers creating the writer and all operations on the
+    /// returned writer.
+    pub fn deadline(mut self, v: Instant) -> Self {
+        self.0 = self.0.with_deadline(Operation::Write, v);
+        self
+    }
+
+    /// Set the content length of op.
+    ///
+    /// If the content length is not set, the content length will be
+    /// calculated automatically by buffering part of data.
+    pub fn content_length(mut self, v: u64) -> Self {
+        self.0 = self.0.map_args(|args| args.with_content_length(v));
+        self
+    }
+
+    /// Set the content type of option
+    pub fn content_type(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_content_type(v));
+        self
+    }
+
+    /// Set the content disposition of option
+    pub fn content_disposition(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_content_disposition(v));
+        self
+    }
+
+    /// Set the content type of option
+    pub fn cache_control(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_cache_control(v));
+        self
+    }
+
+    /// Set the content encoding of option
+    ///
+    /// The content is stored as is, OpenDAL won't compress it. Only services
+    /// that support `write_with_content_encoding` accept this option, others
+    /// will return an `Unsupported` error.
+    pub fn content_encoding(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_content_encoding(v));
+        self
+    }
+
+    /// Set the content language of option
+    ///
+    /// Only services that support `write_with_content_language` accept this
+    /// option, others will return an `Unsupported` error.
+    pub fn content_language(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_content_language(v));
+        self
+    }
+
+    /// Add a custom header to the request of this operation.
+    ///
+    /// This is an escape hatch for vendor-specific features of HTTP based
+    /// services. Only S3 and WebDAV support `write_with_headers` by now, other
+    /// services will return an `Unsupported` error unless
+    /// `ignore_unsupported_headers` is set.
+    ///
+    /// Headers managed by OpenDAL or the signer like `Authorization`,
+    /// `Host` and `Range` are rejected with an `InvalidInput` error.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_header(name, value));
+        self
+    }
+
+    /// Ignore custom headers instead of returning an error if the service
+    /// doesn't support them.
+    pub fn ignore_unsupported_headers(mut self, v: bool) -> Self {
+        self.0 = self
+            .0
+            .map_args(|args| args.with_ignore_unsupported_headers(v));
+        self
+    }
+
+    /// Set the storage class of option
+    ///
+    /// The value will be passed to service as is without validation, like
+    /// `STANDARD_IA` on s3 or `NEARLINE` on gcs. Only services that support
+    /// `write_with_storage_class` accept this option, others will return an
+    /// `Unsupported` error.
+    pub fn storage_class(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_storage_class(v));
+        self
+    }
+
+    /// Set the chunk size of this write.
+    ///
+    /// Services that upload data in parts (s3, gcs, oss, cos and obs) will
+    /// upload a part every `size` bytes instead of their `write_min_size`.
+    /// The size must satisfy the part size limits of the service, for
+    /// example, at least 5 MiB on s3.
+    pub fn chunk(mut self, size: usize) -> Self {
+        self.0 = self.0.map_args(|args| args.with_chunk(size));
+        self
+    }
+
+    /// Set the max number of parts to upload concurrently, `1` by default.
+    ///
+    /// Only services that upload data via `MultipartUploadWriter` (s3 and
+    /// s3 compatible services) support concurrent uploads, others will
+    /// upload parts one by one. Up to `n` parts will be buffered in memory.
+    ///
+    /// # Notes
+    ///
+    /// No task will be spawned, parts in flight only make progress while
+    /// the writer is written or closed.
+    pub fn concurrent(mut self, n: usize) -> Self {
+        self.0 = self.0.map_args(|args| args.with_concurrent(n));
+        self
+    }
+
+    /// Lock the file with given retention mode until `retain_until`.
+    ///
+    /// The file can't be deleted or overwritten before `retain_until`. Only
+    /// services that support `write_with_object_lock` accept this option,
+    /// others will return an `Unsupported` error.
+    pub fn object_lock(mut self, mode: ObjectLockMode, retain_until: DateTime<Utc>) -> Self {
+        self.0 = self
+            .0
+            .map_args(|args| args.with_object_lock(mode, retain_until));
+        self
+    }
+
+    /// Set whether the legal hold of the file is on.
+    ///
+    /// Only services that support `write_with_object_lock` accept this
+    /// option, others will return an `Unsupported` error.
+    pub fn legal_hold(mut self, on: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_legal_hold(on));
+        self
+    }
+
+    /// Set the callback that will be called every time a part has been flushed.
+    ///
+    /// Only services that upload data in parts (s3, gcs, oss, cos and obs)
+    /// will call it. The callback is called in the IO loop, so it should
+    /// return quickly.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::Operator;
+    /// # use opendal::Result;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut w = op
+    ///     .writer_with("path/to/file")
+    ///     .on_part(|stats| {
+    ///         println!(
+    ///             "part {} of {} bytes took {:?}",
+    ///             stats.part_number, stats.size, stats.elapsed
+    ///         )
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_part(mut self, f: impl Fn(WritePartStats) + Send + Sync + 'static) -> Self {
+        let callback = WritePartCallback::new(f);
+        self.0 = self.0.map_args(|args| args.with_part_callback(callback));
+        self
+    }
+
+    /// Resume the upload from given checkpoint.
+    ///
+    /// The checkpoint can be got via [`Writer::checkpoint`]. Users should
+    /// continue writing data from [`WriteCheckpoint::size`].
+    ///
+    /// Only services with [`Capability::write_with_resume`] support this,
+    /// others will return [`ErrorKind::Unsupported`].
+    pub fn resume(mut self, checkpoint: WriteCheckpoint) -> Self {
+        self.0 = self.0.map_args(|args| args.with_resume(checkpoint));
+        self
+    }
+
+    /// Overwrite the given range of an existing file in place.
+    ///
+    /// Only the start of range is used to locate the write position, the
+    /// rest of the file will be kept as is. The file will be created if it
+    /// doesn't exist.
+    ///
+    /// This is fundamentally unavailable on most object storage services
+    /// which can only replace the whole object. Only services that support
+    /// `write_with_range` (like fs) accept this option, others will return
+    /// an `Unsupported` error.
+    pub fn range(mut self, range: impl RangeBounds<u64>) -> Self {
+        self.0 = self.0.map_args(|args| args.with_range(range.into()));
+        self
+    }
+
+    /// Set the If-Match for this operation.
+    ///
+    /// The write will fail with `ConditionNotMatch` if the etag of
+    /// existing file doesn't match.
+    pub fn if_match(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_if_match(v));
+        self
+    }
+
+    /// Set the If-None-Match for this operation.
+    ///
+    /// The write will fail with `ConditionNotMatch` if the etag of
+    /// existing file matches.
+    pub fn if_none_match(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_if_none_match(v));
+        self
+    }
+
+    /// Only create the file if it doesn't exist.
+    ///
+    /// This is the same as `if_none_match("*")`, the write will fail
+    /// with `AlreadyExists` if the file is present.
+    pub fn create(mut self, v: bool) -> Self {
+        if v {
+            self = self.if_none_match("*");
+        }
+        self
+    }
+
+    /// Set the idempotency key for this operation.
+    ///
+    /// Services will apply writes with the same key only once, so that
+    /// this write can be retried without duplicating side effects.
+    ///
+    /// Services without [`Capability::write_with_idempotency_key`] will
+    /// return [`ErrorKind::Unsupported`].
+    pub fn idempotency_key(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_idempotency_key(v));
+        self
+    }
+
+    /// Compute the checksum of the content with given algorithm.
+    ///
+    /// The checksum is computed by OpenDAL over the bytes as they're
+    /// written, and returned by [`Writer::close`]. This avoids reading
+    /// the file back to compute its hash.
+    ///
+    /// For services that upload data in parts (like multipart uploads on
+    /// s3), the etag of the written file is not the md5 of the content, so
+    /// the checksum is the only reliable full-object hash.
+    ///
+    /// The bytes uploaded before the checkpoint are not seen by this writer,
+    /// so an `Unsupported` error will be returned if it's used together with
+    /// [`FutureWriter::resume`].
+    pub fn checksum(mut self, algo: ChecksumAlgo) -> Self {
+        self.0 = self.0.map_args(|args| args.with_checksum(algo));
+        self
+    }
+
+    /// Set the content md5 of option
+    ///
+    /// The value should be the base64 encoded md5 digest of the whole
+    /// content. It only takes effect when the content is uploaded in a
+    /// single request. Services that don't support `write_with_content_md5`
+    /// will return an `Unsupported` error.
+    pub fn content_md5(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_content_md5(v));
+        self
+    }
+
+    /// Set the ttl of option
+    ///
+    /// The written content will expire after the given duration. Only
+    /// services that support `write_with_ttl` accept this option, others
+    /// will return an `Unsupported` error.
+    ///
+    /// The remaining ttl is reported by [`Metadata::ttl`] in `stat` if
+    /// the service can tell it.
+    pub fn ttl(mut self, v: Duration) -> Self {
+        self.0 = self.0.map_args(|args| args.with_ttl(v));
+        self
+    }
+
+    /// Set the append of option
+    ///
+    /// If append is true, the content will be appended to the end of
+    /// existing file (the file will be created if not exist) instead of
+    /// overwriting it. Only services that support `write_with_append`
+    /// accept this option, others will return an `Unsupported` error.
+    pub fn append(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_append(v));
+        self
+    }
+
+    /// Set the tags of this file.
+    ///
+    /// Tags are different from user metadata, they can be updated later via
+    /// [`Operator::put_tags`] without rewriting the file. Only services that
+    /// support `write_with_tags` accept this option, others will return an
+    /// `Unsupported` error.
+    pub fn tags<K, V>(mut self, tags: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let tags = tags
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        self.0 = self.0.map_args(|args| args.with_tags(tags));
+        self
+    }
+
+    /// Set the user metadata of the file.
+    ///
+    /// User metadata will be returned by [`Metadata::user_metadata`] while
+    /// `stat`, like `x-amz-meta-*` on s3 or `user.*` xattrs on fs. Only
+    /// services that support `write_with_user_metadata` accept this option,
+    /// others will return an `Unsupported` error.
+    pub fn user_metadata<K, V>(mut self, data: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let data = data
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        self.0 = self.0.map_args(|args| args.with_user_metadata(data));
+        self
+    }
+
+    /// Set the progress callback of this operation.
+    ///
+    /// The callback will be called with the bytes written so far and the
+    /// content length if known (`None` for streaming writes), and once
+    /// more after the write has completed. It's called in the IO loop, so
+    /// it should return quickly.
+    pub fn on_progress(mut self, f: impl Fn(u64, Option<u64>) + Send + Sync + 'static) -> Self {
+        let progress = ProgressCallback::new(f);
+        self.0 = self.0.map_args(|args| args.with_progress(progress));
+        self
+    }
+}
+
+impl Future for FutureWriter {
+    type Output = Result<Writer>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let deadline = self.0.deadline();
+        self.0.poll_unpin(cx).map(|res| {
+            res.map(|v| match deadline {
+                Some(deadline) => v.with_deadline(deadline),
+                None => v,
+            })
+        })
+    }
+}
+
+/// Future that generated by [`Operator::write_from`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub struct FutureWriteFrom<R>(
+    /// The args for FutureWriteFrom carry the size of copy buffer and the
+    /// reader to copy data from.
+    pub(crate) OperatorFuture<(OpWrite, usize, R), ()>,
+);
+
+impl<R> FutureWriteFrom<R> {
+    /// Set the size of buffer used to copy data from reader.
+    ///
+    /// Default: 256 KiB
+    pub fn buffer(mut self, v: usize) -> Self {
+        self.0 = self.0.map_args(|(args, _, r)| (args, v, r));
+        self
+    }
+}
+
+impl<R: Unpin> Future for FutureWriteFrom<R> {
+    type Output = Result<()>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         self.0.poll_unpin(cx)
     }
 }
+
+/// Future that generated by [`Operator::delete_with`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub struct FutureDelete(
+    /// The args for FutureDelete also carry the limit of operator, which is
+    /// used to delete entries concurrently while deleting recursively.
+    pub(crate) OperatorFuture<(OpDelete, usize), ()>,
+);
+
+impl FutureDelete {
+    /// Set the deadline of this operation.
+    ///
+    /// The operation will be aborted with [`ErrorKind::DeadlineExceeded`] once
+    /// the deadline is exceeded.
+    pub fn deadline(mut self, v: Instant) -> Self {
+        self.0 = self.0.with_deadline(Operation::Delete, v);
+        self
+    }
+
+    /// Change the version of this delete operation.
+    pub fn version(mut self, v: &str) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, limit)| (args.with_version(v), limit));
+        self
+    }
+
+    /// Set the If-Match of this delete operation.
+    ///
+    /// The path will only be deleted if its etag matches, otherwise an
+    /// error with [`ErrorKind::ConditionNotMatch`] will be returned.
+    pub fn if_match(mut self, v: &str) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, limit)| (args.with_if_match(v), limit));
+        self
+    }
+
+    /// Delete the path and all entries under it.
+    ///
+    /// Refer to [`Operator::delete_with`] for more details.
+    pub fn recursive(mut self, v: bool) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, limit)| (args.with_recursive(v), limit));
+        self
+    }
+
+    /// Allow recursive delete on the root of operator.
+    ///
+    /// Recursive delete on root will remove everything under this operator,
+    /// so it's refused by default.
+    pub fn allow_root(mut self, v: bool) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, limit)| (args.with_allow_root(v), limit));
+        self
+    }
+}
+
+impl Future for FutureDelete {
+    type Output = Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.poll_unpin(cx)
+    }
+}
+
+/// Future that generated by [`Operator::copy_with`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub struct FutureCopy(
+    /// The args for FutureCopy is a bit special because we also
+    /// need to move the target path into this function.
+    pub(crate) OperatorFuture<(OpCopy, String), ()>,
+);
+
+impl FutureCopy {
+    /// Set the deadline of this operation.
+    ///
+    /// The operation will be aborted with [`ErrorKind::DeadlineExceeded`] once
+    /// the deadline is exceeded.
+    pub fn deadline(mut self, v: Instant) -> Self {
+        self.0 = self.0.with_deadline(Operation::Copy, v);
+        self
+    }
+
+    /// Set how the metadata of source file is handled.
+    ///
+    /// Default to [`MetadataDirective::Copy`]. Services that don't support
+    /// `copy_with_metadata_directive` will return an
+    /// [`ErrorKind::Unsupported`] error for [`MetadataDirective::Replace`].
+    pub fn metadata_directive(mut self, v: MetadataDirective) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, to)| (args.with_metadata_directive(v), to));
+        self
+    }
+
+    /// Set the content type of the target file.
+    ///
+    /// Requires [`MetadataDirective::Replace`], otherwise an
+    /// [`ErrorKind::InvalidInput`] error will be returned.
+    pub fn content_type(mut self, v: &str) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, to)| (args.with_content_type(v), to));
+        self
+    }
+
+    /// Set the content disposition of the target file.
+    ///
+    /// Requires [`MetadataDirective::Replace`], otherwise an
+    /// [`ErrorKind::InvalidInput`] error will be returned.
+    pub fn content_disposition(mut self, v: &str) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, to)| (args.with_content_disposition(v), to));
+        self
+    }
+
+    /// Set the cache control of the target file.
+    ///
+    /// Requires [`MetadataDirective::Replace`], otherwise an
+    /// [`ErrorKind::InvalidInput`] error will be returned.
+    pub fn cache_control(mut self, v: &str) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, to)| (args.with_cache_control(v), to));
+        self
+    }
+
+    /// Set the user metadata of the target file.
+    ///
+    /// Requires [`MetadataDirective::Replace`], otherwise an
+    /// [`ErrorKind::InvalidInput`] error will be returned. User metadata of
+    /// the source file is dropped while replacing.
+    pub fn user_metadata<K, V>(mut self, data: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let data = data
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        self.0 = self
+            .0
+            .map_args(|(args, to)| (args.with_user_metadata(data), to));
+        self
+    }
+
+    /// Only copy if the etag of source file matches the given one.
+    ///
+    /// Returns [`ErrorKind::ConditionNotMatch`] if the source has been
+    /// changed. Services that don't support `copy_with_if_source_match`
+    /// will return an [`ErrorKind::Unsupported`] error.
+    pub fn if_source_match(mut self, v: &str) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, to)| (args.with_if_source_match(v), to));
+        self
+    }
+}
+
+impl Future for FutureCopy {
+    type Output = Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.poll_unpin(cx)
+    }
+}
+
+/// Future that generated by [`Operator::list_with`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub struct FutureList(pub(crate) OperatorFuture<OpList, Lister>);
+
+impl FutureList {
+    /// Set the deadline of this operation.
+    ///
+    /// The operation will be aborted with [`ErrorKind::DeadlineExceeded`] once
+    /// the deadline is exceeded.
+    ///
+    /// The deadline covers creating the lister and all operations on the
+    /// returned lister.
+    pub fn deadline(mut self, v: Instant) -> Self {
+        self.0 = self.0.with_deadline(Operation::List, v);
+        self
+    }
+
+    /// Change the limit of this list operation.
+    pub fn limit(mut self, v: usize) -> Self {
+        self.0 = self.0.map_args(|args| args.with_limit(v));
+        self
+    }
+
+    /// Stop listing after `v` entries have been returned.
+    ///
+    /// Unlike `limit` which only changes the page size, no more pages will
+    /// be fetched once the count is reached.
+    ///
+    /// Refer to [`Operator::list_with`] for more details.
+    pub fn max_entries(mut self, v: usize) -> Self {
+        self.0 = self.0.map_args(|args| args.with_max_entries(v));
+        self
+    }
+
+    /// Change the start_after of this list operation.
+    pub fn start_after(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_start_after(v));
+        self
+    }
+
+    /// Resume this list operation from the continuation token returned by
+    /// [`Lister::continuation_token`](crate::Lister::continuation_token).
+    pub fn continuation_token(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_continuation_token(v));
+        self
+    }
+
+    /// Change the delimiter. The default delimiter is "/"
+    pub fn delimiter(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_delimiter(v));
+        self
+    }
+
+    /// Make sure entries are returned in lexicographic order.
+    ///
+    /// Refer to [`Operator::list_with`] for more details.
+    pub fn sorted(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_sorted(v));
+        self
+    }
+
+    /// Only return entries that match the glob pattern.
+    ///
+    /// The pattern is relative to the listing path.
+    ///
+    /// Refer to [`Operator::list_with`] for more details.
+    pub fn glob(mut self, pattern: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_glob(pattern));
+        self
+    }
+
+    /// Only return common prefixes (dirs) under the delimiter and skip all
+    /// files.
+    ///
+    /// Refer to [`Operator::list_with`] for more details.
+    pub fn prefixes_only(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_prefixes_only(v));
+        self
+    }
+
+    /// Request services to return metadata like content length and last
+    /// modified along with entries.
+    ///
+    /// Refer to [`Operator::list_with`] for more details.
+    pub fn metadata(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_metadata(v));
+        self
+    }
+
+    /// List all entries under the path recursively.
+    ///
+    /// This is a shortcut of `delimiter("")`, and `recursive(false)` resets
+    /// the delimiter to `/`.
+    pub fn recursive(mut self, v: bool) -> Self {
+        self.0 = self
+            .0
+            .map_args(|args| args.with_delimiter(if v { "" } else { "/" }));
+        self
+    }
+
+    /// List all versions of files instead of the latest ones only.
+    ///
+    /// Services that don't support `list_with_versions` will return an
+    /// [`ErrorKind::Unsupported`] error.
+    ///
+    /// Refer to [`Operator::list_with`] for more details.
+    pub fn versions(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_versions(v));
+        self
+    }
+
+    /// List delete markers along with versions.
+    ///
+    /// Delete markers are returned as entries whose
+    /// [`Metadata::is_deleted`] is `true`. It only works with
+    /// `versions(true)`, otherwise an [`ErrorKind::InvalidInput`] error
+    /// will be returned.
+    pub fn deleted(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_deleted(v));
+        self
+    }
+}
+
+impl Future for FutureList {
+    type Output = Result<Lister>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let deadline = self.0.deadline();
+        self.0.poll_unpin(cx).map(|res| {
+            res.map(|v| match deadline {
+                Some(deadline) => v.with_deadline(deadline),
+                None => v,
+            })
+        })
+    }
+}
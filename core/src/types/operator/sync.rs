@@ -0,0 +1,129 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::*;
+
+/// Status of one object synced by [`Operator::sync_to`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncStatus {
+    /// Object has been copied to destination.
+    Copied,
+    /// Object is not changed, so it's skipped.
+    Skipped,
+}
+
+/// Check if given metakey has been fetched in metadata.
+fn contains_metakey(meta: &Metadata, key: Metakey) -> bool {
+    let bit = meta.bit();
+    bit.contains(key) || bit.contains(Metakey::Complete)
+}
+
+/// Get the field of metadata only if it has been fetched.
+fn fetched<'a, T>(
+    meta: &'a Metadata,
+    key: Metakey,
+    f: impl FnOnce(&'a Metadata) -> Option<T>,
+) -> Option<T> {
+    if contains_metakey(meta, key) {
+        f(meta)
+    } else {
+        None
+    }
+}
+
+/// Check if metadata contains all fields required by [`need_sync`].
+pub(crate) fn contains_sync_metakey(meta: &Metadata) -> bool {
+    [
+        Metakey::ContentLength,
+        Metakey::LastModified,
+        Metakey::ContentMd5,
+    ]
+    .into_iter()
+    .all(|key| contains_metakey(meta, key))
+}
+
+/// Check if the object with `src` metadata needs to be copied to
+/// destination which has `dst` metadata.
+///
+/// - If both content md5 are present, compare them. Etags are never
+///   trusted as md5 since they are not md5 for multipart uploaded or
+///   SSE-KMS encrypted objects.
+/// - Otherwise, skip if content length is the same and destination is not
+///   older than source.
+///
+/// Fields not fetched in metadata are treated as unknown, and the object
+/// will be copied.
+pub(crate) fn need_sync(src: &Metadata, dst: &Metadata) -> bool {
+    if let (Some(l), Some(r)) = (
+        fetched(src, Metakey::ContentMd5, Metadata::content_md5).and_then(normalize_md5),
+        fetched(dst, Metakey::ContentMd5, Metadata::content_md5).and_then(normalize_md5),
+    ) {
+        return l != r;
+    }
+
+    match (
+        fetched(src, Metakey::ContentLength, Metadata::content_length_raw),
+        fetched(dst, Metakey::ContentLength, Metadata::content_length_raw),
+        fetched(src, Metakey::LastModified, Metadata::last_modified),
+        fetched(dst, Metakey::LastModified, Metadata::last_modified),
+    ) {
+        (Some(sl), Some(dl), Some(sm), Some(dm)) => sl != dl || dm < sm,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use chrono::Utc;
+
+    use super::*;
+
+    #[test]
+    fn test_need_sync() {
+        let old = Utc.timestamp_opt(1_000_000, 0).unwrap();
+        let new = Utc.timestamp_opt(2_000_000, 0).unwrap();
+        let meta = |len: u64, lm| {
+            Metadata::new(EntryMode::FILE)
+                .with_content_length(len)
+                .with_last_modified(lm)
+        };
+
+        assert!(!need_sync(&meta(1, old), &meta(1, new)));
+        assert!(!need_sync(&meta(1, old), &meta(1, old)));
+        assert!(need_sync(&meta(1, new), &meta(1, old)));
+        assert!(need_sync(&meta(1, old), &meta(2, new)));
+
+        let md5 = "d41d8cd98f00b204e9800998ecf8427e".to_string();
+        assert!(!need_sync(
+            &meta(1, new).with_content_md5(md5.clone()),
+            &meta(2, old).with_content_md5("1B2M2Y8AsgTpgAmY7PhCfg==".to_string())
+        ));
+        assert!(need_sync(
+            &meta(1, old).with_content_md5(md5.clone()),
+            &meta(1, new).with_content_md5("0cc175b9c0f1b6a831c399e269772661".to_string())
+        ));
+        // Etags that look like md5 are not trusted.
+        assert!(!need_sync(
+            &meta(1, old).with_etag(format!("\"{md5}\"")),
+            &meta(1, new).with_etag("\"0cc175b9c0f1b6a831c399e269772661\"".to_string())
+        ));
+
+        assert!(need_sync(&meta(1, old), &Metadata::new(EntryMode::FILE)));
+        assert!(need_sync(&Metadata::new(EntryMode::FILE), &meta(1, new)));
+    }
+}
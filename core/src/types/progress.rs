@@ -0,0 +1,238 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::io::SeekFrom;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use crate::raw::*;
+use crate::*;
+
+/// ProgressCallback will be called with the bytes transferred so far and the
+/// total bytes if known.
+///
+/// The callback is called in the IO loop every time data has been
+/// transferred, so it must return quickly. Users who need to do heavy work
+/// like rendering should send the progress to another task instead.
+#[derive(Clone)]
+pub struct ProgressCallback(Arc<dyn Fn(u64, Option<u64>) + Send + Sync>);
+
+impl Debug for ProgressCallback {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProgressCallback").finish_non_exhaustive()
+    }
+}
+
+impl ProgressCallback {
+    /// Create a new callback from given function.
+    pub fn new(f: impl Fn(u64, Option<u64>) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+
+    /// Call the callback with transferred and total bytes.
+    pub fn call(&self, transferred: u64, total: Option<u64>) {
+        (self.0)(transferred, total)
+    }
+}
+
+/// ProgressReader will report the bytes that have been read.
+pub(crate) struct ProgressReader<R> {
+    inner: R,
+    callback: ProgressCallback,
+    transferred: u64,
+    total: Option<u64>,
+}
+
+impl<R> ProgressReader<R> {
+    pub(crate) fn new(inner: R, callback: ProgressCallback, total: Option<u64>) -> Self {
+        Self {
+            inner,
+            callback,
+            transferred: 0,
+            total,
+        }
+    }
+
+    fn report(&mut self, n: usize) {
+        if n > 0 {
+            self.transferred += n as u64;
+            self.callback.call(self.transferred, self.total);
+        }
+    }
+}
+
+impl<R: oio::Read> oio::Read for ProgressReader<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        let res = self.inner.poll_read(cx, buf);
+        if let Poll::Ready(Ok(n)) = res {
+            self.report(n);
+        }
+        res
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        self.inner.poll_seek(cx, pos)
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        let res = self.inner.poll_next(cx);
+        if let Poll::Ready(Some(Ok(bs))) = &res {
+            self.report(bs.len());
+        }
+        res
+    }
+}
+
+/// ProgressWriter will report the bytes that have been written, and
+/// report again once the writer has been closed.
+pub(crate) struct ProgressWriter<W> {
+    inner: W,
+    callback: ProgressCallback,
+    transferred: u64,
+    total: Option<u64>,
+}
+
+impl<W> ProgressWriter<W> {
+    pub(crate) fn new(inner: W, callback: ProgressCallback, total: Option<u64>) -> Self {
+        Self {
+            inner,
+            callback,
+            transferred: 0,
+            total,
+        }
+    }
+
+    fn report(&mut self, n: u64) {
+        if n > 0 {
+            self.transferred += n;
+            self.callback.call(self.transferred, self.total);
+        }
+    }
+}
+
+#[async_trait]
+impl<W: oio::Write> oio::Write for ProgressWriter<W> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        let size = bs.len() as u64;
+        self.inner.write(bs).await?;
+        self.report(size);
+        Ok(())
+    }
+
+    async fn sink(&mut self, size: u64, s: oio::Streamer) -> Result<()> {
+        self.inner.sink(size, s).await?;
+        self.report(size);
+        Ok(())
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+
+    /// Report once more after close, so that users can tell the write
+    /// has completed even if all data is uploaded by close.
+    async fn close(&mut self) -> Result<()> {
+        self.inner.close().await?;
+        self.callback.call(self.transferred, self.total);
+        Ok(())
+    }
+
+    fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        self.inner.checkpoint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::raw::oio::ReadExt;
+
+    #[tokio::test]
+    async fn test_progress_reader() {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let collected = reports.clone();
+        let callback = ProgressCallback::new(move |n, total| {
+            collected.lock().unwrap().push((n, total));
+        });
+
+        let r = oio::Cursor::from(vec![0; 10]);
+        let mut r = ProgressReader::new(r, callback, Some(10));
+
+        let mut buf = vec![0; 4];
+        while r.read(&mut buf).await.unwrap() > 0 {}
+
+        assert_eq!(
+            reports.lock().unwrap().clone(),
+            vec![(4, Some(10)), (8, Some(10)), (10, Some(10))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_progress_writer() {
+        struct MockWriter;
+
+        #[async_trait]
+        impl oio::Write for MockWriter {
+            async fn write(&mut self, _: Bytes) -> Result<()> {
+                Ok(())
+            }
+
+            async fn sink(&mut self, _: u64, _: oio::Streamer) -> Result<()> {
+                Ok(())
+            }
+
+            async fn abort(&mut self) -> Result<()> {
+                Ok(())
+            }
+
+            async fn close(&mut self) -> Result<()> {
+                Ok(())
+            }
+        }
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let collected = reports.clone();
+        let callback = ProgressCallback::new(move |n, total| {
+            collected.lock().unwrap().push((n, total));
+        });
+
+        // One-shot write reports on write and completion.
+        let mut w = ProgressWriter::new(MockWriter, callback.clone(), Some(10));
+        oio::Write::write(&mut w, Bytes::from(vec![0; 10]))
+            .await
+            .unwrap();
+        oio::Write::close(&mut w).await.unwrap();
+        assert_eq!(
+            reports.lock().unwrap().clone(),
+            vec![(10, Some(10)), (10, Some(10))]
+        );
+
+        // Empty write still reports on completion.
+        reports.lock().unwrap().clear();
+        let mut w = ProgressWriter::new(MockWriter, callback, Some(0));
+        oio::Write::close(&mut w).await.unwrap();
+        assert_eq!(reports.lock().unwrap().clone(), vec![(0, Some(0))]);
+    }
+}
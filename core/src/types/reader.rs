@@ -20,15 +20,89 @@ use std::pin::Pin;
 use std::task::ready;
 use std::task::Context;
 use std::task::Poll;
+use std::time::Instant;
 
 use bytes::Bytes;
+use bytes::BytesMut;
+use futures::future::BoxFuture;
 use futures::AsyncRead;
 use futures::AsyncSeek;
+use futures::FutureExt;
 use futures::Stream;
 
+use super::deadline::DeadlineWrapper;
+use super::progress::ProgressReader;
+use crate::raw::oio::ReadExt;
 use crate::raw::*;
 use crate::*;
 
+/// Read given range of path by sending one range request for every
+/// `chunk` bytes.
+///
+/// The range will be resolved by an extra `stat`, and the etag returned by
+/// it will be used to make sure all chunks come from the same file. Services
+/// that can't read with range will ignore `chunk` and read the whole range
+/// at once.
+pub(crate) async fn read_in_chunks(
+    acc: FusedAccessor,
+    path: &str,
+    op: OpRead,
+    chunk: usize,
+) -> Result<(RpRead, oio::Reader)> {
+    if chunk == 0 {
+        return Err(
+            Error::new(ErrorKind::InvalidInput, "chunk size must be greater than 0")
+                .with_operation(Operation::Read)
+                .with_context("service", acc.info().scheme())
+                .with_context("path", path),
+        );
+    }
+
+    let capability = acc.info().capability();
+    if !capability.read_with_range {
+        return acc.read(path, op).await;
+    }
+
+    let mut args = OpStat::new();
+    if let Some(v) = op.if_match() {
+        args = args.with_if_match(v);
+    }
+    if let Some(v) = op.if_none_match() {
+        args = args.with_if_none_match(v);
+    }
+    if let Some(v) = op.version() {
+        args = args.with_version(v);
+    }
+    let meta = acc.stat(path, args).await?.into_metadata();
+
+    let total = meta.content_length();
+    let br = op.range();
+    let (offset, size) = match (br.offset(), br.size()) {
+        (Some(offset), size) => {
+            let offset = offset.min(total);
+            (offset, size.unwrap_or(u64::MAX).min(total - offset))
+        }
+        (None, Some(size)) => {
+            let size = size.min(total);
+            (total - size, size)
+        }
+        (None, None) => (0, total),
+    };
+
+    let op = match meta.etag() {
+        Some(etag) if op.if_match().is_none() && capability.read_with_if_match => {
+            op.with_if_match(etag)
+        }
+        _ => op,
+    };
+
+    let r = oio::into_chunked_read(acc, path, op, offset, size, chunk);
+    Ok((
+        RpRead::with_metadata(meta.with_content_length(size)),
+        Box::new(r) as oio::Reader,
+    ))
+}
+
 /// Reader is designed to read data from given path in an asynchronous
 /// manner.
 ///
@@ -67,13 +141,113 @@ impl Reader {
     /// We don't want to expose those details to users so keep this function
     /// in crate only.
     pub(crate) async fn create_dir(acc: FusedAccessor, path: &str, op: OpRead) -> Result<Self> {
-        let (_, r) = acc.read(path, op).await?;
+        let progress = op.progress().cloned();
+        let buffer = op.buffer();
+        let (rp, r) = match op.chunk() {
+            Some(chunk) => read_in_chunks(acc, path, op, chunk).await?,
+            None => acc.read(path, op).await?,
+        };
+
+        let r = match buffer {
+            Some(size) => Box::new(oio::into_read_ahead(r, size)) as oio::Reader,
+            None => r,
+        };
+        let r = match progress {
+            Some(cb) => {
+                let total = rp.metadata().content_length_raw();
+                Box::new(ProgressReader::new(r, cb, total)) as oio::Reader
+            }
+            None => r,
+        };
 
         Ok(Reader {
             inner: r,
             seek_state: SeekState::Init,
         })
     }
+
+    /// Make all following operations fail once the deadline is exceeded.
+    pub(crate) fn with_deadline(self, deadline: Instant) -> Self {
+        Reader {
+            inner: Box::new(DeadlineWrapper::new(self.inner, deadline)),
+            seek_state: self.seek_state,
+        }
+    }
+
+    /// Read data into the spare capacity of given buffer.
+    ///
+    /// This function will keep reading until the spare capacity of `buf` is
+    /// filled or EOF is reached, and returns the number of bytes appended.
+    /// `buf` will never be reallocated, so users can reuse the same buffer
+    /// across reads by calling `BytesMut::clear` or `BytesMut::split`.
+    ///
+    /// # Notes
+    ///
+    /// - Returning less bytes than the spare capacity means EOF is reached.
+    /// - Returning `0` means EOF or `buf` has no spare capacity.
+    /// - Reader will never read beyond the range it was created with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use bytes::BytesMut;
+    /// use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut buf = BytesMut::with_capacity(4096);
+    /// let mut r = op.reader("path/to/file").await?;
+    /// loop {
+    ///     buf.clear();
+    ///     if r.read_into(&mut buf).await? == 0 {
+    ///         break;
+    ///     }
+    ///     // Consume data in buf.
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_into(&mut self, buf: &mut BytesMut) -> Result<usize> {
+        let start = buf.len();
+        // Initialize the spare capacity without reallocating.
+        buf.resize(buf.capacity(), 0);
+
+        let res = self.read_exact_into(&mut buf[start..]).await;
+        let n = *res.as_ref().unwrap_or(&0);
+        buf.truncate(start + n);
+        res
+    }
+
+    /// Convert this reader into a stream of lines.
+    ///
+    /// Refer to [`Lines`] for more details.
+    pub fn lines(self) -> Lines {
+        Lines::new(LinesState::Reading(self))
+    }
+
+    /// Convert this reader into a stream of chunks.
+    ///
+    /// Refer to [`Chunks`] for more details.
+    pub fn chunks(self) -> Chunks {
+        Chunks { inner: self }
+    }
+
+    /// Read data to fill the whole `buf`.
+    ///
+    /// Returns the number of bytes filled, which is less than `buf.len()`
+    /// only if EOF is reached. Content after the filled part of `buf` is
+    /// left untouched.
+    pub async fn read_exact_into(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let mut filled = 0;
+        while filled < buf.len() {
+            let n = self.inner.read(&mut buf[filled..]).await?;
+            if n == 0 {
+                break;
+            }
+            filled += n;
+        }
+        Ok(filled)
+    }
 }
 
 impl oio::Read for Reader {
@@ -177,6 +351,157 @@ impl Stream for Reader {
     }
 }
 
+/// Lines is a stream of newline-delimited records read from a file.
+///
+/// Lines are split on `\n` across chunk boundaries, and the trailing
+/// `\r` of CRLF is removed. The last line will be returned even if the
+/// file doesn't end with a newline.
+///
+/// Data is consumed chunk by chunk, so the memory usage is bounded by the
+/// longest line plus one chunk, regardless of the file size.
+///
+/// # Examples
+///
+/// ```
+/// # use anyhow::Result;
+/// use futures::TryStreamExt;
+/// use opendal::Operator;
+/// # #[tokio::main]
+/// # async fn test(op: Operator) -> Result<()> {
+/// let mut lines = op.reader_with("path/to/file.jsonl").lines();
+/// while let Some(line) = lines.try_next().await? {
+///     // Handle the line.
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Lines {
+    state: LinesState,
+    buf: BytesMut,
+    /// The position in `buf` that has been searched for newline.
+    searched: usize,
+}
+
+enum LinesState {
+    Opening(BoxFuture<'static, Result<Reader>>),
+    Reading(Reader),
+    Done,
+}
+
+impl Lines {
+    /// Create lines from a future that will open the reader.
+    pub(crate) fn opening(fut: BoxFuture<'static, Result<Reader>>) -> Self {
+        Lines::new(LinesState::Opening(fut))
+    }
+
+    fn new(state: LinesState) -> Self {
+        Lines {
+            state,
+            buf: BytesMut::new(),
+            searched: 0,
+        }
+    }
+
+    /// Split a line of `len` bytes out of buf, `len` includes the newline.
+    fn split_line(&mut self, len: usize) -> Result<String> {
+        let mut line = self.buf.split_to(len);
+        self.searched = 0;
+
+        if line.ends_with(b"\n") {
+            line.truncate(line.len() - 1);
+        }
+        if line.ends_with(b"\r") {
+            line.truncate(line.len() - 1);
+        }
+
+        String::from_utf8(line.to_vec()).map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "line is not valid utf-8").set_source(err)
+        })
+    }
+}
+
+impl Stream for Lines {
+    type Item = Result<String>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.as_mut().get_mut();
+
+        loop {
+            match &mut this.state {
+                LinesState::Opening(fut) => match ready!(fut.poll_unpin(cx)) {
+                    Ok(r) => this.state = LinesState::Reading(r),
+                    Err(err) => {
+                        this.state = LinesState::Done;
+                        return Poll::Ready(Some(Err(err)));
+                    }
+                },
+                LinesState::Reading(r) => {
+                    if let Some(pos) = this.buf[this.searched..].iter().position(|b| *b == b'\n') {
+                        let len = this.searched + pos + 1;
+                        return Poll::Ready(Some(this.split_line(len)));
+                    }
+                    this.searched = this.buf.len();
+
+                    match ready!(oio::Read::poll_next(r, cx)) {
+                        Some(Ok(bs)) => this.buf.extend_from_slice(&bs),
+                        Some(Err(err)) => {
+                            this.state = LinesState::Done;
+                            return Poll::Ready(Some(Err(err)));
+                        }
+                        None => {
+                            this.state = LinesState::Done;
+                            if !this.buf.is_empty() {
+                                let len = this.buf.len();
+                                return Poll::Ready(Some(this.split_line(len)));
+                            }
+                        }
+                    }
+                }
+                LinesState::Done => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+/// Chunks is a stream of [`Bytes`] read from a file.
+///
+/// Chunks are returned as they are received from the service without any
+/// copy, empty chunks are skipped. For HTTP based services like s3, every
+/// chunk shares the allocation of the HTTP body, so they can be passed to
+/// downstream consumers directly.
+///
+/// # Examples
+///
+/// ```
+/// # use anyhow::Result;
+/// use futures::TryStreamExt;
+/// use opendal::Operator;
+/// # #[tokio::main]
+/// # async fn test(op: Operator) -> Result<()> {
+/// let mut chunks = op.read_chunks("path/to/file").await?;
+/// while let Some(bs) = chunks.try_next().await? {
+///     // Handle the chunk.
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Chunks {
+    inner: Reader,
+}
+
+impl Stream for Chunks {
+    type Item = Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match ready!(oio::Read::poll_next(&mut self.inner, cx)) {
+                Some(Ok(bs)) if bs.is_empty() => continue,
+                v => return Poll::Ready(v),
+            }
+        }
+    }
+}
+
 /// BlockingReader is designed to read data from given path in an blocking
 /// manner.
 pub struct BlockingReader {
@@ -259,14 +584,28 @@ impl Iterator for BlockingReader {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::VecDeque;
+    use std::io;
+    use std::task::Context;
+    use std::task::Poll;
+
+    use bytes::Bytes;
+    use bytes::BytesMut;
+    use futures::TryStreamExt;
     use rand::rngs::ThreadRng;
     use rand::Rng;
     use rand::RngCore;
     use tokio::io::AsyncReadExt;
     use tokio::io::AsyncSeekExt;
 
+    use super::Reader;
+    use super::SeekState;
+    use crate::raw::oio;
     use crate::services;
+    use crate::Error;
+    use crate::ErrorKind;
     use crate::Operator;
+    use crate::Result;
 
     fn gen_random_bytes() -> Vec<u8> {
         let mut rng = ThreadRng::default();
@@ -325,4 +664,165 @@ mod tests {
             .expect("read to end must succeed");
         assert_eq!(buf, content);
     }
+
+    #[tokio::test]
+    async fn test_reader_read_into() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+
+        let content = gen_random_bytes();
+        op.write(path, content.clone())
+            .await
+            .expect("write must succeed");
+
+        let mut reader = op.reader(path).await.unwrap();
+        let mut buf = BytesMut::with_capacity(4096);
+        let cap = buf.capacity();
+        let mut actual = Vec::with_capacity(content.len());
+        loop {
+            buf.clear();
+            let n = reader.read_into(&mut buf).await.expect("read must succeed");
+            assert_eq!(n, buf.len());
+            assert_eq!(buf.capacity(), cap, "buffer must not be reallocated");
+            if n == 0 {
+                break;
+            }
+            actual.extend_from_slice(&buf);
+        }
+        assert_eq!(actual, content);
+
+        // Partial fill at EOF.
+        let mut reader = op.reader_with(path).range(0..10).await.unwrap();
+        let mut buf = [0; 16];
+        let n = reader.read_exact_into(&mut buf).await.unwrap();
+        assert_eq!(n, 10.min(content.len()));
+        assert_eq!(&buf[..n], &content[..n]);
+    }
+
+    /// MockReader returns given chunks one by one in `poll_next`.
+    struct MockReader(VecDeque<Bytes>);
+
+    impl oio::Read for MockReader {
+        fn poll_read(&mut self, _: &mut Context<'_>, _: &mut [u8]) -> Poll<Result<usize>> {
+            Poll::Ready(Err(Error::new(
+                ErrorKind::Unsupported,
+                "mock reader doesn't support read",
+            )))
+        }
+
+        fn poll_seek(&mut self, _: &mut Context<'_>, _: io::SeekFrom) -> Poll<Result<u64>> {
+            Poll::Ready(Err(Error::new(
+                ErrorKind::Unsupported,
+                "mock reader doesn't support seeking",
+            )))
+        }
+
+        fn poll_next(&mut self, _: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+            Poll::Ready(self.0.pop_front().map(Ok))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reader_lines() {
+        let cases = vec![
+            ("empty", vec![], vec![]),
+            ("single line", vec!["hello"], vec!["hello"]),
+            (
+                "trailing newline",
+                vec!["hello\nworld\n"],
+                vec!["hello", "world"],
+            ),
+            (
+                "cross chunks",
+                vec!["hel", "lo\nwo", "rld\n", "!"],
+                vec!["hello", "world", "!"],
+            ),
+            (
+                "crlf",
+                vec!["hello\r", "\nworld\r\n\r\n"],
+                vec!["hello", "world", ""],
+            ),
+            ("empty lines", vec!["\n\n", "a"], vec!["", "", "a"]),
+        ];
+
+        for (name, chunks, expected) in cases {
+            let r = Reader {
+                inner: Box::new(MockReader(
+                    chunks
+                        .into_iter()
+                        .map(|s| Bytes::from_static(s.as_bytes()))
+                        .collect(),
+                )),
+                seek_state: SeekState::Init,
+            };
+            let lines: Vec<String> = r.lines().try_collect().await.expect("lines must succeed");
+            assert_eq!(lines, expected, "{name}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reader_chunks() {
+        let given = vec![
+            Bytes::from(vec![1; 1024]),
+            Bytes::new(),
+            Bytes::from(vec![2; 2048]),
+        ];
+        let r = Reader {
+            inner: Box::new(MockReader(given.clone().into())),
+            seek_state: SeekState::Init,
+        };
+
+        let chunks: Vec<Bytes> = r.chunks().try_collect().await.expect("chunks must succeed");
+        assert_eq!(chunks.len(), 2, "empty chunks must be skipped");
+        // Chunks must share the allocation with the underlying reader.
+        assert_eq!(chunks[0].as_ptr(), given[0].as_ptr());
+        assert_eq!(chunks[1].as_ptr(), given[2].as_ptr());
+    }
+
+    #[tokio::test]
+    async fn test_read_with_zero_chunk() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+        op.write(path, "hello").await.expect("write must succeed");
+
+        let err = op
+            .read_with(path)
+            .chunk(0)
+            .await
+            .expect_err("read must fail");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let err = op
+            .reader_with(path)
+            .chunk(0)
+            .await
+            .err()
+            .expect("reader must fail");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[tokio::test]
+    async fn test_future_reader_lines() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file.jsonl";
+
+        op.write(path, "{\"a\":1}\r\n{\"a\":2}")
+            .await
+            .expect("write must succeed");
+
+        let lines: Vec<String> = op
+            .reader_with(path)
+            .lines()
+            .try_collect()
+            .await
+            .expect("lines must succeed");
+        assert_eq!(lines, vec!["{\"a\":1}", "{\"a\":2}"]);
+
+        let err = op
+            .reader_with("not_exist")
+            .lines()
+            .try_collect::<Vec<_>>()
+            .await
+            .expect_err("lines must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
 }
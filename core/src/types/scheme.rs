@@ -20,6 +20,7 @@ use std::fmt::Formatter;
 use std::str::FromStr;
 
 use crate::Error;
+use crate::ErrorKind;
 
 /// Services that OpenDAL supports
 ///
@@ -66,6 +67,8 @@ pub enum Scheme {
     Memory,
     /// [mini-moka][crate::services::MiniMoka]: Mini Moka backend support.
     MiniMoka,
+    /// [mock][crate::services::Mock]: Mock service for unit tests.
+    Mock,
     /// [moka][crate::services::Moka]: moka backend support.
     Moka,
     /// [obs][crate::services::Obs]: Huawei Cloud OBS services.
@@ -118,6 +121,171 @@ impl Scheme {
     pub fn into_static(self) -> &'static str {
         self.into()
     }
+
+    /// Get the cargo features that enable this scheme.
+    ///
+    /// The first feature is the main feature of this scheme, others are
+    /// variants that enable it too, like `services-redis-rustls`.
+    /// [`Scheme::Custom`] returns an empty slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opendal::Scheme;
+    ///
+    /// assert_eq!(Scheme::S3.associated_features(), &["services-s3"]);
+    /// ```
+    pub fn associated_features(self) -> &'static [&'static str] {
+        match self {
+            Scheme::Azblob => &["services-azblob"],
+            Scheme::Azdfs => &["services-azdfs"],
+            Scheme::Cacache => &["services-cacache"],
+            Scheme::Cos => &["services-cos"],
+            Scheme::Dashmap => &["services-dashmap"],
+            Scheme::Dropbox => &["services-dropbox"],
+            Scheme::Etcd => &["services-etcd"],
+            Scheme::Fs => &["services-fs"],
+            Scheme::Ftp => &["services-ftp"],
+            Scheme::Gcs => &["services-gcs"],
+            Scheme::Gdrive => &["services-gdrive"],
+            Scheme::Ghac => &["services-ghac"],
+            Scheme::Hdfs => &["services-hdfs"],
+            Scheme::Http => &["services-http"],
+            Scheme::Ipfs => &["services-ipfs"],
+            Scheme::Ipmfs => &["services-ipmfs"],
+            Scheme::Memcached => &["services-memcached"],
+            Scheme::Memory => &["services-memory"],
+            Scheme::MiniMoka => &["services-mini-moka"],
+            Scheme::Mock => &["services-mock"],
+            Scheme::Moka => &["services-moka"],
+            Scheme::Obs => &["services-obs"],
+            Scheme::Onedrive => &["services-onedrive"],
+            Scheme::Oss => &["services-oss"],
+            Scheme::Persy => &["services-persy"],
+            Scheme::Redb => &["services-redb"],
+            Scheme::Redis => &[
+                "services-redis",
+                "services-redis-native-tls",
+                "services-redis-rustls",
+            ],
+            Scheme::Rocksdb => &["services-rocksdb"],
+            Scheme::S3 => &["services-s3"],
+            Scheme::Sftp => &["services-sftp"],
+            Scheme::Sled => &["services-sled"],
+            Scheme::Supabase => &["services-supabase"],
+            Scheme::Tikv => &["services-tikv"],
+            Scheme::VercelArtifacts => &["services-vercel-artifacts"],
+            Scheme::Wasabi => &["services-wasabi"],
+            Scheme::Webdav => &["services-webdav"],
+            Scheme::Webhdfs => &["services-webhdfs"],
+            Scheme::Custom(_) => &[],
+        }
+    }
+
+    /// Get the optional dependencies that will be pulled in by the main
+    /// feature of this scheme.
+    pub fn dependencies(self) -> &'static [&'static str] {
+        match self {
+            Scheme::Azblob => &["sha2", "hmac", "reqsign"],
+            Scheme::Azdfs => &["reqsign"],
+            Scheme::Cacache => &["cacache"],
+            Scheme::Cos => &["reqsign"],
+            Scheme::Dashmap => &["dashmap"],
+            Scheme::Dropbox => &[],
+            Scheme::Etcd => &["etcd-client"],
+            Scheme::Fs => &["xattr", "libc"],
+            Scheme::Ftp => &["suppaftp", "lazy-regex", "bb8", "async-tls"],
+            Scheme::Gcs => &["reqsign"],
+            Scheme::Gdrive => &[],
+            Scheme::Ghac => &[],
+            Scheme::Hdfs => &["hdrs"],
+            Scheme::Http => &["flate2"],
+            Scheme::Ipfs => &["prost"],
+            Scheme::Ipmfs => &[],
+            Scheme::Memcached => &["bb8"],
+            Scheme::Memory => &[],
+            Scheme::MiniMoka => &["mini-moka"],
+            Scheme::Mock => &[],
+            Scheme::Moka => &["moka"],
+            Scheme::Obs => &["reqsign"],
+            Scheme::Onedrive => &[],
+            Scheme::Oss => &["reqsign"],
+            Scheme::Persy => &["persy"],
+            Scheme::Redb => &["redb"],
+            Scheme::Redis => &["redis"],
+            Scheme::Rocksdb => &["rocksdb"],
+            Scheme::S3 => &["reqsign"],
+            Scheme::Sftp => &["bb8", "openssh", "openssh-sftp-client", "dirs"],
+            Scheme::Sled => &["sled"],
+            Scheme::Supabase => &[],
+            Scheme::Tikv => &["tikv-client"],
+            Scheme::VercelArtifacts => &[],
+            Scheme::Wasabi => &["reqsign"],
+            Scheme::Webdav => &["flate2"],
+            Scheme::Webhdfs => &[],
+            Scheme::Custom(_) => &[],
+        }
+    }
+
+    /// Check if this scheme is enabled in current build.
+    ///
+    /// [`Scheme::Custom`] is always treated as enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opendal::Scheme;
+    ///
+    /// let scheme = Scheme::Hdfs;
+    /// if !scheme.is_enabled() {
+    ///     println!(
+    ///         "scheme {scheme} is not enabled, please enable feature {}",
+    ///         scheme.associated_features()[0]
+    ///     );
+    /// }
+    /// ```
+    pub fn is_enabled(self) -> bool {
+        match self {
+            Scheme::Azblob => cfg!(feature = "services-azblob"),
+            Scheme::Azdfs => cfg!(feature = "services-azdfs"),
+            Scheme::Cacache => cfg!(feature = "services-cacache"),
+            Scheme::Cos => cfg!(feature = "services-cos"),
+            Scheme::Dashmap => cfg!(feature = "services-dashmap"),
+            Scheme::Dropbox => cfg!(feature = "services-dropbox"),
+            Scheme::Etcd => cfg!(feature = "services-etcd"),
+            Scheme::Fs => cfg!(feature = "services-fs"),
+            Scheme::Ftp => cfg!(feature = "services-ftp"),
+            Scheme::Gcs => cfg!(feature = "services-gcs"),
+            Scheme::Gdrive => cfg!(feature = "services-gdrive"),
+            Scheme::Ghac => cfg!(feature = "services-ghac"),
+            Scheme::Hdfs => cfg!(feature = "services-hdfs"),
+            Scheme::Http => cfg!(feature = "services-http"),
+            Scheme::Ipfs => cfg!(feature = "services-ipfs"),
+            Scheme::Ipmfs => cfg!(feature = "services-ipmfs"),
+            Scheme::Memcached => cfg!(feature = "services-memcached"),
+            Scheme::Memory => cfg!(feature = "services-memory"),
+            Scheme::MiniMoka => cfg!(feature = "services-mini-moka"),
+            Scheme::Mock => cfg!(feature = "services-mock"),
+            Scheme::Moka => cfg!(feature = "services-moka"),
+            Scheme::Obs => cfg!(feature = "services-obs"),
+            Scheme::Onedrive => cfg!(feature = "services-onedrive"),
+            Scheme::Oss => cfg!(feature = "services-oss"),
+            Scheme::Persy => cfg!(feature = "services-persy"),
+            Scheme::Redb => cfg!(feature = "services-redb"),
+            Scheme::Redis => cfg!(feature = "services-redis"),
+            Scheme::Rocksdb => cfg!(feature = "services-rocksdb"),
+            Scheme::S3 => cfg!(feature = "services-s3"),
+            Scheme::Sftp => cfg!(feature = "services-sftp"),
+            Scheme::Sled => cfg!(feature = "services-sled"),
+            Scheme::Supabase => cfg!(feature = "services-supabase"),
+            Scheme::Tikv => cfg!(feature = "services-tikv"),
+            Scheme::VercelArtifacts => cfg!(feature = "services-vercel-artifacts"),
+            Scheme::Wasabi => cfg!(feature = "services-wasabi"),
+            Scheme::Webdav => cfg!(feature = "services-webdav"),
+            Scheme::Webhdfs => cfg!(feature = "services-webhdfs"),
+            Scheme::Custom(_) => true,
+        }
+    }
 }
 
 impl Default for Scheme {
@@ -132,47 +300,118 @@ impl Display for Scheme {
     }
 }
 
+/// All names (including aliases) that could be parsed into [`Scheme`].
+///
+/// The first name of every scheme must be the same as its `Display`.
+const SCHEME_NAMES: &[(&str, Scheme)] = &[
+    ("azblob", Scheme::Azblob),
+    ("azure", Scheme::Azblob),
+    ("azdfs", Scheme::Azdfs),
+    ("abfs", Scheme::Azdfs),
+    ("cacache", Scheme::Cacache),
+    ("cos", Scheme::Cos),
+    ("dashmap", Scheme::Dashmap),
+    ("dropbox", Scheme::Dropbox),
+    ("etcd", Scheme::Etcd),
+    ("fs", Scheme::Fs),
+    ("file", Scheme::Fs),
+    ("ftp", Scheme::Ftp),
+    ("ftps", Scheme::Ftp),
+    ("gcs", Scheme::Gcs),
+    ("gs", Scheme::Gcs),
+    ("gdrive", Scheme::Gdrive),
+    ("ghac", Scheme::Ghac),
+    ("hdfs", Scheme::Hdfs),
+    ("http", Scheme::Http),
+    ("https", Scheme::Http),
+    ("ipfs", Scheme::Ipfs),
+    ("ipns", Scheme::Ipfs),
+    ("ipmfs", Scheme::Ipmfs),
+    ("memcached", Scheme::Memcached),
+    ("memory", Scheme::Memory),
+    ("mini_moka", Scheme::MiniMoka),
+    ("mock", Scheme::Mock),
+    ("moka", Scheme::Moka),
+    ("obs", Scheme::Obs),
+    ("onedrive", Scheme::Onedrive),
+    ("oss", Scheme::Oss),
+    ("persy", Scheme::Persy),
+    ("redb", Scheme::Redb),
+    ("redis", Scheme::Redis),
+    ("rocksdb", Scheme::Rocksdb),
+    ("s3", Scheme::S3),
+    ("sftp", Scheme::Sftp),
+    ("sled", Scheme::Sled),
+    ("supabase", Scheme::Supabase),
+    ("tikv", Scheme::Tikv),
+    ("vercel_artifacts", Scheme::VercelArtifacts),
+    ("wasabi", Scheme::Wasabi),
+    ("webdav", Scheme::Webdav),
+    ("webhdfs", Scheme::Webhdfs),
+];
+
+/// Parse scheme from string.
+///
+/// - Names are case-insensitive and `-` is treated as `_`, so `Mini-Moka`
+///   could be parsed into [`Scheme::MiniMoka`].
+/// - Common aliases are accepted, for example: `azure` for `azblob`, `gs`
+///   for `gcs` and `file` for `fs`.
+/// - Unknown names will return a `ConfigInvalid` error, names that are
+///   within a few edits of a known scheme will have near-matches listed in
+///   the `did_you_mean` context.
+/// - [`Scheme::Custom`] can't be parsed, please construct it directly.
 impl FromStr for Scheme {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = s.to_lowercase();
-        match s.as_str() {
-            "azblob" => Ok(Scheme::Azblob),
-            "azdfs" => Ok(Scheme::Azdfs),
-            "cacache" => Ok(Scheme::Cacache),
-            "cos" => Ok(Scheme::Cos),
-            "dashmap" => Ok(Scheme::Dashmap),
-            "etcd" => Ok(Scheme::Etcd),
-            "fs" => Ok(Scheme::Fs),
-            "gcs" => Ok(Scheme::Gcs),
-            "ghac" => Ok(Scheme::Ghac),
-            "hdfs" => Ok(Scheme::Hdfs),
-            "http" | "https" => Ok(Scheme::Http),
-            "ftp" | "ftps" => Ok(Scheme::Ftp),
-            "ipfs" | "ipns" => Ok(Scheme::Ipfs),
-            "ipmfs" => Ok(Scheme::Ipmfs),
-            "memcached" => Ok(Scheme::Memcached),
-            "memory" => Ok(Scheme::Memory),
-            "mini_moka" => Ok(Scheme::MiniMoka),
-            "moka" => Ok(Scheme::Moka),
-            "obs" => Ok(Scheme::Obs),
-            "persy" => Ok(Scheme::Persy),
-            "redis" => Ok(Scheme::Redis),
-            "rocksdb" => Ok(Scheme::Rocksdb),
-            "s3" => Ok(Scheme::S3),
-            "sled" => Ok(Scheme::Sled),
-            "supabase" => Ok(Scheme::Supabase),
-            "oss" => Ok(Scheme::Oss),
-            "vercel_artifacts" => Ok(Scheme::VercelArtifacts),
-            "wasabi" => Ok(Scheme::Wasabi),
-            "webdav" => Ok(Scheme::Webdav),
-            "webhdfs" => Ok(Scheme::Webhdfs),
-            _ => Ok(Scheme::Custom(Box::leak(s.into_boxed_str()))),
+        let name = s.trim().to_lowercase().replace('-', "_");
+
+        if let Some((_, scheme)) = SCHEME_NAMES.iter().find(|(v, _)| *v == name) {
+            return Ok(*scheme);
+        }
+
+        let near_matches: Vec<&str> = SCHEME_NAMES
+            .iter()
+            .map(|(v, _)| *v)
+            .filter(|v| is_near_match(&name, v))
+            .collect();
+
+        let mut err =
+            Error::new(ErrorKind::ConfigInvalid, "scheme is unknown").with_context("scheme", s);
+        if !near_matches.is_empty() {
+            err = err.with_context("did_you_mean", near_matches.join(", "));
         }
+        Err(err)
     }
 }
 
+/// Check if `input` is near to `name` by edit distance: one edit for short
+/// names and two edits for names longer than five chars.
+fn is_near_match(input: &str, name: &str) -> bool {
+    if input.is_empty() {
+        return false;
+    }
+    let max_distance = if name.len() > 5 { 2 } else { 1 };
+
+    // Levenshtein distance with a single row.
+    let name: Vec<char> = name.chars().collect();
+    let mut row: Vec<usize> = (0..=name.len()).collect();
+    for (i, a) in input.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, b) in name.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = if a == *b {
+                prev
+            } else {
+                1 + prev.min(row[j]).min(cur)
+            };
+            prev = cur;
+        }
+    }
+    row[name.len()] <= max_distance
+}
+
 impl From<Scheme> for &'static str {
     fn from(v: Scheme) -> Self {
         match v {
@@ -193,6 +432,7 @@ impl From<Scheme> for &'static str {
             Scheme::Memcached => "memcached",
             Scheme::Memory => "memory",
             Scheme::MiniMoka => "mini_moka",
+            Scheme::Mock => "mock",
             Scheme::Moka => "moka",
             Scheme::Obs => "obs",
             Scheme::Onedrive => "onedrive",
@@ -222,3 +462,78 @@ impl From<Scheme> for String {
         v.into_static().to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scheme_from_str() {
+        assert_eq!("S3".parse::<Scheme>().unwrap(), Scheme::S3);
+        assert_eq!("azure".parse::<Scheme>().unwrap(), Scheme::Azblob);
+        assert_eq!("gs".parse::<Scheme>().unwrap(), Scheme::Gcs);
+        assert_eq!("mini-moka".parse::<Scheme>().unwrap(), Scheme::MiniMoka);
+
+        let err = "gcss".parse::<Scheme>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        let msg = err.to_string();
+        assert!(msg.contains("did_you_mean"));
+        assert!(msg.contains("gcs"));
+
+        let err = "webhfds".parse::<Scheme>().unwrap_err();
+        assert!(err.to_string().contains("webhdfs"));
+
+        let err = "not-exist-at-all".parse::<Scheme>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        assert!(!err.to_string().contains("did_you_mean"));
+    }
+
+    #[test]
+    fn test_scheme_display_round_trip() {
+        for (_, scheme) in SCHEME_NAMES {
+            assert_eq!(scheme.to_string().parse::<Scheme>().unwrap(), *scheme);
+        }
+    }
+
+    #[test]
+    fn test_scheme_associated_features() {
+        for (_, scheme) in SCHEME_NAMES {
+            let features = scheme.associated_features();
+            assert_eq!(
+                features[0],
+                format!("services-{}", scheme.into_static().replace('_', "-")),
+            );
+        }
+
+        assert!(Scheme::Memory.is_enabled());
+        assert!(Scheme::Custom("custom").is_enabled());
+        assert!(Scheme::Custom("custom").associated_features().is_empty());
+        assert_eq!(Scheme::S3.dependencies(), &["reqsign"]);
+    }
+
+    /// Make sure `dependencies` is in sync with the features in Cargo.toml.
+    #[test]
+    fn test_scheme_dependencies_match_cargo_features() {
+        let manifest = include_str!("../../Cargo.toml");
+
+        for (_, scheme) in SCHEME_NAMES {
+            let feature = scheme.associated_features()[0];
+            let start = manifest
+                .find(&format!("\n{feature} = ["))
+                .unwrap_or_else(|| panic!("feature {feature} must be defined"));
+            let body = &manifest[start..];
+            let body = &body[body.find('[').unwrap() + 1..body.find(']').unwrap()];
+
+            let mut expected: Vec<&str> = body
+                .split(',')
+                .map(|v| v.trim().trim_matches('"'))
+                .filter(|v| !v.is_empty() && !v.contains('/') && !v.contains('?'))
+                .map(|v| v.strip_prefix("dep:").unwrap_or(v))
+                .collect();
+            let mut actual = scheme.dependencies().to_vec();
+            expected.sort_unstable();
+            actual.sort_unstable();
+            assert_eq!(actual, expected, "dependencies of {scheme} are out of date");
+        }
+    }
+}
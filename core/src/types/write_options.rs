@@ -0,0 +1,243 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+
+use crate::raw::*;
+
+/// WriteOptions are the operator-wide default options of write operations.
+///
+/// `write_with` and `writer_with` will start with these options, and
+/// options set on them will override the defaults. Only options that make
+/// sense for every write can be set here, options like `content_length`,
+/// `append` and `resume` must be set per write.
+///
+/// # Examples
+///
+/// ```
+/// use opendal::WriteOptions;
+///
+/// let opts = WriteOptions::new()
+///     .with_content_type("application/octet-stream")
+///     .with_chunk(16 * 1024 * 1024)
+///     .with_concurrent(4);
+/// assert_eq!(opts.chunk(), Some(16 * 1024 * 1024));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WriteOptions {
+    content_type: Option<String>,
+    content_disposition: Option<String>,
+    cache_control: Option<String>,
+    content_encoding: Option<String>,
+    content_language: Option<String>,
+    storage_class: Option<String>,
+    headers: Vec<(String, String)>,
+    tags: Option<HashMap<String, String>>,
+    user_metadata: Option<HashMap<String, String>>,
+    chunk: Option<usize>,
+    concurrent: Option<usize>,
+}
+
+impl WriteOptions {
+    /// Create a new `WriteOptions` without any options set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the default content type.
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Set the default content type.
+    pub fn with_content_type(mut self, v: &str) -> Self {
+        self.content_type = Some(v.to_string());
+        self
+    }
+
+    /// Get the default content disposition.
+    pub fn content_disposition(&self) -> Option<&str> {
+        self.content_disposition.as_deref()
+    }
+
+    /// Set the default content disposition.
+    pub fn with_content_disposition(mut self, v: &str) -> Self {
+        self.content_disposition = Some(v.to_string());
+        self
+    }
+
+    /// Get the default cache control.
+    pub fn cache_control(&self) -> Option<&str> {
+        self.cache_control.as_deref()
+    }
+
+    /// Set the default cache control.
+    pub fn with_cache_control(mut self, v: &str) -> Self {
+        self.cache_control = Some(v.to_string());
+        self
+    }
+
+    /// Get the default content encoding.
+    pub fn content_encoding(&self) -> Option<&str> {
+        self.content_encoding.as_deref()
+    }
+
+    /// Set the default content encoding.
+    pub fn with_content_encoding(mut self, v: &str) -> Self {
+        self.content_encoding = Some(v.to_string());
+        self
+    }
+
+    /// Get the default content language.
+    pub fn content_language(&self) -> Option<&str> {
+        self.content_language.as_deref()
+    }
+
+    /// Set the default content language.
+    pub fn with_content_language(mut self, v: &str) -> Self {
+        self.content_language = Some(v.to_string());
+        self
+    }
+
+    /// Get the default storage class.
+    pub fn storage_class(&self) -> Option<&str> {
+        self.storage_class.as_deref()
+    }
+
+    /// Set the default storage class.
+    pub fn with_storage_class(mut self, v: &str) -> Self {
+        self.storage_class = Some(v.to_string());
+        self
+    }
+
+    /// Get the default custom headers.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Add a default custom header.
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Get the default tags.
+    pub fn tags(&self) -> Option<&HashMap<String, String>> {
+        self.tags.as_ref()
+    }
+
+    /// Set the default tags.
+    pub fn with_tags<K, V>(mut self, tags: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.tags = Some(
+            tags.into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        );
+        self
+    }
+
+    /// Get the default user metadata.
+    pub fn user_metadata(&self) -> Option<&HashMap<String, String>> {
+        self.user_metadata.as_ref()
+    }
+
+    /// Set the default user metadata.
+    pub fn with_user_metadata<K, V>(mut self, data: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.user_metadata = Some(
+            data.into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        );
+        self
+    }
+
+    /// Get the default chunk size.
+    pub fn chunk(&self) -> Option<usize> {
+        self.chunk
+    }
+
+    /// Set the default chunk size.
+    ///
+    /// Services that upload data in parts will use it as the part size.
+    /// Read [`FutureWriter::chunk`](crate::operator_futures::FutureWriter::chunk)
+    /// for more details.
+    pub fn with_chunk(mut self, size: usize) -> Self {
+        self.chunk = Some(size);
+        self
+    }
+
+    /// Get the default max number of parts to upload concurrently.
+    pub fn concurrent(&self) -> Option<usize> {
+        self.concurrent
+    }
+
+    /// Set the default max number of parts to upload concurrently.
+    ///
+    /// Read [`FutureWriter::concurrent`](crate::operator_futures::FutureWriter::concurrent)
+    /// for more details.
+    pub fn with_concurrent(mut self, n: usize) -> Self {
+        self.concurrent = Some(n);
+        self
+    }
+
+    /// Build the args that a write operation starts with.
+    pub(crate) fn to_op_write(&self) -> OpWrite {
+        let mut op = OpWrite::new();
+        if let Some(v) = &self.content_type {
+            op = op.with_content_type(v);
+        }
+        if let Some(v) = &self.content_disposition {
+            op = op.with_content_disposition(v);
+        }
+        if let Some(v) = &self.cache_control {
+            op = op.with_cache_control(v);
+        }
+        if let Some(v) = &self.content_encoding {
+            op = op.with_content_encoding(v);
+        }
+        if let Some(v) = &self.content_language {
+            op = op.with_content_language(v);
+        }
+        if let Some(v) = &self.storage_class {
+            op = op.with_storage_class(v);
+        }
+        for (k, v) in &self.headers {
+            op = op.with_header(k, v);
+        }
+        if let Some(v) = &self.tags {
+            op = op.with_tags(v.clone());
+        }
+        if let Some(v) = &self.user_metadata {
+            op = op.with_user_metadata(v.clone());
+        }
+        if let Some(v) = self.chunk {
+            op = op.with_chunk(v);
+        }
+        if let Some(v) = self.concurrent {
+            op = op.with_concurrent(v);
+        }
+        op
+    }
+}
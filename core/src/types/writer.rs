@@ -15,19 +15,32 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::fmt::Debug;
 use std::fmt::Display;
+use std::fmt::Formatter;
 use std::io;
 use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
 use std::task::ready;
 use std::task::Context;
 use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
 
 use bytes::Bytes;
 use futures::future::BoxFuture;
 use futures::AsyncWrite;
 use futures::FutureExt;
 use futures::TryStreamExt;
+use serde::Deserialize;
+use serde::Serialize;
 
+use super::checksum::validate_checksum;
+use super::checksum::ChecksumWriter;
+use super::checksum::Hasher;
+use super::deadline::DeadlineWrapper;
+use super::progress::ProgressWriter;
 use crate::raw::oio::Write;
 use crate::raw::*;
 use crate::*;
@@ -40,6 +53,11 @@ use crate::*;
 /// Please make sure either `close` or `abort` has been called before
 /// dropping the writer otherwise the data could be lost.
 ///
+/// Dropping a writer without `close` or `abort` will NOT clean up the
+/// data that has been uploaded. For services that write via multipart
+/// uploads (like s3), the uploaded parts will be left on server side and
+/// keep being charged until they are aborted by `abort` or lifecycle rules.
+///
 /// ## Notes
 ///
 /// Writer can be used in two ways:
@@ -56,6 +74,7 @@ use crate::*;
 /// after `close` has been called.
 pub struct Writer {
     state: State,
+    checksum: Option<Arc<Mutex<Hasher>>>,
 }
 
 /// # Safety
@@ -72,13 +91,40 @@ impl Writer {
     /// We don't want to expose those details to users so keep this function
     /// in crate only.
     pub(crate) async fn create(acc: FusedAccessor, path: &str, op: OpWrite) -> Result<Self> {
+        validate_checksum(Operation::Write, &op)?;
+
+        let progress = op.progress().cloned();
+        let total = op.content_length();
+        let checksum = op
+            .checksum()
+            .map(|algo| Arc::new(Mutex::new(Hasher::new(algo))));
         let (_, w) = acc.write(path, op).await?;
 
+        let w = match progress {
+            Some(cb) => Box::new(ProgressWriter::new(w, cb, total)) as oio::Writer,
+            None => w,
+        };
+        let w = match &checksum {
+            Some(hasher) => Box::new(ChecksumWriter::new(w, hasher.clone())) as oio::Writer,
+            None => w,
+        };
+
         Ok(Writer {
             state: State::Idle(Some(w)),
+            checksum,
         })
     }
 
+    /// Make all following operations fail once the deadline is exceeded.
+    pub(crate) fn with_deadline(mut self, deadline: Instant) -> Self {
+        if let State::Idle(w) = &mut self.state {
+            *w = w
+                .take()
+                .map(|w| Box::new(DeadlineWrapper::new(w, deadline)) as oio::Writer);
+        }
+        self
+    }
+
     /// Write into inner writer.
     pub async fn write(&mut self, bs: impl Into<Bytes>) -> Result<()> {
         if let State::Idle(Some(w)) = &mut self.state {
@@ -184,8 +230,32 @@ impl Writer {
         }
     }
 
+    /// Get the checkpoint of this writer, which can be used to resume the
+    /// upload later via `Operator::writer_with(path).resume(checkpoint)`.
+    ///
+    /// The checkpoint is available once the multipart upload has been
+    /// initiated, even if no part has been uploaded yet. It only covers
+    /// completed parts: data still buffered in the writer or parts being
+    /// uploaded are not included, so users should resume writing from
+    /// [`WriteCheckpoint::size`].
+    ///
+    /// Returns `None` if the service doesn't support resume (see
+    /// [`Capability::write_with_resume`]), the upload hasn't been initiated
+    /// yet, or a write or close is in progress.
+    pub fn checkpoint(&self) -> Option<WriteCheckpoint> {
+        if let State::Idle(Some(w)) = &self.state {
+            w.checkpoint()
+        } else {
+            None
+        }
+    }
+
     /// Abort the writer and clean up all written data.
     ///
+    /// Unlike `close` which commits the data, `abort` will discard all
+    /// data that has been written, including the server side state like
+    /// multipart uploads on s3 (via `AbortMultipartUpload`).
+    ///
     /// ## Notes
     ///
     /// Abort should only be called when the writer is not closed or
@@ -203,13 +273,34 @@ impl Writer {
 
     /// Close the writer and make sure all data have been committed.
     ///
+    /// Returns the metadata of the written file. Only `mode` is set by
+    /// default. If the checksum has been requested via
+    /// `Operator::writer_with(path).checksum(algo)`, the checksum computed
+    /// over all written bytes will be set too:
+    ///
+    /// - `ChecksumAlgo::Md5`: [`Metadata::content_md5`]
+    /// - `ChecksumAlgo::Sha256`: [`Metadata::checksum_sha256`], requires
+    ///   feature `checksum-sha256`.
+    /// - `ChecksumAlgo::Crc32c`: [`Metadata::checksum_crc32c`]
+    ///
     /// ## Notes
     ///
     /// Close should only be called when the writer is not closed or
     /// aborted, otherwise an unexpected error could be returned.
-    pub async fn close(&mut self) -> Result<()> {
+    ///
+    /// For services that upload data in parts (like multipart uploads on
+    /// s3), the etag of the written file is NOT the md5 of the content.
+    /// Use the checksum returned here instead to record the content hash.
+    pub async fn close(&mut self) -> Result<Metadata> {
         if let State::Idle(Some(w)) = &mut self.state {
-            w.close().await
+            w.close().await?;
+
+            let mut meta = Metadata::new(EntryMode::FILE);
+            if let Some(hasher) = &self.checksum {
+                let hasher = hasher.lock().expect("lock must succeed").clone();
+                hasher.finish(&mut meta);
+            }
+            Ok(meta)
         } else {
             unreachable!(
                 "writer state invalid while close, expect Idle, actual {}",
@@ -226,7 +317,7 @@ enum State {
 }
 
 impl Display for State {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             State::Idle(_) => write!(f, "Idle"),
             State::Write(_) => write!(f, "Write"),
@@ -383,6 +474,8 @@ impl BlockingWriter {
     /// We don't want to expose those details to users so keep this function
     /// in crate only.
     pub(crate) fn create(acc: FusedAccessor, path: &str, op: OpWrite) -> Result<Self> {
+        validate_checksum(Operation::BlockingWrite, &op)?;
+
         let (_, w) = acc.blocking_write(path, op)?;
 
         Ok(BlockingWriter { inner: w })
@@ -412,3 +505,97 @@ impl io::Write for BlockingWriter {
         Ok(())
     }
 }
+
+/// WritePartStats carries the stats of a part that has been flushed by
+/// services that upload data in parts (like multipart uploads on s3).
+///
+/// Users can collect them via `Operator::writer_with(path).on_part(..)` to
+/// tune the chunk size and figure out whether part uploads are the bottleneck.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct WritePartStats {
+    /// The number of the part, starting from 0.
+    pub part_number: usize,
+    /// The size of the part in bytes.
+    pub size: u64,
+    /// The time spent on uploading this part.
+    pub elapsed: Duration,
+    /// The number of parts that are still being uploaded after this part.
+    ///
+    /// Only writers that upload parts concurrently could report a value
+    /// larger than `0`.
+    pub parts_in_flight: usize,
+    /// The bytes still buffered in writer after this part has been flushed.
+    pub buffered: u64,
+}
+
+/// WritePartCallback will be called every time a part has been flushed.
+#[derive(Clone)]
+pub struct WritePartCallback(Arc<dyn Fn(WritePartStats) + Send + Sync>);
+
+impl Debug for WritePartCallback {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WritePartCallback").finish_non_exhaustive()
+    }
+}
+
+impl WritePartCallback {
+    /// Create a new callback from given function.
+    pub fn new(f: impl Fn(WritePartStats) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+
+    /// Call the callback with given stats.
+    ///
+    /// The callback is called in the IO loop, so it should return quickly.
+    pub fn call(&self, stats: WritePartStats) {
+        (self.0)(stats)
+    }
+}
+
+/// WriteCheckpoint records the progress of an in-progress multipart upload,
+/// which can be used to resume the upload even after the process restarts.
+///
+/// Users can get a checkpoint via [`Writer::checkpoint`], persist it (it
+/// implements `Serialize` and `Deserialize`), and resume the upload via
+/// `Operator::writer_with(path).resume(checkpoint)` with the data starting
+/// from [`WriteCheckpoint::size`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WriteCheckpoint {
+    upload_id: String,
+    parts: Vec<(usize, String)>,
+    size: u64,
+}
+
+impl WriteCheckpoint {
+    /// Create a new checkpoint.
+    ///
+    /// - `upload_id` is the id of the multipart upload.
+    /// - `parts` are the part numbers and etags of uploaded parts.
+    /// - `size` is the total size of uploaded parts.
+    pub fn new(upload_id: &str, parts: Vec<(usize, String)>, size: u64) -> Self {
+        Self {
+            upload_id: upload_id.to_string(),
+            parts,
+            size,
+        }
+    }
+
+    /// Get the upload id of this checkpoint.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+
+    /// Get the part numbers and etags of uploaded parts.
+    pub fn parts(&self) -> &[(usize, String)] {
+        &self.parts
+    }
+
+    /// Get the size of data that has been uploaded.
+    ///
+    /// Data buffered in writer is not included, so users should resume
+    /// writing from this offset.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
@@ -34,7 +34,9 @@ pub fn behavior_blocking_list_tests(op: &Operator) -> Vec<Trial> {
         op,
         test_blocking_list_dir,
         test_blocking_list_non_exist_dir,
+        test_blocking_list_sorted,
         test_blocking_scan,
+        test_blocking_stat_with_treat_prefix_as_dir,
         test_blocking_remove_all
     )
 }
@@ -83,6 +85,31 @@ pub fn test_blocking_list_non_exist_dir(op: BlockingOperator) -> Result<()> {
     Ok(())
 }
 
+/// List with sorted should return entries in lexicographic order.
+pub fn test_blocking_list_sorted(op: BlockingOperator) -> Result<()> {
+    let parent = format!("{}/", uuid::Uuid::new_v4());
+    op.create_dir(&parent)?;
+
+    let mut expected: Vec<String> = (0..=20).map(|num| format!("{parent}file-{num}")).collect();
+
+    for path in expected.iter() {
+        op.write(path, "test_blocking_list_sorted")
+            .expect("write must succeed");
+    }
+
+    let ds = op.list_with(&parent).sorted(true).call()?;
+    let mut actual = vec![];
+    for de in ds {
+        actual.push(de?.path().to_string());
+    }
+    expected.sort_unstable();
+
+    assert_eq!(actual, expected, "entries must be sorted");
+
+    op.remove_all(&parent)?;
+    Ok(())
+}
+
 // Walk top down should output as expected
 pub fn test_blocking_scan(op: BlockingOperator) -> Result<()> {
     let parent = uuid::Uuid::new_v4().to_string();
@@ -149,3 +176,28 @@ pub fn test_blocking_remove_all(op: BlockingOperator) -> Result<()> {
 
     Ok(())
 }
+
+/// Stat a non-empty prefix with treat_prefix_as_dir should return a dir.
+pub fn test_blocking_stat_with_treat_prefix_as_dir(op: BlockingOperator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+    let file = format!("{parent}/sub/file");
+    op.write(&file, "content")?;
+
+    let meta = op
+        .stat_with(&format!("{parent}/sub"))
+        .treat_prefix_as_dir(true)
+        .call()?;
+    assert_eq!(meta.mode(), EntryMode::DIR);
+
+    let meta = op.stat_with(&file).treat_prefix_as_dir(true).call()?;
+    assert_eq!(meta.mode(), EntryMode::FILE);
+
+    let res = op
+        .stat_with(&format!("{parent}/not_exist"))
+        .treat_prefix_as_dir(true)
+        .call();
+    assert_eq!(res.expect_err("stat must fail").kind(), ErrorKind::NotFound);
+
+    op.remove_all(&format!("{parent}/"))?;
+    Ok(())
+}
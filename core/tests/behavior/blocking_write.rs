@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::io::Read;
 use std::io::Seek;
 
@@ -51,7 +52,9 @@ pub fn behavior_blocking_write_tests(op: &Operator) -> Vec<Trial> {
         test_blocking_fuzz_offset_reader,
         test_blocking_fuzz_part_reader,
         test_blocking_delete_file,
-        test_blocking_remove_one_file
+        test_blocking_remove_one_file,
+        test_blocking_update,
+        test_blocking_put_tags
     )
 }
 
@@ -394,3 +397,54 @@ pub fn test_blocking_remove_one_file(op: BlockingOperator) -> Result<()> {
 
     Ok(())
 }
+
+/// Update a file via compare-and-swap should succeed.
+pub fn test_blocking_update(op: BlockingOperator) -> Result<()> {
+    if !op.info().capability().write_with_if_match {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    op.write(&path, "0")?;
+
+    for _ in 0..3 {
+        op.update(&path, |bs| {
+            let n: u64 = String::from_utf8_lossy(&bs).parse().unwrap();
+            bytes::Bytes::from((n + 1).to_string())
+        })?;
+    }
+    assert_eq!(op.read(&path)?, b"3");
+
+    let res = op
+        .write_with(&path, "4")
+        .if_match("\"invalid-etag\"")
+        .call();
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::ConditionNotMatch);
+
+    op.delete(&path).expect("delete must succeed");
+    Ok(())
+}
+
+/// Tags replaced by put_tags should be fetched by get_tags.
+pub fn test_blocking_put_tags(op: BlockingOperator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+    op.write(&path, content)?;
+
+    let tags = HashMap::from([("expire".to_string(), "30d".to_string())]);
+    let res = op.put_tags(&path, tags.clone());
+    if !op.info().capability().put_tags {
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+        op.delete(&path).expect("delete must succeed");
+        return Ok(());
+    }
+    res?;
+
+    if op.info().capability().get_tags {
+        assert_eq!(op.get_tags(&path)?, tags);
+    }
+
+    op.delete(&path).expect("delete must succeed");
+    Ok(())
+}
@@ -34,7 +34,11 @@ pub fn behavior_copy_tests(op: &Operator) -> Vec<Trial> {
         test_copy_target_dir,
         test_copy_self,
         test_copy_nested,
-        test_copy_overwrite
+        test_copy_overwrite,
+        test_copy_with_metadata_directive_copy,
+        test_copy_with_metadata_directive_replace,
+        test_copy_with_metadata_without_replace,
+        test_copy_with_if_source_match
     )
 }
 
@@ -170,3 +174,153 @@ pub async fn test_copy_overwrite(op: Operator) -> Result<()> {
     op.delete(&target_path).await.expect("delete must succeed");
     Ok(())
 }
+
+/// Copy with default metadata directive should preserve source metadata.
+pub async fn test_copy_with_metadata_directive_copy(op: Operator) -> Result<()> {
+    if !op.info().capability().write_with_content_type {
+        return Ok(());
+    }
+
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let (source_content, _) = gen_bytes();
+
+    op.write_with(&source_path, source_content)
+        .content_type("application/json")
+        .await?;
+
+    let target_path = uuid::Uuid::new_v4().to_string();
+
+    op.copy_with(&source_path, &target_path)
+        .metadata_directive(MetadataDirective::Copy)
+        .await?;
+
+    let meta = op.stat(&target_path).await.expect("stat must succeed");
+    assert_eq!(
+        meta.content_type().expect("content type must exist"),
+        "application/json"
+    );
+
+    op.delete(&source_path).await.expect("delete must succeed");
+    op.delete(&target_path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Copy with replace metadata directive should use the given metadata.
+pub async fn test_copy_with_metadata_directive_replace(op: Operator) -> Result<()> {
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let (source_content, _) = gen_bytes();
+
+    op.write(&source_path, source_content.clone()).await?;
+
+    let target_path = uuid::Uuid::new_v4().to_string();
+
+    let res = op
+        .copy_with(&source_path, &target_path)
+        .metadata_directive(MetadataDirective::Replace)
+        .content_type("text/plain")
+        .user_metadata([("location", "everywhere")])
+        .await;
+
+    if !op.info().capability().copy_with_metadata_directive {
+        assert_eq!(
+            res.expect_err("copy must fail").kind(),
+            ErrorKind::Unsupported
+        );
+        op.delete(&source_path).await.expect("delete must succeed");
+        return Ok(());
+    }
+    res?;
+
+    let meta = op.stat(&target_path).await.expect("stat must succeed");
+    assert_eq!(
+        meta.content_type().expect("content type must exist"),
+        "text/plain"
+    );
+    assert_eq!(
+        meta.user_metadata()
+            .and_then(|v| v.get("location"))
+            .map(String::as_str),
+        Some("everywhere")
+    );
+
+    let target_content = op.read(&target_path).await.expect("read must succeed");
+    assert_eq!(target_content, source_content);
+
+    op.delete(&source_path).await.expect("delete must succeed");
+    op.delete(&target_path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Copy with metadata but without replace metadata directive should fail
+/// instead of dropping the metadata.
+pub async fn test_copy_with_metadata_without_replace(op: Operator) -> Result<()> {
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let target_path = uuid::Uuid::new_v4().to_string();
+
+    let res = op
+        .copy_with(&source_path, &target_path)
+        .content_type("text/plain")
+        .await;
+    assert_eq!(
+        res.expect_err("copy must fail").kind(),
+        ErrorKind::InvalidInput
+    );
+
+    let res = op
+        .copy_with(&source_path, &target_path)
+        .metadata_directive(MetadataDirective::Copy)
+        .user_metadata([("location", "everywhere")])
+        .await;
+    assert_eq!(
+        res.expect_err("copy must fail").kind(),
+        ErrorKind::InvalidInput
+    );
+
+    Ok(())
+}
+
+/// Copy with if source match should only succeed while etag matches.
+pub async fn test_copy_with_if_source_match(op: Operator) -> Result<()> {
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let (source_content, _) = gen_bytes();
+
+    op.write(&source_path, source_content.clone()).await?;
+
+    let target_path = uuid::Uuid::new_v4().to_string();
+
+    if !op.info().capability().copy_with_if_source_match {
+        let res = op
+            .copy_with(&source_path, &target_path)
+            .if_source_match("\"invalid_etag\"")
+            .await;
+        assert_eq!(
+            res.expect_err("copy must fail").kind(),
+            ErrorKind::Unsupported
+        );
+        op.delete(&source_path).await.expect("delete must succeed");
+        return Ok(());
+    }
+
+    let meta = op.stat(&source_path).await?;
+    let etag = meta.etag().expect("etag must exist");
+
+    let res = op
+        .copy_with(&source_path, &target_path)
+        .if_source_match("\"invalid_etag\"")
+        .await;
+    assert_eq!(
+        res.expect_err("copy must fail").kind(),
+        ErrorKind::ConditionNotMatch
+    );
+
+    op.copy_with(&source_path, &target_path)
+        .if_source_match(etag)
+        .await?;
+
+    let target_content = op.read(&target_path).await.expect("read must succeed");
+    assert_eq!(target_content, source_content);
+
+    op.delete(&source_path).await.expect("delete must succeed");
+    op.delete(&target_path).await.expect("delete must succeed");
+    Ok(())
+}
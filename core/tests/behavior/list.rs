@@ -19,6 +19,8 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 
 use anyhow::Result;
+use chrono::Duration;
+use chrono::Utc;
 use futures::stream::FuturesUnordered;
 use futures::StreamExt;
 use futures::TryStreamExt;
@@ -38,15 +40,28 @@ pub fn behavior_list_tests(op: &Operator) -> Vec<Trial> {
         test_check,
         test_list_dir,
         test_list_rich_dir,
+        test_list_sorted,
+        test_list_with_glob,
+        test_list_with_metadata,
         test_list_empty_dir,
         test_list_non_exist_dir,
         test_list_sub_dir,
         test_list_nested_dir,
         test_list_dir_with_file_path,
         test_list_with_start_after,
+        test_list_with_continuation_token,
+        test_list_with_versions_recursive,
+        test_list_with_max_entries,
+        test_list_prefixes_only,
+        test_last_modified_since,
+        test_is_dir,
+        test_stat_with_treat_prefix_as_dir,
         test_scan,
         test_scan_root,
-        test_remove_all
+        test_remove_all,
+        test_delete_with_recursive,
+        test_delete_with_recursive_root,
+        test_sync_to
     )
 }
 
@@ -120,6 +135,102 @@ pub async fn test_list_rich_dir(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// List with sorted should return entries in lexicographic order.
+pub async fn test_list_sorted(op: Operator) -> Result<()> {
+    let parent = format!("{}/", uuid::Uuid::new_v4());
+    op.create_dir(&parent).await?;
+
+    let mut expected: Vec<String> = (0..=20).map(|num| format!("{parent}file-{num}")).collect();
+
+    for path in expected.iter() {
+        op.write(path, "test_list_sorted")
+            .await
+            .expect("write must succeed");
+    }
+
+    let mut ds = op.list_with(&parent).limit(5).sorted(true).await?;
+    let mut actual = vec![];
+    while let Some(de) = ds.try_next().await? {
+        actual.push(de.path().to_string());
+    }
+    expected.sort_unstable();
+
+    assert_eq!(actual, expected, "entries must be sorted");
+
+    op.remove_all(&parent).await?;
+    Ok(())
+}
+
+/// List with glob should only return entries matching the pattern.
+pub async fn test_list_with_glob(op: Operator) -> Result<()> {
+    let parent = format!("{}/", uuid::Uuid::new_v4());
+
+    let paths = [
+        "2023-01/a.json",
+        "2023-01/b.txt",
+        "2023-02/x/c.json",
+        "2022-12/d.json",
+    ];
+    for path in paths {
+        op.write(&format!("{parent}{path}"), "test_list_with_glob")
+            .await
+            .expect("write must succeed");
+    }
+
+    let mut ds = op.list_with(&parent).glob("2023-*/**/*.json").await?;
+    let mut actual = HashSet::new();
+    while let Some(de) = ds.try_next().await? {
+        actual.insert(de.path().to_string());
+    }
+
+    let expected: HashSet<String> = ["2023-01/a.json", "2023-02/x/c.json"]
+        .iter()
+        .map(|v| format!("{parent}{v}"))
+        .collect();
+    assert_eq!(actual, expected);
+
+    op.remove_all(&parent).await?;
+    Ok(())
+}
+
+/// List with metadata should return content length along with entries.
+pub async fn test_list_with_metadata(op: Operator) -> Result<()> {
+    if !op.info().capability().list_with_metadata {
+        return Ok(());
+    }
+
+    let parent = uuid::Uuid::new_v4().to_string();
+    let path = format!("{parent}/{}", uuid::Uuid::new_v4());
+    let (content, size) = gen_bytes();
+
+    op.write(&path, content).await.expect("write must succeed");
+
+    let mut ds = op.list_with(&format!("{parent}/")).metadata(true).await?;
+    let mut entry = None;
+    while let Some(de) = ds.try_next().await? {
+        if de.path() == path {
+            entry = Some(de);
+        }
+    }
+    let de = entry.expect("file should be found in list");
+
+    // Delete the file first so that metadata can't be fetched via stat.
+    op.delete(&path).await.expect("delete must succeed");
+
+    let meta = op
+        .metadata(
+            &de,
+            Metakey::Mode | Metakey::ContentLength | Metakey::LastModified,
+        )
+        .await
+        .expect("metadata must be returned by list");
+    assert_eq!(meta.mode(), EntryMode::FILE);
+    assert_eq!(meta.content_length(), size as u64);
+    assert!(meta.last_modified().is_some());
+
+    Ok(())
+}
+
 /// List empty dir should return nothing.
 pub async fn test_list_empty_dir(op: Operator) -> Result<()> {
     let dir = format!("{}/", uuid::Uuid::new_v4());
@@ -283,6 +394,297 @@ pub async fn test_list_with_start_after(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// List with continuation token should resume from the next page.
+pub async fn test_list_with_continuation_token(op: Operator) -> Result<()> {
+    let cap = op.info().capability();
+    if !(cap.list_with_continuation_token && cap.list_with_limit) {
+        return Ok(());
+    }
+
+    let dir = &format!("{}/", uuid::Uuid::new_v4());
+    op.create_dir(dir).await?;
+
+    let given: Vec<String> = (0..5)
+        .map(|i| format!("{dir}file-{i}-{}", uuid::Uuid::new_v4()))
+        .collect();
+    for name in given.iter() {
+        op.write(name, "content").await?;
+    }
+
+    let mut lister = op.list_with(dir).limit(2).await?;
+    let mut actual = HashSet::new();
+    let first = lister.next_page().await?.expect("first page must exist");
+    actual.extend(first.into_iter().map(|e| e.path().to_string()));
+    let token = lister
+        .continuation_token()
+        .expect("continuation token must exist after first page");
+    drop(lister);
+
+    let mut lister = op
+        .list_with(dir)
+        .limit(2)
+        .continuation_token(&token)
+        .await?;
+    while let Some(e) = lister.try_next().await? {
+        actual.insert(e.path().to_string());
+    }
+
+    for name in given.iter() {
+        assert!(actual.contains(name), "{name} must be listed");
+    }
+
+    op.remove_all(dir).await?;
+    Ok(())
+}
+
+/// List versions recursively should return every version of files in subtree.
+pub async fn test_list_with_versions_recursive(op: Operator) -> Result<()> {
+    let cap = op.info().capability();
+    if !cap.list_with_versions {
+        let res = op.list_with("/").versions(true).await;
+        assert_eq!(
+            res.err().expect("list must fail").kind(),
+            ErrorKind::Unsupported
+        );
+        return Ok(());
+    }
+
+    let dir = &format!("{}/", uuid::Uuid::new_v4());
+    op.create_dir(dir).await?;
+
+    let given: Vec<String> = (0..3)
+        .map(|i| format!("{dir}sub-{i}/file-{}", uuid::Uuid::new_v4()))
+        .collect();
+    for name in given.iter() {
+        op.write(name, "content").await?;
+        op.write(name, "new content").await?;
+    }
+
+    // Delete markers are only created on versioned buckets.
+    op.delete(&given[0]).await?;
+
+    // Use a small limit to make sure versions can be paginated.
+    let mut lister = op
+        .list_with(dir)
+        .versions(true)
+        .deleted(true)
+        .recursive(true)
+        .limit(1)
+        .await?;
+    let mut versions: HashMap<String, Vec<String>> = HashMap::new();
+    let mut deleted: HashMap<String, Vec<String>> = HashMap::new();
+    while let Some(e) = lister.try_next().await? {
+        if e.path().ends_with('/') {
+            continue;
+        }
+        let meta = op.metadata(&e, Metakey::Version).await?;
+        let version = meta.version().expect("version must exist").to_string();
+        let m = if meta.is_deleted() {
+            &mut deleted
+        } else {
+            &mut versions
+        };
+        m.entry(e.path().to_string()).or_default().push(version);
+    }
+
+    // Buckets without versioning will return the latest version only, with
+    // `null` as the version id.
+    let unversioned = versions.values().flatten().any(|v| v == "null");
+    for (idx, name) in given.iter().enumerate() {
+        let ids = versions.get(name).map(Vec::as_slice).unwrap_or_default();
+        if unversioned {
+            let expected = usize::from(idx != 0);
+            assert_eq!(
+                ids.len(),
+                expected,
+                "{name} must be listed {expected} times"
+            );
+            continue;
+        }
+
+        assert_eq!(ids.len(), 2, "both versions of {name} must be listed");
+        assert_ne!(ids[0], ids[1], "versions of {name} must be different");
+
+        let markers = deleted.get(name).map(Vec::as_slice).unwrap_or_default();
+        assert_eq!(
+            markers.len(),
+            usize::from(idx == 0),
+            "delete marker of {name} must be listed only if it's deleted"
+        );
+    }
+
+    // `remove_all` only removes the latest versions, remove all versions
+    // and delete markers so that nothing is left behind.
+    if op.info().capability().delete_with_version {
+        for (name, ids) in versions.iter().chain(deleted.iter()) {
+            for id in ids {
+                op.delete_with(name).version(id).await?;
+            }
+        }
+    }
+    op.remove_all(dir).await?;
+    Ok(())
+}
+
+/// List with max entries should stop after given count of entries.
+pub async fn test_list_with_max_entries(op: Operator) -> Result<()> {
+    let dir = &format!("{}/", uuid::Uuid::new_v4());
+    op.create_dir(dir).await?;
+
+    let given: Vec<String> = (0..5)
+        .map(|i| format!("{dir}sub/file-{i}-{}", uuid::Uuid::new_v4()))
+        .collect();
+    for name in given.iter() {
+        op.write(name, "content").await?;
+    }
+
+    let actual: Vec<_> = op
+        .list_with(dir)
+        .delimiter("")
+        .max_entries(3)
+        .await?
+        .try_collect()
+        .await?;
+    assert_eq!(actual.len(), 3, "only 3 entries should be returned");
+
+    if op.info().capability().list_with_start_after {
+        let mut sorted = given.clone();
+        sorted.sort_unstable();
+
+        let actual: Vec<String> = op
+            .list_with(&format!("{dir}sub/"))
+            .start_after(&sorted[0])
+            .max_entries(2)
+            .await?
+            .map_ok(|e| e.path().to_string())
+            .try_collect()
+            .await?;
+        assert_eq!(actual, sorted[1..3].to_vec());
+    }
+
+    op.remove_all(dir).await?;
+    Ok(())
+}
+
+/// List with prefixes only should skip all files.
+pub async fn test_list_prefixes_only(op: Operator) -> Result<()> {
+    let dir = &format!("{}/", uuid::Uuid::new_v4());
+
+    op.write(&format!("{dir}file"), "content").await?;
+    op.write(&format!("{dir}a/file"), "content").await?;
+    op.write(&format!("{dir}b/file"), "content").await?;
+
+    let mut actual: Vec<String> = op
+        .list_with(dir)
+        .delimiter("/")
+        .prefixes_only(true)
+        .await?
+        .map_ok(|e| e.path().to_string())
+        .try_collect()
+        .await?;
+    actual.sort_unstable();
+    assert_eq!(actual, vec![format!("{dir}a/"), format!("{dir}b/")]);
+
+    op.remove_all(dir).await?;
+    Ok(())
+}
+
+/// Last modified since should only return files modified after given time.
+pub async fn test_last_modified_since(op: Operator) -> Result<()> {
+    let dir = &format!("{}/", uuid::Uuid::new_v4());
+    let path = format!("{dir}sub/{}", uuid::Uuid::new_v4());
+    op.write(&path, "content").await?;
+
+    let since = Utc::now() - Duration::hours(1);
+    if op.stat(&path).await?.last_modified().is_none() {
+        let res: Result<Vec<_>, _> = op
+            .last_modified_since(dir, since)
+            .await?
+            .try_collect()
+            .await;
+        assert_eq!(
+            res.expect_err("must fail").kind(),
+            ErrorKind::Unsupported,
+            "service doesn't return last modified"
+        );
+
+        op.remove_all(dir).await?;
+        return Ok(());
+    }
+
+    let actual: Vec<_> = op
+        .last_modified_since(dir, since)
+        .await?
+        .try_collect()
+        .await?;
+    assert_eq!(actual.len(), 1);
+    assert_eq!(actual[0].path(), path);
+    let meta = op.metadata(&actual[0], Metakey::LastModified).await?;
+    assert!(meta.last_modified().expect("last modified must exist") > since);
+
+    let since = Utc::now() + Duration::hours(1);
+    let actual: Vec<_> = op
+        .last_modified_since(dir, since)
+        .await?
+        .try_collect()
+        .await?;
+    assert!(actual.is_empty(), "no file should be modified in future");
+
+    op.remove_all(dir).await?;
+    Ok(())
+}
+
+/// is_dir should classify paths consistently.
+pub async fn test_is_dir(op: Operator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+    let file = format!("{parent}/sub/file");
+    op.write(&file, "content").await?;
+
+    assert!(op.is_dir(&format!("{parent}/sub")).await?);
+    assert!(op.is_dir(&format!("{parent}/sub/")).await?);
+    assert!(!op.is_dir(&file).await?);
+    assert!(!op.is_dir(&format!("{parent}/not_exist")).await?);
+
+    let entries: Vec<_> = op.list(&format!("{parent}/")).await?.try_collect().await?;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].mode(), EntryMode::DIR);
+    let entries: Vec<_> = op
+        .list(&format!("{parent}/sub/"))
+        .await?
+        .try_collect()
+        .await?;
+    assert_eq!(entries[0].mode(), EntryMode::FILE);
+
+    op.remove_all(&format!("{parent}/")).await?;
+    Ok(())
+}
+
+/// Stat a non-empty prefix with treat_prefix_as_dir should return a dir.
+pub async fn test_stat_with_treat_prefix_as_dir(op: Operator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+    let file = format!("{parent}/sub/file");
+    op.write(&file, "content").await?;
+
+    let meta = op
+        .stat_with(&format!("{parent}/sub"))
+        .treat_prefix_as_dir(true)
+        .await?;
+    assert_eq!(meta.mode(), EntryMode::DIR);
+
+    let meta = op.stat_with(&file).treat_prefix_as_dir(true).await?;
+    assert_eq!(meta.mode(), EntryMode::FILE);
+
+    let res = op
+        .stat_with(&format!("{parent}/not_exist"))
+        .treat_prefix_as_dir(true)
+        .await;
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::NotFound);
+
+    op.remove_all(&format!("{parent}/")).await?;
+    Ok(())
+}
+
 pub async fn test_scan_root(op: Operator) -> Result<()> {
     let w = op.scan("").await?;
     let actual = w
@@ -361,3 +763,85 @@ pub async fn test_remove_all(op: Operator) -> Result<()> {
     }
     Ok(())
 }
+
+/// Delete with recursive should remove all entries under this path.
+pub async fn test_delete_with_recursive(op: Operator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+
+    let expected = vec!["x/", "x/y", "x/x/", "x/x/y", "x/x/x/", "x/x/x/y"];
+    for path in expected.iter() {
+        if path.ends_with('/') {
+            op.create_dir(&format!("{parent}/{path}")).await?;
+        } else {
+            op.write(&format!("{parent}/{path}"), "test_delete").await?;
+        }
+    }
+
+    op.delete_with(&format!("{parent}/x/"))
+        .recursive(true)
+        .await?;
+
+    for path in expected.iter() {
+        if path.ends_with('/') {
+            continue;
+        }
+        assert!(
+            !op.is_exist(&format!("{parent}/{path}")).await?,
+            "{parent}/{path} should be removed"
+        )
+    }
+    Ok(())
+}
+
+/// Delete with recursive on root should be refused without allow_root.
+pub async fn test_delete_with_recursive_root(op: Operator) -> Result<()> {
+    let err = op
+        .delete_with("/")
+        .recursive(true)
+        .await
+        .expect_err("recursive delete on root must be refused");
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+    let err = op
+        .delete_with("")
+        .recursive(true)
+        .await
+        .expect_err("recursive delete on root must be refused");
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+    Ok(())
+}
+
+/// Sync to should copy all files under given path.
+pub async fn test_sync_to(op: Operator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+
+    let files = vec!["x", "y/z", "y/y/z"];
+    for path in files.iter() {
+        op.write(&format!("{parent}/src/{path}"), path.to_string())
+            .await?;
+    }
+
+    let results = op
+        .sync_to(&op, &format!("{parent}/src/"), &format!("{parent}/dst/"))
+        .await?;
+    assert_eq!(results.len(), files.len());
+    for (path, res) in results {
+        assert_eq!(res?, SyncStatus::Copied, "{path} should be copied");
+    }
+    for path in files.iter() {
+        let bs = op.read(&format!("{parent}/dst/{path}")).await?;
+        assert_eq!(bs, path.as_bytes());
+    }
+
+    // Sync again should not fail, unchanged files could be skipped.
+    let results = op
+        .sync_to(&op, &format!("{parent}/src/"), &format!("{parent}/dst/"))
+        .await?;
+    for (_, res) in results {
+        res?;
+    }
+
+    op.remove_all(&format!("{parent}/")).await?;
+    Ok(())
+}
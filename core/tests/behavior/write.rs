@@ -15,7 +15,11 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::Result;
@@ -25,6 +29,7 @@ use futures::stream;
 use futures::AsyncReadExt;
 use futures::AsyncSeekExt;
 use futures::StreamExt;
+use futures::TryStreamExt;
 use http::StatusCode;
 use log::debug;
 use log::warn;
@@ -41,27 +46,49 @@ pub fn behavior_write_tests(op: &Operator) -> Vec<Trial> {
         return vec![];
     }
 
-    async_trials!(
+    let trials = async_trials!(
         op,
         test_create_dir,
         test_create_dir_existing,
+        test_create_dir_nested,
         test_write_only,
         test_write_with_dir_path,
         test_write_with_special_chars,
         test_write_with_cache_control,
         test_write_with_content_type,
         test_write_with_content_disposition,
+        test_write_with_content_encoding_and_language,
+        test_write_with_custom_header,
+        test_write_with_storage_class,
+        test_write_with_content_md5,
+        test_write_with_ttl,
+        test_write_with_append,
+        test_write_with_range,
+        test_update,
+        test_write_with_tags,
+        test_write_with_object_lock,
+        test_write_with_create,
+        test_write_with_user_metadata,
+        test_write_with_idempotency_key,
         test_stat_file,
+        test_stat_with_metakey,
         test_stat_dir,
         test_stat_with_special_chars,
         test_stat_not_cleaned_path,
+        test_stat_with_path_normalization,
         test_stat_not_exist,
+        test_stat_many,
         test_stat_with_if_match,
         test_stat_with_if_none_match,
         test_stat_root,
         test_read_full,
+        test_read_chunks,
+        test_read_with_chunk,
+        test_read_with_metadata,
+        test_read_to_string,
         test_read_range,
         test_read_large_range,
+        test_read_with_size,
         test_reader_range,
         test_reader_from,
         test_reader_tail,
@@ -75,7 +102,11 @@ pub fn behavior_write_tests(op: &Operator) -> Vec<Trial> {
         test_read_with_special_chars,
         test_read_with_override_cache_control,
         test_read_with_override_content_disposition,
+        test_read_with_verify_checksum,
+        test_read_write_with_progress,
         test_delete_file,
+        test_delete_with_if_match,
+        test_delete_with_version,
         test_delete_empty_dir,
         test_delete_with_special_chars,
         test_delete_not_existing,
@@ -85,10 +116,24 @@ pub fn behavior_write_tests(op: &Operator) -> Vec<Trial> {
         test_writer_sink,
         test_writer_copy,
         test_writer_abort,
+        test_writer_with_checksum,
+        test_writer_resume,
+        test_write_with_checksum_crc32c,
         test_writer_futures_copy,
+        test_write_from,
+        test_write_stream,
         test_fuzz_unsized_writer,
         test_invalid_reader_seek
-    )
+    );
+
+    #[cfg(feature = "download")]
+    let trials = {
+        let mut trials = trials;
+        trials.extend(async_trials!(op, test_download_to));
+        trials
+    };
+
+    trials
 }
 
 /// Create dir with dir path should succeed.
@@ -119,6 +164,30 @@ pub async fn test_create_dir_existing(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Create nested dir should make parents and show up in parent's list.
+pub async fn test_create_dir_nested(op: Operator) -> Result<()> {
+    let parent = format!("{}/", uuid::Uuid::new_v4());
+    let path = format!("{parent}{}/", uuid::Uuid::new_v4());
+
+    op.create_dir(&path).await?;
+
+    let meta = op.stat(&path).await?;
+    assert_eq!(meta.mode(), EntryMode::DIR);
+
+    if op.info().capability().list {
+        let entries: Vec<_> = op.list(&parent).await?.try_collect().await?;
+        let de = entries
+            .iter()
+            .find(|de| de.path() == path)
+            .expect("created dir must be listed");
+        assert_eq!(op.metadata(de, Metakey::Mode).await?.mode(), EntryMode::DIR);
+    }
+
+    op.delete(&path).await.expect("delete must succeed");
+    op.delete(&parent).await.expect("delete must succeed");
+    Ok(())
+}
+
 /// Write a single file and test with stat.
 pub async fn test_write_only(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
@@ -191,6 +260,283 @@ pub async fn test_write_with_cache_control(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Write a single file with content md5 should succeed and reject
+/// mismatched content.
+pub async fn test_write_with_content_md5(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, size) = gen_bytes();
+
+    let res = op
+        .write_with(&path, content.clone())
+        .content_md5(&opendal::raw::format_content_md5(&content))
+        .await;
+    if !op.info().capability().write_with_content_md5 {
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+        return Ok(());
+    }
+    res?;
+
+    let meta = op.stat(&path).await.expect("stat must succeed");
+    assert_eq!(meta.content_length(), size as u64);
+
+    let res = op
+        .write_with(&path, content)
+        .content_md5(&opendal::raw::format_content_md5(b"not the content"))
+        .await;
+    assert!(res.is_err(), "write with mismatched md5 must fail");
+
+    op.delete(&path).await.expect("delete must succeed");
+
+    Ok(())
+}
+
+/// Write a single file with ttl should succeed or be rejected as unsupported.
+pub async fn test_write_with_ttl(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, size) = gen_bytes();
+
+    let res = op
+        .write_with(&path, content)
+        .ttl(Duration::from_secs(60))
+        .await;
+    if !op.info().capability().write_with_ttl {
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+        return Ok(());
+    }
+    res?;
+
+    let meta = op.stat(&path).await.expect("stat must succeed");
+    assert_eq!(meta.content_length(), size as u64);
+    // Services that can't report the remaining ttl return `None`.
+    if let Some(ttl) = meta.ttl() {
+        assert!(ttl <= Duration::from_secs(60), "remaining ttl: {ttl:?}");
+    }
+
+    op.delete(&path).await.expect("delete must succeed");
+
+    Ok(())
+}
+
+/// Write with append should append content to existing file.
+pub async fn test_write_with_append(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content_one, size_one) = gen_bytes();
+    let (content_two, size_two) = gen_bytes();
+
+    let res = op.write_with(&path, content_one.clone()).append(true).await;
+    if !op.info().capability().write_with_append {
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+        return Ok(());
+    }
+    res?;
+
+    op.write_with(&path, content_two.clone())
+        .append(true)
+        .await
+        .expect("append to existing file must succeed");
+
+    let bs = op.read(&path).await.expect("read file must succeed");
+    assert_eq!(bs.len(), size_one + size_two);
+    assert_eq!(&bs[..size_one], content_one.as_slice());
+    assert_eq!(&bs[size_one..], content_two.as_slice());
+
+    op.delete(&path).await.expect("delete must succeed");
+
+    Ok(())
+}
+
+/// Write with range should overwrite the range in place.
+pub async fn test_write_with_range(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+
+    let res = op.write_with(&path, "0123456789").range(0..).await;
+    if !op.info().capability().write_with_range {
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+        return Ok(());
+    }
+    res?;
+
+    op.write_with(&path, "abc")
+        .range(4..7)
+        .await
+        .expect("write with range must succeed");
+
+    let bs = op.read(&path).await.expect("read file must succeed");
+    assert_eq!(bs, b"0123abc789");
+
+    op.delete(&path).await.expect("delete must succeed");
+
+    Ok(())
+}
+
+/// Write with tags should be fetched by get_tags and replaced by put_tags.
+pub async fn test_write_with_tags(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    let res = op
+        .write_with(&path, content)
+        .tags([("project", "opendal")])
+        .await;
+    if !op.info().capability().write_with_tags {
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+        return Ok(());
+    }
+    res?;
+
+    if op.info().capability().get_tags {
+        let tags = op.get_tags(&path).await?;
+        assert_eq!(tags.get("project").map(|v| v.as_str()), Some("opendal"));
+    }
+
+    if op.info().capability().put_tags {
+        let tags = HashMap::from([("expire".to_string(), "30d".to_string())]);
+        op.put_tags(&path, tags.clone()).await?;
+
+        if op.info().capability().get_tags {
+            assert_eq!(op.get_tags(&path).await?, tags);
+        }
+    }
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Write with object lock should return unsupported if the service can't
+/// lock files.
+///
+/// Services that support object lock require it to be enabled on the bucket,
+/// so we don't lock real files here.
+pub async fn test_write_with_object_lock(op: Operator) -> Result<()> {
+    let cap = op.info().capability();
+    if cap.write_with_object_lock && cap.get_object_lock && cap.put_object_lock {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    if !cap.write_with_object_lock {
+        let res = op.write_with(&path, content.clone()).legal_hold(true).await;
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+    }
+
+    op.write(&path, content).await?;
+
+    if !cap.get_object_lock {
+        let res = op.get_object_lock(&path).await;
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+    }
+    if !cap.put_object_lock {
+        let res = op
+            .put_object_lock(&path, ObjectLock::new().with_legal_hold(true))
+            .await;
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+    }
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Write with create should only succeed if the file doesn't exist.
+pub async fn test_write_with_create(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    let res = op.write_with(&path, content.clone()).create(true).await;
+    if !op.info().capability().write_with_if_none_match {
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+        return Ok(());
+    }
+    res?;
+
+    let (other, _) = gen_bytes();
+    let res = op.write_with(&path, other).create(true).await;
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::AlreadyExists);
+
+    let bs = op.read(&path).await?;
+    assert_eq!(bs, content, "existing file must not be overwritten");
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Write with user metadata should be returned by stat.
+pub async fn test_write_with_user_metadata(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    let res = op
+        .write_with(&path, content.clone())
+        .user_metadata([("location", "everywhere"), ("owner", "opendal")])
+        .await;
+    if !op.info().capability().write_with_user_metadata {
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+        return Ok(());
+    }
+    res?;
+
+    let meta = op.stat(&path).await?;
+    let expected = HashMap::from([
+        ("location".to_string(), "everywhere".to_string()),
+        ("owner".to_string(), "opendal".to_string()),
+    ]);
+    assert_eq!(meta.user_metadata(), Some(&expected));
+
+    // Rewrite with other user metadata should replace the old ones.
+    op.write_with(&path, content.clone())
+        .user_metadata([("owner", "others")])
+        .await?;
+    let meta = op.stat(&path).await?;
+    let expected = HashMap::from([("owner".to_string(), "others".to_string())]);
+    assert_eq!(meta.user_metadata(), Some(&expected));
+
+    // Rewrite without user metadata should clean up the old ones.
+    op.write(&path, content).await?;
+    let meta = op.stat(&path).await?;
+    assert!(meta.user_metadata().map_or(true, |v| v.is_empty()));
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Write with idempotency key should be applied only once.
+pub async fn test_write_with_idempotency_key(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+    let key = uuid::Uuid::new_v4().to_string();
+
+    let res = op
+        .write_with(&path, content.clone())
+        .idempotency_key(&key)
+        .await;
+    if !op.info().capability().write_with_idempotency_key {
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+        return Ok(());
+    }
+    res?;
+
+    // Retry with the same key should succeed without being applied again.
+    let (other, _) = gen_bytes();
+    op.write_with(&path, other.clone())
+        .idempotency_key(&key)
+        .await?;
+    assert_eq!(
+        op.read(&path).await?,
+        content,
+        "write with applied key must not be applied again"
+    );
+
+    // Write with another key should be applied.
+    op.write_with(&path, other.clone())
+        .idempotency_key(&uuid::Uuid::new_v4().to_string())
+        .await?;
+    assert_eq!(op.read(&path).await?, other);
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
 /// Write a single file with content type should succeed.
 pub async fn test_write_with_content_type(op: Operator) -> Result<()> {
     if !op.info().capability().write_with_content_type {
@@ -245,6 +591,86 @@ pub async fn test_write_with_content_disposition(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Write a single file with content encoding and language should succeed.
+pub async fn test_write_with_content_encoding_and_language(op: Operator) -> Result<()> {
+    let cap = op.info().capability();
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, size) = gen_bytes();
+
+    if !cap.write_with_content_encoding || !cap.write_with_content_language {
+        let res = op
+            .write_with(&path, content)
+            .content_encoding("gzip")
+            .content_language("en-US")
+            .await;
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+        return Ok(());
+    }
+
+    op.write_with(&path, content)
+        .content_encoding("gzip")
+        .content_language("en-US")
+        .await?;
+
+    let meta = op.stat(&path).await.expect("stat must succeed");
+    assert_eq!(meta.mode(), EntryMode::FILE);
+    assert_eq!(meta.content_encoding(), Some("gzip"));
+    assert_eq!(meta.content_language(), Some("en-US"));
+    assert_eq!(meta.content_length(), size as u64);
+
+    op.delete(&path).await.expect("delete must succeed");
+
+    Ok(())
+}
+
+/// Write and read a single file with custom headers should succeed.
+pub async fn test_write_with_custom_header(op: Operator) -> Result<()> {
+    let cap = op.info().capability();
+    if !cap.write_with_headers || !cap.read_with_headers {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    op.write_with(&path, content.clone())
+        .header("x-opendal-test", "write")
+        .await?;
+
+    let bs = op.read_with(&path).header("x-opendal-test", "read").await?;
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content)),
+        "read content"
+    );
+
+    op.delete(&path).await.expect("delete must succeed");
+
+    Ok(())
+}
+
+/// Write a single file with storage class should succeed.
+pub async fn test_write_with_storage_class(op: Operator) -> Result<()> {
+    if !op.info().capability().write_with_storage_class {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    // `STANDARD` is the only storage class accepted by all services.
+    op.write_with(&path, content)
+        .storage_class("STANDARD")
+        .await?;
+
+    let meta = op.stat(&path).await.expect("stat must succeed");
+    assert_eq!(meta.storage_class(), Some("STANDARD"));
+
+    op.delete(&path).await.expect("delete must succeed");
+
+    Ok(())
+}
+
 /// Stat existing file should return metadata
 pub async fn test_stat_file(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
@@ -260,6 +686,26 @@ pub async fn test_stat_file(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Stat with metakey should populate the given fields only.
+pub async fn test_stat_with_metakey(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, size) = gen_bytes();
+
+    op.write(&path, content).await.expect("write must succeed");
+
+    let meta = op.stat_with(&path).metakey(Metakey::ContentLength).await?;
+    assert_eq!(meta.mode(), EntryMode::FILE);
+    assert_eq!(meta.content_length(), size as u64);
+    assert!(meta.metakey().contains(Metakey::ContentLength));
+    assert!(!meta.metakey().contains(Metakey::Complete));
+
+    let meta = op.stat(&path).await?;
+    assert!(meta.metakey().contains(Metakey::Complete));
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
 /// Stat existing file should return metadata
 pub async fn test_stat_dir(op: Operator) -> Result<()> {
     let path = format!("{}/", uuid::Uuid::new_v4());
@@ -310,6 +756,26 @@ pub async fn test_stat_not_cleaned_path(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Stat path with dot segments should succeed with path normalization.
+pub async fn test_stat_with_path_normalization(op: Operator) -> Result<()> {
+    let dir = uuid::Uuid::new_v4().to_string();
+    let path = format!("{dir}/{}", uuid::Uuid::new_v4());
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+
+    op.write(&path, content).await.expect("write must succeed");
+
+    let op = op.with_path_normalization(true);
+    let meta = op
+        .stat(&format!("/{dir}/./xyz/..//{}", &path[dir.len() + 1..]))
+        .await?;
+    assert_eq!(meta.mode(), EntryMode::FILE);
+    assert_eq!(meta.content_length(), size as u64);
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
 /// Stat not exist file should return NotFound
 pub async fn test_stat_not_exist(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
@@ -321,6 +787,32 @@ pub async fn test_stat_not_exist(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Stat many paths should return results for all of them.
+pub async fn test_stat_many(op: Operator) -> Result<()> {
+    let (content, size) = gen_bytes();
+    let exist = uuid::Uuid::new_v4().to_string();
+    op.write(&exist, content).await.expect("write must succeed");
+    let not_exist = uuid::Uuid::new_v4().to_string();
+
+    let results: Vec<_> = op
+        .stat_many(vec![exist.clone(), not_exist.clone()])
+        .collect()
+        .await;
+    assert_eq!(results.len(), 2);
+
+    for (path, res) in results {
+        if path == exist {
+            assert_eq!(res?.content_length(), size as u64);
+        } else {
+            assert_eq!(path, not_exist);
+            assert_eq!(res.unwrap_err().kind(), ErrorKind::NotFound);
+        }
+    }
+
+    op.delete(&exist).await.expect("delete must succeed");
+    Ok(())
+}
+
 /// Stat with if_match should succeed, else get a ConditionNotMatch error.
 pub async fn test_stat_with_if_match(op: Operator) -> Result<()> {
     if !op.info().capability().stat_with_if_match {
@@ -367,56 +859,247 @@ pub async fn test_stat_with_if_none_match(op: Operator) -> Result<()> {
         .await
         .expect("write must succeed");
 
-    let meta = op.stat(&path).await?;
-    assert_eq!(meta.mode(), EntryMode::FILE);
-    assert_eq!(meta.content_length(), size as u64);
-
-    let res = op
-        .stat_with(&path)
-        .if_none_match(meta.etag().expect("etag must exist"))
-        .await;
-    assert!(res.is_err());
-    assert_eq!(res.unwrap_err().kind(), ErrorKind::ConditionNotMatch);
-
-    let res = op
-        .stat_with(&path)
-        .if_none_match("\"invalid_etag\"")
-        .await?;
-    assert_eq!(res.mode(), meta.mode());
-    assert_eq!(res.content_length(), meta.content_length());
+    let meta = op.stat(&path).await?;
+    assert_eq!(meta.mode(), EntryMode::FILE);
+    assert_eq!(meta.content_length(), size as u64);
+
+    let res = op
+        .stat_with(&path)
+        .if_none_match(meta.etag().expect("etag must exist"))
+        .await;
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::ConditionNotMatch);
+
+    let res = op
+        .stat_with(&path)
+        .if_none_match("\"invalid_etag\"")
+        .await?;
+    assert_eq!(res.mode(), meta.mode());
+    assert_eq!(res.content_length(), meta.content_length());
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Root should be able to stat and returns DIR.
+pub async fn test_stat_root(op: Operator) -> Result<()> {
+    let meta = op.stat("").await?;
+    assert_eq!(meta.mode(), EntryMode::DIR);
+
+    let meta = op.stat("/").await?;
+    assert_eq!(meta.mode(), EntryMode::DIR);
+
+    Ok(())
+}
+
+/// Read full content should match.
+pub async fn test_read_full(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    let bs = op.read(&path).await?;
+    assert_eq!(size, bs.len(), "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content)),
+        "read content"
+    );
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Read chunks should return the whole content.
+pub async fn test_read_chunks(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    let chunks: Vec<bytes::Bytes> = op.read_chunks(&path).await?.try_collect().await?;
+    assert!(
+        chunks.iter().all(|v| !v.is_empty()),
+        "chunks must not be empty"
+    );
+    let bs: Vec<u8> = chunks.iter().flat_map(|v| v.iter().copied()).collect();
+    assert_eq!(size, bs.len(), "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content)),
+        "read content"
+    );
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Update a file via compare-and-swap should succeed.
+pub async fn test_update(op: Operator) -> Result<()> {
+    if !op.info().capability().write_with_if_match {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    op.write(&path, "0").await?;
+
+    for _ in 0..3 {
+        op.update(&path, |bs| {
+            let n: u64 = String::from_utf8_lossy(&bs).parse().unwrap();
+            bytes::Bytes::from((n + 1).to_string())
+        })
+        .await?;
+    }
+    assert_eq!(op.read(&path).await?, b"3");
+
+    let res = op.write_with(&path, "4").if_match("\"invalid-etag\"").await;
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::ConditionNotMatch);
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Read with metadata should return content and metadata together.
+pub async fn test_read_with_metadata(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let content = gen_fixed_bytes(1024);
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    let (meta, bs) = op.read_with(&path).with_metadata().await?;
+    assert_eq!(bs, content, "read content");
+    assert_eq!(meta.content_length(), 1024);
+
+    let stat = op.stat(&path).await?;
+    if let (Some(expected), Some(actual)) = (stat.etag(), meta.etag()) {
+        assert_eq!(expected, actual, "etag should be the same as stat");
+    }
+
+    let (meta, bs) = op.read_with(&path).range(0..10).with_metadata().await?;
+    assert_eq!(bs, content[0..10], "read content with range");
+    assert_eq!(meta.content_length(), 10, "content length of range");
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Read with chunk should return the same content.
+pub async fn test_read_with_chunk(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let content = gen_fixed_bytes(4096);
+    let size = content.len();
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    let bs = op.read_with(&path).chunk(1000).await?;
+    assert_eq!(size, bs.len(), "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content)),
+        "read content"
+    );
+
+    let bs = op.read_with(&path).range(10..).chunk(1000).await?;
+    assert_eq!(bs, content[10..], "read content with range");
+
+    let r = op
+        .reader_with(&path)
+        .chunk(1000)
+        .buffer(2000)
+        .await
+        .expect("reader must be created");
+    let chunks: Vec<_> = r.try_collect().await?;
+    let bs: Vec<u8> = chunks.iter().flat_map(|v| v.iter().copied()).collect();
+    assert_eq!(bs, content, "read content with reader");
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Read full content with checksum verification should succeed.
+pub async fn test_read_with_verify_checksum(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    let bs = op.read_with(&path).verify_checksum(true).await?;
+    assert_eq!(size, bs.len(), "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content)),
+        "read content"
+    );
 
     op.delete(&path).await.expect("delete must succeed");
     Ok(())
 }
 
-/// Root should be able to stat and returns DIR.
-pub async fn test_stat_root(op: Operator) -> Result<()> {
-    let meta = op.stat("").await?;
-    assert_eq!(meta.mode(), EntryMode::DIR);
+/// Read and write with progress should report all bytes transferred.
+pub async fn test_read_write_with_progress(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
 
-    let meta = op.stat("/").await?;
-    assert_eq!(meta.mode(), EntryMode::DIR);
+    let written = Arc::new(AtomicU64::new(0));
+    let w = written.clone();
+    op.write_with(&path, content.clone())
+        .on_progress(move |n, total| {
+            assert_eq!(total, Some(size as u64), "write total");
+            w.store(n, Ordering::SeqCst);
+        })
+        .await
+        .expect("write must succeed");
+    assert_eq!(written.load(Ordering::SeqCst), size as u64, "written bytes");
+
+    let read = Arc::new(AtomicU64::new(0));
+    let r = read.clone();
+    let bs = op
+        .read_with(&path)
+        .on_progress(move |n, _| r.store(n, Ordering::SeqCst))
+        .await?;
+    assert_eq!(bs.len(), size, "read size");
+    assert_eq!(read.load(Ordering::SeqCst), size as u64, "read bytes");
 
+    op.delete(&path).await.expect("delete must succeed");
     Ok(())
 }
 
-/// Read full content should match.
-pub async fn test_read_full(op: Operator) -> Result<()> {
+/// Read full content as string should match.
+pub async fn test_read_to_string(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
     debug!("Generate a random file: {}", &path);
-    let (content, size) = gen_bytes();
+    let content = "Hello, OpenDAL!".to_string();
 
     op.write(&path, content.clone())
         .await
         .expect("write must succeed");
 
-    let bs = op.read(&path).await?;
-    assert_eq!(size, bs.len(), "read size");
-    assert_eq!(
-        format!("{:x}", Sha256::digest(&bs)),
-        format!("{:x}", Sha256::digest(&content)),
-        "read content"
-    );
+    let s = op.read_to_string(&path).await?;
+    assert_eq!(s, content, "read content");
+
+    op.write(&path, vec![0xff, 0xfe, 0xfd])
+        .await
+        .expect("write must succeed");
+    let res = op.read_to_string(&path).await;
+    assert!(res.is_err(), "read invalid utf-8 must fail");
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::Unexpected);
 
     op.delete(&path).await.expect("delete must succeed");
     Ok(())
@@ -483,6 +1166,81 @@ pub async fn test_read_large_range(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Read with size should only return the first n bytes.
+pub async fn test_read_with_size(op: Operator) -> Result<()> {
+    if !op.info().capability().read_with_range {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+    let (offset, length) = gen_offset_length(size);
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    let bs = op.read_with(&path).size(length).await?;
+    assert_eq!(bs.len() as u64, length, "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content[..length as usize])),
+        "read content"
+    );
+
+    let bs = op.read_with(&path).range(offset..).size(length).await?;
+    assert_eq!(bs.len() as u64, length, "read size with offset");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!(
+            "{:x}",
+            Sha256::digest(&content[offset as usize..(offset + length) as usize])
+        ),
+        "read content with offset"
+    );
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Download to local file should resume from partial bytes.
+#[cfg(feature = "download")]
+pub async fn test_download_to(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+    let (offset, _) = gen_offset_length(size);
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    let local = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+    std::fs::write(&local, &content[..offset as usize])?;
+    // Partial bytes will be kept only if the persisted etag matches.
+    let etag_path = local.with_extension("etag");
+    if let Some(etag) = op.stat(&path).await?.etag() {
+        std::fs::write(&etag_path, etag)?;
+    }
+
+    let n = op.download_to(&path, &local).await?;
+    assert_eq!(n, size as u64, "download size");
+
+    let bs = std::fs::read(&local)?;
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content)),
+        "download content"
+    );
+
+    assert!(!etag_path.exists(), "persisted etag must be removed");
+
+    std::fs::remove_file(&local)?;
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
 /// Read range content should match.
 pub async fn test_reader_range(op: Operator) -> Result<()> {
     if !op.info().capability().read_with_range {
@@ -970,6 +1728,66 @@ pub async fn test_delete_file(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Delete with if_match should only delete the matched file.
+pub async fn test_delete_with_if_match(op: Operator) -> Result<()> {
+    if !op.info().capability().delete_with_if_match {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    op.write(&path, content).await.expect("write must succeed");
+
+    let meta = op.stat(&path).await?;
+    let etag = meta.etag().expect("etag must exist");
+
+    let res = op.delete_with(&path).if_match("\"invalid_etag\"").await;
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::ConditionNotMatch);
+    assert!(op.is_exist(&path).await?);
+
+    op.delete_with(&path).if_match(etag).await?;
+    assert!(!op.is_exist(&path).await?);
+
+    Ok(())
+}
+
+/// Delete with version should only delete the given version.
+pub async fn test_delete_with_version(op: Operator) -> Result<()> {
+    if !op.info().capability().delete_with_version {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+
+    op.write(&path, "v1").await.expect("write must succeed");
+    let v1 = match op.stat(&path).await?.version() {
+        Some(v) => v.to_string(),
+        // Versioning is not enabled for this service.
+        None => {
+            op.delete(&path).await.expect("delete must succeed");
+            return Ok(());
+        }
+    };
+    op.write(&path, "v2").await.expect("write must succeed");
+    let v2 = op
+        .stat(&path)
+        .await?
+        .version()
+        .expect("version must exist")
+        .to_string();
+    assert_ne!(v1, v2);
+
+    op.delete_with(&path).version(&v1).await?;
+    assert_eq!(op.read(&path).await?, b"v2");
+
+    op.delete_with(&path).version(&v2).await?;
+    assert!(!op.is_exist(&path).await?);
+
+    Ok(())
+}
+
 /// Delete empty dir should succeed.
 pub async fn test_delete_empty_dir(op: Operator) -> Result<()> {
     let path = format!("{}/", uuid::Uuid::new_v4());
@@ -1093,6 +1911,119 @@ pub async fn test_writer_write(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Writer resumed from checkpoint should continue the upload.
+pub async fn test_writer_resume(op: Operator) -> Result<()> {
+    if !op.info().capability().write_with_resume {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    let size = 6 * 1024 * 1024; // write file with 6 MiB
+    let content_a = gen_fixed_bytes(size);
+    let content_b = gen_fixed_bytes(size);
+
+    let mut w = op.writer(&path).await?;
+    w.write(content_a.clone()).await?;
+    let checkpoint = w
+        .checkpoint()
+        .expect("checkpoint must exist after upload initiated");
+    let written = checkpoint.size() as usize;
+    assert!(written <= size, "checkpoint must not exceed written size");
+    drop(w);
+
+    let mut w = op.writer_with(&path).resume(checkpoint).await?;
+    w.write(content_a[written..].to_vec()).await?;
+    w.write(content_b.clone()).await?;
+    w.close().await?;
+
+    let bs = op.read(&path).await?;
+    assert_eq!(bs.len(), size * 2, "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs[..size])),
+        format!("{:x}", Sha256::digest(content_a)),
+        "read content a"
+    );
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs[size..])),
+        format!("{:x}", Sha256::digest(content_b)),
+        "read content b"
+    );
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Writer with checksum should return the checksum of written content.
+pub async fn test_writer_with_checksum(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let size = 5 * 1024 * 1024;
+    let content_a = gen_fixed_bytes(size);
+    let content_b = gen_fixed_bytes(size);
+    let content = [content_a.clone(), content_b.clone()].concat();
+
+    let mut w = match op.writer_with(&path).checksum(ChecksumAlgo::Md5).await {
+        Ok(w) => w,
+        Err(err) if err.kind() == ErrorKind::Unsupported => {
+            warn!("service doesn't support writer");
+            return Ok(());
+        }
+        Err(err) => return Err(err.into()),
+    };
+    w.write(content_a).await?;
+    w.write(content_b).await?;
+    let meta = w.close().await?;
+    assert_eq!(
+        meta.content_md5(),
+        Some(opendal::raw::format_content_md5(&content).as_str()),
+        "md5 of written content"
+    );
+
+    #[cfg(feature = "checksum-sha256")]
+    {
+        use base64::Engine;
+
+        let meta = op
+            .write_with(&path, content.clone())
+            .checksum(ChecksumAlgo::Sha256)
+            .with_metadata()
+            .await?;
+        assert_eq!(
+            meta.checksum_sha256(),
+            Some(
+                base64::engine::general_purpose::STANDARD
+                    .encode(Sha256::digest(&content))
+                    .as_str()
+            ),
+            "sha256 of written content"
+        );
+    }
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Write with crc32c checksum and stat should return the same checksum
+/// if service stores it.
+pub async fn test_write_with_checksum_crc32c(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    let meta = op
+        .write_with(&path, content)
+        .checksum(ChecksumAlgo::Crc32c)
+        .with_metadata()
+        .await?;
+    let checksum = meta.checksum_crc32c().expect("checksum must be returned");
+
+    let meta = op.stat(&path).await.expect("stat must succeed");
+    if let Some(v) = meta.checksum_crc32c() {
+        assert_eq!(v, checksum, "crc32c of stored content");
+    }
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
 /// Streaming data into writer
 pub async fn test_writer_sink(op: Operator) -> Result<()> {
     let cap = op.info().capability();
@@ -1208,6 +2139,85 @@ pub async fn test_writer_futures_copy(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Write data from reader into path
+pub async fn test_write_from(op: Operator) -> Result<()> {
+    if !op.info().capability().write_without_content_length {
+        warn!("{op:?} doesn't support write without content length, test skip");
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, size): (Vec<u8>, usize) =
+        gen_bytes_with_range(10 * 1024 * 1024..20 * 1024 * 1024);
+
+    op.write_from(&path, Cursor::new(content.clone())).await?;
+
+    let meta = op.stat(&path).await.expect("stat must succeed");
+    assert_eq!(meta.content_length(), size as u64);
+
+    let bs = op.read(&path).await?;
+    assert_eq!(bs.len(), size, "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content)),
+        "read content"
+    );
+
+    // Small buffer should still copy all content.
+    op.write_from(&path, Cursor::new(content.clone()))
+        .buffer(4096)
+        .await?;
+    let bs = op.read(&path).await?;
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(content)),
+        "read content written with small buffer"
+    );
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Write from stream should succeed and abort on stream error.
+pub async fn test_write_stream(op: Operator) -> Result<()> {
+    if !op.info().capability().write_without_content_length {
+        warn!("{op:?} doesn't support write without content length, test skip");
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content_a, size_a) = gen_bytes();
+    let (content_b, size_b) = gen_bytes();
+
+    let s = stream::iter(vec![content_a.clone(), content_b.clone()]).map(Ok);
+    op.write_stream(&path, s).await?;
+
+    let bs = op.read(&path).await?;
+    assert_eq!(bs.len(), size_a + size_b, "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!(
+            "{:x}",
+            Sha256::digest([content_a.clone(), content_b].concat())
+        ),
+        "read content"
+    );
+    op.delete(&path).await.expect("delete must succeed");
+
+    let path = uuid::Uuid::new_v4().to_string();
+    let s = stream::iter(vec![
+        Ok(content_a),
+        Err(opendal::Error::new(ErrorKind::Unexpected, "input broken")),
+    ]);
+    let res = op.write_stream(&path, s).await;
+    assert!(res.is_err(), "write stream with error must fail");
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::Unexpected);
+
+    // Cleanup in case the service has no way to abort.
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
 /// Add test for unsized writer
 pub async fn test_fuzz_unsized_writer(op: Operator) -> Result<()> {
     if !op.info().capability().write_without_content_length {